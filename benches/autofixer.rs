@@ -0,0 +1,60 @@
+//! Autofixer runs over real components
+//!
+//! Every rule runs on every file, so these grow with the rule count: a
+//! single file as `leptos-autofixer` checks it, and a project of many files
+//! with the cross-file checks `analyze-project` adds.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use leptos_mcp_server::cancel::CancelToken;
+use leptos_mcp_server::progress::Progress;
+use leptos_mcp_server::project::{self, SourceFile};
+use leptos_mcp_server::rules;
+use std::path::PathBuf;
+
+const SNIPPETS: &[(&str, &str)] = &[
+    (
+        "auth_guard_route",
+        include_str!("../snippets/auth-guard-route.rs"),
+    ),
+    (
+        "debounced_input",
+        include_str!("../snippets/debounced-input.rs"),
+    ),
+    ("file_upload", include_str!("../snippets/file-upload.rs")),
+    (
+        "infinite_scroll",
+        include_str!("../snippets/infinite-scroll.rs"),
+    ),
+    (
+        "websocket_server_signals",
+        include_str!("../snippets/websocket-server-signals.rs"),
+    ),
+];
+
+fn check_file(c: &mut Criterion) {
+    let mut group = c.benchmark_group("check_file");
+    for (name, code) in SNIPPETS {
+        group.bench_function(*name, |b| b.iter(|| rules::check_file(black_box(code))));
+    }
+    group.finish();
+}
+
+fn analyze_sources(c: &mut Criterion) {
+    // 100 files: each snippet as 20 modules of one crate
+    let mut sources = Vec::new();
+    for copy in 0..20 {
+        for (name, code) in SNIPPETS {
+            sources.push(SourceFile {
+                path: PathBuf::from(format!("src/{}_{}.rs", name, copy)),
+                content: code.to_string(),
+            });
+        }
+    }
+    let progress = Progress::new(None, CancelToken::default());
+    c.bench_function("analyze_sources/100_files", |b| {
+        b.iter(|| project::analyze_sources(black_box(&sources), None, rules::check_file, &progress))
+    });
+}
+
+criterion_group!(benches, check_file, analyze_sources);
+criterion_main!(benches);
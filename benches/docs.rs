@@ -0,0 +1,63 @@
+//! Section lookup and keyword search over the embedded docs
+//!
+//! `get-documentation` resolves its `section` argument with `get_section` on
+//! every call, and `search-docs` runs `search`; both scan every section, so
+//! they slow down as the doc set grows.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use leptos_mcp_server::docs::{self, LeptosVersion, OverrideMode};
+use std::fs;
+
+fn get_section(c: &mut Criterion) {
+    let version = LeptosVersion::default();
+    let mut group = c.benchmark_group("get_section");
+    // An exact path, a title, and a fuzzy use-case query
+    for query in [
+        "router/nested-routes",
+        "Server Functions",
+        "reactive derived values",
+    ] {
+        group.bench_function(query, |b| {
+            b.iter(|| docs::get_section(black_box(query), version))
+        });
+    }
+    group.finish();
+}
+
+fn search(c: &mut Criterion) {
+    let version = LeptosVersion::default();
+    let mut group = c.benchmark_group("search");
+    for query in ["signal", "server function error", "sig"] {
+        group.bench_function(query, |b| {
+            b.iter(|| docs::search(black_box(query), version, 5))
+        });
+    }
+    group.finish();
+}
+
+/// The same lookups with 500 team pages added as overrides, as a large doc
+/// set would have; runs last, since the pages stay loaded
+fn large_doc_set(c: &mut Criterion) {
+    let dir = std::env::temp_dir().join("leptos-mcp-bench-pages");
+    fs::create_dir_all(&dir).expect("create page directory");
+    for page in 0..500 {
+        let content = format!(
+            "# Team Page {page}\n\nNotes on feature {page}.\n\n## Signals In Feature {page}\n\nUse a signal for the selected item.\n\n## Server Functions In Feature {page}\n\nLoad the items with a server function.\n"
+        );
+        fs::write(dir.join(format!("team-{}.md", page)), content).expect("write page");
+    }
+    docs::apply_overrides(&dir, OverrideMode::Replace).expect("load pages");
+
+    let version = LeptosVersion::default();
+    let mut group = c.benchmark_group("large_doc_set");
+    group.bench_function("get_section", |b| {
+        b.iter(|| docs::get_section(black_box("reactive derived values"), version))
+    });
+    group.bench_function("search", |b| {
+        b.iter(|| docs::search(black_box("server function error"), version, 5))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, get_section, search, large_doc_set);
+criterion_main!(benches);
@@ -0,0 +1,75 @@
+//! Build the documentation search index
+//!
+//! Every markdown file under `docs/` is split into its second-level
+//! subsections (the unit `search-docs` returns), tokenized, and written as an
+//! inverted index to `$OUT_DIR/search_index.json`, which `docs.rs` embeds.
+//! Translations under `docs/translations/` are served whole and not indexed.
+
+#[allow(dead_code)]
+#[path = "src/markdown.rs"]
+mod markdown;
+
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::{env, fs};
+
+fn main() {
+    println!("cargo:rerun-if-changed=docs");
+    println!("cargo:rerun-if-changed=src/markdown.rs");
+
+    let mut files = Vec::new();
+    collect_markdown(Path::new("docs"), Path::new("docs"), &mut files);
+    files.sort();
+
+    let mut indexed = Vec::new();
+    // token -> [file, chunk, occurrences]
+    let mut postings: BTreeMap<String, Vec<[usize; 3]>> = BTreeMap::new();
+    for (file_id, path) in files.iter().enumerate() {
+        let content = fs::read_to_string(Path::new("docs").join(path))
+            .unwrap_or_else(|e| panic!("failed to read docs/{}: {}", path, e));
+        let chunks = markdown::search_chunks(&content);
+        for (chunk_id, chunk) in chunks.iter().enumerate() {
+            for (token, count) in &chunk.tokens {
+                postings
+                    .entry(token.clone())
+                    .or_default()
+                    .push([file_id, chunk_id, *count]);
+            }
+        }
+        indexed.push(json!({
+            "path": path,
+            "chunks": chunks
+                .iter()
+                .map(|c| json!({ "heading": c.heading, "slug": c.slug, "start": c.start, "end": c.end }))
+                .collect::<Vec<Value>>(),
+        }));
+    }
+
+    let index = json!({ "files": indexed, "postings": postings });
+    let out = Path::new(&env::var("OUT_DIR").expect("OUT_DIR")).join("search_index.json");
+    fs::write(&out, serde_json::to_vec(&index).expect("serialize index"))
+        .unwrap_or_else(|e| panic!("failed to write {}: {}", out.display(), e));
+}
+
+/// Markdown files under `dir`, as `/`-separated paths relative to `root`
+fn collect_markdown(root: &Path, dir: &Path, files: &mut Vec<String>) {
+    let entries =
+        fs::read_dir(dir).unwrap_or_else(|e| panic!("failed to list {}: {}", dir.display(), e));
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            if path == root.join("translations") {
+                continue;
+            }
+            collect_markdown(root, &path, files);
+        } else if path.extension().is_some_and(|ext| ext == "md") {
+            let relative = path.strip_prefix(root).expect("under root");
+            let parts: Vec<String> = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                .collect();
+            files.push(parts.join("/"));
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use leptos::prelude::*;
+use leptos_router::{
+    components::{ProtectedRoute, Redirect, Route, Router, Routes},
+    path,
+};
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct User {
+    pub name: String,
+}
+
+#[server]
+pub async fn current_user() -> Result<Option<User>, ServerFnError> {
+    // Read the session cookie and load the user here
+    Ok(None)
+}
+
+#[server]
+pub async fn log_in(name: String) -> Result<(), ServerFnError> {
+    // Verify credentials and set the session cookie here
+    let _ = name;
+    Ok(())
+}
+
+#[component]
+pub fn App() -> impl IntoView {
+    let log_in = ServerAction::<LogIn>::new();
+    // Refetches after every login, so the guard sees the new session
+    let user = Resource::new(move || log_in.version().get(), |_| current_user());
+    // None while loading, so a logged-in user isn't bounced to /login on reload
+    let logged_in = move || user.get().map(|user| matches!(user, Ok(Some(_))));
+    provide_context(log_in);
+
+    view! {
+        <Router>
+            <Routes fallback=|| "Not found">
+                <Route path=path!("/login") view=Login />
+                <ProtectedRoute
+                    path=path!("/dashboard")
+                    condition=logged_in
+                    redirect_path=|| "/login"
+                    view=Dashboard
+                />
+            </Routes>
+        </Router>
+    }
+}
+
+#[component]
+fn Login() -> impl IntoView {
+    let log_in = expect_context::<ServerAction<LogIn>>();
+    let succeeded = move || matches!(log_in.value().get(), Some(Ok(())));
+
+    view! {
+        <ActionForm action=log_in>
+            <input type="text" name="name" placeholder="Name" />
+            <button type="submit">"Log in"</button>
+        </ActionForm>
+        <Show when=succeeded>
+            <Redirect path="/dashboard" />
+        </Show>
+    }
+}
+
+#[component]
+fn Dashboard() -> impl IntoView {
+    view! { <h1>"Dashboard"</h1> }
+}
@@ -0,0 +1,56 @@
+use leptos::prelude::*;
+use leptos_use::signal_debounced;
+
+#[server]
+pub async fn search_users(query: String) -> Result<Vec<String>, ServerFnError> {
+    let users = ["ada", "grace", "linus", "margaret"];
+    Ok(users
+        .iter()
+        .filter(|u| u.contains(&query.to_lowercase()))
+        .map(|u| u.to_string())
+        .collect())
+}
+
+#[component]
+pub fn UserSearch() -> impl IntoView {
+    let (query, set_query) = signal(String::new());
+    // Settles 300ms after the last keystroke, so typing doesn't fire a request per key
+    let debounced: Signal<String> = signal_debounced(query, 300.0);
+    let results = Resource::new(
+        move || debounced.get(),
+        |query| async move {
+            if query.is_empty() {
+                Ok(Vec::new())
+            } else {
+                search_users(query).await
+            }
+        },
+    );
+
+    view! {
+        <input
+            type="search"
+            placeholder="Search users"
+            prop:value=query
+            on:input=move |ev| set_query.set(event_target_value(&ev))
+        />
+        <Transition fallback=|| view! { <p>"Searching..."</p> }>
+            <ul>
+                {move || {
+                    results
+                        .get()
+                        .map(|result| match result {
+                            Ok(users) => {
+                                users
+                                    .into_iter()
+                                    .map(|user| view! { <li>{user}</li> })
+                                    .collect_view()
+                                    .into_any()
+                            }
+                            Err(e) => view! { <li class="error">{e.to_string()}</li> }.into_any(),
+                        })
+                }}
+            </ul>
+        </Transition>
+    }
+}
@@ -0,0 +1,49 @@
+use leptos::prelude::*;
+use leptos::server_fn::codec::{MultipartData, MultipartFormData};
+use leptos::wasm_bindgen::JsCast;
+use leptos::web_sys::{FormData, HtmlFormElement, SubmitEvent};
+
+/// Receives the form's files as a multipart stream and returns the bytes read
+#[server(input = MultipartFormData)]
+pub async fn upload_file(data: MultipartData) -> Result<usize, ServerFnError> {
+    // `into_inner` is only `Some` on the server
+    let mut data = data.into_inner().unwrap();
+    let mut size = 0;
+    while let Ok(Some(mut field)) = data.next_field().await {
+        let name = field.file_name().unwrap_or_default().to_string();
+        // Write each chunk to disk or object storage here instead of counting it
+        while let Ok(Some(chunk)) = field.chunk().await {
+            size += chunk.len();
+        }
+        leptos::logging::log!("received {} ({} bytes so far)", name, size);
+    }
+    Ok(size)
+}
+
+#[component]
+pub fn FileUpload() -> impl IntoView {
+    // FormData isn't Send, so the action stays on the client
+    let upload = Action::new_local(|data: &FormData| upload_file(data.clone().into()));
+
+    let on_submit = move |ev: SubmitEvent| {
+        ev.prevent_default();
+        let form = ev.target().unwrap().unchecked_into::<HtmlFormElement>();
+        let data = FormData::new_with_form(&form).unwrap();
+        upload.dispatch_local(data);
+    };
+
+    view! {
+        <form on:submit=on_submit>
+            <input type="file" name="file" />
+            <button type="submit" disabled=move || upload.pending().get()>"Upload"</button>
+        </form>
+        <p>
+            {move || match upload.value().get() {
+                None if upload.pending().get() => "Uploading...".to_string(),
+                None => String::new(),
+                Some(Ok(size)) => format!("Uploaded {} bytes", size),
+                Some(Err(e)) => format!("Upload failed: {}", e),
+            }}
+        </p>
+    }
+}
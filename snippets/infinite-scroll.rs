@@ -0,0 +1,48 @@
+use leptos::html::Div;
+use leptos::prelude::*;
+use leptos_use::{use_infinite_scroll_with_options, UseInfiniteScrollOptions};
+
+const PAGE_SIZE: usize = 20;
+
+#[server]
+pub async fn list_posts(page: usize) -> Result<Vec<String>, ServerFnError> {
+    // Stand-in for a database query with LIMIT/OFFSET
+    let start = page * PAGE_SIZE;
+    let end = (start + PAGE_SIZE).min(95);
+    Ok((start..end).map(|i| format!("Post {}", i + 1)).collect())
+}
+
+#[component]
+pub fn PostFeed() -> impl IntoView {
+    let feed = NodeRef::<Div>::new();
+    let posts = RwSignal::new(Vec::<String>::new());
+    let page = RwSignal::new(0);
+    let done = RwSignal::new(false);
+
+    // Called on mount and whenever the feed is scrolled near its bottom
+    let _ = use_infinite_scroll_with_options(
+        feed,
+        move |_| async move {
+            if done.get_untracked() {
+                return;
+            }
+            if let Ok(batch) = list_posts(page.get_untracked()).await {
+                done.set(batch.len() < PAGE_SIZE);
+                posts.update(|posts| posts.extend(batch));
+                page.update(|page| *page += 1);
+            }
+        },
+        UseInfiniteScrollOptions::default().distance(200.0),
+    );
+
+    view! {
+        <div node_ref=feed style="height: 400px; overflow-y: auto">
+            <For each=move || posts.get() key=|post| post.clone() let:post>
+                <article>{post}</article>
+            </For>
+            <Show when=move || done.get()>
+                <p>"No more posts"</p>
+            </Show>
+        </div>
+    }
+}
@@ -0,0 +1,64 @@
+use leptos::prelude::*;
+use leptos::server_fn::codec::JsonEncoding;
+use leptos::server_fn::{BoxedStream, Websocket};
+
+/// Streams every message back in upper case; the server end of the socket
+#[server(protocol = Websocket<JsonEncoding, JsonEncoding>)]
+pub async fn shout(
+    input: BoxedStream<String, ServerFnError>,
+) -> Result<BoxedStream<String, ServerFnError>, ServerFnError> {
+    use futures::channel::mpsc;
+    use futures::{SinkExt, StreamExt};
+
+    let mut input = input;
+    let (mut tx, rx) = mpsc::channel(1);
+    tokio::spawn(async move {
+        while let Some(message) = input.next().await {
+            if tx.send(message.map(|m| m.to_uppercase())).await.is_err() {
+                break;
+            }
+        }
+    });
+    Ok(rx.into())
+}
+
+#[component]
+pub fn Shout() -> impl IntoView {
+    use futures::channel::mpsc;
+    use futures::StreamExt;
+
+    let (tx, rx) = mpsc::channel(1);
+    // Every message the server pushes lands in this signal
+    let latest = RwSignal::new(None::<Result<String, ServerFnError>>);
+
+    // The socket only exists in the browser
+    if cfg!(feature = "hydrate") {
+        leptos::task::spawn_local(async move {
+            match shout(rx.into()).await {
+                Ok(mut messages) => {
+                    while let Some(message) = messages.next().await {
+                        latest.set(Some(message));
+                    }
+                }
+                Err(e) => latest.set(Some(Err(e))),
+            }
+        });
+    }
+
+    view! {
+        <input
+            type="text"
+            placeholder="Say something"
+            on:input=move |ev| {
+                let _ = tx.clone().try_send(Ok(event_target_value(&ev)));
+            }
+        />
+        <p>
+            {move || match latest.get() {
+                None => "Waiting for the server...".to_string(),
+                Some(Ok(message)) => message,
+                Some(Err(e)) => format!("Socket error: {}", e),
+            }}
+        </p>
+    }
+}
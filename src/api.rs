@@ -0,0 +1,495 @@
+//! Leptos API reference
+//!
+//! Item signatures and doc comments of the leptos crates, bundled per Leptos
+//! version so lookups work offline. A reference generated from the crates'
+//! rustdoc JSON ([`crate::rustdoc`], `leptos-mcp-server api-index`) records the
+//! crates and versions it covers; one without is a hand-written subset of the
+//! most used items. [`diff`] compares two versions' references item by item.
+
+use crate::docs::{similarity, LeptosVersion};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::OnceLock;
+
+/// Scheme of the MCP resource URIs items are served under, like
+/// `leptos-api://Resource::new`
+pub const RESOURCE_SCHEME: &str = "leptos-api";
+
+/// A public item of the leptos crates
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ApiItem {
+    /// Full path, e.g. `leptos::prelude::Resource::new`
+    pub path: String,
+    pub kind: String,
+    pub signature: String,
+    /// Generic bounds from the `where` clause
+    #[serde(default)]
+    pub bounds: Vec<String>,
+    pub docs: String,
+    /// Deprecation note, empty if the deprecation gives none
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<String>,
+}
+
+/// An API reference as bundled under `api/`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ApiReference {
+    /// Leptos version the reference is for, like `0.7`
+    pub crate_version: String,
+    /// Documented crates and their exact versions; empty for a hand-written
+    /// reference
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub crates: BTreeMap<String, String>,
+    pub items: Vec<ApiItem>,
+}
+
+impl ApiReference {
+    /// Whether the reference was generated from rustdoc output rather than
+    /// written by hand
+    pub fn is_generated(&self) -> bool {
+        !self.crates.is_empty()
+    }
+
+    /// What the reference covers, for telling apart an item that doesn't
+    /// exist from one it leaves out
+    pub fn coverage(&self) -> String {
+        if !self.is_generated() {
+            return format!(
+                "The Leptos {} API reference is a hand-written subset; `leptos-mcp-server api-index {}` generates a complete one.",
+                self.crate_version, self.crate_version
+            );
+        }
+        let crates: Vec<String> = self
+            .crates
+            .iter()
+            .map(|(name, version)| format!("{} {}", name, version))
+            .collect();
+        format!(
+            "The Leptos {} API reference covers {}.",
+            self.crate_version,
+            crates.join(", ")
+        )
+    }
+}
+
+/// Outcome of resolving an item path
+pub enum Lookup {
+    /// Items whose path matches the query
+    Found(Vec<ApiItem>),
+    /// Nothing matched; closest item paths instead
+    NotFound(Vec<String>),
+}
+
+/// Bundled API reference for a Leptos version, if one exists
+fn bundled(version: LeptosVersion) -> Option<&'static str> {
+    match version {
+        LeptosVersion::V0_6 => Some(include_str!("../api/leptos-0.6.json")),
+        LeptosVersion::V0_7 => Some(include_str!("../api/leptos-0.7.json")),
+        LeptosVersion::V0_8 => Some(include_str!("../api/leptos-0.8.json")),
+    }
+}
+
+/// The API reference for a Leptos version, parsed on first use
+pub fn reference(version: LeptosVersion) -> Result<&'static ApiReference, String> {
+    static REFERENCES: [OnceLock<Result<ApiReference, String>>; LeptosVersion::ALL.len()] =
+        [const { OnceLock::new() }; LeptosVersion::ALL.len()];
+    REFERENCES[version as usize]
+        .get_or_init(|| {
+            let raw = bundled(version).ok_or_else(|| {
+                format!("No API reference bundled for Leptos {}", version.as_str())
+            })?;
+            serde_json::from_str(raw)
+                .map_err(|e| format!("Failed to parse bundled API reference: {}", e))
+        })
+        .as_ref()
+        .map_err(Clone::clone)
+}
+
+/// All items of the API reference for a Leptos version
+pub fn items(version: LeptosVersion) -> Result<&'static [ApiItem], String> {
+    Ok(&reference(version)?.items)
+}
+
+/// Resolve an item path such as `Resource::new` or `leptos::prelude::signal`
+///
+/// Matches the full path exactly, then by `::`-separated suffix, so agents can
+/// omit the crate and module prefix.
+pub fn lookup(query: &str, version: LeptosVersion) -> Result<Lookup, String> {
+    let items = items(version)?;
+    let query = query.trim().trim_end_matches("()").trim_start_matches("::");
+    let query_lower = query.to_lowercase();
+
+    let exact: Vec<ApiItem> = items
+        .iter()
+        .filter(|i| i.path.to_lowercase() == query_lower)
+        .cloned()
+        .collect();
+    if !exact.is_empty() {
+        return Ok(Lookup::Found(exact));
+    }
+
+    let suffix = format!("::{}", query_lower);
+    let by_suffix: Vec<ApiItem> = items
+        .iter()
+        .filter(|i| i.path.to_lowercase().ends_with(&suffix))
+        .cloned()
+        .collect();
+    if !by_suffix.is_empty() {
+        return Ok(Lookup::Found(by_suffix));
+    }
+
+    // Compare the query against the same number of trailing path segments
+    let depth = query.split("::").count();
+    let mut ranked: Vec<(f64, &ApiItem)> = items
+        .iter()
+        .map(|i| {
+            let segments: Vec<&str> = i.path.split("::").collect();
+            let tail = segments[segments.len().saturating_sub(depth)..]
+                .join("::")
+                .to_lowercase();
+            (similarity(&query_lower, &tail), i)
+        })
+        .filter(|(score, _)| *score >= 0.5)
+        .collect();
+    ranked.sort_by(|a, b| b.0.total_cmp(&a.0));
+    Ok(Lookup::NotFound(
+        ranked
+            .into_iter()
+            .take(5)
+            .map(|(_, i)| i.path.clone())
+            .collect(),
+    ))
+}
+
+/// Replacements a path comparison can't pair up: `(old path, new path, version
+/// the new path arrived in)`
+///
+/// Items that only moved between modules, such as `leptos::spawn_local` to
+/// `leptos::task::spawn_local`, are paired by name and need no entry.
+const RENAMES: &[(&str, &str, LeptosVersion)] = &[
+    (
+        "leptos::create_signal",
+        "leptos::prelude::signal",
+        LeptosVersion::V0_7,
+    ),
+    (
+        "leptos::create_rw_signal",
+        "leptos::prelude::RwSignal::new",
+        LeptosVersion::V0_7,
+    ),
+    (
+        "leptos::create_memo",
+        "leptos::prelude::Memo::new",
+        LeptosVersion::V0_7,
+    ),
+    (
+        "leptos::store_value",
+        "leptos::prelude::StoredValue::new",
+        LeptosVersion::V0_7,
+    ),
+    (
+        "leptos::create_effect",
+        "leptos::prelude::Effect::new",
+        LeptosVersion::V0_7,
+    ),
+    (
+        "leptos::watch",
+        "leptos::prelude::Effect::watch",
+        LeptosVersion::V0_7,
+    ),
+    (
+        "leptos::create_resource",
+        "leptos::prelude::Resource::new",
+        LeptosVersion::V0_7,
+    ),
+    (
+        "leptos::create_blocking_resource",
+        "leptos::prelude::Resource::new_blocking",
+        LeptosVersion::V0_7,
+    ),
+    (
+        "leptos::create_local_resource",
+        "leptos::prelude::LocalResource::new",
+        LeptosVersion::V0_7,
+    ),
+    (
+        "leptos::create_action",
+        "leptos::prelude::Action::new",
+        LeptosVersion::V0_7,
+    ),
+    (
+        "leptos::create_server_action",
+        "leptos::prelude::ServerAction::new",
+        LeptosVersion::V0_7,
+    ),
+    (
+        "leptos::create_node_ref",
+        "leptos::prelude::NodeRef::new",
+        LeptosVersion::V0_7,
+    ),
+    (
+        "leptos::View",
+        "leptos::prelude::AnyView",
+        LeptosVersion::V0_7,
+    ),
+    (
+        "leptos::IntoView::into_view",
+        "leptos::prelude::IntoAny::into_any",
+        LeptosVersion::V0_7,
+    ),
+];
+
+/// How the API reference changed between two versions
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ApiDiff {
+    pub from: &'static str,
+    pub to: &'static str,
+    pub renamed: Vec<Renamed>,
+    pub removed: Vec<ApiItem>,
+    pub added: Vec<ApiItem>,
+    /// Items at the same path whose signature or bounds changed
+    pub changed: Vec<Changed>,
+    /// Paths present and identical in both versions
+    pub unchanged: Vec<String>,
+    /// What the comparison leaves out when the two references don't cover the
+    /// same crates or one is a hand-written subset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+/// An item that exists under another path in the newer version
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct Renamed {
+    pub from: String,
+    pub to: String,
+    pub from_signature: String,
+    pub to_signature: String,
+}
+
+/// An item whose signature changed in place
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct Changed {
+    pub path: String,
+    pub from_signature: String,
+    pub to_signature: String,
+    pub from_bounds: Vec<String>,
+    pub to_bounds: Vec<String>,
+}
+
+impl ApiDiff {
+    pub fn is_empty(&self) -> bool {
+        self.renamed.is_empty()
+            && self.removed.is_empty()
+            && self.added.is_empty()
+            && self.changed.is_empty()
+    }
+}
+
+/// Compare the API references of `from` and `to`, optionally only entries
+/// involving items matching `item` (a path or `::`-suffix, as for [`lookup`])
+///
+/// `from` may be the newer version; renames are then reported backwards.
+pub fn diff(from: LeptosVersion, to: LeptosVersion, item: Option<&str>) -> Result<ApiDiff, String> {
+    let (old_reference, new_reference) = (reference(from)?, reference(to)?);
+    // A generated reference only speaks for the crates it documents
+    let covered = |item: &ApiItem, other: &ApiReference| {
+        let krate = item.path.split("::").next().unwrap_or_default();
+        !other.is_generated() || other.crates.contains_key(krate)
+    };
+    let old: Vec<&ApiItem> = old_reference
+        .items
+        .iter()
+        .filter(|i| covered(i, new_reference))
+        .collect();
+    let new: Vec<&ApiItem> = new_reference
+        .items
+        .iter()
+        .filter(|i| covered(i, old_reference))
+        .collect();
+    let positions = |items: &[&ApiItem]| {
+        let mut positions = HashMap::new();
+        for (i, item) in items.iter().enumerate() {
+            positions.entry(item.path.clone()).or_insert(i);
+        }
+        positions
+    };
+    let (old_positions, new_positions) = (positions(&old), positions(&new));
+    let mut old_matched = vec![false; old.len()];
+    let mut new_matched = vec![false; new.len()];
+    let mut diff = ApiDiff {
+        from: from.as_str(),
+        to: to.as_str(),
+        renamed: Vec::new(),
+        removed: Vec::new(),
+        added: Vec::new(),
+        changed: Vec::new(),
+        unchanged: Vec::new(),
+        note: note(old_reference, new_reference),
+    };
+
+    for (i, old_item) in old.iter().enumerate() {
+        let Some(&j) = new_positions.get(&old_item.path) else {
+            continue;
+        };
+        old_matched[i] = true;
+        new_matched[j] = true;
+        let new_item = &new[j];
+        if old_item.signature == new_item.signature && old_item.bounds == new_item.bounds {
+            diff.unchanged.push(old_item.path.clone());
+        } else {
+            diff.changed.push(Changed {
+                path: old_item.path.clone(),
+                from_signature: old_item.signature.clone(),
+                to_signature: new_item.signature.clone(),
+                from_bounds: old_item.bounds.clone(),
+                to_bounds: new_item.bounds.clone(),
+            });
+        }
+    }
+
+    // Known renames between the two versions, oriented from `from` to `to`
+    let (low, high) = (from.min(to), from.max(to));
+    let renames = RENAMES
+        .iter()
+        .filter(|(_, _, since)| low < *since && *since <= high)
+        .map(|&(old, new, _)| if from < to { (old, new) } else { (new, old) });
+    let mut pairs: Vec<(usize, usize)> = renames
+        .filter_map(|(old_path, new_path)| {
+            Some((*old_positions.get(old_path)?, *new_positions.get(new_path)?))
+        })
+        .filter(|(i, _)| !old_matched[*i])
+        .collect();
+    // Items that only moved module, paired when their name is unambiguous
+    let mut by_name: HashMap<String, Vec<usize>> = HashMap::new();
+    for j in (0..new.len()).filter(|&j| !new_matched[j]) {
+        by_name.entry(item_name(&new[j].path)).or_default().push(j);
+    }
+    for (i, old_item) in old.iter().enumerate() {
+        if old_matched[i] || pairs.iter().any(|(p, _)| *p == i) {
+            continue;
+        }
+        if let Some([j]) = by_name.get(&item_name(&old_item.path)).map(Vec::as_slice) {
+            pairs.push((i, *j));
+        }
+    }
+    pairs.sort_unstable();
+    for (i, j) in pairs {
+        old_matched[i] = true;
+        new_matched[j] = true;
+        diff.renamed.push(Renamed {
+            from: old[i].path.clone(),
+            to: new[j].path.clone(),
+            from_signature: old[i].signature.clone(),
+            to_signature: new[j].signature.clone(),
+        });
+    }
+
+    // Items a hand-written subset leaves out aren't gone from the other version
+    if old_reference.is_generated() || !new_reference.is_generated() {
+        diff.removed = unmatched(&old, &old_matched);
+    }
+    if new_reference.is_generated() || !old_reference.is_generated() {
+        diff.added = unmatched(&new, &new_matched);
+        // Shims kept under an old name, like 0.7's `create_signal`, aren't new API
+        diff.added.retain(|i| i.deprecated.is_none());
+    }
+
+    if let Some(query) = item {
+        let query = query.trim().trim_end_matches("()").to_lowercase();
+        let matches = |path: &str| {
+            let path = path.to_lowercase();
+            path == query || path.ends_with(&format!("::{}", query))
+        };
+        diff.renamed.retain(|r| matches(&r.from) || matches(&r.to));
+        diff.removed.retain(|i| matches(&i.path));
+        diff.added.retain(|i| matches(&i.path));
+        diff.changed.retain(|c| matches(&c.path));
+        diff.unchanged.retain(|path| matches(path));
+    }
+    Ok(diff)
+}
+
+fn unmatched(items: &[&ApiItem], matched: &[bool]) -> Vec<ApiItem> {
+    items
+        .iter()
+        .zip(matched)
+        .filter(|(_, matched)| !**matched)
+        .map(|(item, _)| (*item).clone())
+        .collect()
+}
+
+/// What a diff of `old` and `new` can't report
+fn note(old: &ApiReference, new: &ApiReference) -> Option<String> {
+    match (old.is_generated(), new.is_generated()) {
+        (false, false) => Some(
+            "Both references are hand-written subsets, so items missing from them aren't compared."
+                .to_string(),
+        ),
+        (true, false) => Some(format!(
+            "The Leptos {} reference is a hand-written subset: items it leaves out aren't listed as removed, and its signatures may differ from the generated ones in form only (`Self`, component props).",
+            new.crate_version
+        )),
+        (false, true) => Some(format!(
+            "The Leptos {} reference is a hand-written subset: items it leaves out aren't listed as added, and its signatures may differ from the generated ones in form only (`Self`, component props).",
+            old.crate_version
+        )),
+        (true, true) if old.crates.keys().ne(new.crates.keys()) => {
+            let shared: Vec<&str> = old
+                .crates
+                .keys()
+                .filter(|k| new.crates.contains_key(*k))
+                .map(String::as_str)
+                .collect();
+            Some(format!("Only crates both references cover are compared: {}.", shared.join(", ")))
+        }
+        (true, true) => None,
+    }
+}
+
+/// An item's path without its crate and modules: `RwSignal::new`, `signal`
+fn item_name(path: &str) -> String {
+    let segments: Vec<&str> = path.split("::").collect();
+    // Modules are lowercase; the name starts after the last one before the final segment
+    let last = segments.len() - 1;
+    let start = segments[..last]
+        .iter()
+        .rposition(|s| s.starts_with(|c: char| c.is_ascii_lowercase()))
+        .map_or(last, |i| i + 1);
+    segments[start..].join("::")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_reference_records_its_crates() {
+        let generated = reference(LeptosVersion::V0_7).unwrap();
+        assert!(generated.is_generated());
+        assert!(generated.crates.contains_key("leptos"));
+        assert!(!reference(LeptosVersion::V0_8).unwrap().is_generated());
+    }
+
+    #[test]
+    fn diff_follows_renames_without_listing_deprecated_shims() {
+        let diff = diff(
+            LeptosVersion::V0_6,
+            LeptosVersion::V0_7,
+            Some("create_signal"),
+        )
+        .unwrap();
+        let renamed: Vec<(&str, &str)> = diff
+            .renamed
+            .iter()
+            .map(|r| (r.from.as_str(), r.to.as_str()))
+            .collect();
+        assert_eq!(
+            renamed,
+            [("leptos::create_signal", "leptos::prelude::signal")]
+        );
+        // 0.7 keeps a deprecated `leptos::prelude::create_signal`
+        assert!(diff.added.is_empty());
+        assert!(diff.note.is_some());
+    }
+}
@@ -0,0 +1,467 @@
+//! WASM bundle size advisor
+//!
+//! The browser downloads and compiles the client's `.wasm` before the page
+//! becomes interactive. [`analyze`] looks for what is known to make it large:
+//! a release profile tuned for speed instead of size, debug info, no
+//! `wasm-opt` pass, heavy crates the client build compiles, and client code
+//! that embeds files or encodes JSON itself. Each finding comes with the
+//! change that fixes it, the largest savings first.
+
+use crate::context::Manifest;
+use crate::progress::Progress;
+use crate::project::{self, SourceFile};
+use crate::rules::{cfg_ssr_items, position};
+use crate::source::{functions, mask_literals};
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::fs;
+use std::ops::Range;
+use std::path::Path;
+use toml::Value;
+
+/// How much a suggestion is likely to save
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Priority {
+    High,
+    Medium,
+    Low,
+}
+
+impl Priority {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::High => "high",
+            Self::Medium => "medium",
+            Self::Low => "low",
+        }
+    }
+}
+
+/// A size reduction and how to make it
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct Suggestion {
+    /// Stable kind, e.g. `release-opt-level`
+    pub id: &'static str,
+    pub priority: Priority,
+    pub message: String,
+    /// The TOML, HTML or Rust to change
+    pub fix: String,
+    /// `file:line` of the code involved, for code patterns
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub locations: Vec<String>,
+}
+
+/// Crates that add much to a WASM bundle: name, what to do instead, priority
+const HEAVY_CRATES: &[(&str, &str, Priority)] = &[
+    (
+        "regex",
+        "compiles its matching engines and Unicode tables into the bundle; use `regex-lite`, or `js_sys::RegExp` in the browser",
+        Priority::High,
+    ),
+    (
+        "image",
+        "brings every codec it decodes into the bundle; resize and convert images on the server or with the browser's canvas",
+        Priority::High,
+    ),
+    (
+        "syntect",
+        "ships its grammars and themes with the client; highlight code on the server and send HTML",
+        Priority::High,
+    ),
+    (
+        "chrono",
+        "adds its parsing and formatting machinery; format dates with `js_sys::Date`/`Intl` or use `time`, which compiles smaller",
+        Priority::Medium,
+    ),
+    (
+        "reqwest",
+        "wraps `fetch` in a full HTTP client; call a server function or use `gloo-net`",
+        Priority::Medium,
+    ),
+    (
+        "pulldown-cmark",
+        "is only needed where markdown is rendered; render it on the server and send HTML",
+        Priority::Medium,
+    ),
+    (
+        "comrak",
+        "is only needed where markdown is rendered; render it on the server and send HTML",
+        Priority::Medium,
+    ),
+    (
+        "serde_yaml",
+        "parses YAML in the browser; parse it on the server and send the values",
+        Priority::Medium,
+    ),
+    (
+        "toml",
+        "parses TOML in the browser; parse it on the server and send the values",
+        Priority::Medium,
+    ),
+    (
+        "rand",
+        "pulls in several generators and distributions; `fastrand` or `getrandom` are enough for most UI needs",
+        Priority::Low,
+    ),
+];
+
+/// Types deriving both `Serialize` and `Deserialize` in client code past which
+/// one-directional derives are worth suggesting
+const SERDE_DERIVE_LIMIT: usize = 20;
+
+/// Embedded files this large are worth serving as static assets instead
+const EMBED_LIMIT: u64 = 16 * 1024;
+
+/// Size suggestions for the project at `root`, highest priority first
+pub fn analyze(root: &Path, progress: &Progress) -> Result<Vec<Suggestion>, String> {
+    let manifest = Manifest::load(root)?;
+    let own = &manifest.own;
+    let metadata = own
+        .get("package")
+        .and_then(|p| p.get("metadata"))
+        .and_then(|m| m.get("leptos"));
+    let dependencies = own.get("dependencies").and_then(Value::as_table);
+    let depends = |name: &str| dependencies.is_some_and(|d| d.contains_key(name));
+    if !depends("leptos") {
+        return Err(format!(
+            "{} doesn't depend on leptos",
+            root.join("Cargo.toml").display()
+        ));
+    }
+    // cargo-leptos builds the client from the lib; Trunk from the whole crate
+    let is_ssr_app = metadata.is_some() || depends("leptos_axum") || depends("leptos_actix");
+    let trunk_index = root.join("index.html");
+
+    let mut suggestions = Vec::new();
+    check_profile(&manifest, metadata, is_ssr_app, &mut suggestions);
+    if !is_ssr_app && trunk_index.is_file() {
+        let html = fs::read_to_string(&trunk_index)
+            .map_err(|e| format!("Failed to read {}: {}", trunk_index.display(), e))?;
+        suggestions.extend(check_wasm_opt(&html));
+    }
+    check_crates(own, is_ssr_app, &mut suggestions);
+
+    let sources = project::load_sources(root, progress)?;
+    let client: Vec<&SourceFile> = sources
+        .iter()
+        // The server binary of an SSR app
+        .filter(|f| {
+            !is_ssr_app || !(f.path == Path::new("src/main.rs") || f.path.starts_with("src/bin"))
+        })
+        .filter(|f| !f.content.contains("#![cfg(feature = \"ssr\")]"))
+        .collect();
+    check_code(root, &client, &mut suggestions);
+
+    suggestions.sort_by_key(|s| s.priority);
+    Ok(suggestions)
+}
+
+/// Size settings of the profile the client is built with
+fn check_profile(
+    manifest: &Manifest,
+    metadata: Option<&Value>,
+    is_ssr_app: bool,
+    suggestions: &mut Vec<Suggestion>,
+) {
+    // Cargo only reads profiles from the workspace root
+    let root = manifest.workspace.as_ref().unwrap_or(&manifest.own);
+    let profiles = root.get("profile");
+    let named = metadata
+        .and_then(|m| m.get("lib-profile-release"))
+        .and_then(Value::as_str);
+    let name = named.unwrap_or("release");
+    let setting = |key: &str| {
+        let profile = profiles.and_then(|p| p.get(name))?;
+        profile.get(key).or_else(|| {
+            let parent = profile.get("inherits")?.as_str()?;
+            profiles?.get(parent)?.get(key)
+        })
+    };
+
+    let mut found = Vec::new();
+    let opt_level = setting("opt-level");
+    if !opt_level
+        .and_then(Value::as_str)
+        .is_some_and(|l| l == "z" || l == "s")
+    {
+        found.push((
+            "release-opt-level",
+            Priority::High,
+            "The client is built with `opt-level` tuned for speed; `'z'` optimizes for size, \
+             usually the largest single saving"
+                .to_string(),
+            "opt-level = 'z'",
+        ));
+    }
+    let lto = setting("lto");
+    let full_lto = match lto {
+        Some(Value::Boolean(on)) => *on,
+        Some(Value::String(kind)) => kind == "fat",
+        _ => false,
+    };
+    if !full_lto {
+        found.push((
+            "release-lto",
+            Priority::High,
+            "Link-time optimization is off, so unused code across crates stays in the bundle"
+                .to_string(),
+            "lto = true",
+        ));
+    }
+    if setting("codegen-units").and_then(Value::as_integer) != Some(1) {
+        found.push((
+            "release-codegen-units",
+            Priority::Medium,
+            "Several codegen units are optimized apart from each other; one unit lets LLVM \
+             remove and merge more code"
+                .to_string(),
+            "codegen-units = 1",
+        ));
+    }
+    let debug = match setting("debug") {
+        Some(Value::Boolean(on)) => *on,
+        Some(Value::Integer(level)) => *level > 0,
+        Some(Value::String(level)) => !matches!(level.as_str(), "none" | "0"),
+        _ => false,
+    };
+    if debug {
+        found.push((
+            "release-debug-info",
+            Priority::High,
+            "The client profile keeps debug info, which can make the `.wasm` several times \
+             larger"
+                .to_string(),
+            "debug = false",
+        ));
+    }
+    if setting("panic").and_then(Value::as_str) != Some("abort") {
+        found.push((
+            "release-panic",
+            Priority::Low,
+            "Unwinding support is compiled in; with `panic = \"abort\"` a panic stops the app \
+             the same way but takes less code"
+                .to_string(),
+            "panic = \"abort\"",
+        ));
+    }
+    if found.is_empty() {
+        return;
+    }
+
+    // A shared release profile would also shrink the server, at the cost of speed
+    let shared = is_ssr_app && named.is_none();
+    if shared {
+        let lines: Vec<&str> = found.iter().map(|(_, _, _, line)| *line).collect();
+        suggestions.push(Suggestion {
+            id: "client-profile",
+            priority: Priority::High,
+            message: "The client is built with `[profile.release]`, which the server binary \
+                      shares; a profile of its own can optimize for size while the server \
+                      stays fast"
+                .to_string(),
+            fix: format!(
+                "[profile.wasm-release]\ninherits = \"release\"\n{}\n\n\
+                 [package.metadata.leptos]\nlib-profile-release = \"wasm-release\"",
+                lines.join("\n")
+            ),
+            locations: Vec::new(),
+        });
+    }
+    let name = if shared { "wasm-release" } else { name };
+    suggestions.extend(
+        found
+            .into_iter()
+            .map(|(id, priority, message, line)| Suggestion {
+                id,
+                priority,
+                message,
+                fix: format!("[profile.{}]\n{}", name, line),
+                locations: Vec::new(),
+            }),
+    );
+}
+
+/// Trunk only runs `wasm-opt` when the rust link asks for it
+fn check_wasm_opt(html: &str) -> Option<Suggestion> {
+    let link = html.match_indices("<link").find_map(|(offset, _)| {
+        let tag = &html[offset..offset + html[offset..].find('>')? + 1];
+        (tag.contains("data-trunk") && tag.contains("rel=\"rust\"")).then_some(tag)
+    });
+    let fix = match link {
+        Some(tag) if tag.contains("data-wasm-opt") => return None,
+        Some(tag) => tag.replacen("rel=\"rust\"", "rel=\"rust\" data-wasm-opt=\"z\"", 1),
+        None => "<link data-trunk rel=\"rust\" data-wasm-opt=\"z\" />".to_string(),
+    };
+    Some(Suggestion {
+        id: "missing-wasm-opt",
+        priority: Priority::High,
+        message: "Trunk doesn't run `wasm-opt` on this app; `data-wasm-opt=\"z\"` on the rust \
+                  link shrinks release builds further"
+            .to_string(),
+        fix,
+        locations: vec!["index.html".to_string()],
+    })
+}
+
+/// Heavy crates the client build compiles
+fn check_crates(own: &Value, is_ssr_app: bool, suggestions: &mut Vec<Suggestion>) {
+    let Some(dependencies) = own.get("dependencies").and_then(Value::as_table) else {
+        return;
+    };
+    let client_features: Vec<&str> = ["hydrate", "csr", "default"]
+        .into_iter()
+        .filter_map(|name| own.get("features")?.get(name)?.as_array())
+        .flatten()
+        .filter_map(Value::as_str)
+        .collect();
+    for (name, why, priority) in HEAVY_CRATES {
+        let Some(entry) = dependencies.get(*name) else {
+            continue;
+        };
+        let optional = entry.get("optional").and_then(Value::as_bool) == Some(true);
+        let enabled = client_features.iter().any(|f| {
+            let dep = f.strip_prefix("dep:").unwrap_or(f);
+            dep == *name || dep.split(['/', '?']).next() == Some(*name)
+        });
+        if optional && !enabled {
+            continue;
+        }
+        let fix = if is_ssr_app && !optional {
+            format!(
+                "# If only server code uses it, leave it out of the client build\n\
+                 [dependencies]\n{} = {{ version = \"...\", optional = true }}\n\n\
+                 [features]\nssr = [\"dep:{}\", ...]",
+                name, name
+            )
+        } else {
+            format!(
+                "# Replace `{}` in client code, then drop it from [dependencies] or the client feature",
+                name
+            )
+        };
+        suggestions.push(Suggestion {
+            id: "heavy-crate",
+            priority: *priority,
+            message: format!("`{}` is compiled into the client and {}", name, why),
+            fix,
+            locations: vec!["Cargo.toml".to_string()],
+        });
+    }
+}
+
+/// Client code that embeds files, encodes JSON itself or derives serde
+/// implementations for many types
+fn check_code(root: &Path, files: &[&SourceFile], suggestions: &mut Vec<Suggestion>) {
+    let mut json = Vec::new();
+    let mut embedded = Vec::new();
+    let mut derives = Vec::new();
+    for file in files {
+        let code = file.content.as_str();
+        let masked = mask_literals(code);
+        let mut server: Vec<Range<usize>> = functions(code)
+            .into_iter()
+            .filter(|f| f.has_attr("server"))
+            .map(|f| f.body)
+            .collect();
+        server.extend(cfg_ssr_items(code, &masked));
+        let client = |offset: &usize| !server.iter().any(|r| r.contains(offset));
+        let location =
+            |offset: usize| format!("{}:{}", file.path.display(), position(code, offset).0);
+
+        for (offset, _) in masked
+            .match_indices("serde_json::")
+            .filter(|(o, _)| client(o))
+        {
+            let line = location(offset);
+            if !json.contains(&line) {
+                json.push(line);
+            }
+        }
+        for (offset, _) in masked
+            .match_indices("include_bytes!(")
+            .chain(masked.match_indices("include_str!("))
+            .filter(|(o, _)| client(o))
+        {
+            let size = embedded_size(root, &file.path, &code[offset..]);
+            if size.is_none_or(|size| size >= EMBED_LIMIT) {
+                embedded.push((location(offset), size));
+            }
+        }
+        for (offset, _) in masked.match_indices("#[derive(").filter(|(o, _)| client(o)) {
+            let attr = &masked[offset..offset + masked[offset..].find(']').unwrap_or(0)];
+            if attr.contains("Serialize") && attr.contains("Deserialize") {
+                derives.push(location(offset));
+            }
+        }
+    }
+
+    if !embedded.is_empty() {
+        let sizes: Vec<String> = embedded
+            .iter()
+            .filter_map(|(_, size)| *size)
+            .map(|size| format!("{} KB", size.div_ceil(1024)))
+            .collect();
+        let known = if sizes.is_empty() {
+            String::new()
+        } else {
+            format!(" ({})", sizes.join(", "))
+        };
+        suggestions.push(Suggestion {
+            id: "embedded-file",
+            priority: Priority::Medium,
+            message: format!(
+                "Client code embeds {} file(s){} into the `.wasm`; serve them as static \
+                 assets and fetch them, or keep them in server code",
+                embedded.len(),
+                known
+            ),
+            fix: "// Put the file under `assets/` (cargo-leptos copies it to the site root) and load it:\n\
+                  let data = LocalResource::new(|| async {\n    \
+                      gloo_net::http::Request::get(\"/data.json\").send().await?.text().await\n\
+                  });"
+                .to_string(),
+            locations: embedded.into_iter().map(|(line, _)| line).collect(),
+        });
+    }
+    if !json.is_empty() {
+        suggestions.push(Suggestion {
+            id: "client-json",
+            priority: Priority::Medium,
+            message: "Client code calls `serde_json` directly, which compiles its encoder and \
+                      decoder for every type it handles; return typed values from server \
+                      functions, or convert JS values with `serde-wasm-bindgen`"
+                .to_string(),
+            fix: "// Instead of `serde_json::from_str::<T>(&text)` on a fetched body:\n\
+                  #[server]\npub async fn load() -> Result<T, ServerFnError> { ... }"
+                .to_string(),
+            locations: json,
+        });
+    }
+    if derives.len() > SERDE_DERIVE_LIMIT {
+        suggestions.push(Suggestion {
+            id: "serde-derives",
+            priority: Priority::Low,
+            message: format!(
+                "{} client types derive both `Serialize` and `Deserialize`; the client only \
+                 needs `Serialize` for server function arguments and `Deserialize` for \
+                 results, so derive the other direction for the server build only",
+                derives.len()
+            ),
+            fix: "#[derive(Clone, Deserialize)]\n\
+                  #[cfg_attr(feature = \"ssr\", derive(Serialize))]\n\
+                  pub struct Item { ... }"
+                .to_string(),
+            locations: derives,
+        });
+    }
+}
+
+/// Size of the file an `include_bytes!("...")`/`include_str!("...")` at the
+/// start of `call` embeds, read relative to the including source file
+fn embedded_size(root: &Path, source: &Path, call: &str) -> Option<u64> {
+    let literal = call.split_once('(')?.1.trim_start().strip_prefix('"')?;
+    let relative = &literal[..literal.find('"')?];
+    let path = root.join(source.parent()?).join(relative);
+    fs::metadata(path).ok().map(|m| m.len())
+}
@@ -0,0 +1,176 @@
+//! Tool result caching
+//!
+//! Results of the tools that only read the docs and API data are kept in a
+//! least-recently-used cache shared by every session, keyed by the tool, its
+//! arguments and the session's version, syntax and backend. Each tool has
+//! its own time to live; a sync, reload or override that changes the docs
+//! empties the cache (see [`docs::generation`]).
+
+use crate::docs::{self, LeptosVersion, Syntax};
+use crate::generate::Backend;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Cached tools and how long their results stay fresh
+const TTLS: &[(&str, Duration)] = &[
+    ("list-sections", Duration::from_secs(3600)),
+    ("get-documentation", Duration::from_secs(3600)),
+    ("get-examples", Duration::from_secs(3600)),
+    ("search-docs", Duration::from_secs(600)),
+    ("recommend-sections", Duration::from_secs(600)),
+    ("lookup-api", Duration::from_secs(3600)),
+    ("api-diff", Duration::from_secs(3600)),
+    ("get-changelog", Duration::from_secs(3600)),
+    ("define-term", Duration::from_secs(3600)),
+    ("list-snippets", Duration::from_secs(3600)),
+    ("get-snippet", Duration::from_secs(3600)),
+];
+
+/// How long `tool`'s results are cached; `None` if they aren't
+pub fn ttl(tool: &str) -> Option<Duration> {
+    TTLS.iter()
+        .find(|(name, _)| *name == tool)
+        .map(|(_, ttl)| *ttl)
+}
+
+/// Everything a cached tool's result depends on
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Key {
+    tool: String,
+    /// Arguments as JSON text, without `null`s
+    arguments: String,
+    version: LeptosVersion,
+    syntax: Syntax,
+    backend: Backend,
+    /// Docs generation when the call started
+    generation: u64,
+}
+
+impl Key {
+    /// Key of a call; an absent argument and a `null` one are the same call
+    pub fn new(
+        tool: &str,
+        arguments: &Value,
+        version: LeptosVersion,
+        syntax: Syntax,
+        backend: Backend,
+    ) -> Self {
+        let arguments = match arguments {
+            // Object keys serialize sorted, so argument order doesn't matter
+            Value::Object(map) => {
+                let mut map = map.clone();
+                map.retain(|_, value| !value.is_null());
+                Value::Object(map).to_string()
+            }
+            other => other.to_string(),
+        };
+        Self {
+            tool: tool.to_string(),
+            arguments,
+            version,
+            syntax,
+            backend,
+            generation: docs::generation(),
+        }
+    }
+}
+
+struct Entry {
+    result: Value,
+    expires: Instant,
+    /// Tick of the last lookup or insert, for eviction
+    used: u64,
+}
+
+struct Entries {
+    map: HashMap<Key, Entry>,
+    /// Docs generation the entries were computed from
+    generation: u64,
+    tick: u64,
+}
+
+/// Least-recently-used cache of tool results
+pub struct ResponseCache {
+    capacity: usize,
+    entries: Mutex<Entries>,
+}
+
+impl ResponseCache {
+    /// A cache of at most `capacity` results; `None` if `capacity` is 0
+    pub fn new(capacity: usize) -> Option<Self> {
+        (capacity > 0).then(|| Self {
+            capacity,
+            entries: Mutex::new(Entries {
+                map: HashMap::new(),
+                generation: docs::generation(),
+                tick: 0,
+            }),
+        })
+    }
+
+    /// The cached result of a call, if it's still fresh
+    pub fn get(&self, key: &Key) -> Option<Value> {
+        let mut entries = self.lock();
+        entries.tick += 1;
+        let tick = entries.tick;
+        let entry = entries.map.get_mut(key)?;
+        if entry.expires <= Instant::now() {
+            entries.map.remove(key);
+            return None;
+        }
+        entry.used = tick;
+        Some(entry.result.clone())
+    }
+
+    /// Remember a call's result for `ttl`, evicting the least recently used
+    /// one if the cache is full
+    ///
+    /// A result computed from docs that changed meanwhile isn't kept.
+    pub fn insert(&self, key: Key, result: Value, ttl: Duration) {
+        let mut entries = self.lock();
+        if key.generation != entries.generation {
+            return;
+        }
+        entries.tick += 1;
+        let tick = entries.tick;
+        if !entries.map.contains_key(&key) && entries.map.len() >= self.capacity {
+            let now = Instant::now();
+            entries.map.retain(|_, entry| entry.expires > now);
+            if entries.map.len() >= self.capacity {
+                let oldest = entries
+                    .map
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.used)
+                    .map(|(key, _)| key.clone());
+                if let Some(oldest) = oldest {
+                    entries.map.remove(&oldest);
+                }
+            }
+        }
+        entries.map.insert(
+            key,
+            Entry {
+                result,
+                expires: Instant::now() + ttl,
+                used: tick,
+            },
+        );
+    }
+
+    /// The entries, emptied first if the docs changed since they were cached
+    fn lock(&self) -> std::sync::MutexGuard<'_, Entries> {
+        let mut entries = self.entries.lock().unwrap();
+        let generation = docs::generation();
+        if entries.generation != generation {
+            tracing::debug!(
+                "Docs changed; dropping {} cached result(s)",
+                entries.map.len()
+            );
+            entries.map.clear();
+            entries.generation = generation;
+        }
+        entries
+    }
+}
@@ -0,0 +1,98 @@
+//! Request cancellation
+//!
+//! Every in-flight request gets a [`CancelToken`] in the server's [`Registry`].
+//! `notifications/cancelled` flips the token, and so does a tool call running
+//! past its time limit; long-running work (directory walks, cargo
+//! subprocesses) polls it and stops early.
+
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Shared flag telling a request's work to stop
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<Flags>);
+
+#[derive(Debug, Default)]
+struct Flags {
+    cancelled: AtomicBool,
+    /// Stopped by its time limit rather than the client
+    timed_out: AtomicBool,
+}
+
+impl CancelToken {
+    pub fn cancel(&self) {
+        self.0.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Cancel a request whose time limit passed; it has been answered with
+    /// a timeout error already
+    pub fn time_out(&self) {
+        self.0.timed_out.store(true, Ordering::Relaxed);
+        self.cancel();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.cancelled.load(Ordering::Relaxed)
+    }
+
+    pub fn is_timed_out(&self) -> bool {
+        self.0.timed_out.load(Ordering::Relaxed)
+    }
+}
+
+/// In-flight requests by JSON-RPC id
+#[derive(Debug, Default)]
+pub struct Registry {
+    requests: Mutex<HashMap<String, CancelToken>>,
+}
+
+impl Registry {
+    /// Track a request and return the token its work should poll
+    pub fn register(&self, id: &Value) -> CancelToken {
+        let token = CancelToken::default();
+        self.requests.lock().unwrap().insert(key(id), token.clone());
+        token
+    }
+
+    /// Cancel a request; false if it isn't in flight (already answered or unknown)
+    pub fn cancel(&self, id: &Value) -> bool {
+        match self.requests.lock().unwrap().get(&key(id)) {
+            Some(token) => {
+                token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Stop a request that ran out of time; false if it isn't in flight or
+    /// the client cancelled it already
+    pub fn time_out(&self, id: &Value) -> bool {
+        match self.requests.lock().unwrap().get(&key(id)) {
+            Some(token) if !token.is_cancelled() => {
+                token.time_out();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Cancel every request in flight, when shutdown stops waiting for them
+    pub fn cancel_all(&self) {
+        for token in self.requests.lock().unwrap().values() {
+            token.cancel();
+        }
+    }
+
+    /// Stop tracking a request once it has been answered
+    pub fn finish(&self, id: &Value) {
+        self.requests.lock().unwrap().remove(&key(id));
+    }
+}
+
+/// Ids are compared by their JSON form, so `1` and `"1"` stay distinct
+fn key(id: &Value) -> String {
+    id.to_string()
+}
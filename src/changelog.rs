@@ -0,0 +1,133 @@
+//! Leptos release notes
+//!
+//! The changelog bundled from `changelog/leptos.md`: one `##` heading per
+//! release, one `###` heading per kind of change. [`between`] picks the
+//! entries an upgrade crosses, so agents can enumerate what changed.
+
+use serde::Serialize;
+
+const CHANGELOG: &str = include_str!("../changelog/leptos.md");
+
+/// Kind of change, in the order entries are reported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Category {
+    Breaking,
+    Feature,
+    Fix,
+}
+
+impl Category {
+    pub const ALL: [Category; 3] = [Self::Breaking, Self::Feature, Self::Fix];
+
+    fn from_heading(heading: &str) -> Option<Self> {
+        let heading = heading.to_lowercase();
+        if heading.starts_with("breaking") {
+            Some(Self::Breaking)
+        } else if heading.starts_with("feature") || heading.starts_with("added") {
+            Some(Self::Feature)
+        } else if heading.starts_with("fix") {
+            Some(Self::Fix)
+        } else {
+            None
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Self::Breaking => "Breaking changes",
+            Self::Feature => "Features",
+            Self::Fix => "Fixes",
+        }
+    }
+}
+
+/// One changelog line
+#[derive(Debug, Clone, Serialize)]
+pub struct Entry {
+    /// Release the change shipped in, e.g. `0.7.0`
+    pub version: String,
+    pub category: Category,
+    pub text: String,
+}
+
+/// A version as `major.minor` or `major.minor.patch`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Release {
+    major: u32,
+    minor: u32,
+    patch: Option<u32>,
+}
+
+impl Release {
+    fn parse(version: &str) -> Option<Self> {
+        let mut parts = version.trim().trim_start_matches('v').split('.');
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next()?.parse().ok()?;
+        let patch = parts.next().map(|p| p.parse()).transpose().ok()?;
+        parts.next().is_none().then_some(Self {
+            major,
+            minor,
+            patch,
+        })
+    }
+
+    fn line(&self) -> (u32, u32) {
+        (self.major, self.minor)
+    }
+}
+
+/// Every entry, newest release first
+pub fn entries() -> Vec<Entry> {
+    let mut entries = Vec::new();
+    let mut version = None;
+    let mut category = None;
+    for line in CHANGELOG.lines() {
+        if let Some(heading) = line.strip_prefix("### ") {
+            category = Category::from_heading(heading);
+        } else if let Some(heading) = line.strip_prefix("## ") {
+            version = Some(heading.trim());
+            category = None;
+        } else if let (Some(version), Some(category), Some(text)) =
+            (version, category, line.strip_prefix("- "))
+        {
+            entries.push(Entry {
+                version: version.to_string(),
+                category,
+                text: text.trim().to_string(),
+            });
+        }
+    }
+    entries
+}
+
+/// Releases the changelog covers, newest first
+pub fn releases() -> Vec<&'static str> {
+    CHANGELOG
+        .lines()
+        .filter_map(|line| line.strip_prefix("## "))
+        .map(str::trim)
+        .collect()
+}
+
+/// Entries released after `from` up to and including `to`
+///
+/// A `major.minor` bound stands for its whole release line: upgrading from
+/// `0.6` skips every 0.6.x entry, and `to` `0.7` includes all 0.7.x ones.
+pub fn between(from: &str, to: &str) -> Result<Vec<Entry>, String> {
+    let invalid = |v: &str| format!("Invalid version '{}'. Expected e.g. '0.7' or '0.7.2'", v);
+    let from_release = Release::parse(from).ok_or_else(|| invalid(from))?;
+    let to_release = Release::parse(to).ok_or_else(|| invalid(to))?;
+    let after_from = |r: &Release| match from_release.patch {
+        Some(patch) => (r.line(), r.patch.unwrap_or(0)) > (from_release.line(), patch),
+        None => r.line() > from_release.line(),
+    };
+    let up_to = |r: &Release| match to_release.patch {
+        Some(patch) => (r.line(), r.patch.unwrap_or(0)) <= (to_release.line(), patch),
+        None => r.line() <= to_release.line(),
+    };
+    Ok(entries()
+        .into_iter()
+        .filter(|entry| Release::parse(&entry.version).is_some_and(|r| after_from(&r) && up_to(&r)))
+        .collect())
+}
@@ -0,0 +1,567 @@
+//! Command-line interface
+//!
+//! Without a subcommand the binary serves MCP over stdio. Subcommands run the
+//! same tool implementations directly, for shell scripts and CI.
+
+use clap::{Parser, Subcommand};
+use leptos_mcp_server::cancel::CancelToken;
+use leptos_mcp_server::config::{Limits, ServerConfig, ToolsConfig};
+use leptos_mcp_server::docs::LeptosVersion;
+use leptos_mcp_server::format;
+use leptos_mcp_server::progress::Progress;
+use leptos_mcp_server::protocol::McpServer;
+use leptos_mcp_server::record;
+use leptos_mcp_server::rustdoc;
+use leptos_mcp_server::tools::{
+    DocFormat, DocOptions, LeptosTools, OutputFormat, SearchMode, DEFAULT_SEARCH_LIMIT,
+};
+use serde_json::Value;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+#[derive(Parser)]
+#[command(
+    version,
+    about = "Leptos documentation and code analysis, over MCP or the command line"
+)]
+pub struct Cli {
+    /// Config file; defaults to leptos-mcp.toml in the working directory if present
+    #[arg(long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+    /// Leptos version used when a tool call doesn't specify one (0.6, 0.7, 0.8)
+    #[arg(long, value_name = "VERSION")]
+    pub leptos_version: Option<String>,
+    /// Directory laid out like the bundled docs/ whose pages replace the bundled ones
+    #[arg(long, value_name = "DIR")]
+    pub docs_dir: Option<PathBuf>,
+    /// Reload --docs-dir pages when they change on disk, notifying subscribed clients
+    #[arg(long)]
+    pub watch_docs: bool,
+    /// Directory of team pages keyed by section path [default: ./docs-overrides if present]
+    #[arg(long, value_name = "DIR")]
+    pub docs_overrides: Option<PathBuf>,
+    /// Whether override pages replace or append to built-in sections [default: append]
+    #[arg(long, value_name = "MODE")]
+    pub overrides_mode: Option<String>,
+    /// Transport to serve MCP over: stdio, unix to accept connections on --socket, or ws for WebSocket connections to --listen
+    #[arg(long, value_name = "NAME")]
+    pub transport: Option<String>,
+    /// Socket path for the unix transport
+    #[arg(long, value_name = "PATH")]
+    pub socket: Option<PathBuf>,
+    /// Address for the ws transport, like 127.0.0.1:8765
+    #[arg(long, value_name = "ADDR")]
+    pub listen: Option<String>,
+    /// Browser origins the ws transport accepts besides loopback ones (comma-separated, * for any)
+    #[arg(long, value_name = "ORIGINS", value_delimiter = ',')]
+    pub allowed_origins: Option<Vec<String>>,
+    /// Serve Prometheus metrics at http://ADDR/metrics when serving, like 127.0.0.1:9464
+    #[arg(long, value_name = "ADDR")]
+    pub metrics_listen: Option<String>,
+    /// Append every message received and sent when serving to this JSONL file (see replay)
+    #[arg(long, value_name = "FILE")]
+    pub record: Option<PathBuf>,
+    /// How stdio messages are delimited: ndjson, content-length or auto [default: auto]
+    #[arg(long, value_name = "FRAMING")]
+    pub framing: Option<String>,
+    /// Stderr log level when RUST_LOG isn't set: trace, debug, info, warn, error or off
+    #[arg(long, value_name = "LEVEL")]
+    pub log_level: Option<String>,
+    /// Only serve these tools (comma-separated names)
+    #[arg(long, value_name = "NAMES", value_delimiter = ',')]
+    pub tools: Option<Vec<String>>,
+    /// Don't serve these tools (comma-separated names), even if listed in --tools
+    #[arg(long, value_name = "NAMES", value_delimiter = ',')]
+    pub disable_tools: Vec<String>,
+    /// Serve tools that write to project files (apply-fix)
+    #[arg(long)]
+    pub allow_writes: bool,
+    /// Serve tools that run the code they're sent (render-preview)
+    #[arg(long)]
+    pub allow_exec: bool,
+    /// Longest JSON-RPC message accepted, in bytes
+    #[arg(long, value_name = "BYTES")]
+    pub max_line_bytes: Option<usize>,
+    /// Longest source-text tool argument (code, html, output), in bytes
+    #[arg(long, value_name = "BYTES")]
+    pub max_code_bytes: Option<usize>,
+    /// Most Rust files a project scan collects
+    #[arg(long, value_name = "COUNT")]
+    pub max_project_files: Option<usize>,
+    /// Most bytes of Rust source a project scan reads
+    #[arg(long, value_name = "BYTES")]
+    pub max_project_bytes: Option<usize>,
+    /// Most bytes of output kept from a cargo run or rendered preview
+    #[arg(long, value_name = "BYTES")]
+    pub max_output_bytes: Option<usize>,
+    /// Longest text block of a tool result; longer text is split into parts
+    #[arg(long, value_name = "CHARS")]
+    pub max_block_chars: Option<usize>,
+    /// Results of docs and API tool calls cached for identical calls; 0 disables the cache [default: 256]
+    #[arg(long, value_name = "COUNT")]
+    pub cache_entries: Option<usize>,
+    /// Tool calls each session may make per minute; further calls get an error with a retry time
+    #[arg(long, value_name = "COUNT")]
+    pub calls_per_minute: Option<u32>,
+    /// Tool calls each session may make at once before --calls-per-minute applies
+    #[arg(long, value_name = "COUNT")]
+    pub call_burst: Option<u32>,
+    /// Seconds a tool call may run before it gets a timeout error; 0 for no limit [default: 120]
+    #[arg(long, value_name = "SECONDS")]
+    pub tool_timeout: Option<u64>,
+    /// Seconds a call to check-snippet, cargo-check, render-preview or sync-docs may run; 0 for no limit [default: 900]
+    #[arg(long, value_name = "SECONDS")]
+    pub subprocess_timeout: Option<u64>,
+    /// Fetch the latest Leptos book in the background when serving (see the sync-docs tool)
+    #[arg(long)]
+    pub sync_on_start: bool,
+    /// Ping the client after this many idle seconds when serving, so a hung peer is noticed
+    #[arg(long, value_name = "SECONDS")]
+    pub keepalive: Option<u64>,
+    /// Seconds to let in-flight requests finish on shutdown before abandoning them [default: 10]
+    #[arg(long, value_name = "SECONDS")]
+    pub drain_timeout: Option<u64>,
+    /// OpenAI-compatible /embeddings endpoint for semantic search [default: built-in embedder]
+    #[arg(long, value_name = "URL")]
+    pub embeddings_url: Option<String>,
+    /// Model requested from --embeddings-url
+    #[arg(long, value_name = "MODEL")]
+    pub embeddings_model: Option<String>,
+    /// Log format on stderr when serving: text or json (one object per line)
+    #[arg(long, value_name = "FORMAT")]
+    pub log_format: Option<String>,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Serve MCP over stdio (the default)
+    Serve,
+    /// Browse the documentation
+    #[command(subcommand)]
+    Docs(DocsCommand),
+    /// Search documentation subsections by keyword or meaning
+    Search {
+        query: String,
+        #[arg(long, default_value_t = DEFAULT_SEARCH_LIMIT)]
+        limit: usize,
+        /// Continue from the cursor printed after a previous page
+        #[arg(long)]
+        cursor: Option<String>,
+        /// Leptos version (0.6, 0.7, 0.8)
+        #[arg(long)]
+        version: Option<String>,
+        /// keyword or semantic
+        #[arg(long, default_value = "keyword")]
+        mode: String,
+    },
+    /// Run the autofixer on files or project directories; exits 1 on errors
+    Lint {
+        #[arg(required = true)]
+        paths: Vec<String>,
+        /// text or json
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+    /// Review a unified diff read from a file or stdin; exits 1 if the change introduces errors
+    Review {
+        /// File with the diff; reads stdin if omitted
+        file: Option<String>,
+        /// Project the diff applies to [default: the working directory]
+        #[arg(long)]
+        path: Option<String>,
+        /// text or json
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+    /// List the lint rules, or explain one with its example and docs
+    Rules {
+        /// Rule id or name, like L0001 or get-without-move
+        rule: Option<String>,
+    },
+    /// Explain Leptos errors in cargo build output read from a file or stdin
+    Explain {
+        /// File with the build output; reads stdin if omitted
+        file: Option<String>,
+    },
+    /// Generate the lookup-api reference for a Leptos version from the crates' rustdoc JSON (needs a nightly toolchain)
+    ApiIndex {
+        /// Leptos version (0.6, 0.7, 0.8)
+        version: String,
+        /// Crates to document (comma-separated) [default: leptos, leptos_router, leptos_meta, leptos_axum]
+        #[arg(long = "crates", value_name = "NAMES", value_delimiter = ',')]
+        crates: Vec<String>,
+        /// File to write, like api/leptos-0.8.json; prints to stdout if omitted
+        #[arg(long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+    /// Replay a session recorded with --record and diff the responses; exits 1 if any changed
+    Replay {
+        file: PathBuf,
+        /// Only this session of the recording
+        #[arg(long)]
+        session: Option<u64>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum DocsCommand {
+    /// List documentation sections
+    List,
+    /// Print a section, or a subsection with `section#heading`
+    Get {
+        section: String,
+        /// Leptos version (0.6, 0.7, 0.8)
+        #[arg(long)]
+        version: Option<String>,
+        /// Language (en, zh); untranslated sections are printed in English
+        #[arg(long)]
+        lang: Option<String>,
+        /// Only list the section's headings
+        #[arg(long)]
+        toc: bool,
+        /// markdown, plain or html
+        #[arg(long, default_value = "markdown")]
+        format: String,
+    },
+}
+
+impl Cli {
+    /// Settings given as flags, the top layer of the server config
+    pub fn server_config(&self) -> ServerConfig {
+        ServerConfig {
+            version: self.leptos_version.clone(),
+            docs_dir: self.docs_dir.clone(),
+            watch_docs: self.watch_docs.then_some(true),
+            docs_overrides: self.docs_overrides.clone(),
+            overrides_mode: self.overrides_mode.clone(),
+            transport: self.transport.clone(),
+            socket: self.socket.clone(),
+            listen: self.listen.clone(),
+            allowed_origins: self.allowed_origins.clone(),
+            // Tokens given as flags would show in the process list
+            auth: None,
+            metrics_listen: self.metrics_listen.clone(),
+            record: self.record.clone(),
+            framing: self.framing.clone(),
+            log_level: self.log_level.clone(),
+            log_format: self.log_format.clone(),
+            keepalive: self.keepalive,
+            drain_timeout: self.drain_timeout,
+            sync_on_start: self.sync_on_start.then_some(true),
+            embeddings_url: self.embeddings_url.clone(),
+            embeddings_model: self.embeddings_model.clone(),
+        }
+    }
+
+    /// Override `limits` with the limits given as flags
+    pub fn apply_limits(&self, limits: &mut Limits) {
+        if let Some(bytes) = self.max_line_bytes {
+            limits.max_line_bytes = bytes;
+        }
+        if let Some(bytes) = self.max_code_bytes {
+            limits.max_code_bytes = bytes;
+        }
+        if let Some(count) = self.max_project_files {
+            limits.max_project_files = count;
+        }
+        if let Some(bytes) = self.max_project_bytes {
+            limits.max_project_bytes = bytes;
+        }
+        if let Some(bytes) = self.max_output_bytes {
+            limits.max_output_bytes = bytes;
+        }
+        if let Some(chars) = self.max_block_chars {
+            limits.max_block_chars = chars;
+        }
+        if let Some(count) = self.cache_entries {
+            limits.cache_entries = count;
+        }
+        if let Some(count) = self.calls_per_minute {
+            limits.calls_per_minute = count;
+        }
+        if let Some(count) = self.call_burst {
+            limits.call_burst = count;
+        }
+        if let Some(secs) = self.tool_timeout {
+            limits.tool_timeout = secs;
+        }
+        if let Some(secs) = self.subprocess_timeout {
+            limits.subprocess_timeout = secs;
+        }
+    }
+
+    /// Tool filter given as flags, layered over the config file and environment
+    pub fn tools_config(&self) -> ToolsConfig {
+        ToolsConfig {
+            enabled: self.tools.clone(),
+            disabled: self.disable_tools.clone(),
+            allow_writes: self.allow_writes.then_some(true),
+            allow_exec: self.allow_exec.then_some(true),
+        }
+    }
+}
+
+/// Run a tool subcommand; tool errors go to stderr with exit code 1
+pub fn run(command: Command, tools: LeptosTools, limits: Limits) -> ExitCode {
+    let result = match command {
+        Command::Serve | Command::Replay { .. } => unreachable!("handled by main"),
+        Command::Docs(DocsCommand::List) => Ok(tools.list_sections().text),
+        Command::Docs(DocsCommand::Get {
+            section,
+            version,
+            lang,
+            toc,
+            format,
+        }) => DocFormat::parse(&format).and_then(|format| {
+            let options = DocOptions {
+                toc,
+                ..DocOptions::default()
+            };
+            tools
+                .get_documentation(
+                    &section,
+                    version.as_deref(),
+                    lang.as_deref(),
+                    options,
+                    format,
+                )
+                .map(|output| output.text)
+        }),
+        Command::Search {
+            query,
+            limit,
+            cursor,
+            version,
+            mode,
+        } => SearchMode::parse(&mode).and_then(|mode| {
+            tools
+                .search_docs(
+                    &query,
+                    mode,
+                    version.as_deref(),
+                    limit,
+                    cursor.as_deref(),
+                    None,
+                )
+                .map(|output| output.text)
+        }),
+        Command::Rules { rule: None } => Ok(tools.list_rules().text),
+        Command::Rules { rule: Some(rule) } => {
+            tools.explain_rule(&rule, None).map(|output| output.text)
+        }
+        Command::Explain { file } => read_input(file.as_deref())
+            .and_then(|output| tools.explain_error(&output, OutputFormat::Text))
+            .map(|output| output.text),
+        Command::Lint { paths, format } => return lint(&tools, &paths, &format, limits),
+        Command::Review { file, path, format } => {
+            return review(&tools, file.as_deref(), path.as_deref(), &format, limits)
+        }
+        Command::ApiIndex {
+            version,
+            crates,
+            output,
+        } => api_index(&version, &crates, output.as_deref()),
+    };
+    match result {
+        Ok(text) => {
+            print(&text);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Generate an API reference and write it to `output`, or return it
+fn api_index(version: &str, crates: &[String], output: Option<&Path>) -> Result<String, String> {
+    let version = LeptosVersion::parse(version)
+        .ok_or_else(|| format!("Unsupported Leptos version '{}'", version))?;
+    let crates = match crates.is_empty() {
+        true => rustdoc::CRATES
+            .iter()
+            .map(|(name, _)| name.to_string())
+            .collect(),
+        false => crates.to_vec(),
+    };
+    let reference = rustdoc::generate(version, &crates)?;
+    let json = serde_json::to_string_pretty(&reference)
+        .map_err(|e| format!("Failed to serialize API reference: {}", e))?;
+    let Some(output) = output else {
+        return Ok(json);
+    };
+    fs::write(output, json + "\n")
+        .map_err(|e| format!("Failed to write {}: {}", output.display(), e))?;
+    let crates: Vec<String> = reference
+        .crates
+        .iter()
+        .map(|(name, version)| format!("{} {}", name, version))
+        .collect();
+    Ok(format!(
+        "Wrote {} items from {} to {}",
+        reference.items.len(),
+        crates.join(", "),
+        output.display()
+    ))
+}
+
+/// Replay a recording through `server` and print what changed
+pub fn replay(server: &McpServer, file: &Path, session: Option<u64>) -> ExitCode {
+    let replayed = match record::replay(file, server, session) {
+        Ok(replayed) => replayed,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let (mut same, mut changed, mut unmatched) = (0, 0, 0);
+    for r in &replayed {
+        let request = match &r.tool {
+            Some(tool) => format!("{} {}", r.method, tool),
+            None => r.method.clone(),
+        };
+        let id = &r.replayed["id"];
+        match (r.matches(), &r.recorded) {
+            (Some(true), _) => {
+                same += 1;
+                print(&format!("✓ session {} #{} {}", r.session, id, request));
+            }
+            (Some(false), Some(recorded)) => {
+                changed += 1;
+                let pretty = |v: &Value| serde_json::to_string_pretty(v).unwrap_or_default();
+                print(&format!("✗ session {} #{} {}", r.session, id, request));
+                print(&format::unified_diff_labeled(
+                    &pretty(recorded),
+                    &pretty(&r.replayed),
+                    "recorded",
+                    "replayed",
+                ));
+            }
+            _ => {
+                unmatched += 1;
+                print(&format!(
+                    "? session {} #{} {}: no recorded response",
+                    r.session, id, request
+                ));
+            }
+        }
+    }
+    print(&format!(
+        "\n{} request(s) replayed: {} unchanged, {} changed, {} without a recorded response",
+        replayed.len(),
+        same,
+        changed,
+        unmatched
+    ));
+    if changed > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn lint(tools: &LeptosTools, paths: &[String], format: &str, limits: Limits) -> ExitCode {
+    let format = match OutputFormat::parse(format) {
+        Ok(format) => format,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let progress = Progress::new(None, CancelToken::default()).with_limits(limits);
+    let mut failed = false;
+    for path in paths {
+        // Whether any finding is an error, and the report
+        let output = if Path::new(path).is_dir() {
+            tools
+                .analyze_project(Some(path), None, format, &progress)
+                .map(|output| {
+                    let errors = output.structured.is_some_and(|f| f.has_errors());
+                    (errors, output.text)
+                })
+        } else {
+            fs::read_to_string(path)
+                .map_err(|e| format!("Failed to read {}: {}", path, e))
+                .and_then(|code| tools.leptos_autofixer(&code, None, format))
+                .map(|output| {
+                    let errors = output.structured.is_some_and(|f| f.has_errors());
+                    (errors, output.text)
+                })
+        };
+        match output {
+            Ok((errors, text)) => {
+                failed |= errors;
+                if paths.len() > 1 && format == OutputFormat::Text {
+                    print(&format!("# {}\n", path));
+                }
+                print(&text);
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                failed = true;
+            }
+        }
+    }
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn review(
+    tools: &LeptosTools,
+    file: Option<&str>,
+    path: Option<&str>,
+    format: &str,
+    limits: Limits,
+) -> ExitCode {
+    let progress = Progress::new(None, CancelToken::default()).with_limits(limits);
+    let output = OutputFormat::parse(format).and_then(|format| {
+        let diff = read_input(file)?;
+        tools.review_diff(&diff, path, None, format, &progress)
+    });
+    match output {
+        Ok(output) => {
+            print(&output.text);
+            if output
+                .structured
+                .is_some_and(|review| review.introduces_errors())
+            {
+                ExitCode::FAILURE
+            } else {
+                ExitCode::SUCCESS
+            }
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Whether a file or project report contains error-severity diagnostics
+fn read_input(file: Option<&str>) -> Result<String, String> {
+    match file {
+        Some(path) => {
+            fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path, e))
+        }
+        None => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|e| format!("Failed to read stdin: {}", e))?;
+            Ok(input)
+        }
+    }
+}
+
+/// Print a line, ignoring a closed pipe (e.g. when piped into `head`)
+fn print(text: &str) {
+    let _ = writeln!(io::stdout().lock(), "{}", text);
+}
@@ -0,0 +1,221 @@
+//! Component inventory
+//!
+//! Lists the `#[component]`s and `#[island]`s of a project with their props,
+//! read from the parameter list and its `#[prop(...)]` options, and the
+//! places that use each one: `<Name .../>` in a `view!`, or the name passed
+//! as a view, like `view=HomePage` on a route.
+
+use crate::project::SourceFile;
+use crate::rules::position;
+use crate::source::{functions, mask_literals, split_top_level, FnItem};
+use crate::view::{parse_views, skip_balanced, Node};
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::ops::Range;
+
+/// One prop of a component
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct Prop {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: String,
+    /// `#[prop(optional)]`, `#[prop(optional_no_strip)]` or a default: callers may leave it out
+    pub optional: bool,
+    /// The `#[prop(default = ...)]` expression
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+    /// `#[prop(into)]`: callers may pass anything that converts into the type
+    pub into: bool,
+    /// The prop's doc comment
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub doc: String,
+}
+
+/// A place a component is used
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct Usage {
+    /// Function the use is in, empty for code outside any function
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub function: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub file: String,
+    pub line: usize,
+}
+
+/// A component and where it is used
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct Component {
+    pub name: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub file: String,
+    pub line: usize,
+    /// Declared with `#[island]`
+    pub island: bool,
+    /// The component's doc comment
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub doc: String,
+    pub props: Vec<Prop>,
+    /// Takes a `children` prop
+    pub children: bool,
+    pub used_by: Vec<Usage>,
+}
+
+/// Every component in `files`, in file and source order
+pub fn inventory(files: &[SourceFile]) -> Vec<Component> {
+    let mut components = Vec::new();
+    for file in files {
+        let code = file.content.as_str();
+        for item in functions(code).into_iter().filter(|f| f.is_component()) {
+            let props = props(code, item.params.clone());
+            components.push(Component {
+                file: file.path.display().to_string(),
+                line: position(code, item.params.start).0,
+                island: item.has_attr("island"),
+                doc: doc_comment(code, &item),
+                children: props.iter().any(|p| p.name == "children"),
+                props,
+                name: item.name,
+                used_by: Vec::new(),
+            });
+        }
+    }
+    for file in files {
+        let code = file.content.as_str();
+        let items = functions(code);
+        let owner = |offset: usize| {
+            items
+                .iter()
+                .filter(|f| f.body.contains(&offset))
+                .min_by_key(|f| f.body.len())
+                .map_or_else(String::new, |f| f.name.clone())
+        };
+        for view in parse_views(code) {
+            for node in &view.nodes {
+                let Node::Open(element) = node else {
+                    continue;
+                };
+                // `<Name/>`, or `view=Name` and the like passing it as a view
+                let mut names = vec![(element.name.as_str(), element.offset)];
+                names.extend(element.attrs.iter().filter_map(|attr| {
+                    let expr = attr.expr().filter(|e| is_path(e))?;
+                    Some((expr, attr.offset))
+                }));
+                for (name, offset) in names {
+                    let name = name.rsplit("::").next().unwrap_or(name);
+                    let Some(component) = components.iter_mut().find(|c| c.name == name) else {
+                        continue;
+                    };
+                    component.used_by.push(Usage {
+                        function: owner(offset),
+                        file: file.path.display().to_string(),
+                        line: position(code, offset).0,
+                    });
+                }
+            }
+        }
+    }
+    components
+}
+
+/// A bare name or path like `HomePage` or `pages::HomePage`
+fn is_path(expr: &str) -> bool {
+    expr.starts_with(|c: char| c.is_uppercase() || c.is_lowercase())
+        && expr
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == ':')
+}
+
+/// Props of a component's parameter list with their `#[prop]` options and docs
+fn props(code: &str, params: Range<usize>) -> Vec<Prop> {
+    // Commas in doc comments and string defaults don't separate parameters
+    let masked = mask_literals(&code[params.clone()]);
+    let base = masked.as_ptr() as usize;
+    split_top_level(&masked)
+        .into_iter()
+        .filter_map(|part| {
+            let start = params.start + (part.as_ptr() as usize - base);
+            let masked_part = part;
+            let part = &code[start..start + part.len()];
+            let mut doc = Vec::new();
+            let mut options = Vec::new();
+            let mut rest = 0;
+            for line in part.lines() {
+                let trimmed = line.trim();
+                if let Some(text) = trimmed.strip_prefix("///") {
+                    doc.push(text.trim().to_string());
+                } else if !trimmed.is_empty() && !trimmed.starts_with("//") {
+                    break;
+                }
+                rest += line.len() + 1;
+            }
+            // Attributes come after the doc comment
+            let bytes = masked_part.as_bytes();
+            let mut i = rest.min(part.len());
+            loop {
+                while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+                    i += 1;
+                }
+                if !masked_part[i..].starts_with("#[") {
+                    break;
+                }
+                let end = skip_balanced(bytes, i + 1, b'[', b']')?;
+                options.extend(prop_options(&part[i + 2..end - 1]));
+                i = end;
+            }
+            let (pattern, ty) = part[i..].split_once(':')?;
+            let name = pattern.trim().trim_start_matches("mut ").trim();
+            if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+                return None;
+            }
+            let default = options
+                .iter()
+                .find_map(|(key, value)| (key == "default").then(|| value.clone()))
+                .flatten();
+            let has = |option: &str| options.iter().any(|(key, _)| key == option);
+            Some(Prop {
+                name: name.to_string(),
+                ty: ty.split_whitespace().collect::<Vec<_>>().join(" "),
+                optional: has("optional") || has("optional_no_strip") || has("default"),
+                default,
+                into: has("into"),
+                doc: doc.join(" "),
+            })
+        })
+        .collect()
+}
+
+/// `(key, value)` pairs of a `prop(optional, default = 3)` attribute; other
+/// attributes give none
+fn prop_options(attr: &str) -> Vec<(String, Option<String>)> {
+    let Some(inner) = attr
+        .trim()
+        .strip_prefix("prop(")
+        .and_then(|rest| rest.strip_suffix(')'))
+    else {
+        return Vec::new();
+    };
+    split_top_level(inner)
+        .into_iter()
+        .filter(|option| !option.trim().is_empty())
+        .map(|option| match option.split_once('=') {
+            Some((key, value)) => (key.trim().to_string(), Some(value.trim().to_string())),
+            None => (option.trim().to_string(), None),
+        })
+        .collect()
+}
+
+/// The `///` lines directly above a function and its attributes
+fn doc_comment(code: &str, item: &FnItem) -> String {
+    let fn_start = code[..item.params.start].rfind("fn ").unwrap_or(0);
+    let line_start = code[..fn_start].rfind('\n').map_or(0, |i| i + 1);
+    let mut lines: Vec<&str> = code[..line_start]
+        .lines()
+        .rev()
+        .map(str::trim)
+        .take_while(|l| l.starts_with("#[") || l.starts_with("///"))
+        .filter_map(|l| l.strip_prefix("///"))
+        .map(str::trim)
+        .collect();
+    lines.reverse();
+    lines.join(" ").trim().to_string()
+}
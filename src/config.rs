@@ -0,0 +1,733 @@
+//! Server configuration
+//!
+//! Settings come from three layers, later ones winning: a `leptos-mcp.toml`
+//! file (`--config <PATH>`, or the one in the working directory), `LEPTOS_MCP_*`
+//! environment variables, and command-line flags. The file's `[server]` and
+//! `[tools]` tables configure the server; its `[rules]` table is the same one
+//! a project's `leptos-mcp.toml` uses and becomes the base rule config.
+//!
+//! ```toml
+//! [server]
+//! version = "0.7"
+//! docs_dir = "./leptos-docs"
+//! watch_docs = true
+//! docs_overrides = "./team-docs"
+//! overrides_mode = "replace"
+//! log_level = "debug"
+//!
+//! [tools]
+//! disabled = ["check-snippet", "analyze-project"]
+//! allow_writes = true
+//! allow_exec = true
+//!
+//! [server.auth]
+//! tokens = [{ token = "ci-secret", tools = ["leptos-autofixer", "get-documentation"] }]
+//!
+//! [limits]
+//! calls_per_minute = 120
+//! tool_timeout = 60
+//!
+//! [rules]
+//! disable = ["L0005"]
+//! plugins = ["house-rules.toml"]
+//! ```
+
+use crate::docs::{self, LeptosVersion, OverrideMode};
+use crate::protocol::Framing;
+use crate::registry::{self, Entry, TOOL_NAMES};
+use crate::rules::{self, RuleConfig};
+use crate::tools::OutputFormat;
+use serde::Deserialize;
+use std::fs;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Default Leptos version
+pub const VERSION_ENV: &str = "LEPTOS_MCP_VERSION";
+/// Directory of markdown merged over the bundled documentation
+pub const DOCS_DIR_ENV: &str = "LEPTOS_MCP_DOCS_DIR";
+/// Directory of team pages merged into the documentation by section path
+pub const DOCS_OVERRIDES_ENV: &str = "LEPTOS_MCP_DOCS_OVERRIDES";
+/// Transport the server speaks
+pub const TRANSPORT_ENV: &str = "LEPTOS_MCP_TRANSPORT";
+/// Socket path for the `unix` transport
+pub const SOCKET_ENV: &str = "LEPTOS_MCP_SOCKET";
+/// Address the `ws` transport listens on
+pub const LISTEN_ENV: &str = "LEPTOS_MCP_LISTEN";
+/// Address of the Prometheus metrics endpoint
+pub const METRICS_LISTEN_ENV: &str = "LEPTOS_MCP_METRICS_LISTEN";
+/// How stdio messages are delimited
+pub const FRAMING_ENV: &str = "LEPTOS_MCP_FRAMING";
+/// Stderr log level
+pub const LOG_LEVEL_ENV: &str = "LEPTOS_MCP_LOG_LEVEL";
+/// Stderr log format
+pub const LOG_FORMAT_ENV: &str = "LEPTOS_MCP_LOG_FORMAT";
+/// OpenAI-compatible `/embeddings` endpoint for semantic search
+pub const EMBEDDINGS_URL_ENV: &str = "LEPTOS_MCP_EMBEDDINGS_URL";
+/// Model requested from the embeddings endpoint
+pub const EMBEDDINGS_MODEL_ENV: &str = "LEPTOS_MCP_EMBEDDINGS_MODEL";
+/// Comma-separated names of the tools to serve
+pub const TOOLS_ENV: &str = "LEPTOS_MCP_TOOLS";
+/// Comma-separated names of tools not to serve
+pub const DISABLED_TOOLS_ENV: &str = "LEPTOS_MCP_DISABLED_TOOLS";
+/// Serve tools that write to the project's files (`true`)
+pub const ALLOW_WRITES_ENV: &str = "LEPTOS_MCP_ALLOW_WRITES";
+/// Serve tools that run the code they're sent (`true`)
+pub const ALLOW_EXEC_ENV: &str = "LEPTOS_MCP_ALLOW_EXEC";
+/// Bearer token the `ws` transport accepts for every tool
+pub const AUTH_TOKEN_ENV: &str = "LEPTOS_MCP_AUTH_TOKEN";
+
+/// Transports the server can speak
+#[cfg(unix)]
+const TRANSPORTS: &[&str] = &["stdio", "unix", "ws"];
+#[cfg(not(unix))]
+const TRANSPORTS: &[&str] = &["stdio", "ws"];
+
+/// Stderr log levels, as `tracing` filter directives
+const LOG_LEVELS: &[&str] = &["trace", "debug", "info", "warn", "error", "off"];
+
+/// Everything `leptos-mcp.toml` and the other layers can set
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub server: ServerConfig,
+    pub tools: ToolsConfig,
+    pub limits: Limits,
+    pub rules: RuleConfig,
+}
+
+/// Server settings; `None` leaves the default in place
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfig {
+    /// Leptos version used when a tool call doesn't specify one
+    pub version: Option<String>,
+    /// Directory laid out like the bundled `docs/` whose pages are merged over it
+    pub docs_dir: Option<PathBuf>,
+    /// Reload `docs_dir` pages when they change on disk
+    pub watch_docs: Option<bool>,
+    /// Team pages keyed by section path; `docs-overrides/` in the working
+    /// directory if unset and present
+    pub docs_overrides: Option<PathBuf>,
+    /// `append` (the default) or `replace`, for pages without their own `mode`
+    pub overrides_mode: Option<String>,
+    /// `stdio` (the default), `unix` or `ws`
+    pub transport: Option<String>,
+    /// Socket path the `unix` transport listens on
+    pub socket: Option<PathBuf>,
+    /// Address the `ws` transport listens on, like `127.0.0.1:8765`
+    pub listen: Option<String>,
+    /// Browser origins the `ws` transport accepts besides loopback ones; `*` for any
+    pub allowed_origins: Option<Vec<String>>,
+    /// Bearer tokens the `ws` transport requires of its clients
+    pub auth: Option<AuthConfig>,
+    /// Address to serve Prometheus metrics on at `/metrics`, like `127.0.0.1:9464`
+    pub metrics_listen: Option<String>,
+    /// JSONL file every message of every session is appended to
+    pub record: Option<PathBuf>,
+    /// ndjson, content-length or auto
+    pub framing: Option<String>,
+    /// Stderr log level when `RUST_LOG` isn't set
+    pub log_level: Option<String>,
+    /// Stderr log format: text or json
+    pub log_format: Option<String>,
+    /// Seconds without a client message before a keepalive ping; 0 disables
+    pub keepalive: Option<u64>,
+    /// Seconds in-flight requests get on shutdown
+    pub drain_timeout: Option<u64>,
+    /// Sync the Leptos book in the background on start
+    pub sync_on_start: Option<bool>,
+    /// OpenAI-compatible `/embeddings` endpoint for semantic search; the
+    /// built-in embedder if unset
+    pub embeddings_url: Option<String>,
+    /// Model requested from `embeddings_url`
+    pub embeddings_model: Option<String>,
+}
+
+/// Size limits checked before a request is handled, and the time and
+/// resources a tool call may use
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Limits {
+    /// Longest message accepted; longer lines are discarded unread
+    pub max_line_bytes: usize,
+    /// Longest source-text argument (`code`, `html`, `output`)
+    pub max_code_bytes: usize,
+    /// Most Rust files a project scan collects
+    pub max_project_files: usize,
+    /// Most bytes of Rust source a project scan reads
+    pub max_project_bytes: usize,
+    /// Most bytes of output kept from a cargo run or a rendered preview
+    pub max_output_bytes: usize,
+    /// Longest text content block of a tool result; longer text is split
+    /// into numbered parts
+    pub max_block_chars: usize,
+    /// Results of docs and API tool calls kept for identical calls; 0
+    /// disables the cache
+    pub cache_entries: usize,
+    /// Tool calls a session may make per minute; 0 for no limit
+    pub calls_per_minute: u32,
+    /// Tool calls a session may make at once before `calls_per_minute`
+    /// applies; 0 for a minute's worth
+    pub call_burst: u32,
+    /// Seconds a tool call may run before it's answered with a timeout
+    /// error; 0 for no limit
+    pub tool_timeout: u64,
+    /// Seconds a call to a tool running cargo or curl may run instead of
+    /// `tool_timeout`; 0 for no limit
+    pub subprocess_timeout: u64,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            max_line_bytes: 8 * 1024 * 1024,
+            max_code_bytes: 1024 * 1024,
+            max_project_files: 5000,
+            max_project_bytes: 64 * 1024 * 1024,
+            max_output_bytes: 64 * 1024 * 1024,
+            max_block_chars: 256 * 1024,
+            cache_entries: 256,
+            calls_per_minute: 0,
+            call_burst: 0,
+            tool_timeout: 120,
+            subprocess_timeout: 900,
+        }
+    }
+}
+
+impl Limits {
+    /// Name and value of the time limit on calls to `tool`; `None` if
+    /// they aren't limited
+    pub fn timeout(&self, tool: &Entry) -> Option<(&'static str, Duration)> {
+        let (name, secs) = match tool.subprocess {
+            true => ("subprocess_timeout", self.subprocess_timeout),
+            false => ("tool_timeout", self.tool_timeout),
+        };
+        (secs > 0).then(|| (name, Duration::from_secs(secs)))
+    }
+}
+
+/// Which tools are served
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ToolsConfig {
+    /// Names of the tools to serve; all of them if unset
+    pub enabled: Option<Vec<String>>,
+    /// Names of tools not to serve, even if enabled
+    pub disabled: Vec<String>,
+    /// Serve tools that write to files, like `apply-fix`; off unless set
+    pub allow_writes: Option<bool>,
+    /// Serve tools that run the code they're sent, like `render-preview`;
+    /// off unless set
+    pub allow_exec: Option<bool>,
+}
+
+impl ToolsConfig {
+    /// Layer `other` on top: its allowlist replaces this one, disabled tools add up
+    pub fn merge(&mut self, other: ToolsConfig) {
+        if other.enabled.is_some() {
+            self.enabled = other.enabled;
+        }
+        self.disabled.extend(other.disabled);
+        if other.allow_writes.is_some() {
+            self.allow_writes = other.allow_writes;
+        }
+        if other.allow_exec.is_some() {
+            self.allow_exec = other.allow_exec;
+        }
+    }
+
+    /// These settings with only the tools in `names` left enabled
+    pub fn restricted(&self, names: &[String]) -> ToolsConfig {
+        let enabled = match &self.enabled {
+            Some(enabled) => enabled
+                .iter()
+                .filter(|name| names.contains(name))
+                .cloned()
+                .collect(),
+            None => names.to_vec(),
+        };
+        ToolsConfig {
+            enabled: Some(enabled),
+            ..self.clone()
+        }
+    }
+
+    fn allows_writes(&self) -> bool {
+        self.allow_writes == Some(true)
+    }
+
+    fn allows_exec(&self) -> bool {
+        self.allow_exec == Some(true)
+    }
+
+    /// Whether `tool` is served
+    pub fn is_enabled(&self, tool: &str) -> bool {
+        self.enabled
+            .as_ref()
+            .is_none_or(|names| names.iter().any(|name| name == tool))
+            && !self.disabled.iter().any(|name| name == tool)
+            && (self.allows_writes() || !registry::find(tool).is_some_and(|t| t.writes))
+            && (self.allows_exec() || !registry::find(tool).is_some_and(|t| t.executes))
+    }
+
+    /// Reject names that aren't tools, so typos don't silently do nothing
+    pub fn validate(&self) -> Result<(), String> {
+        let unknown: Vec<&str> = self
+            .enabled
+            .iter()
+            .flatten()
+            .chain(&self.disabled)
+            .filter(|name| !TOOL_NAMES.contains(&name.as_str()))
+            .map(|name| name.as_str())
+            .collect();
+        if !unknown.is_empty() {
+            return Err(format!(
+                "Unknown tool(s): {}. Available tools: {}",
+                unknown.join(", "),
+                TOOL_NAMES.join(", ")
+            ));
+        }
+        // An allowlisted writer that would silently not be served
+        let writers: Vec<&str> = self
+            .enabled
+            .iter()
+            .flatten()
+            .filter(|name| registry::find(name).is_some_and(|t| t.writes))
+            .map(|name| name.as_str())
+            .collect();
+        if !writers.is_empty() && !self.allows_writes() {
+            return Err(format!(
+                "Enabled tool(s) {} write project files; set tools.allow_writes = true (--allow-writes) to serve them",
+                writers.join(", ")
+            ));
+        }
+        let executors: Vec<&str> = self
+            .enabled
+            .iter()
+            .flatten()
+            .filter(|name| registry::find(name).is_some_and(|t| t.executes))
+            .map(|name| name.as_str())
+            .collect();
+        if !executors.is_empty() && !self.allows_exec() {
+            return Err(format!(
+                "Enabled tool(s) {} run the code they're sent; set tools.allow_exec = true (--allow-exec) to serve them",
+                executors.join(", ")
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Bearer-token authentication of `ws` clients; none is required without tokens
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AuthConfig {
+    pub tokens: Vec<AuthToken>,
+}
+
+/// A token a client sends as `Authorization: Bearer <token>`
+#[derive(Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AuthToken {
+    pub token: String,
+    /// Names of the tools its sessions may list and call, of those the
+    /// server serves; all of them if unset
+    pub tools: Option<Vec<String>>,
+}
+
+// Keeps the secret out of logs and error messages
+impl std::fmt::Debug for AuthToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AuthToken")
+            .field("token", &"<redacted>")
+            .field("tools", &self.tools)
+            .finish()
+    }
+}
+
+impl AuthConfig {
+    /// Whether clients must send a token
+    pub fn is_required(&self) -> bool {
+        !self.tokens.is_empty()
+    }
+
+    /// The configured token matching `token`
+    pub fn find(&self, token: &str) -> Option<&AuthToken> {
+        // Every token is compared in full, so timing doesn't reveal a prefix
+        self.tokens
+            .iter()
+            .fold(None, |found, t| match constant_time_eq(&t.token, token) {
+                true => found.or(Some(t)),
+                false => found,
+            })
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        for (i, token) in self.tokens.iter().enumerate() {
+            if token.token.trim().is_empty() {
+                return Err(format!("Auth token {} is empty", i + 1));
+            }
+            let unknown: Vec<&str> = token
+                .tools
+                .iter()
+                .flatten()
+                .filter(|name| !TOOL_NAMES.contains(&name.as_str()))
+                .map(|name| name.as_str())
+                .collect();
+            if !unknown.is_empty() {
+                return Err(format!(
+                    "Auth token {} allows unknown tool(s): {}. Available tools: {}",
+                    i + 1,
+                    unknown.join(", "),
+                    TOOL_NAMES.join(", ")
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Whether `a` and `b` are equal, taking as long for any `b` of the same length
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0u8, |diff, (x, y)| diff | (x ^ y))
+            == 0
+}
+
+/// Names in a comma-separated list
+fn split_names(list: &str) -> Vec<String> {
+    list.split(',')
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+/// Whether `host:port` can only be reached from this machine
+fn is_loopback(addr: &str) -> bool {
+    match addr.parse::<SocketAddr>() {
+        Ok(addr) => addr.ip().is_loopback(),
+        Err(_) => addr
+            .rsplit_once(':')
+            .is_some_and(|(host, _)| host.eq_ignore_ascii_case("localhost")),
+    }
+}
+
+impl ServerConfig {
+    /// Layer `other` on top: its set fields win
+    pub fn merge(&mut self, other: ServerConfig) {
+        fn take<T>(field: &mut Option<T>, other: Option<T>) {
+            if other.is_some() {
+                *field = other;
+            }
+        }
+        take(&mut self.version, other.version);
+        take(&mut self.docs_dir, other.docs_dir);
+        take(&mut self.watch_docs, other.watch_docs);
+        take(&mut self.docs_overrides, other.docs_overrides);
+        take(&mut self.overrides_mode, other.overrides_mode);
+        take(&mut self.transport, other.transport);
+        take(&mut self.socket, other.socket);
+        take(&mut self.listen, other.listen);
+        take(&mut self.allowed_origins, other.allowed_origins);
+        take(&mut self.auth, other.auth);
+        take(&mut self.metrics_listen, other.metrics_listen);
+        take(&mut self.record, other.record);
+        take(&mut self.framing, other.framing);
+        take(&mut self.log_level, other.log_level);
+        take(&mut self.log_format, other.log_format);
+        take(&mut self.keepalive, other.keepalive);
+        take(&mut self.drain_timeout, other.drain_timeout);
+        take(&mut self.sync_on_start, other.sync_on_start);
+        take(&mut self.embeddings_url, other.embeddings_url);
+        take(&mut self.embeddings_model, other.embeddings_model);
+    }
+
+    /// Leptos version to select at start, if configured
+    pub fn leptos_version(&self) -> Option<LeptosVersion> {
+        self.version.as_deref().and_then(LeptosVersion::parse)
+    }
+
+    /// Overrides directory to load, if configured or present
+    pub fn overrides_dir(&self) -> Option<PathBuf> {
+        self.docs_overrides.clone().or_else(|| {
+            let dir = Path::new(docs::OVERRIDES_DIR);
+            dir.is_dir().then(|| dir.to_path_buf())
+        })
+    }
+
+    pub fn overrides_mode(&self) -> OverrideMode {
+        self.overrides_mode
+            .as_deref()
+            .and_then(|mode| OverrideMode::parse(mode).ok())
+            .unwrap_or_default()
+    }
+
+    /// Configured framing; detected from the client if unset
+    pub fn framing(&self) -> Framing {
+        self.framing
+            .as_deref()
+            .and_then(|framing| Framing::parse(framing).ok())
+            .unwrap_or(Framing::Auto)
+    }
+
+    pub fn log_format(&self) -> OutputFormat {
+        self.log_format
+            .as_deref()
+            .and_then(|format| OutputFormat::parse(format).ok())
+            .unwrap_or_default()
+    }
+}
+
+impl Config {
+    /// Read a config file; without `path`, `leptos-mcp.toml` in the working
+    /// directory if there is one
+    pub fn from_file(path: Option<&Path>) -> Result<Self, String> {
+        let path = match path {
+            Some(path) => path,
+            None if Path::new(rules::CONFIG_FILE).is_file() => Path::new(rules::CONFIG_FILE),
+            None => return Ok(Self::default()),
+        };
+        let raw = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let mut config: Self =
+            toml::from_str(&raw).map_err(|e| format!("Invalid {}: {}", path.display(), e))?;
+        // Plugin paths are relative to the file that lists them
+        let dir = path.parent().unwrap_or(Path::new(""));
+        for plugin in &mut config.rules.plugins {
+            *plugin = dir.join(&*plugin);
+        }
+        Ok(config)
+    }
+
+    /// Read the config file, then layer the environment over it
+    pub fn load(path: Option<&Path>) -> Result<Self, String> {
+        let mut config = Self::from_file(path)?;
+        config.apply_env();
+        Ok(config)
+    }
+
+    /// Override settings from `LEPTOS_MCP_*` environment variables
+    pub fn apply_env(&mut self) {
+        let var = |name| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+        self.server.merge(ServerConfig {
+            version: var(VERSION_ENV),
+            docs_dir: var(DOCS_DIR_ENV).map(PathBuf::from),
+            docs_overrides: var(DOCS_OVERRIDES_ENV).map(PathBuf::from),
+            transport: var(TRANSPORT_ENV),
+            socket: var(SOCKET_ENV).map(PathBuf::from),
+            listen: var(LISTEN_ENV),
+            metrics_listen: var(METRICS_LISTEN_ENV),
+            framing: var(FRAMING_ENV),
+            log_level: var(LOG_LEVEL_ENV),
+            log_format: var(LOG_FORMAT_ENV),
+            embeddings_url: var(EMBEDDINGS_URL_ENV),
+            embeddings_model: var(EMBEDDINGS_MODEL_ENV),
+            ..ServerConfig::default()
+        });
+        self.tools.merge(ToolsConfig {
+            enabled: var(TOOLS_ENV).as_deref().map(split_names),
+            disabled: var(DISABLED_TOOLS_ENV)
+                .as_deref()
+                .map(split_names)
+                .unwrap_or_default(),
+            allow_writes: var(ALLOW_WRITES_ENV).map(|v| matches!(v.trim(), "1" | "true")),
+            allow_exec: var(ALLOW_EXEC_ENV).map(|v| matches!(v.trim(), "1" | "true")),
+        });
+        if let Some(token) = var(AUTH_TOKEN_ENV) {
+            let auth = self.server.auth.get_or_insert_with(AuthConfig::default);
+            auth.tokens.push(AuthToken { token, tools: None });
+        }
+    }
+
+    /// Reject values that would otherwise be ignored or fail later
+    pub fn validate(&self) -> Result<(), String> {
+        let limits = &self.limits;
+        if limits.max_line_bytes == 0
+            || limits.max_code_bytes == 0
+            || limits.max_project_files == 0
+            || limits.max_project_bytes == 0
+            || limits.max_output_bytes == 0
+            || limits.max_block_chars == 0
+        {
+            return Err("Limits must be greater than zero".to_string());
+        }
+        if limits.call_burst > 0 && limits.calls_per_minute == 0 {
+            return Err("A call burst needs a rate limit (calls_per_minute)".to_string());
+        }
+        let server = &self.server;
+        if let Some(version) = &server.version {
+            if LeptosVersion::parse(version).is_none() {
+                return Err(format!("Unsupported Leptos version '{}'", version));
+            }
+        }
+        if let Some(transport) = &server.transport {
+            if !TRANSPORTS.contains(&transport.as_str()) {
+                return Err(format!(
+                    "Unsupported transport '{}'. Expected one of: {}",
+                    transport,
+                    TRANSPORTS.join(", ")
+                ));
+            }
+            if transport == "unix" && server.socket.is_none() {
+                return Err("The unix transport needs a socket path (--socket)".to_string());
+            }
+            if transport == "ws" && server.listen.is_none() {
+                return Err("The ws transport needs an address to listen on (--listen)".to_string());
+            }
+        }
+        let auth = server.auth.clone().unwrap_or_default();
+        auth.validate()?;
+        if let Some(listen) = &server.listen {
+            // Anyone who can connect can run cargo and build scripts
+            if !is_loopback(listen) && !auth.is_required() {
+                return Err(format!(
+                    "Refusing to listen on {} without authentication: configure server.auth tokens ({}) or bind a loopback address (127.0.0.1, [::1], localhost)",
+                    listen, AUTH_TOKEN_ENV
+                ));
+            }
+        }
+        if let Some(framing) = &server.framing {
+            Framing::parse(framing)?;
+        }
+        if let Some(level) = &server.log_level {
+            if !LOG_LEVELS.contains(&level.as_str()) {
+                return Err(format!(
+                    "Unknown log level '{}'. Expected one of: {}",
+                    level,
+                    LOG_LEVELS.join(", ")
+                ));
+            }
+        }
+        if let Some(format) = &server.log_format {
+            OutputFormat::parse(format)?;
+        }
+        if let Some(url) = &server.embeddings_url {
+            if !url.starts_with("http://") && !url.starts_with("https://") {
+                return Err(format!("Embeddings URL '{}' is not an http(s) URL", url));
+            }
+        }
+        if let Some(dir) = &server.docs_dir {
+            if !dir.is_dir() {
+                return Err(format!("Docs directory {} does not exist", dir.display()));
+            }
+        } else if server.watch_docs == Some(true) {
+            return Err("Watching docs needs a docs directory (--docs-dir)".to_string());
+        }
+        if let Some(dir) = &server.docs_overrides {
+            if !dir.is_dir() {
+                return Err(format!(
+                    "Docs overrides directory {} does not exist",
+                    dir.display()
+                ));
+            }
+        }
+        if let Some(mode) = &server.overrides_mode {
+            OverrideMode::parse(mode)?;
+        }
+        self.tools.validate()?;
+        self.rules.validate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn listening(addr: &str) -> Config {
+        let mut config = Config::default();
+        config.server.transport = Some("ws".to_string());
+        config.server.listen = Some(addr.to_string());
+        config
+    }
+
+    #[test]
+    fn loopback_listen_addresses() {
+        for addr in ["127.0.0.1:8765", "[::1]:8765", "localhost:8765"] {
+            assert!(listening(addr).validate().is_ok(), "{}", addr);
+        }
+        for addr in [
+            "0.0.0.0:8765",
+            "[::]:8765",
+            "192.168.1.2:8765",
+            "example.com:80",
+        ] {
+            assert!(listening(addr).validate().is_err(), "{}", addr);
+        }
+    }
+
+    #[test]
+    fn auth_allows_any_listen_address() {
+        let mut config = listening("0.0.0.0:8765");
+        config.server.auth = Some(AuthConfig {
+            tokens: vec![AuthToken {
+                token: "secret".to_string(),
+                tools: None,
+            }],
+        });
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn auth_tokens_are_checked() {
+        let config: Config = toml::from_str(
+            r#"
+            [server.auth]
+            tokens = [
+                { token = "ci", tools = ["leptos-autofixer"] },
+                { token = "admin" },
+            ]
+            "#,
+        )
+        .unwrap();
+        let auth = config.server.auth.as_ref().unwrap();
+        assert!(auth.find("ci").unwrap().tools.is_some());
+        assert!(auth.find("admin").unwrap().tools.is_none());
+        assert!(auth.find("cI").is_none());
+        assert!(auth.find("").is_none());
+        assert!(!format!("{:?}", auth).contains("admin"));
+        assert!(config.validate().is_ok());
+
+        let config: Config =
+            toml::from_str("[server.auth]\ntokens = [{ token = \"x\", tools = [\"nope\"] }]")
+                .unwrap();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn restricted_tools_intersect() {
+        let names = vec!["leptos-autofixer".to_string(), "apply-fix".to_string()];
+        let restricted = ToolsConfig::default().restricted(&names);
+        assert!(restricted.is_enabled("leptos-autofixer"));
+        assert!(!restricted.is_enabled("get-documentation"));
+        // Still subject to the server's own settings
+        assert!(!restricted.is_enabled("apply-fix"));
+        let some = ToolsConfig {
+            enabled: Some(vec!["get-documentation".to_string()]),
+            ..ToolsConfig::default()
+        };
+        assert!(!some.restricted(&names).is_enabled("leptos-autofixer"));
+    }
+
+    #[test]
+    fn render_preview_needs_allow_exec() {
+        let tools = ToolsConfig::default();
+        assert!(!tools.is_enabled("render-preview"));
+        assert!(tools.is_enabled("check-snippet"));
+        let allowlisted = ToolsConfig {
+            enabled: Some(vec!["render-preview".to_string()]),
+            ..ToolsConfig::default()
+        };
+        assert!(allowlisted.validate().is_err());
+        let allowed = ToolsConfig {
+            allow_exec: Some(true),
+            ..allowlisted
+        };
+        assert!(allowed.validate().is_ok());
+        assert!(allowed.is_enabled("render-preview"));
+    }
+}
@@ -0,0 +1,379 @@
+//! Project context
+//!
+//! What a session knows about the project it is working on: where it lives,
+//! which Leptos version its `Cargo.toml` depends on and which server
+//! integration it uses. Tools fall back to it when a call doesn't say.
+//!
+//! [`Environment`] is the fuller picture `detect-environment` reports: the
+//! exact versions `Cargo.lock` pins and the Leptos features the manifest
+//! turns on.
+//!
+//! Whether the project may call signals as functions follows from the
+//! `nightly` feature; stable projects get only method-call syntax.
+
+use crate::docs::{LeptosVersion, Syntax};
+use crate::generate::Backend;
+use crate::rules::StyleFramework;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml::Value;
+
+/// Crates whose versions `detect-environment` reports
+pub const LEPTOS_CRATES: &[&str] = &[
+    "leptos",
+    "leptos_router",
+    "leptos_meta",
+    "leptos_axum",
+    "leptos_actix",
+];
+
+/// `leptos` features that decide how the app is built
+pub const LEPTOS_FEATURES: &[&str] = &["csr", "ssr", "hydrate", "islands", "nightly"];
+
+/// The project a session is working on
+#[derive(Debug, Clone)]
+pub struct ProjectContext {
+    /// Directory holding the project's `Cargo.toml`
+    pub root: PathBuf,
+    /// `[package] name`, absent for a virtual workspace manifest
+    pub package: Option<String>,
+    /// Leptos version the project uses, if it is one the docs cover
+    pub version: Option<LeptosVersion>,
+    /// Server integration, from `leptos_axum`/`leptos_actix` dependencies
+    pub backend: Option<Backend>,
+    /// Styling frameworks whose lint rules run for the project
+    pub styling: Vec<StyleFramework>,
+    /// Signal call syntax lints, docs and generated code follow
+    pub syntax: Syntax,
+}
+
+impl ProjectContext {
+    /// Read the context from `<root>/Cargo.toml` and its `Cargo.lock`
+    pub fn detect(root: &Path) -> Result<Self, String> {
+        Environment::detect(root).map(|env| env.context())
+    }
+}
+
+/// A project's Leptos dependencies as its manifest and lock file describe them
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct Environment {
+    pub root: PathBuf,
+    pub package: Option<String>,
+    /// `Cargo.lock` the locked versions came from; may be a workspace's
+    pub lockfile: Option<PathBuf>,
+    /// Entries of [`LEPTOS_CRATES`] the project depends on or has locked
+    pub crates: Vec<CrateVersion>,
+    /// One entry per [`LEPTOS_FEATURES`] feature
+    pub features: Vec<FeatureUse>,
+    /// Tailwind, stylers and stylance, if set up
+    pub styling: Vec<StyleFramework>,
+}
+
+/// A Leptos crate's requested and resolved version
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct CrateVersion {
+    pub name: &'static str,
+    /// Listed in `Cargo.toml` rather than only pulled in by another crate
+    pub direct: bool,
+    /// Requirement in `Cargo.toml`; `None` for git/path dependencies
+    pub requirement: Option<String>,
+    /// Version pinned in `Cargo.lock`
+    pub locked: Option<String>,
+}
+
+/// How a `leptos` feature gets enabled
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct FeatureUse {
+    pub name: &'static str,
+    /// Listed in the `leptos` dependency's own `features`, so always on
+    pub always: bool,
+    /// Crate features that forward to it (`ssr = ["leptos/ssr"]`)
+    pub crate_features: Vec<String>,
+}
+
+impl FeatureUse {
+    pub fn is_enabled(&self) -> bool {
+        self.always || !self.crate_features.is_empty()
+    }
+}
+
+impl Environment {
+    /// Read `<root>/Cargo.toml` and the nearest `Cargo.lock` at or above `root`
+    pub fn detect(root: &Path) -> Result<Self, String> {
+        if !root.is_dir() {
+            return Err(format!("Directory {} does not exist", root.display()));
+        }
+        let manifest = Manifest::load(root)?;
+        let lockfile = root
+            .ancestors()
+            .map(|dir| dir.join("Cargo.lock"))
+            .find(|path| path.is_file());
+        let locked = match &lockfile {
+            Some(path) => locked_packages(&read_toml(path)?),
+            None => Vec::new(),
+        };
+
+        let package = manifest
+            .own
+            .get("package")
+            .and_then(|p| p.get("name"))
+            .and_then(Value::as_str)
+            .map(String::from);
+        let crates = LEPTOS_CRATES
+            .iter()
+            .filter_map(|&name| {
+                let dep = manifest.dependency(name);
+                let locked = locked
+                    .iter()
+                    .filter(|(locked, _)| locked == name)
+                    .map(|(_, version)| version.clone())
+                    .next_back();
+                (dep.is_some() || locked.is_some()).then_some(CrateVersion {
+                    name,
+                    direct: dep.is_some(),
+                    requirement: dep.and_then(|dep| dep.requirement),
+                    locked,
+                })
+            })
+            .collect();
+        let features = leptos_features(&manifest);
+        let styling = StyleFramework::detect(
+            root,
+            |name| manifest.dependency(name).is_some(),
+            manifest.tailwind_input(),
+        );
+        Ok(Self {
+            root: root.to_path_buf(),
+            package,
+            lockfile,
+            crates,
+            features,
+            styling,
+        })
+    }
+
+    pub fn crate_version(&self, name: &str) -> Option<&CrateVersion> {
+        self.crates.iter().find(|c| c.name == name)
+    }
+
+    /// Leptos release line, from the locked version or else the requirement
+    pub fn leptos_version(&self) -> Option<LeptosVersion> {
+        let leptos = self.crate_version("leptos")?;
+        leptos
+            .locked
+            .as_deref()
+            .or(leptos.requirement.as_deref())
+            .and_then(|version| {
+                LeptosVersion::parse(version.trim_start_matches(['^', '=', '~', ' ']))
+            })
+    }
+
+    /// Server integration the project depends on
+    pub fn backend(&self) -> Option<Backend> {
+        let direct = |name| self.crate_version(name).is_some_and(|c| c.direct);
+        if direct("leptos_actix") {
+            Some(Backend::Actix)
+        } else if direct("leptos_axum") {
+            Some(Backend::Axum)
+        } else {
+            None
+        }
+    }
+
+    /// Nightly when the `nightly` feature is on in any build
+    pub fn syntax(&self) -> Syntax {
+        let nightly = self
+            .features
+            .iter()
+            .any(|f| f.name == "nightly" && f.is_enabled());
+        if nightly {
+            Syntax::Nightly
+        } else {
+            Syntax::Stable
+        }
+    }
+
+    /// The session context this environment implies
+    pub fn context(&self) -> ProjectContext {
+        ProjectContext {
+            root: self.root.clone(),
+            package: self.package.clone(),
+            version: self.leptos_version(),
+            backend: self.backend(),
+            styling: self.styling.clone(),
+            syntax: self.syntax(),
+        }
+    }
+}
+
+pub(crate) fn read_toml(path: &Path) -> Result<Value, String> {
+    let raw = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    toml::from_str(&raw).map_err(|e| format!("Invalid {}: {}", path.display(), e))
+}
+
+/// `(name, version)` of every `[[package]]` in a lock file
+fn locked_packages(lock: &Value) -> Vec<(String, String)> {
+    lock.get("package")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|package| {
+            let name = package.get("name")?.as_str()?;
+            let version = package.get("version")?.as_str()?;
+            Some((name.to_string(), version.to_string()))
+        })
+        .collect()
+}
+
+/// A project's `Cargo.toml`, with the workspace root manifest it inherits from
+pub(crate) struct Manifest {
+    pub(crate) own: Value,
+    /// Nearest ancestor manifest with a `[workspace]` table, if `own` has none
+    pub(crate) workspace: Option<Value>,
+}
+
+/// What a manifest asks of one dependency
+struct Dependency {
+    /// `None` for git/path dependencies
+    requirement: Option<String>,
+    features: Vec<String>,
+}
+
+impl Manifest {
+    pub(crate) fn load(root: &Path) -> Result<Self, String> {
+        let own = read_toml(&root.join("Cargo.toml"))?;
+        let workspace = if own.get("workspace").is_some() {
+            None
+        } else {
+            root.ancestors()
+                .skip(1)
+                .map(|dir| dir.join("Cargo.toml"))
+                .filter(|path| path.is_file())
+                .filter_map(|path| read_toml(&path).ok())
+                .find(|manifest| manifest.get("workspace").is_some())
+        };
+        Ok(Self { own, workspace })
+    }
+
+    /// `tailwind-input-file` of the cargo-leptos metadata, the package's or
+    /// a workspace project's
+    fn tailwind_input(&self) -> Option<&str> {
+        let package = self
+            .own
+            .get("package")
+            .and_then(|p| p.get("metadata"))
+            .and_then(|m| m.get("leptos"));
+        let workspace = self
+            .own
+            .get("workspace")
+            .and_then(|w| w.get("metadata"))
+            .and_then(|m| m.get("leptos"))
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten();
+        package
+            .into_iter()
+            .chain(workspace)
+            .find_map(|leptos| leptos.get("tailwind-input-file")?.as_str())
+    }
+
+    pub(crate) fn workspace_dependency(&self, name: &str) -> Option<&Value> {
+        self.workspace
+            .as_ref()
+            .unwrap_or(&self.own)
+            .get("workspace")?
+            .get("dependencies")?
+            .get(name)
+    }
+
+    /// `[dependencies]` entry for `name`, following `workspace = true`; a
+    /// virtual manifest's `[workspace.dependencies]` stands in for its members
+    fn dependency(&self, name: &str) -> Option<Dependency> {
+        let Some(entry) = self.own.get("dependencies").and_then(|deps| deps.get(name)) else {
+            return self
+                .workspace
+                .is_none()
+                .then(|| self.workspace_dependency(name))
+                .flatten()
+                .map(Dependency::from_entry);
+        };
+        let mut dep = Dependency::from_entry(entry);
+        if entry.get("workspace").and_then(Value::as_bool) == Some(true) {
+            if let Some(inherited) = self.workspace_dependency(name) {
+                let inherited = Dependency::from_entry(inherited);
+                dep.requirement = inherited.requirement;
+                dep.features.extend(inherited.features);
+            }
+        }
+        Some(dep)
+    }
+}
+
+impl Dependency {
+    fn from_entry(entry: &Value) -> Self {
+        match entry {
+            Value::String(req) => Self {
+                requirement: Some(req.clone()),
+                features: Vec::new(),
+            },
+            _ => Self {
+                requirement: entry
+                    .get("version")
+                    .and_then(Value::as_str)
+                    .map(String::from),
+                features: entry
+                    .get("features")
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(Value::as_str)
+                    .map(String::from)
+                    .collect(),
+            },
+        }
+    }
+}
+
+/// How each of [`LEPTOS_FEATURES`] is turned on in the manifest
+pub(crate) fn leptos_features(manifest: &Manifest) -> Vec<FeatureUse> {
+    let always = manifest
+        .dependency("leptos")
+        .map(|dep| dep.features)
+        .unwrap_or_default();
+    let crate_features: Vec<(&str, Vec<&str>)> = manifest
+        .own
+        .get("features")
+        .and_then(Value::as_table)
+        .into_iter()
+        .flatten()
+        .map(|(feature, enables)| {
+            let enables = enables
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_str)
+                .collect();
+            (feature.as_str(), enables)
+        })
+        .collect();
+
+    LEPTOS_FEATURES
+        .iter()
+        .map(|&name| FeatureUse {
+            name,
+            always: always.iter().any(|feature| feature == name),
+            crate_features: crate_features
+                .iter()
+                .filter(|(_, enables)| {
+                    enables.iter().any(|e| {
+                        *e == format!("leptos/{}", name) || *e == format!("leptos?/{}", name)
+                    })
+                })
+                .map(|(feature, _)| feature.to_string())
+                .collect(),
+        })
+        .collect()
+}
@@ -0,0 +1,893 @@
+//! Dependency advisor
+//!
+//! Vetted crates for the needs Leptos apps keep running into (charts, i18n,
+//! sessions, websockets, ...), each with the release that matches every
+//! supported Leptos version, the Cargo features an SSR app has to forward and
+//! a minimal usage example. `suggest-dependencies` matches a need stated in
+//! plain words against the keywords of [`NEEDS`].
+
+use crate::docs::{similarity, LeptosVersion};
+use crate::generate::Backend;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use LeptosVersion::{V0_6, V0_7, V0_8};
+
+/// A recommended crate
+#[derive(Debug)]
+pub struct Crate {
+    pub name: &'static str,
+    /// What it does for the need
+    pub purpose: &'static str,
+    /// Requirement to use with each Leptos version; a version missing has no
+    /// compatible release
+    pub versions: &'static [(LeptosVersion, &'static str)],
+    /// Cargo features always enabled
+    pub features: &'static [&'static str],
+    /// Features of the crate an SSR app enables from its own feature of the
+    /// same name (`ssr = ["leptos_meta/ssr"]`)
+    pub forwards: &'static [&'static str],
+    /// Only used by the server: `optional = true` and enabled by `ssr`
+    pub server_only: bool,
+    /// Used by tests only: a `[dev-dependencies]` entry
+    pub dev: bool,
+    /// Server integration the crate belongs to, if it is tied to one
+    pub backend: Option<Backend>,
+    /// Extra `Cargo.toml` tables the crate reads
+    pub metadata: Option<&'static str>,
+    /// Usage examples, each for its version and the later ones
+    pub usage: &'static [(LeptosVersion, &'static str)],
+}
+
+/// A need and the crates that cover it
+#[derive(Debug)]
+pub struct Need {
+    pub id: &'static str,
+    pub title: &'static str,
+    /// Words and phrases a request for this need uses
+    pub keywords: &'static [&'static str],
+    /// Advice that applies whichever crate is picked
+    pub note: Option<&'static str>,
+    pub crates: &'static [Crate],
+}
+
+/// A crate as recommended for one Leptos version and backend
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct Advice {
+    pub name: &'static str,
+    pub purpose: &'static str,
+    pub version: &'static str,
+    pub features: &'static [&'static str],
+    /// Lines to add to `Cargo.toml`, under their table headers
+    pub cargo_toml: String,
+    pub usage: &'static str,
+}
+
+const CRATE: Crate = Crate {
+    name: "",
+    purpose: "",
+    versions: &[],
+    features: &[],
+    forwards: &[],
+    server_only: false,
+    dev: false,
+    backend: None,
+    metadata: None,
+    usage: &[],
+};
+
+/// Every need, alphabetically by id
+pub const NEEDS: &[Need] = &[
+    Need {
+        id: "auth-oidc",
+        title: "Sign-in with an OpenID Connect provider",
+        keywords: &["oidc", "openid", "oauth", "sso", "keycloak", "auth0", "single sign-on"],
+        note: Some("The browser runs the authorization code flow with PKCE; pass the access token to server functions and verify it there."),
+        crates: &[Crate {
+            name: "leptos_oidc",
+            purpose: "OIDC login, logout and token refresh with components that render by auth state",
+            versions: &[(V0_6, "0.4"), (V0_7, "0.8"), (V0_8, "0.9")],
+            usage: &[(
+                V0_6,
+                r#"use leptos_oidc::{Auth, AuthParameters, Authenticated, LoginLink, LogoutLink};
+
+#[component]
+fn App() -> impl IntoView {
+    Auth::init(AuthParameters {
+        issuer: "https://id.example.com/realms/app".into(),
+        client_id: "app".into(),
+        redirect_uri: "http://localhost:3000/".into(),
+        post_logout_redirect_uri: "http://localhost:3000/".into(),
+        challenge: Default::default(),
+        scope: None,
+        audience: None,
+    });
+    view! {
+        <Authenticated unauthenticated=|| view! { <LoginLink>"Sign in"</LoginLink> }>
+            <LogoutLink>"Sign out"</LogoutLink>
+        </Authenticated>
+    }
+}"#,
+            )],
+            ..CRATE
+        }],
+    },
+    Need {
+        id: "auth-sessions",
+        title: "Authentication with server-side sessions",
+        keywords: &["auth", "authentication", "login", "logout", "session", "sessions", "cookie", "user accounts", "password"],
+        note: Some("Read the session in server functions with the backend's `extract()`; never trust a user id sent from the client. Hash passwords with `argon2`."),
+        crates: &[
+            Crate {
+                name: "axum-login",
+                purpose: "Login/logout and the current user as an extractor, on top of tower-sessions",
+                versions: &[(V0_6, "0.15"), (V0_7, "0.16"), (V0_8, "0.17")],
+                server_only: true,
+                backend: Some(Backend::Axum),
+                usage: &[(
+                    V0_6,
+                    r#"// main.rs: the session layer, then the auth layer over your `AuthnBackend`
+use axum_login::tower_sessions::{MemoryStore, SessionManagerLayer};
+use axum_login::AuthManagerLayerBuilder;
+
+let session_layer = SessionManagerLayer::new(MemoryStore::default());
+let auth_layer = AuthManagerLayerBuilder::new(backend, session_layer).build();
+let app = Router::new()
+    .leptos_routes(&leptos_options, routes, App)
+    .layer(auth_layer)
+    .with_state(leptos_options);
+
+// A server function reads the session from the request
+#[server]
+pub async fn current_user() -> Result<Option<String>, ServerFnError> {
+    let auth: axum_login::AuthSession<Backend> = leptos_axum::extract().await?;
+    Ok(auth.user.map(|user| user.name))
+}"#,
+                )],
+                ..CRATE
+            },
+            Crate {
+                name: "tower-sessions",
+                purpose: "Session storage behind axum-login; swap MemoryStore for a database store in production",
+                versions: &[(V0_6, "0.12"), (V0_7, "0.13"), (V0_8, "0.14")],
+                server_only: true,
+                backend: Some(Backend::Axum),
+                usage: &[(
+                    V0_6,
+                    r#"use tower_sessions::{Expiry, MemoryStore, SessionManagerLayer};
+use time::Duration;
+
+let session_layer = SessionManagerLayer::new(MemoryStore::default())
+    .with_secure(true)
+    .with_expiry(Expiry::OnInactivity(Duration::days(1)));"#,
+                )],
+                ..CRATE
+            },
+            Crate {
+                name: "actix-identity",
+                purpose: "The logged-in user id, stored in the session",
+                versions: &[(V0_6, "0.8"), (V0_7, "0.8"), (V0_8, "0.8")],
+                server_only: true,
+                backend: Some(Backend::Actix),
+                usage: &[(
+                    V0_6,
+                    r#"// main.rs: identity over a cookie session
+use actix_identity::IdentityMiddleware;
+use actix_session::{storage::CookieSessionStore, SessionMiddleware};
+
+App::new()
+    .wrap(IdentityMiddleware::default())
+    .wrap(SessionMiddleware::new(CookieSessionStore::default(), key.clone()))
+
+// A server function reads the identity from the request
+#[server]
+pub async fn current_user() -> Result<Option<String>, ServerFnError> {
+    let identity: Option<actix_identity::Identity> = leptos_actix::extract().await?;
+    Ok(identity.and_then(|id| id.id().ok()))
+}"#,
+                )],
+                ..CRATE
+            },
+            Crate {
+                name: "actix-session",
+                purpose: "Session storage behind actix-identity",
+                versions: &[(V0_6, "0.10"), (V0_7, "0.10"), (V0_8, "0.10")],
+                features: &["cookie-session"],
+                server_only: true,
+                backend: Some(Backend::Actix),
+                usage: &[(
+                    V0_6,
+                    r#"use actix_session::{storage::CookieSessionStore, SessionMiddleware};
+use actix_web::cookie::Key;
+
+let key = Key::generate();
+App::new().wrap(SessionMiddleware::new(CookieSessionStore::default(), key.clone()))"#,
+                )],
+                ..CRATE
+            },
+        ],
+    },
+    Need {
+        id: "browser-apis",
+        title: "Browser APIs as signals",
+        keywords: &["browser", "window", "mouse", "scroll", "resize", "media query", "clipboard", "geolocation", "intersection observer", "local storage", "interval", "debounce"],
+        note: Some("The functions return signals that stay at their initial value during SSR, so markup depending on them doesn't break hydration."),
+        crates: &[Crate {
+            name: "leptos-use",
+            purpose: "Reactive wrappers for window, element, sensor, storage and timing APIs",
+            versions: &[(V0_6, "0.13"), (V0_7, "0.15"), (V0_8, "0.16")],
+            forwards: &["ssr"],
+            usage: &[(
+                V0_6,
+                r#"use leptos_use::{use_mouse, UseMouseReturn};
+
+#[component]
+fn Pointer() -> impl IntoView {
+    let UseMouseReturn { x, y, .. } = use_mouse();
+    view! { <p>{move || format!("{}, {}", x.get(), y.get())}</p> }
+}"#,
+            )],
+            ..CRATE
+        }],
+    },
+    Need {
+        id: "charts",
+        title: "Charts",
+        keywords: &["chart", "charts", "graph", "plot", "line chart", "bar chart", "visualization", "dashboard"],
+        note: None,
+        crates: &[Crate {
+            name: "leptos-chartistry",
+            purpose: "Line and bar charts rendered as SVG, reactive to their data signal",
+            versions: &[(V0_6, "0.1"), (V0_7, "0.2"), (V0_8, "0.2")],
+            usage: &[(
+                V0_6,
+                r#"use leptos_chartistry::*;
+
+#[derive(Clone, PartialEq)]
+struct Point {
+    x: f64,
+    y: f64,
+}
+
+#[component]
+fn Sales() -> impl IntoView {
+    let data = Signal::derive(|| vec![Point { x: 1.0, y: 2.0 }, Point { x: 2.0, y: 3.5 }]);
+    let series = Series::new(|p: &Point| p.x).line(Line::new(|p: &Point| p.y).with_name("sales"));
+    view! {
+        <Chart
+            aspect_ratio=AspectRatio::from_outer_height(300.0, 1.6)
+            series=series
+            data=data
+        />
+    }
+}"#,
+            )],
+            ..CRATE
+        }],
+    },
+    Need {
+        id: "data-fetching",
+        title: "Cached data fetching",
+        keywords: &["cache", "caching", "query", "queries", "refetch", "invalidate", "stale", "react query", "swr"],
+        note: Some("Plain resources already dedupe SSR loads; a query cache adds sharing between components, invalidation and refetch on focus."),
+        crates: &[
+            Crate {
+                name: "leptos_query",
+                purpose: "Query cache with invalidation and background refetching",
+                versions: &[(V0_6, "0.5")],
+                forwards: &["ssr", "hydrate"],
+                usage: &[(
+                    V0_6,
+                    r#"use leptos_query::*;
+
+fn todo_query() -> QueryScope<u32, Option<Todo>> {
+    create_query(get_todo, QueryOptions::default())
+}
+
+#[component]
+fn TodoView(id: Signal<u32>) -> impl IntoView {
+    let QueryResult { data, .. } = todo_query().use_query(move || id.get());
+    view! { <p>{move || data.get().flatten().map(|todo| todo.title)}</p> }
+}
+
+// In the root component
+provide_query_client();"#,
+                )],
+                ..CRATE
+            },
+            Crate {
+                name: "leptos-fetch",
+                purpose: "Query cache with invalidation and background refetching",
+                versions: &[(V0_7, "0.3"), (V0_8, "0.4")],
+                usage: &[(
+                    V0_7,
+                    r#"use leptos_fetch::QueryClient;
+
+// In the root component
+QueryClient::new().provide();
+
+#[component]
+fn TodoView(id: Signal<u32>) -> impl IntoView {
+    let client: QueryClient = expect_context();
+    let todo = client.resource(get_todo, move || id.get());
+    view! {
+        <Suspense>{move || Suspend::new(async move { todo.await.map(|todo| todo.title) })}</Suspense>
+    }
+}"#,
+                )],
+                ..CRATE
+            },
+        ],
+    },
+    Need {
+        id: "database",
+        title: "Database access",
+        keywords: &["database", "db", "sql", "postgres", "postgresql", "sqlite", "mysql", "orm", "migrations"],
+        note: Some("Create the pool in `main` and hand it to server functions as context (`leptos_routes_with_context`), or through the router state."),
+        crates: &[Crate {
+            name: "sqlx",
+            purpose: "Async SQL with compile-time checked queries and migrations",
+            versions: &[(V0_6, "0.8"), (V0_7, "0.8"), (V0_8, "0.8")],
+            features: &["runtime-tokio", "sqlite"],
+            server_only: true,
+            usage: &[(
+                V0_6,
+                r#"#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+pub struct Todo {
+    pub id: i64,
+    pub title: String,
+}
+
+#[server]
+pub async fn list_todos() -> Result<Vec<Todo>, ServerFnError> {
+    let pool = expect_context::<sqlx::SqlitePool>();
+    let todos = sqlx::query_as::<_, Todo>("SELECT id, title FROM todos")
+        .fetch_all(&pool)
+        .await?;
+    Ok(todos)
+}"#,
+            )],
+            ..CRATE
+        }],
+    },
+    Need {
+        id: "date-time",
+        title: "Dates and times",
+        keywords: &["date", "time", "datetime", "timestamp", "timezone", "calendar", "duration"],
+        note: Some("Don't read the clock while rendering: the server and the browser see different times and hydration breaks. Read it in an effect or pass it from the server."),
+        crates: &[Crate {
+            name: "chrono",
+            purpose: "Dates, times and time zones; `wasmbind` reads the clock in the browser",
+            versions: &[(V0_6, "0.4"), (V0_7, "0.4"), (V0_8, "0.4")],
+            features: &["serde", "wasmbind"],
+            usage: &[(
+                V0_6,
+                r#"use chrono::{DateTime, Utc};
+
+#[component]
+fn Posted(at: DateTime<Utc>) -> impl IntoView {
+    view! { <time datetime=at.to_rfc3339()>{at.format("%B %e, %Y").to_string()}</time> }
+}"#,
+            )],
+            ..CRATE
+        }],
+    },
+    Need {
+        id: "form-validation",
+        title: "Form validation",
+        keywords: &["validation", "validate", "form validation", "constraints", "email validation"],
+        note: Some("Validate in the server function too: browser checks can be bypassed. `generate-form` scaffolds both sides."),
+        crates: &[Crate {
+            name: "validator",
+            purpose: "Derive-based field validation that runs the same on the client and in server functions",
+            versions: &[(V0_6, "0.20"), (V0_7, "0.20"), (V0_8, "0.20")],
+            features: &["derive"],
+            usage: &[
+                (
+                    V0_6,
+                    r#"use validator::Validate;
+
+#[derive(Clone, Serialize, Deserialize, Validate)]
+pub struct SignUp {
+    #[validate(email)]
+    email: String,
+    #[validate(length(min = 8))]
+    password: String,
+}
+
+#[server]
+pub async fn sign_up(form: SignUp) -> Result<(), ServerFnError> {
+    form.validate()
+        .map_err(|e| ServerFnError::ServerError(e.to_string()))?;
+    Ok(())
+}"#,
+                ),
+                (
+                    V0_7,
+                    r#"use validator::Validate;
+
+#[derive(Clone, Serialize, Deserialize, Validate)]
+pub struct SignUp {
+    #[validate(email)]
+    email: String,
+    #[validate(length(min = 8))]
+    password: String,
+}
+
+#[server]
+pub async fn sign_up(form: SignUp) -> Result<(), ServerFnError> {
+    form.validate().map_err(|e| ServerFnError::new(e.to_string()))?;
+    Ok(())
+}"#,
+                ),
+            ],
+            ..CRATE
+        }],
+    },
+    Need {
+        id: "head",
+        title: "Head management: title, meta tags, stylesheets",
+        keywords: &["head", "title", "meta", "meta tags", "seo", "open graph", "stylesheet", "favicon", "document title"],
+        note: Some("With SSR, render `<MetaTags/>` in the shell's `<head>` (0.7+) so the tags are in the server's HTML."),
+        crates: &[Crate {
+            name: "leptos_meta",
+            purpose: "`<Title>`, `<Meta>`, `<Stylesheet>` and `<Link>` components that render into the document head",
+            versions: &[(V0_6, "0.6"), (V0_7, "0.7"), (V0_8, "0.8")],
+            forwards: &["ssr"],
+            usage: &[(
+                V0_6,
+                r#"use leptos_meta::{provide_meta_context, Meta, Title};
+
+#[component]
+fn App() -> impl IntoView {
+    provide_meta_context();
+    view! {
+        <Title text="My App"/>
+        <Meta name="description" content="What the app does"/>
+    }
+}"#,
+            )],
+            ..CRATE
+        }],
+    },
+    Need {
+        id: "http-client",
+        title: "Calling external HTTP APIs",
+        keywords: &["http", "fetch", "rest", "api client", "request", "external api", "json api"],
+        note: Some("For your own backend use server functions instead. A third-party API with a secret key belongs in a server function too."),
+        crates: &[
+            Crate {
+                name: "gloo-net",
+                purpose: "`fetch` from the browser",
+                versions: &[(V0_6, "0.6"), (V0_7, "0.6"), (V0_8, "0.6")],
+                usage: &[
+                    (
+                        V0_6,
+                        r#"let users = create_local_resource(|| (), |_| async {
+    gloo_net::http::Request::get("https://api.example.com/users")
+        .send()
+        .await?
+        .json::<Vec<User>>()
+        .await
+});"#,
+                    ),
+                    (
+                        V0_7,
+                        r#"let users = LocalResource::new(|| async {
+    gloo_net::http::Request::get("https://api.example.com/users")
+        .send()
+        .await?
+        .json::<Vec<User>>()
+        .await
+});"#,
+                    ),
+                ],
+                ..CRATE
+            },
+            Crate {
+                name: "reqwest",
+                purpose: "HTTP client for server functions",
+                versions: &[(V0_6, "0.12"), (V0_7, "0.12"), (V0_8, "0.12")],
+                features: &["json"],
+                server_only: true,
+                usage: &[(
+                    V0_6,
+                    r#"#[server]
+pub async fn weather(city: String) -> Result<Forecast, ServerFnError> {
+    let url = format!("https://api.example.com/weather?city={}", city);
+    Ok(reqwest::get(url).await?.json::<Forecast>().await?)
+}"#,
+                )],
+                ..CRATE
+            },
+        ],
+    },
+    Need {
+        id: "i18n",
+        title: "Internationalization",
+        keywords: &["i18n", "internationalization", "translation", "translations", "localization", "l10n", "locale", "languages", "multilingual"],
+        note: Some("Translations live in `locales/<locale>.json`; keys are checked at compile time."),
+        crates: &[Crate {
+            name: "leptos_i18n",
+            purpose: "Compile-time checked translations with a locale context, `t!` macro and locale switching",
+            versions: &[(V0_6, "0.4"), (V0_7, "0.5"), (V0_8, "0.5")],
+            forwards: &["ssr", "hydrate"],
+            metadata: Some("[package.metadata.leptos-i18n]\ndefault = \"en\"\nlocales = [\"en\", \"fr\"]\n"),
+            usage: &[
+                (
+                    V0_6,
+                    r#"leptos_i18n::load_locales!();
+use crate::i18n::*;
+
+#[component]
+fn App() -> impl IntoView {
+    provide_i18n_context();
+    view! { <Greeting/> }
+}
+
+#[component]
+fn Greeting() -> impl IntoView {
+    let i18n = use_i18n();
+    view! { <p>{t!(i18n, hello_world)}</p> }
+}"#,
+                ),
+                (
+                    V0_7,
+                    r#"leptos_i18n::load_locales!();
+use crate::i18n::*;
+
+#[component]
+fn App() -> impl IntoView {
+    view! {
+        <I18nContextProvider>
+            <Greeting/>
+        </I18nContextProvider>
+    }
+}
+
+#[component]
+fn Greeting() -> impl IntoView {
+    let i18n = use_i18n();
+    view! { <p>{t!(i18n, hello_world)}</p> }
+}"#,
+                ),
+            ],
+            ..CRATE
+        }],
+    },
+    Need {
+        id: "icons",
+        title: "Icons",
+        keywords: &["icon", "icons", "svg icons", "font awesome", "heroicons", "lucide"],
+        note: None,
+        crates: &[
+            Crate {
+                name: "leptos_icons",
+                purpose: "`<Icon>` component rendering an icon as inline SVG",
+                versions: &[(V0_6, "0.3"), (V0_7, "0.4"), (V0_8, "0.6")],
+                usage: &[(
+                    V0_6,
+                    r#"use leptos_icons::Icon;
+
+view! { <Icon icon=icondata::LuTrash2 width="1em" height="1em"/> }"#,
+                )],
+                ..CRATE
+            },
+            Crate {
+                name: "icondata",
+                purpose: "The icon sets (Lucide, Heroicons, Font Awesome, ...) `<Icon>` renders",
+                versions: &[(V0_6, "0.3"), (V0_7, "0.5"), (V0_8, "0.6")],
+                usage: &[(V0_6, "let trash = icondata::LuTrash2;")],
+                ..CRATE
+            },
+        ],
+    },
+    Need {
+        id: "markdown",
+        title: "Rendering markdown",
+        keywords: &["markdown", "md", "commonmark", "rich text", "blog posts"],
+        note: Some("Sanitize markdown users wrote (`ammonia`) before rendering it with `inner_html`."),
+        crates: &[Crate {
+            name: "pulldown-cmark",
+            purpose: "CommonMark parser and HTML renderer",
+            versions: &[(V0_6, "0.13"), (V0_7, "0.13"), (V0_8, "0.13")],
+            usage: &[(
+                V0_6,
+                r#"use pulldown_cmark::{html, Parser};
+
+#[component]
+fn Markdown(source: String) -> impl IntoView {
+    let mut rendered = String::new();
+    html::push_html(&mut rendered, Parser::new(&source));
+    view! { <div inner_html=rendered></div> }
+}"#,
+            )],
+            ..CRATE
+        }],
+    },
+    Need {
+        id: "stores",
+        title: "Nested reactive state",
+        keywords: &["store", "stores", "state management", "global state", "nested state", "redux"],
+        note: Some("Signals in context cover most shared state; a store pays off for nested structs and lists edited field by field."),
+        crates: &[Crate {
+            name: "reactive_stores",
+            purpose: "`#[derive(Store)]` state with per-field reactivity",
+            versions: &[(V0_7, "0.1"), (V0_8, "0.2")],
+            usage: &[(
+                V0_7,
+                r#"use reactive_stores::Store;
+
+#[derive(Store, Clone, Default)]
+struct State {
+    count: i32,
+}
+
+#[component]
+fn Counter() -> impl IntoView {
+    let state = Store::new(State::default());
+    view! {
+        <button on:click=move |_| *state.count().write() += 1>
+            {move || state.count().get()}
+        </button>
+    }
+}"#,
+            )],
+            ..CRATE
+        }],
+    },
+    Need {
+        id: "testing",
+        title: "Component tests in the browser",
+        keywords: &["test", "tests", "testing", "unit test", "browser test", "wasm test"],
+        note: Some("Run with `wasm-pack test --headless --firefox`. `generate-tests` writes the harness for your components."),
+        crates: &[Crate {
+            name: "wasm-bindgen-test",
+            purpose: "Test runner for tests that mount components in a headless browser",
+            versions: &[(V0_6, "0.3"), (V0_7, "0.3"), (V0_8, "0.3")],
+            dev: true,
+            usage: &[
+                (
+                    V0_6,
+                    r#"use wasm_bindgen_test::*;
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn shows_greeting() {
+    leptos::mount_to_body(|| view! { <p id="greeting">"Hello"</p> });
+    let document = leptos::document();
+    let greeting = document.get_element_by_id("greeting").unwrap();
+    assert_eq!(greeting.text_content().unwrap(), "Hello");
+}"#,
+                ),
+                (
+                    V0_7,
+                    r#"use wasm_bindgen_test::*;
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn shows_greeting() {
+    leptos::mount::mount_to_body(|| view! { <p id="greeting">"Hello"</p> });
+    let document = leptos::prelude::document();
+    let greeting = document.get_element_by_id("greeting").unwrap();
+    assert_eq!(greeting.text_content().unwrap(), "Hello");
+}"#,
+                ),
+            ],
+            ..CRATE
+        }],
+    },
+    Need {
+        id: "ui-components",
+        title: "UI component library",
+        keywords: &["ui", "component library", "components", "design system", "buttons", "modal", "dialog", "table", "widgets"],
+        note: None,
+        crates: &[Crate {
+            name: "thaw",
+            purpose: "Fluent-style components: buttons, inputs, dialogs, tables, menus",
+            versions: &[(V0_6, "0.3"), (V0_7, "0.4")],
+            forwards: &["ssr", "hydrate"],
+            usage: &[
+                (
+                    V0_6,
+                    r#"use thaw::*;
+
+view! {
+    <Button variant=ButtonVariant::Primary on_click=move |_| save()>"Save"</Button>
+}"#,
+                ),
+                (
+                    V0_7,
+                    r#"use thaw::*;
+
+view! {
+    <ConfigProvider>
+        <Button appearance=ButtonAppearance::Primary on_click=move |_| save()>"Save"</Button>
+    </ConfigProvider>
+}"#,
+                ),
+            ],
+            ..CRATE
+        }],
+    },
+    Need {
+        id: "websockets",
+        title: "WebSockets",
+        keywords: &["websocket", "websockets", "ws", "realtime", "real-time", "live updates", "push", "chat"],
+        note: Some("On 0.8, a server function with the `Websocket` protocol streams both ways without a separate endpoint; see `get-snippet websocket-server-signals`."),
+        crates: &[
+            Crate {
+                name: "leptos-use",
+                purpose: "`use_websocket`: a reactive client for any WebSocket server",
+                versions: &[(V0_6, "0.13"), (V0_7, "0.15"), (V0_8, "0.16")],
+                forwards: &["ssr"],
+                usage: &[
+                    (
+                        V0_6,
+                        r#"use leptos_use::{use_websocket, UseWebsocketReturn};
+
+let UseWebsocketReturn { message, send, .. } = use_websocket("wss://echo.example.com");
+view! {
+    <button on:click=move |_| send("ping")>"Send"</button>
+    <p>{move || message.get()}</p>
+}"#,
+                    ),
+                    (
+                        V0_7,
+                        r#"use codee::string::FromToStringCodec;
+use leptos_use::{use_websocket, UseWebSocketReturn};
+
+let UseWebSocketReturn { message, send, .. } =
+    use_websocket::<String, String, FromToStringCodec>("wss://echo.example.com");
+view! {
+    <button on:click=move |_| send(&"ping".to_string())>"Send"</button>
+    <p>{move || message.get()}</p>
+}"#,
+                    ),
+                ],
+                ..CRATE
+            },
+            Crate {
+                name: "codee",
+                purpose: "Message codecs for `use_websocket` (strings, JSON, binary)",
+                versions: &[(V0_7, "0.2"), (V0_8, "0.3")],
+                features: &["json_serde"],
+                usage: &[(V0_7, "use codee::string::JsonSerdeCodec;")],
+                ..CRATE
+            },
+        ],
+    },
+];
+
+impl Crate {
+    /// Requirement to use with `version`, if a release supports it
+    pub fn version_for(&self, version: LeptosVersion) -> Option<&'static str> {
+        self.versions
+            .iter()
+            .find(|(v, _)| *v == version)
+            .map(|(_, requirement)| *requirement)
+    }
+
+    /// The usage example for `version`: the latest one written for it or before
+    pub fn usage_for(&self, version: LeptosVersion) -> &'static str {
+        self.usage
+            .iter()
+            .rfind(|(since, _)| *since <= version)
+            .map_or("", |(_, usage)| *usage)
+    }
+
+    /// `Cargo.toml` lines depending on `requirement` of this crate
+    pub fn cargo_toml(&self, requirement: &str) -> String {
+        let mut fields = vec![format!("version = \"{}\"", requirement)];
+        if !self.features.is_empty() {
+            let quoted: Vec<String> = self.features.iter().map(|f| format!("\"{}\"", f)).collect();
+            fields.push(format!("features = [{}]", quoted.join(", ")));
+        }
+        if self.server_only {
+            fields.push("optional = true".to_string());
+        }
+        let entry = if fields.len() == 1 {
+            format!("{} = \"{}\"", self.name, requirement)
+        } else {
+            format!("{} = {{ {} }}", self.name, fields.join(", "))
+        };
+        let table = if self.dev {
+            "[dev-dependencies]"
+        } else {
+            "[dependencies]"
+        };
+        let mut toml = format!("{}\n{}\n", table, entry);
+
+        let mut features: Vec<String> = self
+            .forwards
+            .iter()
+            .map(|feature| format!("{} = [\"{}/{}\"]", feature, self.name, feature))
+            .collect();
+        if self.server_only {
+            features.push(format!("ssr = [\"dep:{}\"]", self.name));
+        }
+        if !features.is_empty() {
+            toml.push_str("\n[features]\n# Add to the app's existing features\n");
+            toml.push_str(&features.join("\n"));
+            toml.push('\n');
+        }
+        if let Some(metadata) = self.metadata {
+            toml.push('\n');
+            toml.push_str(metadata);
+        }
+        toml
+    }
+
+    /// This crate as recommended for `version`, if a release supports it
+    pub fn advice(&self, version: LeptosVersion) -> Option<Advice> {
+        let requirement = self.version_for(version)?;
+        Some(Advice {
+            name: self.name,
+            purpose: self.purpose,
+            version: requirement,
+            features: self.features,
+            cargo_toml: self.cargo_toml(requirement),
+            usage: self.usage_for(version),
+        })
+    }
+}
+
+impl Need {
+    /// Crates for `version`, leaving out ones for the other backend
+    pub fn advice(&self, version: LeptosVersion, backend: Backend) -> Vec<Advice> {
+        self.crates
+            .iter()
+            .filter(|c| c.backend.is_none_or(|b| b == backend))
+            .filter_map(|c| c.advice(version))
+            .collect()
+    }
+}
+
+/// Similarity at which a word of the request counts as a keyword's word
+const WORD_MATCH: f64 = 0.8;
+
+/// A need matches when it scores at least this share of the best match
+const RELATIVE_MATCH: f64 = 0.6;
+
+/// Needs the request describes, best first
+pub fn find(request: &str, limit: usize) -> Vec<&'static Need> {
+    let lower = request.to_lowercase();
+    let words: Vec<&str> = lower
+        .split(|c: char| !c.is_alphanumeric() && c != '-')
+        .filter(|w| !w.is_empty())
+        .collect();
+    let mut scored: Vec<(&Need, f64)> = NEEDS
+        .iter()
+        .map(|need| (need, score(need, &words)))
+        .filter(|(_, score)| *score > 0.0)
+        .collect();
+    scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+    let best = scored.first().map_or(0.0, |(_, score)| *score);
+    scored
+        .into_iter()
+        .filter(|(_, score)| *score >= best * RELATIVE_MATCH)
+        .take(limit)
+        .map(|(need, _)| need)
+        .collect()
+}
+
+/// Words of keywords the request contains, all of a phrase's words needed
+fn score(need: &Need, words: &[&str]) -> f64 {
+    let contains = |word: &str| words.iter().any(|w| similarity(w, word) >= WORD_MATCH);
+    let id_words: Vec<&str> = need.id.split('-').collect();
+    let mut matched: Vec<&str> = Vec::new();
+    for phrase in need.keywords.iter().copied().chain([need.id]) {
+        let parts: Vec<&str> = if phrase == need.id {
+            id_words.clone()
+        } else {
+            phrase.split_whitespace().collect()
+        };
+        if parts.iter().all(|part| contains(part)) {
+            matched.extend(parts);
+        }
+    }
+    matched.sort_unstable();
+    matched.dedup();
+    matched.len() as f64
+}
@@ -0,0 +1,139 @@
+//! Project build diagnostics
+//!
+//! Runs `cargo check` in a project directory and reports rustc's diagnostics
+//! by file. Errors matching an [`explain`] pattern carry its explanation, fix
+//! and documentation section, so agents get the Leptos context without a
+//! second call.
+
+use crate::explain;
+use crate::progress::Progress;
+use crate::sandbox;
+use schemars::JsonSchema;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::path::Path;
+use std::time::Duration;
+
+/// Leptos context for a diagnostic, from the explain-error patterns
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct Hint {
+    pub pattern: &'static str,
+    pub title: &'static str,
+    pub explanation: &'static str,
+    pub fix: &'static str,
+    /// Documentation section, for get-documentation
+    pub doc: &'static str,
+}
+
+/// A rustc diagnostic from the project's own sources
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct Diagnostic {
+    /// `error` or `warning`
+    pub level: String,
+    /// rustc error code such as `E0277`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    pub message: String,
+    /// Path relative to the workspace root, as rustc reports it
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+    pub rendered: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<Hint>,
+}
+
+/// Outcome of checking a project
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct Report {
+    pub success: bool,
+    pub errors: usize,
+    pub warnings: usize,
+    pub diagnostics: Vec<Diagnostic>,
+    /// Diagnostics left out because they are in other files than those asked for
+    pub filtered_out: usize,
+}
+
+/// Run `cargo check` in `root` and collect its diagnostics
+///
+/// `features` are passed to `--features`. With `files`, only diagnostics
+/// whose primary span is in one of them are reported; a file matches by its
+/// trailing path components, so `src/app.rs` and `app.rs` both work.
+pub fn check(
+    root: &Path,
+    files: &[String],
+    features: &[String],
+    timeout: Duration,
+    progress: &Progress,
+) -> Result<Report, String> {
+    if !root.join("Cargo.toml").is_file() {
+        return Err(format!("No Cargo.toml in {}", root.display()));
+    }
+    let mut args = Vec::new();
+    if !features.is_empty() {
+        args.push("--features".to_string());
+        args.push(features.join(","));
+    }
+    let run = sandbox::cargo("check", root, &args, None, timeout, progress)?;
+
+    // Targets sharing a file (lib and bin) report its diagnostics once each
+    let mut seen = HashSet::new();
+    let all: Vec<Diagnostic> = run
+        .messages
+        .iter()
+        .filter_map(diagnostic)
+        .filter(|d| seen.insert(d.rendered.clone()))
+        .collect();
+    if !run.success && all.is_empty() {
+        return Err(format!("cargo check failed:\n{}", run.stderr.trim()));
+    }
+
+    let total = all.len();
+    let diagnostics: Vec<Diagnostic> = all
+        .into_iter()
+        .filter(|d| files.is_empty() || files.iter().any(|f| same_file(&d.file, f)))
+        .collect();
+    let count = |level: &str| diagnostics.iter().filter(|d| d.level == level).count();
+    Ok(Report {
+        success: run.success,
+        errors: count("error"),
+        warnings: count("warning"),
+        filtered_out: total - diagnostics.len(),
+        diagnostics,
+    })
+}
+
+fn diagnostic(message: &Value) -> Option<Diagnostic> {
+    let spans = message["spans"].as_array()?;
+    let primary = spans.iter().find(|s| s["is_primary"] == true)?;
+    let rendered = message["rendered"].as_str().unwrap_or_default().to_string();
+    let level = message["level"].as_str().unwrap_or("error").to_string();
+    let hint = (level == "error")
+        .then(|| explain::pattern(&rendered))
+        .flatten()
+        .map(|p| Hint {
+            pattern: p.id,
+            title: p.title,
+            explanation: p.explanation,
+            fix: p.fix,
+            doc: p.doc,
+        });
+    Some(Diagnostic {
+        level,
+        code: message["code"]["code"].as_str().map(String::from),
+        message: message["message"].as_str().unwrap_or_default().to_string(),
+        file: primary["file_name"].as_str()?.to_string(),
+        line: primary["line_start"].as_u64()? as usize,
+        column: primary["column_start"].as_u64()? as usize,
+        rendered,
+        hint,
+    })
+}
+
+/// Whether `reported` and `requested` name the same file, one possibly a suffix of the other
+fn same_file(reported: &str, requested: &str) -> bool {
+    let requested = Path::new(requested.trim_start_matches("./"));
+    let reported = Path::new(reported);
+    reported.ends_with(requested) || requested.ends_with(reported)
+}
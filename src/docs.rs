@@ -1,93 +1,1458 @@
 //! Leptos documentation content
 //!
-//! Contains documentation sections for the Leptos framework.
+//! Contains documentation sections for the Leptos framework, and for its
+//! companion crates under namespaced paths like `router/nested-routes`.
+//!
+//! Translations live under `docs/translations/<lang>/`, one page per section
+//! path. They follow the latest Leptos version and keep the English page's
+//! heading outline, so `section#heading` addresses work in every language;
+//! sections without a translation are served in English.
+//!
+//! Teams can add their own pages in a `docs-overrides/` directory, keyed by
+//! section path: a page for a built-in path replaces or extends that section,
+//! and any other path becomes a new section.
+
+use crate::generate::Backend;
+use crate::markdown::{parse_headings, search_chunks, slugify, Heading};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock, RwLock};
+use std::time::{Duration, SystemTime};
+use tokio::sync::broadcast;
+
+/// Scheme of the MCP resource URIs sections are served under
+pub const RESOURCE_SCHEME: &str = "leptos-docs";
+
+/// Leptos release line a documentation variant targets
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum LeptosVersion {
+    V0_6,
+    V0_7,
+    #[default]
+    V0_8,
+}
+
+impl LeptosVersion {
+    pub const ALL: [LeptosVersion; 3] = [Self::V0_6, Self::V0_7, Self::V0_8];
+
+    /// Parse a version string like "0.6", "v0.7" or "0.8.2"
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim().trim_start_matches('v');
+        Self::ALL
+            .into_iter()
+            .find(|v| value == v.as_str() || value.starts_with(&format!("{}.", v.as_str())))
+    }
+
+    /// Position in `ALL`
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::V0_6 => "0.6",
+            Self::V0_7 => "0.7",
+            Self::V0_8 => "0.8",
+        }
+    }
+}
+
+/// Language documentation is served in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    #[serde(rename = "en")]
+    English,
+    #[serde(rename = "zh")]
+    Chinese,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Self::English, Self::Chinese];
+
+    /// Parse a language tag like "zh", "zh-CN" or "en-US"
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let tag = value.trim().to_lowercase();
+        let primary = tag.split(['-', '_']).next().unwrap_or("");
+        Self::ALL
+            .into_iter()
+            .find(|l| l.as_str() == primary)
+            .ok_or_else(|| {
+                let known: Vec<&str> = Self::ALL.iter().map(|l| l.as_str()).collect();
+                format!(
+                    "Unsupported language '{}'. Expected one of: {}",
+                    value,
+                    known.join(", ")
+                )
+            })
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::English => "en",
+            Self::Chinese => "zh",
+        }
+    }
+
+    /// The language's name in itself
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::English => "English",
+            Self::Chinese => "简体中文",
+        }
+    }
+}
+
+/// Whether a project calls signals as functions
+///
+/// Leptos's `nightly` feature lets `count()` stand for `count.get()` and
+/// `set_count(1)` for `set_count.set(1)`; on stable Rust only the methods
+/// compile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Syntax {
+    #[default]
+    Stable,
+    Nightly,
+}
+
+impl Syntax {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Stable => "stable",
+            Self::Nightly => "nightly",
+        }
+    }
+}
+
+/// Subsections showing only nightly syntax, left out for stable projects.
+/// Subsections for one server integration end their heading with its name,
+/// like `## Extractors (Actix)`, and are left out for the other.
+const NIGHTLY_HEADINGS: &[&str] = &["nightly-syntax"];
 
 /// Documentation section
+///
+/// Content and outline are shared, so a section is cheap to clone, and one
+/// handed out stays as it was when the docs are reloaded.
 #[derive(Debug, Clone)]
 pub struct DocSection {
-    pub title: String,
-    pub path: String,
-    pub use_cases: String,
-    pub content: String,
+    pub title: &'static str,
+    pub path: &'static str,
+    pub use_cases: &'static str,
+    pub content: Arc<str>,
+    /// Version the content was written for (may differ from the requested one on fallback)
+    pub version: LeptosVersion,
+    /// Language the content is written in
+    pub language: Language,
+    /// Heading outline of `content`, filled in at load time
+    pub headings: Arc<[Heading]>,
 }
 
-/// Get all available documentation sections
-pub fn list_sections() -> Vec<DocSection> {
-    vec![
-        DocSection {
-            title: "Getting Started".to_string(),
-            path: "getting-started".to_string(),
-            use_cases: "new project, setup, installation, basics, hello world".to_string(),
-            content: include_str!("../docs/getting-started.md").to_string(),
-        },
-        DocSection {
-            title: "Components".to_string(),
-            path: "components".to_string(),
-            use_cases: "UI, view, component, props, children, #[component], always".to_string(),
-            content: include_str!("../docs/components.md").to_string(),
-        },
-        DocSection {
-            title: "Signals".to_string(),
-            path: "signals".to_string(),
-            use_cases: "state, reactivity, signals, derived, effects, get, set, read, write, update, always".to_string(),
-            content: include_str!("../docs/signals.md").to_string(),
-        },
-        DocSection {
-            title: "Views".to_string(),
-            path: "views".to_string(),
-            use_cases: "view macro, dynamic classes, dynamic styles, attributes, class:, style:, events, always".to_string(),
-            content: include_str!("../docs/views.md").to_string(),
-        },
-        DocSection {
-            title: "Resources".to_string(),
-            path: "resources".to_string(),
-            use_cases: "async, data loading, Resource, LocalResource, OnceResource, fetch, API".to_string(),
-            content: include_str!("../docs/resources.md").to_string(),
-        },
-        DocSection {
-            title: "Actions".to_string(),
-            path: "actions".to_string(),
-            use_cases: "mutations, POST, forms, ActionForm, ServerAction, submit, create, update, delete".to_string(),
-            content: include_str!("../docs/actions.md").to_string(),
-        },
-        DocSection {
-            title: "Server Functions".to_string(),
-            path: "server-functions".to_string(),
-            use_cases: "backend, API, database, server, SSR, #[server], extractors, Axum".to_string(),
-            content: include_str!("../docs/server-functions.md").to_string(),
-        },
-        DocSection {
-            title: "Routing".to_string(),
-            path: "routing".to_string(),
-            use_cases: "navigation, pages, routes, params, nested routes, Router".to_string(),
-            content: include_str!("../docs/routing.md").to_string(),
-        },
-        DocSection {
-            title: "Forms".to_string(),
-            path: "forms".to_string(),
-            use_cases: "form, input, validation, submit, controlled input, prop:value".to_string(),
-            content: include_str!("../docs/forms.md").to_string(),
-        },
-        DocSection {
-            title: "Error Handling".to_string(),
-            path: "error-handling".to_string(),
-            use_cases: "errors, ErrorBoundary, Result, ServerFnError, try".to_string(),
-            content: include_str!("../docs/error-handling.md").to_string(),
-        },
+impl DocSection {
+    /// Find a subsection by heading slug or title
+    pub fn find_heading(&self, query: &str) -> Option<&Heading> {
+        let slug = slugify(query.trim().trim_start_matches('#'));
+        if slug.is_empty() {
+            return None;
+        }
+        self.headings
+            .iter()
+            .find(|h| h.slug == slug)
+            .or_else(|| self.headings.iter().find(|h| h.slug.contains(&slug)))
+    }
+
+    /// Group the section belongs to, by its path prefix
+    pub fn group(&self) -> &'static DocGroup {
+        let prefix = self.path.split_once('/').map_or("", |(prefix, _)| prefix);
+        GROUPS
+            .iter()
+            .find(|g| g.prefix == prefix)
+            .unwrap_or(&GROUPS[0])
+    }
+
+    /// The section in `language`, or `None` without a translation of this
+    /// section's content
+    ///
+    /// Translations follow the latest version, so older-version content has
+    /// none. A translation whose outline matches the English one takes the
+    /// English heading slugs.
+    pub fn translated(&self, language: Language) -> Option<DocSection> {
+        if language == self.language || self.version != LeptosVersion::default() {
+            return None;
+        }
+        let content = translation(self.path, language)?;
+        let mut headings = parse_headings(content);
+        let same_outline = headings.len() == self.headings.len()
+            && headings
+                .iter()
+                .zip(self.headings.iter())
+                .all(|(t, h)| t.level == h.level);
+        if same_outline {
+            for (translated, english) in headings.iter_mut().zip(self.headings.iter()) {
+                translated.slug = english.slug.clone();
+            }
+        }
+        Some(DocSection {
+            content: content.into(),
+            language,
+            headings: headings.into(),
+            ..self.clone()
+        })
+    }
+
+    /// Markdown of a subsection, including nested headings
+    pub fn subsection(&self, heading: &Heading) -> &str {
+        &self.content[heading.start..heading.end]
+    }
+
+    /// The content without the subsections written for the other syntax or
+    /// the other server integration
+    pub fn content_for(&self, syntax: Syntax, backend: Backend) -> String {
+        let other_backend = match backend {
+            Backend::Axum => Backend::Actix,
+            Backend::Actix => Backend::Axum,
+        };
+        let hidden = |slug: &str| {
+            (syntax == Syntax::Stable && NIGHTLY_HEADINGS.contains(&slug))
+                || slug
+                    .strip_suffix(other_backend.as_str())
+                    .is_some_and(|rest| rest.ends_with('-'))
+        };
+        let mut content = String::with_capacity(self.content.len());
+        let mut offset = 0;
+        for heading in self.headings.iter() {
+            if heading.start >= offset && hidden(&heading.slug) {
+                content.push_str(&self.content[offset..heading.start]);
+                offset = heading.end;
+            }
+        }
+        content.push_str(&self.content[offset..]);
+        content
+    }
+}
+
+/// Content written specifically for an older Leptos version
+fn version_variant(path: &str, version: LeptosVersion) -> Option<&'static str> {
+    match (version, path) {
+        (LeptosVersion::V0_6, "getting-started") => {
+            Some(include_str!("../docs/v0.6/getting-started.md"))
+        }
+        (LeptosVersion::V0_6, "signals") => Some(include_str!("../docs/v0.6/signals.md")),
+        (LeptosVersion::V0_6, "resources") => Some(include_str!("../docs/v0.6/resources.md")),
+        (LeptosVersion::V0_6, "actions") => Some(include_str!("../docs/v0.6/actions.md")),
+        (LeptosVersion::V0_6, "stores") => Some(include_str!("../docs/v0.6/stores.md")),
+        _ => None,
+    }
+}
+
+/// A section translated into `language`, written for the latest Leptos version
+fn translation(path: &str, language: Language) -> Option<&'static str> {
+    match (language, path) {
+        (Language::Chinese, "getting-started") => {
+            Some(include_str!("../docs/translations/zh/getting-started.md"))
+        }
+        (Language::Chinese, "signals") => Some(include_str!("../docs/translations/zh/signals.md")),
+        _ => None,
+    }
+}
+
+/// A crate whose documentation forms a group of sections
+#[derive(Debug)]
+pub struct DocGroup {
+    /// Path prefix of the group's sections, e.g. `router` for `router/navigation`;
+    /// empty for core Leptos
+    pub prefix: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+}
+
+/// Section groups, in listing order
+pub const GROUPS: &[DocGroup] = &[
+    DocGroup {
+        prefix: "",
+        name: "leptos",
+        description: "Core concepts",
+    },
+    DocGroup {
+        prefix: "router",
+        name: "leptos_router",
+        description: "Nested routes, navigation and route guards",
+    },
+    DocGroup {
+        prefix: "meta",
+        name: "leptos_meta",
+        description: "Document head: title, meta tags, stylesheets",
+    },
+    DocGroup {
+        prefix: "server-fn",
+        name: "server_fn",
+        description: "Server function encodings and error types",
+    },
+    DocGroup {
+        prefix: "integration",
+        name: "leptos_axum / leptos_actix",
+        description: "Server setup: custom handlers, middleware, static files",
+    },
+    DocGroup {
+        prefix: "i18n",
+        name: "leptos_i18n / leptos-fluent",
+        description: "Translations, locales and language switching",
+    },
+    DocGroup {
+        prefix: "leptos-use",
+        name: "leptos-use",
+        description: "Browser utilities as reactive hooks",
+    },
+];
+
+/// A section as written for the latest supported Leptos version
+struct SectionSource {
+    title: &'static str,
+    path: &'static str,
+    use_cases: &'static str,
+    content: &'static str,
+}
+
+/// Sections written for the latest supported Leptos version
+const SECTIONS: &[SectionSource] = &[
+    SectionSource {
+        title: "Getting Started",
+        path: "getting-started",
+        use_cases: "new project, setup, installation, basics, hello world",
+        content: include_str!("../docs/getting-started.md"),
+    },
+    SectionSource {
+        title: "Components",
+        path: "components",
+        use_cases: "UI, view, component, props, children, #[component], attr:, attribute spreading, {..}, always",
+        content: include_str!("../docs/components.md"),
+    },
+    SectionSource {
+        title: "Signals",
+        path: "signals",
+        use_cases: "state, reactivity, signals, derived, effects, get, set, read, write, update, always",
+        content: include_str!("../docs/signals.md"),
+    },
+    SectionSource {
+        title: "Stores",
+        path: "stores",
+        use_cases: "reactive_stores, Store, derive(Store), nested state, field-level reactivity, keyed lists, Field",
+        content: include_str!("../docs/stores.md"),
+    },
+    SectionSource {
+        title: "Views",
+        path: "views",
+        use_cases: "view macro, dynamic classes, dynamic styles, attributes, class:, style:, events, AnyView, into_any, NodeRef, node_ref, always",
+        content: include_str!("../docs/views.md"),
+    },
+    SectionSource {
+        title: "Resources",
+        path: "resources",
+        use_cases: "async, data loading, Resource, LocalResource, OnceResource, fetch, API",
+        content: include_str!("../docs/resources.md"),
+    },
+    SectionSource {
+        title: "Actions",
+        path: "actions",
+        use_cases: "mutations, POST, forms, ActionForm, ServerAction, submit, create, update, delete",
+        content: include_str!("../docs/actions.md"),
+    },
+    SectionSource {
+        title: "Server Functions",
+        path: "server-functions",
+        use_cases: "backend, API, database, server, SSR, #[server], extractors, Axum",
+        content: include_str!("../docs/server-functions.md"),
+    },
+    SectionSource {
+        title: "Routing",
+        path: "routing",
+        use_cases: "navigation, pages, routes, params, nested routes, Router",
+        content: include_str!("../docs/routing.md"),
+    },
+    SectionSource {
+        title: "Forms",
+        path: "forms",
+        use_cases: "form, input, validation, submit, controlled input, prop:value",
+        content: include_str!("../docs/forms.md"),
+    },
+    SectionSource {
+        title: "Error Handling",
+        path: "error-handling",
+        use_cases: "errors, ErrorBoundary, Result, ServerFnError, try, custom error enum, thiserror, FromServerFnError, From impls, downcast",
+        content: include_str!("../docs/error-handling.md"),
+    },
+    SectionSource {
+        title: "Suspense",
+        path: "suspense",
+        use_cases: "loading, async, Suspense, Transition, streaming, fallback",
+        content: include_str!("../docs/suspense.md"),
+    },
+    SectionSource {
+        title: "Hydration",
+        path: "hydration",
+        use_cases: "SSR, hydration mismatch, window, document, browser APIs, cfg ssr, random, time",
+        content: include_str!("../docs/hydration.md"),
+    },
+    SectionSource {
+        title: "Accessibility",
+        path: "accessibility",
+        use_cases: "a11y, alt text, labels, aria, keyboard, screen readers, role, tabindex",
+        content: include_str!("../docs/accessibility.md"),
+    },
+    SectionSource {
+        title: "Security",
+        path: "security",
+        use_cases: "XSS, inner_html, sanitize, ammonia, authorization, server function auth, secrets, env!, API keys, CORS, cookies, HttpOnly, SameSite",
+        content: include_str!("../docs/security.md"),
+    },
+    SectionSource {
+        title: "Nested Routes",
+        path: "router/nested-routes",
+        use_cases: "ParentRoute, Outlet, layouts, nested params, child routes, empty path",
+        content: include_str!("../docs/router/nested-routes.md"),
+    },
+    SectionSource {
+        title: "Navigation",
+        path: "router/navigation",
+        use_cases: "A, links, use_navigate, NavigateOptions, Redirect, use_location, query, Form GET",
+        content: include_str!("../docs/router/navigation.md"),
+    },
+    SectionSource {
+        title: "Protected Routes",
+        path: "router/protected-routes",
+        use_cases: "auth, login, guards, ProtectedRoute, ProtectedParentRoute, redirect, permissions",
+        content: include_str!("../docs/router/protected-routes.md"),
+    },
+    SectionSource {
+        title: "Meta Setup",
+        path: "meta/setup",
+        use_cases: "provide_meta_context, MetaTags, head, shell, SSR, leptos_meta",
+        content: include_str!("../docs/meta/setup.md"),
+    },
+    SectionSource {
+        title: "Title",
+        path: "meta/title",
+        use_cases: "Title, document title, formatter, Meta, description, SEO, og tags",
+        content: include_str!("../docs/meta/title.md"),
+    },
+    SectionSource {
+        title: "Stylesheet",
+        path: "meta/stylesheet",
+        use_cases: "Stylesheet, CSS, Link, favicon, Script, Style, head tags, Tailwind, stylers, stylance, scoped styles",
+        content: include_str!("../docs/meta/stylesheet.md"),
+    },
+    SectionSource {
+        title: "Encodings",
+        path: "server-fn/encodings",
+        use_cases: "codec, GetUrl, PostUrl, Json, Cbor, multipart, streaming, endpoint, prefix",
+        content: include_str!("../docs/server-fn/encodings.md"),
+    },
+    SectionSource {
+        title: "Custom Errors",
+        path: "server-fn/custom-errors",
+        use_cases: "FromServerFnError, ServerFnErrorErr, error enum, typed errors, Result",
+        content: include_str!("../docs/server-fn/custom-errors.md"),
+    },
+    SectionSource {
+        title: "Custom Handlers",
+        path: "integration/custom-handlers",
+        use_cases: "routes, API endpoints, health check, webhooks, file downloads, Axum, Actix, render_app_to_stream",
+        content: include_str!("../docs/integration/custom-handlers.md"),
+    },
+    SectionSource {
+        title: "Middleware",
+        path: "integration/middleware",
+        use_cases: "tower layers, wrap, compression, CORS, tracing, logging, sessions, auth, Axum, Actix",
+        content: include_str!("../docs/integration/middleware.md"),
+    },
+    SectionSource {
+        title: "Static Files",
+        path: "integration/static-files",
+        use_cases: "assets, pkg, site_root, ServeDir, actix-files, favicon, file_and_error_handler, 404, Axum, Actix",
+        content: include_str!("../docs/integration/static-files.md"),
+    },
+    SectionSource {
+        title: "leptos_i18n",
+        path: "i18n/leptos_i18n",
+        use_cases: "i18n, translations, locales, t!, t_string!, use_i18n, I18nContextProvider, plurals, interpolation, language switch",
+        content: include_str!("../docs/i18n/leptos_i18n.md"),
+    },
+    SectionSource {
+        title: "leptos-fluent",
+        path: "i18n/leptos-fluent",
+        use_cases: "i18n, translations, Fluent, ftl, move_tr!, tr!, locales, language switch, Accept-Language",
+        content: include_str!("../docs/i18n/leptos-fluent.md"),
+    },
+    SectionSource {
+        title: "use_interval",
+        path: "leptos-use/use_interval",
+        use_cases: "timer, interval, counter, use_interval_fn, pause, resume, polling",
+        content: include_str!("../docs/leptos-use/use_interval.md"),
+    },
+    SectionSource {
+        title: "use_event_listener",
+        path: "leptos-use/use_event_listener",
+        use_cases: "events, keyboard shortcuts, window, document, resize, NodeRef, listener cleanup",
+        content: include_str!("../docs/leptos-use/use_event_listener.md"),
+    },
+    SectionSource {
+        title: "use_storage",
+        path: "leptos-use/use_storage",
+        use_cases: "localStorage, sessionStorage, persist, use_local_storage, codee, codec",
+        content: include_str!("../docs/leptos-use/use_storage.md"),
+    },
+];
+
+/// Directory of override pages looked up in the working directory
+pub const OVERRIDES_DIR: &str = "docs-overrides";
+
+/// How an override page combines with the built-in section at its path
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OverrideMode {
+    /// The page becomes the section's content
+    Replace,
+    /// The page follows the section's content
+    #[default]
+    Append,
+}
+
+impl OverrideMode {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "replace" => Ok(Self::Replace),
+            "append" => Ok(Self::Append),
+            other => Err(format!(
+                "Unsupported override mode '{}'. Expected 'replace' or 'append'",
+                other
+            )),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Replace => "replace",
+            Self::Append => "append",
+        }
+    }
+}
+
+/// A page of the overrides directory
+#[derive(Debug, Clone)]
+pub struct Override {
+    /// Section path, from the page's path relative to the directory
+    pub path: &'static str,
+    /// Front matter title, else the page's first `#` heading, else the path
+    pub title: &'static str,
+    pub use_cases: &'static str,
+    pub mode: OverrideMode,
+    pub content: Arc<str>,
+    /// Whether `path` names a built-in section
+    pub builtin: bool,
+}
+
+impl Override {
+    /// `content` with this page replacing or following it
+    fn apply(&self, content: &str) -> Arc<str> {
+        match self.mode {
+            OverrideMode::Replace => self.content.clone(),
+            OverrideMode::Append => format!("{}\n\n{}", content.trim_end(), self.content).into(),
+        }
+    }
+
+    /// The new section this page adds, for every version alike
+    fn section(&self, version: LeptosVersion) -> DocSection {
         DocSection {
-            title: "Suspense".to_string(),
-            path: "suspense".to_string(),
-            use_cases: "loading, async, Suspense, Transition, streaming, fallback".to_string(),
-            content: include_str!("../docs/suspense.md").to_string(),
-        },
-    ]
-}
-
-/// Get a specific documentation section by path or title
-pub fn get_section(query: &str) -> Option<DocSection> {
-    let query_lower = query.to_lowercase();
-    list_sections().into_iter().find(|s| {
-        s.path.to_lowercase().contains(&query_lower)
-            || s.title.to_lowercase().contains(&query_lower)
-    })
+            title: self.title,
+            path: self.path,
+            use_cases: self.use_cases,
+            content: self.content.clone(),
+            version,
+            language: Language::English,
+            headings: parse_headings(&self.content).into(),
+        }
+    }
+}
+
+/// Optional TOML between `+++` lines at the top of an override page
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FrontMatter {
+    title: Option<String>,
+    use_cases: Option<String>,
+    mode: Option<OverrideMode>,
+}
+
+/// Sections of every version with their search index
+///
+/// Built once from the embedded docs, and rebuilt when the Leptos book is
+/// synced, the docs directory changes or overrides are loaded. Lookups take
+/// a snapshot of the current library; a replaced one is freed once the last
+/// lookup using it is done.
+struct Library {
+    /// Indexed like `LeptosVersion::ALL`
+    sections: [Arc<[DocSection]>; 3],
+    /// Match keys of `sections`, in the same order
+    keys: [Vec<SectionKeys>; 3],
+    /// Distinct words of every section's keys
+    vocabulary: Vocabulary,
+    index: SearchIndex,
+    /// Latest-version content merged from the synced book, by section path
+    book: HashMap<&'static str, Arc<str>>,
+}
+
+/// A section's names and use cases, lowercased and split into tokens once per
+/// library instead of for every query
+struct SectionKeys {
+    path: String,
+    title: String,
+    use_cases: String,
+    /// Words of the path and the title, in the library's [`Vocabulary`]
+    name_words: Vec<usize>,
+    use_case_words: Vec<usize>,
+    /// Position of the section's docs file in the search index
+    file: Option<usize>,
+}
+
+impl SectionKeys {
+    fn new(section: &DocSection, index: &SearchIndex, words: &mut Vocabulary) -> Self {
+        let path = section.path.to_lowercase();
+        let title = section.title.to_lowercase();
+        let use_cases = section.use_cases.to_lowercase();
+        let index_path = index_path(section);
+        Self {
+            name_words: words.intern(tokenize(&path).chain(tokenize(&title))),
+            use_case_words: words.intern(tokenize(&use_cases)),
+            file: index.files.iter().position(|f| f.path == index_path),
+            path,
+            title,
+            use_cases,
+        }
+    }
+}
+
+/// Words shared by many sections' keys, stored once so a query is compared
+/// with each only once
+#[derive(Default)]
+struct Vocabulary {
+    words: Vec<String>,
+    ids: HashMap<String, usize>,
+}
+
+impl Vocabulary {
+    /// Ids of `tokens`, each appearing once
+    fn intern<'a>(&mut self, tokens: impl Iterator<Item = &'a str>) -> Vec<usize> {
+        let mut ids: Vec<usize> = tokens
+            .map(|token| match self.ids.get(token) {
+                Some(&id) => id,
+                None => {
+                    self.words.push(token.to_string());
+                    self.ids.insert(token.to_string(), self.words.len() - 1);
+                    self.words.len() - 1
+                }
+            })
+            .collect();
+        ids.sort_unstable();
+        ids.dedup();
+        ids
+    }
+}
+
+impl Library {
+    /// Embedded sections, with the content of the sections in `book` merged
+    /// over the latest version's and the loaded overrides on top
+    fn new(book: &HashMap<&str, String>) -> Self {
+        let merged: HashMap<&'static str, Arc<str>> = SECTIONS
+            .iter()
+            .filter_map(|source| {
+                let page = book.get(source.path)?;
+                Some((source.path, merge_book(page, source.content).into()))
+            })
+            .collect();
+        Self::build(merged, &overrides_slot().read().unwrap())
+    }
+
+    fn build(book: HashMap<&'static str, Arc<str>>, overrides: &[Override]) -> Self {
+        // Versions without a variant of a section share its latest content, so
+        // each distinct content is merged with its override and parsed once
+        let mut interned = HashMap::new();
+        let added: Vec<DocSection> = overrides
+            .iter()
+            .filter(|page| !page.builtin)
+            .map(|page| page.section(LeptosVersion::default()))
+            .collect();
+        let sections = LeptosVersion::ALL.map(|version| {
+            let mut sections: Vec<DocSection> = SECTIONS
+                .iter()
+                .map(|source| {
+                    let (content, content_version) = match version_variant(source.path, version) {
+                        Some(content) if version != LeptosVersion::default() => (content, version),
+                        _ => (
+                            book.get(source.path).map_or(source.content, |c| &**c),
+                            LeptosVersion::default(),
+                        ),
+                    };
+                    let (content, headings) = interned
+                        .entry((source.path, content.as_ptr()))
+                        .or_insert_with(|| {
+                            let content = match overrides.iter().find(|o| o.path == source.path) {
+                                Some(page) => page.apply(content),
+                                None => content.into(),
+                            };
+                            let headings: Arc<[Heading]> = parse_headings(&content).into();
+                            (content, headings)
+                        });
+                    DocSection {
+                        title: source.title,
+                        path: source.path,
+                        use_cases: source.use_cases,
+                        content: content.clone(),
+                        version: content_version,
+                        language: Language::English,
+                        headings: headings.clone(),
+                    }
+                })
+                .collect();
+            sections.extend(added.iter().map(|section| DocSection {
+                version,
+                ..section.clone()
+            }));
+            sections
+        });
+        // The embedded index only matches the embedded content
+        let index = if book.is_empty() && overrides.is_empty() {
+            serde_json::from_slice::<SearchIndex>(include_bytes!(concat!(
+                env!("OUT_DIR"),
+                "/search_index.json"
+            )))
+            .expect("embedded search index is valid")
+            .lowercase_headings()
+        } else {
+            let mut seen = HashSet::new();
+            let files: Vec<(String, &str)> = sections
+                .iter()
+                .flatten()
+                .map(|section| (index_path(section), &*section.content))
+                .filter(|(path, _)| seen.insert(path.clone()))
+                .collect();
+            SearchIndex::build(&files)
+        };
+        let mut vocabulary = Vocabulary::default();
+        let keys = sections.each_ref().map(|sections| {
+            sections
+                .iter()
+                .map(|s| SectionKeys::new(s, &index, &mut vocabulary))
+                .collect()
+        });
+        Self {
+            sections: sections.map(Arc::from),
+            keys,
+            vocabulary,
+            index,
+            book,
+        }
+    }
+}
+
+fn overrides_slot() -> &'static RwLock<Vec<Override>> {
+    static OVERRIDES: OnceLock<RwLock<Vec<Override>>> = OnceLock::new();
+    OVERRIDES.get_or_init(|| RwLock::new(Vec::new()))
+}
+
+fn library_slot() -> &'static RwLock<Arc<Library>> {
+    static LIBRARY: OnceLock<RwLock<Arc<Library>>> = OnceLock::new();
+    LIBRARY.get_or_init(|| RwLock::new(Arc::new(Library::new(&HashMap::new()))))
+}
+
+/// The library being served
+fn library() -> Arc<Library> {
+    library_slot().read().unwrap().clone()
+}
+
+/// Merge synced book pages, keyed by section path, over the embedded sections
+///
+/// Book content replaces a section's latest-version content; the section's
+/// own subsections the book doesn't cover are kept after it, so existing
+/// `section#heading` addresses still resolve. Older-version variants are
+/// left alone.
+pub fn apply_book(book: &HashMap<&str, String>) {
+    install(Library::new(book));
+}
+
+/// Load the pages of an overrides directory over the current sections
+///
+/// Every `<path>.md` below `dir` is keyed by its relative path, so
+/// `router/nested-routes.md` overrides that section. A page for a built-in
+/// section replaces or follows its content, by the page's front matter
+/// `mode` or else `mode`; a page for any other path becomes a new section.
+/// Overrides stay applied when the book is synced later. Returns the pages
+/// loaded.
+pub fn apply_overrides(dir: &Path, mode: OverrideMode) -> Result<Vec<Override>, String> {
+    let pages = read_overrides(dir, mode)?;
+    *overrides_slot().write().unwrap() = pages.clone();
+    install(Library::build(library().book.clone(), &pages));
+    Ok(pages)
+}
+
+/// Pages of an overrides directory, sorted by path
+fn read_overrides(dir: &Path, mode: OverrideMode) -> Result<Vec<Override>, String> {
+    let mut files = Vec::new();
+    collect_pages(dir, &mut files)?;
+    let mut pages = Vec::new();
+    for file in files {
+        let path = file
+            .strip_prefix(dir)
+            .unwrap_or(&file)
+            .with_extension("")
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        let raw = fs::read_to_string(&file)
+            .map_err(|e| format!("Failed to read {}: {}", file.display(), e))?;
+        let (front, content) = match raw
+            .strip_prefix("+++")
+            .and_then(|rest| rest.split_once("\n+++"))
+        {
+            Some((front, content)) => {
+                let front: FrontMatter = toml::from_str(front)
+                    .map_err(|e| format!("Invalid front matter in {}: {}", file.display(), e))?;
+                (front, content.trim_start_matches(['\r', '\n']))
+            }
+            None => (FrontMatter::default(), raw.as_str()),
+        };
+        let title = front.title.unwrap_or_else(|| {
+            parse_headings(content)
+                .into_iter()
+                .find(|h| h.level == 1)
+                .map_or_else(|| path.clone(), |h| h.title)
+        });
+        pages.push(Override {
+            builtin: SECTIONS.iter().any(|s| s.path == path),
+            path: path.leak(),
+            title: title.leak(),
+            use_cases: front.use_cases.unwrap_or_default().leak(),
+            mode: front.mode.unwrap_or(mode),
+            content: content.into(),
+        });
+    }
+    pages.sort_by_key(|page| page.path);
+    Ok(pages)
+}
+
+/// Markdown files below `dir`, recursively
+fn collect_pages(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), String> {
+    let entries =
+        fs::read_dir(dir).map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_pages(&path, files)?;
+        } else if path.extension().is_some_and(|ext| ext == "md") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Serve `library`, notifying subscribers of the sections whose content changed
+fn install(library: Library) {
+    let library = Arc::new(library);
+    let previous = std::mem::replace(&mut *library_slot().write().unwrap(), library.clone());
+    let changed: Vec<(LeptosVersion, &'static str)> = LeptosVersion::ALL
+        .into_iter()
+        .flat_map(|version| {
+            let (old, new) = (
+                &previous.sections[version.index()],
+                &library.sections[version.index()],
+            );
+            new.iter()
+                .filter(|new| {
+                    old.iter()
+                        .find(|old| old.path == new.path)
+                        .is_none_or(|old| old.content != new.content)
+                })
+                .map(move |new| (version, new.path))
+        })
+        .collect();
+    if !changed.is_empty() {
+        GENERATION.fetch_add(1, Ordering::Relaxed);
+        // Nobody listening is fine
+        let _ = changes().send(changed);
+    }
+}
+
+/// Bumped each time [`install`] changes a section's content
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Counter that moves whenever a sync, reload or override changes the
+/// served docs, so results computed from them can tell they're stale
+pub fn generation() -> u64 {
+    GENERATION.load(Ordering::Relaxed)
+}
+
+fn changes() -> &'static broadcast::Sender<Vec<(LeptosVersion, &'static str)>> {
+    static CHANGES: OnceLock<broadcast::Sender<Vec<(LeptosVersion, &'static str)>>> =
+        OnceLock::new();
+    CHANGES.get_or_init(|| broadcast::channel(16).0)
+}
+
+/// Receive the sections whose content each later sync or reload changed,
+/// per version
+pub fn subscribe_changes() -> broadcast::Receiver<Vec<(LeptosVersion, &'static str)>> {
+    changes().subscribe()
+}
+
+/// Resource URI of a section, like `leptos-docs://0.8/router/nested-routes`
+pub fn resource_uri(version: LeptosVersion, path: &str) -> String {
+    format!("{}://{}/{}", RESOURCE_SCHEME, version.as_str(), path)
+}
+
+/// Section a resource URI names, with the version it asks for
+pub fn resolve_uri(uri: &str) -> Option<(LeptosVersion, DocSection)> {
+    let rest = uri.strip_prefix(RESOURCE_SCHEME)?.strip_prefix("://")?;
+    let (version, path) = rest.split_once('/')?;
+    let version = LeptosVersion::parse(version)?;
+    let section = list_sections(version)
+        .iter()
+        .find(|s| s.path == path)?
+        .clone();
+    Some((version, section))
+}
+
+/// Merge local pages over the embedded sections, as [`apply_book`] does
+///
+/// `dir` is laid out like the bundled `docs/` directory, one `<path>.md` per
+/// section; sections without a page keep their embedded content. Returns the
+/// paths of the sections that were replaced.
+pub fn apply_dir(dir: &Path) -> Result<Vec<&'static str>, String> {
+    let pages = read_dir_pages(dir)?;
+    let mut paths: Vec<&'static str> = pages.keys().copied().collect();
+    paths.sort_unstable();
+    if !pages.is_empty() {
+        apply_book(&pages);
+    }
+    Ok(paths)
+}
+
+fn read_dir_pages(dir: &Path) -> Result<HashMap<&'static str, String>, String> {
+    let mut pages = HashMap::new();
+    for source in SECTIONS {
+        let file = dir.join(format!("{}.md", source.path));
+        if file.is_file() {
+            let content = fs::read_to_string(&file)
+                .map_err(|e| format!("Failed to read {}: {}", file.display(), e))?;
+            pages.insert(source.path, content);
+        }
+    }
+    Ok(pages)
+}
+
+/// Re-apply `dir` whenever one of its pages is added, edited or removed
+///
+/// Polls the pages' modification times every `interval` on a background
+/// thread for the life of the process, and reloads once they have stopped
+/// changing for an interval, so a burst of saves costs one rebuild. A removed
+/// page's section goes back to its embedded content; a synced book is
+/// replaced like [`apply_dir`] does.
+pub fn watch_dir(dir: PathBuf, interval: Duration) {
+    let stamps = |dir: &Path| -> Vec<Option<(SystemTime, u64)>> {
+        SECTIONS
+            .iter()
+            .map(|source| {
+                let meta = fs::metadata(dir.join(format!("{}.md", source.path))).ok()?;
+                Some((meta.modified().ok()?, meta.len()))
+            })
+            .collect()
+    };
+    std::thread::spawn(move || {
+        let mut seen = stamps(&dir);
+        let mut applied = seen.clone();
+        loop {
+            std::thread::sleep(interval);
+            let current = stamps(&dir);
+            if current != seen {
+                // Still being written; wait for it to settle
+                seen = current;
+                continue;
+            }
+            if current == applied {
+                continue;
+            }
+            applied = current;
+            match read_dir_pages(&dir) {
+                Ok(pages) => {
+                    tracing::info!("Docs directory changed; serving {} page(s)", pages.len());
+                    apply_book(&pages);
+                }
+                Err(e) => tracing::warn!("{}", e),
+            }
+        }
+    });
+}
+
+/// Book content followed by the embedded subsections it has no heading for
+fn merge_book(book: &str, embedded: &str) -> String {
+    let covered: Vec<String> = parse_headings(book).into_iter().map(|h| h.slug).collect();
+    let mut merged = book.trim_end().to_string();
+    for heading in parse_headings(embedded) {
+        if heading.level == 2 && !covered.contains(&heading.slug) {
+            merged.push_str("\n\n");
+            merged.push_str(embedded[heading.start..heading.end].trim_end());
+        }
+    }
+    merged.push('\n');
+    merged
+}
+
+/// Get all available documentation sections for a Leptos version
+///
+/// Sections without a version-specific variant fall back to the latest content.
+/// Built once per sync; calls don't allocate.
+pub fn list_sections(version: LeptosVersion) -> Arc<[DocSection]> {
+    library().sections[version.index()].clone()
+}
+
+/// Minimum score for a query to resolve to a section without asking
+const MATCH_THRESHOLD: f64 = 0.7;
+
+/// Minimum score for a section to be offered as a "did you mean" candidate
+const SUGGESTION_THRESHOLD: f64 = 0.3;
+
+/// Get the best matching documentation section by path, title or use case
+///
+/// Returns `None` when no section scores above the confidence threshold;
+/// use [`suggest_sections`] to offer candidates in that case.
+pub fn get_section(query: &str, version: LeptosVersion) -> Option<DocSection> {
+    // An exact path or title scores highest, and ties keep section order
+    let library = library();
+    let query = query.trim().to_lowercase();
+    let exact = library.keys[version.index()]
+        .iter()
+        .position(|keys| keys.path == query || keys.title == query);
+    if let Some(position) = exact {
+        return Some(library.sections[version.index()][position].clone());
+    }
+    rank(&library, &query, version)
+        .into_iter()
+        .next()
+        .filter(|(_, score)| *score >= MATCH_THRESHOLD)
+        .map(|(section, _)| section.clone())
+}
+
+/// Candidate sections for a query that didn't resolve confidently, best first
+pub fn suggest_sections(query: &str, version: LeptosVersion, limit: usize) -> Vec<DocSection> {
+    rank(&library(), query, version)
+        .into_iter()
+        .filter(|(_, score)| *score >= SUGGESTION_THRESHOLD)
+        .take(limit)
+        .map(|(section, _)| section.clone())
+        .collect()
+}
+
+/// Score every section against a query, best match first
+pub fn rank_sections(query: &str, version: LeptosVersion) -> Vec<(DocSection, f64)> {
+    rank(&library(), query, version)
+        .into_iter()
+        .map(|(section, score)| (section.clone(), score))
+        .collect()
+}
+
+fn rank<'a>(
+    library: &'a Library,
+    query: &str,
+    version: LeptosVersion,
+) -> Vec<(&'a DocSection, f64)> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return Vec::new();
+    }
+    // Each query token against each distinct word, not once per section using it
+    let similarities: Vec<Vec<f64>> = tokenize(&query)
+        .map(|token| {
+            library
+                .vocabulary
+                .words
+                .iter()
+                .map(|word| similarity(token, word))
+                .collect()
+        })
+        .collect();
+    let mut ranked: Vec<(&DocSection, f64)> = library.sections[version.index()]
+        .iter()
+        .zip(&library.keys[version.index()])
+        .map(|(s, keys)| (s, score_section(&query, &similarities, keys)))
+        .filter(|(_, score)| *score > 0.0)
+        .collect();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+    ranked
+}
+
+/// Score a section against a lowercase query in the range 0.0..=1.0, given
+/// the similarity of each query token to each word of the vocabulary
+fn score_section(query: &str, similarities: &[Vec<f64>], keys: &SectionKeys) -> f64 {
+    if query == keys.path || query == keys.title {
+        return 1.0;
+    }
+    if query.len() >= 3 && (keys.path.contains(query) || keys.title.contains(query)) {
+        return 0.9;
+    }
+
+    // Name matches are worth more than use-case keyword matches
+    let name_score = token_score(similarities, &keys.name_words);
+    let keyword_score = token_score(similarities, &keys.use_case_words) * 0.85;
+    name_score.max(keyword_score)
+}
+
+fn tokenize(text: &str) -> impl Iterator<Item = &str> {
+    text.split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|t| !t.is_empty())
+}
+
+/// Average over query tokens of the best similarity against any candidate word
+fn token_score(similarities: &[Vec<f64>], candidates: &[usize]) -> f64 {
+    if similarities.is_empty() || candidates.is_empty() {
+        return 0.0;
+    }
+    let total: f64 = similarities
+        .iter()
+        .map(|row| candidates.iter().map(|&c| row[c]).fold(0.0, f64::max))
+        .sum();
+    total / similarities.len() as f64
+}
+
+/// Similarity between two words from edit distance and shared prefix
+pub fn similarity(a: &str, b: &str) -> f64 {
+    if a == b {
+        return 1.0;
+    }
+    // Most words are ASCII, whose bytes are their characters
+    if a.is_ascii() && b.is_ascii() {
+        return char_similarity(a.as_bytes(), b.as_bytes());
+    }
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    char_similarity(&a, &b)
+}
+
+fn char_similarity<T: PartialEq>(a: &[T], b: &[T]) -> f64 {
+    let max_len = a.len().max(b.len());
+    let min_len = a.len().min(b.len());
+
+    let edit = 1.0 - edit_distance(a, b) as f64 / max_len as f64;
+
+    // "route" vs "routing": a long shared prefix is a strong signal
+    let prefix = if min_len >= 3 {
+        let shared = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+        shared as f64 / min_len as f64 * 0.9
+    } else {
+        0.0
+    };
+
+    edit.max(prefix)
+}
+
+/// Optimal string alignment distance (Levenshtein with transpositions)
+///
+/// Keeps the three rows of the table a transposition looks back over.
+fn edit_distance<T: PartialEq>(a: &[T], b: &[T]) -> usize {
+    let mut before: Vec<usize> = vec![0; b.len() + 1];
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current: Vec<usize> = vec![0; b.len() + 1];
+    for i in 1..=a.len() {
+        current[0] = i;
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            current[j] = (previous[j] + 1)
+                .min(current[j - 1] + 1)
+                .min(previous[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                current[j] = current[j].min(before[j - 2] + 1);
+            }
+        }
+        std::mem::swap(&mut before, &mut previous);
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+/// A documentation subsection matching a search query
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    pub section_title: String,
+    pub section_path: String,
+    pub heading: String,
+    pub slug: String,
+    pub score: f64,
+    pub excerpt: String,
+}
+
+impl SearchHit {
+    /// Address usable as the `section` argument of get-documentation
+    pub fn address(&self) -> String {
+        if self.slug.is_empty() {
+            self.section_path.clone()
+        } else {
+            format!("{}#{}", self.section_path, self.slug)
+        }
+    }
+}
+
+/// A second-level subsection as indexed for search; the whole section when
+/// it has no second-level headings
+#[derive(Debug, Clone)]
+pub struct DocChunk {
+    pub section: DocSection,
+    pub heading: String,
+    pub slug: String,
+    /// Where the subsection is in the section's content
+    pub range: Range<usize>,
+}
+
+impl DocChunk {
+    pub fn text(&self) -> &str {
+        &self.section.content[self.range.clone()]
+    }
+
+    /// The hit a search scoring this chunk `score` returns
+    pub fn hit(&self, score: f64, terms: &[&str]) -> SearchHit {
+        SearchHit {
+            section_title: self.section.title.to_string(),
+            section_path: self.section.path.to_string(),
+            heading: self.heading.clone(),
+            slug: self.slug.clone(),
+            score,
+            excerpt: excerpt(self.text(), terms),
+        }
+    }
+}
+
+/// Every indexed subsection of a version's sections, in section order
+pub fn chunks(version: LeptosVersion) -> Vec<DocChunk> {
+    let library = library();
+    let index = &library.index;
+    let mut chunks = Vec::new();
+    let keys = &library.keys[version.index()];
+    for (section, keys) in library.sections[version.index()].iter().zip(keys) {
+        let Some(file) = keys.file.map(|file| &index.files[file]) else {
+            continue;
+        };
+        chunks.extend(file.chunks.iter().map(|chunk| DocChunk {
+            section: section.clone(),
+            heading: chunk.heading.clone(),
+            slug: chunk.slug.clone(),
+            range: chunk.start..chunk.end,
+        }));
+    }
+    chunks
+}
+
+/// Inverted index over the second-level subsections of every docs file,
+/// built by `build.rs` (or at runtime once the Leptos book is synced)
+#[derive(Debug, Deserialize)]
+struct SearchIndex {
+    files: Vec<IndexedFile>,
+    /// Lowercase token -> `[file, chunk, occurrences]`
+    postings: BTreeMap<String, Vec<[usize; 3]>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct IndexedFile {
+    /// Path relative to `docs/`, e.g. `signals.md` or `v0.6/signals.md`
+    path: String,
+    chunks: Vec<IndexedChunk>,
+}
+
+/// A subsection; the whole file when it has no second-level headings
+#[derive(Debug, Deserialize)]
+struct IndexedChunk {
+    heading: String,
+    slug: String,
+    start: usize,
+    end: usize,
+    /// `heading` in lowercase, filled in at load time
+    #[serde(skip)]
+    heading_lower: String,
+}
+
+impl SearchIndex {
+    /// Index `(path, content)` files at runtime, the way `build.rs` does
+    fn build(files: &[(String, &str)]) -> Self {
+        let mut postings: BTreeMap<String, Vec<[usize; 3]>> = BTreeMap::new();
+        let files = files
+            .iter()
+            .enumerate()
+            .map(|(file_id, (path, content))| {
+                let chunks = search_chunks(content);
+                for (chunk_id, chunk) in chunks.iter().enumerate() {
+                    for (token, count) in &chunk.tokens {
+                        postings
+                            .entry(token.clone())
+                            .or_default()
+                            .push([file_id, chunk_id, *count]);
+                    }
+                }
+                IndexedFile {
+                    path: path.clone(),
+                    chunks: chunks
+                        .into_iter()
+                        .map(|c| IndexedChunk {
+                            heading_lower: c.heading.to_lowercase(),
+                            heading: c.heading,
+                            slug: c.slug,
+                            start: c.start,
+                            end: c.end,
+                        })
+                        .collect(),
+                }
+            })
+            .collect();
+        Self { files, postings }
+    }
+
+    /// Fill in the lowercase headings `build.rs` leaves out
+    fn lowercase_headings(mut self) -> Self {
+        for chunk in self.files.iter_mut().flat_map(|f| &mut f.chunks) {
+            chunk.heading_lower = chunk.heading.to_lowercase();
+        }
+        self
+    }
+}
+
+/// Docs file a section's content was loaded from, as indexed by `build.rs`
+fn index_path(section: &DocSection) -> String {
+    if section.version == LeptosVersion::default() {
+        format!("{}.md", section.path)
+    } else {
+        format!("v{}/{}.md", section.version.as_str(), section.path)
+    }
+}
+
+/// Keyword search over the second-level subsections of every section
+///
+/// A term matches any indexed token containing it, so `sig` finds `signal`.
+pub fn search(query: &str, version: LeptosVersion, limit: usize) -> Vec<SearchHit> {
+    let query = query.to_lowercase();
+    let terms: Vec<&str> = tokenize(&query).filter(|t| t.len() > 1).collect();
+    if terms.is_empty() {
+        return Vec::new();
+    }
+    let library = library();
+    let index = &library.index;
+
+    // Occurrences of each term per (file, chunk)
+    let term_counts: Vec<HashMap<(usize, usize), usize>> = terms
+        .iter()
+        .map(|term| {
+            let mut counts = HashMap::new();
+            for (token, postings) in &index.postings {
+                let in_token = token.matches(term).count();
+                if in_token == 0 {
+                    continue;
+                }
+                for &[file, chunk, occurrences] in postings {
+                    *counts.entry((file, chunk)).or_default() += occurrences * in_token;
+                }
+            }
+            counts
+        })
+        .collect();
+
+    // Excerpts are only cut for the hits returned
+    let mut scored: Vec<(f64, &DocSection, &IndexedChunk)> = Vec::new();
+    let keys = &library.keys[version.index()];
+    for (section, keys) in library.sections[version.index()].iter().zip(keys) {
+        let Some(file_id) = keys.file else {
+            continue;
+        };
+        for (chunk_id, chunk) in index.files[file_id].chunks.iter().enumerate() {
+            let mut score = 0.0;
+            for (term, counts) in terms.iter().zip(&term_counts) {
+                let count = counts.get(&(file_id, chunk_id)).copied().unwrap_or(0);
+                if count > 0 {
+                    score += 1.0 + (count as f64).ln();
+                }
+                if chunk.heading_lower.contains(term) {
+                    score += 2.0;
+                }
+                if keys.use_cases.contains(term) {
+                    score += 0.5;
+                }
+            }
+            if score > 0.0 {
+                scored.push((score, section, chunk));
+            }
+        }
+    }
+
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+    scored.truncate(limit);
+    scored
+        .into_iter()
+        .map(|(score, section, chunk)| SearchHit {
+            section_title: section.title.to_string(),
+            section_path: section.path.to_string(),
+            heading: chunk.heading.clone(),
+            slug: chunk.slug.clone(),
+            score,
+            excerpt: excerpt(&section.content[chunk.start..chunk.end], &terms),
+        })
+        .collect()
+}
+
+/// First prose line mentioning a search term, or the first prose line at all
+fn excerpt(text: &str, terms: &[&str]) -> String {
+    let mut in_fence = false;
+    let prose: Vec<&str> = text
+        .lines()
+        .filter(|line| {
+            if line.trim_start().starts_with("```") {
+                in_fence = !in_fence;
+                return false;
+            }
+            let line = line.trim();
+            !in_fence && !line.is_empty() && !line.starts_with('#') && !line.starts_with('|')
+        })
+        .collect();
+    let line = prose
+        .iter()
+        .find(|line| {
+            let lower = line.to_lowercase();
+            terms.iter().any(|t| lower.contains(t))
+        })
+        .or(prose.first())
+        .map(|line| line.trim())
+        .unwrap_or("");
+
+    if line.chars().count() > 160 {
+        let cut: String = line.chars().take(157).collect();
+        format!("{}...", cut)
+    } else {
+        line.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sections_outlive_their_library() {
+        let library = Arc::new(Library::new(&HashMap::new()));
+        let section = library.sections[LeptosVersion::default().index()][0].clone();
+        let content = section.content.to_string();
+        let replaced = Arc::downgrade(&library);
+        drop(library);
+        assert!(replaced.upgrade().is_none());
+        assert_eq!(&*section.content, content);
+        assert!(section
+            .headings
+            .iter()
+            .all(|h| h.end <= section.content.len()));
+    }
 }
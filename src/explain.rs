@@ -0,0 +1,273 @@
+//! Compiler error explanations
+//!
+//! Recognizes Leptos-specific failures in raw `cargo build` output (and
+//! runtime panics pasted from the browser console) and maps them to an
+//! explanation, a fix sketch and the documentation section covering it.
+
+use schemars::JsonSchema;
+use serde::Serialize;
+
+/// A recognizable class of Leptos error
+pub struct ErrorPattern {
+    pub id: &'static str,
+    pub title: &'static str,
+    /// The error block matches if it contains any of these
+    needles: &'static [&'static str],
+    pub explanation: &'static str,
+    pub fix: &'static str,
+    pub doc: &'static str,
+}
+
+/// Checked in order; put specific patterns before general ones
+pub const PATTERNS: &[ErrorPattern] = &[
+    ErrorPattern {
+        id: "branch-types",
+        title: "Branches return different view types",
+        needles: &[
+            "`if` and `else` have incompatible types",
+            "`match` arms have incompatible types",
+        ],
+        explanation: "Each `view!` has its own concrete type, so `if`/`else` or `match` arms returning different markup don't unify.",
+        fix: "Call `.into_any()` on every branch, use `Either::Left`/`Either::Right`, or use `<Show when=... fallback=...>`.",
+        doc: "views#conditional-rendering",
+    },
+    ErrorPattern {
+        id: "view-type-generics",
+        title: "0.6 `View` type used in Leptos 0.7+",
+        needles: &["missing generics for struct `View`", "missing generics for struct `leptos::prelude::View`"],
+        explanation: "Leptos 0.6's type-erased `View` is gone. Since 0.7 `View<T>` wraps one concrete view type, and `.into_view()` no longer erases the type.",
+        fix: "Return or store `AnyView` instead, and turn each view into one with `.into_any()`.",
+        doc: "views#views-as-values",
+    },
+    ErrorPattern {
+        id: "fn-once-closure",
+        title: "Closure in view! is FnOnce",
+        needles: &[
+            "but this closure only implements `FnOnce`",
+            "closure is `FnOnce` because it moves",
+        ],
+        explanation: "Reactive closures and event handlers can run many times, so they must be `Fn`. This closure moves a captured non-Copy value out of itself (e.g. passes a `String` by value), which it can only do once.",
+        fix: "Clone inside the closure (`move || name.clone()`), or store the value in `StoredValue::new(value)` and read it with `.get_value()`.",
+        doc: "views#using-a-value-in-several-closures",
+    },
+    ErrorPattern {
+        id: "moved-value",
+        title: "Value moved into a closure and used again",
+        needles: &["use of moved value", "value moved into closure here"],
+        explanation: "A `move` closure took ownership of a non-Copy value (a `String`, `Vec` or prop), so it can't be used again afterwards. Signals are `Copy` and don't have this problem.",
+        fix: "let name_clone = name.clone();\nview! { <button on:click=move |_| log!(\"{}\", name_clone)>\"Log\"</button> <p>{name}</p> }",
+        doc: "views#using-a-value-in-several-closures",
+    },
+    ErrorPattern {
+        id: "closure-borrows",
+        title: "Closure borrows a local that doesn't live long enough",
+        needles: &[
+            "closure may outlive the current function",
+            "borrowed value does not live long enough",
+            "is borrowed for `'static`",
+        ],
+        explanation: "Views, event handlers and effects outlive the component function, so their closures must own what they capture (`'static`).",
+        fix: "Add `move` to the closure: `on:click=move |_| ...`, and clone owned values it needs.",
+        doc: "views#event-handlers",
+    },
+    ErrorPattern {
+        id: "not-into-view",
+        title: "Value in view! is not renderable",
+        needles: &[
+            "IntoView` is not satisfied",
+            "IntoView` is not implemented",
+            "RenderHtml` is not implemented",
+            "Render` is not implemented",
+            "IntoRender` is not implemented",
+        ],
+        explanation: "Only strings, numbers, views, signals, `Option`/`Vec` of renderable values and closures returning them can be placed in a view. Structs, `Result` with a non-renderable error, or `()`-returning closures can't.",
+        fix: "Render a field or formatted string: `{move || user.get().name}` / `{format!(\"{:?}\", value)}`; for `Result`, match it or use `<ErrorBoundary>`.",
+        doc: "views#conditional-rendering",
+    },
+    ErrorPattern {
+        id: "server-fn-error",
+        title: "Error can't be converted into ServerFnError",
+        needles: &[
+            "couldn't convert the error to `ServerFnError",
+            "for `ServerFnError",
+            "FromServerFnError` is not implemented",
+        ],
+        explanation: "`?` in a server function needs the error to convert into the function's error type. `ServerFnError` doesn't implement `From` for arbitrary errors.",
+        fix: "Map the error before `?`: `db.find(&id).await.map_err(|e| ServerFnError::new(e.to_string()))?`, or return a custom error type implementing `FromServerFnError`.",
+        doc: "server-functions#error-handling",
+    },
+    ErrorPattern {
+        id: "server-fn-serde",
+        title: "Server function type isn't serializable",
+        needles: &[
+            "Serialize` is not satisfied",
+            "Deserialize<'de>` is not satisfied",
+            "Serialize` is not implemented",
+            "DeserializeOwned` is not satisfied",
+        ],
+        explanation: "Server function arguments and return values cross the network and must implement `Serialize` and `Deserialize`.",
+        fix: "#[derive(Clone, Debug, Serialize, Deserialize)]\npub struct UserDto { pub id: String, pub name: String }",
+        doc: "server-functions#dtos-data-transfer-objects",
+    },
+    ErrorPattern {
+        id: "not-send",
+        title: "Non-Send value in a signal, resource or server context",
+        needles: &[
+            "cannot be sent between threads safely",
+            "cannot be shared between threads safely",
+        ],
+        explanation: "Signals, resources and actions are `Send + Sync` by default so they work with SSR. Browser types (`web_sys` elements, `Rc`, `RefCell`, JS futures) aren't.",
+        fix: "Use the local variants: `signal_local()`, `RwSignal::new_local()`, `LocalResource::new(...)`, `Action::new_local(...)`, or wrap in `SendWrapper`.",
+        doc: "signals#thread-local-signals",
+    },
+    ErrorPattern {
+        id: "missing-prop",
+        title: "Missing required component prop",
+        needles: &[
+            "missing required",
+            "PropsBuilder",
+        ],
+        explanation: "A component was used without one of its required props. Props without `#[prop(optional)]` or `#[prop(default = ...)]` must be passed.",
+        fix: "Pass the prop (`<UserCard name=name/>`) or make it optional in the component: `#[prop(optional)] name: Option<String>`.",
+        doc: "components#props",
+    },
+    ErrorPattern {
+        id: "signal-call-syntax",
+        title: "Signal called like a function",
+        needles: &[
+            "expected function, found `ReadSignal",
+            "expected function, found `WriteSignal",
+            "expected function, found `RwSignal",
+            "expected function, found `Memo",
+            "expected function, found `Signal",
+        ],
+        explanation: "`count()` / `set_count(1)` is the nightly-only call syntax. On stable Rust signals are read and written with methods.",
+        fix: "Use `count.get()` and `set_count.set(1)`, or enable Leptos's `nightly` feature on a nightly toolchain.",
+        doc: "signals#nightly-syntax",
+    },
+    ErrorPattern {
+        id: "deprecated-api",
+        title: "Pre-0.7 API",
+        needles: &[
+            "use of deprecated function `leptos::prelude::create_",
+            "cannot find function `create_signal`",
+            "cannot find function `create_resource`",
+            "cannot find function `create_effect`",
+        ],
+        explanation: "Leptos 0.7 replaced `create_*` functions with constructors and `signal()`.",
+        fix: "let (count, set_count) = signal(0);      // create_signal(0)\nlet double = Memo::new(move |_| count.get() * 2); // create_memo\nEffect::new(move |_| log!(\"{}\", count.get())); // create_effect\nlet data = Resource::new(source, fetcher);   // create_resource",
+        doc: "signals#creating-signals",
+    },
+    ErrorPattern {
+        id: "params-derive",
+        title: "Route params struct",
+        needles: &["Params` is not implemented", "IntoParam` is not implemented"],
+        explanation: "`use_params::<T>()` needs `T` to derive `Params`, with every field an `Option` of a type that parses from a string.",
+        fix: "#[derive(Params, PartialEq, Clone, Debug)]\nstruct UserParams { id: Option<u32> }",
+        doc: "routing#typed-params",
+    },
+    ErrorPattern {
+        id: "server-crate-in-wasm",
+        title: "Server-only dependency compiled for the browser",
+        needles: &[
+            "This wasm target is unsupported by mio",
+            "wasm32-unknown-unknown targets are not supported",
+            "the wasm*-unknown-unknown targets are not supported",
+            "failed to resolve: could not find `net` in `tokio`",
+        ],
+        explanation: "A server-only crate (tokio, mio, sqlx, getrandom without `js`) is being compiled for the WASM client build. Dependencies used only on the server must be optional and enabled by the `ssr` feature.",
+        fix: "[dependencies]\nsqlx = { version = \"0.8\", optional = true }\n\n[features]\nssr = [\"dep:sqlx\", \"leptos/ssr\"]",
+        doc: "server-functions#ssr-only-dependencies",
+    },
+    ErrorPattern {
+        id: "hydration-panic",
+        title: "Hydration mismatch",
+        needles: &[
+            "hydration error",
+            "Hydration error",
+            "hydration mismatch",
+            "Unrecoverable hydration",
+            "failed_to_cast",
+            "HydrationError",
+        ],
+        explanation: "The browser rendered different markup than the server did, so hydration found unexpected nodes. Typical causes: browser APIs during render, random or time-based values, `cfg!(feature = \"ssr\")` branches in views, or invalid HTML nesting (e.g. `<div>` inside `<p>`) that the browser's parser rearranged.",
+        fix: "Move browser-only work into `Effect::new`, render the same markup on both sides, and run `leptos-autofixer` to find L03xx hydration issues.",
+        doc: "hydration",
+    },
+];
+
+/// An error from the build output matched to a pattern
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct Explanation {
+    pub pattern: &'static str,
+    pub title: &'static str,
+    /// The error's headline, e.g. `error[E0382]: use of moved value: `name``
+    pub error: String,
+    /// `file:line:col` from the ` --> ` marker
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    pub explanation: &'static str,
+    pub fix: &'static str,
+    pub doc: &'static str,
+}
+
+/// Explanations for recognized errors, and the headlines of the rest
+pub fn explain(output: &str) -> (Vec<Explanation>, Vec<String>) {
+    let mut explained = Vec::new();
+    let mut unrecognized = Vec::new();
+    for block in error_blocks(output) {
+        let headline = block.lines().next().unwrap_or_default().trim().to_string();
+        let location = block
+            .lines()
+            .find_map(|l| l.trim_start().strip_prefix("--> "))
+            .map(|l| l.trim().to_string());
+        match pattern(&block) {
+            Some(p) => explained.push(Explanation {
+                pattern: p.id,
+                title: p.title,
+                error: headline,
+                location,
+                explanation: p.explanation,
+                fix: p.fix,
+                doc: p.doc,
+            }),
+            None => unrecognized.push(headline),
+        }
+    }
+    (explained, unrecognized)
+}
+
+/// The first pattern an error's text matches
+pub fn pattern(error: &str) -> Option<&'static ErrorPattern> {
+    PATTERNS
+        .iter()
+        .find(|p| p.needles.iter().any(|n| error.contains(n)))
+}
+
+/// Split build output into `error...` blocks and runtime `panicked at` messages
+fn error_blocks(output: &str) -> Vec<String> {
+    let mut blocks: Vec<String> = Vec::new();
+    let mut current: Option<String> = None;
+    for line in output.lines() {
+        let summary =
+            line.starts_with("error: could not compile") || line.starts_with("error: aborting");
+        let starts_error = line.starts_with("error") && !summary;
+        let starts_panic = line.contains("panicked at");
+        let ends_block = line.starts_with("warning") || summary;
+        if starts_error || starts_panic {
+            blocks.extend(current.take());
+            current = Some(format!("{}\n", line));
+        } else if ends_block {
+            blocks.extend(current.take());
+        } else if let Some(block) = current.as_mut() {
+            block.push_str(line);
+            block.push('\n');
+        }
+    }
+    blocks.extend(current);
+    // Output that isn't in rustc's format at all is treated as one block
+    if blocks.is_empty() && !output.trim().is_empty() {
+        blocks.push(output.to_string());
+    }
+    blocks
+}
@@ -0,0 +1,397 @@
+//! Fact base for checking claims about Leptos
+//!
+//! `verify-claims` matches statements an agent makes about Leptos behavior
+//! against short facts, each taken from the documentation subsection it
+//! cites. A fact says whether its subject does what its topic words describe;
+//! a claim naming both is confirmed when it says the same and contradicted
+//! when it says the opposite, through a negation ("doesn't refetch") or an
+//! opposite topic word ("fetches once").
+
+use crate::docs::{similarity, LeptosVersion};
+use schemars::JsonSchema;
+use serde::Serialize;
+
+/// What the fact base says about a claim
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Verdict {
+    Confirmed,
+    Contradicted,
+    Unknown,
+}
+
+impl Verdict {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Confirmed => "confirmed",
+            Self::Contradicted => "contradicted",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
+/// A statement about Leptos behavior and where the docs make it
+pub struct Fact {
+    pub id: &'static str,
+    /// The fact in one sentence
+    pub statement: &'static str,
+    /// Phrases naming what the fact is about; a claim must contain one
+    subject: &'static [&'static str],
+    /// Phrases for the behavior; a claim must contain one. A leading `!`
+    /// marks a phrase saying the opposite, like "once" for refetching
+    topic: &'static [&'static str],
+    /// Whether the subject does what `topic` describes
+    affirms: bool,
+    /// Versions the fact holds for; all when empty
+    versions: &'static [LeptosVersion],
+    /// Documentation subsection, as `section#heading`
+    pub doc: &'static str,
+}
+
+const MODERN: &[LeptosVersion] = &[LeptosVersion::V0_7, LeptosVersion::V0_8];
+
+pub const FACTS: &[Fact] = &[
+    Fact {
+        id: "resource-refetch",
+        statement: "A resource re-runs its fetcher whenever a signal read in its source closure changes.",
+        subject: &["resource", "localresource", "create_resource"],
+        topic: &["refetch", "reload", "rerun", "fetch again", "!once"],
+        affirms: true,
+        versions: &[],
+        doc: "resources#resource-dependencies",
+    },
+    Fact {
+        id: "resource-fetcher-untracked",
+        statement: "`Resource::new` tracks only its source closure; signals read in the fetcher don't trigger a refetch.",
+        subject: &["fetcher"],
+        topic: &["tracked", "track", "subscribe", "!untracked"],
+        affirms: false,
+        versions: MODERN,
+        doc: "resources#resource-ssr",
+    },
+    Fact {
+        id: "resource-loading-none",
+        statement: "Reading a resource returns `None` while it is loading and `Some` once it resolves.",
+        subject: &["resource"],
+        topic: &["none", "option"],
+        affirms: true,
+        versions: &[],
+        doc: "resources#accessing-resource-values",
+    },
+    Fact {
+        id: "once-resource",
+        statement: "`OnceResource` loads once and never refetches.",
+        subject: &["onceresource"],
+        topic: &["refetch", "reload", "rerun", "!once"],
+        affirms: false,
+        versions: MODERN,
+        doc: "resources#onceresource",
+    },
+    Fact {
+        id: "local-resource-ssr",
+        statement: "`LocalResource` only loads in the browser; it isn't run on the server or serialized to the client.",
+        subject: &["localresource", "create_local_resource"],
+        topic: &["ssr", "server", "serializ", "!browser", "!client only", "!csr"],
+        affirms: false,
+        versions: &[],
+        doc: "resources#localresource-csr",
+    },
+    Fact {
+        id: "action-manual",
+        statement: "An action runs only when dispatched (or submitted through `ActionForm`), never on its own when signals change.",
+        subject: &["action", "serveraction", "create_action"],
+        topic: &["automatic", "signal change", "!dispatch", "!manual", "!submit"],
+        affirms: false,
+        versions: &[],
+        doc: "actions#action-vs-resource",
+    },
+    Fact {
+        id: "effects-browser-only",
+        statement: "Effects run only in the browser, after rendering; they don't run during server rendering.",
+        subject: &["effect", "create_effect"],
+        topic: &["server", "ssr", "!browser", "!client"],
+        affirms: false,
+        versions: &[],
+        doc: "hydration#browser-apis",
+    },
+    Fact {
+        id: "browser-apis-on-server",
+        statement: "`window()`, `document()` and other `web_sys` APIs don't exist on the server; touch them only in effects and event handlers.",
+        subject: &["window", "document", "web_sys", "browser api"],
+        topic: &["server", "ssr", "component body", "anywhere"],
+        affirms: false,
+        versions: &[],
+        doc: "hydration#browser-apis",
+    },
+    Fact {
+        id: "random-values-hydration",
+        statement: "Random numbers, UUIDs and the current time rendered in a view differ between server and client and break hydration.",
+        subject: &["random", "uuid", "current time", "timestamp"],
+        topic: &["mismatch", "break", "differ", "!safe", "!fine", "!same"],
+        affirms: true,
+        versions: &[],
+        doc: "hydration#non-deterministic-values",
+    },
+    Fact {
+        id: "cfg-ssr-markup",
+        statement: "Branching markup on `cfg!(feature = \"ssr\")` renders different HTML on server and client, which breaks hydration.",
+        subject: &["cfg", "feature ssr"],
+        topic: &["mismatch", "break", "differ", "!safe", "!fine", "!same"],
+        affirms: true,
+        versions: &[],
+        doc: "hydration#server-only-markup",
+    },
+    Fact {
+        id: "component-runs-once",
+        statement: "A component function runs once; it doesn't re-run when the signals it reads change, only the reactive closures inside it do.",
+        subject: &["component"],
+        topic: &["rerun", "rerender", "runs again", "every change", "!once"],
+        affirms: false,
+        versions: &[],
+        doc: "signals#reading-in-the-component-body",
+    },
+    Fact {
+        id: "untracked-reads",
+        statement: "`get_untracked`, `with_untracked` and `read_untracked` read a signal without subscribing to it.",
+        subject: &["get_untracked", "with_untracked", "read_untracked", "untracked"],
+        topic: &["subscribe", "track", "rerun", "update", "react"],
+        affirms: false,
+        versions: &[],
+        doc: "signals#untracked-reads",
+    },
+    Fact {
+        id: "get-clones",
+        statement: "`.get()` clones the signal's value; `.read()` and `.with()` borrow it instead.",
+        subject: &["get"],
+        topic: &["clone", "copies", "!reference", "!borrow"],
+        affirms: true,
+        versions: &[],
+        doc: "signals#reading-signals",
+    },
+    Fact {
+        id: "read-borrows",
+        statement: "`.read()` returns a guard borrowing the value without cloning it.",
+        subject: &["read"],
+        topic: &["clone", "copies", "!reference", "!borrow", "!guard"],
+        affirms: false,
+        versions: MODERN,
+        doc: "signals#reading-signals",
+    },
+    Fact {
+        id: "signals-copy",
+        statement: "Signals are `Copy`, so any number of `move` closures can use the same signal.",
+        subject: &["signal", "readsignal", "writesignal", "rwsignal"],
+        topic: &["copy", "several closures", "multiple closures", "!moved"],
+        affirms: true,
+        versions: &[],
+        doc: "views#using-a-value-in-several-closures",
+    },
+    Fact {
+        id: "memo-caches",
+        statement: "A `Memo` caches its result and recomputes only when its dependencies change; a derived closure recomputes on every access.",
+        subject: &["memo", "create_memo"],
+        topic: &["cache", "recompute", "every access", "every read"],
+        affirms: true,
+        versions: &[],
+        doc: "signals#derived-signals",
+    },
+    Fact {
+        id: "nightly-call-syntax",
+        statement: "Calling signals as functions (`count()`, `set_count(1)`) needs Leptos's `nightly` feature on a nightly toolchain.",
+        subject: &["call signal function", "signal function", "function call syntax"],
+        topic: &["stable", "!nightly"],
+        affirms: false,
+        versions: &[],
+        doc: "signals#nightly-syntax",
+    },
+    Fact {
+        id: "create-functions-0.7",
+        statement: "From 0.7 the `create_*` constructors are deprecated in favor of `signal()`, `Memo::new`, `Effect::new` and `Resource::new`.",
+        subject: &["create_signal", "create_memo", "create_effect", "create_resource", "create_rw_signal"],
+        topic: &["deprecat", "removed", "old", "!recommended", "!preferred", "!current"],
+        affirms: true,
+        versions: MODERN,
+        doc: "signals#creating-signals",
+    },
+    Fact {
+        id: "create-functions-0.6",
+        statement: "In 0.6 signals, memos, effects and resources are created with the `create_*` functions.",
+        subject: &["create_signal", "create_memo", "create_effect", "create_resource", "create_rw_signal"],
+        topic: &["deprecat", "removed", "old", "!recommended", "!preferred", "!current"],
+        affirms: false,
+        versions: &[LeptosVersion::V0_6],
+        doc: "signals#creating-signals",
+    },
+    Fact {
+        id: "expect-context-panics",
+        statement: "`expect_context` panics when no ancestor provided the type; `use_context` returns an `Option` instead.",
+        subject: &["expect_context"],
+        topic: &["panic", "!option", "!none"],
+        affirms: true,
+        versions: &[],
+        doc: "components#sharing-state-with-context",
+    },
+    Fact {
+        id: "use-context-option",
+        statement: "`use_context` returns `None` when no ancestor provided the type.",
+        subject: &["use_context"],
+        topic: &["option", "none", "!panic"],
+        affirms: true,
+        versions: &[],
+        doc: "components#sharing-state-with-context",
+    },
+    Fact {
+        id: "context-shadowing",
+        statement: "Providing the same context type again further down shadows the outer value for that subtree.",
+        subject: &["provide_context", "context"],
+        topic: &["shadow", "override", "nested provider", "further down"],
+        affirms: true,
+        versions: &[],
+        doc: "components#sharing-state-with-context",
+    },
+    Fact {
+        id: "for-keyed",
+        statement: "`<For>` is keyed: when the list changes, only the items whose key changed are created or removed.",
+        subject: &["for", "for component"],
+        topic: &["whole list", "entire list", "every item", "all items", "!key", "!only changed"],
+        affirms: false,
+        versions: &[],
+        doc: "views#iterating-lists",
+    },
+    Fact {
+        id: "transition-keeps-content",
+        statement: "`<Transition>` keeps showing the previous content while resources reload; it shows the fallback only on the first load.",
+        subject: &["transition"],
+        topic: &["old content", "previous", "keep", "stale"],
+        affirms: true,
+        versions: &[],
+        doc: "suspense#transition-component",
+    },
+    Fact {
+        id: "suspense-fallback",
+        statement: "`<Suspense>` shows its fallback while resources read inside it are loading.",
+        subject: &["suspense"],
+        topic: &["fallback", "loading"],
+        affirms: true,
+        versions: &[],
+        doc: "suspense#suspense-component",
+    },
+    Fact {
+        id: "server-fn-server-only",
+        statement: "A `#[server]` function's body runs only on the server; the client calls it over HTTP.",
+        subject: &["server function", "server fn", "server_fn"],
+        topic: &["client", "browser", "wasm", "!only", "!http"],
+        affirms: false,
+        versions: &[],
+        doc: "server-functions#basic-server-function",
+    },
+    Fact {
+        id: "inner-html-unescaped",
+        statement: "`inner_html` inserts its string as raw HTML without escaping, so untrusted content opens an XSS hole.",
+        subject: &["inner_html"],
+        topic: &["escape", "sanitiz", "safe", "!xss", "!raw", "!unescaped"],
+        affirms: false,
+        versions: &[],
+        doc: "security#injecting-html",
+    },
+];
+
+/// Words that turn a claim into its negation; "n't" is split off first
+const NEGATIONS: &[&str] = &["not", "no", "never", "cannot", "neither", "nor", "without"];
+
+/// Similarity at which a word of the claim counts as a phrase's word
+const WORD_MATCH: f64 = 0.8;
+
+/// Phrase words shorter than this only match themselves
+const SHORT_WORD: usize = 4;
+
+/// The result of checking one claim
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct Check {
+    pub claim: String,
+    pub verdict: Verdict,
+    /// Fact the verdict rests on
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fact: Option<&'static str>,
+    /// `section#heading` of the documentation stating the fact
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub doc: Option<&'static str>,
+}
+
+/// Check a claim against the facts holding for `version`
+pub fn verify(claim: &str, version: LeptosVersion) -> Check {
+    let lower = claim
+        .to_lowercase()
+        .replace(['’', '\''], "'")
+        .replace("n't", " not");
+    // "re-runs" reads as "reruns"
+    let lower = format!(" {}", lower).replace(" re-", " re");
+    let words: Vec<&str> = lower
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|w| !w.is_empty())
+        .collect();
+    let best = FACTS
+        .iter()
+        .filter(|fact| fact.versions.is_empty() || fact.versions.contains(&version))
+        .filter_map(|fact| {
+            let subject = matched(fact.subject, &words)?;
+            let topics: Vec<&str> = fact
+                .topic
+                .iter()
+                .copied()
+                .filter(|phrase| contains(phrase.trim_start_matches('!'), &words))
+                .collect();
+            let longest = topics
+                .iter()
+                .map(|phrase| phrase.split_whitespace().count())
+                .max()?;
+            Some((fact, subject + longest, topics))
+        })
+        // The first of equally specific facts
+        .rev()
+        .max_by_key(|(_, score, _)| *score);
+    let Some((fact, _, topics)) = best else {
+        return Check {
+            claim: claim.to_string(),
+            verdict: Verdict::Unknown,
+            fact: None,
+            doc: None,
+        };
+    };
+    let negated = words.iter().filter(|w| NEGATIONS.contains(w)).count() % 2 == 1;
+    let opposite = topics.iter().all(|phrase| phrase.starts_with('!'));
+    let asserts = negated == opposite;
+    Check {
+        claim: claim.to_string(),
+        verdict: if asserts == fact.affirms {
+            Verdict::Confirmed
+        } else {
+            Verdict::Contradicted
+        },
+        fact: Some(fact.statement),
+        doc: Some(fact.doc),
+    }
+}
+
+/// Words of the longest phrase the claim contains
+fn matched(phrases: &[&str], words: &[&str]) -> Option<usize> {
+    phrases
+        .iter()
+        .filter(|phrase| contains(phrase, words))
+        .map(|phrase| phrase.split_whitespace().count())
+        .max()
+}
+
+/// Whether every word of `phrase` starts or closely matches a word of the
+/// claim; short words like "re" or "get" must match exactly
+fn contains(phrase: &str, words: &[&str]) -> bool {
+    phrase.split_whitespace().all(|part| {
+        words.iter().any(|w| {
+            if part.len() < SHORT_WORD {
+                return *w == part;
+            }
+            w.starts_with(part)
+                || (w.len().abs_diff(part.len()) <= 2 && similarity(w, part) >= WORD_MATCH)
+        })
+    })
+}
@@ -0,0 +1,345 @@
+//! Cargo feature advisor
+//!
+//! An SSR Leptos app is built twice from one crate: the server binary with
+//! `ssr` and the WASM client with `hydrate`. Each build must turn on exactly
+//! its own Leptos feature and leave the other side's crates out, which only
+//! works when `[features]`, optional dependencies and cargo-leptos'
+//! `[package.metadata.leptos]` agree. [`check`] finds where they don't and
+//! returns the corrected TOML for each problem.
+
+use crate::context::{Environment, Manifest};
+use crate::docs::LeptosVersion;
+use crate::rules::SSR_ONLY_CRATES;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::path::Path;
+use toml::Value;
+
+/// A misconfiguration and the TOML that fixes it
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct FeatureIssue {
+    /// Stable kind, e.g. `ssr-crate-not-optional`
+    pub id: &'static str,
+    pub message: String,
+    /// Replacement lines under their table header
+    pub patch: String,
+}
+
+/// Feature issues of the project at `root`
+pub fn check(root: &Path) -> Result<Vec<FeatureIssue>, String> {
+    let env = Environment::detect(root)?;
+    let manifest = Manifest::load(root)?;
+    if !env.crate_version("leptos").is_some_and(|c| c.direct) {
+        return Err(format!(
+            "{} doesn't depend on leptos",
+            root.join("Cargo.toml").display()
+        ));
+    }
+    let own = &manifest.own;
+    let metadata = own
+        .get("package")
+        .and_then(|p| p.get("metadata"))
+        .and_then(|m| m.get("leptos"));
+    let feature = |name: &str| env.features.iter().find(|f| f.name == name);
+    let is_ssr_app = env.backend().is_some()
+        || metadata.is_some()
+        || feature("ssr").is_some_and(|f| f.is_enabled());
+
+    let mut issues = Vec::new();
+    if !is_ssr_app {
+        if let Some(issue) = missing_csr(root, &manifest, &env) {
+            issues.push(issue);
+        }
+        return Ok(issues);
+    }
+
+    // Crate features forwarding to leptos/ssr and leptos/hydrate, by their usual names if absent
+    let ssr = feature("ssr")
+        .and_then(|f| f.crate_features.first().cloned())
+        .unwrap_or_else(|| "ssr".to_string());
+    let hydrate = feature("hydrate")
+        .and_then(|f| f.crate_features.first().cloned())
+        .unwrap_or_else(|| "hydrate".to_string());
+    let mut ssr_line = feature_list(own, &ssr);
+    let mut hydrate_line = feature_list(own, &hydrate);
+    let mut ssr_issues = Vec::new();
+    let mut hydrate_issues = Vec::new();
+
+    for (name, crate_feature) in [("ssr", &ssr), ("hydrate", &hydrate)] {
+        if feature(name).is_some_and(|f| f.always) {
+            issues.push(FeatureIssue {
+                id: "leptos-feature-always-on",
+                message: format!(
+                    "The leptos dependency enables `{}` unconditionally, so both the server and \
+                     the client build get it; enable it only through the `{}` crate feature",
+                    name, crate_feature
+                ),
+                patch: without_leptos_feature(&manifest, name),
+            });
+        }
+    }
+
+    let version = env.leptos_version().unwrap_or_default();
+    let direct = |name| env.crate_version(name).is_some_and(|c| c.direct);
+    let mut companions = vec!["leptos"];
+    companions.extend(
+        ["leptos_router", "leptos_meta"]
+            .into_iter()
+            .filter(|c| direct(c)),
+    );
+    for (line, issues, leptos_feature, crate_feature) in [
+        (&mut ssr_line, &mut ssr_issues, "ssr", &ssr),
+        (&mut hydrate_line, &mut hydrate_issues, "hydrate", &hydrate),
+    ] {
+        // Since 0.7 the companion crates follow leptos' hydrate feature themselves
+        let needs_own =
+            |c: &&&str| leptos_feature == "ssr" || **c == "leptos" || version < LeptosVersion::V0_7;
+        let missing: Vec<String> = companions
+            .iter()
+            .filter(needs_own)
+            .map(|c| format!("{}/{}", c, leptos_feature))
+            .filter(|entry| !enables(line, entry))
+            .collect();
+        if !missing.is_empty() {
+            issues.push((
+                "missing-feature-wiring",
+                format!(
+                    "The `{}` feature doesn't enable {}",
+                    crate_feature,
+                    quoted(&missing)
+                ),
+            ));
+            line.extend(missing);
+        }
+    }
+
+    let dependencies = own.get("dependencies").and_then(Value::as_table);
+    let mut gates = Vec::new();
+    for (name, entry) in dependencies.into_iter().flatten() {
+        if !SSR_ONLY_CRATES.contains(&name.replace('-', "_").as_str()) {
+            continue;
+        }
+        if entry.get("optional").and_then(Value::as_bool) != Some(true) {
+            issues.push(FeatureIssue {
+                id: "ssr-crate-not-optional",
+                message: format!(
+                    "`{}` only builds for the server but isn't optional, so the WASM client \
+                     build compiles it too",
+                    name
+                ),
+                patch: format!("[dependencies]\n{} = {}", name, optional(entry)),
+            });
+        }
+        let gated = format!("dep:{}", name);
+        if !enables(&ssr_line, &gated) && !enables(&ssr_line, name) {
+            ssr_issues.push((
+                "ssr-crate-not-gated",
+                format!("The `{}` feature doesn't enable `{}`", ssr, name),
+            ));
+            gates.push(gated);
+        }
+    }
+    ssr_line.splice(0..0, gates);
+
+    for (line, line_issues, crate_feature) in [
+        (&ssr_line, ssr_issues, &ssr),
+        (&hydrate_line, hydrate_issues, &hydrate),
+    ] {
+        let patch = format!("[features]\n{} = {}", crate_feature, array(line));
+        issues.extend(line_issues.into_iter().map(|(id, message)| FeatureIssue {
+            id,
+            message,
+            patch: patch.clone(),
+        }));
+    }
+
+    if let Some(metadata) = metadata {
+        issues.extend(check_metadata(own, metadata, &ssr, &hydrate));
+    }
+    Ok(issues)
+}
+
+/// cargo-leptos settings: which feature each build uses and how the lib is built
+fn check_metadata(own: &Value, metadata: &Value, ssr: &str, hydrate: &str) -> Vec<FeatureIssue> {
+    let mut issues = Vec::new();
+    for (key, expected, build) in [
+        ("bin-features", ssr, "server"),
+        ("lib-features", hydrate, "client"),
+    ] {
+        let features = feature_values(metadata.get(key));
+        if !features.iter().any(|f| f == expected) {
+            issues.push(FeatureIssue {
+                id: "metadata-build-features",
+                message: format!(
+                    "`{}` in [package.metadata.leptos] should enable `{}` for the {} build",
+                    key, expected, build
+                ),
+                patch: format!("[package.metadata.leptos]\n{} = [\"{}\"]", key, expected),
+            });
+        }
+    }
+
+    let defaults = feature_list(own, "default");
+    let conflicting: Vec<String> = [ssr, hydrate]
+        .into_iter()
+        .filter(|f| defaults.iter().any(|d| d == f))
+        .map(String::from)
+        .collect();
+    for key in ["bin-default-features", "lib-default-features"] {
+        if !conflicting.is_empty() && metadata.get(key).and_then(Value::as_bool) != Some(false) {
+            issues.push(FeatureIssue {
+                id: "metadata-default-features",
+                message: format!(
+                    "The `default` feature enables {}, which cargo-leptos turns on for both \
+                     builds unless `{}` is false",
+                    quoted(&conflicting),
+                    key
+                ),
+                patch: format!("[package.metadata.leptos]\n{} = false", key),
+            });
+        }
+    }
+
+    let crate_types = feature_values(own.get("lib").and_then(|lib| lib.get("crate-type")));
+    let missing: Vec<String> = ["cdylib", "rlib"]
+        .into_iter()
+        .filter(|t| !crate_types.iter().any(|c| c == t))
+        .map(String::from)
+        .collect();
+    if !missing.is_empty() {
+        let mut crate_types = crate_types;
+        crate_types.extend(missing.iter().cloned());
+        issues.push(FeatureIssue {
+            id: "lib-crate-type",
+            message: format!(
+                "[lib] crate-type is missing {}: cargo-leptos builds the client from the lib \
+                 as a cdylib and links the server binary against it as an rlib",
+                quoted(&missing)
+            ),
+            patch: format!("[lib]\ncrate-type = {}", array(&crate_types)),
+        });
+    }
+    issues
+}
+
+/// A client-side rendered app served by Trunk needs leptos' `csr` feature
+fn missing_csr(root: &Path, manifest: &Manifest, env: &Environment) -> Option<FeatureIssue> {
+    let csr = env.features.iter().find(|f| f.name == "csr")?;
+    if csr.is_enabled() || !root.join("index.html").is_file() {
+        return None;
+    }
+    let entry = manifest.own.get("dependencies")?.get("leptos")?;
+    let mut entry = as_table(entry);
+    let mut features = feature_values(entry.get("features"));
+    features.push("csr".to_string());
+    entry.insert("features".to_string(), strings(&features));
+    Some(FeatureIssue {
+        id: "csr-not-enabled",
+        message: "This looks like a Trunk app (index.html, no server integration) but leptos' \
+                  `csr` feature is off, so nothing mounts in the browser"
+            .to_string(),
+        patch: format!("[dependencies]\nleptos = {}", inline(&Value::Table(entry))),
+    })
+}
+
+/// The leptos dependency without `feature` in its `features`, where it is declared
+fn without_leptos_feature(manifest: &Manifest, feature: &str) -> String {
+    let own = manifest
+        .own
+        .get("dependencies")
+        .and_then(|d| d.get("leptos"));
+    let declares = |entry: &Value| {
+        feature_values(entry.get("features"))
+            .iter()
+            .any(|f| f == feature)
+    };
+    let (header, entry) = match own {
+        Some(entry) if declares(entry) => ("[dependencies]", entry),
+        _ => match manifest.workspace_dependency("leptos") {
+            Some(entry) => ("[workspace.dependencies]", entry),
+            None => ("[dependencies]", own.unwrap_or(&Value::Boolean(false))),
+        },
+    };
+    let mut entry = as_table(entry);
+    let features: Vec<String> = feature_values(entry.get("features"))
+        .into_iter()
+        .filter(|f| f != feature)
+        .collect();
+    if features.is_empty() {
+        entry.remove("features");
+    } else {
+        entry.insert("features".to_string(), strings(&features));
+    }
+    format!("{}\nleptos = {}", header, inline(&Value::Table(entry)))
+}
+
+/// Whether a feature list already turns on `entry`, also as `crate?/feature`
+fn enables(line: &[String], entry: &str) -> bool {
+    let weak = entry.replacen('/', "?/", 1);
+    line.iter().any(|e| e == entry || *e == weak)
+}
+
+fn feature_list(manifest: &Value, name: &str) -> Vec<String> {
+    feature_values(manifest.get("features").and_then(|f| f.get(name)))
+}
+
+fn feature_values(value: Option<&Value>) -> Vec<String> {
+    value
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .map(String::from)
+        .collect()
+}
+
+/// A dependency entry as a table, so keys can be added
+fn as_table(entry: &Value) -> toml::Table {
+    match entry {
+        Value::Table(table) => table.clone(),
+        Value::String(version) => {
+            toml::Table::from_iter([("version".to_string(), Value::String(version.clone()))])
+        }
+        _ => toml::Table::new(),
+    }
+}
+
+/// A dependency entry made optional
+fn optional(entry: &Value) -> String {
+    let mut entry = as_table(entry);
+    entry.insert("optional".to_string(), Value::Boolean(true));
+    inline(&Value::Table(entry))
+}
+
+fn strings(values: &[String]) -> Value {
+    Value::Array(values.iter().cloned().map(Value::String).collect())
+}
+
+fn array(values: &[String]) -> String {
+    inline(&strings(values))
+}
+
+/// A value on one line, tables as inline tables with `version` first
+fn inline(value: &Value) -> String {
+    match value {
+        Value::Table(table) => {
+            let entries: Vec<String> = table
+                .iter()
+                .filter(|(key, _)| *key == "version")
+                .chain(table.iter().filter(|(key, _)| *key != "version"))
+                .map(|(key, value)| format!("{} = {}", key, inline(value)))
+                .collect();
+            format!("{{ {} }}", entries.join(", "))
+        }
+        Value::Array(values) => {
+            let values: Vec<String> = values.iter().map(inline).collect();
+            format!("[{}]", values.join(", "))
+        }
+        other => other.to_string(),
+    }
+}
+
+fn quoted(values: &[String]) -> String {
+    let values: Vec<String> = values.iter().map(|v| format!("`{}`", v)).collect();
+    values.join(", ")
+}
@@ -0,0 +1,562 @@
+//! `view!` formatter
+//!
+//! Re-indents the markup of `view!` invocations in the style of leptosfmt:
+//! one element per line, attributes wrapped one per line when the tag is too
+//! long, and short elements kept on a single line. Rust code outside `view!`
+//! and inside `{...}` blocks and attribute values is only re-indented, never
+//! reformatted.
+
+use crate::source;
+use crate::view::{parse_views, Attr, Element, Node, ViewMacro, VOID_ELEMENTS};
+
+/// Formatter settings, named after their leptosfmt equivalents
+#[derive(Debug, Clone, Copy)]
+pub struct FormatOptions {
+    pub max_width: usize,
+    pub tab_spaces: usize,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            max_width: 100,
+            tab_spaces: 4,
+        }
+    }
+}
+
+/// Markup of one `view!` body as a tree
+enum Tree<'a> {
+    Element {
+        element: &'a Element,
+        children: Vec<Tree<'a>>,
+        /// Written `<x/>` (or a void element); printed without a closing tag
+        self_closing: bool,
+    },
+    /// Source of a text literal or a `{...}` block
+    Leaf(String),
+    Comment(String),
+}
+
+/// Format every `view!` invocation in `code`
+///
+/// Views with unbalanced tags are rejected rather than guessed at.
+pub fn format_views(code: &str, options: FormatOptions) -> Result<String, String> {
+    let views = parse_views(code);
+    // Nested view! calls live inside blocks of an outer view and move with it
+    let outermost: Vec<&ViewMacro> = views
+        .iter()
+        .filter(|v| {
+            !views
+                .iter()
+                .any(|outer| outer.body_start < v.start && v.start < outer.body_end)
+        })
+        .collect();
+
+    let mut out = code.to_string();
+    for view in outermost.iter().rev() {
+        let tree = build_tree(code, view)?;
+        let line_start = code[..view.start].rfind('\n').map_or(0, |p| p + 1);
+        let indent: String = code[line_start..view.start]
+            .chars()
+            .take_while(|c| c.is_whitespace())
+            .collect();
+        let column = code[line_start..view.start].chars().count();
+        let printer = Printer { options };
+        let formatted = printer.view(&tree, &indent, column);
+        // The parser is tolerant; never return output that lost or reordered code
+        if significant(&code[view.start..view.body_end + 1]) != significant(&formatted) {
+            let line = code[..view.start].matches('\n').count() + 1;
+            return Err(format!(
+                "Can't format the view! at line {} safely: its markup couldn't be parsed completely. Wrap complex attribute values in braces, e.g. `attr={{move || a > b}}`.",
+                line
+            ));
+        }
+        out.replace_range(view.start..view.body_end + 1, &formatted);
+    }
+    Ok(out)
+}
+
+/// Source without whitespace, ignoring the `/` the formatter adds to void elements
+fn significant(code: &str) -> String {
+    let compact: String = code.chars().filter(|c| !c.is_whitespace()).collect();
+    compact.replace("/>", ">")
+}
+
+fn build_tree<'a>(code: &str, view: &'a ViewMacro) -> Result<Vec<Tree<'a>>, String> {
+    // Each open element with the children collected so far
+    let mut stack: Vec<(Option<&'a Element>, Vec<Tree<'a>>)> = vec![(None, Vec::new())];
+    for node in &view.nodes {
+        match node {
+            Node::Open(element) => {
+                if element.self_closing || VOID_ELEMENTS.contains(&element.name.as_str()) {
+                    let top = &mut stack.last_mut().expect("root frame").1;
+                    top.push(Tree::Element {
+                        element,
+                        children: Vec::new(),
+                        self_closing: true,
+                    });
+                } else {
+                    stack.push((Some(element), Vec::new()));
+                }
+            }
+            Node::Close { name, offset } => {
+                let (Some(element), children) = stack.pop().expect("root frame") else {
+                    return Err(unbalanced(
+                        code,
+                        *offset,
+                        &format!("`</{}>` closes nothing", name),
+                    ));
+                };
+                if &element.name != name {
+                    return Err(unbalanced(
+                        code,
+                        *offset,
+                        &format!("`</{}>` closes `<{}>`", name, element.name),
+                    ));
+                }
+                let top = &mut stack.last_mut().expect("root frame").1;
+                top.push(Tree::Element {
+                    element,
+                    children,
+                    self_closing: false,
+                });
+            }
+            Node::Text { text, .. } => {
+                let top = &mut stack.last_mut().expect("root frame").1;
+                top.push(Tree::Leaf(format!("\"{}\"", text)));
+            }
+            Node::Block { code: block, .. } => {
+                let top = &mut stack.last_mut().expect("root frame").1;
+                // Multi-line blocks keep their layout for `reindent`
+                let block = if block.contains('\n') {
+                    block.trim_start_matches([' ', '\t'])
+                } else {
+                    block.trim()
+                };
+                top.push(Tree::Leaf(format!("{{{}}}", block)));
+            }
+            Node::Comment { text, .. } => {
+                let top = &mut stack.last_mut().expect("root frame").1;
+                top.push(Tree::Comment(text.trim().to_string()));
+            }
+        }
+    }
+    match stack.pop() {
+        Some((None, children)) => Ok(children),
+        Some((Some(element), _)) => Err(unbalanced(
+            code,
+            element.offset,
+            &format!("`<{}>` is never closed", element.name),
+        )),
+        None => unreachable!("root frame"),
+    }
+}
+
+fn unbalanced(code: &str, offset: usize, what: &str) -> String {
+    let line = code[..offset].matches('\n').count() + 1;
+    format!(
+        "Can't format view! with unbalanced tags: {} (line {}). Run leptos-autofixer to locate the problem.",
+        what, line
+    )
+}
+
+struct Printer {
+    options: FormatOptions,
+}
+
+impl Printer {
+    /// The whole `view! { ... }` invocation; `column` is where `view!` starts
+    fn view(&self, tree: &[Tree], indent: &str, column: usize) -> String {
+        if let [only] = tree {
+            if let Some(line) = self.inline(only) {
+                let single = format!("view! {{ {} }}", line);
+                if column + single.len() <= self.options.max_width {
+                    return single;
+                }
+            }
+        }
+        let inner = self.indent(indent);
+        let mut out = String::from("view! {\n");
+        for node in tree {
+            self.node(node, &inner, &mut out);
+        }
+        out.push_str(indent);
+        out.push('}');
+        out
+    }
+
+    fn indent(&self, indent: &str) -> String {
+        format!("{}{}", indent, " ".repeat(self.options.tab_spaces))
+    }
+
+    /// A node on one line, if it has no comments or multi-line code
+    fn inline(&self, node: &Tree) -> Option<String> {
+        match node {
+            Tree::Comment(_) => None,
+            Tree::Leaf(text) => (!text.contains('\n')).then(|| text.clone()),
+            Tree::Element {
+                element,
+                children,
+                self_closing,
+            } => {
+                let attrs: Vec<String> = element.attrs.iter().map(attr_source).collect();
+                if attrs.iter().any(|a| a.contains('\n')) {
+                    return None;
+                }
+                let open = open_tag(&element.name, &attrs);
+                if *self_closing {
+                    return Some(format!("{} />", open));
+                }
+                let children: Option<Vec<String>> =
+                    children.iter().map(|c| self.inline(c)).collect();
+                Some(format!(
+                    "{}>{}</{}>",
+                    open,
+                    children?.join(" "),
+                    element.name
+                ))
+            }
+        }
+    }
+
+    fn node(&self, node: &Tree, indent: &str, out: &mut String) {
+        if let Some(line) = self.inline(node) {
+            if indent.len() + line.len() <= self.options.max_width {
+                out.push_str(&format!("{}{}\n", indent, line));
+                return;
+            }
+        }
+        match node {
+            Tree::Comment(text) => out.push_str(&format!("{}{}\n", indent, text)),
+            Tree::Leaf(text) => out.push_str(&format!(
+                "{}{}\n",
+                indent,
+                reindent(text, indent, self.options)
+            )),
+            Tree::Element {
+                element,
+                children,
+                self_closing,
+            } => {
+                let attrs: Vec<String> = element.attrs.iter().map(attr_source).collect();
+                let open = open_tag(&element.name, &attrs);
+                let end = if *self_closing { " />" } else { ">" };
+                let fits = indent.len() + open.len() + end.len() <= self.options.max_width;
+                if fits && !attrs.iter().any(|a| a.contains('\n')) {
+                    out.push_str(&format!("{}{}{}\n", indent, open, end));
+                } else {
+                    // One attribute per line, `>` back at the tag's indentation
+                    let inner = self.indent(indent);
+                    out.push_str(&format!("{}<{}\n", indent, element.name));
+                    for attr in &attrs {
+                        out.push_str(&format!(
+                            "{}{}\n",
+                            inner,
+                            reindent(attr, &inner, self.options)
+                        ));
+                    }
+                    out.push_str(&format!("{}{}\n", indent, end.trim_start()));
+                }
+                if *self_closing {
+                    return;
+                }
+                let inner = self.indent(indent);
+                for child in children {
+                    self.node(child, &inner, out);
+                }
+                out.push_str(&format!("{}</{}>\n", indent, element.name));
+            }
+        }
+    }
+}
+
+fn open_tag(name: &str, attrs: &[String]) -> String {
+    if attrs.is_empty() {
+        format!("<{}", name)
+    } else {
+        format!("<{} {}", name, attrs.join(" "))
+    }
+}
+
+fn attr_source(attr: &Attr) -> String {
+    match &attr.value {
+        Some(value) => format!("{}={}", attr.name, value.trim()),
+        None => attr.name.clone(),
+    }
+}
+
+/// Re-indent the continuation lines of multi-line code to `indent`, keeping
+/// their depth relative to each other and lines inside string literals as
+/// they are
+///
+/// A trailing closing bracket that was the least indented line lands at
+/// `indent`; otherwise the continuation lines go one level inside it.
+fn reindent(code: &str, indent: &str, options: FormatOptions) -> String {
+    // Whitespace on a line break inside a string literal is part of its value
+    let strings = source::string_contents(code);
+    let in_string = |offset: usize| strings.iter().any(|r| r.contains(&offset));
+    // Each line, whether it starts inside a string literal and whether it ends in one
+    let mut lines = Vec::new();
+    let mut start = 0;
+    for line in code.split('\n') {
+        let end = start + line.len();
+        lines.push((line, start > 0 && in_string(start - 1), in_string(end)));
+        start = end + 1;
+    }
+    fn trim((line, starts, ends): (&str, bool, bool)) -> &str {
+        let line = if starts { line } else { line.trim_start() };
+        if ends {
+            line
+        } else {
+            line.trim_end()
+        }
+    }
+    let (head, rest) = lines.split_first().expect("split yields a line");
+    let first = trim(*head).to_string();
+    let depth = |line: &str| line.len() - line.trim_start().len();
+    let base = rest
+        .iter()
+        .filter(|(line, starts, _)| !starts && !line.trim().is_empty())
+        .map(|(line, _, _)| depth(line))
+        .min();
+    if base.is_none() && !rest.iter().any(|(_, starts, _)| *starts) {
+        return first;
+    }
+    let base = base.unwrap_or_default();
+    let closed = rest.last().is_some_and(|(line, starts, _)| {
+        !starts && line.trim_start().starts_with(['}', ')', ']']) && depth(line) == base
+    });
+    let extra = if closed { 0 } else { options.tab_spaces };
+
+    let mut out = first;
+    for line in rest {
+        out.push('\n');
+        let text = trim(*line);
+        if line.1 {
+            out.push_str(text);
+        } else if !text.is_empty() {
+            out.push_str(indent);
+            out.push_str(&" ".repeat(depth(line.0) - base + extra));
+            out.push_str(text);
+        }
+    }
+    out
+}
+
+/// Line-based unified diff of `old` to `new` with three lines of context
+pub fn unified_diff(old: &str, new: &str) -> String {
+    unified_diff_labeled(old, new, "original", "formatted")
+}
+
+/// [`unified_diff`] with the file headers naming `old` and `new`
+pub fn unified_diff_labeled(old: &str, new: &str, old_label: &str, new_label: &str) -> String {
+    const CONTEXT: usize = 3;
+    let a: Vec<&str> = old.lines().collect();
+    let b: Vec<&str> = new.lines().collect();
+
+    // Lines shared at both ends are only needed as context, so a small
+    // change to a large file keeps the table small
+    let prefix = a.iter().zip(&b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    let skip = prefix.saturating_sub(CONTEXT);
+    let keep = suffix.saturating_sub(CONTEXT);
+    let (a_mid, b_mid) = (&a[skip..a.len() - keep], &b[skip..b.len() - keep]);
+
+    // Edit script as (old line, new line, marker), removals before
+    // additions within each run of changes as in `diff -u`
+    let mut markers = Vec::with_capacity(a_mid.len() + b_mid.len());
+    diff_lines(a_mid, b_mid, &mut markers);
+    let mut run = 0;
+    while run < markers.len() {
+        let len = markers[run..]
+            .iter()
+            .take_while(|&&marker| marker != ' ')
+            .count();
+        markers[run..run + len].sort_by_key(|&marker| marker == '+');
+        run += len.max(1);
+    }
+    let (mut i, mut j) = (skip, skip);
+    let ops: Vec<(usize, usize, char)> = markers
+        .into_iter()
+        .map(|marker| {
+            let op = (i, j, marker);
+            i += usize::from(marker != '+');
+            j += usize::from(marker != '-');
+            op
+        })
+        .collect();
+
+    let changed: Vec<usize> = (0..ops.len()).filter(|&k| ops[k].2 != ' ').collect();
+    let mut out = format!("--- {}\n+++ {}\n", old_label, new_label);
+    let mut k = 0;
+    while k < changed.len() {
+        // Extend the hunk while the next change is within two contexts
+        let start = changed[k].saturating_sub(CONTEXT);
+        let mut end = changed[k];
+        while k + 1 < changed.len() && changed[k + 1] <= end + 2 * CONTEXT {
+            k += 1;
+            end = changed[k];
+        }
+        let end = (end + CONTEXT + 1).min(ops.len());
+        let hunk = &ops[start..end];
+        let old_len = hunk.iter().filter(|op| op.2 != '+').count();
+        let new_len = hunk.iter().filter(|op| op.2 != '-').count();
+        out.push_str(&format!(
+            "@@ -{},{} +{},{} @@\n",
+            hunk[0].0 + 1,
+            old_len,
+            hunk[0].1 + 1,
+            new_len
+        ));
+        for &(i, j, marker) in hunk {
+            let line = if marker == '+' { b[j] } else { a[i] };
+            out.push_str(&format!("{}{}\n", marker, line));
+        }
+        k += 1;
+    }
+    out
+}
+
+/// Myers' diff of `a` to `b` in linear space, as one marker per line: ` `
+/// kept, `-` removed, `+` added
+///
+/// Each step splits the problem at the middle snake of a shortest edit
+/// script, so memory stays linear in the number of lines. Parts that differ
+/// in more than [`MAX_EDIT_COST`] lines are replaced whole, which keeps the
+/// time bounded too.
+fn diff_lines(a: &[&str], b: &[&str], out: &mut Vec<char>) {
+    let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    out.extend(std::iter::repeat_n(' ', prefix));
+    let (a_mid, b_mid) = (&a[prefix..a.len() - suffix], &b[prefix..b.len() - suffix]);
+    let snake = match a_mid.is_empty() || b_mid.is_empty() {
+        true => None,
+        false => middle_snake(a_mid, b_mid),
+    };
+    match snake {
+        Some((x, y, u, v)) => {
+            diff_lines(&a_mid[..x], &b_mid[..y], out);
+            out.extend(std::iter::repeat_n(' ', u - x));
+            diff_lines(&a_mid[u..], &b_mid[v..], out);
+        }
+        None => {
+            out.extend(std::iter::repeat_n('-', a_mid.len()));
+            out.extend(std::iter::repeat_n('+', b_mid.len()));
+        }
+    }
+    out.extend(std::iter::repeat_n(' ', suffix));
+}
+
+/// Most differing lines [`diff_lines`] searches for an alignment
+const MAX_EDIT_COST: isize = 4096;
+
+/// Start and end `(x, y, u, v)` of the middle snake of a shortest edit script
+/// of `a` to `b`, both non-empty, found by searching from both ends at once;
+/// `None` if the script is longer than [`MAX_EDIT_COST`]
+fn middle_snake(a: &[&str], b: &[&str]) -> Option<(usize, usize, usize, usize)> {
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    let delta = n - m;
+    let odd = delta % 2 != 0;
+    let max = (n + m + 1) / 2;
+    // Furthest x reached on each diagonal k = x - y, offset to stay positive;
+    // the backward search runs over both inputs reversed
+    let offset = max + 1;
+    let mut forward = vec![0isize; 2 * offset as usize + 1];
+    let mut backward = vec![0isize; 2 * offset as usize + 1];
+    let at = |k: isize| (k + offset) as usize;
+    for d in 0..=max.min(MAX_EDIT_COST / 2) {
+        for k in (-d..=d).step_by(2) {
+            let mut x = if k == -d || (k != d && forward[at(k - 1)] < forward[at(k + 1)]) {
+                forward[at(k + 1)]
+            } else {
+                forward[at(k - 1)] + 1
+            };
+            let mut y = x - k;
+            let (x0, y0) = (x, y);
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            forward[at(k)] = x;
+            let c = delta - k;
+            if odd && (-(d - 1)..=d - 1).contains(&c) && x + backward[at(c)] >= n {
+                return Some((x0 as usize, y0 as usize, x as usize, y as usize));
+            }
+        }
+        for k in (-d..=d).step_by(2) {
+            let mut x = if k == -d || (k != d && backward[at(k - 1)] < backward[at(k + 1)]) {
+                backward[at(k + 1)]
+            } else {
+                backward[at(k - 1)] + 1
+            };
+            let mut y = x - k;
+            let (x0, y0) = (x, y);
+            while x < n && y < m && a[(n - 1 - x) as usize] == b[(m - 1 - y) as usize] {
+                x += 1;
+                y += 1;
+            }
+            backward[at(k)] = x;
+            let c = delta - k;
+            if !odd && (-d..=d).contains(&c) && x + forward[at(c)] >= n {
+                return Some((
+                    (n - x) as usize,
+                    (m - y) as usize,
+                    (n - x0) as usize,
+                    (m - y0) as usize,
+                ));
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multi_line_string_literals_are_kept() {
+        let code = "fn app() -> impl IntoView {\n    view! { <pre>\"a   b\n   c\"</pre> <p>{\"x\n  y\"}</p> }\n}\n";
+        let formatted = format_views(code, FormatOptions::default()).unwrap();
+        assert!(formatted.contains("\"a   b\n   c\""), "{}", formatted);
+        assert!(formatted.contains("\"x\n  y\""), "{}", formatted);
+    }
+
+    #[test]
+    fn diff_removes_before_adding() {
+        let diff = unified_diff("a\nb\nc\nd\n", "a\nB\nC\nd\n");
+        assert_eq!(
+            diff,
+            "--- original\n+++ formatted\n@@ -1,4 +1,4 @@\n a\n-b\n-c\n+B\n+C\n d\n"
+        );
+    }
+
+    #[test]
+    fn diff_of_large_inputs() {
+        let old: String = (0..20_000).map(|i| format!("line {}\n", i)).collect();
+        let new: String = (0..20_000)
+            .map(|i| match i % 1000 {
+                0 => format!("changed {}\n", i),
+                _ => format!("line {}\n", i),
+            })
+            .collect();
+        let diff = unified_diff(&old, &new);
+        assert_eq!(diff.matches("\n-line").count(), 20);
+        assert_eq!(diff.matches("\n+changed").count(), 20);
+        let unrelated: String = (0..12_000).map(|i| format!("other {}\n", i)).collect();
+        let diff = unified_diff(&old, &unrelated);
+        assert_eq!(diff.matches("\n-").count(), 20_000);
+        assert_eq!(diff.matches("\n+").count(), 12_000 + 1);
+    }
+}
@@ -0,0 +1,1817 @@
+//! Code generators
+//!
+//! Deterministic templates for common Leptos items. Specs are deserialized
+//! straight from tool arguments; every generator validates its identifiers
+//! so the output compiles once pasted.
+
+use crate::docs::LeptosVersion;
+use crate::view::{dom_interface, element_type, VOID_ELEMENTS};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Arguments of `generate-component`
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ComponentSpec {
+    /// PascalCase component name, e.g. `UserCard`
+    pub name: String,
+    /// Component props in order
+    #[serde(default)]
+    pub props: Vec<PropSpec>,
+    /// Whether the component takes `children: Children`
+    #[serde(default)]
+    pub children: bool,
+    /// Local signals created in the component body
+    #[serde(default)]
+    pub signals: Vec<SignalSpec>,
+    /// Keep the signals as typed fields of a `#[derive(Store)]` state struct
+    /// (reactive_stores, Leptos 0.7+) instead of separate signals
+    #[serde(default)]
+    pub store: bool,
+    /// Root element of the view (default `div`)
+    pub element: Option<String>,
+    /// Bind the root element to a typed `NodeRef`, read in an effect once it mounts
+    #[serde(default)]
+    pub node_ref: bool,
+    /// Take HTML attributes from callers (`attr:id`, or after `{..}`) onto
+    /// the root element; on Leptos 0.6 through a `#[prop(attrs)]` prop
+    #[serde(default)]
+    pub attrs: bool,
+}
+
+/// A component prop
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PropSpec {
+    /// snake_case prop name
+    pub name: String,
+    /// Rust type, e.g. `String` or `Signal<i32>`
+    #[serde(rename = "type")]
+    pub ty: String,
+    /// `#[prop(optional)]`: omitted props get `Default::default()`
+    #[serde(default)]
+    pub optional: bool,
+    /// `#[prop(default = ...)]` expression
+    pub default: Option<String>,
+    /// `#[prop(into)]`: accept anything convertible into the type
+    #[serde(default)]
+    pub into: bool,
+    /// Doc comment for the prop
+    pub doc: Option<String>,
+}
+
+/// A local signal created in the component body
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SignalSpec {
+    /// snake_case name of the `RwSignal`, which both reads and writes
+    pub name: String,
+    /// Value type, when the initial value doesn't determine it
+    #[serde(rename = "type")]
+    pub ty: Option<String>,
+    /// Initial value expression
+    pub initial: String,
+}
+
+/// A `#[component]` function with documented props and a skeleton `view!`
+pub fn component(spec: &ComponentSpec, version: LeptosVersion) -> Result<String, String> {
+    check_pascal_case(&spec.name, "Component name")?;
+    let element = spec.element.as_deref().unwrap_or("div");
+    let is_tag = element.starts_with(|c: char| c.is_ascii_lowercase())
+        && element
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+    if !is_tag {
+        return Err(format!(
+            "Element '{}' must be a lowercase HTML tag, e.g. 'section'",
+            element
+        ));
+    }
+    if VOID_ELEMENTS.contains(&element) {
+        return Err(format!(
+            "<{}> can't have children, so it can't be the root holding the component's content",
+            element
+        ));
+    }
+    // 0.6 collects the attributes in a prop; later versions pass them through
+    let attrs_prop = spec.attrs && version == LeptosVersion::V0_6;
+    for prop in &spec.props {
+        check_snake_case(&prop.name, "Prop name")?;
+        if prop.optional && prop.default.is_some() {
+            return Err(format!(
+                "Prop '{}' can't be both optional and have a default",
+                prop.name
+            ));
+        }
+        if spec.children && prop.name == "children" {
+            return Err("'children' is added by `children: true`; don't list it as a prop".into());
+        }
+        if attrs_prop && prop.name == "attrs" {
+            return Err("'attrs' is added by `attrs: true`; don't list it as a prop".into());
+        }
+    }
+    for signal in &spec.signals {
+        check_snake_case(&signal.name, "Signal name")?;
+        if spec.store && signal.ty.is_none() {
+            return Err(format!(
+                "Signal '{}' needs a type to become a field of the store",
+                signal.name
+            ));
+        }
+    }
+    if spec.store && spec.signals.is_empty() {
+        return Err("`store: true` needs at least one signal to turn into a store field".into());
+    }
+
+    let mut out = String::from("use leptos::prelude::*;\n");
+    if spec.node_ref {
+        out.push_str("use leptos::html;\n");
+    }
+    if spec.store {
+        out.push_str("use reactive_stores::Store;\n\n");
+        out.push_str(&format!("/// State of the {} component.\n", spec.name));
+        out.push_str("#[derive(Store, Clone, Debug)]\n");
+        out.push_str(&format!("pub struct {}State {{\n", spec.name));
+        for signal in &spec.signals {
+            let ty = signal.ty.as_deref().unwrap_or_default();
+            out.push_str(&format!("    pub {}: {},\n", signal.name, ty));
+        }
+        out.push_str("}\n");
+    }
+    out.push('\n');
+    out.push_str(&format!("/// {} component.\n", spec.name));
+    if spec.attrs && !attrs_prop {
+        out.push_str("///\n");
+        out.push_str(
+            "/// HTML attributes given to it, as `attr:id=\"...\"` or after `{..}`, are\n",
+        );
+        out.push_str(&format!("/// set on its root `<{}>`.\n", element));
+    }
+    out.push_str("#[component]\n");
+    out.push_str(&format!("pub fn {}(", spec.name));
+    if spec.props.is_empty() && !spec.children && !attrs_prop {
+        out.push_str(") -> impl IntoView {\n");
+    } else {
+        out.push('\n');
+        for prop in &spec.props {
+            let doc = prop
+                .doc
+                .clone()
+                .unwrap_or_else(|| format!("The `{}` prop.", prop.name));
+            for line in doc.lines() {
+                out.push_str(&format!("    /// {}\n", line.trim()));
+            }
+            let mut attrs = Vec::new();
+            if prop.optional {
+                attrs.push("optional".to_string());
+            }
+            if let Some(default) = &prop.default {
+                attrs.push(format!("default = {}", default));
+            }
+            if prop.into {
+                attrs.push("into".to_string());
+            }
+            if !attrs.is_empty() {
+                out.push_str(&format!("    #[prop({})]\n", attrs.join(", ")));
+            }
+            out.push_str(&format!("    {}: {},\n", prop.name, prop.ty));
+        }
+        if spec.children {
+            out.push_str("    /// Content rendered inside the component.\n");
+            out.push_str("    children: Children,\n");
+        }
+        if attrs_prop {
+            out.push_str("    /// Attributes given with `attr:`, set on the root element.\n");
+            out.push_str("    #[prop(attrs)]\n");
+            out.push_str("    attrs: Vec<(&'static str, Attribute)>,\n");
+        }
+        out.push_str(") -> impl IntoView {\n");
+    }
+
+    if spec.store {
+        out.push_str(&format!(
+            "    let state = Store::new({}State {{\n",
+            spec.name
+        ));
+        for signal in &spec.signals {
+            out.push_str(&format!("        {}: {},\n", signal.name, signal.initial));
+        }
+        out.push_str("    });\n");
+    }
+    for signal in spec.signals.iter().filter(|_| !spec.store) {
+        let turbofish = signal
+            .ty
+            .as_ref()
+            .map_or(String::new(), |ty| format!("::<{}>", ty));
+        // One handle, so a scaffold that only reads it leaves no setter unused
+        out.push_str(&format!(
+            "    let {name} = RwSignal{turbofish}::new({initial});\n",
+            name = signal.name,
+            turbofish = turbofish,
+            initial = signal.initial
+        ));
+    }
+    if !spec.signals.is_empty() {
+        out.push('\n');
+    }
+    if spec.node_ref {
+        out.push_str(&format!(
+            "    let root_ref = NodeRef::<html::{}>::new();\n",
+            element_type(element)
+        ));
+        out.push_str("    Effect::new(move |_| {\n");
+        out.push_str("        if let Some(root) = root_ref.get() {\n");
+        out.push_str(&format!(
+            "            // `root` is the mounted `web_sys::{}`\n",
+            dom_interface(element)
+        ));
+        out.push_str(
+            "            leptos::logging::log!(\"mounted <{}>\", root.tag_name().to_lowercase());\n",
+        );
+        out.push_str("        }\n");
+        out.push_str("    });\n\n");
+    }
+
+    let mut root_attrs = Vec::new();
+    if spec.node_ref {
+        root_attrs.push("node_ref=root_ref".to_string());
+    }
+    root_attrs.push(format!("class=\"{}\"", kebab_case(&spec.name)));
+    if attrs_prop {
+        root_attrs.push("{..attrs}".to_string());
+    }
+    out.push_str("    view! {\n");
+    out.push_str(&format!("        <{} {}>\n", element, root_attrs.join(" ")));
+    for signal in &spec.signals {
+        let read = if spec.store {
+            format!("state.{}()", signal.name)
+        } else {
+            signal.name.clone()
+        };
+        out.push_str(&format!("            <p>{{move || {}.get()}}</p>\n", read));
+    }
+    if spec.children {
+        out.push_str("            {children()}\n");
+    }
+    if spec.signals.is_empty() && !spec.children {
+        out.push_str(&format!("            \"{}\"\n", spec.name));
+    }
+    out.push_str(&format!("        </{}>\n", element));
+    out.push_str("    }\n");
+    out.push_str("}\n");
+    Ok(out)
+}
+
+/// The Cargo dependency `store: true` components need
+pub fn store_dependency(version: LeptosVersion) -> String {
+    let crate_version = match version {
+        LeptosVersion::V0_7 => "0.1",
+        _ => "0.2",
+    };
+    format!(
+        "# Cargo.toml: stores live in their own crate\n[dependencies]\nreactive_stores = \"{}\"\n",
+        crate_version
+    )
+}
+
+/// Words Rust reserves, which can't name a generated item, field or parameter
+const KEYWORDS: &[&str] = &[
+    "_", "abstract", "as", "async", "await", "become", "box", "break", "const", "continue",
+    "crate", "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if",
+    "impl", "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub",
+    "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "try", "type",
+    "typeof", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+fn check_not_keyword(name: &str, what: &str) -> Result<(), String> {
+    match KEYWORDS.contains(&name) {
+        true => Err(format!(
+            "{} '{}' is a Rust keyword; pick another name",
+            what, name
+        )),
+        false => Ok(()),
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn check_pascal_case(name: &str, what: &str) -> Result<(), String> {
+    check_not_keyword(name, what)?;
+    if is_identifier(name)
+        && name.starts_with(|c: char| c.is_ascii_uppercase())
+        && !name.contains('_')
+    {
+        Ok(())
+    } else {
+        Err(format!(
+            "{} '{}' must be PascalCase, e.g. 'UserCard'",
+            what, name
+        ))
+    }
+}
+
+fn check_snake_case(name: &str, what: &str) -> Result<(), String> {
+    check_not_keyword(name, what)?;
+    if is_identifier(name) && !name.chars().any(|c| c.is_ascii_uppercase()) {
+        Ok(())
+    } else {
+        Err(format!(
+            "{} '{}' must be snake_case, e.g. 'user_id'",
+            what, name
+        ))
+    }
+}
+
+/// `UserCard` → `user-card`
+fn kebab_case(name: &str) -> String {
+    let mut out = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i > 0 {
+                out.push('-');
+            }
+            out.push(c.to_ascii_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Arguments of `generate-server-fn`
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ServerFnSpec {
+    /// snake_case function name, e.g. `create_todo`
+    pub name: String,
+    /// Function arguments; they become the `ActionForm`'s input fields
+    #[serde(default)]
+    pub inputs: Vec<FieldSpec>,
+    /// Success type `T` of `Result<T, ServerFnError>`
+    #[serde(default = "unit_type")]
+    pub returns: String,
+    /// Server integration; defaults to the project context's backend, then Axum
+    #[serde(default)]
+    pub backend: Option<Backend>,
+    /// App state type the function reads, like `SqlitePool`: adds its
+    /// extraction and the main.rs setup that injects it
+    #[serde(default)]
+    pub state: Option<String>,
+}
+
+/// A named, typed value
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct FieldSpec {
+    /// snake_case name
+    pub name: String,
+    /// Owned, serializable Rust type, e.g. `String` or `u32`
+    #[serde(rename = "type")]
+    pub ty: String,
+}
+
+/// Server integration crate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Backend {
+    #[default]
+    Axum,
+    Actix,
+}
+
+impl Backend {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Axum => "axum",
+            Self::Actix => "actix",
+        }
+    }
+}
+
+fn unit_type() -> String {
+    "()".to_string()
+}
+
+/// Types that already implement `Serialize`/`Deserialize`
+const SERDE_TYPES: &[&str] = &[
+    "String", "bool", "char", "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32",
+    "u64", "u128", "usize", "f32", "f64",
+];
+
+/// The server function, its ActionForm usage and the Cargo feature setup,
+/// and with a state type the server setup injecting it
+///
+/// `backend` is used when the spec doesn't name one.
+pub fn server_fn(spec: &ServerFnSpec, backend: Backend) -> Result<Vec<String>, String> {
+    let backend = spec.backend.unwrap_or(backend);
+    check_snake_case(&spec.name, "Server function name")?;
+    for input in &spec.inputs {
+        check_snake_case(&input.name, "Input name")?;
+        if input.ty.trim_start().starts_with('&') {
+            return Err(format!(
+                "Input '{}' must be an owned type; server function arguments are deserialized",
+                input.name
+            ));
+        }
+    }
+    let state = spec.state.as_deref().map(str::trim);
+    if let Some(state) = state {
+        if state.is_empty() || state.starts_with('&') {
+            return Err(format!(
+                "State type '{}' must be an owned type; it is cloned into every request",
+                state
+            ));
+        }
+    }
+    let type_name = crate::project::camel_case(&spec.name);
+    let returns = spec.returns.trim();
+    let mut blocks = vec![
+        server_fn_definition(spec, returns, backend, state),
+        action_form(spec, &type_name),
+        cargo_features(backend),
+    ];
+    blocks.extend(state.map(|state| state_setup(state, backend)));
+    Ok(blocks)
+}
+
+fn server_fn_definition(
+    spec: &ServerFnSpec,
+    returns: &str,
+    backend: Backend,
+    state: Option<&str>,
+) -> String {
+    let extract = match backend {
+        Backend::Axum => "leptos_axum::extract",
+        Backend::Actix => "leptos_actix::extract",
+    };
+    let mut out = String::from("// Server function: shared by the client and server builds\n");
+    out.push_str("use leptos::prelude::*;\n");
+
+    // A custom return type needs a serializable DTO
+    let is_custom = is_identifier(returns)
+        && returns.starts_with(|c: char| c.is_ascii_uppercase())
+        && !SERDE_TYPES.contains(&returns);
+    if is_custom {
+        out.push_str("use serde::{Deserialize, Serialize};\n\n");
+        out.push_str("#[derive(Clone, Debug, Serialize, Deserialize)]\n");
+        out.push_str(&format!(
+            "pub struct {} {{\n    // TODO: fields\n}}\n\n",
+            returns
+        ));
+    } else {
+        out.push('\n');
+    }
+
+    let args: Vec<String> = spec
+        .inputs
+        .iter()
+        .map(|i| {
+            // Unchecked checkboxes aren't submitted at all, so default them to false
+            let default = if i.ty.trim() == "bool" {
+                "#[server(default)] "
+            } else {
+                ""
+            };
+            format!("{}{}: {}", default, i.name, i.ty)
+        })
+        .collect();
+    out.push_str("#[server]\n");
+    out.push_str(&format!(
+        "pub async fn {}({}) -> Result<{}, ServerFnError> {{\n",
+        spec.name,
+        args.join(", "),
+        returns
+    ));
+    out.push_str(
+        "    // Only compiled with the `ssr` feature: server-only crates are safe here.\n",
+    );
+    out.push_str(&format!(
+        "    // Request data: `let headers: http::HeaderMap = {}().await?;`\n",
+        extract
+    ));
+    match (state, backend) {
+        (Some(state), Backend::Axum) => out.push_str(&format!(
+            "    // Provided by `leptos_routes_with_context` in main.rs\n    let state = expect_context::<{}>();\n",
+            state
+        )),
+        (Some(state), Backend::Actix) => out.push_str(&format!(
+            "    // Registered with `app_data` in main.rs\n    let state: actix_web::web::Data<{}> = {}().await?;\n",
+            state, extract
+        )),
+        (None, _) => {}
+    }
+    out.push_str("    todo!()\n}\n");
+    out
+}
+
+/// The main.rs changes that make `state` available to server functions
+fn state_setup(state: &str, backend: Backend) -> String {
+    match backend {
+        Backend::Axum => format!(
+            "// main.rs: provide the state to server functions and rendering\n\
+             let state: {} = todo!(\"create the state\");\n\n\
+             let app = Router::new()\n    \
+                 .leptos_routes_with_context(\n        \
+                     &leptos_options,\n        \
+                     routes,\n        \
+                     {{\n            \
+                         let state = state.clone();\n            \
+                         move || provide_context(state.clone())\n        \
+                     }},\n        \
+                     {{\n            \
+                         let leptos_options = leptos_options.clone();\n            \
+                         move || shell(leptos_options.clone())\n        \
+                     }},\n    \
+                 )\n    \
+                 .fallback(leptos_axum::file_and_error_handler(shell))\n    \
+                 .with_state(leptos_options);\n",
+            state
+        ),
+        Backend::Actix => format!(
+            "// main.rs: register the state for `web::Data` extraction\n\
+             let state: {} = todo!(\"create the state\");\n\n\
+             HttpServer::new(move || {{\n    \
+                 // ...\n    \
+                 actix_web::App::new()\n        \
+                     .app_data(web::Data::new(state.clone()))\n        \
+                     // .service(...) and .leptos_routes(...) as before\n\
+             }})\n",
+            state
+        ),
+    }
+}
+
+fn action_form(spec: &ServerFnSpec, type_name: &str) -> String {
+    let mut out = String::from("// Usage: submit the server function from a form\n");
+    out.push_str(&format!(
+        "#[component]\npub fn {}Form() -> impl IntoView {{\n",
+        type_name
+    ));
+    out.push_str(&format!(
+        "    let action = ServerAction::<{}>::new();\n",
+        type_name
+    ));
+    out.push_str("    let pending = action.pending();\n");
+    out.push_str("    let value = action.value();\n\n");
+    out.push_str("    view! {\n        <ActionForm action=action>\n");
+    for input in &spec.inputs {
+        out.push_str(&format!("            {}\n", form_input(input)));
+    }
+    out.push_str("            <button type=\"submit\" disabled=pending>\"Submit\"</button>\n");
+    out.push_str("        </ActionForm>\n");
+    out.push_str("        {move || match value.get() {\n");
+    out.push_str("            Some(Ok(_)) => \"Saved\".to_string(),\n");
+    out.push_str("            Some(Err(e)) => format!(\"Error: {}\", e),\n");
+    out.push_str("            None => String::new(),\n");
+    out.push_str("        }}\n    }\n}\n\n");
+
+    let fields: Vec<String> = spec
+        .inputs
+        .iter()
+        .map(|i| format!("{}: todo!()", i.name))
+        .collect();
+    let literal = if fields.is_empty() {
+        format!("{} {{}}", type_name)
+    } else {
+        format!("{} {{ {} }}", type_name, fields.join(", "))
+    };
+    out.push_str(&format!(
+        "// Or dispatch without a form: action.dispatch({});\n",
+        literal
+    ));
+    out
+}
+
+/// An `<input>` whose `name` matches the argument so ActionForm can deserialize it
+fn form_input(input: &FieldSpec) -> String {
+    let ty = input.ty.trim();
+    let inner = ty
+        .strip_prefix("Option<")
+        .and_then(|t| t.strip_suffix('>'))
+        .unwrap_or(ty);
+    let kind = match inner {
+        "bool" => "checkbox",
+        t if t.starts_with(['i', 'u', 'f']) && SERDE_TYPES.contains(&t) => "number",
+        _ => "text",
+    };
+    let required = if ty.starts_with("Option<") || kind == "checkbox" {
+        ""
+    } else {
+        " required"
+    };
+    let value = if kind == "checkbox" {
+        " value=\"true\""
+    } else {
+        ""
+    };
+    format!(
+        "<input type=\"{}\" name=\"{}\"{}{} />",
+        kind, input.name, value, required
+    )
+}
+
+fn cargo_features(backend: Backend) -> String {
+    let (deps, ssr) = match backend {
+        Backend::Axum => (
+            "leptos_axum = { version = \"0.8\", optional = true }\n\
+             axum = { version = \"0.8\", optional = true }\n\
+             tokio = { version = \"1\", features = [\"rt-multi-thread\"], optional = true }\n",
+            "ssr = [\"dep:axum\", \"dep:tokio\", \"dep:leptos_axum\", \"leptos/ssr\"]",
+        ),
+        Backend::Actix => (
+            "leptos_actix = { version = \"0.8\", optional = true }\n\
+             actix-web = { version = \"4\", optional = true }\n",
+            "ssr = [\"dep:actix-web\", \"dep:leptos_actix\", \"leptos/ssr\"]",
+        ),
+    };
+    format!(
+        "# Cargo.toml: server-only crates are optional and enabled by `ssr`\n\
+         [dependencies]\n\
+         leptos = \"0.8\"\n\
+         serde = {{ version = \"1\", features = [\"derive\"] }}\n\
+         {}\n\
+         [features]\n\
+         hydrate = [\"leptos/hydrate\"]\n\
+         {}\n",
+        deps, ssr
+    )
+}
+
+/// Arguments of `generate-error-type`
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ErrorTypeSpec {
+    /// PascalCase enum name
+    #[serde(default = "app_error")]
+    pub name: String,
+    /// Variants; defaults to `NotFound`, `Unauthorized`, `Validation(String)`
+    /// and `Internal(String)`
+    #[serde(default)]
+    pub variants: Vec<VariantSpec>,
+    /// Error types converted into a variant with a `From` impl, so `?` works
+    /// on them
+    #[serde(default)]
+    pub from: Vec<SourceSpec>,
+    /// Leptos version (0.6, 0.7, 0.8); defaults to the session's
+    pub version: Option<String>,
+}
+
+/// A variant of the error enum
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct VariantSpec {
+    /// PascalCase variant name
+    pub name: String,
+    /// `#[error(...)]` message; `{0}` is the payload. Defaults to the name in
+    /// words
+    pub message: Option<String>,
+    /// Type of the payload, if the variant carries one
+    #[serde(rename = "type")]
+    pub ty: Option<String>,
+}
+
+/// An error type converted into a variant
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SourceSpec {
+    /// Path of the type, e.g. `sqlx::Error`
+    #[serde(rename = "type")]
+    pub ty: String,
+    /// Variant it becomes: one with a `String` payload gets the error's
+    /// message, one without a payload drops it
+    pub variant: String,
+}
+
+fn app_error() -> String {
+    "AppError".to_string()
+}
+
+/// Crates only compiled into the server build; `From` impls for their
+/// errors are gated behind `ssr`
+const SERVER_ONLY_CRATES: &[&str] = &[
+    "sqlx",
+    "diesel",
+    "sea_orm",
+    "tokio",
+    "axum",
+    "actix_web",
+    "leptos_axum",
+    "leptos_actix",
+    "reqwest",
+    "argon2",
+    "redis",
+    "tower_sessions",
+    "axum_login",
+    "std::io",
+];
+
+/// Variant wrapping the framework's own failures (Leptos 0.8)
+const SERVER_FN_VARIANT: &str = "ServerFn";
+
+/// The error enum with its server function wiring, a server function using
+/// it, an `ErrorBoundary` fallback showing it and the Cargo dependencies
+pub fn error_type(spec: &ErrorTypeSpec, version: LeptosVersion) -> Result<Vec<String>, String> {
+    check_pascal_case(&spec.name, "Error type name")?;
+    let defaults;
+    let variants = if spec.variants.is_empty() {
+        defaults = default_variants();
+        &defaults
+    } else {
+        &spec.variants
+    };
+    for variant in variants {
+        check_pascal_case(&variant.name, "Variant name")?;
+        if variant.name == SERVER_FN_VARIANT && version >= LeptosVersion::V0_8 {
+            return Err(format!(
+                "Variant name '{}' is reserved for the framework's errors",
+                SERVER_FN_VARIANT
+            ));
+        }
+        let message = variant_message(variant);
+        if variant.ty.is_some() != message.contains("{0}") {
+            return Err(format!(
+                "The message of variant '{}' must contain {{0}} exactly when it has a type",
+                variant.name
+            ));
+        }
+        // 0.6/0.7 parse the message back on the client
+        if version < LeptosVersion::V0_8 && variant.ty.is_some() && !message.ends_with("{0}") {
+            return Err(format!(
+                "The message of variant '{}' must end with {{0}} so Leptos {} can parse it back",
+                variant.name,
+                version.as_str()
+            ));
+        }
+    }
+    for source in &spec.from {
+        let Some(variant) = variants.iter().find(|v| v.name == source.variant) else {
+            return Err(format!(
+                "'from' names variant '{}', which {} doesn't have",
+                source.variant, spec.name
+            ));
+        };
+        if variant
+            .ty
+            .as_deref()
+            .is_some_and(|ty| ty.trim() != "String")
+        {
+            return Err(format!(
+                "Variant '{}' carries a {}; errors convert into a variant with a String or no payload",
+                variant.name,
+                variant.ty.as_deref().unwrap_or_default()
+            ));
+        }
+    }
+    Ok(vec![
+        error_enum(spec, variants, version),
+        error_server_fn(spec, variants, version),
+        error_fallback(spec, version),
+        "# Cargo.toml: thiserror derives Display and Error\n\
+         [dependencies]\n\
+         thiserror = \"2\"\n\
+         serde = { version = \"1\", features = [\"derive\"] }\n"
+            .to_string(),
+    ])
+}
+
+fn default_variants() -> Vec<VariantSpec> {
+    let variant = |name: &str, ty: Option<&str>| VariantSpec {
+        name: name.to_string(),
+        message: None,
+        ty: ty.map(String::from),
+    };
+    vec![
+        variant("NotFound", None),
+        variant("Unauthorized", None),
+        variant("Validation", Some("String")),
+        variant("Internal", Some("String")),
+    ]
+}
+
+/// The `#[error]` message: given, or the variant name in words
+fn variant_message(variant: &VariantSpec) -> String {
+    if let Some(message) = &variant.message {
+        return message.clone();
+    }
+    let words = kebab_case(&variant.name).replace('-', " ");
+    match variant.ty {
+        Some(_) => format!("{}: {{0}}", words),
+        None => words,
+    }
+}
+
+fn error_enum(spec: &ErrorTypeSpec, variants: &[VariantSpec], version: LeptosVersion) -> String {
+    let name = &spec.name;
+    let mut out = String::from("// Error type: shared by the client and server builds\n");
+    if version >= LeptosVersion::V0_8 {
+        out.push_str(
+            "use leptos::server_fn::codec::JsonEncoding;\n\
+             use leptos::server_fn::error::{FromServerFnError, ServerFnErrorErr};\n",
+        );
+    }
+    out.push_str("use serde::{Deserialize, Serialize};\n");
+    if version < LeptosVersion::V0_8 {
+        out.push_str("use std::str::FromStr;\n");
+    }
+    out.push_str(&format!(
+        "\n#[derive(Debug, Clone, Serialize, Deserialize, thiserror::Error)]\npub enum {} {{\n",
+        name
+    ));
+    for variant in variants {
+        out.push_str(&format!(
+            "    #[error(\"{}\")]\n",
+            variant_message(variant).replace('"', "\\\"")
+        ));
+        match &variant.ty {
+            Some(ty) => out.push_str(&format!("    {}({}),\n", variant.name, ty.trim())),
+            None => out.push_str(&format!("    {},\n", variant.name)),
+        }
+    }
+    if version >= LeptosVersion::V0_8 {
+        out.push_str(&format!(
+            "    /// Failures of the call itself: network, encoding, a missing server function\n    \
+             #[error(\"{{0}}\")]\n    {}(ServerFnErrorErr),\n",
+            SERVER_FN_VARIANT
+        ));
+    }
+    out.push_str("}\n\n");
+
+    if version >= LeptosVersion::V0_8 {
+        out.push_str(&format!(
+            "// Lets server functions return `Result<T, {name}>` directly\n\
+             impl FromServerFnError for {name} {{\n    \
+             type Encoder = JsonEncoding;\n\n    \
+             fn from_server_fn_error(value: ServerFnErrorErr) -> Self {{\n        \
+             {name}::{variant}(value)\n    \
+             }}\n\
+             }}\n",
+            name = name,
+            variant = SERVER_FN_VARIANT
+        ));
+    } else {
+        // `ServerFnError<E>` sends `E` as its Display text and parses it back
+        out.push_str(&format!(
+            "// `ServerFnError<{name}>` sends the error as its message and parses it back\n\
+             impl FromStr for {name} {{\n    \
+             type Err = String;\n\n    \
+             fn from_str(message: &str) -> Result<Self, Self::Err> {{\n        \
+             match message {{\n",
+            name = name
+        ));
+        for variant in variants.iter().filter(|v| v.ty.is_none()) {
+            out.push_str(&format!(
+                "            {:?} => Ok({}::{}),\n",
+                variant_message(variant),
+                name,
+                variant.name
+            ));
+        }
+        let payloads: Vec<&VariantSpec> = variants.iter().filter(|v| v.ty.is_some()).collect();
+        if payloads.is_empty() {
+            out.push_str("            _ => Err(format!(\"unknown error: {}\", message)),\n");
+        } else {
+            out.push_str("            _ => None\n");
+            for variant in payloads {
+                let prefix = variant_message(variant).trim_end_matches("{0}").to_string();
+                out.push_str(&format!(
+                    "                .or_else(|| {{\n                    \
+                     let payload = message.strip_prefix({:?})?;\n                    \
+                     payload.parse().ok().map({}::{})\n                \
+                     }})\n",
+                    prefix, name, variant.name
+                ));
+            }
+            out.push_str(
+                "                .ok_or_else(|| format!(\"unknown error: {}\", message)),\n",
+            );
+        }
+        out.push_str("        }\n    }\n}\n");
+    }
+
+    for source in &spec.from {
+        let ty = source.ty.trim();
+        let root = ty.trim_start_matches("::");
+        let server_only = SERVER_ONLY_CRATES
+            .iter()
+            .any(|krate| root.starts_with(&format!("{}::", krate)));
+        let carries = variants
+            .iter()
+            .any(|v| v.name == source.variant && v.ty.is_some());
+        out.push('\n');
+        if server_only {
+            out.push_str("// Server-only: the client build doesn't have this crate\n");
+            out.push_str("#[cfg(feature = \"ssr\")]\n");
+        }
+        let body = if carries {
+            format!("{}::{}(error.to_string())", name, source.variant)
+        } else {
+            format!("{}::{}", name, source.variant)
+        };
+        let binding = if carries { "error" } else { "_error" };
+        out.push_str(&format!(
+            "impl From<{ty}> for {name} {{\n    \
+             fn from({binding}: {ty}) -> Self {{\n        \
+             {body}\n    \
+             }}\n\
+             }}\n",
+            ty = ty,
+            name = name,
+            binding = binding,
+            body = body
+        ));
+    }
+    out
+}
+
+fn error_server_fn(
+    spec: &ErrorTypeSpec,
+    variants: &[VariantSpec],
+    version: LeptosVersion,
+) -> String {
+    let name = &spec.name;
+    let variant = &variants[0];
+    let value = match variant.ty.as_deref().map(str::trim) {
+        None => format!("{}::{}", name, variant.name),
+        Some("String") => format!(
+            "{}::{}(\"id must not be 0\".to_string())",
+            name, variant.name
+        ),
+        Some(_) => format!("{}::{}(Default::default())", name, variant.name),
+    };
+    let prelude = if version >= LeptosVersion::V0_7 {
+        "use leptos::prelude::*;"
+    } else {
+        "use leptos::*;"
+    };
+    let mut out = format!(
+        "// Usage: a server function returning the error\n{}\n\n#[server]\n",
+        prelude
+    );
+    if version >= LeptosVersion::V0_8 {
+        out.push_str(&format!(
+            "pub async fn load_item(id: u32) -> Result<String, {}> {{\n    \
+             if id == 0 {{\n        \
+             return Err({});\n    \
+             }}\n    \
+             // `?` converts errors with a `From` impl\n    \
+             Ok(format!(\"item {{}}\", id))\n\
+             }}\n",
+            name, value
+        ));
+    } else {
+        out.push_str(&format!(
+            "pub async fn load_item(id: u32) -> Result<String, ServerFnError<{}>> {{\n    \
+             if id == 0 {{\n        \
+             return Err({}.into());\n    \
+             }}\n    \
+             // Convert other errors first: `.map_err({}::from)?`\n    \
+             Ok(format!(\"item {{}}\", id))\n\
+             }}\n",
+            name, value, name
+        ));
+    }
+    out
+}
+
+fn error_fallback(spec: &ErrorTypeSpec, version: LeptosVersion) -> String {
+    let name = &spec.name;
+    let (imports, signal, matched) = match version {
+        LeptosVersion::V0_6 => (
+            "use leptos::*;",
+            "RwSignal<Errors>",
+            format!(
+                "match error.downcast_ref::<ServerFnError<{name}>>() {{\n                    \
+                 Some(ServerFnError::WrappedServerError(app_error)) => app_error.to_string(),\n                    \
+                 Some(_) => \"Couldn't reach the server. Try again.\".to_string(),\n                    \
+                 None => error.to_string(),\n                \
+                 }}",
+                name = name
+            ),
+        ),
+        LeptosVersion::V0_7 => (
+            "use leptos::error::Errors;\nuse leptos::prelude::*;",
+            "ArcRwSignal<Errors>",
+            format!(
+                "match error.downcast_ref::<ServerFnError<{name}>>() {{\n                    \
+                 Some(ServerFnError::WrappedServerError(app_error)) => app_error.to_string(),\n                    \
+                 Some(_) => \"Couldn't reach the server. Try again.\".to_string(),\n                    \
+                 None => error.to_string(),\n                \
+                 }}",
+                name = name
+            ),
+        ),
+        LeptosVersion::V0_8 => (
+            "use leptos::error::Errors;\nuse leptos::prelude::*;",
+            "ArcRwSignal<Errors>",
+            format!(
+                "match error.downcast_ref::<{name}>() {{\n                    \
+                 Some({name}::{variant}(_)) => \"Couldn't reach the server. Try again.\".to_string(),\n                    \
+                 Some(app_error) => app_error.to_string(),\n                    \
+                 None => error.to_string(),\n                \
+                 }}",
+                name = name,
+                variant = SERVER_FN_VARIANT
+            ),
+        ),
+    };
+    let function = snake_name(name);
+    format!(
+        "// Fallback: `<ErrorBoundary fallback={function}>` around views rendering a `Result`\n\
+         {imports}\n\n\
+         pub fn {function}(errors: {signal}) -> impl IntoView {{\n    \
+         view! {{\n        \
+         <ul class=\"errors\">\n            \
+         {{move || errors.get().into_iter().map(|(_, error)| {{\n                \
+         let message = {matched};\n                \
+         view! {{ <li>{{message}}</li> }}\n            \
+         }}).collect_view()}}\n        \
+         </ul>\n    \
+         }}\n\
+         }}\n",
+        function = function,
+        imports = imports,
+        signal = signal,
+        matched = matched
+    )
+}
+
+/// `AppError` → `app_error_fallback`
+fn snake_name(name: &str) -> String {
+    format!("{}_fallback", kebab_case(name).replace('-', "_"))
+}
+
+/// Arguments of `generate-route`
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RouteSpec {
+    /// Path pattern, e.g. `/users/:id/posts`; `:name?` is optional and
+    /// `*name` a wildcard
+    pub path: String,
+    /// Page component name; derived from the path when omitted
+    pub component: Option<String>,
+    /// Types of path params keyed by name (default `String`)
+    #[serde(default)]
+    pub param_types: std::collections::BTreeMap<String, String>,
+    /// Prefix of `path` served by a parent layout with an `<Outlet/>`
+    pub parent: Option<String>,
+    /// Emit a `LazyRoute` wrapper so the page is code-split
+    #[serde(default)]
+    pub lazy: bool,
+}
+
+/// A path segment of a route pattern
+enum Segment<'a> {
+    Static(&'a str),
+    /// `:name` or `:name?`
+    Param(&'a str),
+    /// `*name`
+    Wildcard(&'a str),
+}
+
+fn segments(path: &str) -> Result<Vec<Segment<'_>>, String> {
+    if !path.starts_with('/') {
+        return Err(format!("Route path '{}' must start with '/'", path));
+    }
+    path.split('/')
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            let segment = if let Some(name) = s.strip_prefix(':') {
+                Segment::Param(name.trim_end_matches('?'))
+            } else if let Some(name) = s.strip_prefix('*') {
+                Segment::Wildcard(name)
+            } else {
+                Segment::Static(s)
+            };
+            match segment {
+                Segment::Param(name) | Segment::Wildcard(name)
+                    if check_snake_case(name, "Path param").is_err() =>
+                {
+                    Err(format!(
+                        "Path param '{}' in '{}' must be a snake_case identifier",
+                        name, path
+                    ))
+                }
+                other => Ok(other),
+            }
+        })
+        .collect()
+}
+
+/// The route declaration, the page component with typed params, and the
+/// optional lazy-loading wrapper
+pub fn route(spec: &RouteSpec) -> Result<Vec<String>, String> {
+    let all = segments(&spec.path)?;
+    let params: Vec<&str> = all
+        .iter()
+        .filter_map(|s| match s {
+            Segment::Param(name) | Segment::Wildcard(name) => Some(*name),
+            Segment::Static(_) => None,
+        })
+        .collect();
+    if let Some(unknown) = spec
+        .param_types
+        .keys()
+        .find(|k| !params.contains(&k.as_str()))
+    {
+        return Err(format!(
+            "param_types names '{}', which isn't a param of '{}'",
+            unknown, spec.path
+        ));
+    }
+
+    let component = match &spec.component {
+        Some(name) => {
+            check_pascal_case(name, "Component name")?;
+            name.clone()
+        }
+        None => page_name(&all),
+    };
+    let child_path = match &spec.parent {
+        Some(parent) => {
+            let parent = parent.trim_end_matches('/');
+            let rest = spec
+                .path
+                .strip_prefix(parent)
+                .filter(|r| r.is_empty() || r.starts_with('/'));
+            let Some(rest) = rest else {
+                return Err(format!(
+                    "Route path '{}' is not under parent '{}'",
+                    spec.path, parent
+                ));
+            };
+            segments(if parent.is_empty() { "/" } else { parent })?;
+            Some((
+                parent.to_string(),
+                if rest.is_empty() { "" } else { rest }.to_string(),
+            ))
+        }
+        None => None,
+    };
+
+    let view = if spec.lazy {
+        format!("{{Lazy::<{}Route>::new()}}", component)
+    } else {
+        component.clone()
+    };
+    let mut blocks = vec![route_declaration(spec, &view, child_path.as_ref())];
+    blocks.push(page_component(spec, &component, &params));
+    if spec.lazy {
+        blocks.push(lazy_wrapper(&component));
+    }
+    Ok(blocks)
+}
+
+/// `/users/:id/posts` → `UsersPostsPage`
+fn page_name(segments: &[Segment]) -> String {
+    let words: String = segments
+        .iter()
+        .filter_map(|s| match s {
+            Segment::Static(s) => Some(crate::project::camel_case(&s.replace('-', "_"))),
+            _ => None,
+        })
+        .collect();
+    if words.is_empty() {
+        "HomePage".to_string()
+    } else {
+        format!("{}Page", words)
+    }
+}
+
+fn route_declaration(spec: &RouteSpec, view: &str, nested: Option<&(String, String)>) -> String {
+    let mut out = String::from("// Route declaration: inside <Routes> in your App\n");
+    match nested {
+        Some((parent, child)) => {
+            out.push_str(
+                "use leptos_router::{components::{Outlet, ParentRoute, Route}, path};\n\n",
+            );
+            let layout = format!(
+                "{}Layout",
+                page_name(&segments(parent).unwrap_or_default()).trim_end_matches("Page")
+            );
+            out.push_str(&format!(
+                "<ParentRoute path=path!(\"{}\") view={}>\n",
+                if parent.is_empty() { "/" } else { parent },
+                layout
+            ));
+            out.push_str(&format!(
+                "    <Route path=path!(\"{}\") view={} />\n",
+                child, view
+            ));
+            out.push_str("</ParentRoute>\n\n");
+            out.push_str("// The parent renders the matched child where <Outlet/> is placed\n");
+            out.push_str("#[component]\n");
+            out.push_str(&format!("pub fn {}() -> impl IntoView {{\n", layout));
+            out.push_str(
+                "    view! {\n        <div>\n            <Outlet />\n        </div>\n    }\n}\n",
+            );
+        }
+        None => {
+            out.push_str("use leptos_router::{components::Route, path};\n\n");
+            out.push_str(&format!(
+                "<Route path=path!(\"{}\") view={} />\n",
+                spec.path, view
+            ));
+        }
+    }
+    out
+}
+
+fn page_component(spec: &RouteSpec, component: &str, params: &[&str]) -> String {
+    let mut out = String::from("// Page component\nuse leptos::prelude::*;\n");
+    if params.is_empty() {
+        out.push_str(&format!(
+            "\n#[component]\npub fn {}() -> impl IntoView {{\n    view! {{ <h1>\"{}\"</h1> }}\n}}\n",
+            component, component
+        ));
+        return out;
+    }
+
+    let params_struct = format!("{}Params", component.trim_end_matches("Page"));
+    out.push_str("use leptos_router::{hooks::use_params, params::Params};\n\n");
+    out.push_str("#[derive(Params, PartialEq, Clone, Debug)]\n");
+    out.push_str(&format!("pub struct {} {{\n", params_struct));
+    for name in params {
+        let ty = spec.param_types.get(*name).map_or("String", String::as_str);
+        // Params fields are Option: a param can be missing or fail to parse
+        out.push_str(&format!("    pub {}: Option<{}>,\n", name, ty));
+    }
+    out.push_str("}\n\n");
+
+    out.push_str(&format!(
+        "#[component]\npub fn {}() -> impl IntoView {{\n",
+        component
+    ));
+    out.push_str(&format!(
+        "    let params = use_params::<{}>();\n",
+        params_struct
+    ));
+    for name in params {
+        out.push_str(&format!(
+            "    let {name} = move || params.read().as_ref().ok().and_then(|p| p.{name}.clone());\n",
+            name = name
+        ));
+    }
+    out.push_str("\n    view! {\n");
+    out.push_str(&format!("        <h1>\"{}\"</h1>\n", component));
+    for name in params {
+        out.push_str(&format!(
+            "        <p>\"{name}: \" {{move || format!(\"{{:?}}\", {name}())}}</p>\n",
+            name = name
+        ));
+    }
+    out.push_str("    }\n}\n");
+    out
+}
+
+fn lazy_wrapper(component: &str) -> String {
+    format!(
+        "// Lazy-loading wrapper: the page's code is split into its own WASM chunk\n\
+         use leptos_router::{{lazy_route, Lazy, LazyRoute}};\n\n\
+         #[derive(Debug)]\n\
+         pub struct {c}Route;\n\n\
+         #[lazy_route]\n\
+         impl LazyRoute for {c}Route {{\n\
+         \x20   fn data() -> Self {{\n\
+         \x20       // Start data loading here so it runs in parallel with the code download\n\
+         \x20       Self\n\
+         \x20   }}\n\n\
+         \x20   fn view(_this: Self) -> AnyView {{\n\
+         \x20       view! {{ <{c} /> }}.into_any()\n\
+         \x20   }}\n\
+         }}\n",
+        c = component
+    )
+}
+
+/// Arguments of `generate-form`
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct FormSpec {
+    /// snake_case name of the server function handling the form, e.g.
+    /// `create_user`; the component is `CreateUserForm`
+    pub name: String,
+    /// Form fields in display order
+    pub fields: Vec<FormField>,
+}
+
+/// A form field with its validation constraints
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct FormField {
+    /// snake_case field name
+    pub name: String,
+    /// `String`, `bool`, a number type, or an `Option` of one
+    #[serde(rename = "type")]
+    pub ty: String,
+    /// Label text; derived from the name when omitted
+    pub label: Option<String>,
+    /// Must be filled in (checked for `bool`); defaults to true unless the type is an `Option` or `bool`
+    pub required: Option<bool>,
+    /// Minimum characters (`String`)
+    pub min_length: Option<usize>,
+    /// Maximum characters (`String`)
+    pub max_length: Option<usize>,
+    /// Smallest allowed value (numbers)
+    pub min: Option<f64>,
+    /// Largest allowed value (numbers)
+    pub max: Option<f64>,
+    /// Must look like an email address
+    #[serde(default)]
+    pub email: bool,
+}
+
+/// What a field holds, from its declared type
+#[derive(Clone, Copy, PartialEq)]
+enum FieldKind<'a> {
+    Text,
+    Flag,
+    /// A number type, e.g. `u32`
+    Number(&'a str),
+}
+
+/// A field with everything the templates derive from it
+struct Field<'a> {
+    spec: &'a FormField,
+    kind: FieldKind<'a>,
+    optional: bool,
+    label: String,
+    /// Statements of the field's `check_` function; empty when nothing is checked
+    checks: Vec<String>,
+}
+
+impl Field<'_> {
+    /// Whether the field has an error memo: a check, or a number to parse
+    fn validated(&self) -> bool {
+        !self.checks.is_empty() || matches!(self.kind, FieldKind::Number(_))
+    }
+}
+
+/// Validators, the server function that re-runs them, and the form component
+pub fn form(spec: &FormSpec) -> Result<Vec<String>, String> {
+    check_snake_case(&spec.name, "Server function name")?;
+    if spec.fields.is_empty() {
+        return Err("A form needs at least one field".to_string());
+    }
+    let fields = spec
+        .fields
+        .iter()
+        .map(form_field)
+        .collect::<Result<Vec<_>, _>>()?;
+    let type_name = crate::project::camel_case(&spec.name);
+    let mut blocks = Vec::new();
+    if fields.iter().any(|f| !f.checks.is_empty()) {
+        blocks.push(validators(&fields));
+    }
+    blocks.push(validated_server_fn(&spec.name, &fields));
+    blocks.push(form_component(&type_name, &fields));
+    Ok(blocks)
+}
+
+fn form_field(spec: &FormField) -> Result<Field<'_>, String> {
+    check_snake_case(&spec.name, "Field name")?;
+    let ty = spec.ty.trim();
+    let (inner, optional) = match ty.strip_prefix("Option<").and_then(|t| t.strip_suffix('>')) {
+        Some(inner) => (inner.trim(), true),
+        None => (ty, false),
+    };
+    let kind = match inner {
+        "String" => FieldKind::Text,
+        "bool" if !optional => FieldKind::Flag,
+        t if SERDE_TYPES.contains(&t) && t.starts_with(['i', 'u', 'f']) => FieldKind::Number(t),
+        _ => {
+            return Err(format!(
+                "Field '{}' has type '{}'; form fields are String, bool or a number type, optionally in an Option",
+                spec.name, spec.ty
+            ))
+        }
+    };
+    let label = spec.label.clone().unwrap_or_else(|| {
+        let words = spec.name.replace('_', " ");
+        let mut chars = words.chars();
+        chars
+            .next()
+            .map_or_else(String::new, |c| c.to_uppercase().chain(chars).collect())
+    });
+    let misplaced = |constraint: &str, applies: &str| {
+        Err(format!(
+            "'{}' on field '{}' only applies to {} fields",
+            constraint, spec.name, applies
+        ))
+    };
+    if kind != FieldKind::Text {
+        if spec.min_length.is_some() || spec.max_length.is_some() {
+            return misplaced("min_length/max_length", "String");
+        }
+        if spec.email {
+            return misplaced("email", "String");
+        }
+    }
+    if !matches!(kind, FieldKind::Number(_)) && (spec.min.is_some() || spec.max.is_some()) {
+        return misplaced("min/max", "number");
+    }
+
+    let required = spec
+        .required
+        .unwrap_or(!optional && kind != FieldKind::Flag);
+    if required && optional {
+        return Err(format!(
+            "Field '{}' is an Option, so it can't be required; make it '{}'",
+            spec.name, inner
+        ));
+    }
+    let name = &spec.name;
+    let fail = |condition: String, message: String| {
+        format!(
+            "    if {} {{\n        return Err({:?}.to_string());\n    }}\n",
+            condition, message
+        )
+    };
+    let mut checks = Vec::new();
+    match kind {
+        FieldKind::Text => {
+            if required {
+                checks.push(fail(
+                    format!("{}.trim().is_empty()", name),
+                    format!("{} is required", label),
+                ));
+            }
+            if let Some(min) = spec.min_length {
+                checks.push(fail(
+                    format!("{}.chars().count() < {}", name, min),
+                    format!("{} must be at least {} characters", label, min),
+                ));
+            }
+            if let Some(max) = spec.max_length {
+                checks.push(fail(
+                    format!("{}.chars().count() > {}", name, max),
+                    format!("{} must be at most {} characters", label, max),
+                ));
+            }
+            if spec.email {
+                checks.push(fail(
+                    format!(
+                        "!{}.split_once('@').is_some_and(|(user, domain)| !user.is_empty() && domain.contains('.'))",
+                        name
+                    ),
+                    format!("{} must be an email address", label),
+                ));
+            }
+        }
+        FieldKind::Flag => {
+            if required {
+                checks.push(fail(
+                    format!("!{}", name),
+                    format!("{} must be checked", label),
+                ));
+            }
+        }
+        FieldKind::Number(number) => {
+            for (bound, value, op, word) in [
+                ("min", spec.min, "<", "least"),
+                ("max", spec.max, ">", "most"),
+            ] {
+                let Some(value) = value else { continue };
+                let literal = number_literal(value, number).ok_or_else(|| {
+                    format!(
+                        "'{}' of field '{}' must be a valid {} value",
+                        bound, name, number
+                    )
+                })?;
+                checks.push(fail(
+                    format!("{} {} {}", name, op, literal),
+                    format!("{} must be at {} {}", label, word, literal),
+                ));
+            }
+        }
+    }
+    Ok(Field {
+        spec,
+        kind,
+        optional,
+        label,
+        checks,
+    })
+}
+
+/// `value` written as a literal of the number type `ty`, if it fits
+fn number_literal(value: f64, ty: &str) -> Option<String> {
+    if ty.starts_with('f') {
+        return Some(format!("{:?}", value));
+    }
+    let integral = value.fract() == 0.0 && (ty.starts_with('i') || value >= 0.0);
+    integral.then(|| format!("{}", value as i64))
+}
+
+fn validators(fields: &[Field]) -> String {
+    let mut out = String::from(
+        "// Validation: shared by the form and the server function, so keep it in their module\n",
+    );
+    for field in fields.iter().filter(|f| !f.checks.is_empty()) {
+        let param = match field.kind {
+            FieldKind::Text => "&str",
+            FieldKind::Flag => "bool",
+            FieldKind::Number(number) => number,
+        };
+        out.push_str(&format!(
+            "\nfn check_{}({}: {}) -> Result<(), String> {{\n{}    Ok(())\n}}\n",
+            field.spec.name,
+            field.spec.name,
+            param,
+            field.checks.concat()
+        ));
+    }
+    out
+}
+
+fn validated_server_fn(name: &str, fields: &[Field]) -> String {
+    let mut out = String::from("// Server function: the form posts here\n");
+    out.push_str("use leptos::prelude::*;\n\n");
+    let args: Vec<String> = fields
+        .iter()
+        .map(|f| {
+            // Unchecked checkboxes aren't submitted at all, so default them to false
+            let default = if f.kind == FieldKind::Flag {
+                "#[server(default)] "
+            } else {
+                ""
+            };
+            format!("{}{}: {}", default, f.spec.name, f.spec.ty.trim())
+        })
+        .collect();
+    let one_line = args.join(", ");
+    let args = match one_line.len() > 60 {
+        true => format!("\n    {},\n", args.join(",\n    ")),
+        false => one_line,
+    };
+    out.push_str(&format!(
+        "#[server]\npub async fn {}({}) -> Result<(), ServerFnError> {{\n",
+        name, args
+    ));
+    let checks: Vec<String> = fields
+        .iter()
+        .filter(|f| !f.checks.is_empty())
+        .map(|f| {
+            let name = &f.spec.name;
+            match (f.kind, f.optional) {
+                (FieldKind::Text, false) => format!("check_{}(&{})", name, name),
+                (FieldKind::Text, true) => format!(
+                    "{}.as_deref().filter(|v| !v.is_empty()).map_or(Ok(()), check_{})",
+                    name, name
+                ),
+                (_, false) => format!("check_{}({})", name, name),
+                (_, true) => format!("{}.map_or(Ok(()), check_{})", name, name),
+            }
+        })
+        .collect();
+    if !checks.is_empty() {
+        out.push_str("    // The browser's checks can be bypassed, so run them again here\n");
+        out.push_str("    let checks = [\n");
+        for check in &checks {
+            out.push_str(&format!("        {},\n", check));
+        }
+        out.push_str("    ];\n");
+        out.push_str(
+            "    if let Some(message) = checks.into_iter().find_map(Result::err) {\n        return Err(ServerFnError::new(message));\n    }\n\n",
+        );
+    }
+    out.push_str("    // TODO: save the submission\n    Ok(())\n}\n");
+    out
+}
+
+fn form_component(type_name: &str, fields: &[Field]) -> String {
+    let mut out = String::from("// Form component: controlled inputs, checked as the user types\n");
+    out.push_str(&format!(
+        "#[component]\npub fn {}Form() -> impl IntoView {{\n",
+        type_name
+    ));
+    out.push_str(&format!(
+        "    let action = ServerAction::<{}>::new();\n    let pending = action.pending();\n\n",
+        type_name
+    ));
+
+    out.push_str("    // What the user entered, kept in sync with the inputs\n");
+    for field in fields {
+        let initial = match field.kind {
+            FieldKind::Flag => "false",
+            _ => "String::new()",
+        };
+        out.push_str(&format!(
+            "    let {} = RwSignal::new({});\n",
+            field.spec.name, initial
+        ));
+    }
+
+    let validated: Vec<&Field> = fields.iter().filter(|f| f.validated()).collect();
+    if !validated.is_empty() {
+        out.push_str("\n    // Each field's error; shown once the user has left the field\n");
+    }
+    for field in &validated {
+        let name = &field.spec.name;
+        let check = |value: &str| match field.checks.is_empty() {
+            true => "None".to_string(),
+            false => format!("check_{}({}).err()", name, value),
+        };
+        let memo = match (field.kind, field.optional) {
+            (FieldKind::Text, false) => check(&format!("&{}.read()", name)),
+            (FieldKind::Text, true) => format!(
+                "match {}.read().as_str() {{\n        \"\" => None,\n        value => {},\n    }}",
+                name,
+                check("value")
+            ),
+            (FieldKind::Flag, _) => check(&format!("{}.get()", name)),
+            (FieldKind::Number(number), optional) => {
+                let ok = match field.checks.is_empty() {
+                    true => "Ok(_)",
+                    false => "Ok(value)",
+                };
+                let parsed = format!(
+                    "match value.parse::<{}>() {{\n            {} => {},\n            Err(_) => Some({:?}.to_string()),\n        }}",
+                    number,
+                    ok,
+                    check("value"),
+                    format!("{} must be a number", field.label)
+                );
+                match optional {
+                    false => format!(
+                        "{{\n        let value = {}.read();\n        {}\n    }}",
+                        name, parsed
+                    ),
+                    true => format!(
+                        "match {}.read().as_str() {{\n        \"\" => None,\n        value => {},\n    }}",
+                        name, parsed
+                    ),
+                }
+            }
+        };
+        out.push_str(&format!(
+            "    let {}_error = Memo::new(move |_| {});\n",
+            name, memo
+        ));
+        out.push_str(&format!(
+            "    let {}_touched = RwSignal::new(false);\n",
+            name
+        ));
+    }
+    let any_error: Vec<String> = validated
+        .iter()
+        .map(|f| format!("{}_error.read().is_some()", f.spec.name))
+        .collect();
+    if !any_error.is_empty() {
+        out.push_str(&format!(
+            "    let invalid = move || {{\n        {}\n    }};\n",
+            any_error.join("\n            || ")
+        ));
+    }
+
+    out.push_str("\n    view! {\n        <ActionForm action=action>\n");
+    for field in fields {
+        out.push_str(&controlled_input(field));
+    }
+    let disabled = match any_error.is_empty() {
+        true => "pending",
+        false => "move || pending.get() || invalid()",
+    };
+    out.push_str(&format!(
+        "            <button type=\"submit\" disabled={}>\"Submit\"</button>\n",
+        disabled
+    ));
+    out.push_str("        </ActionForm>\n");
+    out.push_str(
+        "        // Errors from the server function, including its own validation\n\
+         \x20       <ErrorBoundary fallback=|errors| {\n\
+         \x20           view! {\n\
+         \x20               <ul class=\"form-errors\">\n\
+         \x20                   {move || {\n\
+         \x20                       errors\n\
+         \x20                           .get()\n\
+         \x20                           .into_iter()\n\
+         \x20                           .map(|(_, error)| view! { <li>{error.to_string()}</li> })\n\
+         \x20                           .collect_view()\n\
+         \x20                   }}\n\
+         \x20               </ul>\n\
+         \x20           }\n\
+         \x20       }>\n\
+         \x20           {move || action.value().get().map(|result| result.map(|_| \"Saved\"))}\n\
+         \x20       </ErrorBoundary>\n",
+    );
+    out.push_str("    }\n}\n");
+    out
+}
+
+/// A labelled input bound to the field's signal, with its error below
+fn controlled_input(field: &Field) -> String {
+    let name = &field.spec.name;
+    let spec = field.spec;
+    let mut attrs = Vec::new();
+    match field.kind {
+        FieldKind::Flag => {
+            attrs.push("type=\"checkbox\"".to_string());
+            attrs.push(format!("name=\"{}\"", name));
+            attrs.push("value=\"true\"".to_string());
+            attrs.push(format!("prop:checked={}", name));
+            attrs.push(format!(
+                "on:change=move |ev| {}.set(event_target_checked(&ev))",
+                name
+            ));
+        }
+        kind => {
+            let input_type = match kind {
+                FieldKind::Number(_) => "number",
+                _ if spec.email => "email",
+                _ => "text",
+            };
+            attrs.push(format!("type=\"{}\"", input_type));
+            attrs.push(format!("name=\"{}\"", name));
+            // Native constraints too, so the browser helps before the first blur
+            if field.checks.iter().any(|c| c.contains("is_empty()"))
+                || (matches!(kind, FieldKind::Number(_)) && !field.optional)
+            {
+                attrs.push("required".to_string());
+            }
+            if let Some(min) = spec.min_length {
+                attrs.push(format!("minlength=\"{}\"", min));
+            }
+            if let Some(max) = spec.max_length {
+                attrs.push(format!("maxlength=\"{}\"", max));
+            }
+            if let FieldKind::Number(number) = kind {
+                for (attr, value) in [("min", spec.min), ("max", spec.max)] {
+                    if let Some(literal) = value.and_then(|v| number_literal(v, number)) {
+                        attrs.push(format!("{}=\"{}\"", attr, literal));
+                    }
+                }
+            }
+            attrs.push(format!("prop:value={}", name));
+            attrs.push(format!(
+                "on:input=move |ev| {}.set(event_target_value(&ev))",
+                name
+            ));
+        }
+    }
+    if field.validated() {
+        attrs.push(format!("on:blur=move |_| {}_touched.set(true)", name));
+    }
+    let mut out = format!(
+        "            <label>\n                {:?}\n                <input\n{}                />\n            </label>\n",
+        field.label,
+        attrs
+            .iter()
+            .map(|attr| format!("                    {}\n", attr))
+            .collect::<String>()
+    );
+    if field.validated() {
+        out.push_str(&format!(
+            "            <p class=\"field-error\">\n                {{move || {n}_touched.get().then(|| {n}_error.get()).flatten()}}\n            </p>\n",
+            n = name
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn server_fn_named(name: &str, input: &str) -> Result<Vec<String>, String> {
+        let spec: ServerFnSpec = serde_json::from_value(json!({
+            "name": name,
+            "inputs": [{ "name": input, "type": "u32" }],
+        }))
+        .unwrap();
+        server_fn(&spec, Backend::Axum)
+    }
+
+    #[test]
+    fn component_signals_are_used() {
+        let spec: ComponentSpec = serde_json::from_value(json!({
+            "name": "Counter",
+            "props": [],
+            "signals": [{ "name": "count", "initial": "0" }],
+        }))
+        .unwrap();
+        let code = component(&spec, LeptosVersion::V0_7).unwrap();
+        assert!(code.contains("let count = RwSignal::new(0);"), "{}", code);
+        assert!(!code.contains("set_count"), "{}", code);
+
+        let spec: ComponentSpec = serde_json::from_value(json!({
+            "name": "Card",
+            "props": [{ "name": "type", "type": "String" }],
+        }))
+        .unwrap();
+        assert!(component(&spec, LeptosVersion::V0_7).is_err());
+    }
+
+    #[test]
+    fn keywords_are_rejected() {
+        assert!(server_fn_named("get_todo", "id").is_ok());
+        let err = server_fn_named("match", "id").unwrap_err();
+        assert!(err.contains("keyword"), "{}", err);
+        assert!(server_fn_named("get_todo", "fn").is_err());
+        assert!(check_pascal_case("Self", "Component name").is_err());
+        assert!(check_snake_case("_", "Input name").is_err());
+    }
+}
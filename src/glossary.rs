@@ -0,0 +1,228 @@
+//! Glossary of Leptos terms
+//!
+//! One-paragraph definitions of the concepts the docs keep referring to
+//! (signal, memo, owner, island, ...), each with the types that implement it
+//! and the section covering it in full. An agent that only needs to know
+//! what a word means gets that without reading a chapter. Definitions target
+//! the latest supported Leptos version.
+
+use crate::docs::similarity;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+/// A defined term
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct Term {
+    pub term: &'static str,
+    /// Other names the term goes by, lowercase
+    pub aliases: &'static [&'static str],
+    pub definition: &'static str,
+    /// Types and functions implementing the concept
+    pub types: &'static [&'static str],
+    /// Documentation section covering the term, as `get-documentation` takes it
+    pub doc: &'static str,
+}
+
+/// Every term, alphabetically
+pub const TERMS: &[Term] = &[
+    Term {
+        term: "action",
+        aliases: &["actions", "server action", "mutation"],
+        definition: "An async task started on demand, usually to change data (submit a form, save, delete), as opposed to a resource, which loads data when its inputs change. `dispatch` runs it; `pending`, `value` and `input` are signals tracking the latest run. `ServerAction` wraps a server function and pairs with `<ActionForm>`.",
+        types: &["Action", "ServerAction", "ActionForm", "MultiAction"],
+        doc: "actions",
+    },
+    Term {
+        term: "any view",
+        aliases: &["anyview", "into_any", "type erasure", "type-erased view"],
+        definition: "A view whose concrete type has been erased, so views with different markup can be returned from the branches of an `if` or `match`, stored in a struct or collected in a `Vec`. `.into_any()` turns any view into an `AnyView`. It replaced 0.6's `View` and `.into_view()`; since 0.7 `View<T>` keeps the type. `Either` avoids the erasure for two branches.",
+        types: &["AnyView", "IntoAny", "Either"],
+        doc: "views#views-as-values",
+    },
+    Term {
+        term: "arena",
+        aliases: &["reactive arena", "slotmap"],
+        definition: "Global storage holding the values of signals, memos and stored values. The handles you pass around are small `Copy` ids into it, which is why signals can be moved into any number of closures. A value lives until its owner is disposed; reading a signal after that panics or returns `None` from `try_get`. `ArcRwSignal` and the other `Arc*` types are reference-counted instead and live outside it.",
+        types: &["ArenaItem", "ArcRwSignal", "ArcMemo", "ArcTrigger"],
+        doc: "signals#creating-signals",
+    },
+    Term {
+        term: "attribute spreading",
+        aliases: &["spread", "spreading", "{..}", "attr:", "attribute forwarding"],
+        definition: "Passing HTML attributes to a component, which applies them to the elements at the root of its view: `attr:id=\"main\"`, or plain attributes after a `{..}` marker. `view! { <{..} role=\"dialog\"/> }` builds a reusable set, spread with `{..name}`. It replaced 0.6's `#[prop(attrs)]`, and makes props that only copy `class` or `id` onto the root unnecessary.",
+        types: &["attr:", "{..}"],
+        doc: "components#forwarding-attributes",
+    },
+    Term {
+        term: "component",
+        aliases: &["components", "#[component]"],
+        definition: "A function annotated with `#[component]` that takes props and returns `impl IntoView`. It runs once to set up its view and reactive graph; afterwards only the signals, memos and effects it created update, never the function itself. Its name is PascalCase, and `view!` uses it like an element.",
+        types: &["#[component]", "IntoView", "Children", "ChildrenFn"],
+        doc: "components",
+    },
+    Term {
+        term: "context",
+        aliases: &["provide_context", "use_context"],
+        definition: "Values made available to a component's descendants without passing props. `provide_context(value)` stores it on the current owner; `use_context::<T>()` or `expect_context::<T>()` walks up the owner tree to find the nearest one of type `T`. Signals are typical context values, since they are `Copy`.",
+        types: &["provide_context", "use_context", "expect_context"],
+        doc: "components#sharing-state-with-context",
+    },
+    Term {
+        term: "derived signal",
+        aliases: &["derived signals", "derived state"],
+        definition: "A plain closure that reads signals, such as `move || count.get() * 2`. It recomputes on every read and tracks whatever it reads, so it is the cheapest way to compute one value from others. Use a memo instead when the computation is expensive or many places read it. `Signal::derive` wraps one so it can be stored or passed as a prop.",
+        types: &["Signal::derive", "Signal<T>"],
+        doc: "signals#derived-signals",
+    },
+    Term {
+        term: "effect",
+        aliases: &["effects", "side effect"],
+        definition: "A closure that re-runs whenever the signals it read last time change, for synchronizing with the world outside the reactive system: logging, local storage, imperative DOM or JS calls. Effects run only in the browser, after rendering, never during server rendering. Don't use one to set signals from other signals; derive the value instead.",
+        types: &["Effect::new", "Effect::watch", "RenderEffect"],
+        doc: "signals#signal-dependencies",
+    },
+    Term {
+        term: "fine-grained reactivity",
+        aliases: &["reactivity", "reactive graph", "reactive system"],
+        definition: "Leptos's update model. Signals are the sources, memos and derived signals compute from them, and effects and the rendered DOM subscribe to what they read. A change re-runs only the subscribers that depend on it and patches just the affected DOM nodes; there is no virtual DOM and components don't re-render.",
+        types: &["ReadSignal", "Memo", "Effect"],
+        doc: "signals",
+    },
+    Term {
+        term: "hydration",
+        aliases: &["hydrate", "ssr", "server-side rendering", "hydration mismatch"],
+        definition: "Making server-rendered HTML interactive in the browser. The WASM app runs the same components again, walks the existing DOM instead of creating it, and attaches event listeners and reactive bindings. The client's first render must produce exactly the server's markup, so browser APIs, randomness and the current time must not affect it.",
+        types: &["hydrate_body", "HydrationScripts"],
+        doc: "hydration",
+    },
+    Term {
+        term: "island",
+        aliases: &["islands", "#[island]", "islands architecture"],
+        definition: "A component marked `#[island]` in an app built with the `islands` feature. Only islands ship WASM and hydrate; everything else stays static server-rendered HTML, which shrinks the bundle. Props of an island must be serializable, and state shared between islands has to go through context provided by a common island or through the server.",
+        types: &["#[island]", "Islands"],
+        doc: "hydration",
+    },
+    Term {
+        term: "memo",
+        aliases: &["memos", "memoized", "computed"],
+        definition: "A derived value computed from signals and cached. It recomputes when an input changes but notifies its own subscribers only if the new value differs (`PartialEq`). That makes it the right choice for expensive computations and for values read in many places. Reading a memo is tracked like reading a signal.",
+        types: &["Memo::new", "Memo<T>", "ArcMemo", "Selector"],
+        doc: "signals#derived-signals",
+    },
+    Term {
+        term: "node ref",
+        aliases: &["noderef", "node_ref", "element reference", "dom reference"],
+        definition: "A reference to a DOM element, filled when the element it is bound to with `node_ref=` is mounted. It is typed by that element: `NodeRef<html::Input>` returns an `HtmlInputElement` from `.get()`, with no cast. `.get()` is a tracked read, `None` until mount and during server rendering, so read it in an effect or event handler.",
+        types: &["NodeRef", "node_ref="],
+        doc: "views#element-references",
+    },
+    Term {
+        term: "owner",
+        aliases: &["owners", "ownership", "reactive owner", "disposal", "cleanup"],
+        definition: "The node of the reactive tree that whatever is created under it belongs to. Every component, effect and memo runs under an owner, and the signals, effects, resources and context created there are disposed with it when it re-runs or is removed. `on_cleanup` registers work for that moment. Code spawned outside the tree (`spawn_local`, event listeners) has no owner unless one is captured.",
+        types: &["Owner", "on_cleanup"],
+        doc: "components#sharing-state-with-context",
+    },
+    Term {
+        term: "resource",
+        aliases: &["resources", "async data"],
+        definition: "Async data that reloads when the signals its source reads change. `Resource` runs on the server during SSR and serializes its result into the page so the client doesn't fetch again; `LocalResource` runs only in the browser, and `OnceResource` loads once. Read it inside `<Suspense>` or `<Transition>` so the view waits for it.",
+        types: &["Resource", "LocalResource", "OnceResource"],
+        doc: "resources",
+    },
+    Term {
+        term: "server function",
+        aliases: &["server functions", "server fn", "#[server]"],
+        definition: "An async function annotated with `#[server]` whose body only compiles and runs on the server. On the client it becomes an HTTP call to a generated endpoint, so components can call it like any function. Arguments and return values must be serializable, and it returns `Result<T, ServerFnError>` or a custom error type.",
+        types: &["#[server]", "ServerFnError", "ServerAction"],
+        doc: "server-functions",
+    },
+    Term {
+        term: "signal",
+        aliases: &["signals", "reactive state"],
+        definition: "The basic unit of reactive state: a value that notifies whoever read it when it changes. `signal(value)` returns a read and a write handle, and `RwSignal` is both in one. Reading with `get` or `with` inside a reactive context (a view, memo or effect) subscribes it; `set` and `update` notify the subscribers. Handles are `Copy`.",
+        types: &["signal", "ReadSignal", "WriteSignal", "RwSignal", "Signal<T>"],
+        doc: "signals",
+    },
+    Term {
+        term: "store",
+        aliases: &["stores", "reactive store", "reactive_stores"],
+        definition: "Reactive state for nested data with per-field tracking, from the `reactive_stores` crate. Deriving `Store` on a struct lets `store.field()` read or write one field and notify only what read that field, rather than everything that reads the whole struct. Lists inside a store can be keyed for fine-grained updates.",
+        types: &["Store", "#[derive(Store)]", "Field"],
+        doc: "stores",
+    },
+    Term {
+        term: "stored value",
+        aliases: &["storedvalue", "stored_value"],
+        definition: "A non-reactive value kept in the arena so it can be shared among closures as a `Copy` handle. Use it for data that several event handlers or views need but that never changes the view by itself, such as configuration or a large struct you don't want to clone.",
+        types: &["StoredValue::new", "ArcStoredValue"],
+        doc: "views#using-a-value-in-several-closures",
+    },
+    Term {
+        term: "suspense",
+        aliases: &["<suspense>", "loading state", "fallback"],
+        definition: "A component that shows its `fallback` while resources read inside it are loading, then its children. During SSR it can stream: the fallback is sent first, and the resolved HTML follows in the same response.",
+        types: &["Suspense", "Suspend", "Await"],
+        doc: "suspense",
+    },
+    Term {
+        term: "transition",
+        aliases: &["<transition>", "transitions"],
+        definition: "A `<Suspense>` that shows its fallback only the first time. When its resources reload later, it keeps showing the previous content until the new data is ready, avoiding a flash of the loading state. `set_pending` reports whether a reload is in progress.",
+        types: &["Transition"],
+        doc: "suspense#transition-component",
+    },
+    Term {
+        term: "trigger",
+        aliases: &["triggers"],
+        definition: "A signal without a value: `notify()` makes everything that called `track()` on it re-run. Useful for making non-reactive data reactive, such as a value mutated in place behind a `StoredValue`.",
+        types: &["Trigger", "ArcTrigger"],
+        doc: "signals#signal-dependencies",
+    },
+    Term {
+        term: "view macro",
+        aliases: &["view!", "view", "rsx"],
+        definition: "The `view!` macro: HTML-like syntax for building the UI, with components, attributes, `class:` and `style:` bindings and `on:` event handlers. Values in braces are rendered once, while closures and signals in braces become reactive and update their part of the DOM when what they read changes.",
+        types: &["view!", "IntoView", "AnyView"],
+        doc: "views",
+    },
+];
+
+/// How similar a query must be to a term or alias to count as a match
+const MATCH_THRESHOLD: f64 = 0.8;
+
+fn normalize(query: &str) -> String {
+    query.trim().to_lowercase().replace(['_', '-'], " ")
+}
+
+/// Term by name or alias, tolerating typos and plurals
+pub fn get(query: &str) -> Option<&'static Term> {
+    let query = normalize(query);
+    TERMS
+        .iter()
+        .find(|t| t.term == query || t.aliases.iter().any(|a| normalize(a) == query))
+        .or_else(|| {
+            suggest(&query, 1)
+                .into_iter()
+                .find(|t| score(&query, t) >= MATCH_THRESHOLD)
+        })
+}
+
+/// Terms closest to `query`, best first
+pub fn suggest(query: &str, limit: usize) -> Vec<&'static Term> {
+    let query = normalize(query);
+    let mut scored: Vec<(&Term, f64)> = TERMS
+        .iter()
+        .map(|t| (t, score(&query, t)))
+        .filter(|(_, score)| *score > 0.3)
+        .collect();
+    scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+    scored.into_iter().take(limit).map(|(t, _)| t).collect()
+}
+
+fn score(query: &str, term: &Term) -> f64 {
+    term.aliases
+        .iter()
+        .map(|alias| similarity(query, &normalize(alias)))
+        .fold(similarity(query, term.term), f64::max)
+}
@@ -0,0 +1,479 @@
+//! HTML fragment to `view!` conversion
+//!
+//! A forgiving HTML parser (unclosed and stray tags are tolerated the way a
+//! browser would mostly tolerate them) and a printer that emits idiomatic
+//! `view!` markup.
+
+use crate::view::VOID_ELEMENTS;
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+/// Elements whose content is raw text rather than markup
+const RAW_TEXT_ELEMENTS: &[&str] = &["script", "style", "textarea"];
+
+/// How `style="..."` attributes are converted
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum StyleMode {
+    /// Keep a single `style="..."` string
+    #[default]
+    Inline,
+    /// One `style:property="value"` per declaration
+    Properties,
+}
+
+/// Attribute names with their (entity-decoded) values; `None` for bare attributes
+type Attrs = Vec<(String, Option<String>)>;
+
+/// An element still waiting for its closing tag: name, attributes, children so far
+type OpenElement = (String, Attrs, Vec<HtmlNode>);
+
+#[derive(Debug)]
+enum HtmlNode {
+    Element {
+        name: String,
+        attrs: Attrs,
+        children: Vec<HtmlNode>,
+    },
+    Text(String),
+    Comment(String),
+}
+
+/// Convert an HTML fragment into a `view! { ... }` block
+pub fn to_view(html: &str, style: StyleMode) -> String {
+    let nodes = parse(html);
+    let mut out = String::from("view! {\n");
+    for node in &nodes {
+        print_node(node, 1, style, &mut out);
+    }
+    out.push('}');
+    out
+}
+
+fn parse(html: &str) -> Vec<HtmlNode> {
+    // Stack of open elements: (name, attrs, children); index 0 is the fragment root
+    let mut stack: Vec<OpenElement> = vec![(String::new(), Vec::new(), Vec::new())];
+    let mut i = 0;
+    let bytes = html.as_bytes();
+
+    while i < html.len() {
+        if html[i..].starts_with("<!--") {
+            let end = html[i + 4..].find("-->").map_or(html.len(), |p| i + 4 + p);
+            let text = html[i + 4..end].trim().to_string();
+            push_child(&mut stack, HtmlNode::Comment(text));
+            i = (end + 3).min(html.len());
+        } else if html[i..].starts_with("<!") || html[i..].starts_with("<?") {
+            i = html[i..].find('>').map_or(html.len(), |p| i + p + 1);
+        } else if html[i..].starts_with("</") {
+            let end = html[i..].find('>').map_or(html.len(), |p| i + p);
+            let name = normalize_tag(html[i + 2..end].trim());
+            // Close up to the matching open element; ignore stray closers
+            if let Some(depth) = stack.iter().rposition(|(n, _, _)| *n == name) {
+                if depth > 0 {
+                    while stack.len() > depth {
+                        close_top(&mut stack);
+                    }
+                }
+            }
+            i = (end + 1).min(html.len());
+        } else if bytes[i] == b'<' && bytes.get(i + 1).is_some_and(u8::is_ascii_alphabetic) {
+            let (name, attrs, self_closing, next) = parse_open_tag(html, i + 1);
+            i = next;
+            // `<li>One<li>Two` closes the first item, as browsers do
+            while stack.len() > 1
+                && stack
+                    .last()
+                    .is_some_and(|(open, _, _)| implicitly_closed(open, &name))
+            {
+                close_top(&mut stack);
+            }
+            let void = VOID_ELEMENTS.contains(&name.as_str());
+            if RAW_TEXT_ELEMENTS.contains(&name.as_str()) && !self_closing {
+                let close = format!("</{}", name);
+                let end = html[i..]
+                    .to_ascii_lowercase()
+                    .find(&close)
+                    .map_or(html.len(), |p| i + p);
+                let text = html[i..end].to_string();
+                let children = if text.trim().is_empty() {
+                    Vec::new()
+                } else {
+                    vec![HtmlNode::Text(text)]
+                };
+                push_child(
+                    &mut stack,
+                    HtmlNode::Element {
+                        name,
+                        attrs,
+                        children,
+                    },
+                );
+                i = html[end..].find('>').map_or(html.len(), |p| end + p + 1);
+            } else if void || self_closing {
+                push_child(
+                    &mut stack,
+                    HtmlNode::Element {
+                        name,
+                        attrs,
+                        children: Vec::new(),
+                    },
+                );
+            } else {
+                stack.push((name, attrs, Vec::new()));
+            }
+        } else {
+            // Text runs to the next `<` after its first character, which may be one
+            let first = html[i..].chars().next().map_or(1, char::len_utf8);
+            let end = html[i + first..]
+                .find('<')
+                .map_or(html.len(), |p| i + first + p);
+            push_child(&mut stack, HtmlNode::Text(decode_entities(&html[i..end])));
+            i = end;
+        }
+    }
+    while stack.len() > 1 {
+        close_top(&mut stack);
+    }
+    stack
+        .pop()
+        .map(|(_, _, children)| children)
+        .unwrap_or_default()
+}
+
+/// Whether opening `next` ends an open `open` element without a closing tag
+fn implicitly_closed(open: &str, next: &str) -> bool {
+    match open {
+        "li" => next == "li",
+        "dt" | "dd" => matches!(next, "dt" | "dd"),
+        "tr" => next == "tr",
+        "td" | "th" => matches!(next, "td" | "th" | "tr"),
+        "option" => matches!(next, "option" | "optgroup"),
+        "p" => matches!(
+            next,
+            "p" | "div"
+                | "ul"
+                | "ol"
+                | "dl"
+                | "table"
+                | "section"
+                | "article"
+                | "header"
+                | "footer"
+                | "nav"
+                | "form"
+                | "h1"
+                | "h2"
+                | "h3"
+                | "h4"
+                | "h5"
+                | "h6"
+                | "pre"
+                | "blockquote"
+                | "hr"
+        ),
+        _ => false,
+    }
+}
+
+fn push_child(stack: &mut [OpenElement], node: HtmlNode) {
+    if let Some((_, _, children)) = stack.last_mut() {
+        children.push(node);
+    }
+}
+
+fn close_top(stack: &mut Vec<OpenElement>) {
+    if let Some((name, attrs, children)) = stack.pop() {
+        push_child(
+            stack,
+            HtmlNode::Element {
+                name,
+                attrs,
+                children,
+            },
+        );
+    }
+}
+
+/// Tag name, attributes, whether it ended with `/>`, and the index after `>`
+fn parse_open_tag(html: &str, start: usize) -> (String, Attrs, bool, usize) {
+    let bytes = html.as_bytes();
+    let name_end = html[start..]
+        .find(|c: char| c.is_whitespace() || c == '>' || c == '/')
+        .map_or(html.len(), |p| start + p);
+    let name = normalize_tag(&html[start..name_end]);
+    let mut attrs = Vec::new();
+    let mut i = name_end;
+    loop {
+        while i < html.len() && bytes[i].is_ascii_whitespace() {
+            i += 1;
+        }
+        if i >= html.len() {
+            return (name, attrs, false, i);
+        }
+        match bytes[i] {
+            b'>' => return (name, attrs, false, i + 1),
+            b'/' if bytes.get(i + 1) == Some(&b'>') => return (name, attrs, true, i + 2),
+            b'/' => i += 1,
+            _ => {
+                let attr_end = html[i..]
+                    .find(|c: char| c.is_whitespace() || c == '=' || c == '>' || c == '/')
+                    .map_or(html.len(), |p| i + p);
+                let attr = html[i..attr_end].to_string();
+                i = attr_end;
+                while i < html.len() && bytes[i].is_ascii_whitespace() {
+                    i += 1;
+                }
+                if bytes.get(i) != Some(&b'=') {
+                    attrs.push((attr, None));
+                    continue;
+                }
+                i += 1;
+                while i < html.len() && bytes[i].is_ascii_whitespace() {
+                    i += 1;
+                }
+                let value = match bytes.get(i) {
+                    Some(&quote @ (b'"' | b'\'')) => {
+                        let end = html[i + 1..]
+                            .find(quote as char)
+                            .map_or(html.len(), |p| i + 1 + p);
+                        let value = &html[i + 1..end];
+                        i = (end + 1).min(html.len());
+                        value
+                    }
+                    _ => {
+                        let end = html[i..]
+                            .find(|c: char| c.is_whitespace() || c == '>')
+                            .map_or(html.len(), |p| i + p);
+                        let value = &html[i..end];
+                        i = end;
+                        value
+                    }
+                };
+                attrs.push((attr, Some(decode_entities(value))));
+            }
+        }
+    }
+}
+
+/// HTML tag names are case-insensitive, but `view!` treats capitalized names as components
+fn normalize_tag(name: &str) -> String {
+    if name.starts_with(|c: char| c.is_ascii_uppercase()) {
+        name.to_ascii_lowercase()
+    } else {
+        name.to_string()
+    }
+}
+
+fn decode_entities(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+    let mut out = String::new();
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let Some(semi) = rest.bytes().take(10).position(|b| b == b';') else {
+            out.push('&');
+            rest = &rest[1..];
+            continue;
+        };
+        let entity = &rest[1..semi];
+        let decoded = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some('\u{a0}'),
+            _ => entity
+                .strip_prefix("#x")
+                .or_else(|| entity.strip_prefix("#X"))
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .or_else(|| entity.strip_prefix('#').and_then(|d| d.parse().ok()))
+                .and_then(char::from_u32),
+        };
+        match decoded {
+            Some(c) => {
+                out.push(c);
+                rest = &rest[semi + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn print_node(node: &HtmlNode, depth: usize, style: StyleMode, out: &mut String) {
+    let indent = "    ".repeat(depth);
+    match node {
+        HtmlNode::Comment(text) => {
+            for line in text.lines() {
+                out.push_str(&format!("{}// {}\n", indent, line.trim()));
+            }
+        }
+        HtmlNode::Text(text) => {
+            if let Some(text) = text_literal(text) {
+                out.push_str(&format!("{}{}\n", indent, text));
+            }
+        }
+        HtmlNode::Element {
+            name,
+            attrs,
+            children,
+        } => {
+            let mut rendered = Vec::new();
+            for (attr, value) in attrs {
+                convert_attr(attr, value.as_deref(), style, &mut rendered, out, &indent);
+            }
+            let open = if rendered.is_empty() {
+                format!("<{}", name)
+            } else {
+                format!("<{} {}", name, rendered.join(" "))
+            };
+
+            if RAW_TEXT_ELEMENTS.contains(&name.as_str()) {
+                match children.first() {
+                    Some(HtmlNode::Text(raw)) => out.push_str(&format!(
+                        "{}{}>{}</{}>\n",
+                        indent,
+                        open,
+                        raw_string(raw),
+                        name
+                    )),
+                    _ => out.push_str(&format!("{}{}></{}>\n", indent, open, name)),
+                }
+                return;
+            }
+            let printable: Vec<&HtmlNode> = children
+                .iter()
+                .filter(|c| !matches!(c, HtmlNode::Text(t) if text_literal(t).is_none()))
+                .collect();
+            match printable.as_slice() {
+                [] => out.push_str(&format!("{}{} />\n", indent, open)),
+                [HtmlNode::Text(text)] => out.push_str(&format!(
+                    "{}{}>{}</{}>\n",
+                    indent,
+                    open,
+                    text_literal(text).unwrap_or_default(),
+                    name
+                )),
+                _ => {
+                    out.push_str(&format!("{}{}>\n", indent, open));
+                    for child in printable {
+                        print_node(child, depth + 1, style, out);
+                    }
+                    out.push_str(&format!("{}</{}>\n", indent, name));
+                }
+            }
+        }
+    }
+}
+
+/// Push the `view!` form of one attribute, or a TODO comment for event handlers
+fn convert_attr(
+    attr: &str,
+    value: Option<&str>,
+    style: StyleMode,
+    rendered: &mut Vec<String>,
+    out: &mut String,
+    indent: &str,
+) {
+    let lower = attr.to_ascii_lowercase();
+    if let Some(event) = lower.strip_prefix("on").filter(|e| !e.is_empty()) {
+        out.push_str(&format!(
+            "{}// TODO: port {}={:?} to `on:{}=move |ev| {{ ... }}`\n",
+            indent,
+            attr,
+            value.unwrap_or_default(),
+            event
+        ));
+        return;
+    }
+    match (lower.as_str(), value) {
+        ("style", Some(css)) if style == StyleMode::Properties => {
+            for declaration in css.split(';') {
+                if let Some((property, value)) = declaration.split_once(':') {
+                    rendered.push(format!(
+                        "style:{}={}",
+                        property.trim(),
+                        string_literal(value.trim())
+                    ));
+                }
+            }
+        }
+        // `class` and `style` are kept as plain strings; see class:/style: for reactive values
+        (_, Some(value)) => rendered.push(format!("{}={}", attr, string_literal(value))),
+        (_, None) => rendered.push(format!("{}=true", attr)),
+    }
+}
+
+/// A quoted text node with whitespace collapsed, or None if it's only whitespace
+fn text_literal(text: &str) -> Option<String> {
+    if text.trim().is_empty() {
+        return None;
+    }
+    let collapsed: String = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    // Keep a separating space unless the whitespace was a line break
+    let leading = text.starts_with(char::is_whitespace)
+        && !text[..text.len() - text.trim_start().len()].contains('\n');
+    let trailing =
+        text.ends_with(char::is_whitespace) && !text[text.trim_end().len()..].contains('\n');
+    Some(string_literal(&format!(
+        "{}{}{}",
+        if leading { " " } else { "" },
+        collapsed,
+        if trailing { " " } else { "" }
+    )))
+}
+
+fn string_literal(value: &str) -> String {
+    let mut out = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// `r#"..."#` with enough hashes for the content
+fn raw_string(content: &str) -> String {
+    let mut hashes = 1;
+    while content.contains(&format!("\"{}", "#".repeat(hashes))) {
+        hashes += 1;
+    }
+    let h = "#".repeat(hashes);
+    format!("r{}\"{}\"{}", h, content, h)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn non_ascii_text() {
+        let view = to_view("é", StyleMode::Inline);
+        assert!(view.contains("\"é\""), "{}", view);
+        let view = to_view("ü<p>größer</p>", StyleMode::Inline);
+        assert!(view.contains("\"ü\""), "{}", view);
+        assert!(view.contains("<p>\"größer\"</p>"), "{}", view);
+        let view = to_view("<p>a < ü</p><li>日本語", StyleMode::Inline);
+        assert!(view.contains("日本語"), "{}", view);
+    }
+
+    #[test]
+    fn entities_next_to_non_ascii_text() {
+        assert_eq!(decode_entities("&ééééé;"), "&ééééé;");
+        assert_eq!(decode_entities("a&amp;ü&lt;"), "a&ü<");
+        assert_eq!(decode_entities("&#233;t&eacute"), "ét&eacute");
+    }
+}
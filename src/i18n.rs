@@ -0,0 +1,395 @@
+//! Translatable string extraction
+//!
+//! Finds the user-facing text hard-coded in `view!` bodies: text literals and
+//! the attributes people read, like `placeholder` or `alt`. Each string gets a
+//! translation key scoped by its component, the view code that replaces it
+//! for the i18n crate in use, and an entry for the default locale's file.
+
+use crate::project::SourceFile;
+use crate::rules::position;
+use crate::source::functions;
+use crate::view::{parse_views, Node};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Attributes whose text is shown to (or read out for) the user
+const USER_FACING_ATTRS: &[&str] = &[
+    "placeholder",
+    "title",
+    "alt",
+    "label",
+    "aria-label",
+    "aria-description",
+    "aria-placeholder",
+    "aria-roledescription",
+    "aria-valuetext",
+];
+
+/// Elements whose text isn't prose
+const NON_TEXT_ELEMENTS: &[&str] = &["style", "script", "code", "pre", "kbd"];
+
+/// Words of a string that make up its key
+const KEY_WORDS: usize = 5;
+
+/// Rust keywords a one-word key can't be
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "false",
+    "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref",
+    "return", "self", "static", "struct", "super", "trait", "true", "type", "use", "where",
+    "while", "yield",
+];
+
+/// Translation crate the replacements are written for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+pub enum Library {
+    #[default]
+    #[serde(rename = "leptos_i18n", alias = "leptos-i18n")]
+    LeptosI18n,
+    #[serde(rename = "leptos-fluent", alias = "leptos_fluent")]
+    LeptosFluent,
+}
+
+impl Library {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::LeptosI18n => "leptos_i18n",
+            Self::LeptosFluent => "leptos-fluent",
+        }
+    }
+}
+
+/// A hard-coded string and what replaces it
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct Message {
+    pub key: String,
+    /// The text as the user sees it, escapes resolved
+    pub text: String,
+    /// The literal in the source, quotes included
+    pub original: String,
+    /// View code to put in its place
+    pub replacement: String,
+    /// Attribute holding the text, absent for text nodes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attribute: Option<String>,
+    /// Component or function the view is in
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub function: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub file: String,
+    pub line: usize,
+}
+
+/// Every string found, and the locale file holding them
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct Extraction {
+    pub library: Library,
+    pub messages: Vec<Message>,
+    /// Path of the default locale's file, relative to the project root
+    pub locale_file: String,
+    /// Its content, one entry per key
+    pub locale_content: String,
+    /// What each component using the keys needs
+    pub setup: String,
+}
+
+/// The user-facing strings in `files`' views, keyed for `library`
+pub fn extract(files: &[SourceFile], library: Library, locale: &str) -> Extraction {
+    let mut messages: Vec<Message> = Vec::new();
+    for file in files {
+        let code = file.content.as_str();
+        let items = functions(code);
+        let owner = |offset: usize| {
+            items
+                .iter()
+                .filter(|f| f.body.contains(&offset))
+                .min_by_key(|f| f.body.len())
+                .map_or_else(String::new, |f| f.name.clone())
+        };
+        for view in parse_views(code) {
+            let mut open: Vec<&str> = Vec::new();
+            for node in &view.nodes {
+                let (raw, offset) = match node {
+                    Node::Open(element) => {
+                        if !element.self_closing {
+                            open.push(&element.name);
+                        }
+                        for attr in &element.attrs {
+                            let Some(value) = attr.value.as_deref() else {
+                                continue;
+                            };
+                            if !attr.is_string_literal()
+                                || !USER_FACING_ATTRS.contains(&attr.name.as_str())
+                            {
+                                continue;
+                            }
+                            let raw = &value[1..value.len().saturating_sub(1).max(1)];
+                            let function = owner(attr.offset);
+                            push(
+                                &mut messages,
+                                library,
+                                raw,
+                                Some(&attr.name),
+                                function,
+                                file,
+                                position(code, attr.offset).0,
+                            );
+                        }
+                        continue;
+                    }
+                    Node::Close { name, .. } => {
+                        if let Some(at) = open.iter().rposition(|n| n == name) {
+                            open.truncate(at);
+                        }
+                        continue;
+                    }
+                    Node::Text { offset, text } => (text.as_str(), *offset),
+                    Node::Block { .. } | Node::Comment { .. } => continue,
+                };
+                if open.iter().any(|n| NON_TEXT_ELEMENTS.contains(n)) {
+                    continue;
+                }
+                push(
+                    &mut messages,
+                    library,
+                    raw,
+                    None,
+                    owner(offset),
+                    file,
+                    position(code, offset).0,
+                );
+            }
+        }
+    }
+
+    let (locale_file, locale_content) = match library {
+        Library::LeptosI18n => {
+            let mut root = Map::new();
+            for message in &messages {
+                let Some((scope, name)) = message.key.split_once('.') else {
+                    continue;
+                };
+                let table = root
+                    .entry(scope)
+                    .or_insert_with(|| Value::Object(Map::new()));
+                if let Value::Object(table) = table {
+                    table.insert(name.to_string(), Value::String(message.text.clone()));
+                }
+            }
+            let content = serde_json::to_string_pretty(&Value::Object(root)).unwrap_or_default();
+            (format!("locales/{}.json", locale), content + "\n")
+        }
+        Library::LeptosFluent => {
+            let mut content = String::new();
+            let mut seen = Vec::new();
+            for message in &messages {
+                if seen.contains(&&message.key) {
+                    continue;
+                }
+                seen.push(&message.key);
+                content.push_str(&format!(
+                    "{} = {}\n",
+                    message.key,
+                    fluent_value(&message.text)
+                ));
+            }
+            (format!("locales/{}/main.ftl", locale), content)
+        }
+    };
+    Extraction {
+        library,
+        setup: setup(library, locale),
+        messages,
+        locale_file,
+        locale_content,
+    }
+}
+
+/// Record `raw` if it is text a user reads, reusing the key of the same text
+/// in the same component
+fn push(
+    messages: &mut Vec<Message>,
+    library: Library,
+    raw: &str,
+    attribute: Option<&str>,
+    function: String,
+    file: &SourceFile,
+    line: usize,
+) {
+    let text = unescape(raw);
+    if !text.chars().any(char::is_alphabetic) {
+        return;
+    }
+    // Views outside any function share one scope
+    let scope = match words(&function) {
+        scope if scope.is_empty() => "common".to_string(),
+        scope => scope.join("_"),
+    };
+    let existing = messages
+        .iter()
+        .find(|m| m.function == function && m.text == text)
+        .map(|m| m.key.clone());
+    let key = existing.unwrap_or_else(|| {
+        let mut name: Vec<String> = words(&text).into_iter().take(KEY_WORDS).collect();
+        // A key is a field of the generated i18n module
+        if name.is_empty() || (name.len() == 1 && KEYWORDS.contains(&name[0].as_str())) {
+            name.push("text".to_string());
+        }
+        let base = key_for(library, &scope, &name);
+        let mut key = base.clone();
+        let mut n = 2;
+        while messages.iter().any(|m| m.key == key) {
+            key = format!("{}{}{}", base, separator(library), n);
+            n += 1;
+        }
+        key
+    });
+    let replacement = match (library, attribute) {
+        (Library::LeptosI18n, None) => format!("{{t!(i18n, {})}}", key),
+        (Library::LeptosI18n, Some(attr)) => format!("{}=move || t_string!(i18n, {})", attr, key),
+        (Library::LeptosFluent, None) => format!("{{move_tr!(\"{}\")}}", key),
+        (Library::LeptosFluent, Some(attr)) => format!("{}=move_tr!(\"{}\")", attr, key),
+    };
+    messages.push(Message {
+        key,
+        text,
+        original: format!("\"{}\"", raw),
+        replacement,
+        attribute: attribute.map(String::from),
+        function,
+        file: file.path.display().to_string(),
+        line,
+    });
+}
+
+/// `home_page.welcome_back` for leptos_i18n, `home-page-welcome-back` for Fluent
+fn key_for(library: Library, scope: &str, name: &[String]) -> String {
+    match library {
+        Library::LeptosI18n => format!("{}.{}", scope, name.join("_")),
+        Library::LeptosFluent => {
+            let mut parts: Vec<&str> = scope.split('_').collect();
+            parts.extend(name.iter().map(String::as_str));
+            parts.join("-")
+        }
+    }
+}
+
+fn separator(library: Library) -> char {
+    match library {
+        Library::LeptosI18n => '_',
+        Library::LeptosFluent => '-',
+    }
+}
+
+/// Lowercase ASCII words of a text or a `CamelCase`/`snake_case` name; keys
+/// must be identifiers, and a key starting with a digit gets an `n` prefix
+fn words(text: &str) -> Vec<String> {
+    let mut words: Vec<String> = Vec::new();
+    let mut current = String::new();
+    let mut previous_lower = false;
+    for c in text.chars() {
+        if c.is_ascii_alphanumeric() {
+            if c.is_ascii_uppercase() && previous_lower && !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            previous_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+            current.push(c.to_ascii_lowercase());
+        } else if c == '\'' {
+            // "don't" → "dont"
+        } else {
+            previous_lower = false;
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+        }
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    if let Some(first) = words.first_mut() {
+        if first.starts_with(|c: char| c.is_ascii_digit()) {
+            first.insert(0, 'n');
+        }
+    }
+    words
+}
+
+/// A Rust string literal's content with its escapes resolved
+fn unescape(raw: &str) -> String {
+    let mut out = String::with_capacity(raw.len());
+    let mut chars = raw.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some('0') => out.push('\0'),
+            Some(other) => out.push(other),
+            None => {}
+        }
+    }
+    out
+}
+
+/// A Fluent message value: trimmed, on one line, with braces escaped
+fn fluent_value(text: &str) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '{' => out.push_str("{\"{\"}"),
+            '}' => out.push_str("{\"}\"}"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// What a project and each component need before the replacements compile
+fn setup(library: Library, locale: &str) -> String {
+    match library {
+        Library::LeptosI18n => format!(
+            "// Cargo.toml\n\
+             [dependencies]\n\
+             leptos_i18n = \"0.5\"\n\n\
+             [package.metadata.leptos-i18n]\n\
+             default = \"{locale}\"\n\
+             locales = [\"{locale}\"]\n\n\
+             // Crate root: generate the `i18n` module from the locale files\n\
+             leptos_i18n::load_locales!();\n\n\
+             // Root component: wrap the app in the provider\n\
+             view! {{ <I18nContextProvider>/* app */</I18nContextProvider> }}\n\n\
+             // Every component using the keys\n\
+             use crate::i18n::*;\n\
+             let i18n = use_i18n();\n",
+            locale = locale
+        ),
+        Library::LeptosFluent => format!(
+            "// Cargo.toml\n\
+             [dependencies]\n\
+             leptos-fluent = \"0.2\"\n\
+             fluent-templates = \"0.13\"\n\n\
+             // Load the translations and provide them from a wrapper component\n\
+             fluent_templates::static_loader! {{\n    \
+                 pub static TRANSLATIONS = {{\n        \
+                     locales: \"./locales\",\n        \
+                     fallback_language: \"{locale}\",\n    \
+                 }};\n\
+             }}\n\n\
+             #[component]\n\
+             pub fn I18n(children: Children) -> impl IntoView {{\n    \
+                 leptos_fluent::leptos_fluent! {{\n        \
+                     children: children(),\n        \
+                     translations: [TRANSLATIONS],\n        \
+                     locales: \"./locales\",\n    \
+                 }}\n\
+             }}\n\n\
+             // Every component using the keys\n\
+             use leptos_fluent::move_tr;\n",
+            locale = locale
+        ),
+    }
+}
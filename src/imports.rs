@@ -0,0 +1,367 @@
+//! Import suggestions
+//!
+//! Maps the Leptos, leptos_router, leptos_meta and server integration names a
+//! snippet uses to the `use` statements that bring them into scope for a
+//! Leptos version. Items of the leptos prelude are covered by its glob import;
+//! everything else, including the companion crates, is imported by name.
+
+use crate::docs::LeptosVersion;
+use crate::generate::Backend;
+use crate::source::{mask_literals, split_top_level};
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use std::ops::Range;
+
+/// `(name, module in 0.6, module since 0.7)`, empty where the item doesn't exist
+///
+/// `leptos` is the 0.6 prelude and `leptos::prelude` the later one.
+/// `leptos_axum` stands for the session's server integration crate.
+const SYMBOLS: &[(&str, &str, &str)] = &[
+    // Macros and component building blocks
+    ("view", "leptos", "leptos::prelude"),
+    ("component", "leptos", "leptos::prelude"),
+    ("server", "leptos", "leptos::prelude"),
+    ("island", "leptos", "leptos::prelude"),
+    ("IntoView", "leptos", "leptos::prelude"),
+    ("IntoAny", "", "leptos::prelude"),
+    ("AnyView", "", "leptos::prelude"),
+    ("View", "leptos", ""),
+    ("Fragment", "leptos", ""),
+    ("CollectView", "leptos", "leptos::prelude"),
+    ("Children", "leptos", "leptos::prelude"),
+    ("ChildrenFn", "leptos", "leptos::prelude"),
+    ("ChildrenFnMut", "leptos", "leptos::prelude"),
+    ("Callback", "leptos", "leptos::prelude"),
+    ("Either", "", "leptos::either"),
+    ("EitherOf3", "", "leptos::either"),
+    ("EitherOf4", "", "leptos::either"),
+    // Reactive primitives
+    ("signal", "", "leptos::prelude"),
+    ("signal_local", "", "leptos::prelude"),
+    ("create_signal", "leptos", ""),
+    ("create_rw_signal", "leptos", ""),
+    ("create_memo", "leptos", ""),
+    ("create_effect", "leptos", ""),
+    ("create_resource", "leptos", ""),
+    ("create_blocking_resource", "leptos", ""),
+    ("create_local_resource", "leptos", ""),
+    ("create_action", "leptos", ""),
+    ("create_server_action", "leptos", ""),
+    ("create_multi_action", "leptos", ""),
+    ("create_node_ref", "leptos", ""),
+    ("create_trigger", "leptos", ""),
+    ("store_value", "leptos", ""),
+    ("watch", "leptos", ""),
+    ("batch", "leptos", ""),
+    ("RwSignal", "leptos", "leptos::prelude"),
+    ("ArcRwSignal", "", "leptos::prelude"),
+    ("ReadSignal", "leptos", "leptos::prelude"),
+    ("WriteSignal", "leptos", "leptos::prelude"),
+    ("Memo", "leptos", "leptos::prelude"),
+    ("Signal", "leptos", "leptos::prelude"),
+    ("MaybeSignal", "leptos", "leptos::prelude"),
+    ("Effect", "", "leptos::prelude"),
+    ("Resource", "leptos", "leptos::prelude"),
+    ("LocalResource", "", "leptos::prelude"),
+    ("OnceResource", "", "leptos::prelude"),
+    ("Action", "leptos", "leptos::prelude"),
+    ("ServerAction", "", "leptos::prelude"),
+    ("MultiAction", "leptos", "leptos::prelude"),
+    ("StoredValue", "leptos", "leptos::prelude"),
+    ("NodeRef", "leptos", "leptos::prelude"),
+    ("Trigger", "leptos", "leptos::prelude"),
+    ("Owner", "leptos", "leptos::prelude"),
+    ("untrack", "leptos", "leptos::prelude"),
+    ("on_cleanup", "leptos", "leptos::prelude"),
+    ("provide_context", "leptos", "leptos::prelude"),
+    ("use_context", "leptos", "leptos::prelude"),
+    ("expect_context", "leptos", "leptos::prelude"),
+    ("ServerFnError", "leptos", "leptos::prelude"),
+    // Control flow and forms
+    ("Show", "leptos", "leptos::prelude"),
+    ("For", "leptos", "leptos::prelude"),
+    ("ForEnumerate", "", "leptos::prelude"),
+    ("Suspense", "leptos", "leptos::prelude"),
+    ("Transition", "leptos", "leptos::prelude"),
+    ("ErrorBoundary", "leptos", "leptos::prelude"),
+    ("Await", "leptos", "leptos::prelude"),
+    ("Portal", "leptos", "leptos::portal"),
+    ("ActionForm", "leptos_router", "leptos::prelude"),
+    ("MultiActionForm", "leptos_router", "leptos::prelude"),
+    ("event_target_value", "leptos", "leptos::prelude"),
+    ("event_target_checked", "leptos", "leptos::prelude"),
+    // Mounting and tasks
+    ("mount_to_body", "leptos", "leptos::mount"),
+    ("mount_to", "leptos", "leptos::mount"),
+    ("hydrate_body", "", "leptos::mount"),
+    ("hydrate_islands", "", "leptos::mount"),
+    ("spawn_local", "leptos", "leptos::task"),
+    ("tick", "", "leptos::task"),
+    // Router
+    ("Router", "leptos_router", "leptos_router::components"),
+    ("Routes", "leptos_router", "leptos_router::components"),
+    ("FlatRoutes", "", "leptos_router::components"),
+    ("Route", "leptos_router", "leptos_router::components"),
+    ("ParentRoute", "", "leptos_router::components"),
+    (
+        "ProtectedRoute",
+        "leptos_router",
+        "leptos_router::components",
+    ),
+    ("ProtectedParentRoute", "", "leptos_router::components"),
+    ("A", "leptos_router", "leptos_router::components"),
+    ("Form", "leptos_router", "leptos_router::components"),
+    ("Outlet", "leptos_router", "leptos_router::components"),
+    ("Redirect", "leptos_router", "leptos_router::components"),
+    ("use_navigate", "leptos_router", "leptos_router::hooks"),
+    ("use_params", "leptos_router", "leptos_router::hooks"),
+    ("use_params_map", "leptos_router", "leptos_router::hooks"),
+    ("use_query", "leptos_router", "leptos_router::hooks"),
+    ("use_query_map", "leptos_router", "leptos_router::hooks"),
+    ("use_location", "leptos_router", "leptos_router::hooks"),
+    ("path", "", "leptos_router"),
+    ("StaticSegment", "", "leptos_router"),
+    ("ParamSegment", "", "leptos_router"),
+    ("WildcardSegment", "", "leptos_router"),
+    ("SsrMode", "leptos_router", "leptos_router"),
+    ("NavigateOptions", "leptos_router", "leptos_router"),
+    ("Params", "leptos_router", "leptos_router::params"),
+    ("ParamsMap", "leptos_router", "leptos_router::params"),
+    // Meta
+    ("provide_meta_context", "leptos_meta", "leptos_meta"),
+    ("MetaTags", "", "leptos_meta"),
+    ("Title", "leptos_meta", "leptos_meta"),
+    ("Meta", "leptos_meta", "leptos_meta"),
+    ("Link", "leptos_meta", "leptos_meta"),
+    ("Stylesheet", "leptos_meta", "leptos_meta"),
+    ("Script", "leptos_meta", "leptos_meta"),
+    ("Style", "leptos_meta", "leptos_meta"),
+    ("Html", "leptos_meta", "leptos_meta"),
+    ("Body", "leptos_meta", "leptos_meta"),
+    // Server integration
+    ("generate_route_list", "leptos_axum", "leptos_axum"),
+    ("LeptosRoutes", "leptos_axum", "leptos_axum"),
+    ("extract", "leptos_axum", "leptos_axum"),
+    ("redirect", "leptos_axum", "leptos_axum"),
+    ("ResponseOptions", "leptos_axum", "leptos_axum"),
+];
+
+/// A name the snippet uses and where it comes from
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct Import {
+    pub name: String,
+    /// Module to import it from
+    pub module: String,
+    /// Covered by the prelude glob rather than imported by name
+    pub prelude: bool,
+}
+
+/// What a snippet needs imported
+#[derive(Debug, Clone, Default, Serialize, JsonSchema)]
+pub struct Suggestion {
+    /// `use` statements to add, the prelude glob first
+    pub statements: Vec<String>,
+    pub imports: Vec<Import>,
+    /// Known names that already resolve through the snippet's own `use` statements
+    pub already_imported: Vec<String>,
+    /// Known names that don't exist in the target version
+    pub unavailable: Vec<String>,
+}
+
+/// The `use` statements for the Leptos names `code` uses but doesn't import
+pub fn suggest(code: &str, version: LeptosVersion, backend: Backend) -> Suggestion {
+    let masked = mask_literals(code);
+    let (uses, statements) = use_statements(&masked);
+    let defined = definitions(&masked);
+    let prelude = match version {
+        LeptosVersion::V0_6 => "leptos",
+        _ => "leptos::prelude",
+    };
+    let integration = format!("leptos_{}", backend.as_str());
+
+    let mut suggestion = Suggestion::default();
+    let mut seen = HashSet::new();
+    for name in used_names(&masked, &statements) {
+        if defined.contains(name) || !seen.insert(name) {
+            continue;
+        }
+        let Some(&(_, legacy, modern)) = SYMBOLS.iter().find(|(n, _, _)| *n == name) else {
+            continue;
+        };
+        let module = match version {
+            LeptosVersion::V0_6 => legacy,
+            _ => modern,
+        };
+        let module = match module {
+            "" => {
+                suggestion.unavailable.push(name.to_string());
+                continue;
+            }
+            "leptos_axum" => integration.as_str(),
+            module => module,
+        };
+        if uses.covers(name, module) {
+            suggestion.already_imported.push(name.to_string());
+            continue;
+        }
+        suggestion.imports.push(Import {
+            name: name.to_string(),
+            module: module.to_string(),
+            prelude: module == prelude,
+        });
+    }
+
+    if suggestion.imports.iter().any(|i| i.prelude) {
+        suggestion.statements.push(format!("use {}::*;", prelude));
+    }
+    // Ordered the way rustfmt sorts them: by path, lowercase names first
+    let mut by_module: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for import in suggestion.imports.iter().filter(|i| !i.prelude) {
+        by_module
+            .entry(&import.module)
+            .or_default()
+            .push(&import.name);
+    }
+    let mut statements: Vec<String> = by_module
+        .into_iter()
+        .map(|(module, mut names)| {
+            names.sort_by_key(|n| (n.starts_with(char::is_uppercase), *n));
+            match names.as_slice() {
+                [name] => format!("use {}::{};", module, name),
+                names => format!("use {}::{{{}}};", module, names.join(", ")),
+            }
+        })
+        .collect();
+    statements.sort();
+    suggestion.statements.extend(statements);
+    suggestion
+}
+
+/// Names and globs brought into scope by `use` statements
+#[derive(Default)]
+struct Uses {
+    names: HashSet<String>,
+    globs: HashSet<String>,
+}
+
+impl Uses {
+    fn covers(&self, name: &str, module: &str) -> bool {
+        self.names.contains(name) || self.globs.contains(module)
+    }
+}
+
+/// The `use` statements of already-masked code and their byte ranges
+fn use_statements(masked: &str) -> (Uses, Vec<Range<usize>>) {
+    let mut uses = Uses::default();
+    let mut ranges = Vec::new();
+    for (start, _) in masked.match_indices("use ") {
+        let boundary = masked[..start]
+            .chars()
+            .next_back()
+            .is_none_or(|c| c.is_whitespace() || matches!(c, ';' | '{' | '}'));
+        if !boundary {
+            continue;
+        }
+        let Some(len) = masked[start..].find(';') else {
+            continue;
+        };
+        let tree = masked[start + 4..start + len]
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        expand(tree.trim_start_matches("::"), "", &mut uses);
+        ranges.push(start..start + len);
+    }
+    (uses, ranges)
+}
+
+/// Record the paths of a use tree such as `a::{b, c::*}` under `prefix`
+fn expand(tree: &str, prefix: &str, uses: &mut Uses) {
+    let join = |path: &str| match prefix {
+        "" => path.to_string(),
+        prefix => format!("{}::{}", prefix, path),
+    };
+    let tree = tree.trim();
+    if let Some(open) = tree.find('{') {
+        let base = join(tree[..open].trim().trim_end_matches("::"));
+        let inner = tree[open + 1..]
+            .strip_suffix('}')
+            .unwrap_or(&tree[open + 1..]);
+        for part in split_top_level(inner) {
+            expand(part, &base, uses);
+        }
+    } else if let Some(module) = tree.strip_suffix("::*") {
+        uses.globs.insert(join(module));
+    } else if tree == "*" {
+        uses.globs.insert(prefix.to_string());
+    } else if let Some((_, alias)) = tree.split_once(" as ") {
+        uses.names.insert(alias.trim().to_string());
+    } else if let Some(name) = tree.rsplit("::").next().filter(|n| !n.is_empty()) {
+        uses.names.insert(name.to_string());
+    }
+}
+
+/// Names of items, bindings and generics the snippet declares itself
+fn definitions(masked: &str) -> HashSet<&str> {
+    let words: Vec<&str> = masked
+        .split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '!'))
+        .filter(|w| !w.is_empty())
+        .collect();
+    words
+        .windows(2)
+        .filter(|pair| {
+            matches!(
+                pair[0],
+                "fn" | "struct"
+                    | "enum"
+                    | "type"
+                    | "trait"
+                    | "mod"
+                    | "let"
+                    | "mut"
+                    | "const"
+                    | "static"
+                    | "macro_rules!"
+            )
+        })
+        .map(|pair| pair[1].trim_end_matches('!'))
+        .collect()
+}
+
+/// Unqualified names in expression, type or tag position, outside `use` statements
+///
+/// Lowercase names count only as calls, macros or attributes, so variables
+/// and fields sharing a function's name aren't mistaken for it.
+fn used_names<'a>(masked: &'a str, statements: &[Range<usize>]) -> Vec<&'a str> {
+    let bytes = masked.as_bytes();
+    let is_ident = |b: u8| b.is_ascii_alphanumeric() || b == b'_';
+    let mut names = Vec::new();
+    let mut pos = 0;
+    while pos < bytes.len() {
+        if !is_ident(bytes[pos]) {
+            pos += 1;
+            continue;
+        }
+        let start = pos;
+        while pos < bytes.len() && is_ident(bytes[pos]) {
+            pos += 1;
+        }
+        let name = &masked[start..pos];
+        if statements.iter().any(|r| r.contains(&start)) || name.as_bytes()[0].is_ascii_digit() {
+            continue;
+        }
+        let before = masked[..start].trim_end();
+        if before.ends_with('.') || before.ends_with("::") {
+            continue;
+        }
+        let after = masked[pos..].trim_start();
+        let capitalized = name.as_bytes()[0].is_ascii_uppercase();
+        let attribute = before.ends_with("#[");
+        let call = after.starts_with('(') || after.starts_with('!') && !after.starts_with("!=");
+        if capitalized || attribute || call {
+            names.push(name);
+        }
+    }
+    names
+}
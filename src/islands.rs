@@ -0,0 +1,431 @@
+//! Islands advice
+//!
+//! With the islands architecture only `#[island]` components ship to the
+//! browser and hydrate; everything else renders once on the server. Good
+//! islands are the small interactive leaves of the tree. Their props cross
+//! from server to client, so they must be serializable, and the app only
+//! runs in islands mode with the right features and hydration entry point.
+
+use crate::docs::LeptosVersion;
+use crate::project::SourceFile;
+use crate::reactivity::{accesses, sources, Access, NodeKind};
+use crate::rules::position;
+use crate::source::{attributes_before, functions, mask_literals, typed_params};
+use crate::view::{parse_views, Node};
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// Code that only does something in the browser, with how to describe it
+const CLIENT_CALLS: &[(&str, &str)] = &[
+    ("Effect::new", "runs an effect"),
+    ("create_effect", "runs an effect"),
+    ("RenderEffect::new", "runs an effect"),
+    ("Effect::watch", "runs an effect"),
+    ("NodeRef::new", "uses a NodeRef"),
+    ("create_node_ref", "uses a NodeRef"),
+    ("spawn_local", "spawns a browser task"),
+    (".dispatch(", "dispatches an action"),
+    ("window()", "calls browser APIs"),
+    ("document()", "calls browser APIs"),
+    ("web_sys::", "calls browser APIs"),
+    ("set_interval", "sets a timer"),
+    ("set_timeout", "sets a timer"),
+    ("request_animation_frame", "schedules animation frames"),
+];
+
+/// Reactive handles, which exist only in the runtime that created them
+const REACTIVE_TYPES: &[&str] = &[
+    "ReadSignal",
+    "WriteSignal",
+    "RwSignal",
+    "ArcRwSignal",
+    "Signal",
+    "MaybeSignal",
+    "Memo",
+    "StoredValue",
+    "Trigger",
+    "Resource",
+    "LocalResource",
+    "Action",
+    "ServerAction",
+    "NodeRef",
+];
+
+/// Closure types
+const CLOSURE_TYPES: &[&str] = &["Fn", "FnMut", "FnOnce", "Callback", "UnsyncCallback", "dyn"];
+
+/// View types other than `Children`, which islands take as server-rendered HTML
+const VIEW_TYPES: &[&str] = &[
+    "IntoView",
+    "AnyView",
+    "View",
+    "ViewFn",
+    "ChildrenFn",
+    "ChildrenFnMut",
+    "HtmlElement",
+];
+
+/// What a component should be
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    /// An interactive leaf: make it an `#[island]`
+    Island,
+    /// Interactive, but wraps static components: move the interactive part into an island
+    Split,
+    /// Nothing runs in the browser: keep it a `#[component]`
+    Server,
+}
+
+/// Advice for one component
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct Advice {
+    pub component: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub file: String,
+    pub line: usize,
+    /// Currently declared with `#[island]`
+    pub is_island: bool,
+    pub role: Role,
+    /// What makes it interactive, empty for server-only components
+    pub interactivity: Vec<String>,
+    /// Project components it renders that have no interactivity of their own
+    pub static_children: Vec<String>,
+}
+
+/// A prop an island can't receive from the server
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct PropIssue {
+    pub component: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub file: String,
+    pub line: usize,
+    pub prop: String,
+    #[serde(rename = "type")]
+    pub ty: String,
+    pub reason: String,
+}
+
+/// Advice for a set of source files
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct Report {
+    pub components: Vec<Advice>,
+    /// Props of `#[island]` components and island candidates
+    pub props: Vec<PropIssue>,
+    /// Problems with the hydration entry point or shell found in the sources
+    pub setup_issues: Vec<String>,
+}
+
+impl Report {
+    pub fn with_role(&self, role: Role) -> impl Iterator<Item = &Advice> {
+        self.components.iter().filter(move |a| a.role == role)
+    }
+}
+
+/// Classify every component in `files` and check island props and setup
+pub fn advise(files: &[SourceFile], version: LeptosVersion) -> Report {
+    let structs = serde_types(files);
+    let mut components = Vec::new();
+    let mut children: Vec<Vec<String>> = Vec::new();
+    let mut params: Vec<Vec<(String, String)>> = Vec::new();
+    for file in files {
+        let code = file.content.as_str();
+        let masked = mask_literals(code);
+        for item in functions(code).into_iter().filter(|f| f.is_component()) {
+            components.push(Advice {
+                component: item.name.clone(),
+                file: file.path.display().to_string(),
+                line: position(code, item.params.start).0,
+                is_island: item.has_attr("island"),
+                role: Role::Server,
+                interactivity: interactivity(code, &masked, item.body.clone()),
+                static_children: Vec::new(),
+            });
+            children.push(rendered(code, item.body.clone()));
+            params.push(typed_params(&code[item.params.clone()]));
+        }
+    }
+
+    let interactive: HashMap<String, bool> = components
+        .iter()
+        .map(|a| (a.component.clone(), !a.interactivity.is_empty()))
+        .collect();
+    for (advice, rendered) in components.iter_mut().zip(&children) {
+        for child in rendered {
+            if interactive.get(child) == Some(&false) && !advice.static_children.contains(child) {
+                advice.static_children.push(child.clone());
+            }
+        }
+        advice.role = match (
+            advice.interactivity.is_empty(),
+            advice.static_children.is_empty(),
+        ) {
+            (true, _) => Role::Server,
+            (false, true) => Role::Island,
+            (false, false) => Role::Split,
+        };
+    }
+
+    let mut props = Vec::new();
+    for (advice, params) in components.iter().zip(params) {
+        if !advice.is_island && advice.role != Role::Island {
+            continue;
+        }
+        for (prop, ty) in params {
+            if let Some(reason) = prop_problem(&prop, &ty, &structs) {
+                props.push(PropIssue {
+                    component: advice.component.clone(),
+                    file: advice.file.clone(),
+                    line: advice.line,
+                    prop,
+                    ty,
+                    reason,
+                });
+            }
+        }
+    }
+
+    Report {
+        components,
+        props,
+        setup_issues: setup_issues(files, version),
+    }
+}
+
+/// What in `body` only makes sense in the browser
+fn interactivity(code: &str, masked: &str, body: Range<usize>) -> Vec<String> {
+    let mut found: Vec<String> = Vec::new();
+    let mut add = |reason: String| {
+        if !found.contains(&reason) {
+            found.push(reason);
+        }
+    };
+    for view in parse_views(code)
+        .into_iter()
+        .filter(|v| body.contains(&v.start))
+    {
+        for node in &view.nodes {
+            let Node::Open(element) = node else { continue };
+            for attr in &element.attrs {
+                if attr.name.starts_with("on:") {
+                    add(format!("handles `{}`", attr.name));
+                } else if attr.name.starts_with("bind:") {
+                    add(format!("binds `{}`", attr.name));
+                }
+            }
+        }
+    }
+    for signal in sources(code, masked, body.clone())
+        .iter()
+        .filter(|n| n.kind == NodeKind::Signal)
+    {
+        let written = accesses(masked, body.clone(), signal)
+            .iter()
+            .any(|(offset, access)| {
+                *access == Access::Write && !signal.definition.contains(offset)
+            });
+        if written {
+            add(format!("writes `{}`", signal.name));
+        }
+    }
+    let text = &masked[body];
+    for (call, reason) in CLIENT_CALLS {
+        if text.contains(call) {
+            add(reason.to_string());
+        }
+    }
+    found
+}
+
+/// Components the views in `body` render, including `view=Name` attributes
+fn rendered(code: &str, body: Range<usize>) -> Vec<String> {
+    let mut names = Vec::new();
+    for view in parse_views(code)
+        .into_iter()
+        .filter(|v| body.contains(&v.start))
+    {
+        for node in &view.nodes {
+            let Node::Open(element) = node else { continue };
+            if !element.is_component() {
+                continue;
+            }
+            names.push(short_name(&element.name).to_string());
+            names.extend(
+                element
+                    .attrs
+                    .iter()
+                    .filter_map(|attr| attr.expr())
+                    .filter(|value| {
+                        value.starts_with(|c: char| c.is_ascii_uppercase())
+                            && value.chars().all(|c| c.is_alphanumeric() || c == '_')
+                    })
+                    .map(String::from),
+            );
+        }
+    }
+    names
+}
+
+/// Why an island can't take `prop: ty`, if it can't
+fn prop_problem(prop: &str, ty: &str, structs: &HashMap<String, bool>) -> Option<String> {
+    let words: Vec<&str> = ty
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|w| !w.is_empty())
+        .collect();
+    let has = |list: &[&str]| words.iter().find(|w| list.contains(w)).copied();
+    if has(CLOSURE_TYPES).is_some() {
+        return Some(
+            "closures can't be serialized into the page; handle the event inside the island, or pass the data it needs".to_string(),
+        );
+    }
+    if let Some(handle) = has(REACTIVE_TYPES) {
+        return Some(format!(
+            "a `{}` belongs to the server's reactive runtime; pass the plain value and create the signal inside the island",
+            handle
+        ));
+    }
+    if has(&["web_sys"]).is_some() {
+        return Some("browser objects don't exist while the server renders".to_string());
+    }
+    if has(VIEW_TYPES).is_some() {
+        return Some(
+            "views can't be props of islands; pass server-rendered content as `children: Children`"
+                .to_string(),
+        );
+    }
+    if ty.starts_with('&') {
+        return Some(
+            "borrowed data can't be deserialized in the browser; take an owned value".to_string(),
+        );
+    }
+    if prop == "children" && has(&["Children"]).is_some() {
+        return None;
+    }
+    words
+        .iter()
+        .find(|w| structs.get(**w) == Some(&false))
+        .map(|name| {
+            format!(
+                "`{}` must derive `Serialize` and `Deserialize` to be sent to the browser",
+                name
+            )
+        })
+}
+
+/// Structs and enums declared in `files`, and whether they derive both serde traits
+fn serde_types(files: &[SourceFile]) -> HashMap<String, bool> {
+    let mut types = HashMap::new();
+    for file in files {
+        let code = file.content.as_str();
+        for keyword in ["struct ", "enum "] {
+            for (offset, _) in code.match_indices(keyword) {
+                let line_start = code[..offset].rfind('\n').map_or(0, |i| i + 1);
+                let prefix = code[line_start..offset].trim();
+                if !(prefix.is_empty() || prefix.starts_with("pub")) {
+                    continue;
+                }
+                let name: String = code[offset + keyword.len()..]
+                    .chars()
+                    .take_while(|c| c.is_alphanumeric() || *c == '_')
+                    .collect();
+                if name.is_empty() {
+                    continue;
+                }
+                let derives = attributes_before(code, offset).join(" ");
+                let serde = derives.contains("Serialize") && derives.contains("Deserialize");
+                types.insert(name, serde);
+            }
+        }
+    }
+    types
+}
+
+/// Entry points and shells still set up for full hydration
+fn setup_issues(files: &[SourceFile], version: LeptosVersion) -> Vec<String> {
+    let mut issues = Vec::new();
+    for file in files {
+        let code = file.content.as_str();
+        let path = match file.path.display().to_string() {
+            path if path.is_empty() => "The code".to_string(),
+            path => path,
+        };
+        let whole_app = match version {
+            LeptosVersion::V0_6 => ["mount_to_body", "hydrate_body"]
+                .into_iter()
+                .find(|call| code.contains(&format!("{}(", call))),
+            _ => code.contains("hydrate_body(").then_some("hydrate_body"),
+        };
+        if let Some(call) = whole_app {
+            issues.push(format!(
+                "{} calls `{}`, which hydrates the whole app; {}",
+                path,
+                call,
+                hydrate_step(version)
+            ));
+        }
+        if version != LeptosVersion::V0_6 {
+            let masked = mask_literals(code);
+            for view in parse_views(code) {
+                for node in &view.nodes {
+                    let Node::Open(element) = node else { continue };
+                    if short_name(&element.name) == "HydrationScripts"
+                        && element.attr("islands").is_none()
+                        && !masked[view.body_start..view.body_end].contains("islands")
+                    {
+                        issues.push(format!(
+                            "{} renders `<HydrationScripts/>` without `islands=true`",
+                            path
+                        ));
+                    }
+                }
+            }
+        }
+    }
+    issues
+}
+
+fn hydrate_step(version: LeptosVersion) -> &'static str {
+    match version {
+        LeptosVersion::V0_6 => "call `leptos::leptos_dom::HydrationCtx::stop_hydrating()` in the `hydrate` entry point instead, and islands hydrate themselves",
+        _ => "call `leptos::mount::hydrate_islands()` in the `hydrate` entry point instead",
+    }
+}
+
+/// The steps that turn on islands mode, for `version`
+pub fn setup_steps(version: LeptosVersion) -> Vec<String> {
+    let mut steps = match version {
+        LeptosVersion::V0_6 => vec![
+            "Enable the `experimental-islands` feature on `leptos` and on `leptos_axum` (or `leptos_actix`), next to the usual `ssr`/`hydrate` features".to_string(),
+        ],
+        _ => vec![format!(
+            "Enable the `islands` feature on `leptos` for both the `ssr` and `hydrate` builds: `leptos = {{ version = \"{}\", features = [\"islands\"] }}`",
+            version.as_str()
+        )],
+    };
+    steps.push(match version {
+        LeptosVersion::V0_6 => "In the `hydrate` entry point in `lib.rs`, replace `mount_to_body(App)` with `leptos::leptos_dom::HydrationCtx::stop_hydrating()`".to_string(),
+        _ => "In the `hydrate` entry point in `lib.rs`, call `leptos::mount::hydrate_islands()` instead of `hydrate_body(App)`".to_string(),
+    });
+    if version != LeptosVersion::V0_6 {
+        steps.push(
+            "In the shell, render `<HydrationScripts options islands=true/>` so only island code is loaded"
+                .to_string(),
+        );
+    }
+    steps.push(
+        "Mark only the interactive leaves `#[island]`; everything else stays `#[component]` and never reaches the wasm bundle".to_string(),
+    );
+    steps.push(
+        "Island props are serialized into the HTML and read back in the browser, so they must implement `Serialize` and `Deserialize`; `children: Children` is rendered on the server and passed in as HTML".to_string(),
+    );
+    steps.push(
+        "Context provided by server-only components isn't there when the island hydrates; provide it inside the island or pass the value as a prop".to_string(),
+    );
+    steps
+}
+
+fn short_name(name: &str) -> &str {
+    name.rsplit("::").next().unwrap_or(name)
+}
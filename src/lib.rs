@@ -0,0 +1,70 @@
+//! Leptos MCP Server
+//!
+//! Leptos documentation and code assistance tools for AI agents. The
+//! `leptos-mcp-server` binary serves them over MCP (JSON-RPC over stdio);
+//! this library exposes the same tools for embedding without a subprocess.
+//!
+//! [`tools::LeptosTools`] is the entry point for every tool. The docs index
+//! ([`docs`]) and the autofixer rules ([`rules`]) can also be used directly:
+//!
+//! ```
+//! use leptos_mcp_server::docs::{self, LeptosVersion};
+//! use leptos_mcp_server::rules;
+//!
+//! let hits = docs::search("derived signal", LeptosVersion::default(), 3);
+//! assert!(!hits.is_empty());
+//!
+//! let findings = rules::check_file("let (count, set_count) = create_signal(0);");
+//! assert!(findings.iter().any(|f| f.rule_id == "L0005"));
+//! ```
+
+pub mod api;
+pub mod bundle;
+pub mod cache;
+pub mod cancel;
+pub mod changelog;
+pub mod components;
+pub mod config;
+pub mod context;
+pub mod dependencies;
+pub mod diagnostics;
+pub mod docs;
+pub mod explain;
+pub mod facts;
+pub mod features;
+pub mod format;
+pub mod generate;
+pub mod glossary;
+pub mod html;
+pub mod i18n;
+pub mod imports;
+pub mod islands;
+pub mod logging;
+pub mod markdown;
+pub mod metrics;
+pub mod output;
+pub mod progress;
+pub mod project;
+pub mod protocol;
+pub mod ratelimit;
+pub mod reactivity;
+pub mod recommend;
+pub mod record;
+pub mod registry;
+pub mod related;
+pub mod review;
+pub mod routemap;
+pub mod rules;
+pub mod rustdoc;
+pub mod sandbox;
+pub mod scaffold;
+mod schema;
+pub mod semantic;
+pub mod snippets;
+mod source;
+pub mod state;
+pub mod sync;
+pub mod testgen;
+pub mod tools;
+mod view;
+pub mod workspace;
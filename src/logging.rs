@@ -0,0 +1,179 @@
+//! MCP logging capability
+//!
+//! `tracing` events from this crate are forwarded to the client as
+//! `notifications/message` once it has initialized, at or above the level it
+//! chose with `logging/setLevel` (`info` until it picks one).
+//!
+//! Every session has its own [`ClientLog`]. Events go to the session whose
+//! request is running on the current thread, or else to the default session
+//! (the stdio client, when serving over stdio).
+
+use crate::protocol::Sink;
+use serde_json::{json, Map, Value};
+use std::cell::RefCell;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+
+/// MCP log levels (RFC 5424 syslog severities), least severe first
+pub const LEVELS: [&str; 8] = [
+    "debug",
+    "info",
+    "notice",
+    "warning",
+    "error",
+    "critical",
+    "alert",
+    "emergency",
+];
+
+/// Level used after `initialize` when the client hasn't set one
+const DEFAULT_LEVEL: usize = 1;
+
+/// Level index meaning "not forwarding", before `initialize`
+const OFF: usize = usize::MAX;
+
+/// One client's log subscription
+pub struct ClientLog {
+    /// Index into [`LEVELS`] of the least severe forwarded level, or `OFF`
+    level: AtomicUsize,
+    sink: Sink,
+}
+
+impl ClientLog {
+    pub fn new(sink: Sink) -> Self {
+        Self {
+            level: AtomicUsize::new(OFF),
+            sink,
+        }
+    }
+
+    /// Start forwarding at the default level, unless the client already chose one
+    pub fn enable(&self) {
+        let _ =
+            self.level
+                .compare_exchange(OFF, DEFAULT_LEVEL, Ordering::Relaxed, Ordering::Relaxed);
+    }
+
+    /// Handle `logging/setLevel`
+    pub fn set_level(&self, level: &str) -> Result<(), String> {
+        let index = LEVELS.iter().position(|l| *l == level).ok_or_else(|| {
+            format!(
+                "Unknown log level '{}'. Use one of: {}",
+                level,
+                LEVELS.join(", ")
+            )
+        })?;
+        self.level.store(index, Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<Arc<ClientLog>>> = const { RefCell::new(None) };
+}
+
+static DEFAULT: RwLock<Option<Arc<ClientLog>>> = RwLock::new(None);
+
+/// Send events that don't belong to a request to `client`
+pub fn set_default(client: Arc<ClientLog>) {
+    *DEFAULT.write().unwrap() = Some(client);
+}
+
+/// Send this thread's events to `client` until the guard is dropped
+pub fn enter(client: Arc<ClientLog>) -> Entered {
+    Entered(CURRENT.with(|current| current.replace(Some(client))))
+}
+
+/// Restores the thread's previous client when dropped
+pub struct Entered(Option<Arc<ClientLog>>);
+
+impl Drop for Entered {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        CURRENT.with(|current| *current.borrow_mut() = previous);
+    }
+}
+
+fn current_client() -> Option<Arc<ClientLog>> {
+    CURRENT
+        .with(|current| current.borrow().clone())
+        .or_else(|| DEFAULT.read().unwrap().clone())
+}
+
+fn mcp_level(level: &Level) -> usize {
+    match *level {
+        Level::ERROR => 4,
+        Level::WARN => 3,
+        Level::INFO => 1,
+        Level::DEBUG | Level::TRACE => 0,
+    }
+}
+
+/// Layer sending this crate's events to the client
+pub struct ClientLayer;
+
+impl<S: Subscriber> Layer<S> for ClientLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let level = mcp_level(metadata.level());
+        let Some(client) = current_client() else {
+            return;
+        };
+        let threshold = client.level.load(Ordering::Relaxed);
+        if threshold == OFF
+            || level < threshold
+            || !metadata.target().starts_with(env!("CARGO_CRATE_NAME"))
+        {
+            return;
+        }
+        let mut fields = Fields::default();
+        event.record(&mut fields);
+        let data = if fields.extra.is_empty() {
+            Value::String(fields.message)
+        } else {
+            fields
+                .extra
+                .insert("message".to_string(), Value::String(fields.message));
+            Value::Object(fields.extra)
+        };
+        client.sink.send(&json!({
+            "jsonrpc": "2.0",
+            "method": "notifications/message",
+            "params": {
+                "level": LEVELS[level],
+                "logger": metadata.target(),
+                "data": data,
+            }
+        }));
+    }
+}
+
+/// An event's message and any structured fields
+#[derive(Default)]
+struct Fields {
+    message: String,
+    extra: Map<String, Value>,
+}
+
+impl Visit for Fields {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = value.to_string();
+        } else {
+            self.extra.insert(field.name().to_string(), json!(value));
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            self.message = format!("{:?}", value);
+        } else {
+            self.extra
+                .insert(field.name().to_string(), json!(format!("{:?}", value)));
+        }
+    }
+}
@@ -3,29 +3,164 @@
 //! A Model Context Protocol server that provides Leptos documentation
 //! and code assistance tools for AI agents.
 //!
-//! Implements MCP protocol via JSON-RPC over stdio.
+//! Implements MCP protocol via JSON-RPC over stdio. Subcommands run the
+//! tools directly from the command line (see [`cli`]).
 
-mod docs;
-mod protocol;
-mod tools;
+mod cli;
 
 use anyhow::Result;
-use protocol::McpServer;
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
+use clap::Parser;
+use cli::{Cli, Command};
+use leptos_mcp_server::cancel::CancelToken;
+use leptos_mcp_server::config::Config;
+use leptos_mcp_server::docs;
+use leptos_mcp_server::logging;
+use leptos_mcp_server::metrics;
+use leptos_mcp_server::progress::Progress;
+use leptos_mcp_server::protocol::McpServer;
+use leptos_mcp_server::record;
+use leptos_mcp_server::rules;
+use leptos_mcp_server::semantic::{self, HttpEmbedder};
+use leptos_mcp_server::tools::{LeptosTools, OutputFormat};
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, Layer};
+
+/// How often a watched docs directory is checked for changed pages
+const DOCS_POLL_INTERVAL: Duration = Duration::from_secs(2);
 
 #[tokio::main]
-async fn main() -> Result<()> {
-    // Initialize logging (to stderr for MCP compatibility)
+async fn main() -> Result<ExitCode> {
+    let cli = Cli::parse();
+
+    // Config file, then environment, then flags
+    let mut config = Config::load(cli.config.as_deref()).map_err(anyhow::Error::msg)?;
+    config.server.merge(cli.server_config());
+    config.tools.merge(cli.tools_config());
+    cli.apply_limits(&mut config.limits);
+    // Plugin rules first, so the config can refer to them
+    rules::load_plugins(&config.rules.plugins).map_err(anyhow::Error::msg)?;
+    config.validate().map_err(anyhow::Error::msg)?;
+    let settings = config.server;
+
+    let replaced = match &settings.docs_dir {
+        Some(dir) => docs::apply_dir(dir).map_err(anyhow::Error::msg)?,
+        None => Vec::new(),
+    };
+    let overrides = match settings.overrides_dir() {
+        Some(dir) => {
+            docs::apply_overrides(&dir, settings.overrides_mode()).map_err(anyhow::Error::msg)?
+        }
+        None => Vec::new(),
+    };
+    if let Some(url) = &settings.embeddings_url {
+        semantic::set_embedder(Arc::new(HttpEmbedder::new(
+            url,
+            settings.embeddings_model.clone(),
+        )));
+    }
+    let mut tools = LeptosTools::new().with_rules(config.rules);
+    if let Some(version) = settings.leptos_version() {
+        tools = tools.with_version(version);
+    }
+
+    let mut server = McpServer::new().with_tools(tools.clone());
+    if let Some(secs) = settings.keepalive.filter(|secs| *secs > 0) {
+        server = server.with_keepalive(Duration::from_secs(secs));
+    }
+    if let Some(secs) = settings.drain_timeout {
+        server = server.with_drain_timeout(Duration::from_secs(secs));
+    }
+    server = server
+        .with_framing(settings.framing())
+        .with_tool_filter(config.tools)
+        .with_limits(config.limits);
+
+    match cli.command {
+        None | Some(Command::Serve) => {}
+        Some(Command::Replay { file, session }) => return Ok(cli::replay(&server, &file, session)),
+        Some(command) => return Ok(cli::run(command, tools, config.limits)),
+    }
+    if let Some(path) = &settings.record {
+        record::start(path).map_err(anyhow::Error::msg)?;
+    }
+
+    // Initialize logging: stderr filtered by RUST_LOG, or the configured level
+    // (stdout is the MCP channel), and the client at the level it selects with
+    // logging/setLevel
+    let stderr_filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| {
+        EnvFilter::new(format!(
+            "leptos_mcp_server={}",
+            settings.log_level.as_deref().unwrap_or("info")
+        ))
+    });
+    let stderr_layer = match settings.log_format() {
+        OutputFormat::Text => tracing_subscriber::fmt::layer()
+            .with_writer(std::io::stderr)
+            .boxed(),
+        OutputFormat::Json => tracing_subscriber::fmt::layer()
+            .json()
+            .with_writer(std::io::stderr)
+            .boxed(),
+    };
     tracing_subscriber::registry()
-        .with(EnvFilter::from_default_env().add_directive("leptos_mcp=info".parse()?))
-        .with(tracing_subscriber::fmt::layer().with_writer(std::io::stderr))
+        .with(stderr_layer.with_filter(stderr_filter))
+        .with(logging::ClientLayer)
         .init();
 
     tracing::info!("Starting Leptos MCP Server...");
+    metrics::start();
+    if let Some(addr) = settings.metrics_listen.clone() {
+        tokio::spawn(async move {
+            if let Err(e) = metrics::serve(&addr).await {
+                tracing::error!("Failed to serve metrics on {}: {}", addr, e);
+            }
+        });
+    }
+    if !replaced.is_empty() {
+        tracing::info!(
+            "Serving {} section(s) from the docs directory: {}",
+            replaced.len(),
+            replaced.join(", ")
+        );
+    }
+    if !overrides.is_empty() {
+        let paths: Vec<&str> = overrides.iter().map(|page| page.path).collect();
+        tracing::info!(
+            "Serving {} override page(s): {}",
+            overrides.len(),
+            paths.join(", ")
+        );
+    }
+    if let (Some(dir), Some(true)) = (&settings.docs_dir, settings.watch_docs) {
+        docs::watch_dir(dir.clone(), DOCS_POLL_INTERVAL);
+    }
+
+    if settings.sync_on_start.unwrap_or(false) {
+        // Off the request path: until it finishes the embedded docs are served
+        tokio::task::spawn_blocking(|| {
+            let progress = Progress::new(None, CancelToken::default());
+            match LeptosTools::new().sync_docs(false, &progress) {
+                Ok(summary) => tracing::info!("{}", summary.text),
+                Err(e) => tracing::warn!("{}", e),
+            }
+        });
+    }
 
-    // Create and run MCP server
-    let server = McpServer::new();
-    server.run().await?;
+    let shutdown = match (settings.transport.as_deref(), &settings.socket) {
+        #[cfg(unix)]
+        (Some("unix"), Some(socket)) => server.run_unix(socket).await?,
+        (Some("ws"), _) => {
+            let addr = settings.listen.as_deref().unwrap_or_default();
+            let origins = settings.allowed_origins.clone().unwrap_or_default();
+            let auth = settings.auth.clone().unwrap_or_default();
+            server.run_ws(addr, origins, auth).await?
+        }
+        _ => server.run().await?,
+    };
 
-    Ok(())
+    // Exit without dropping the runtime: that would wait for the thread blocked
+    // reading stdin and for any abandoned requests
+    std::process::exit(shutdown.exit_code().into())
 }
@@ -0,0 +1,627 @@
+//! Markdown helpers
+//!
+//! Lightweight parsing of the bundled documentation: heading outlines and
+//! subsection extraction, and rendering to plain text or HTML for clients
+//! that don't display markdown. Fenced code blocks are skipped so `#`
+//! comments in shell snippets aren't mistaken for headings.
+
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+/// A heading within a markdown document
+#[derive(Debug, Clone)]
+pub struct Heading {
+    pub level: usize,
+    pub title: String,
+    pub slug: String,
+    /// Byte offset where the heading line starts
+    pub start: usize,
+    /// Byte offset where the subsection ends (next heading of same or higher level)
+    pub end: usize,
+}
+
+/// Parse all headings of a markdown document, in document order
+pub fn parse_headings(content: &str) -> Vec<Heading> {
+    let mut headings: Vec<Heading> = Vec::new();
+    let mut in_fence = false;
+    let mut offset = 0;
+
+    for line in content.split_inclusive('\n') {
+        let trimmed = line.trim_end();
+        if trimmed.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+        } else if !in_fence {
+            if let Some((level, title)) = heading_line(trimmed) {
+                headings.push(Heading {
+                    level,
+                    title: title.to_string(),
+                    slug: slugify(title),
+                    start: offset,
+                    end: content.len(),
+                });
+            }
+        }
+        offset += line.len();
+    }
+
+    // Close each heading at the next heading of the same or a higher level
+    for i in 0..headings.len() {
+        let level = headings[i].level;
+        if let Some(next) = headings[i + 1..].iter().find(|h| h.level <= level) {
+            headings[i].end = next.start;
+        }
+    }
+
+    headings
+}
+
+/// A second-level subsection with its token counts, the unit `search-docs` returns
+#[derive(Debug)]
+pub struct SearchChunk {
+    pub heading: String,
+    pub slug: String,
+    pub start: usize,
+    pub end: usize,
+    /// Lowercase token -> occurrences
+    pub tokens: BTreeMap<String, usize>,
+}
+
+/// Split a document into searchable chunks; the whole document if it has no
+/// second-level headings
+pub fn search_chunks(content: &str) -> Vec<SearchChunk> {
+    let mut chunks: Vec<(String, String, usize, usize)> = parse_headings(content)
+        .into_iter()
+        .filter(|h| h.level == 2)
+        .map(|h| (h.title, h.slug, h.start, h.end))
+        .collect();
+    if chunks.is_empty() {
+        chunks.push((String::new(), String::new(), 0, content.len()));
+    }
+    chunks
+        .into_iter()
+        .map(|(heading, slug, start, end)| {
+            let mut tokens: BTreeMap<String, usize> = BTreeMap::new();
+            let text = content[start..end].to_lowercase();
+            for token in text
+                .split(|c: char| !c.is_alphanumeric() && c != '_')
+                .filter(|t| !t.is_empty())
+            {
+                *tokens.entry(token.to_string()).or_default() += 1;
+            }
+            SearchChunk {
+                heading,
+                slug,
+                start,
+                end,
+                tokens,
+            }
+        })
+        .collect()
+}
+
+fn heading_line(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    if level == 0 || level > 6 {
+        return None;
+    }
+    let rest = &line[level..];
+    if !rest.starts_with(' ') {
+        return None;
+    }
+    Some((level, rest.trim()))
+}
+
+/// GitHub-style anchor slug: lowercase, punctuation dropped, spaces to dashes
+pub fn slugify(title: &str) -> String {
+    title
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            'a'..='z' | '0'..='9' | '_' | '-' => Some(c),
+            ' ' => Some('-'),
+            c if c.is_alphanumeric() => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Byte offset to cut `content` at so it fits in `max_chars`, or `None` if it already fits
+///
+/// Prefers the last heading start, code block end or paragraph break within
+/// the budget, so the kept part stays well-formed. Falls back to a line break
+/// outside code blocks when those would waste more than half the budget.
+pub fn truncation_point(content: &str, max_chars: usize) -> Option<usize> {
+    if content.len() <= max_chars {
+        return None;
+    }
+
+    let mut heading_cut = 0;
+    let mut fence_cut = 0;
+    let mut paragraph_cut = 0;
+    let mut line_cut = 0;
+    let mut in_fence = false;
+    let mut offset = 0;
+
+    for line in content.split_inclusive('\n') {
+        let end = offset + line.len();
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") {
+            in_fence = !in_fence;
+            if !in_fence && end <= max_chars {
+                fence_cut = end;
+            }
+        } else if !in_fence {
+            if offset <= max_chars && heading_line(line.trim_end()).is_some() {
+                heading_cut = offset;
+            }
+            if trimmed.is_empty() && end <= max_chars {
+                paragraph_cut = end;
+            }
+        }
+        if end <= max_chars && !in_fence {
+            line_cut = end;
+        }
+        if offset > max_chars {
+            break;
+        }
+        offset = end;
+    }
+
+    let block_cut = heading_cut.max(fence_cut).max(paragraph_cut);
+    let cut = if block_cut >= max_chars / 2 || block_cut >= line_cut {
+        block_cut
+    } else {
+        line_cut
+    };
+    if cut > 0 {
+        Some(cut)
+    } else {
+        Some(floor_char_boundary(content, max_chars))
+    }
+}
+
+/// Byte ranges splitting `content` into parts of at most `max_chars`, each
+/// cut where [`truncation_point`] would cut it
+pub fn chunks(content: &str, max_chars: usize) -> Vec<Range<usize>> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    while start < content.len() {
+        let rest = &content[start..];
+        let len = match truncation_point(rest, max_chars) {
+            // At least one character, so a tiny budget still makes progress
+            Some(0) => rest.chars().next().map_or(rest.len(), char::len_utf8),
+            Some(cut) => cut,
+            None => rest.len(),
+        };
+        parts.push(start..start + len);
+        start += len;
+    }
+    parts
+}
+
+fn floor_char_boundary(text: &str, index: usize) -> usize {
+    (0..=index.min(text.len()))
+        .rev()
+        .find(|i| text.is_char_boundary(*i))
+        .unwrap_or(0)
+}
+
+/// A fenced code block with the heading it appears under
+#[derive(Debug, Clone)]
+pub struct CodeBlock {
+    /// Info string of the fence, e.g. `rust`
+    pub lang: String,
+    /// Nearest preceding heading, empty if the block comes before any heading
+    pub heading: String,
+    pub code: String,
+}
+
+/// Extract all fenced code blocks in document order
+pub fn code_blocks(content: &str) -> Vec<CodeBlock> {
+    let mut blocks = Vec::new();
+    let mut heading = String::new();
+    let mut current: Option<CodeBlock> = None;
+
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        if let Some(info) = trimmed.strip_prefix("```") {
+            match current.take() {
+                Some(block) => blocks.push(block),
+                None => {
+                    current = Some(CodeBlock {
+                        lang: info.trim().to_string(),
+                        heading: heading.clone(),
+                        code: String::new(),
+                    })
+                }
+            }
+        } else if let Some(block) = current.as_mut() {
+            block.code.push_str(line);
+            block.code.push('\n');
+        } else if let Some((_, title)) = heading_line(line.trim_end()) {
+            heading = title.to_string();
+        }
+    }
+
+    blocks
+}
+
+/// A block-level element of a markdown document
+#[derive(Debug)]
+enum Block {
+    Heading(usize, String),
+    Code(String, String),
+    Paragraph(String),
+    /// Items with their nesting depth and marker (`-` or `1.`)
+    List(Vec<(usize, String, String)>),
+    Quote(Vec<Block>),
+    /// Rows of cells, the header first; separator rows dropped
+    Table(Vec<Vec<String>>),
+    Rule,
+}
+
+/// Marker and text of a list item line
+fn list_item(line: &str) -> Option<(&str, &str)> {
+    let trimmed = line.trim_start();
+    if let Some(text) = ["- ", "* ", "+ "]
+        .iter()
+        .find_map(|marker| trimmed.strip_prefix(marker))
+    {
+        return Some((&trimmed[..1], text));
+    }
+    let digits = trimmed.chars().take_while(char::is_ascii_digit).count();
+    let text = trimmed[digits..].strip_prefix(". ")?;
+    (digits > 0).then(|| (&trimmed[..digits + 1], text))
+}
+
+fn is_rule(line: &str) -> bool {
+    let line = line.trim();
+    line.len() >= 3
+        && ['-', '*', '_']
+            .iter()
+            .any(|c| line.chars().all(|l| l == *c))
+}
+
+/// Whether `line` starts a block other than a paragraph
+fn starts_block(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    trimmed.starts_with("```")
+        || trimmed.starts_with('>')
+        || trimmed.starts_with('|')
+        || heading_line(line.trim_end()).is_some()
+        || list_item(line).is_some()
+        || is_rule(line)
+}
+
+fn parse_blocks(content: &str) -> Vec<Block> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut blocks = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() {
+            i += 1;
+        } else if let Some(info) = trimmed.strip_prefix("```") {
+            let mut code = String::new();
+            i += 1;
+            while i < lines.len() && !lines[i].trim_start().starts_with("```") {
+                code.push_str(lines[i]);
+                code.push('\n');
+                i += 1;
+            }
+            blocks.push(Block::Code(info.trim().to_string(), code));
+            i += 1;
+        } else if let Some((level, title)) = heading_line(line.trim_end()) {
+            blocks.push(Block::Heading(level, title.to_string()));
+            i += 1;
+        } else if is_rule(line) {
+            blocks.push(Block::Rule);
+            i += 1;
+        } else if trimmed.starts_with('>') {
+            let mut quoted = String::new();
+            while let Some(rest) = lines.get(i).and_then(|l| l.trim_start().strip_prefix('>')) {
+                quoted.push_str(rest.strip_prefix(' ').unwrap_or(rest));
+                quoted.push('\n');
+                i += 1;
+            }
+            blocks.push(Block::Quote(parse_blocks(&quoted)));
+        } else if trimmed.starts_with('|') {
+            let mut rows = Vec::new();
+            while let Some(row) = lines
+                .get(i)
+                .map(|l| l.trim())
+                .filter(|l| l.starts_with('|'))
+            {
+                let cells = table_cells(row);
+                let separator = cells
+                    .iter()
+                    .all(|cell| !cell.is_empty() && cell.chars().all(|c| matches!(c, '-' | ':')));
+                if !separator {
+                    rows.push(cells);
+                }
+                i += 1;
+            }
+            blocks.push(Block::Table(rows));
+        } else if list_item(line).is_some() {
+            let mut items: Vec<(usize, String, String)> = Vec::new();
+            while i < lines.len() {
+                let line = lines[i];
+                if let Some((marker, text)) = list_item(line) {
+                    let depth = (line.len() - line.trim_start().len()) / 2;
+                    items.push((depth, marker.to_string(), text.to_string()));
+                } else if !line.trim().is_empty() && line.starts_with(' ') {
+                    // Continuation of the previous item
+                    if let Some(item) = items.last_mut() {
+                        item.2.push(' ');
+                        item.2.push_str(line.trim());
+                    }
+                } else if !(line.trim().is_empty()
+                    && lines
+                        .get(i + 1)
+                        .is_some_and(|next| list_item(next).is_some()))
+                {
+                    break;
+                }
+                i += 1;
+            }
+            blocks.push(Block::List(items));
+        } else {
+            let mut text = trimmed.trim_end().to_string();
+            i += 1;
+            while i < lines.len() && !lines[i].trim().is_empty() && !starts_block(lines[i]) {
+                text.push(' ');
+                text.push_str(lines[i].trim());
+                i += 1;
+            }
+            blocks.push(Block::Paragraph(text));
+        }
+    }
+    blocks
+}
+
+/// Cells of a table row; `\\|` is a pipe within a cell, code spans included
+fn table_cells(row: &str) -> Vec<String> {
+    let row = row.strip_prefix('|').unwrap_or(row);
+    let row = row
+        .strip_suffix('|')
+        .filter(|r| !r.ends_with('\\'))
+        .unwrap_or(row);
+    let mut cells = vec![String::new()];
+    let mut chars = row.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'|') => {
+                cells.last_mut().unwrap().push('|');
+                chars.next();
+            }
+            '|' => cells.push(String::new()),
+            c => cells.last_mut().unwrap().push(c),
+        }
+    }
+    cells.iter().map(|cell| cell.trim().to_string()).collect()
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Inline markup of `text` as HTML, or stripped when `html` is false
+///
+/// Covers code spans, `**strong**`, `*emphasis*`, links and backslash
+/// escapes. Underscores are left alone: in these docs they are identifiers.
+fn inline(text: &str, html: bool) -> String {
+    let mut out = String::new();
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        let after = &rest[c.len_utf8()..];
+        if c == '\\' && after.starts_with(|c: char| c.is_ascii_punctuation()) {
+            let escaped = &after[..1];
+            out.push_str(&if html {
+                escape_html(escaped)
+            } else {
+                escaped.to_string()
+            });
+            rest = &after[1..];
+            continue;
+        }
+        if c == '`' {
+            if let Some(end) = after.find('`') {
+                let code = &after[..end];
+                if html {
+                    out.push_str(&format!("<code>{}</code>", escape_html(code)));
+                } else {
+                    out.push_str(code);
+                }
+                rest = &after[end + 1..];
+                continue;
+            }
+        }
+        if let Some(inner) = rest.strip_prefix("**") {
+            if let Some(end) = inner.find("**").filter(|end| *end > 0) {
+                let content = inline(&inner[..end], html);
+                if html {
+                    out.push_str(&format!("<strong>{}</strong>", content));
+                } else {
+                    out.push_str(&content);
+                }
+                rest = &inner[end + 2..];
+                continue;
+            }
+        }
+        if c == '*' && !after.starts_with([' ', '*']) {
+            if let Some(end) = after.find('*').filter(|end| *end > 0) {
+                let content = inline(&after[..end], html);
+                if html {
+                    out.push_str(&format!("<em>{}</em>", content));
+                } else {
+                    out.push_str(&content);
+                }
+                rest = &after[end + 1..];
+                continue;
+            }
+        }
+        if c == '[' {
+            if let Some((label, target)) = after.split_once("](") {
+                if let Some(end) = target.find(')').filter(|_| !label.contains(']')) {
+                    let url = &target[..end];
+                    let label = inline(label, html);
+                    if html {
+                        out.push_str(&format!("<a href=\"{}\">{}</a>", escape_html(url), label));
+                    } else {
+                        out.push_str(&format!("{} ({})", label, url));
+                    }
+                    rest = &target[end + 1..];
+                    continue;
+                }
+            }
+        }
+        let plain = &rest[..c.len_utf8()];
+        out.push_str(&if html {
+            escape_html(plain)
+        } else {
+            plain.to_string()
+        });
+        rest = after;
+    }
+    out
+}
+
+/// Markdown rendered as plain text, for clients that show text literally
+///
+/// Markup is stripped, list items keep their bullets or numbers, tables are
+/// aligned in columns and code blocks are indented.
+pub fn to_plain(content: &str) -> String {
+    blocks_to_plain(&parse_blocks(content)).join("\n\n") + "\n"
+}
+
+fn blocks_to_plain(blocks: &[Block]) -> Vec<String> {
+    blocks
+        .iter()
+        .map(|block| match block {
+            Block::Heading(_, title) => inline(title, false),
+            Block::Code(_, code) => code
+                .lines()
+                .map(|line| format!("    {}", line).trim_end().to_string())
+                .collect::<Vec<_>>()
+                .join("\n"),
+            Block::Paragraph(text) => inline(text, false),
+            Block::List(items) => items
+                .iter()
+                .map(|(depth, marker, text)| {
+                    let marker = if marker.ends_with('.') { marker } else { "•" };
+                    format!("{}{} {}", "  ".repeat(*depth), marker, inline(text, false))
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+            Block::Quote(inner) => blocks_to_plain(inner).join("\n\n"),
+            Block::Table(rows) => {
+                let rows: Vec<Vec<String>> = rows
+                    .iter()
+                    .map(|row| row.iter().map(|cell| inline(cell, false)).collect())
+                    .collect();
+                let mut widths: Vec<usize> = Vec::new();
+                for row in &rows {
+                    for (column, cell) in row.iter().enumerate() {
+                        let width = cell.chars().count();
+                        match widths.get_mut(column) {
+                            Some(w) => *w = (*w).max(width),
+                            None => widths.push(width),
+                        }
+                    }
+                }
+                rows.iter()
+                    .map(|row| {
+                        row.iter()
+                            .zip(&widths)
+                            .map(|(cell, width)| {
+                                format!("{}{}", cell, " ".repeat(width - cell.chars().count()))
+                            })
+                            .collect::<Vec<_>>()
+                            .join("  ")
+                            .trim_end()
+                            .to_string()
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            }
+            Block::Rule => "----".to_string(),
+        })
+        .collect()
+}
+
+/// Markdown rendered as an HTML fragment
+///
+/// Headings get their anchor slug as `id`; code blocks keep their language
+/// as a `language-*` class for client-side highlighting.
+pub fn to_html(content: &str) -> String {
+    blocks_to_html(&parse_blocks(content))
+}
+
+fn blocks_to_html(blocks: &[Block]) -> String {
+    let mut out = String::new();
+    for block in blocks {
+        match block {
+            Block::Heading(level, title) => out.push_str(&format!(
+                "<h{level} id=\"{}\">{}</h{level}>\n",
+                slugify(title),
+                inline(title, true)
+            )),
+            Block::Code(lang, code) if lang.is_empty() => {
+                out.push_str(&format!("<pre><code>{}</code></pre>\n", escape_html(code)))
+            }
+            Block::Code(lang, code) => out.push_str(&format!(
+                "<pre><code class=\"language-{}\">{}</code></pre>\n",
+                escape_html(lang),
+                escape_html(code)
+            )),
+            Block::Paragraph(text) => out.push_str(&format!("<p>{}</p>\n", inline(text, true))),
+            Block::List(items) => {
+                // Tags of the open lists, innermost last
+                let mut open: Vec<&str> = Vec::new();
+                for (depth, marker, text) in items {
+                    let tag = if marker.ends_with('.') { "ol" } else { "ul" };
+                    while open.len() > depth + 1 {
+                        out.push_str(&format!("</li></{}>\n", open.pop().unwrap_or("ul")));
+                    }
+                    if open.len() == depth + 1 {
+                        out.push_str("</li>\n");
+                    }
+                    while open.len() < depth + 1 {
+                        // Lists split by code blocks continue their numbering
+                        match marker.trim_end_matches('.') {
+                            "1" | "-" | "*" | "+" => out.push_str(&format!("<{}>\n", tag)),
+                            start => out.push_str(&format!("<ol start=\"{}\">\n", start)),
+                        }
+                        open.push(tag);
+                    }
+                    out.push_str(&format!("<li>{}", inline(text, true)));
+                }
+                while let Some(tag) = open.pop() {
+                    out.push_str(&format!("</li>\n</{}>\n", tag));
+                }
+            }
+            Block::Quote(inner) => out.push_str(&format!(
+                "<blockquote>\n{}</blockquote>\n",
+                blocks_to_html(inner)
+            )),
+            Block::Table(rows) => {
+                out.push_str("<table>\n");
+                for (i, row) in rows.iter().enumerate() {
+                    let cell = if i == 0 { "th" } else { "td" };
+                    let cells: String = row
+                        .iter()
+                        .map(|text| format!("<{cell}>{}</{cell}>", inline(text, true)))
+                        .collect();
+                    out.push_str(&format!("<tr>{}</tr>\n", cells));
+                }
+                out.push_str("</table>\n");
+            }
+            Block::Rule => out.push_str("<hr>\n"),
+        }
+    }
+    out
+}
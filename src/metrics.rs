@@ -0,0 +1,277 @@
+//! Request metrics
+//!
+//! Counts, outcomes and latencies of the requests every session handles, per
+//! method and tool, kept for the life of the process. The `server-stats` tool
+//! reports them, and [`serve`] exposes them to Prometheus over HTTP, so
+//! operators can see which tools agents use and which are slow.
+
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// Upper bounds of the latency histogram buckets, in milliseconds
+const BUCKETS_MS: [u64; 12] = [1, 5, 10, 25, 50, 100, 250, 500, 1000, 2500, 10000, 60000];
+
+/// Longest HTTP request head the metrics endpoint reads
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+
+/// How requests ended, as logged for each request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Ok,
+    /// JSON-RPC error response
+    Error,
+    /// Tool call answered with `isError: true`
+    ToolError,
+    /// Cancelled by the client; no response sent
+    Cancelled,
+    /// Refused because the session made too many tool calls
+    RateLimited,
+    /// Tool call answered with a timeout error when its time limit passed
+    TimedOut,
+}
+
+impl Outcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Ok => "ok",
+            Self::Error => "error",
+            Self::ToolError => "tool_error",
+            Self::Cancelled => "cancelled",
+            Self::RateLimited => "rate_limited",
+            Self::TimedOut => "timed_out",
+        }
+    }
+}
+
+/// Requests of one method (and tool, for `tools/call`)
+#[derive(Debug, Clone, Default)]
+struct Series {
+    /// Indexed like `Outcome`
+    outcomes: [u64; 6],
+    /// Requests per latency bucket; the last counts those over every bound
+    buckets: [u64; BUCKETS_MS.len() + 1],
+    total: Duration,
+    max: Duration,
+}
+
+impl Series {
+    fn requests(&self) -> u64 {
+        self.outcomes.iter().sum()
+    }
+
+    /// Upper bound of the bucket holding the `quantile`, or the slowest request
+    /// if it's past the last bound
+    fn quantile_ms(&self, quantile: f64) -> u64 {
+        let target = (self.requests() as f64 * quantile).ceil() as u64;
+        let mut seen = 0;
+        for (bound, count) in BUCKETS_MS.iter().zip(&self.buckets) {
+            seen += count;
+            if seen >= target {
+                return *bound;
+            }
+        }
+        self.max.as_millis() as u64
+    }
+}
+
+struct Registry {
+    started: Instant,
+    /// Keyed by method and tool (empty for other methods)
+    series: Mutex<BTreeMap<(String, String), Series>>,
+}
+
+fn registry() -> &'static Registry {
+    static REGISTRY: OnceLock<Registry> = OnceLock::new();
+    REGISTRY.get_or_init(|| Registry {
+        started: Instant::now(),
+        series: Mutex::default(),
+    })
+}
+
+/// Count a handled request
+pub fn record(method: &str, tool: Option<&str>, outcome: Outcome, latency: Duration) {
+    let key = (method.to_string(), tool.unwrap_or_default().to_string());
+    let mut series = registry().series.lock().unwrap();
+    let series = series.entry(key).or_default();
+    series.outcomes[outcome as usize] += 1;
+    let ms = latency.as_millis() as u64;
+    let bucket = BUCKETS_MS
+        .iter()
+        .position(|bound| ms <= *bound)
+        .unwrap_or(BUCKETS_MS.len());
+    series.buckets[bucket] += 1;
+    series.total += latency;
+    series.max = series.max.max(latency);
+}
+
+/// Statistics of one method (and tool)
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct Stats {
+    pub method: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool: Option<String>,
+    pub requests: u64,
+    pub ok: u64,
+    pub errors: u64,
+    pub tool_errors: u64,
+    pub cancelled: u64,
+    pub rate_limited: u64,
+    pub timed_out: u64,
+    pub mean_ms: f64,
+    /// Upper bound of the latency bucket holding the 95th percentile
+    pub p95_ms: u64,
+    pub max_ms: u64,
+}
+
+/// Start the uptime clock; otherwise it starts with the first request
+pub fn start() {
+    registry();
+}
+
+/// Time since the server started
+pub fn uptime() -> Duration {
+    registry().started.elapsed()
+}
+
+/// Statistics of every method and tool called so far, most requested first
+pub fn snapshot() -> Vec<Stats> {
+    let series = registry().series.lock().unwrap();
+    let mut stats: Vec<Stats> = series
+        .iter()
+        .map(|((method, tool), s)| Stats {
+            method: method.clone(),
+            tool: (!tool.is_empty()).then(|| tool.clone()),
+            requests: s.requests(),
+            ok: s.outcomes[Outcome::Ok as usize],
+            errors: s.outcomes[Outcome::Error as usize],
+            tool_errors: s.outcomes[Outcome::ToolError as usize],
+            cancelled: s.outcomes[Outcome::Cancelled as usize],
+            rate_limited: s.outcomes[Outcome::RateLimited as usize],
+            timed_out: s.outcomes[Outcome::TimedOut as usize],
+            mean_ms: (s.total.as_secs_f64() * 100_000.0 / s.requests().max(1) as f64).round()
+                / 100.0,
+            p95_ms: s.quantile_ms(0.95),
+            max_ms: s.max.as_millis() as u64,
+        })
+        .collect();
+    stats.sort_by_key(|s| std::cmp::Reverse(s.requests));
+    stats
+}
+
+/// Every metric in the Prometheus text exposition format
+pub fn prometheus() -> String {
+    const OUTCOMES: [Outcome; 6] = [
+        Outcome::Ok,
+        Outcome::Error,
+        Outcome::ToolError,
+        Outcome::Cancelled,
+        Outcome::RateLimited,
+        Outcome::TimedOut,
+    ];
+    let series = registry().series.lock().unwrap();
+    let mut out = String::new();
+    out.push_str(
+        "# HELP leptos_mcp_requests_total Requests handled, by method, tool and outcome\n",
+    );
+    out.push_str("# TYPE leptos_mcp_requests_total counter\n");
+    for ((method, tool), s) in series.iter() {
+        for outcome in OUTCOMES {
+            let _ = writeln!(
+                out,
+                "leptos_mcp_requests_total{{method=\"{}\",tool=\"{}\",outcome=\"{}\"}} {}",
+                method,
+                tool,
+                outcome.as_str(),
+                s.outcomes[outcome as usize]
+            );
+        }
+    }
+    out.push_str("# HELP leptos_mcp_request_duration_seconds Time to handle a request\n");
+    out.push_str("# TYPE leptos_mcp_request_duration_seconds histogram\n");
+    for ((method, tool), s) in series.iter() {
+        let labels = format!("method=\"{}\",tool=\"{}\"", method, tool);
+        let mut cumulative = 0;
+        for (bound, count) in BUCKETS_MS.iter().zip(&s.buckets) {
+            cumulative += count;
+            let _ = writeln!(
+                out,
+                "leptos_mcp_request_duration_seconds_bucket{{{},le=\"{}\"}} {}",
+                labels,
+                *bound as f64 / 1000.0,
+                cumulative
+            );
+        }
+        let _ = writeln!(
+            out,
+            "leptos_mcp_request_duration_seconds_bucket{{{},le=\"+Inf\"}} {}",
+            labels,
+            s.requests()
+        );
+        let _ = writeln!(
+            out,
+            "leptos_mcp_request_duration_seconds_sum{{{}}} {}",
+            labels,
+            s.total.as_secs_f64()
+        );
+        let _ = writeln!(
+            out,
+            "leptos_mcp_request_duration_seconds_count{{{}}} {}",
+            labels,
+            s.requests()
+        );
+    }
+    out.push_str("# HELP leptos_mcp_uptime_seconds Seconds since the server started\n");
+    out.push_str("# TYPE leptos_mcp_uptime_seconds gauge\n");
+    let _ = writeln!(out, "leptos_mcp_uptime_seconds {}", uptime().as_secs());
+    out
+}
+
+/// Answer `GET /metrics` on `addr` with [`prometheus`] until the process exits
+///
+/// A minimal HTTP/1.1 responder: one request per connection, anything else
+/// gets a 404.
+pub async fn serve(addr: &str) -> std::io::Result<()> {
+    let listener = TcpListener::bind(addr).await?;
+    tracing::info!(
+        "Serving metrics on http://{}/metrics",
+        listener.local_addr()?
+    );
+    loop {
+        let (mut stream, _) = match listener.accept().await {
+            Ok(accepted) => accepted,
+            Err(e) => {
+                tracing::warn!("Failed to accept a metrics connection: {}", e);
+                continue;
+            }
+        };
+        tokio::spawn(async move {
+            let mut head = Vec::new();
+            let mut buf = [0; 1024];
+            while !head.windows(4).any(|w| w == b"\r\n\r\n") && head.len() < MAX_REQUEST_BYTES {
+                match stream.read(&mut buf).await {
+                    Ok(0) | Err(_) => return,
+                    Ok(n) => head.extend_from_slice(&buf[..n]),
+                }
+            }
+            let request_line = String::from_utf8_lossy(&head);
+            let mut parts = request_line.split_whitespace();
+            let (status, body) = match (parts.next(), parts.next()) {
+                (Some("GET"), Some("/metrics")) => ("200 OK", prometheus()),
+                _ => ("404 Not Found", "Not found\n".to_string()),
+            };
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            let _ = stream.write_all(response.as_bytes()).await;
+        });
+    }
+}
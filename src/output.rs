@@ -0,0 +1,560 @@
+//! Structured content of tool results
+//!
+//! Each tool returns one of these types (or a type of the module doing the
+//! work) as its `structuredContent`, and the tool's `outputSchema` is
+//! generated from that same type, so the two can't drift apart. Fields that
+//! only some results carry are left out rather than set to null.
+
+use crate::api::ApiItem;
+use crate::bundle::Suggestion;
+use crate::components::Component;
+use crate::context::Environment;
+use crate::dependencies::Advice;
+use crate::docs::{DocSection, Syntax};
+use crate::explain::Explanation;
+use crate::facts::Check;
+use crate::features::FeatureIssue;
+use crate::generate::Backend;
+use crate::glossary::Term;
+use crate::imports;
+use crate::islands::{self, PropIssue};
+use crate::metrics::Stats;
+use crate::reactivity::Graph;
+use crate::recommend::Recommendation;
+use crate::related::Related;
+use crate::review::FileReview;
+use crate::rules::{Diagnostic, Rule, Severity, StyleFramework};
+use crate::scaffold::{ProjectFile, RenderMode, Styling};
+use crate::snippets::Snippet;
+use crate::state::State;
+use crate::workspace::Member;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+/// A documentation section's identity
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct SectionInfo {
+    pub title: &'static str,
+    pub path: &'static str,
+    /// Name of the crate group the section belongs to
+    pub group: &'static str,
+    /// Leptos version the content targets
+    pub version: &'static str,
+    /// Language the content is in
+    pub language: &'static str,
+}
+
+impl From<&DocSection> for SectionInfo {
+    fn from(doc: &DocSection) -> Self {
+        Self {
+            title: doc.title,
+            path: doc.path,
+            group: doc.group().name,
+            version: doc.version.as_str(),
+            language: doc.language.as_str(),
+        }
+    }
+}
+
+/// `list-sections` result
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct SectionList {
+    pub version: &'static str,
+    pub sections: Vec<ListedSection>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ListedSection {
+    #[serde(flatten)]
+    pub section: SectionInfo,
+    pub use_cases: &'static str,
+}
+
+/// A heading of a section, with the address that fetches its subsection
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct HeadingInfo {
+    pub title: String,
+    pub level: usize,
+    /// `section#slug`
+    pub address: String,
+}
+
+/// `get-documentation` result
+#[derive(Debug, Default, Serialize, JsonSchema)]
+pub struct Documentation {
+    /// Whether the section (and heading, if one was asked for) exists
+    pub found: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub section: Option<SectionInfo>,
+    /// Second-level and deeper headings of the section
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub headings: Option<Vec<HeadingInfo>>,
+    /// Address of the subsection returned
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub heading: Option<String>,
+    /// The section or subsection, or its part, in `format`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    /// Cursor of the next part, when the content was split
+    #[serde(rename = "continue", skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<&'static str>,
+    /// Sections to read next
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub related: Option<Vec<Related>>,
+    /// Sections matching a section name that wasn't found
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggestions: Option<Vec<SectionInfo>>,
+}
+
+/// `get-examples` result
+#[derive(Debug, Default, Serialize, JsonSchema)]
+pub struct Examples {
+    /// Whether the section exists
+    pub found: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub section: Option<SectionInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub examples: Option<Vec<Example>>,
+    /// Sections matching a section name that wasn't found
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggestions: Option<Vec<SectionInfo>>,
+}
+
+/// A Rust code block of a section
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct Example {
+    /// Heading the block is under, or the section title
+    pub heading: String,
+    pub code: String,
+}
+
+/// Result of looking up a section that may not exist
+pub trait SectionLookup: Default {
+    /// The result for a missing section, with the closest matches
+    fn not_found(suggestions: Vec<SectionInfo>) -> Self;
+}
+
+impl SectionLookup for Documentation {
+    fn not_found(suggestions: Vec<SectionInfo>) -> Self {
+        Self {
+            suggestions: Some(suggestions),
+            ..Self::default()
+        }
+    }
+}
+
+impl SectionLookup for Examples {
+    fn not_found(suggestions: Vec<SectionInfo>) -> Self {
+        Self {
+            suggestions: Some(suggestions),
+            ..Self::default()
+        }
+    }
+}
+
+/// `search-docs` result
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct SearchResults {
+    pub results: Vec<SearchResult>,
+    /// Cursor of the next page, when there are more results
+    #[serde(rename = "nextCursor", skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+/// A matching subsection
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct SearchResult {
+    pub section_title: String,
+    pub section_path: String,
+    /// Subsection heading; empty for a match outside any subsection
+    pub heading: String,
+    /// `section#slug`, usable as get-documentation's `section`
+    pub address: String,
+    pub score: f64,
+    pub excerpt: String,
+}
+
+/// `recommend-sections` result
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct Recommendations {
+    pub recommendations: Vec<Recommendation>,
+}
+
+/// `set-leptos-version` result
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct VersionSelected {
+    pub version: &'static str,
+}
+
+/// `set-project-context` result
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ProjectContextSet {
+    pub root: String,
+    pub package: Option<String>,
+    /// Leptos version the session now targets
+    pub version: &'static str,
+    /// Whether the version was read from the project
+    pub version_detected: bool,
+    pub backend: Option<Backend>,
+    pub styling: Vec<StyleFramework>,
+    pub syntax: Syntax,
+}
+
+/// `detect-environment` result
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct EnvironmentReport {
+    #[serde(flatten)]
+    pub environment: Environment,
+    /// Leptos version the session now targets
+    pub version: &'static str,
+    pub backend: Option<Backend>,
+    pub syntax: Syntax,
+}
+
+/// `check-features` result
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct FeatureCheck {
+    pub root: String,
+    pub issues: Vec<FeatureIssue>,
+}
+
+/// `analyze-bundle` result
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct BundleReport {
+    pub root: String,
+    /// Largest savings first
+    pub suggestions: Vec<Suggestion>,
+}
+
+/// `list-snippets` result
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct SnippetList {
+    pub snippets: Vec<&'static Snippet>,
+    /// Every tag in the library
+    pub tags: Vec<&'static str>,
+}
+
+/// `get-snippet` result
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct SnippetLookup {
+    pub found: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snippet: Option<SnippetCode>,
+    /// Snippets matching a name that wasn't found
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggestions: Option<Vec<&'static Snippet>>,
+}
+
+/// A snippet with its code
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct SnippetCode {
+    #[serde(flatten)]
+    pub snippet: &'static Snippet,
+    pub code: &'static str,
+}
+
+/// `define-term` result
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct TermLookup {
+    pub found: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub term: Option<&'static Term>,
+    /// Terms matching one that isn't defined
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggestions: Option<Vec<&'static Term>>,
+}
+
+/// `suggest-dependencies` result
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct DependencyAdvice {
+    pub version: &'static str,
+    pub needs: Vec<NeedAdvice>,
+    /// Needs covered, when none matched
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub available: Option<Vec<&'static str>>,
+}
+
+/// The vetted crates for one need
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct NeedAdvice {
+    pub id: &'static str,
+    pub title: &'static str,
+    pub note: Option<&'static str>,
+    /// Empty when no vetted crate supports the version yet
+    pub crates: Vec<Advice>,
+}
+
+/// `lookup-api` result
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ApiLookup {
+    pub version: &'static str,
+    pub items: Vec<ApiItem>,
+    /// Paths close to one that matched nothing
+    pub candidates: Vec<String>,
+}
+
+/// `get-changelog` result
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct Changelog {
+    pub from: String,
+    pub to: String,
+    pub breaking: Vec<ChangelogEntry>,
+    pub features: Vec<ChangelogEntry>,
+    pub fixes: Vec<ChangelogEntry>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ChangelogEntry {
+    /// Release the change shipped in
+    pub version: String,
+    pub text: String,
+}
+
+/// `verify-claims` result
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ClaimChecks {
+    pub version: &'static str,
+    pub checks: Vec<Check>,
+}
+
+/// Diagnostics of one file
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct FileFindings {
+    pub path: String,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// `leptos-autofixer` result: `diagnostics` for `code`, the file counts
+/// and `files` for `files`
+#[derive(Debug, Default, Serialize, JsonSchema)]
+pub struct Findings {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diagnostics: Option<Vec<Diagnostic>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub files_scanned: Option<usize>,
+    /// Diagnostics in all files
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total: Option<usize>,
+    /// Files with diagnostics
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub files: Option<Vec<FileFindings>>,
+}
+
+impl Findings {
+    /// Whether any diagnostic is an error
+    pub fn has_errors(&self) -> bool {
+        let files = self.files.iter().flatten().flat_map(|f| &f.diagnostics);
+        self.diagnostics
+            .iter()
+            .flatten()
+            .chain(files)
+            .any(|d| d.severity == Severity::Error)
+    }
+}
+
+/// `analyze-project` result
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ProjectFindings {
+    pub root: String,
+    pub files_scanned: usize,
+    /// Diagnostics in all files
+    pub total: usize,
+    /// Files with diagnostics
+    pub files: Vec<FileFindings>,
+    /// Crates of the workspace, when the project is one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub crates: Option<Vec<Member>>,
+}
+
+impl ProjectFindings {
+    /// Whether any diagnostic is an error
+    pub fn has_errors(&self) -> bool {
+        self.files
+            .iter()
+            .flat_map(|f| &f.diagnostics)
+            .any(|d| d.severity == Severity::Error)
+    }
+}
+
+/// `list-rules` result
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct RuleList {
+    pub rules: Vec<&'static Rule>,
+}
+
+/// `explain-rule` result
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct RuleExplanation {
+    pub rule: &'static Rule,
+    /// The documentation the rule cites
+    pub rationale: Option<String>,
+}
+
+/// `apply-fix` result
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct AppliedFix {
+    pub id: String,
+    pub rule_id: &'static str,
+    /// File changed, relative to the project
+    pub path: String,
+    /// Unified diff of the change
+    pub diff: String,
+}
+
+/// `review-diff` result
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct Review {
+    pub files_reviewed: usize,
+    pub introduced: usize,
+    pub pre_existing: usize,
+    pub resolved: usize,
+    /// Changed files with findings in their changed regions
+    pub files: Vec<FileReview>,
+}
+
+impl Review {
+    /// Whether the change introduces an error
+    pub fn introduces_errors(&self) -> bool {
+        self.files
+            .iter()
+            .flat_map(|f| &f.introduced)
+            .any(|d| d.severity == Severity::Error)
+    }
+}
+
+/// `analyze-reactivity` result
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ReactiveGraphs {
+    pub components: Vec<ComponentGraph>,
+}
+
+/// A component's graph, also rendered as Mermaid and DOT
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ComponentGraph {
+    #[serde(flatten)]
+    pub graph: Graph,
+    pub mermaid: String,
+    pub dot: String,
+}
+
+/// `islands-advisor` result
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct IslandsAdvice {
+    pub version: &'static str,
+    /// Whether Cargo.toml enables the `islands` feature; null when unknown
+    pub islands_enabled: Option<bool>,
+    pub components: Vec<islands::Advice>,
+    /// Props islands can't take
+    pub props: Vec<PropIssue>,
+    pub setup_issues: Vec<String>,
+    pub setup_steps: Vec<String>,
+}
+
+/// `list-components` result
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ComponentList {
+    pub components: Vec<Component>,
+}
+
+/// `extract-state` result
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct StateList {
+    pub state: Vec<State>,
+}
+
+/// Generated code, in the order of the content blocks
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct Generated {
+    pub blocks: Vec<GeneratedBlock>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct GeneratedBlock {
+    /// Where the code goes, from its leading comment
+    pub label: Option<String>,
+    pub language: BlockLanguage,
+    pub code: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum BlockLanguage {
+    Rust,
+    Toml,
+}
+
+/// `scaffold-project` result
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ScaffoldedProject {
+    pub name: String,
+    pub version: &'static str,
+    pub mode: RenderMode,
+    /// Server framework; null for client-side rendering
+    pub backend: Option<Backend>,
+    pub styling: Styling,
+    pub files: Vec<ProjectFile>,
+    pub next_steps: Vec<String>,
+}
+
+/// `html-to-view` result
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct View {
+    pub view: String,
+}
+
+/// `explain-error` result
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct Explanations {
+    pub explanations: Vec<Explanation>,
+    /// Headlines of the errors not recognized as Leptos-specific
+    pub unrecognized: Vec<String>,
+}
+
+/// `format-view` result
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct Formatted {
+    pub changed: bool,
+    pub formatted: String,
+    /// Unified diff of the changes, when asked for
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diff: Option<String>,
+}
+
+/// `suggest-imports` result
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ImportSuggestions {
+    pub version: &'static str,
+    #[serde(flatten)]
+    pub suggestion: imports::Suggestion,
+}
+
+/// `sync-docs` result
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct SyncSummary {
+    /// Sections now served from the book
+    pub sections: Vec<&'static str>,
+    /// Pages downloaded because they were new or changed
+    pub updated: usize,
+    /// Pages whose cached copy was still current
+    pub unchanged: usize,
+    /// Pages that couldn't be fetched; cached copies are used if present
+    pub failed: Vec<FailedPage>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct FailedPage {
+    pub page: &'static str,
+    pub reason: String,
+}
+
+/// `server-stats` result
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct Statistics {
+    pub uptime_secs: u64,
+    /// Requests of every method
+    pub requests: u64,
+    /// One entry per method, and per tool for `tools/call`
+    pub stats: Vec<Stats>,
+}
@@ -0,0 +1,114 @@
+//! Progress notifications
+//!
+//! Long-running tool calls report staged progress as `notifications/progress`
+//! when the request carries a `_meta.progressToken`. Without a token every
+//! report is a no-op. The reporter also carries the request's cancellation
+//! token and the server's resource limits, since the same long-running work
+//! has to poll the one and stay within the other.
+
+use crate::cancel::CancelToken;
+use crate::config::Limits;
+use crate::protocol::Sink;
+use serde_json::{json, Value};
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+
+/// Minimum time between two notifications, so large scans don't flood the client
+const MIN_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Error returned by work stopped because its request was cancelled
+pub const CANCELLED: &str = "Request cancelled";
+
+/// Progress reporter for one request
+pub struct Progress {
+    token: Option<Value>,
+    cancel: CancelToken,
+    limits: Limits,
+    sink: Sink,
+    /// Last reported value; the spec requires progress to increase
+    last: Cell<Option<u64>>,
+    last_sent: Cell<Option<Instant>>,
+}
+
+impl Progress {
+    /// Reporter for a request's `_meta.progressToken`, if it has one
+    pub fn new(token: Option<Value>, cancel: CancelToken) -> Self {
+        Self {
+            token,
+            cancel,
+            limits: Limits::default(),
+            sink: Sink::stdout(),
+            last: Cell::new(None),
+            last_sent: Cell::new(None),
+        }
+    }
+
+    /// Send notifications to `sink` instead of stdout
+    pub fn with_sink(mut self, sink: Sink) -> Self {
+        self.sink = sink;
+        self
+    }
+
+    /// Hold the work to `limits` instead of the defaults
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Ceilings on the files, bytes and output the work may use
+    pub fn limits(&self) -> &Limits {
+        &self.limits
+    }
+
+    /// Report `progress` out of an optional `total`
+    ///
+    /// Reports that don't advance, or arrive sooner than 200ms after
+    /// the previous one, are dropped unless they complete the total.
+    pub fn report(&self, progress: u64, total: Option<u64>, message: &str) {
+        let Some(token) = &self.token else {
+            return;
+        };
+        if self.last.get().is_some_and(|last| progress <= last) {
+            return;
+        }
+        let complete = total.is_some_and(|t| progress >= t);
+        if !complete
+            && self
+                .last_sent
+                .get()
+                .is_some_and(|sent| sent.elapsed() < MIN_INTERVAL)
+        {
+            return;
+        }
+        self.last.set(Some(progress));
+        self.last_sent.set(Some(Instant::now()));
+
+        let mut params = json!({
+            "progressToken": token,
+            "progress": progress,
+            "message": message,
+        });
+        if let Some(total) = total {
+            params["total"] = json!(total);
+        }
+        let notification = json!({
+            "jsonrpc": "2.0",
+            "method": "notifications/progress",
+            "params": params,
+        });
+        self.sink.send(&notification);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.is_cancelled()
+    }
+
+    /// `Err` once the client has cancelled the request
+    pub fn check_cancelled(&self) -> Result<(), String> {
+        if self.is_cancelled() {
+            Err(CANCELLED.to_string())
+        } else {
+            Ok(())
+        }
+    }
+}
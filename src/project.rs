@@ -0,0 +1,328 @@
+//! Project analysis
+//!
+//! Walks the Rust sources of a Leptos project, runs the autofixer checks on
+//! every file, and adds checks that need to see more than one file at a time.
+//! The same analysis runs on files passed to `leptos-autofixer` directly.
+//! Pointed at a cargo workspace, it scans every member and knows which crate
+//! each file belongs to.
+
+use crate::progress::Progress;
+use crate::rules::{check_contexts, check_modules, check_routes, lines_with_offsets, Finding};
+use crate::workspace::{self, Workspace};
+use ignore::WalkBuilder;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Findings for a single source file
+#[derive(Debug, Clone)]
+pub struct FileReport {
+    /// Path relative to the project root
+    pub path: PathBuf,
+    pub findings: Vec<Finding>,
+}
+
+/// Findings for a whole project
+#[derive(Debug, Clone)]
+pub struct ProjectReport {
+    pub files_scanned: usize,
+    /// Only files with at least one finding
+    pub files: Vec<FileReport>,
+    /// The workspace the project is part of, if it has several crates
+    pub workspace: Option<Workspace>,
+}
+
+/// A source file loaded for analysis
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SourceFile {
+    /// Path in the project, e.g. `src/app.rs`
+    pub path: PathBuf,
+    pub content: String,
+}
+
+/// A `#[server]` function declared somewhere in the project
+#[derive(Debug, Clone)]
+pub struct ServerFn {
+    pub name: String,
+    /// Generated struct name used with `ServerAction::<T>`
+    pub type_name: String,
+    pub file: PathBuf,
+}
+
+/// Collect `.rs` files under `root/src` (or `root` if it has no `src` or is
+/// a workspace listing members), honoring `.gitignore`
+///
+/// The walk stops early if the request is cancelled, and fails once it finds
+/// more than `max_project_files` files.
+pub fn rust_sources(root: &Path, progress: &Progress) -> Result<Vec<PathBuf>, String> {
+    if !root.is_dir() {
+        return Err(format!("'{}' is not a directory", root.display()));
+    }
+    let src = root.join("src");
+    let base = if src.is_dir() && !workspace::has_members(root) {
+        src
+    } else {
+        root.to_path_buf()
+    };
+
+    let max = progress.limits().max_project_files;
+    let mut files: Vec<PathBuf> = walk(&base, progress).take(max.saturating_add(1)).collect();
+    progress.check_cancelled()?;
+    if files.len() > max {
+        return Err(format!(
+            "'{}' has more than {} Rust source files (limits.max_project_files); point the tool at a smaller crate",
+            root.display(),
+            max
+        ));
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Polls for cancellation at every entry, so a tree without Rust files
+/// can't keep it going either
+fn walk<'a>(base: &Path, progress: &'a Progress) -> impl Iterator<Item = PathBuf> + 'a {
+    WalkBuilder::new(base)
+        .require_git(false)
+        .filter_entry(|e| e.file_name() != "target" && e.file_name() != "node_modules")
+        .build()
+        .take_while(|_| !progress.is_cancelled())
+        .filter_map(|entry| entry.ok())
+        .filter(|e| e.file_type().is_some_and(|t| t.is_file()))
+        .map(|e| e.into_path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "rs"))
+}
+
+/// Load every Rust source of a project, with paths relative to `root`
+///
+/// Fails before reading past `max_project_bytes` in all.
+pub fn load_sources(root: &Path, progress: &Progress) -> Result<Vec<SourceFile>, String> {
+    let max = progress.limits().max_project_bytes;
+    let mut total = 0u64;
+    rust_sources(root, progress)?
+        .into_iter()
+        .map(|path| {
+            total += fs::metadata(&path).map_or(0, |m| m.len());
+            if total > max as u64 {
+                return Err(format!(
+                    "Rust sources under '{}' are over {} bytes (limits.max_project_bytes); point the tool at a smaller crate",
+                    root.display(),
+                    max
+                ));
+            }
+            let content = fs::read_to_string(&path)
+                .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+            let path = path.strip_prefix(root).unwrap_or(&path).to_path_buf();
+            Ok(SourceFile { path, content })
+        })
+        .collect()
+}
+
+/// Run per-file checks plus cross-file checks over a project
+pub fn analyze_project(
+    root: &Path,
+    check_file: impl Fn(&str) -> Vec<Finding>,
+    progress: &Progress,
+) -> Result<ProjectReport, String> {
+    progress.report(0, None, &format!("Scanning {}", root.display()));
+    let sources = load_sources(root, progress)?;
+    let workspace = Workspace::load(root);
+    analyze_sources(&sources, workspace, check_file, progress)
+}
+
+/// Run per-file checks plus cross-file checks over a set of loaded files
+///
+/// With a `workspace`, paths are relative to the directory it was loaded
+/// for, and files of crates the client compiles are checked for server-only
+/// crates.
+pub fn analyze_sources(
+    sources: &[SourceFile],
+    workspace: Option<Workspace>,
+    check_file: impl Fn(&str) -> Vec<Finding>,
+    progress: &Progress,
+) -> Result<ProjectReport, String> {
+    let server_fns = server_functions(sources);
+    let mut contexts = check_contexts(sources);
+    let mut modules = check_modules(sources, workspace.as_ref());
+    let mut routes = check_routes(sources);
+
+    let total = sources.len() as u64;
+    let mut files = Vec::new();
+    for (i, file) in sources.iter().enumerate() {
+        progress.check_cancelled()?;
+        progress.report(
+            i as u64 + 1,
+            Some(total),
+            &format!("Checked {}/{} files", i + 1, total),
+        );
+        let mut findings = check_file(&file.content);
+        let scope = modules.remove(&file.path).unwrap_or_default();
+        // Where the component is known to live, L0105 says how to bring it in
+        findings.retain(|f| {
+            f.rule_id != "L0204"
+                || !scope
+                    .iter()
+                    .any(|s| s.rule_id == "L0105" && (s.line, s.column) == (f.line, f.column))
+        });
+        findings.extend(scope);
+        findings.extend(check_server_fn_usage(file, &server_fns));
+        findings.extend(contexts.remove(&file.path).unwrap_or_default());
+        findings.extend(routes.remove(&file.path).unwrap_or_default());
+        if !findings.is_empty() {
+            files.push(FileReport {
+                path: file.path.clone(),
+                findings,
+            });
+        }
+    }
+
+    Ok(ProjectReport {
+        files_scanned: sources.len(),
+        files,
+        workspace,
+    })
+}
+
+/// Find every `#[server]` function and the struct name it generates
+pub fn server_functions(sources: &[SourceFile]) -> Vec<ServerFn> {
+    let mut found = Vec::new();
+    for file in sources {
+        let mut pending_attr: Option<Option<String>> = None;
+        for line in file.content.lines() {
+            let line = line.trim();
+            if let Some(rest) = line.strip_prefix("#[server") {
+                pending_attr = Some(explicit_type_name(rest));
+                continue;
+            }
+            let Some(explicit) = pending_attr.as_ref() else {
+                continue;
+            };
+            if let Some(name) = fn_name(line) {
+                found.push(ServerFn {
+                    type_name: explicit.clone().unwrap_or_else(|| camel_case(&name)),
+                    name,
+                    file: file.path.clone(),
+                });
+                pending_attr = None;
+            } else if !line.starts_with("#[") && !line.is_empty() && !line.starts_with("//") {
+                pending_attr = None;
+            }
+        }
+    }
+    found
+}
+
+/// `#[server(CreateTodo, "/api")]` and `#[server(name = CreateTodo)]` name the struct explicitly
+fn explicit_type_name(attr_rest: &str) -> Option<String> {
+    let args = attr_rest.strip_prefix('(')?;
+    let args = &args[..args.find(')')?];
+    args.split(',').map(str::trim).find_map(|arg| {
+        let value = match arg.split_once('=') {
+            Some((key, value)) if key.trim() == "name" => value.trim(),
+            Some(_) => return None,
+            None => arg,
+        };
+        let is_ident = !value.is_empty()
+            && value.chars().all(|c| c.is_alphanumeric() || c == '_')
+            && value.starts_with(|c: char| c.is_uppercase());
+        is_ident.then(|| value.to_string())
+    })
+}
+
+/// Name of the function declared on this line, if any
+fn fn_name(line: &str) -> Option<String> {
+    let idx = line.find("fn ")?;
+    let before = &line[..idx];
+    if !before
+        .split_whitespace()
+        .all(|w| matches!(w, "pub" | "pub(crate)" | "async" | "unsafe" | "const"))
+    {
+        return None;
+    }
+    let name: String = line[idx + 3..]
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || *c == '_')
+        .collect();
+    (!name.is_empty()).then_some(name)
+}
+
+/// `create_todo` → `CreateTodo`, the struct name `#[server]` derives by default
+pub fn camel_case(snake: &str) -> String {
+    snake
+        .split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+/// Cross-file checks for how a file uses server functions declared anywhere in the project
+fn check_server_fn_usage(file: &SourceFile, server_fns: &[ServerFn]) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let by_type: HashMap<&str, &ServerFn> = server_fns
+        .iter()
+        .map(|f| (f.type_name.as_str(), f))
+        .collect();
+
+    for (offset, line) in lines_with_offsets(&file.content) {
+        // ServerAction::<T> must name a generated server fn struct
+        for (idx, _) in line.match_indices("ServerAction::<") {
+            let type_name: String = line[idx + "ServerAction::<".len()..]
+                .chars()
+                .take_while(|c| c.is_alphanumeric() || *c == '_')
+                .collect();
+            if !type_name.is_empty() && !by_type.contains_key(type_name.as_str()) {
+                let mut finding = Finding::new(
+                    "L0100",
+                    format!(
+                        "`ServerAction::<{}>` doesn't match any #[server] function in the project",
+                        type_name
+                    ),
+                )
+                .at(&file.content, offset + idx);
+                if let Some(f) = server_fns
+                    .iter()
+                    .find(|f| f.type_name.eq_ignore_ascii_case(&type_name))
+                {
+                    finding = finding.with_fix(format!("ServerAction::<{}>", f.type_name));
+                }
+                findings.push(finding);
+            }
+        }
+
+        // A server fn call used as a statement drops its future without running it
+        let trimmed = line.trim();
+        let indent = line.len() - line.trim_start().len();
+        for server_fn in server_fns {
+            let call = format!("{}(", server_fn.name);
+            if trimmed.starts_with(&call) && trimmed.ends_with(");") && !trimmed.contains(".await")
+            {
+                findings.push(
+                    Finding::new(
+                        "L0101",
+                        format!(
+                            "`{}` (declared in {}) is a #[server] function; calling it without `.await` never sends the request",
+                            server_fn.name,
+                            server_fn.file.display()
+                        ),
+                    )
+                    .at(&file.content, offset + indent)
+                    .with_fix(format!(
+                        "{}.await, or dispatch it through a ServerAction",
+                        trimmed.trim_end_matches(';')
+                    )),
+                );
+            }
+        }
+    }
+
+    findings
+}
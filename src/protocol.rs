@@ -1,16 +1,159 @@
 //! MCP Protocol implementation
 //!
-//! JSON-RPC over stdio using newline-delimited JSON (NDJSON).
+//! JSON-RPC over stdio, framed as newline-delimited JSON (NDJSON) or with
+//! LSP-style `Content-Length` headers (see [`Framing`]).
 
-use crate::tools::LeptosTools;
+use crate::api;
+use crate::cache::{self, ResponseCache};
+use crate::cancel::{CancelToken, Registry};
+use crate::config::{AuthConfig, Limits, ToolsConfig};
+use crate::docs;
+use crate::logging::{self, ClientLog};
+use crate::markdown;
+use crate::metrics::{self, Outcome};
+use crate::progress::Progress;
+use crate::ratelimit::TokenBucket;
+use crate::record::{self, Direction};
+use crate::registry::{self, TOOL_NAMES};
+use crate::tools::{parse_cursor, LeptosTools, ToolOutput};
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
-use std::io::{self, BufRead, BufReader, Write};
+use std::collections::HashSet;
+use std::future::Future;
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::sync::mpsc;
+use tokio::task::JoinSet;
+
+/// MCP revisions the server speaks, newest first
+///
+/// Tool annotations came with 2025-03-26, output schemas and structured
+/// content with 2025-06-18; a session on an older revision doesn't get them.
+const PROTOCOL_VERSIONS: [&str; 3] = ["2025-06-18", "2025-03-26", "2024-11-05"];
+
+/// Items per page of a paginated list response
+const PAGE_SIZE: usize = 50;
+
+/// Most completion values a response may carry, per the MCP spec
+const MAX_COMPLETIONS: usize = 100;
+
+/// JSON-RPC error code for a message that isn't valid JSON
+const PARSE_ERROR: i32 = -32700;
+
+/// JSON-RPC error code for a message that isn't a valid request
+const INVALID_REQUEST: i32 = -32600;
+
+/// JSON-RPC error code for a method the server doesn't implement
+const METHOD_NOT_FOUND: i32 = -32601;
+
+/// JSON-RPC error code for parameters rejected before dispatch or by the
+/// method's handler
+const INVALID_PARAMS: i32 = -32602;
+
+/// JSON-RPC error code for a request whose handler failed unexpectedly
+const INTERNAL_ERROR: i32 = -32603;
+
+/// MCP error code for a resource URI that names no section or API item
+const RESOURCE_NOT_FOUND: i32 = -32002;
+
+/// JSON-RPC server error code for a tool call over the session's rate limit
+const RATE_LIMITED: i32 = -32000;
+
+/// JSON-RPC server error code for a tool call that ran past its time limit
+const TIMED_OUT: i32 = -32001;
+
+/// URI template of documentation section resources
+const DOCS_TEMPLATE: &str = "leptos-docs://{version}/{section}";
+
+/// URI template of API reference resources
+const API_TEMPLATE: &str = "leptos-api://{item_path}";
+
+/// Arguments carrying source text, bounded by `max_code_bytes`
+const SOURCE_ARGUMENTS: [&str; 3] = ["code", "html", "output"];
+
+/// How long shutdown waits for in-flight requests unless configured otherwise
+pub const DEFAULT_DRAIN_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Why the server stopped reading requests
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShutdownReason {
+    /// stdin or the connection closed
+    Eof,
+    /// SIGINT (Ctrl-C)
+    Interrupt,
+    /// SIGTERM
+    Terminate,
+    /// The client sent `notifications/shutdown`
+    Requested,
+}
+
+impl std::fmt::Display for ShutdownReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Eof => "input closed",
+            Self::Interrupt => "interrupted",
+            Self::Terminate => "terminated",
+            Self::Requested => "shutdown requested by client",
+        })
+    }
+}
+
+/// How a serving session ended
+#[derive(Debug, Clone, Copy)]
+pub struct Shutdown {
+    pub reason: ShutdownReason,
+    /// Whether every in-flight request was answered within the drain timeout
+    pub drained: bool,
+}
+
+impl Shutdown {
+    /// Process exit status: 128 + signal number after a signal, 1 if requests
+    /// were abandoned, 0 otherwise
+    pub fn exit_code(&self) -> u8 {
+        match self.reason {
+            ShutdownReason::Interrupt => 130,
+            ShutdownReason::Terminate => 143,
+            _ if !self.drained => 1,
+            _ => 0,
+        }
+    }
+}
 
 /// MCP Server
 pub struct McpServer {
     tools: LeptosTools,
+    requests: Registry,
+    /// Ping the client after this long without a message from it
+    keepalive: Option<Duration>,
+    /// Sequence number of the last keepalive ping sent
+    pings: AtomicU64,
+    /// Whether the last keepalive ping is still unanswered
+    ping_pending: AtomicBool,
+    /// How long shutdown waits for in-flight requests
+    drain_timeout: Duration,
+    /// Which tools are listed and may be called
+    tool_filter: ToolsConfig,
+    /// Sizes checked before a request is handled
+    limits: Limits,
+    /// This session's tool call allowance; `None` if calls aren't limited
+    calls: Option<TokenBucket>,
+    /// Results of pure tool calls, shared by every session; `None` if disabled
+    cache: Option<Arc<ResponseCache>>,
+    /// How messages are delimited on stdio
+    framing: Framing,
+    /// Where this session's messages go
+    sink: Sink,
+    /// This session's log level
+    client_log: Arc<ClientLog>,
+    /// URIs of the resources this session wants `notifications/resources/updated` for
+    subscriptions: Mutex<HashSet<String>>,
+    /// MCP revision agreed on in `initialize`, the newest until then
+    protocol_version: Mutex<&'static str>,
 }
 
 /// JSON-RPC Request
@@ -38,27 +181,643 @@ struct JsonRpcResponse {
 struct JsonRpcError {
     code: i32,
     message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data: Option<Value>,
+}
+
+/// What the reader does with one parsed message
+enum Accepted {
+    /// A request to run and answer
+    Request(JsonRpcRequest),
+    /// A notification or a response to the server, already handled; `Some`
+    /// if it asks the server to shut down
+    Handled(Option<ShutdownReason>),
+    /// Not a JSON-RPC message
+    Invalid(String),
+}
+
+/// Stops tracking a request in its [`Registry`] when dropped
+struct Finish<'a> {
+    requests: &'a Registry,
+    id: &'a Value,
+}
+
+impl Drop for Finish<'_> {
+    fn drop(&mut self) {
+        self.requests.finish(self.id);
+    }
+}
+
+/// An error response to the request `id`
+fn error_response(id: Value, code: i32, message: String) -> JsonRpcResponse {
+    JsonRpcResponse {
+        jsonrpc: "2.0".to_string(),
+        id,
+        result: None,
+        error: Some(JsonRpcError {
+            code,
+            message,
+            data: None,
+        }),
+    }
+}
+
+/// The error answering a message, or batch entry, that isn't a request
+fn invalid_request(message: String) -> JsonRpcResponse {
+    error_response(Value::Null, INVALID_REQUEST, message)
+}
+
+/// Runs a request method with its `params`
+type RequestHandler = fn(&McpServer, Option<&Value>, &CancelToken) -> Result<Value, RequestError>;
+
+/// Why a request handler refused a request, and the code it's answered with
+///
+/// Messages convert to [`INVALID_PARAMS`]: a handler only fails on what the
+/// request asked for.
+struct RequestError {
+    code: i32,
+    message: String,
+}
+
+impl RequestError {
+    fn resource_not_found(message: String) -> Self {
+        Self {
+            code: RESOURCE_NOT_FOUND,
+            message,
+        }
+    }
+}
+
+impl From<String> for RequestError {
+    fn from(message: String) -> Self {
+        Self {
+            code: INVALID_PARAMS,
+            message,
+        }
+    }
+}
+
+impl From<&str> for RequestError {
+    fn from(message: &str) -> Self {
+        message.to_string().into()
+    }
+}
+
+/// Handles a notification; `Some` if it asks the server to shut down
+type NotificationHandler = fn(&McpServer, Option<&Value>) -> Option<ShutdownReason>;
+
+/// Whether a method is answered
+enum Call {
+    Request(RequestHandler),
+    Notification(NotificationHandler),
+}
+
+/// JSON type a parameter must have
+#[derive(Clone, Copy)]
+enum Shape {
+    String,
+    Object,
+    /// A JSON-RPC id: a string or a number
+    Id,
+}
+
+impl Shape {
+    fn matches(self, value: &Value) -> bool {
+        match self {
+            Shape::String => value.is_string(),
+            Shape::Object => value.is_object(),
+            Shape::Id => value.is_string() || value.is_number(),
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Shape::String => "a string",
+            Shape::Object => "an object",
+            Shape::Id => "a string or number",
+        }
+    }
+}
+
+/// A member of a method's `params`
+struct Param {
+    name: &'static str,
+    shape: Shape,
+    required: bool,
+}
+
+const fn required(name: &'static str, shape: Shape) -> Param {
+    Param {
+        name,
+        shape,
+        required: true,
+    }
+}
+
+const fn optional(name: &'static str, shape: Shape) -> Param {
+    Param {
+        name,
+        shape,
+        required: false,
+    }
+}
+
+/// A JSON-RPC method the server handles
+struct Method {
+    name: &'static str,
+    /// Server capability `initialize` declares for it, if it belongs to one
+    capability: Option<&'static str>,
+    params: &'static [Param],
+    call: Call,
+}
+
+/// Every method the server handles; `initialize` declares the capabilities
+/// listed here and nothing else
+const METHODS: &[Method] = &[
+    Method {
+        name: "initialize",
+        capability: None,
+        params: &[optional("protocolVersion", Shape::String)],
+        call: Call::Request(|server, params, _| server.handle_initialize(params)),
+    },
+    Method {
+        name: "ping",
+        capability: None,
+        params: &[],
+        call: Call::Request(|_, _, _| Ok(json!({}))),
+    },
+    Method {
+        name: "tools/list",
+        capability: Some("tools"),
+        params: &[optional("cursor", Shape::String)],
+        call: Call::Request(|server, params, _| server.handle_list_tools(params)),
+    },
+    Method {
+        name: "tools/call",
+        capability: Some("tools"),
+        params: &[
+            required("name", Shape::String),
+            optional("arguments", Shape::Object),
+        ],
+        call: Call::Request(|server, params, cancel| server.handle_call_tool(params, cancel)),
+    },
+    Method {
+        name: "logging/setLevel",
+        capability: Some("logging"),
+        params: &[required("level", Shape::String)],
+        call: Call::Request(|server, params, _| server.handle_set_level(params)),
+    },
+    Method {
+        name: "completion/complete",
+        capability: Some("completions"),
+        params: &[
+            required("ref", Shape::Object),
+            required("argument", Shape::Object),
+        ],
+        call: Call::Request(|server, params, _| server.handle_complete(params)),
+    },
+    Method {
+        name: "resources/list",
+        capability: Some("resources"),
+        params: &[optional("cursor", Shape::String)],
+        call: Call::Request(|server, params, _| server.handle_list_resources(params)),
+    },
+    Method {
+        name: "resources/templates/list",
+        capability: Some("resources"),
+        params: &[optional("cursor", Shape::String)],
+        call: Call::Request(|server, params, _| server.handle_list_templates(params)),
+    },
+    Method {
+        name: "resources/read",
+        capability: Some("resources"),
+        params: &[required("uri", Shape::String)],
+        call: Call::Request(|server, params, _| server.handle_read_resource(params)),
+    },
+    Method {
+        name: "resources/subscribe",
+        capability: Some("resources"),
+        params: &[required("uri", Shape::String)],
+        call: Call::Request(|server, params, _| server.handle_subscribe(params, true)),
+    },
+    Method {
+        name: "resources/unsubscribe",
+        capability: Some("resources"),
+        params: &[required("uri", Shape::String)],
+        call: Call::Request(|server, params, _| server.handle_subscribe(params, false)),
+    },
+    Method {
+        name: "notifications/initialized",
+        capability: None,
+        params: &[],
+        // Log messages may only be sent once the client finished initializing
+        call: Call::Notification(|server, _| {
+            server.client_log.enable();
+            None
+        }),
+    },
+    Method {
+        name: "notifications/cancelled",
+        capability: None,
+        params: &[
+            required("requestId", Shape::Id),
+            optional("reason", Shape::String),
+        ],
+        call: Call::Notification(|server, params| {
+            let id = params.and_then(|p| p.get("requestId"))?;
+            if !server.requests.cancel(id) {
+                tracing::debug!("Cancelled request {} is not in flight", id);
+            }
+            None
+        }),
+    },
+    Method {
+        name: "notifications/shutdown",
+        capability: None,
+        params: &[],
+        call: Call::Notification(|_, _| Some(ShutdownReason::Requested)),
+    },
+];
+
+/// Method prefixes of MCP capabilities the server doesn't declare
+const UNSUPPORTED: &[(&str, &str)] = &[
+    ("prompts/", "prompts"),
+    ("sampling/", "sampling"),
+    ("roots/", "roots"),
+    ("elicitation/", "elicitation"),
+];
+
+fn find_method(name: &str) -> Option<&'static Method> {
+    METHODS.iter().find(|method| method.name == name)
+}
+
+/// Check `params` against what `method` expects
+fn check_params(method: &Method, params: Option<&Value>) -> Result<(), String> {
+    let object = match params {
+        None | Some(Value::Null) => None,
+        Some(Value::Object(object)) => Some(object),
+        Some(_) => return Err(format!("params of {} must be an object", method.name)),
+    };
+    for param in method.params {
+        match object.and_then(|o| o.get(param.name)) {
+            Some(value) if !param.shape.matches(value) => {
+                return Err(format!(
+                    "'{}' of {} must be {}",
+                    param.name,
+                    method.name,
+                    param.shape.as_str()
+                ))
+            }
+            None if param.required => {
+                return Err(format!(
+                    "Missing '{}' ({}) in params of {}",
+                    param.name,
+                    param.shape.as_str(),
+                    method.name
+                ))
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// The error message for a method the server doesn't handle
+fn method_not_found(name: &str) -> String {
+    match UNSUPPORTED
+        .iter()
+        .find(|(prefix, _)| name.starts_with(prefix))
+    {
+        Some((_, capability)) => format!(
+            "Method not found: {} (this server has no {} capability)",
+            name, capability
+        ),
+        None => format!("Method not found: {}", name),
+    }
+}
+
+impl Default for McpServer {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl McpServer {
     pub fn new() -> Self {
         Self {
             tools: LeptosTools::new(),
+            requests: Registry::default(),
+            keepalive: None,
+            pings: AtomicU64::new(0),
+            ping_pending: AtomicBool::new(false),
+            drain_timeout: DEFAULT_DRAIN_TIMEOUT,
+            tool_filter: ToolsConfig::default(),
+            limits: Limits::default(),
+            calls: None,
+            cache: ResponseCache::new(Limits::default().cache_entries).map(Arc::new),
+            framing: Framing::Auto,
+            client_log: Arc::new(ClientLog::new(Sink::stdout())),
+            sink: Sink::stdout(),
+            subscriptions: Mutex::default(),
+            protocol_version: Mutex::new(PROTOCOL_VERSIONS[0]),
         }
     }
 
-    pub async fn run(&self) -> Result<()> {
-        let stdin = io::stdin();
-        let reader = BufReader::new(stdin.lock());
-        let mut stdout = io::stdout();
+    /// Serve these tools instead of default-configured ones
+    pub fn with_tools(mut self, tools: LeptosTools) -> Self {
+        self.tools = tools;
+        self
+    }
 
-        for line in reader.lines() {
-            let line = match line {
-                Ok(l) => l,
-                Err(e) => {
-                    eprintln!("Failed to read line: {}", e);
+    /// Delimit messages with `framing` instead of detecting it
+    pub fn with_framing(mut self, framing: Framing) -> Self {
+        self.framing = framing;
+        self
+    }
+
+    /// Reject messages and arguments larger than `limits` and tool calls
+    /// past its rate limit, and cache as many results as it allows
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self.calls = TokenBucket::new(limits.calls_per_minute, limits.call_burst);
+        self.cache = ResponseCache::new(limits.cache_entries).map(Arc::new);
+        self
+    }
+
+    /// Only list and run the tools `filter` enables
+    pub fn with_tool_filter(mut self, filter: ToolsConfig) -> Self {
+        self.tool_filter = filter;
+        self
+    }
+
+    /// Send a `ping` request whenever the client has been silent for `interval`
+    pub fn with_keepalive(mut self, interval: Duration) -> Self {
+        self.keepalive = Some(interval);
+        self
+    }
+
+    /// Wait at most `timeout` for in-flight requests when shutting down
+    pub fn with_drain_timeout(mut self, timeout: Duration) -> Self {
+        self.drain_timeout = timeout;
+        self
+    }
+
+    /// A fresh session with this server's settings, writing to `sink`
+    ///
+    /// The session starts with the tools' current version selection and its
+    /// own request registry, keepalive state, log level and full allowance of
+    /// tool calls.
+    fn session(&self, sink: Sink, framing: Framing) -> Self {
+        Self {
+            tools: self.tools.clone(),
+            requests: Registry::default(),
+            keepalive: self.keepalive,
+            pings: AtomicU64::new(0),
+            ping_pending: AtomicBool::new(false),
+            drain_timeout: self.drain_timeout,
+            tool_filter: self.tool_filter.clone(),
+            limits: self.limits,
+            calls: TokenBucket::new(self.limits.calls_per_minute, self.limits.call_burst),
+            cache: self.cache.clone(),
+            framing,
+            client_log: Arc::new(ClientLog::new(sink.clone())),
+            sink,
+            subscriptions: Mutex::default(),
+            protocol_version: Mutex::new(PROTOCOL_VERSIONS[0]),
+        }
+    }
+
+    /// Serve requests until stdin closes, a signal arrives or the client asks
+    /// to shut down
+    ///
+    /// On shutdown no further requests are read; those in flight get up to
+    /// the drain timeout to be answered, after which they are cancelled.
+    pub async fn run(self) -> Result<Shutdown> {
+        let server = Arc::new(self);
+        logging::set_default(Arc::clone(&server.client_log));
+        let shutdown = Arc::clone(&server)
+            .serve(tokio::io::stdin(), shutdown_signal())
+            .await;
+        tracing::info!("Leptos MCP Server stopped ({})", shutdown.reason);
+        let _ = io::stdout().flush();
+        Ok(shutdown)
+    }
+
+    /// Serve every connection to a Unix socket at `path` until a signal arrives
+    ///
+    /// Each connection is its own session, with its own `initialize`
+    /// handshake, selected Leptos version and log level. On a signal the
+    /// listener closes and every session drains as [`run`](Self::run) does.
+    #[cfg(unix)]
+    pub async fn run_unix(self, path: &Path) -> Result<Shutdown> {
+        use tokio::io::AsyncWriteExt;
+        use tokio::net::UnixListener;
+
+        // A socket file left by a server that didn't shut down cleanly
+        if path.exists() && std::os::unix::net::UnixStream::connect(path).is_err() {
+            std::fs::remove_file(path)?;
+        }
+        let listener = UnixListener::bind(path)?;
+        tracing::info!("Listening on {}", path.display());
+
+        let (connections, incoming) = mpsc::unbounded_channel();
+        let accepting = tokio::spawn(async move {
+            loop {
+                let stream = match listener.accept().await {
+                    Ok((stream, _)) => stream,
+                    Err(e) => {
+                        tracing::warn!("Failed to accept a connection: {}", e);
+                        continue;
+                    }
+                };
+                let (reader, mut writer) = stream.into_split();
+                let (sender, mut outgoing) = mpsc::unbounded_channel::<Vec<u8>>();
+                tokio::spawn(async move {
+                    while let Some(bytes) = outgoing.recv().await {
+                        if writer.write_all(&bytes).await.is_err() {
+                            break;
+                        }
+                    }
+                });
+                let connection = Connection {
+                    reader: Box::new(reader),
+                    sink: Sink::new(Target::Channel(sender)),
+                    framing: None,
+                    tools: None,
+                };
+                if connections.send(connection).is_err() {
                     break;
                 }
+            }
+        });
+
+        let shutdown = self.serve_sessions(incoming).await;
+        accepting.abort();
+        let _ = std::fs::remove_file(path);
+        tracing::info!("Leptos MCP Server stopped ({})", shutdown.reason);
+        Ok(shutdown)
+    }
+
+    /// Serve every WebSocket connection to `addr` until a signal arrives
+    ///
+    /// Sessions work as with [`run_unix`](Self::run_unix); each text or binary
+    /// message is one JSON-RPC message. Connections from browsers are only
+    /// accepted from loopback origins and those in `origins` (`*` for any).
+    /// With `auth` tokens, a connection must send one of them as a bearer
+    /// token, and its session serves only the tools that token allows.
+    pub async fn run_ws(
+        self,
+        addr: &str,
+        origins: Vec<String>,
+        auth: AuthConfig,
+    ) -> Result<Shutdown> {
+        let listener = tokio::net::TcpListener::bind(addr).await?;
+        tracing::info!("Listening on ws://{}", listener.local_addr()?);
+
+        let origins: Arc<[String]> = origins.into();
+        let auth = Arc::new(auth);
+        let (connections, incoming) = mpsc::unbounded_channel();
+        let accepting = tokio::spawn(async move {
+            loop {
+                let (stream, peer) = match listener.accept().await {
+                    Ok(accepted) => accepted,
+                    Err(e) => {
+                        tracing::warn!("Failed to accept a connection: {}", e);
+                        continue;
+                    }
+                };
+                // Handshake off the accept loop, so a slow client holds up no one else
+                let connections = connections.clone();
+                let origins = Arc::clone(&origins);
+                let auth = Arc::clone(&auth);
+                tokio::spawn(async move {
+                    match accept_websocket(stream, &origins, &auth).await {
+                        Ok(connection) => {
+                            let _ = connections.send(connection);
+                        }
+                        Err(e) => {
+                            tracing::warn!("Rejected WebSocket connection from {}: {}", peer, e)
+                        }
+                    }
+                });
+            }
+        });
+
+        let shutdown = self.serve_sessions(incoming).await;
+        accepting.abort();
+        tracing::info!("Leptos MCP Server stopped ({})", shutdown.reason);
+        Ok(shutdown)
+    }
+
+    /// Serve each connection a listening transport accepts as its own session
+    /// until a signal arrives, then stop and drain them all
+    async fn serve_sessions(self, mut incoming: mpsc::UnboundedReceiver<Connection>) -> Shutdown {
+        let (stop, stopped) = tokio::sync::watch::channel(None);
+        let mut sessions = JoinSet::new();
+        let signal = shutdown_signal();
+        tokio::pin!(signal);
+        let reason = loop {
+            let connection = tokio::select! {
+                reason = &mut signal => break reason,
+                Some(connection) = incoming.recv() => connection,
+            };
+            let framing = connection.framing.unwrap_or(self.framing);
+            let mut session = self.session(connection.sink, framing);
+            if let Some(tools) = &connection.tools {
+                session.tool_filter = session.tool_filter.restricted(tools);
+            }
+            let session = Arc::new(session);
+            let mut stopped = stopped.clone();
+            sessions.spawn(async move {
+                let stop = async move {
+                    let reason = stopped
+                        .wait_for(Option::is_some)
+                        .await
+                        .ok()
+                        .and_then(|r| *r);
+                    match reason {
+                        Some(reason) => reason,
+                        // The listener is gone without a reason: never stop early
+                        None => std::future::pending().await,
+                    }
+                };
+                let shutdown = session.serve(connection.reader, stop).await;
+                tracing::debug!("Session ended ({})", shutdown.reason);
+                shutdown
+            });
+            while sessions.try_join_next().is_some() {}
+        };
+
+        let _ = stop.send(Some(reason));
+        let mut drained = true;
+        while let Some(session) = sessions.join_next().await {
+            drained &= session.is_ok_and(|shutdown| shutdown.drained);
+        }
+        Shutdown { reason, drained }
+    }
+
+    /// Serve one session's requests from `input` until it closes, the client
+    /// asks to shut down or `stop` resolves
+    ///
+    /// Each request runs on the blocking pool so the reader keeps going and can
+    /// see `notifications/cancelled` for requests that are still in flight.
+    async fn serve(
+        self: Arc<Self>,
+        input: impl AsyncRead + Unpin,
+        stop: impl Future<Output = ShutdownReason>,
+    ) -> Shutdown {
+        let server = self;
+        let mut messages = MessageReader::new(
+            input,
+            server.framing,
+            server.limits.max_line_bytes,
+            server.sink.clone(),
+        );
+        let mut in_flight = JoinSet::new();
+        tokio::pin!(stop);
+        let notifier = tokio::spawn(Arc::clone(&server).notify_doc_changes());
+
+        let reason = loop {
+            let next = async {
+                match server.keepalive {
+                    Some(interval) => tokio::time::timeout(interval, messages.next()).await.ok(),
+                    None => Some(messages.next().await),
+                }
+            };
+            let line = tokio::select! {
+                reason = &mut stop => break reason,
+                line = next => line,
+            };
+            let line = match line {
+                Some(Ok(Some(Incoming::Text(l)))) => l,
+                Some(Ok(Some(Incoming::TooLong(len)))) => {
+                    let message = format!(
+                        "Message of {} bytes exceeds the {}-byte limit",
+                        len, server.limits.max_line_bytes
+                    );
+                    tracing::warn!("{}", message);
+                    // The id is in the discarded line, so the error can't name it
+                    server.sink.send(&JsonRpcResponse {
+                        jsonrpc: "2.0".to_string(),
+                        id: Value::Null,
+                        result: None,
+                        error: Some(JsonRpcError {
+                            code: INVALID_PARAMS,
+                            message,
+                            data: None,
+                        }),
+                    });
+                    continue;
+                }
+                Some(Ok(None)) => break ShutdownReason::Eof,
+                Some(Err(e)) => {
+                    tracing::error!("Failed to read line: {}", e);
+                    break ShutdownReason::Eof;
+                }
+                None => {
+                    server.send_ping();
+                    continue;
+                }
             };
 
             // Skip empty lines
@@ -67,76 +826,577 @@ impl McpServer {
             }
 
             // Parse JSON-RPC request
-            let request: JsonRpcRequest = match serde_json::from_str(&line) {
-                Ok(req) => req,
+            let message: Value = match serde_json::from_str(&line) {
+                Ok(message) => message,
                 Err(e) => {
-                    eprintln!("Failed to parse request: {} - line: {}", e, line);
+                    tracing::warn!("Failed to parse request: {} - line: {}", e, line);
+                    server.sink.send(&error_response(
+                        Value::Null,
+                        PARSE_ERROR,
+                        format!("Parse error: {}", e),
+                    ));
                     continue;
                 }
             };
-
-            // Notifications (no id) don't get a response per JSON-RPC spec
-            if request.id.is_none() {
-                // Just handle the notification silently
-                self.handle_notification(&request.method);
+            if let Ok(json) = serde_json::to_string(&message) {
+                record::message(server.sink.session(), Direction::In, &json);
+            }
+            if let Value::Array(batch) = message {
+                if let Some(reason) = server.start_batch(batch, &mut in_flight) {
+                    break reason;
+                }
+                while in_flight.try_join_next().is_some() {}
                 continue;
             }
+            let request = match server.accept(message) {
+                Accepted::Request(request) => request,
+                Accepted::Handled(Some(reason)) => break reason,
+                Accepted::Handled(None) => continue,
+                Accepted::Invalid(e) => {
+                    tracing::warn!("Invalid request: {} - line: {}", e, line);
+                    server
+                        .sink
+                        .send(&invalid_request(format!("Invalid request: {}", e)));
+                    continue;
+                }
+            };
+
+            // Handle request and send response, unless the client cancelled it
+            let execute = Arc::clone(&server).execute(request);
+            let sink = server.sink.clone();
+            in_flight.spawn(async move {
+                if let Some(response) = execute.await {
+                    sink.send(&response);
+                }
+            });
+            while in_flight.try_join_next().is_some() {}
+        };
 
-            // Handle request and send response
-            let response = self.handle_request(&request).await;
-            let response_json = serde_json::to_string(&response)?;
-            writeln!(stdout, "{}", response_json)?;
-            stdout.flush()?;
+        // Answer everything already received before exiting
+        while in_flight.try_join_next().is_some() {}
+        if !in_flight.is_empty() {
+            tracing::info!(
+                "Shutting down ({}); finishing {} in-flight request(s)",
+                reason,
+                in_flight.len()
+            );
+        }
+        let drain = async { while in_flight.join_next().await.is_some() {} };
+        let drained = tokio::time::timeout(server.drain_timeout, drain)
+            .await
+            .is_ok();
+        notifier.abort();
+        if !drained {
+            server.requests.cancel_all();
+            tracing::warn!(
+                "Drain timeout elapsed; abandoned {} in-flight request(s)",
+                in_flight.len()
+            );
         }
+        Shutdown { reason, drained }
+    }
 
-        Ok(())
+    /// Handle a message unless it's a request, which is returned to be run
+    fn accept(&self, message: Value) -> Accepted {
+        // Responses to the server's own requests (keepalive pings)
+        if message.get("method").is_none() {
+            if !message.is_object() {
+                return Accepted::Invalid("not a JSON-RPC object".to_string());
+            }
+            self.handle_response(&message);
+            return Accepted::Handled(None);
+        }
+        let request: JsonRpcRequest = match serde_json::from_value(message) {
+            Ok(request) => request,
+            Err(e) => return Accepted::Invalid(e.to_string()),
+        };
+        // Notifications (no id) don't get a response per JSON-RPC spec
+        if request.id.is_none() {
+            return Accepted::Handled(
+                self.handle_notification(&request.method, request.params.as_ref()),
+            );
+        }
+        Accepted::Request(request)
+    }
+
+    /// Run a request on the blocking pool and yield its response
+    ///
+    /// A tool call still running when its time limit passes is answered with
+    /// an error naming the limit, and its token is flipped so the work stops
+    /// at its next check; whatever it returns later is dropped. Work that
+    /// never checks keeps its blocking thread busy, but the session goes on.
+    /// A request whose handler panics is answered with an internal error.
+    async fn execute(self: Arc<Self>, request: JsonRpcRequest) -> Option<JsonRpcResponse> {
+        let id = request.id.clone().unwrap_or(Value::Null);
+        let time_limit = self.time_limit(&request);
+        let mut job = tokio::task::spawn_blocking(self.start(request));
+        let Some((tool, limit, duration)) = time_limit else {
+            return self.joined(id, job.await);
+        };
+        match tokio::time::timeout(duration, &mut job).await {
+            Ok(response) => self.joined(id, response),
+            Err(_) if self.requests.time_out(&id) => {
+                tracing::warn!(tool, limit, "Tool call timed out");
+                metrics::record("tools/call", Some(tool), Outcome::TimedOut, duration);
+                let mut response = error_response(
+                    id,
+                    TIMED_OUT,
+                    format!(
+                        "Tool '{}' didn't finish within {}s ({}); narrow the call or raise the limit",
+                        tool,
+                        duration.as_secs(),
+                        limit
+                    ),
+                );
+                if let Some(error) = &mut response.error {
+                    error.data = Some(json!({
+                        "limit": limit,
+                        "limit_secs": duration.as_secs(),
+                    }));
+                }
+                Some(response)
+            }
+            // Finished just now, or cancelled by the client
+            Err(_) => self.joined(id, job.await),
+        }
     }
 
-    fn handle_notification(&self, method: &str) {
-        eprintln!("Received notification: {}", method);
-        // Notifications don't require responses
+    /// The response of a finished job, or an internal error for request `id`
+    /// if the job panicked
+    fn joined(
+        &self,
+        id: Value,
+        joined: Result<Option<JsonRpcResponse>, tokio::task::JoinError>,
+    ) -> Option<JsonRpcResponse> {
+        match joined {
+            Ok(response) => response,
+            Err(e) => {
+                tracing::error!(id = %id, "Request handler failed: {}", e);
+                self.requests.finish(&id);
+                Some(error_response(
+                    id,
+                    INTERNAL_ERROR,
+                    "Internal error while handling the request".to_string(),
+                ))
+            }
+        }
     }
 
-    async fn handle_request(&self, request: &JsonRpcRequest) -> JsonRpcResponse {
+    /// The tool a `tools/call` request calls, and the name and value of the
+    /// time limit it runs under, if it has one
+    fn time_limit(
+        &self,
+        request: &JsonRpcRequest,
+    ) -> Option<(&'static str, &'static str, Duration)> {
+        if request.method != "tools/call" {
+            return None;
+        }
+        let name = request.params.as_ref()?.get("name")?.as_str()?;
+        let tool = registry::find(name)?;
+        let (limit, duration) = self.limits.timeout(tool)?;
+        Some((tool.name, limit, duration))
+    }
+
+    /// Register a request as in flight and return the blocking job running it
+    ///
+    /// The job yields the response, or `None` if the client cancelled the
+    /// request or it timed out meanwhile.
+    fn start(
+        self: &Arc<Self>,
+        request: JsonRpcRequest,
+    ) -> impl FnOnce() -> Option<JsonRpcResponse> + Send + 'static {
         let id = request.id.clone().unwrap_or(Value::Null);
+        let cancel = self.requests.register(&id);
+        let server = Arc::clone(self);
+        let span = tracing::info_span!(
+            "request",
+            id = %id,
+            method = %request.method,
+            tool = tracing::field::Empty,
+        );
+        move || {
+            let _entered = span.enter();
+            let _log = logging::enter(Arc::clone(&server.client_log));
+            let started = Instant::now();
+            let response = {
+                // Stops tracking the request even if the handler panics
+                let _finish = Finish {
+                    requests: &server.requests,
+                    id: &id,
+                };
+                server.handle_request(&request, &cancel)
+            };
+            // The timeout was answered and recorded when it passed
+            if cancel.is_timed_out() {
+                return None;
+            }
+            let latency = started.elapsed();
+            let latency_ms = latency.as_millis() as u64;
+            let outcome = if cancel.is_cancelled() {
+                Outcome::Cancelled
+            } else if response
+                .error
+                .as_ref()
+                .is_some_and(|e| e.code == RATE_LIMITED)
+            {
+                Outcome::RateLimited
+            } else if response.error.is_some() {
+                Outcome::Error
+            } else if response.result.as_ref().and_then(|r| r.get("isError"))
+                == Some(&Value::Bool(true))
+            {
+                Outcome::ToolError
+            } else {
+                Outcome::Ok
+            };
+            match outcome {
+                Outcome::Error | Outcome::ToolError | Outcome::RateLimited => {
+                    tracing::warn!(latency_ms, outcome = outcome.as_str(), "Request failed")
+                }
+                _ => {
+                    tracing::debug!(latency_ms, outcome = outcome.as_str(), "Request finished")
+                }
+            }
+            // Names the client made up would each add a series
+            let method = match &response.error {
+                Some(error) if error.code == METHOD_NOT_FOUND => "unknown",
+                _ => request.method.as_str(),
+            };
+            let tool = (method == "tools/call").then(|| {
+                let name = request
+                    .params
+                    .as_ref()
+                    .and_then(|p| p.get("name"))
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default();
+                TOOL_NAMES
+                    .iter()
+                    .find(|t| **t == name)
+                    .copied()
+                    .unwrap_or("unknown")
+            });
+            metrics::record(method, tool, outcome, latency);
+            (!cancel.is_cancelled()).then_some(response)
+        }
+    }
+
+    /// Run the requests of a JSON-RPC batch concurrently and send their
+    /// responses as one array, in the order of the batch
+    ///
+    /// Notifications in the batch are handled right away and get no entry; a
+    /// batch of only notifications gets no response. `Some` if one of them
+    /// asks the server to shut down, after the batch's requests are started.
+    fn start_batch(
+        self: &Arc<Self>,
+        batch: Vec<Value>,
+        in_flight: &mut JoinSet<()>,
+    ) -> Option<ShutdownReason> {
+        if batch.is_empty() {
+            self.sink.send(&invalid_request("Empty batch".to_string()));
+            return None;
+        }
+        let mut shutdown = None;
+        let mut entries = Vec::new();
+        for message in batch {
+            match self.accept(message) {
+                Accepted::Request(request) => {
+                    entries.push(Ok(tokio::spawn(Arc::clone(self).execute(request))))
+                }
+                Accepted::Handled(reason) => shutdown = shutdown.or(reason),
+                Accepted::Invalid(e) => {
+                    tracing::warn!("Invalid request in batch: {}", e);
+                    entries.push(Err(invalid_request(format!("Invalid request: {}", e))));
+                }
+            }
+        }
+        if entries.is_empty() {
+            return shutdown;
+        }
+        let sink = self.sink.clone();
+        in_flight.spawn(async move {
+            let mut responses = Vec::new();
+            for entry in entries {
+                match entry {
+                    Ok(job) => responses.extend(job.await.ok().flatten()),
+                    Err(response) => responses.push(response),
+                }
+            }
+            if !responses.is_empty() {
+                sink.send(&responses);
+            }
+        });
+        shutdown
+    }
 
-        eprintln!("Handling request: {}", request.method);
+    /// A session that handles messages given to [`dispatch`](Self::dispatch)
+    /// and drops everything it would send on its own
+    pub fn detached_session(&self) -> Self {
+        self.session(Sink::new(Target::Discard), Framing::Ndjson)
+    }
 
-        let result = match request.method.as_str() {
-            "initialize" => self.handle_initialize(),
-            "tools/list" => self.handle_list_tools(),
-            "tools/call" => self.handle_call_tool(request.params.as_ref()),
-            _ => {
-                eprintln!("Unknown method: {}", request.method);
-                Ok(json!({}))
+    /// Handle one message as a session would, without a transport
+    ///
+    /// Returns the response to a request, or the error answering a message
+    /// that isn't one; notifications and responses to the server's pings get
+    /// `None`. A batch gets the
+    /// array of its responses. Requests run one at a time, so
+    /// `notifications/cancelled` has nothing to cancel, and tool calls have
+    /// no time limit.
+    pub fn dispatch(&self, message: &Value) -> Option<Value> {
+        if let Value::Array(batch) = message {
+            if batch.is_empty() {
+                return serde_json::to_value(invalid_request("Empty batch".to_string())).ok();
+            }
+            let responses: Vec<Value> = batch
+                .iter()
+                .filter_map(|message| match self.accept(message.clone()) {
+                    Accepted::Request(request) => {
+                        let response = self.handle_request(&request, &CancelToken::default());
+                        serde_json::to_value(response).ok()
+                    }
+                    Accepted::Handled(_) => None,
+                    Accepted::Invalid(e) => {
+                        serde_json::to_value(invalid_request(format!("Invalid request: {}", e)))
+                            .ok()
+                    }
+                })
+                .collect();
+            return (!responses.is_empty()).then_some(Value::Array(responses));
+        }
+        match self.accept(message.clone()) {
+            Accepted::Request(request) => {
+                let response = self.handle_request(&request, &CancelToken::default());
+                serde_json::to_value(response).ok()
+            }
+            Accepted::Handled(_) => None,
+            Accepted::Invalid(e) => {
+                serde_json::to_value(invalid_request(format!("Invalid request: {}", e))).ok()
+            }
+        }
+    }
+
+    /// Tell the client about every subscribed section a sync or docs reload changes
+    async fn notify_doc_changes(self: Arc<Self>) {
+        use tokio::sync::broadcast::error::RecvError;
+
+        let mut changes = docs::subscribe_changes();
+        loop {
+            let changed = match changes.recv().await {
+                Ok(changed) => changed,
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!("Missed {} docs change(s)", skipped);
+                    continue;
+                }
+                Err(RecvError::Closed) => break,
+            };
+            let uris: Vec<String> = {
+                let subscriptions = self.subscriptions.lock().unwrap();
+                changed
+                    .iter()
+                    .map(|(version, path)| docs::resource_uri(*version, path))
+                    .filter(|uri| subscriptions.contains(uri))
+                    .collect()
+            };
+            for uri in uris {
+                tracing::debug!("Resource updated: {}", uri);
+                self.sink.send(&json!({
+                    "jsonrpc": "2.0",
+                    "method": "notifications/resources/updated",
+                    "params": { "uri": uri }
+                }));
             }
+        }
+    }
+
+    /// Ask the client whether it's still there; warn if it ignored the last ask
+    fn send_ping(&self) {
+        if self.ping_pending.swap(true, Ordering::Relaxed) {
+            tracing::warn!("Client has not answered the previous keepalive ping");
+        }
+        let seq = self.pings.fetch_add(1, Ordering::Relaxed) + 1;
+        self.sink.send(&json!({
+            "jsonrpc": "2.0",
+            "id": format!("ping-{}", seq),
+            "method": "ping"
+        }));
+    }
+
+    fn handle_response(&self, response: &Value) {
+        let id = response.get("id").and_then(|v| v.as_str()).unwrap_or("");
+        if id.starts_with("ping-") {
+            self.ping_pending.store(false, Ordering::Relaxed);
+        } else {
+            tracing::debug!("Ignoring response to unknown request {}", response["id"]);
+        }
+    }
+
+    /// Handle a notification; `Some` if it asks the server to shut down
+    fn handle_notification(&self, method: &str, params: Option<&Value>) -> Option<ShutdownReason> {
+        tracing::debug!("Received notification: {}", method);
+        // Notifications don't require responses, so problems are only logged
+        let found = find_method(method)?;
+        let Call::Notification(handle) = found.call else {
+            tracing::warn!("Request {} without an id gets no response", method);
+            return None;
+        };
+        if let Err(message) = check_params(found, params) {
+            tracing::warn!("Ignoring notification: {}", message);
+            return None;
+        }
+        handle(self, params)
+    }
+
+    fn handle_request(&self, request: &JsonRpcRequest, cancel: &CancelToken) -> JsonRpcResponse {
+        let id = request.id.clone().unwrap_or(Value::Null);
+
+        tracing::debug!("Handling request: {}", request.method);
+
+        let Some(method) = find_method(&request.method) else {
+            tracing::warn!("Unknown method: {}", request.method);
+            return error_response(id, METHOD_NOT_FOUND, method_not_found(&request.method));
+        };
+        let Call::Request(handle) = method.call else {
+            return error_response(
+                id,
+                INVALID_REQUEST,
+                format!("{} is a notification; send it without an id", method.name),
+            );
         };
 
-        match result {
+        let params = request.params.as_ref();
+        if let Err(message) = check_params(method, params).and_then(|_| self.validate(request)) {
+            tracing::warn!("Rejected request: {}", message);
+            return error_response(id, INVALID_PARAMS, message);
+        }
+        if let Some(response) = self.rate_limit(&request.method, &id) {
+            return response;
+        }
+
+        match handle(self, params, cancel) {
             Ok(value) => JsonRpcResponse {
                 jsonrpc: "2.0".to_string(),
                 id,
                 result: Some(value),
                 error: None,
             },
-            Err(msg) => JsonRpcResponse {
-                jsonrpc: "2.0".to_string(),
-                id,
-                result: None,
-                error: Some(JsonRpcError {
-                    code: -32600,
-                    message: msg,
-                }),
-            },
+            Err(error) => error_response(id, error.code, error.message),
         }
     }
 
-    fn handle_initialize(&self) -> Result<Value, String> {
+    /// The error refusing a tool call past this session's allowance, if it is
+    fn rate_limit(&self, method: &str, id: &Value) -> Option<JsonRpcResponse> {
+        if method != "tools/call" {
+            return None;
+        }
+        let wait = self.calls.as_ref()?.take().err()?;
+        let retry_after_ms = wait.as_millis() as u64 + 1;
+        tracing::warn!(retry_after_ms, "Rate limited tool call");
+        let mut response = error_response(
+            id.clone(),
+            RATE_LIMITED,
+            format!(
+                "Rate limit of {} tool calls per minute exceeded; retry in {:.1}s",
+                self.limits.calls_per_minute,
+                retry_after_ms as f64 / 1000.0
+            ),
+        );
+        if let Some(error) = &mut response.error {
+            error.data = Some(json!({
+                "retry_after_ms": retry_after_ms,
+                "calls_per_minute": self.limits.calls_per_minute,
+            }));
+        }
+        Some(response)
+    }
+
+    /// Checks that reject a request before it reaches its handler
+    fn validate(&self, request: &JsonRpcRequest) -> Result<(), String> {
+        let params = request.params.as_ref();
+        let tool = params
+            .and_then(|p| p.get("name").or_else(|| p.pointer("/ref/name")))
+            .and_then(|v| v.as_str());
+        match (request.method.as_str(), tool) {
+            ("tools/call" | "completion/complete", Some(tool))
+                if TOOL_NAMES.contains(&tool) && !self.tool_filter.is_enabled(tool) =>
+            {
+                Err(format!("Tool '{}' is disabled on this server", tool))
+            }
+            ("tools/call", Some(_)) => self.check_arguments(params),
+            _ => Ok(()),
+        }
+    }
+
+    /// Enforce the argument size limits of a tool call
+    fn check_arguments(&self, params: Option<&Value>) -> Result<(), String> {
+        let Some(arguments) = params.and_then(|p| p.get("arguments")) else {
+            return Ok(());
+        };
+        let max = self.limits.max_code_bytes;
+        for name in SOURCE_ARGUMENTS {
+            let len = arguments
+                .get(name)
+                .and_then(|v| v.as_str())
+                .map_or(0, str::len);
+            if len > max {
+                return Err(format!(
+                    "Argument '{}' is {} bytes, over the {}-byte limit",
+                    name, len, max
+                ));
+            }
+        }
+        if let Some(files) = arguments.get("files").and_then(|v| v.as_array()) {
+            if files.len() > self.limits.max_project_files {
+                return Err(format!(
+                    "'files' has {} entries, over the limit of {}",
+                    files.len(),
+                    self.limits.max_project_files
+                ));
+            }
+            for file in files {
+                let len = file
+                    .get("content")
+                    .and_then(|v| v.as_str())
+                    .map_or(0, str::len);
+                if len > max {
+                    return Err(format!(
+                        "File '{}' is {} bytes, over the {}-byte limit",
+                        file.get("path")
+                            .and_then(|v| v.as_str())
+                            .unwrap_or_default(),
+                        len,
+                        max
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Agree on the client's protocol revision if the server speaks it, else
+    /// offer the newest one, and declare the server's capabilities
+    fn handle_initialize(&self, params: Option<&Value>) -> Result<Value, RequestError> {
+        let requested = params
+            .and_then(|p| p.get("protocolVersion"))
+            .and_then(|v| v.as_str());
+        let version = PROTOCOL_VERSIONS
+            .into_iter()
+            .find(|v| Some(*v) == requested)
+            .unwrap_or(PROTOCOL_VERSIONS[0]);
+        *self.protocol_version.lock().unwrap() = version;
+
+        let mut capabilities = serde_json::Map::new();
+        for capability in METHODS.iter().filter_map(|m| m.capability) {
+            capabilities.insert(capability.to_string(), json!({}));
+        }
+        if let Some(resources) = capabilities.get_mut("resources") {
+            *resources = json!({
+                "subscribe": find_method("resources/subscribe").is_some(),
+                "listChanged": false
+            });
+        }
         Ok(json!({
-            "protocolVersion": "2024-11-05",
-            "capabilities": {
-                "tools": {}
-            },
+            "protocolVersion": version,
+            "capabilities": capabilities,
             "serverInfo": {
                 "name": "leptos-mcp-server",
                 "version": "0.1.0"
@@ -144,81 +1404,1077 @@ impl McpServer {
         }))
     }
 
-    fn handle_list_tools(&self) -> Result<Value, String> {
-        Ok(json!({
-            "tools": [
-                {
-                    "name": "list-sections",
-                    "description": "List all available Leptos documentation sections with their use cases",
-                    "inputSchema": {
-                        "type": "object",
-                        "properties": {},
-                        "required": []
-                    }
-                },
-                {
-                    "name": "get-documentation",
-                    "description": "Get Leptos documentation for a specific section. Pass section name like 'signals', 'components', 'routing'",
-                    "inputSchema": {
-                        "type": "object",
-                        "properties": {
-                            "section": {
-                                "type": "string",
-                                "description": "Section name or path to retrieve"
-                            }
-                        },
-                        "required": ["section"]
-                    }
-                },
-                {
-                    "name": "leptos-autofixer",
-                    "description": "Analyze Leptos code and suggest fixes for common issues",
-                    "inputSchema": {
-                        "type": "object",
-                        "properties": {
-                            "code": {
-                                "type": "string",
-                                "description": "Leptos code to analyze"
-                            }
-                        },
-                        "required": ["code"]
-                    }
+    fn handle_list_tools(&self, params: Option<&Value>) -> Result<Value, RequestError> {
+        let tools: Value = registry::TOOLS
+            .iter()
+            .filter(|tool| self.tool_filter.is_enabled(tool.name))
+            .map(|tool| {
+                let mut entry = json!({
+                    "name": tool.name,
+                    "description": tool.description,
+                    "inputSchema": tool.input_schema(),
+                });
+                if self.speaks("2025-06-18") {
+                    entry["outputSchema"] = tool.output_schema();
                 }
-            ]
+                if self.speaks("2025-03-26") {
+                    entry["annotations"] = tool.annotations();
+                }
+                entry
+            })
+            .collect();
+        let (page, next_cursor) = paginate(&tools, params)?;
+        let mut result = json!({ "tools": page });
+        if let Some(cursor) = next_cursor {
+            result["nextCursor"] = json!(cursor);
+        }
+        Ok(result)
+    }
+
+    /// Documentation sections of the selected Leptos version, as resources
+    fn handle_list_resources(&self, params: Option<&Value>) -> Result<Value, RequestError> {
+        let version = self.tools.version();
+        let resources: Value = docs::list_sections(version)
+            .iter()
+            .map(|section| {
+                json!({
+                    "uri": docs::resource_uri(version, section.path),
+                    "name": section.path,
+                    "title": section.title,
+                    "description": section.use_cases,
+                    "mimeType": "text/markdown"
+                })
+            })
+            .collect();
+        let (page, next_cursor) = paginate(&resources, params)?;
+        let mut result = json!({ "resources": page });
+        if let Some(cursor) = next_cursor {
+            result["nextCursor"] = json!(cursor);
+        }
+        Ok(result)
+    }
+
+    /// URI templates clients can fill in to read sections and API items directly
+    fn handle_list_templates(&self, params: Option<&Value>) -> Result<Value, RequestError> {
+        let templates = json!([
+            {
+                "uriTemplate": DOCS_TEMPLATE,
+                "name": "leptos-docs",
+                "title": "Leptos documentation section",
+                "description": "A documentation section for a Leptos version (0.6, 0.7 or 0.8) by path, like 'signals' or 'router/nested-routes'",
+                "mimeType": "text/markdown"
+            },
+            {
+                "uriTemplate": API_TEMPLATE,
+                "name": "leptos-api",
+                "title": "Leptos API item",
+                "description": "Signature, bounds and docs of an API item of the selected Leptos version, by full path or path suffix like 'Resource::new'",
+                "mimeType": "text/markdown"
+            }
+        ]);
+        let (page, next_cursor) = paginate(&templates, params)?;
+        let mut result = json!({ "resourceTemplates": page });
+        if let Some(cursor) = next_cursor {
+            result["nextCursor"] = json!(cursor);
+        }
+        Ok(result)
+    }
+
+    fn handle_read_resource(&self, params: Option<&Value>) -> Result<Value, RequestError> {
+        let uri = resource_param(params)?;
+        Ok(json!({
+            "contents": [{
+                "uri": uri,
+                "mimeType": "text/markdown",
+                "text": self.read_resource(uri)?
+            }]
+        }))
+    }
+
+    /// Markdown content of a section or API item resource
+    fn read_resource(&self, uri: &str) -> Result<String, RequestError> {
+        if let Some((_, section)) = docs::resolve_uri(uri) {
+            return Ok(section.content.to_string());
+        }
+        match uri
+            .strip_prefix(api::RESOURCE_SCHEME)
+            .and_then(|rest| rest.strip_prefix("://"))
+        {
+            Some(path) if !path.is_empty() => self
+                .tools
+                .api_reference(path)
+                .map_err(RequestError::resource_not_found),
+            _ => Err(RequestError::resource_not_found(unknown_resource(uri))),
+        }
+    }
+
+    /// Start or stop sending `notifications/resources/updated` for a resource
+    ///
+    /// Only sections change while the server runs; API items can be
+    /// subscribed to but never update.
+    fn handle_subscribe(
+        &self,
+        params: Option<&Value>,
+        subscribe: bool,
+    ) -> Result<Value, RequestError> {
+        let uri = resource_param(params)?;
+        let mut subscriptions = self.subscriptions.lock().unwrap();
+        if subscribe {
+            self.read_resource(uri)?;
+            subscriptions.insert(uri.to_string());
+        } else {
+            subscriptions.remove(uri);
+        }
+        Ok(json!({}))
+    }
+
+    fn handle_set_level(&self, params: Option<&Value>) -> Result<Value, RequestError> {
+        let level = params
+            .and_then(|p| p.get("level"))
+            .and_then(|v| v.as_str())
+            .ok_or("Missing level")?;
+        self.client_log.set_level(level)?;
+        Ok(json!({}))
+    }
+
+    /// Complete a tool argument (`ref.name` is the tool name) or a resource
+    /// template variable (`ref.uri` is the template)
+    fn handle_complete(&self, params: Option<&Value>) -> Result<Value, RequestError> {
+        let params = params.ok_or("Missing params")?;
+        let argument = params
+            .pointer("/argument/name")
+            .and_then(|v| v.as_str())
+            .ok_or("Missing argument name")?;
+        let value = params
+            .pointer("/argument/value")
+            .and_then(|v| v.as_str())
+            .unwrap_or("");
+        let (tool, argument) = match params.pointer("/ref/uri").and_then(|v| v.as_str()) {
+            Some(DOCS_TEMPLATE) => ("get-documentation", argument),
+            Some(API_TEMPLATE) if argument == "item_path" => ("lookup-api", "path"),
+            Some(template) => {
+                return Err(format!("Unknown resource template '{}'", template).into())
+            }
+            None => (
+                params
+                    .pointer("/ref/name")
+                    .and_then(|v| v.as_str())
+                    .ok_or("Missing ref name")?,
+                argument,
+            ),
+        };
+        let values = self.tools.complete(tool, argument, value);
+        let total = values.len();
+        Ok(json!({
+            "completion": {
+                "values": values.into_iter().take(MAX_COMPLETIONS).collect::<Vec<_>>(),
+                "total": total,
+                "hasMore": total > MAX_COMPLETIONS
+            }
         }))
     }
 
-    fn handle_call_tool(&self, params: Option<&Value>) -> Result<Value, String> {
+    fn handle_call_tool(
+        &self,
+        params: Option<&Value>,
+        cancel: &CancelToken,
+    ) -> Result<Value, RequestError> {
         let params = params.ok_or("Missing params")?;
         let name = params
             .get("name")
             .and_then(|v| v.as_str())
             .ok_or("Missing tool name")?;
+        tracing::Span::current().record("tool", name);
         let arguments = params.get("arguments").cloned().unwrap_or(json!({}));
+        let progress = Progress::new(
+            params.pointer("/_meta/progressToken").cloned(),
+            cancel.clone(),
+        )
+        .with_sink(self.sink.clone())
+        .with_limits(self.limits);
 
-        let result = match name {
-            "list-sections" => self.tools.list_sections(),
-            "get-documentation" => {
-                let section = arguments
-                    .get("section")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("");
-                self.tools.get_documentation(section)
+        // Pure tools answer an identical call from the cache
+        let cached = self
+            .cache
+            .as_ref()
+            .zip(cache::ttl(name))
+            .map(|(cache, ttl)| (cache, ttl, self.tools.cache_key(name, &arguments)));
+        if let Some((cache, _, key)) = &cached {
+            if let Some(response) = cache.get(key) {
+                tracing::debug!("Answered from cache");
+                return Ok(self.for_revision(response));
             }
-            "leptos-autofixer" => {
-                let code = arguments.get("code").and_then(|v| v.as_str()).unwrap_or("");
-                self.tools.leptos_autofixer(code)
+        }
+
+        let tool = registry::find(name).ok_or_else(|| format!("Unknown tool: {}", name))?;
+        let result = tool.call(&self.tools, arguments, &progress);
+
+        // Tool failures are reported in the result so the agent can see and correct them
+        let (output, is_error) = match result {
+            Ok(output) => (output, false),
+            Err(text) => (ToolOutput::from(text), true),
+        };
+
+        let content: Vec<Value> = std::iter::once(output.text)
+            .chain(output.blocks)
+            .flat_map(|text| split_block(text, self.limits.max_block_chars))
+            .map(|text| json!({ "type": "text", "text": text }))
+            .collect();
+        let mut response = json!({
+            "content": content,
+            "isError": is_error
+        });
+        if let Some(structured) = output.structured {
+            response["structuredContent"] = structured;
+        }
+        if let Some((cache, ttl, key)) = cached.filter(|_| !is_error) {
+            cache.insert(key, response.clone(), ttl);
+        }
+        Ok(self.for_revision(response))
+    }
+
+    /// Whether the session's protocol revision is `revision` or newer
+    fn speaks(&self, revision: &str) -> bool {
+        // Revisions are dates, so they order as strings
+        *self.protocol_version.lock().unwrap() >= revision
+    }
+
+    /// A tool call result without the structured content an older revision
+    /// doesn't define
+    fn for_revision(&self, mut result: Value) -> Value {
+        if !self.speaks("2025-06-18") {
+            if let Some(result) = result.as_object_mut() {
+                result.remove("structuredContent");
+            }
+        }
+        result
+    }
+}
+
+/// Where a session's outgoing messages go
+///
+/// Responses and notifications come from concurrent requests; each message is
+/// written whole, so they never interleave.
+#[derive(Clone)]
+pub struct Sink(Arc<SinkInner>);
+
+struct SinkInner {
+    target: Target,
+    /// Distinguishes this session's messages in a recording
+    session: u64,
+    /// Frame with `Content-Length` headers instead of a trailing newline
+    content_length: AtomicBool,
+}
+
+enum Target {
+    Stdout,
+    /// Bytes for a task writing to a socket
+    Channel(mpsc::UnboundedSender<Vec<u8>>),
+    /// Nowhere: a session replaying recorded messages
+    Discard,
+}
+
+impl Sink {
+    /// The process's stdout, shared by everything that writes to it
+    pub fn stdout() -> Self {
+        static STDOUT: OnceLock<Sink> = OnceLock::new();
+        STDOUT.get_or_init(|| Self::new(Target::Stdout)).clone()
+    }
+
+    fn new(target: Target) -> Self {
+        static SESSIONS: AtomicU64 = AtomicU64::new(0);
+        Self(Arc::new(SinkInner {
+            target,
+            session: SESSIONS.fetch_add(1, Ordering::Relaxed),
+            content_length: AtomicBool::new(false),
+        }))
+    }
+
+    fn session(&self) -> u64 {
+        self.0.session
+    }
+
+    fn set_framing(&self, framing: Framing) {
+        self.0
+            .content_length
+            .store(framing == Framing::ContentLength, Ordering::Relaxed);
+    }
+
+    /// Write one JSON-RPC message
+    pub fn send(&self, message: &impl Serialize) {
+        let Ok(json) = serde_json::to_string(message) else {
+            return;
+        };
+        if let Target::Discard = self.0.target {
+            return;
+        }
+        record::message(self.0.session, Direction::Out, &json);
+        let framed = if self.0.content_length.load(Ordering::Relaxed) {
+            format!("Content-Length: {}\r\n\r\n{}", json.len(), json)
+        } else {
+            format!("{}\n", json)
+        };
+        let written = match &self.0.target {
+            Target::Stdout => {
+                let mut stdout = io::stdout().lock();
+                stdout
+                    .write_all(framed.as_bytes())
+                    .and_then(|_| stdout.flush())
+                    .is_ok()
             }
-            _ => return Err(format!("Unknown tool: {}", name)),
+            Target::Channel(sender) => sender.send(framed.into_bytes()).is_ok(),
+            Target::Discard => true,
         };
+        if !written {
+            // Not a tracing event: forwarding it would write to the sink again
+            eprintln!("Failed to write message");
+        }
+    }
+}
 
-        Ok(json!({
-            "content": [
+/// A client connection accepted by a listening transport
+struct Connection {
+    reader: Box<dyn AsyncRead + Unpin + Send>,
+    sink: Sink,
+    /// How its messages are delimited, if the transport decides that
+    framing: Option<Framing>,
+    /// The only tools its session serves, if its credentials limit them
+    tools: Option<Vec<String>>,
+}
+
+/// Complete a WebSocket handshake and bridge the socket to a session
+///
+/// Incoming messages become lines of NDJSON for the session's reader, and
+/// each message the session sends goes out as one text message. The origin
+/// and bearer token are checked in the handshake, before any message is read.
+async fn accept_websocket(
+    stream: tokio::net::TcpStream,
+    origins: &[String],
+    auth: &AuthConfig,
+) -> Result<Connection, String> {
+    use futures_util::{SinkExt, StreamExt};
+    use tokio::io::AsyncWriteExt;
+    use tokio_tungstenite::tungstenite::handshake::server::{ErrorResponse, Request, Response};
+    use tokio_tungstenite::tungstenite::http::StatusCode;
+    use tokio_tungstenite::tungstenite::Message;
+
+    use tokio_tungstenite::tungstenite::http::header::{HeaderValue, WWW_AUTHENTICATE};
+
+    let mut tools = None;
+    // The callback's signature is tungstenite's
+    #[allow(clippy::result_large_err)]
+    let check_request = |request: &Request, response: Response| {
+        let header = |name| {
+            request
+                .headers()
+                .get(name)
+                .and_then(|value: &HeaderValue| value.to_str().ok())
+        };
+        if let Some(origin) = header("origin").filter(|o| !origin_allowed(o, origins)) {
+            let mut error = ErrorResponse::new(Some(format!("Origin {} is not allowed", origin)));
+            *error.status_mut() = StatusCode::FORBIDDEN;
+            return Err(error);
+        }
+        if auth.is_required() {
+            let token = header("authorization").and_then(|value| {
+                let (scheme, token) = value.split_once(' ')?;
+                scheme.eq_ignore_ascii_case("bearer").then(|| token.trim())
+            });
+            let Some(found) = token.and_then(|token| auth.find(token)) else {
+                let mut error = ErrorResponse::new(Some("Missing or unknown bearer token".into()));
+                *error.status_mut() = StatusCode::UNAUTHORIZED;
+                error
+                    .headers_mut()
+                    .insert(WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+                return Err(error);
+            };
+            tools = found.tools.clone();
+        }
+        Ok(response)
+    };
+    let socket = tokio_tungstenite::accept_hdr_async(stream, check_request)
+        .await
+        .map_err(|e| e.to_string())?;
+    let (mut socket_out, mut socket_in) = socket.split();
+
+    let (sender, mut outgoing) = mpsc::unbounded_channel::<Vec<u8>>();
+    tokio::spawn(async move {
+        while let Some(bytes) = outgoing.recv().await {
+            let text = String::from_utf8_lossy(&bytes).trim_end().to_string();
+            if socket_out.send(Message::text(text)).await.is_err() {
+                return;
+            }
+        }
+        let _ = socket_out.close().await;
+    });
+
+    let (mut pipe, reader) = tokio::io::duplex(64 * 1024);
+    tokio::spawn(async move {
+        while let Some(Ok(message)) = socket_in.next().await {
+            let mut bytes = match message {
+                Message::Text(text) => text.as_bytes().to_vec(),
+                Message::Binary(bytes) => bytes.to_vec(),
+                Message::Close(_) => break,
+                _ => continue,
+            };
+            // One message per line; line breaks in JSON are only whitespace
+            for byte in &mut bytes {
+                if matches!(byte, b'\n' | b'\r') {
+                    *byte = b' ';
+                }
+            }
+            bytes.push(b'\n');
+            if pipe.write_all(&bytes).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    Ok(Connection {
+        reader: Box::new(reader),
+        sink: Sink::new(Target::Channel(sender)),
+        framing: Some(Framing::Ndjson),
+        tools,
+    })
+}
+
+/// Whether a browser page at `origin` may connect: loopback pages always can
+fn origin_allowed(origin: &str, allowed: &[String]) -> bool {
+    let authority = origin.split_once("://").map_or(origin, |(_, rest)| rest);
+    let host = match authority.strip_prefix('[') {
+        Some(rest) => rest.split(']').next(),
+        None => authority.split(':').next(),
+    };
+    matches!(host, Some("localhost" | "127.0.0.1" | "::1"))
+        || allowed
+            .iter()
+            .any(|a| a == "*" || a.trim_end_matches('/').eq_ignore_ascii_case(origin))
+}
+
+/// How messages are delimited on stdio
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Framing {
+    /// One JSON message per line
+    Ndjson,
+    /// LSP-style `Content-Length` header block before each message
+    ContentLength,
+    /// Whichever the client's first message uses; replies follow suit
+    Auto,
+}
+
+impl Framing {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "ndjson" => Ok(Self::Ndjson),
+            "content-length" => Ok(Self::ContentLength),
+            "auto" => Ok(Self::Auto),
+            other => Err(format!(
+                "Unsupported framing '{}'. Expected 'ndjson', 'content-length' or 'auto'",
+                other
+            )),
+        }
+    }
+}
+
+/// One message read from the client
+#[derive(Debug, PartialEq)]
+enum Incoming {
+    Text(String),
+    /// A message over the limit, discarded; its length in bytes
+    TooLong(usize),
+}
+
+/// Reader of framed messages that holds at most `max` bytes of one
+///
+/// Bytes past the limit are skipped instead of buffered. A partly read message
+/// is kept in the reader, so `next` can be cancelled (by the keepalive timeout
+/// or a signal) without losing data. Detected framing is applied to `sink`.
+struct MessageReader<R> {
+    reader: BufReader<R>,
+    framing: Framing,
+    sink: Sink,
+    max: usize,
+    line: Vec<u8>,
+    /// Length of the current line, counting skipped bytes
+    len: usize,
+    /// `Content-Length` of the header block being read
+    content_length: Option<usize>,
+    /// Body being read after a header block
+    body: Option<Body>,
+}
+
+struct Body {
+    len: usize,
+    remaining: usize,
+    /// Empty when the body is over the limit and being skipped
+    data: Vec<u8>,
+}
+
+impl<R: AsyncRead + Unpin> MessageReader<R> {
+    fn new(reader: R, framing: Framing, max: usize, sink: Sink) -> Self {
+        sink.set_framing(framing);
+        Self {
+            reader: BufReader::new(reader),
+            framing,
+            sink,
+            max,
+            line: Vec::new(),
+            len: 0,
+            content_length: None,
+            body: None,
+        }
+    }
+
+    /// The next message; `None` at EOF
+    async fn next(&mut self) -> io::Result<Option<Incoming>> {
+        loop {
+            if let Some(body) = self.read_body().await? {
+                return Ok(Some(body));
+            }
+            if self.body.is_some() {
+                // EOF in the middle of a body
+                return Ok(None);
+            }
+            let Some(line) = self.next_line().await? else {
+                return Ok(None);
+            };
+            let header = match (&line, self.framing) {
+                (_, Framing::Ndjson) => return Ok(Some(line)),
+                (Incoming::Text(text), Framing::Auto) => {
+                    if text.trim().is_empty() {
+                        continue;
+                    }
+                    if !is_content_length(text) {
+                        self.framing = Framing::Ndjson;
+                        self.sink.set_framing(Framing::Ndjson);
+                        return Ok(Some(line));
+                    }
+                    self.framing = Framing::ContentLength;
+                    self.sink.set_framing(Framing::ContentLength);
+                    text
+                }
+                (Incoming::Text(text), _) => text,
+                // Oversized lines can't be meaningful headers
+                (Incoming::TooLong(_), Framing::Auto) => return Ok(Some(line)),
+                (Incoming::TooLong(_), _) => continue,
+            };
+            if !header.trim().is_empty() {
+                if let Some((_, value)) =
+                    header.split_once(':').filter(|_| is_content_length(header))
                 {
-                    "type": "text",
-                    "text": result
+                    self.content_length = value.trim().parse().ok();
                 }
-            ]
+                continue;
+            }
+            // A blank line ends the header block
+            if let Some(len) = self.content_length.take() {
+                let keep = len <= self.max;
+                self.body = Some(Body {
+                    len,
+                    remaining: len,
+                    data: Vec::with_capacity(if keep { len } else { 0 }),
+                });
+            }
+        }
+    }
+
+    /// Continue the body in progress; `Some` once it is complete
+    async fn read_body(&mut self) -> io::Result<Option<Incoming>> {
+        let Some(body) = &mut self.body else {
+            return Ok(None);
+        };
+        while body.remaining > 0 {
+            let available = self.reader.fill_buf().await?;
+            if available.is_empty() {
+                return Ok(None);
+            }
+            let n = available.len().min(body.remaining);
+            if body.len <= self.max {
+                body.data.extend_from_slice(&available[..n]);
+            }
+            body.remaining -= n;
+            self.reader.consume(n);
+        }
+        let body = self.body.take().expect("body in progress");
+        Ok(Some(if body.len > self.max {
+            Incoming::TooLong(body.len)
+        } else {
+            Incoming::Text(String::from_utf8_lossy(&body.data).into_owned())
         }))
     }
+
+    /// The next line, without its newline; `None` at EOF
+    async fn next_line(&mut self) -> io::Result<Option<Incoming>> {
+        loop {
+            let available = self.reader.fill_buf().await?;
+            if available.is_empty() {
+                return Ok((self.len > 0).then(|| self.take_line()));
+            }
+            let newline = available.iter().position(|b| *b == b'\n');
+            let chunk = &available[..newline.unwrap_or(available.len())];
+            if self.len + chunk.len() <= self.max {
+                self.line.extend_from_slice(chunk);
+            } else {
+                self.line.clear();
+            }
+            self.len += chunk.len();
+            let consumed = chunk.len() + usize::from(newline.is_some());
+            self.reader.consume(consumed);
+            if newline.is_some() {
+                return Ok(Some(self.take_line()));
+            }
+        }
+    }
+
+    fn take_line(&mut self) -> Incoming {
+        let len = std::mem::take(&mut self.len);
+        let line = std::mem::take(&mut self.line);
+        if len > self.max {
+            Incoming::TooLong(len)
+        } else {
+            Incoming::Text(String::from_utf8_lossy(&line).into_owned())
+        }
+    }
+}
+
+fn is_content_length(header: &str) -> bool {
+    header
+        .split_once(':')
+        .is_some_and(|(name, _)| name.trim().eq_ignore_ascii_case("content-length"))
+}
+
+/// Resolves on SIGINT or, on Unix, SIGTERM; never if signals can't be watched
+async fn shutdown_signal() -> ShutdownReason {
+    let interrupt = async {
+        match tokio::signal::ctrl_c().await {
+            Ok(()) => ShutdownReason::Interrupt,
+            Err(e) => {
+                tracing::warn!("Failed to listen for SIGINT: {}", e);
+                std::future::pending().await
+            }
+        }
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        use tokio::signal::unix::{signal, SignalKind};
+        match signal(SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                sigterm.recv().await;
+                ShutdownReason::Terminate
+            }
+            Err(e) => {
+                tracing::warn!("Failed to listen for SIGTERM: {}", e);
+                std::future::pending().await
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending();
+
+    tokio::select! {
+        reason = interrupt => reason,
+        reason = terminate => reason,
+    }
+}
+
+/// One page of a list response, and the cursor of the next page if any
+///
+/// Follows the MCP pagination contract: `params.cursor` is a `nextCursor`
+/// returned earlier, and the last page has no `nextCursor`.
+fn paginate<'a>(
+    items: &'a Value,
+    params: Option<&Value>,
+) -> Result<(&'a [Value], Option<String>), String> {
+    let items = items.as_array().map_or(&[][..], Vec::as_slice);
+    let cursor = params
+        .and_then(|p| p.get("cursor"))
+        .and_then(|v| v.as_str());
+    let start = parse_cursor(cursor)?.min(items.len());
+    let end = (start + PAGE_SIZE).min(items.len());
+    let next = (end < items.len()).then(|| end.to_string());
+    Ok((&items[start..end], next))
+}
+
+/// `text` as blocks of at most `max_chars`, each after a `[Part i of n]` line
+fn split_block(text: String, max_chars: usize) -> Vec<String> {
+    if text.len() <= max_chars {
+        return vec![text];
+    }
+    // Room for the part line
+    let parts = markdown::chunks(&text, max_chars.saturating_sub(24).max(1));
+    parts
+        .iter()
+        .enumerate()
+        .map(|(i, range)| {
+            format!(
+                "[Part {} of {}]\n{}",
+                i + 1,
+                parts.len(),
+                &text[range.clone()]
+            )
+        })
+        .collect()
+}
+
+/// `params.uri` of a resources request
+fn resource_param(params: Option<&Value>) -> Result<&str, String> {
+    params
+        .and_then(|p| p.get("uri"))
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| "Missing uri".to_string())
+}
+
+fn unknown_resource(uri: &str) -> String {
+    format!(
+        "Unknown resource '{}'. Expected {} or {}",
+        uri, DOCS_TEMPLATE, API_TEMPLATE
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn panicking_request_gets_internal_error() {
+        let server = McpServer::new();
+        let id = json!(7);
+        server.requests.register(&id);
+        let job = tokio::task::spawn_blocking(|| -> Option<JsonRpcResponse> { panic!("boom") });
+        let response = server.joined(id.clone(), job.await).unwrap();
+        assert_eq!(response.id, id);
+        assert_eq!(response.error.unwrap().code, INTERNAL_ERROR);
+        // No longer in flight
+        assert!(!server.requests.cancel(&id));
+    }
+
+    fn error_code(server: &McpServer, message: Value) -> i64 {
+        let response = server.dispatch(&message).unwrap();
+        response["error"]["code"].as_i64().unwrap()
+    }
+
+    #[test]
+    fn handler_errors_map_to_codes() {
+        let server = McpServer::new();
+        let request = |method: &str, params: Value| json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let code = error_code(
+            &server,
+            request(
+                "resources/read",
+                json!({ "uri": "leptos-docs://0.8/no-such-section" }),
+            ),
+        );
+        assert_eq!(code, RESOURCE_NOT_FOUND as i64);
+        let code = error_code(
+            &server,
+            request(
+                "completion/complete",
+                json!({ "ref": { "uri": "x://{a}" }, "argument": { "name": "a", "value": "" } }),
+            ),
+        );
+        assert_eq!(code, INVALID_PARAMS as i64);
+        let code = error_code(
+            &server,
+            request("tools/call", json!({ "name": "no-such-tool" })),
+        );
+        assert_eq!(code, INVALID_PARAMS as i64);
+        let code = error_code(&server, request("tools/list", json!({ "cursor": "x" })));
+        assert_eq!(code, INVALID_PARAMS as i64);
+        let code = error_code(&server, request("no/such/method", json!({})));
+        assert_eq!(code, METHOD_NOT_FOUND as i64);
+    }
+
+    #[test]
+    fn invalid_message_gets_invalid_request() {
+        let server = McpServer::new();
+        let response = server.dispatch(&json!("not a request")).unwrap();
+        assert_eq!(response["id"], Value::Null);
+        assert_eq!(response["error"]["code"], INVALID_REQUEST);
+        let response = server
+            .dispatch(&json!({ "jsonrpc": "2.0", "id": 1, "method": 5 }))
+            .unwrap();
+        assert_eq!(response["error"]["code"], INVALID_REQUEST);
+    }
+
+    #[test]
+    fn initialize_negotiates_protocol_version() {
+        let initialize = |version: &str| {
+            json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "initialize",
+                "params": { "protocolVersion": version }
+            })
+        };
+        let list = json!({ "jsonrpc": "2.0", "id": 2, "method": "tools/list" });
+
+        let server = McpServer::new();
+        let response = server.dispatch(&initialize("2025-03-26")).unwrap();
+        assert_eq!(response["result"]["protocolVersion"], "2025-03-26");
+        let tool = &server.dispatch(&list).unwrap()["result"]["tools"][0];
+        assert!(tool.get("annotations").is_some());
+        assert!(tool.get("outputSchema").is_none());
+
+        let response = server.dispatch(&initialize("2024-11-05")).unwrap();
+        assert_eq!(response["result"]["protocolVersion"], "2024-11-05");
+        let tool = &server.dispatch(&list).unwrap()["result"]["tools"][0];
+        assert!(tool.get("annotations").is_none());
+
+        let response = server.dispatch(&initialize("1999-01-01")).unwrap();
+        assert_eq!(response["result"]["protocolVersion"], PROTOCOL_VERSIONS[0]);
+        let tool = &server.dispatch(&list).unwrap()["result"]["tools"][0];
+        assert!(tool.get("outputSchema").is_some());
+    }
+
+    #[test]
+    fn tools_take_only_the_arguments_they_advertise() {
+        let server = McpServer::new();
+        let list = json!({ "jsonrpc": "2.0", "id": 1, "method": "tools/list" });
+        let response = server.dispatch(&list).unwrap();
+        for tool in response["result"]["tools"].as_array().unwrap() {
+            assert_eq!(tool["inputSchema"]["additionalProperties"], false);
+            assert_eq!(tool["outputSchema"]["type"], "object");
+        }
+
+        // `returns` misspelled
+        let call = json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "tools/call",
+            "params": {
+                "name": "generate-server-fn",
+                "arguments": { "name": "count_todos", "return_type": "u32" }
+            }
+        });
+        let result = &server.dispatch(&call).unwrap()["result"];
+        assert_eq!(result["isError"], true);
+        let text = result["content"][0]["text"].as_str().unwrap();
+        assert!(text.contains("unknown field `return_type`"), "{}", text);
+    }
+
+    /// Connect to a one-connection ws listener with `authorization`, and
+    /// return what the server made of the handshake
+    async fn ws_handshake(
+        auth: AuthConfig,
+        authorization: Option<&str>,
+    ) -> Result<Option<Vec<String>>, String> {
+        use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            accept_websocket(stream, &[], &auth)
+                .await
+                .map(|connection| connection.tools)
+        });
+        let mut request = format!("ws://{}", addr).into_client_request().unwrap();
+        if let Some(value) = authorization {
+            request
+                .headers_mut()
+                .insert("authorization", value.parse().unwrap());
+        }
+        let stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        let client = tokio_tungstenite::client_async(request, stream).await;
+        let accepted = server.await.unwrap();
+        assert_eq!(client.is_ok(), accepted.is_ok());
+        accepted
+    }
+
+    #[tokio::test]
+    async fn ws_bearer_tokens() {
+        let auth: AuthConfig = toml::from_str(
+            r#"tokens = [{ token = "ci", tools = ["leptos-autofixer"] }, { token = "admin" }]"#,
+        )
+        .unwrap();
+        assert_eq!(ws_handshake(AuthConfig::default(), None).await, Ok(None));
+        assert!(ws_handshake(auth.clone(), None).await.is_err());
+        assert!(ws_handshake(auth.clone(), Some("Bearer nope"))
+            .await
+            .is_err());
+        assert!(ws_handshake(auth.clone(), Some("Basic ci")).await.is_err());
+        assert_eq!(
+            ws_handshake(auth.clone(), Some("Bearer ci")).await,
+            Ok(Some(vec!["leptos-autofixer".to_string()]))
+        );
+        assert_eq!(ws_handshake(auth, Some("bearer admin")).await, Ok(None));
+    }
+
+    /// Every message `input` holds, read with `framing` and a `max`-byte limit,
+    /// and whether the sink ended up framing with `Content-Length`
+    async fn read_all(input: &[u8], framing: Framing, max: usize) -> (Vec<Incoming>, bool) {
+        let sink = Sink::new(Target::Discard);
+        let mut reader = MessageReader::new(input, framing, max, sink.clone());
+        let mut messages = Vec::new();
+        while let Some(message) = reader.next().await.unwrap() {
+            messages.push(message);
+        }
+        (messages, sink.0.content_length.load(Ordering::Relaxed))
+    }
+
+    fn text(text: &str) -> Incoming {
+        Incoming::Text(text.to_string())
+    }
+
+    #[tokio::test]
+    async fn reads_ndjson() {
+        let input = b"{\"a\":1}\n{\"b\":2}\r\n\n{\"c\":3}";
+        let (messages, content_length) = read_all(input, Framing::Ndjson, 100).await;
+        assert_eq!(
+            messages,
+            // JSON parsing ignores the `\r`
+            [
+                text("{\"a\":1}"),
+                text("{\"b\":2}\r"),
+                text(""),
+                text("{\"c\":3}")
+            ]
+        );
+        assert!(!content_length);
+    }
+
+    #[tokio::test]
+    async fn reads_content_length() {
+        let input = b"Content-Length: 7\r\nContent-Type: application/json\r\n\r\n{\"a\":1}content-length:2\r\n\r\n{}";
+        let (messages, content_length) = read_all(input, Framing::ContentLength, 100).await;
+        assert_eq!(messages, [text("{\"a\":1}"), text("{}")]);
+        assert!(content_length);
+    }
+
+    #[tokio::test]
+    async fn detects_framing() {
+        let input = b"\n{\"a\":1}\nContent-Length: 2\n";
+        let (messages, content_length) = read_all(input, Framing::Auto, 100).await;
+        assert_eq!(messages[0], text("{\"a\":1}"));
+        // Settled on NDJSON, so later lines are messages too
+        assert_eq!(messages[1], text("Content-Length: 2"));
+        assert!(!content_length);
+
+        let input = b"Content-Length: 2\r\n\r\n{}";
+        let (messages, content_length) = read_all(input, Framing::Auto, 100).await;
+        assert_eq!(messages, [text("{}")]);
+        assert!(content_length);
+    }
+
+    #[tokio::test]
+    async fn skips_oversized_messages() {
+        let input = b"0123456789abcdef\n{}\n";
+        let (messages, _) = read_all(input, Framing::Ndjson, 10).await;
+        assert_eq!(messages, [Incoming::TooLong(16), text("{}")]);
+
+        let input = b"Content-Length: 32\r\n\r\n0123456789abcdef0123456789abcdefContent-Length: 2\r\n\r\n{}";
+        let (messages, _) = read_all(input, Framing::ContentLength, 20).await;
+        assert_eq!(messages, [Incoming::TooLong(32), text("{}")]);
+    }
+
+    #[tokio::test]
+    async fn keeps_partial_messages_across_cancelled_reads() {
+        let (mut client, server) = tokio::io::duplex(64);
+        let mut reader = MessageReader::new(
+            server,
+            Framing::ContentLength,
+            100,
+            Sink::new(Target::Discard),
+        );
+        use tokio::io::AsyncWriteExt;
+        client
+            .write_all(b"Content-Length: 7\r\n\r\n{\"a\"")
+            .await
+            .unwrap();
+        let pending = tokio::time::timeout(Duration::from_millis(20), reader.next()).await;
+        assert!(pending.is_err());
+        client.write_all(b":1}").await.unwrap();
+        drop(client);
+        assert_eq!(reader.next().await.unwrap(), Some(text("{\"a\":1}")));
+        assert_eq!(reader.next().await.unwrap(), None);
+    }
+
+    /// Everything a session sends while serving `input` to its end
+    async fn serve_all(input: &str) -> Vec<Value> {
+        let (sender, mut sent) = mpsc::unbounded_channel();
+        let server = McpServer::new().session(Sink::new(Target::Channel(sender)), Framing::Ndjson);
+        Arc::new(server)
+            .serve(input.as_bytes(), std::future::pending())
+            .await;
+        let mut messages = Vec::new();
+        while let Ok(bytes) = sent.try_recv() {
+            messages.push(serde_json::from_slice(&bytes).unwrap());
+        }
+        messages
+    }
+
+    #[tokio::test]
+    async fn batch_responses_keep_batch_order() {
+        let batch = json!([
+            { "jsonrpc": "2.0", "id": "first", "method": "tools/list" },
+            { "jsonrpc": "2.0", "method": "notifications/initialized" },
+            { "jsonrpc": "2.0", "id": 2, "method": "ping" },
+            { "jsonrpc": "2.0", "id": 3, "method": "no/such/method" },
+            42
+        ]);
+        let sent = serve_all(&format!("{}\n", batch)).await;
+        assert_eq!(sent.len(), 1);
+        let responses = sent[0].as_array().unwrap();
+        let ids: Vec<&Value> = responses.iter().map(|r| &r["id"]).collect();
+        assert_eq!(ids, [&json!("first"), &json!(2), &json!(3), &Value::Null]);
+        assert!(responses[0]["result"]["tools"].is_array());
+        assert_eq!(responses[1]["result"], json!({}));
+        assert_eq!(responses[2]["error"]["code"], METHOD_NOT_FOUND);
+        assert_eq!(responses[3]["error"]["code"], INVALID_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn batches_without_requests() {
+        let notifications = json!([{ "jsonrpc": "2.0", "method": "notifications/initialized" }]);
+        assert!(serve_all(&format!("{}\n", notifications)).await.is_empty());
+
+        let sent = serve_all("[]\n").await;
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0]["id"], Value::Null);
+        assert_eq!(sent[0]["error"]["code"], INVALID_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn unparsable_lines_get_parse_errors() {
+        let sent = serve_all("{oops\n\"text\"\n").await;
+        let codes: Vec<&Value> = sent.iter().map(|m| &m["error"]["code"]).collect();
+        assert_eq!(codes, [&json!(PARSE_ERROR), &json!(INVALID_REQUEST)]);
+        assert!(sent.iter().all(|m| m["id"].is_null()));
+    }
+
+    #[test]
+    fn dispatch_answers_batches() {
+        let server = McpServer::new();
+        let batch = json!([
+            { "jsonrpc": "2.0", "id": 1, "method": "ping" },
+            { "jsonrpc": "2.0", "method": "notifications/initialized" },
+            "junk"
+        ]);
+        let responses = server.dispatch(&batch).unwrap();
+        assert_eq!(responses[0]["id"], 1);
+        assert_eq!(responses[1]["error"]["code"], INVALID_REQUEST);
+        assert_eq!(responses.as_array().unwrap().len(), 2);
+        let notifications = json!([{ "jsonrpc": "2.0", "method": "notifications/initialized" }]);
+        assert_eq!(server.dispatch(&notifications), None);
+        assert_eq!(
+            server.dispatch(&json!([])).unwrap()["error"]["code"],
+            INVALID_REQUEST
+        );
+    }
+
+    #[test]
+    fn finish_guard_runs_on_panic() {
+        let requests = Registry::default();
+        let id = json!("a");
+        requests.register(&id);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let _finish = Finish {
+                requests: &requests,
+                id: &id,
+            };
+            panic!("boom");
+        }));
+        assert!(result.is_err());
+        assert!(!requests.cancel(&id));
+    }
 }
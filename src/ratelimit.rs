@@ -0,0 +1,52 @@
+//! Tool call rate limiting
+//!
+//! Each session gets its own [`TokenBucket`], so one client looping on the
+//! same call runs out of tokens without slowing down the others. Buckets
+//! start full, hold `burst` tokens and refill at `calls_per_minute`.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Calls a session may make, refilled continuously
+#[derive(Debug)]
+pub struct TokenBucket {
+    /// Most tokens the bucket holds
+    capacity: f64,
+    /// Tokens added per second
+    refill: f64,
+    /// Tokens left and when they were last counted
+    state: Mutex<(f64, Instant)>,
+}
+
+impl TokenBucket {
+    /// A full bucket of `burst` tokens refilled at `per_minute`; `None` if
+    /// `per_minute` is 0, which means unlimited. A `burst` of 0 allows a
+    /// minute's worth of calls at once.
+    pub fn new(per_minute: u32, burst: u32) -> Option<Self> {
+        if per_minute == 0 {
+            return None;
+        }
+        let capacity = if burst == 0 { per_minute } else { burst } as f64;
+        Some(Self {
+            capacity,
+            refill: per_minute as f64 / 60.0,
+            state: Mutex::new((capacity, Instant::now())),
+        })
+    }
+
+    /// Take a token, or say how long until one is available
+    pub fn take(&self) -> Result<(), Duration> {
+        let mut state = self.state.lock().unwrap();
+        let (tokens, last) = *state;
+        let now = Instant::now();
+        let tokens =
+            (tokens + now.duration_since(last).as_secs_f64() * self.refill).min(self.capacity);
+        if tokens >= 1.0 {
+            *state = (tokens - 1.0, now);
+            Ok(())
+        } else {
+            *state = (tokens, now);
+            Err(Duration::from_secs_f64((1.0 - tokens) / self.refill))
+        }
+    }
+}
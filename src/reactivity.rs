@@ -0,0 +1,559 @@
+//! Reactive dependency graphs
+//!
+//! `analyze-reactivity` recovers, per component, which signals, memos,
+//! derived signals and resources each memo, resource, effect and the view
+//! reads, and which signals they write. The graph is rendered as Mermaid or
+//! DOT and checked for signals nothing reads and for effects that write a
+//! signal they depend on, which re-run themselves forever.
+
+use crate::rules::position;
+use crate::source::{
+    call_ranges, functions, identifier_uses, let_bindings, mask_literals, statement_end, within,
+};
+use crate::view::{parse_views, skip_balanced, Node, ViewMacro};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashSet, VecDeque};
+use std::ops::Range;
+
+/// Constructors returning a `(getter, setter)` pair
+const PAIR_CONSTRUCTORS: &[&str] = &["signal(", "signal_local(", "arc_signal(", "create_signal("];
+
+/// Constructors returning one readable and writable signal
+const RW_CONSTRUCTORS: &[&str] = &["RwSignal::new", "ArcRwSignal::new", "create_rw_signal("];
+
+const MEMO_CONSTRUCTORS: &[&str] = &["Memo::new", "ArcMemo::new", "create_memo("];
+
+const DERIVED_CONSTRUCTORS: &[&str] = &["Signal::derive", "ArcSignal::derive"];
+
+const RESOURCE_CONSTRUCTORS: &[&str] = &[
+    "Resource::new",
+    "LocalResource::new",
+    "OnceResource::new",
+    "ArcResource::new",
+    "create_resource(",
+    "create_local_resource(",
+    "create_blocking_resource(",
+];
+
+const EFFECT_CALLEES: &[&str] = &[
+    "Effect::new",
+    "Effect::new_isomorphic",
+    "Effect::watch",
+    "RenderEffect::new",
+    "create_effect",
+    "create_render_effect",
+    "create_isomorphic_effect",
+];
+
+/// Methods that change a signal's value and notify its subscribers
+const WRITE_METHODS: &[&str] = &[
+    "set",
+    "update",
+    "write",
+    "try_set",
+    "try_update",
+    "maybe_update",
+];
+
+/// Methods that access a signal without subscribing to or notifying anyone
+const UNTRACKED_METHODS: &[&str] = &[
+    "get_untracked",
+    "with_untracked",
+    "read_untracked",
+    "set_untracked",
+    "update_untracked",
+    "write_untracked",
+    "dispose",
+];
+
+/// How the graph is rendered in the text output
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum GraphFormat {
+    #[default]
+    Mermaid,
+    Dot,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum NodeKind {
+    Signal,
+    Memo,
+    Derived,
+    Resource,
+    Effect,
+    View,
+}
+
+impl NodeKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Signal => "signal",
+            Self::Memo => "memo",
+            Self::Derived => "derived",
+            Self::Resource => "resource",
+            Self::Effect => "effect",
+            Self::View => "view",
+        }
+    }
+
+    /// Whether other nodes can depend on this one
+    fn is_source(&self) -> bool {
+        !matches!(self, Self::Effect | Self::View)
+    }
+}
+
+/// A signal, computation or effect
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ReactiveNode {
+    /// Binding name; the getter for `(getter, setter)` pairs, `effect_N` for effects
+    pub name: String,
+    pub kind: NodeKind,
+    pub line: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub setter: Option<String>,
+    /// The whole `let` statement or effect call
+    #[serde(skip)]
+    pub(crate) definition: Range<usize>,
+    /// Code that runs reactively: the initializer or effect arguments
+    #[serde(skip)]
+    pub(crate) body: Range<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum EdgeKind {
+    /// `to` re-runs when `from` changes
+    Reads,
+    /// `from` sets `to`
+    Writes,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct Edge {
+    pub from: String,
+    pub to: String,
+    pub kind: EdgeKind,
+}
+
+/// An effect that writes a signal it depends on
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct Loop {
+    pub effect: String,
+    pub signal: String,
+    /// Dependency chain from the signal back to the effect
+    pub path: Vec<String>,
+}
+
+/// Reactive graph of one component
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct Graph {
+    pub component: String,
+    pub nodes: Vec<ReactiveNode>,
+    pub edges: Vec<Edge>,
+    /// Signals, memos and resources nothing reads
+    pub unused: Vec<String>,
+    pub loops: Vec<Loop>,
+}
+
+/// Graphs of the components in `code`, or of the whole snippet when it has none
+pub fn analyze(code: &str) -> Vec<Graph> {
+    let masked = mask_literals(code);
+    let components: Vec<(String, Range<usize>)> = functions(code)
+        .into_iter()
+        .filter(|f| f.is_component())
+        .map(|f| (f.name, f.body))
+        .collect();
+    if components.is_empty() {
+        return vec![graph(code, &masked, "snippet", 0..code.len())];
+    }
+    components
+        .into_iter()
+        .map(|(name, body)| graph(code, &masked, &name, body))
+        .collect()
+}
+
+fn graph(code: &str, masked: &str, component: &str, body: Range<usize>) -> Graph {
+    let mut nodes = sources(code, masked, body.clone());
+    let effects = call_ranges(masked, EFFECT_CALLEES)
+        .into_iter()
+        .filter(|args| body.contains(&args.start));
+    for (i, args) in effects.enumerate() {
+        nodes.push(ReactiveNode {
+            name: format!("effect_{}", i + 1),
+            kind: NodeKind::Effect,
+            line: position(code, args.start).0,
+            setter: None,
+            definition: args.clone(),
+            body: args,
+        });
+    }
+    let views: Vec<ViewMacro> = parse_views(code)
+        .into_iter()
+        .filter(|v| body.contains(&v.start))
+        .collect();
+    let view_bodies: Vec<Range<usize>> = views.iter().map(|v| v.body_start..v.body_end).collect();
+    if let (Some(first), Some(last)) = (view_bodies.first(), view_bodies.last()) {
+        nodes.push(ReactiveNode {
+            name: "view".to_string(),
+            kind: NodeKind::View,
+            line: position(code, first.start).0,
+            setter: None,
+            definition: first.start..last.end,
+            body: first.start..last.end,
+        });
+    }
+
+    // Reads in event handlers and `untrack` don't subscribe
+    let mut untracked = call_ranges(masked, &["untrack"]);
+    untracked.extend(views.iter().flat_map(|v| handlers(code, v)));
+    let mut edges = Vec::new();
+    // Signal initializers run once, untracked; everything else can subscribe
+    for node in nodes.iter().filter(|n| n.kind != NodeKind::Signal) {
+        let ranges = if node.kind == NodeKind::View {
+            view_bodies.clone()
+        } else {
+            vec![node.body.clone()]
+        };
+        for source in nodes
+            .iter()
+            .filter(|n| n.kind.is_source() && n.name != node.name)
+        {
+            let mut reads = false;
+            let mut writes = false;
+            let found = ranges
+                .iter()
+                .flat_map(|range| accesses(masked, range.clone(), source));
+            for (offset, access) in found {
+                match access {
+                    Access::Read if !within(&untracked, offset) => reads = true,
+                    Access::Write => writes = true,
+                    _ => {}
+                }
+            }
+            if reads {
+                edges.push(Edge {
+                    from: source.name.clone(),
+                    to: node.name.clone(),
+                    kind: EdgeKind::Reads,
+                });
+            }
+            if writes && source.kind == NodeKind::Signal {
+                edges.push(Edge {
+                    from: node.name.clone(),
+                    to: source.name.clone(),
+                    kind: EdgeKind::Writes,
+                });
+            }
+        }
+    }
+
+    let unused = nodes
+        .iter()
+        .filter(|n| n.kind.is_source() && !n.name.starts_with('_'))
+        .filter(|n| {
+            accesses(masked, body.clone(), n)
+                .into_iter()
+                .filter(|(offset, _)| !n.definition.contains(offset))
+                .all(|(_, access)| access == Access::Write)
+        })
+        .map(|n| n.name.clone())
+        .collect();
+    let loops = loops(&nodes, &edges);
+    Graph {
+        component: component.to_string(),
+        nodes,
+        edges,
+        unused,
+        loops,
+    }
+}
+
+/// Value ranges of `on:` attributes and other closure-with-argument attributes
+pub(crate) fn handlers(code: &str, view: &ViewMacro) -> Vec<Range<usize>> {
+    view.nodes
+        .iter()
+        .filter_map(|node| match node {
+            Node::Open(element) => Some(&element.attrs),
+            _ => None,
+        })
+        .flatten()
+        .filter(|attr| {
+            let callback = attr.expr().is_some_and(|e| {
+                let e = e.trim_start_matches("move").trim_start();
+                e.starts_with('|') && !e.starts_with("||")
+            });
+            attr.name.starts_with("on:") || callback
+        })
+        .filter_map(|attr| {
+            let value = attr.value.as_deref()?;
+            let start = attr.offset + code[attr.offset..].find(value)?;
+            Some(start..start + value.len())
+        })
+        .collect()
+}
+
+/// Signals, memos, derived signals and resources bound with `let` in `body`
+pub(crate) fn sources(code: &str, masked: &str, body: Range<usize>) -> Vec<ReactiveNode> {
+    let mut nodes = Vec::new();
+    for (getter, setter, statement, init) in pairs(masked, body.clone()) {
+        nodes.push(ReactiveNode {
+            name: getter,
+            kind: NodeKind::Signal,
+            line: position(code, statement.start).0,
+            setter: Some(setter),
+            definition: statement,
+            body: init,
+        });
+    }
+    for binding in let_bindings(masked, body) {
+        let init = masked[binding.init.clone()].trim_start();
+        let starts = |prefixes: &[&str]| prefixes.iter().any(|p| init.starts_with(p));
+        let kind = if starts(RW_CONSTRUCTORS) {
+            NodeKind::Signal
+        } else if starts(MEMO_CONSTRUCTORS) {
+            NodeKind::Memo
+        } else if starts(DERIVED_CONSTRUCTORS) || init.starts_with("move ||") {
+            NodeKind::Derived
+        } else if starts(RESOURCE_CONSTRUCTORS) {
+            NodeKind::Resource
+        } else {
+            continue;
+        };
+        let start = masked[..binding.init.start]
+            .rfind("let")
+            .unwrap_or(binding.init.start);
+        nodes.push(ReactiveNode {
+            name: binding.name,
+            kind,
+            line: position(code, start).0,
+            setter: None,
+            definition: start..binding.init.end,
+            body: binding.init,
+        });
+    }
+    nodes.sort_by_key(|n| n.definition.start);
+    nodes
+}
+
+/// `let (getter, setter) = signal(...);` statements in `range` of masked code
+fn pairs(masked: &str, range: Range<usize>) -> Vec<(String, String, Range<usize>, Range<usize>)> {
+    let bytes = masked.as_bytes();
+    let mut found = Vec::new();
+    for (rel, _) in masked[range.clone()].match_indices("let ") {
+        let offset = range.start + rel;
+        let Some(open) = masked[offset + 4..]
+            .find(|c: char| !c.is_whitespace())
+            .map(|p| offset + 4 + p)
+            .filter(|p| bytes[*p] == b'(')
+        else {
+            continue;
+        };
+        let Some(close) = skip_balanced(bytes, open, b'(', b')') else {
+            continue;
+        };
+        let names: Vec<&str> = masked[open + 1..close - 1]
+            .split(',')
+            .map(|n| n.trim().trim_start_matches("mut ").trim())
+            .collect();
+        let [getter, setter] = names[..] else {
+            continue;
+        };
+        let is_name = |n: &str| !n.is_empty() && n.chars().all(|c| c.is_alphanumeric() || c == '_');
+        if !is_name(getter) || !is_name(setter) {
+            continue;
+        }
+        let Some(eq) = masked[close..range.end].find('=').map(|p| close + p + 1) else {
+            continue;
+        };
+        let init = masked[eq..range.end].trim_start();
+        if !PAIR_CONSTRUCTORS.iter().any(|c| init.starts_with(c)) {
+            continue;
+        }
+        let Some(semi) = statement_end(masked, eq, range.end) else {
+            continue;
+        };
+        found.push((
+            getter.to_string(),
+            setter.to_string(),
+            offset..semi,
+            eq..semi,
+        ));
+    }
+    found
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Access {
+    Read,
+    Write,
+    Untracked,
+}
+
+/// Offsets where the getter or setter of `node` is used in `range` of masked
+/// code, with how each use accesses it
+pub(crate) fn accesses(
+    masked: &str,
+    range: Range<usize>,
+    node: &ReactiveNode,
+) -> Vec<(usize, Access)> {
+    let mut found: Vec<(usize, Access)> = std::iter::once(node.name.as_str())
+        .chain(node.setter.as_deref())
+        .flat_map(|name| {
+            identifier_uses(masked, range.clone(), name)
+                .into_iter()
+                .map(move |offset| (offset, access(masked, offset, name, node)))
+        })
+        .collect();
+    found.sort_by_key(|(offset, _)| *offset);
+    found
+}
+
+/// How the use of `name` at `offset` accesses `node`
+fn access(masked: &str, offset: usize, name: &str, node: &ReactiveNode) -> Access {
+    if node.setter.as_deref() == Some(name) {
+        return Access::Write;
+    }
+    let Some(method) = masked[offset + name.len()..].trim_start().strip_prefix('.') else {
+        return Access::Read;
+    };
+    let method: String = method
+        .trim_start()
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || *c == '_')
+        .collect();
+    if UNTRACKED_METHODS.contains(&method.as_str()) {
+        Access::Untracked
+    } else if WRITE_METHODS.contains(&method.as_str()) {
+        Access::Write
+    } else {
+        Access::Read
+    }
+}
+
+/// Effects that write a signal from which a chain of reads leads back to them
+fn loops(nodes: &[ReactiveNode], edges: &[Edge]) -> Vec<Loop> {
+    let mut found = Vec::new();
+    for effect in nodes.iter().filter(|n| n.kind == NodeKind::Effect) {
+        let written = edges
+            .iter()
+            .filter(|e| e.kind == EdgeKind::Writes && e.from == effect.name);
+        for write in written {
+            if let Some(path) = read_path(edges, &write.to, &effect.name) {
+                found.push(Loop {
+                    effect: effect.name.clone(),
+                    signal: write.to.clone(),
+                    path,
+                });
+            }
+        }
+    }
+    found
+}
+
+/// Shortest chain of `Reads` edges from `from` to `to`
+fn read_path(edges: &[Edge], from: &str, to: &str) -> Option<Vec<String>> {
+    let mut queue = VecDeque::from([vec![from.to_string()]]);
+    let mut seen = HashSet::from([from.to_string()]);
+    while let Some(path) = queue.pop_front() {
+        let last = path.last()?;
+        for edge in edges
+            .iter()
+            .filter(|e| e.kind == EdgeKind::Reads && &e.from == last)
+        {
+            let mut next = path.clone();
+            next.push(edge.to.clone());
+            if edge.to == to {
+                return Some(next);
+            }
+            if seen.insert(edge.to.clone()) {
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+impl Graph {
+    fn flagged(&self, node: &ReactiveNode) -> Option<&'static str> {
+        if self.loops.iter().any(|l| l.effect == node.name) {
+            Some("loop")
+        } else if self.unused.contains(&node.name) {
+            Some("unused")
+        } else {
+            None
+        }
+    }
+
+    /// Mermaid flowchart; reads are solid arrows, writes dotted
+    pub fn mermaid(&self) -> String {
+        let mut out = String::from("flowchart LR\n");
+        for node in &self.nodes {
+            let (open, close) = match node.kind {
+                NodeKind::Signal => ("([", "])"),
+                NodeKind::Memo => ("[[", "]]"),
+                NodeKind::Derived => ("(", ")"),
+                NodeKind::Resource => ("[(", ")]"),
+                NodeKind::Effect => ("{{", "}}"),
+                NodeKind::View => ("[", "]"),
+            };
+            out.push_str(&format!(
+                "    n_{}{}\"{}\"{}\n",
+                node.name, open, node.name, close
+            ));
+        }
+        for edge in &self.edges {
+            let arrow = match edge.kind {
+                EdgeKind::Reads => "-->",
+                EdgeKind::Writes => "-. writes .->",
+            };
+            out.push_str(&format!("    n_{} {} n_{}\n", edge.from, arrow, edge.to));
+        }
+        out.push_str("    classDef unused stroke-dasharray: 4 4,color:#888\n");
+        out.push_str("    classDef loop stroke:#d00,stroke-width:2px\n");
+        for node in &self.nodes {
+            if let Some(class) = self.flagged(node) {
+                out.push_str(&format!("    class n_{} {}\n", node.name, class));
+            }
+        }
+        out
+    }
+
+    /// Graphviz digraph; reads are solid arrows, writes dashed
+    pub fn dot(&self) -> String {
+        let mut out = format!("digraph \"{}\" {{\n    rankdir=LR;\n", self.component);
+        for node in &self.nodes {
+            let shape = match node.kind {
+                NodeKind::Signal => "ellipse",
+                NodeKind::Memo => "box",
+                NodeKind::Derived => "box, style=rounded",
+                NodeKind::Resource => "cylinder",
+                NodeKind::Effect => "hexagon",
+                NodeKind::View => "note",
+            };
+            let flag = match self.flagged(node) {
+                Some("loop") => ", color=red, penwidth=2",
+                Some(_) => ", style=dashed, fontcolor=gray",
+                None => "",
+            };
+            out.push_str(&format!(
+                "    \"{}\" [shape={}{}];\n",
+                node.name, shape, flag
+            ));
+        }
+        for edge in &self.edges {
+            let style = match edge.kind {
+                EdgeKind::Reads => "",
+                EdgeKind::Writes => " [style=dashed, label=\"writes\"]",
+            };
+            out.push_str(&format!(
+                "    \"{}\" -> \"{}\"{};\n",
+                edge.from, edge.to, style
+            ));
+        }
+        out.push_str("}\n");
+        out
+    }
+}
@@ -0,0 +1,139 @@
+//! Reading lists for a task
+//!
+//! `recommend-sections` turns a description of what the agent is building
+//! into a short ranked list of sections, each with the subsection to start at
+//! and a one-line reason. Sections score on the use cases their metadata
+//! lists that the task mentions, plus their keyword and semantic search hits,
+//! so the list is a cheaper first hop than reading search results.
+
+use crate::docs::{self, DocSection, LeptosVersion, SearchHit};
+use crate::semantic;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Default number of sections recommended
+pub const DEFAULT_LIMIT: usize = 5;
+
+/// Search hits considered per search
+const HITS: usize = 20;
+
+/// Word similarity at which a use-case word counts as mentioned
+const WORD_MATCH: f64 = 0.8;
+
+/// A section to read, and why
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct Recommendation {
+    pub title: &'static str,
+    pub path: &'static str,
+    /// Subsection to start at, usable as get-documentation's `section`
+    pub address: String,
+    pub reason: String,
+    pub score: f64,
+    /// Use cases of the section the task mentions
+    pub use_cases: Vec<&'static str>,
+}
+
+/// Sections of `version` to read for `task`, most relevant first
+pub fn recommend(task: &str, version: LeptosVersion, limit: usize) -> Vec<Recommendation> {
+    let lower = task.to_lowercase();
+    let words: Vec<&str> = lower
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|w| w.len() > 1)
+        .collect();
+    if words.is_empty() {
+        return Vec::new();
+    }
+
+    // Search hits, normalized per search so neither dominates
+    let keyword = docs::search(task, version, HITS);
+    // A failing embeddings endpoint only costs the semantic signal
+    let semantic = semantic::search(task, version, HITS).unwrap_or_else(|e| {
+        tracing::debug!("Semantic search unavailable for recommendations: {}", e);
+        Vec::new()
+    });
+    let mut by_section: HashMap<&str, (f64, Option<&SearchHit>)> = HashMap::new();
+    for hits in [&keyword, &semantic] {
+        let top = hits.first().map_or(1.0, |hit| hit.score);
+        for hit in hits {
+            let entry = by_section.entry(hit.section_path.as_str()).or_default();
+            let score = hit.score / top;
+            if entry.1.is_none_or(|best| score > best.score / top) {
+                entry.1 = Some(hit);
+            }
+            entry.0 += score;
+        }
+    }
+
+    let mut recommendations: Vec<Recommendation> = docs::list_sections(version)
+        .iter()
+        .filter_map(|section| {
+            let use_cases = mentioned_use_cases(section, &words);
+            let (search_score, best) = by_section.get(section.path).copied().unwrap_or_default();
+            let score = use_cases.len() as f64 + search_score;
+            (score > 0.0).then(|| Recommendation {
+                title: section.title,
+                path: section.path,
+                address: best.map_or_else(|| section.path.to_string(), |hit| hit.address()),
+                reason: reason(&use_cases, best, &words),
+                score,
+                use_cases,
+            })
+        })
+        .collect();
+    recommendations.sort_by(|a, b| b.score.total_cmp(&a.score));
+    recommendations.truncate(limit);
+    recommendations
+}
+
+/// Use cases of `section` all of whose words the task mentions
+fn mentioned_use_cases(section: &DocSection, words: &[&str]) -> Vec<&'static str> {
+    section
+        .use_cases
+        .split(',')
+        .map(str::trim)
+        // Marks sections every app needs, not a use case
+        .filter(|use_case| *use_case != "always")
+        .filter(|use_case| {
+            let lower = use_case.to_lowercase();
+            let mut parts = lower
+                .split(|c: char| !c.is_alphanumeric() && c != '_')
+                .filter(|p| p.len() > 1)
+                .peekable();
+            parts.peek().is_some()
+                && parts.all(|part| {
+                    words
+                        .iter()
+                        .any(|w| docs::similarity(w, part) >= WORD_MATCH)
+                })
+        })
+        .collect()
+}
+
+/// One line: the use cases matched, or the task words the best subsection covers
+fn reason(use_cases: &[&str], best: Option<&SearchHit>, words: &[&str]) -> String {
+    let start = best
+        .filter(|hit| !hit.heading.is_empty())
+        .map(|hit| format!("; start at \"{}\"", hit.heading))
+        .unwrap_or_default();
+    if !use_cases.is_empty() {
+        return format!("Covers {}{}", use_cases.join(", "), start);
+    }
+    match best {
+        Some(hit) => {
+            let text = format!("{} {}", hit.heading, hit.excerpt).to_lowercase();
+            let covered: Vec<&str> = words
+                .iter()
+                .copied()
+                .filter(|w| w.len() > 3 && text.contains(w))
+                .take(3)
+                .collect();
+            if covered.is_empty() {
+                format!("Related to the task{}", start)
+            } else {
+                format!("Discusses {}{}", covered.join(", "), start)
+            }
+        }
+        None => "Related to the task".to_string(),
+    }
+}
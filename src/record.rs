@@ -0,0 +1,173 @@
+//! Session recording and replay
+//!
+//! With `--record <FILE>` every message a session receives or sends is
+//! appended to a JSONL file, with a timestamp and the number of the session
+//! it belongs to. [`replay`] feeds the recorded requests of each session back
+//! through a fresh session and pairs every response with the recorded one, so
+//! an odd answer an agent got can be reproduced and compared after a fix.
+
+use crate::protocol::McpServer;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+static RECORDING: OnceLock<Mutex<File>> = OnceLock::new();
+
+/// Which way a recorded message went
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Direction {
+    /// From the client
+    In,
+    /// To the client
+    Out,
+}
+
+/// One line of a recording
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Entry {
+    /// Milliseconds since the Unix epoch
+    pub ts_ms: u64,
+    pub session: u64,
+    pub direction: Direction,
+    pub message: Value,
+}
+
+/// Append every message of every session to `path` from now on
+pub fn start(path: &Path) -> Result<(), String> {
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    RECORDING
+        .set(Mutex::new(file))
+        .map_err(|_| "Already recording".to_string())
+}
+
+/// Record a message already serialized as JSON, if recording
+pub(crate) fn message(session: u64, direction: Direction, json: &str) {
+    let Some(file) = RECORDING.get() else {
+        return;
+    };
+    let ts_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as u64);
+    let direction = match direction {
+        Direction::In => "in",
+        Direction::Out => "out",
+    };
+    let line = format!(
+        "{{\"ts_ms\":{},\"session\":{},\"direction\":\"{}\",\"message\":{}}}\n",
+        ts_ms, session, direction, json
+    );
+    // Not a tracing event: forwarded to the client, it would be recorded again
+    if let Err(e) = file.lock().unwrap().write_all(line.as_bytes()) {
+        eprintln!("Failed to record a message: {}", e);
+    }
+}
+
+/// A recorded request and its response then and now
+#[derive(Debug, Clone)]
+pub struct Replayed {
+    pub session: u64,
+    pub method: String,
+    /// Tool name of a `tools/call`
+    pub tool: Option<String>,
+    /// The response the client got; `None` if it cancelled the request or
+    /// the recording ended first
+    pub recorded: Option<Value>,
+    pub replayed: Value,
+}
+
+impl Replayed {
+    /// Whether the response is unchanged; `None` without a recorded one
+    pub fn matches(&self) -> Option<bool> {
+        self.recorded.as_ref().map(|r| *r == self.replayed)
+    }
+}
+
+/// Replay every recorded session in `path`, or only `session`, against `server`
+///
+/// Each session's messages go through a fresh session of `server` in their
+/// recorded order, one at a time; messages the server sends besides
+/// responses (logs, progress) are dropped. The requests of a batch are
+/// replayed and paired one by one.
+pub fn replay(
+    path: &Path,
+    server: &McpServer,
+    session: Option<u64>,
+) -> Result<Vec<Replayed>, String> {
+    let raw = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let mut entries = Vec::new();
+    for (number, line) in raw.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry: Entry = serde_json::from_str(line)
+            .map_err(|e| format!("{}:{}: invalid entry: {}", path.display(), number + 1, e))?;
+        if session.is_none_or(|s| s == entry.session) {
+            entries.push(entry);
+        }
+    }
+    let mut sessions: Vec<u64> = entries.iter().map(|e| e.session).collect();
+    sessions.sort_unstable();
+    sessions.dedup();
+    if sessions.is_empty() {
+        return Err(match session {
+            Some(session) => format!("No session {} in {}", session, path.display()),
+            None => format!("No messages in {}", path.display()),
+        });
+    }
+
+    let mut replayed = Vec::new();
+    for number in sessions {
+        let messages: Vec<&Entry> = entries.iter().filter(|e| e.session == number).collect();
+        let live = server.detached_session();
+        for (position, entry) in messages.iter().enumerate() {
+            if entry.direction != Direction::In {
+                continue;
+            }
+            for request in batched(&entry.message) {
+                let Some(response) = live.dispatch(request) else {
+                    continue;
+                };
+                // Through text like the recorded one, whose floats parse a bit off
+                let response = serde_json::from_str(&response.to_string()).unwrap_or(response);
+                // The first response with its id after it, in case a client reuses ids
+                let id = &request["id"];
+                let recorded = messages[position..]
+                    .iter()
+                    .filter(|e| e.direction == Direction::Out)
+                    .flat_map(|e| batched(&e.message))
+                    .find(|m| m["id"] == *id && m.get("method").is_none())
+                    .cloned();
+                replayed.push(Replayed {
+                    session: number,
+                    method: request["method"].as_str().unwrap_or_default().to_string(),
+                    tool: request
+                        .pointer("/params/name")
+                        .and_then(|v| v.as_str())
+                        .filter(|_| request["method"] == "tools/call")
+                        .map(String::from),
+                    recorded,
+                    replayed: response,
+                });
+            }
+        }
+    }
+    Ok(replayed)
+}
+
+/// The messages of a batch, or the message itself
+fn batched(message: &Value) -> Vec<&Value> {
+    match message {
+        Value::Array(batch) => batch.iter().collect(),
+        message => vec![message],
+    }
+}
@@ -0,0 +1,1681 @@
+//! MCP tool registry
+//!
+//! Every tool is a type implementing [`McpTool`]: its name, its description,
+//! the type its arguments deserialize into, the type of its structured
+//! result, what it may do and the call into [`LeptosTools`]. `tools/list`
+//! advertises the JSON Schemas generated from the argument and result types,
+//! `tools/call` deserializes into the one and returns the other, so the
+//! advertised schemas and what the tools parse and return can't drift apart.
+//! Arguments are denied unknown fields, so a misspelled one is an error
+//! rather than silently ignored.
+
+use crate::api;
+use crate::diagnostics;
+use crate::docs::Syntax;
+use crate::format::FormatOptions;
+use crate::generate::{Backend, ComponentSpec, ErrorTypeSpec, FormSpec, RouteSpec, ServerFnSpec};
+use crate::html::StyleMode;
+use crate::i18n::{Extraction, Library};
+use crate::output::{
+    ApiLookup, AppliedFix, BundleReport, Changelog, ClaimChecks, ComponentList, DependencyAdvice,
+    Documentation, EnvironmentReport, Examples, Explanations, FeatureCheck, Findings, Formatted,
+    Generated, ImportSuggestions, IslandsAdvice, ProjectContextSet, ProjectFindings,
+    ReactiveGraphs, Recommendations, Review, RuleExplanation, RuleList, ScaffoldedProject,
+    SearchResults, SectionList, SnippetList, SnippetLookup, StateList, Statistics, SyncSummary,
+    TermLookup, VersionSelected, View,
+};
+use crate::progress::Progress;
+use crate::project::SourceFile;
+use crate::reactivity::GraphFormat;
+use crate::recommend;
+use crate::routemap::RouteMap;
+use crate::rules::{RuleConfig, StyleFramework};
+use crate::sandbox::{CheckResult, RenderResult};
+use crate::scaffold::ProjectSpec;
+use crate::schema;
+use crate::tools::{
+    char_budget, DocFormat, DocOptions, LeptosTools, OutputFormat, SearchMode, ToolOutput,
+    DEFAULT_SEARCH_LIMIT,
+};
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// A tool served over MCP
+pub trait McpTool {
+    /// Name clients call the tool by
+    const NAME: &'static str;
+    /// What the tool does, for the agent choosing one
+    const DESCRIPTION: &'static str;
+    /// Human-readable name, the `title` annotation
+    const TITLE: &'static str;
+    /// Arguments of a call; their schema is the tool's `inputSchema`
+    type Args: DeserializeOwned + JsonSchema;
+    /// Structured content of a result; its schema is the tool's `outputSchema`
+    type Output: Serialize + JsonSchema;
+    /// Runs cargo or curl and can take minutes, so calls get the
+    /// `subprocess_timeout` rather than the `tool_timeout`
+    const SUBPROCESS: bool = false;
+    /// Writes to the user's files, so it is only served when
+    /// `tools.allow_writes` is set
+    const WRITES: bool = false;
+    /// Runs code the client sends, so it is only served when
+    /// `tools.allow_exec` is set
+    const EXECUTES: bool = false;
+    /// Only returns a result and changes nothing, not even session state
+    const READ_ONLY: bool = true;
+    /// May delete or overwrite something; only meaningful when not read-only
+    const DESTRUCTIVE: bool = false;
+    /// Repeating a call with the same arguments has no further effect
+    const IDEMPOTENT: bool = true;
+    /// Reaches outside the server: network, or crates fetched by cargo
+    const OPEN_WORLD: bool = false;
+
+    fn call(
+        tools: &LeptosTools,
+        args: Self::Args,
+        progress: &Progress,
+    ) -> Result<ToolOutput<Self::Output>, String>;
+}
+
+/// A registered tool, with its argument type erased
+pub struct Entry {
+    pub name: &'static str,
+    pub description: &'static str,
+    /// See [`McpTool::SUBPROCESS`]
+    pub subprocess: bool,
+    /// See [`McpTool::WRITES`]
+    pub writes: bool,
+    /// See [`McpTool::EXECUTES`]
+    pub executes: bool,
+    title: &'static str,
+    read_only: bool,
+    destructive: bool,
+    idempotent: bool,
+    open_world: bool,
+    input_schema: fn() -> Value,
+    output_schema: fn() -> Value,
+    call: fn(&LeptosTools, Value, &Progress) -> Result<ToolOutput, String>,
+}
+
+impl Entry {
+    const fn of<T: McpTool>() -> Self {
+        Self {
+            name: T::NAME,
+            description: T::DESCRIPTION,
+            subprocess: T::SUBPROCESS,
+            writes: T::WRITES,
+            executes: T::EXECUTES,
+            title: T::TITLE,
+            read_only: T::READ_ONLY,
+            destructive: T::DESTRUCTIVE,
+            idempotent: T::IDEMPOTENT,
+            open_world: T::OPEN_WORLD,
+            input_schema: schema::input::<T::Args>,
+            output_schema: schema::output::<T::Output>,
+            call: call::<T>,
+        }
+    }
+
+    /// JSON Schema of the tool's arguments
+    pub fn input_schema(&self) -> Value {
+        (self.input_schema)()
+    }
+
+    /// JSON Schema of the tool's structured results
+    pub fn output_schema(&self) -> Value {
+        (self.output_schema)()
+    }
+
+    /// MCP `annotations`, telling clients which tools change state so they
+    /// can decide what needs confirmation
+    pub fn annotations(&self) -> Value {
+        let mut annotations = json!({
+            "title": self.title,
+            "readOnlyHint": self.read_only,
+            "idempotentHint": self.idempotent,
+            "openWorldHint": self.open_world,
+        });
+        if !self.read_only {
+            annotations["destructiveHint"] = json!(self.destructive);
+        }
+        annotations
+    }
+
+    /// Run the tool with the `arguments` of a `tools/call`
+    pub fn call(
+        &self,
+        tools: &LeptosTools,
+        arguments: Value,
+        progress: &Progress,
+    ) -> Result<ToolOutput, String> {
+        (self.call)(tools, arguments, progress)
+    }
+}
+
+fn call<T: McpTool>(
+    tools: &LeptosTools,
+    arguments: Value,
+    progress: &Progress,
+) -> Result<ToolOutput, String> {
+    let args =
+        serde_json::from_value(arguments).map_err(|e| format!("Invalid arguments: {}", e))?;
+    T::call(tools, args, progress)?.into_json()
+}
+
+/// Every tool, in `tools/list` order
+pub const TOOLS: &[Entry] = &[
+    Entry::of::<ListSections>(),
+    Entry::of::<GetDocumentation>(),
+    Entry::of::<SearchDocs>(),
+    Entry::of::<RecommendSections>(),
+    Entry::of::<SetLeptosVersion>(),
+    Entry::of::<SetProjectContext>(),
+    Entry::of::<DetectEnvironment>(),
+    Entry::of::<CheckFeatures>(),
+    Entry::of::<AnalyzeBundle>(),
+    Entry::of::<GetExamples>(),
+    Entry::of::<ListSnippets>(),
+    Entry::of::<GetSnippet>(),
+    Entry::of::<DefineTerm>(),
+    Entry::of::<SuggestDependencies>(),
+    Entry::of::<LookupApi>(),
+    Entry::of::<ApiDiff>(),
+    Entry::of::<GetChangelog>(),
+    Entry::of::<VerifyClaims>(),
+    Entry::of::<LeptosAutofixer>(),
+    Entry::of::<ListRules>(),
+    Entry::of::<ExplainRule>(),
+    Entry::of::<AnalyzeProject>(),
+    Entry::of::<ApplyFix>(),
+    Entry::of::<ReviewDiff>(),
+    Entry::of::<AnalyzeReactivity>(),
+    Entry::of::<IslandsAdvisor>(),
+    Entry::of::<ExtractRoutes>(),
+    Entry::of::<ListComponents>(),
+    Entry::of::<ExtractState>(),
+    Entry::of::<ExtractStrings>(),
+    Entry::of::<GenerateComponent>(),
+    Entry::of::<GenerateServerFn>(),
+    Entry::of::<GenerateErrorType>(),
+    Entry::of::<GenerateForm>(),
+    Entry::of::<GenerateRoute>(),
+    Entry::of::<GenerateTests>(),
+    Entry::of::<ScaffoldProject>(),
+    Entry::of::<HtmlToView>(),
+    Entry::of::<CheckSnippet>(),
+    Entry::of::<CargoCheck>(),
+    Entry::of::<RenderPreview>(),
+    Entry::of::<ExplainError>(),
+    Entry::of::<FormatView>(),
+    Entry::of::<SuggestImports>(),
+    Entry::of::<SyncDocs>(),
+    Entry::of::<ServerStats>(),
+];
+
+/// Names of the MCP tools, in `tools/list` order
+pub const TOOL_NAMES: &[&str] = &{
+    let mut names = [""; TOOLS.len()];
+    let mut i = 0;
+    while i < TOOLS.len() {
+        names[i] = TOOLS[i].name;
+        i += 1;
+    }
+    names
+};
+
+/// The tool called `name`
+pub fn find(name: &str) -> Option<&'static Entry> {
+    TOOLS.iter().find(|tool| tool.name == name)
+}
+
+/// Arguments of a tool that takes none
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct NoArgs {}
+
+/// Arguments naming a project directory
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ProjectArgs {
+    /// Absolute path to the directory holding the project's Cargo.toml
+    /// (default: the project context's root)
+    pub path: Option<String>,
+}
+
+pub struct ListSections;
+
+impl McpTool for ListSections {
+    const NAME: &'static str = "list-sections";
+    const TITLE: &'static str = "List Documentation Sections";
+    const DESCRIPTION: &'static str =
+        "List all available Leptos documentation sections with their use cases";
+    type Args = NoArgs;
+    type Output = SectionList;
+
+    fn call(
+        tools: &LeptosTools,
+        _: NoArgs,
+        _: &Progress,
+    ) -> Result<ToolOutput<Self::Output>, String> {
+        Ok(tools.list_sections())
+    }
+}
+
+/// Arguments of `get-documentation`
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetDocumentationArgs {
+    /// Section name or path to retrieve
+    pub section: String,
+    /// Leptos version (0.6, 0.7, 0.8). Defaults to the session version
+    pub version: Option<String>,
+    /// Language to serve the section in: en (default) or zh. Sections without
+    /// a translation are served in English, and the response reports the
+    /// language served
+    pub lang: Option<String>,
+    /// Return only the subsection under this heading (slug or title). Also
+    /// accepted as 'section#heading'
+    pub heading: Option<String>,
+    /// Return the section's heading outline instead of its content
+    #[serde(default)]
+    pub toc: bool,
+    /// Approximate token budget; longer content is returned in parts split at
+    /// a heading or code-block boundary
+    pub max_tokens: Option<u64>,
+    /// Character budget; longer content is returned in parts split at a
+    /// heading or code-block boundary
+    pub max_chars: Option<u64>,
+    /// Cursor of the next part, from the 'continue' of the previous response
+    /// with the same budget
+    #[serde(rename = "continue")]
+    pub cursor: Option<String>,
+    /// Render as markdown (default), plain text with the markup stripped, or
+    /// an HTML fragment, for clients that show text literally
+    #[serde(default)]
+    pub format: DocFormat,
+}
+
+pub struct GetDocumentation;
+
+impl McpTool for GetDocumentation {
+    const NAME: &'static str = "get-documentation";
+    const TITLE: &'static str = "Get Documentation";
+    const DESCRIPTION: &'static str = "Get Leptos documentation for a specific section. Pass section name like 'signals', 'components', 'routing', or 'signals#derived-signals' for a subsection";
+    type Args = GetDocumentationArgs;
+    type Output = Documentation;
+
+    fn call(
+        tools: &LeptosTools,
+        args: GetDocumentationArgs,
+        _: &Progress,
+    ) -> Result<ToolOutput<Self::Output>, String> {
+        let options = DocOptions {
+            heading: args.heading.as_deref(),
+            toc: args.toc,
+            max_chars: char_budget(args.max_tokens, args.max_chars),
+            cursor: args.cursor.as_deref(),
+        };
+        tools.get_documentation(
+            &args.section,
+            args.version.as_deref(),
+            args.lang.as_deref(),
+            options,
+            args.format,
+        )
+    }
+}
+
+/// Arguments of `search-docs`
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SearchDocsArgs {
+    /// Keywords to search for, e.g. 'derived signal', or a question in
+    /// semantic mode
+    pub query: String,
+    /// Rank by matching terms (default) or by embedding similarity
+    #[serde(default)]
+    pub mode: SearchMode,
+    /// Leptos version (0.6, 0.7, 0.8). Defaults to the session version
+    pub version: Option<String>,
+    /// Maximum number of results
+    #[serde(default = "default_search_limit")]
+    pub limit: usize,
+    /// Cursor from a previous search, to get the next page of results
+    pub cursor: Option<String>,
+    /// Approximate token budget for the results
+    pub max_tokens: Option<u64>,
+    /// Character budget for the results
+    pub max_chars: Option<u64>,
+}
+
+fn default_search_limit() -> usize {
+    DEFAULT_SEARCH_LIMIT
+}
+
+pub struct SearchDocs;
+
+impl McpTool for SearchDocs {
+    const NAME: &'static str = "search-docs";
+    const TITLE: &'static str = "Search Documentation";
+    const DESCRIPTION: &'static str = "Search all Leptos documentation subsections and return ranked matches with excerpts. Keyword mode matches terms; semantic mode matches meaning, for questions about symptoms or goals like 'why does my page flash empty then fill in'";
+    type Args = SearchDocsArgs;
+    type Output = SearchResults;
+
+    fn call(
+        tools: &LeptosTools,
+        args: SearchDocsArgs,
+        _: &Progress,
+    ) -> Result<ToolOutput<Self::Output>, String> {
+        tools.search_docs(
+            &args.query,
+            args.mode,
+            args.version.as_deref(),
+            args.limit,
+            args.cursor.as_deref(),
+            char_budget(args.max_tokens, args.max_chars),
+        )
+    }
+}
+
+/// Arguments of `recommend-sections`
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RecommendSectionsArgs {
+    /// What you are trying to build or fix
+    pub task: String,
+    /// Leptos version (0.6, 0.7, 0.8). Defaults to the session version
+    pub version: Option<String>,
+    /// Maximum number of sections
+    #[serde(default = "default_recommend_limit")]
+    pub limit: usize,
+}
+
+fn default_recommend_limit() -> usize {
+    recommend::DEFAULT_LIMIT
+}
+
+pub struct RecommendSections;
+
+impl McpTool for RecommendSections {
+    const NAME: &'static str = "recommend-sections";
+    const TITLE: &'static str = "Recommend Sections";
+    const DESCRIPTION: &'static str = "Describe what you are building ('a paginated table fed by a server function with optimistic updates') and get a short ranked reading list of sections, each with the subsection to start at and a one-line reason. A cheaper first hop than search-docs";
+    type Args = RecommendSectionsArgs;
+    type Output = Recommendations;
+
+    fn call(
+        tools: &LeptosTools,
+        args: RecommendSectionsArgs,
+        _: &Progress,
+    ) -> Result<ToolOutput<Self::Output>, String> {
+        tools.recommend_sections(&args.task, args.version.as_deref(), args.limit)
+    }
+}
+
+/// Arguments of `set-leptos-version`
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SetLeptosVersionArgs {
+    /// Leptos version, e.g. '0.6', '0.7' or '0.8'
+    pub version: String,
+}
+
+pub struct SetLeptosVersion;
+
+impl McpTool for SetLeptosVersion {
+    const NAME: &'static str = "set-leptos-version";
+    const TITLE: &'static str = "Set Leptos Version";
+    const DESCRIPTION: &'static str = "Select the Leptos version (0.6, 0.7, 0.8) that documentation should target for the rest of the session";
+    type Args = SetLeptosVersionArgs;
+    type Output = VersionSelected;
+    // Changes the session's default version
+    const READ_ONLY: bool = false;
+
+    fn call(
+        tools: &LeptosTools,
+        args: SetLeptosVersionArgs,
+        _: &Progress,
+    ) -> Result<ToolOutput<Self::Output>, String> {
+        tools.set_leptos_version(&args.version)
+    }
+}
+
+/// Arguments of `set-project-context`
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SetProjectContextArgs {
+    /// Absolute path to the directory holding the project's Cargo.toml
+    pub path: String,
+    /// Leptos version to use instead of the detected one
+    pub version: Option<String>,
+    /// Backend to use instead of the detected one
+    pub backend: Option<Backend>,
+    /// Styling frameworks to lint for instead of the detected ones; [] turns
+    /// the styling checks off
+    pub styling: Option<Vec<StyleFramework>>,
+    /// Signal syntax to use instead of the detected one: nightly allows
+    /// calling signals as functions (count(), set_count(1))
+    pub syntax: Option<Syntax>,
+}
+
+pub struct SetProjectContext;
+
+impl McpTool for SetProjectContext {
+    const NAME: &'static str = "set-project-context";
+    const TITLE: &'static str = "Set Project Context";
+    const DESCRIPTION: &'static str = "Remember a Leptos project for the rest of the session: its root, the Leptos version and the backend (axum or actix) read from its Cargo.toml, the styling frameworks it uses and whether it has Leptos's nightly feature. Docs default to that version, lints read the project's leptos-mcp.toml and run the styling checks for those frameworks, analyze-project defaults to its root and generate-server-fn to its backend. Stable projects get lints against nightly signal calls like count() and docs without nightly-only snippets";
+    type Args = SetProjectContextArgs;
+    type Output = ProjectContextSet;
+    // Changes the session's project, version and backend
+    const READ_ONLY: bool = false;
+
+    fn call(
+        tools: &LeptosTools,
+        args: SetProjectContextArgs,
+        _: &Progress,
+    ) -> Result<ToolOutput<Self::Output>, String> {
+        tools.set_project_context(
+            &args.path,
+            args.version.as_deref(),
+            args.backend,
+            args.styling.as_deref(),
+            args.syntax,
+        )
+    }
+}
+
+pub struct DetectEnvironment;
+
+impl McpTool for DetectEnvironment {
+    const NAME: &'static str = "detect-environment";
+    const TITLE: &'static str = "Detect Project Environment";
+    const DESCRIPTION: &'static str = "Read a project's Cargo.toml and Cargo.lock and report the exact leptos, leptos_router, leptos_meta, leptos_axum and leptos_actix versions and how the csr/ssr/hydrate/islands/nightly features are enabled. The project becomes the session's context, so docs and lints follow its locked Leptos version";
+    type Args = ProjectArgs;
+    type Output = EnvironmentReport;
+    // Reads the manifest, then makes the project the session's context
+    const READ_ONLY: bool = false;
+
+    fn call(
+        tools: &LeptosTools,
+        args: ProjectArgs,
+        _: &Progress,
+    ) -> Result<ToolOutput<Self::Output>, String> {
+        tools.detect_environment(args.path.as_deref())
+    }
+}
+
+pub struct CheckFeatures;
+
+impl McpTool for CheckFeatures {
+    const NAME: &'static str = "check-features";
+    const TITLE: &'static str = "Check Cargo Features";
+    const DESCRIPTION: &'static str = "Check a Leptos project's Cargo.toml for feature misconfigurations: ssr/hydrate features not wired to leptos and its companion crates, server-only dependencies that aren't optional or not enabled by ssr, leptos features turned on unconditionally, and [package.metadata.leptos]/[lib] settings cargo-leptos needs. Each issue comes with a TOML patch";
+    type Args = ProjectArgs;
+    type Output = FeatureCheck;
+
+    fn call(
+        tools: &LeptosTools,
+        args: ProjectArgs,
+        _: &Progress,
+    ) -> Result<ToolOutput<Self::Output>, String> {
+        tools.check_features(args.path.as_deref())
+    }
+}
+
+pub struct AnalyzeBundle;
+
+impl McpTool for AnalyzeBundle {
+    const NAME: &'static str = "analyze-bundle";
+    const TITLE: &'static str = "Analyze WASM Bundle Size";
+    const DESCRIPTION: &'static str = "Find what makes a Leptos project's WASM bundle large and how to shrink it: release profile settings (opt-level, lto, codegen-units, debug info, panic strategy) for the profile the client is built with, a missing wasm-opt pass in Trunk apps, heavy crates the client compiles (regex, chrono, image, ...), and client code that embeds files, calls serde_json or derives serde both ways for many types. Suggestions are prioritized and each comes with the TOML, HTML or code to change";
+    type Args = ProjectArgs;
+    type Output = BundleReport;
+
+    fn call(
+        tools: &LeptosTools,
+        args: ProjectArgs,
+        progress: &Progress,
+    ) -> Result<ToolOutput<Self::Output>, String> {
+        tools.analyze_bundle(args.path.as_deref(), progress)
+    }
+}
+
+/// Arguments of `get-examples`
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetExamplesArgs {
+    /// Section name or path
+    pub section: String,
+    /// Leptos version (0.6, 0.7, 0.8). Defaults to the session version
+    pub version: Option<String>,
+    /// Language of the section the examples come from: en (default) or zh.
+    /// Sections without a translation are served in English, and the
+    /// response reports the language served
+    pub lang: Option<String>,
+    /// Render as markdown (default), plain text with the markup stripped, or
+    /// an HTML fragment, for clients that show text literally
+    #[serde(default)]
+    pub format: DocFormat,
+}
+
+pub struct GetExamples;
+
+impl McpTool for GetExamples {
+    const NAME: &'static str = "get-examples";
+    const TITLE: &'static str = "Get Code Examples";
+    const DESCRIPTION: &'static str = "Get only the Rust code examples from a Leptos documentation section, each labelled with the heading it appears under";
+    type Args = GetExamplesArgs;
+    type Output = Examples;
+
+    fn call(
+        tools: &LeptosTools,
+        args: GetExamplesArgs,
+        _: &Progress,
+    ) -> Result<ToolOutput<Self::Output>, String> {
+        tools.get_examples(
+            &args.section,
+            args.version.as_deref(),
+            args.lang.as_deref(),
+            args.format,
+        )
+    }
+}
+
+/// Arguments of `list-snippets`
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ListSnippetsArgs {
+    /// Only list snippets with this tag, e.g. 'server-fn' or 'auth'
+    pub tag: Option<String>,
+}
+
+pub struct ListSnippets;
+
+impl McpTool for ListSnippets {
+    const NAME: &'static str = "list-snippets";
+    const TITLE: &'static str = "List Snippets";
+    const DESCRIPTION: &'static str = "List the snippet library: small, complete examples of common Leptos patterns (debounced input, infinite scroll, auth-guarded route, file upload, websockets), optionally filtered by tag";
+    type Args = ListSnippetsArgs;
+    type Output = SnippetList;
+
+    fn call(
+        tools: &LeptosTools,
+        args: ListSnippetsArgs,
+        _: &Progress,
+    ) -> Result<ToolOutput<Self::Output>, String> {
+        Ok(tools.list_snippets(args.tag.as_deref()))
+    }
+}
+
+/// Arguments of `get-snippet`
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetSnippetArgs {
+    /// Snippet name from list-snippets, e.g. 'file-upload'
+    pub name: String,
+}
+
+pub struct GetSnippet;
+
+impl McpTool for GetSnippet {
+    const NAME: &'static str = "get-snippet";
+    const TITLE: &'static str = "Get Snippet";
+    const DESCRIPTION: &'static str = "Get one canonical working example from the snippet library by name; faster than reading a docs section when you just need the pattern";
+    type Args = GetSnippetArgs;
+    type Output = SnippetLookup;
+
+    fn call(
+        tools: &LeptosTools,
+        args: GetSnippetArgs,
+        _: &Progress,
+    ) -> Result<ToolOutput<Self::Output>, String> {
+        tools.get_snippet(&args.name)
+    }
+}
+
+/// Arguments of `define-term`
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DefineTermArgs {
+    /// Term to define, e.g. 'memo' or 'owner'
+    pub term: String,
+}
+
+pub struct DefineTerm;
+
+impl McpTool for DefineTerm {
+    const NAME: &'static str = "define-term";
+    const TITLE: &'static str = "Define Term";
+    const DESCRIPTION: &'static str = "One-paragraph definition of a Leptos term (signal, memo, resource, action, island, hydration, owner, arena, effect, transition, ...) with the types implementing it and the docs section covering it. Cheaper than reading a chapter for grounding";
+    type Args = DefineTermArgs;
+    type Output = TermLookup;
+
+    fn call(
+        tools: &LeptosTools,
+        args: DefineTermArgs,
+        _: &Progress,
+    ) -> Result<ToolOutput<Self::Output>, String> {
+        tools.define_term(&args.term)
+    }
+}
+
+/// Arguments of `suggest-dependencies`
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SuggestDependenciesArgs {
+    /// What the app needs, e.g. 'charts' or 'auth with sessions'
+    pub need: String,
+    /// Leptos version (0.6, 0.7, 0.8). Defaults to the session version
+    pub version: Option<String>,
+}
+
+pub struct SuggestDependencies;
+
+impl McpTool for SuggestDependencies {
+    const NAME: &'static str = "suggest-dependencies";
+    const TITLE: &'static str = "Suggest Dependencies";
+    const DESCRIPTION: &'static str = "Vetted ecosystem crates for a need stated in plain words (charts, i18n, auth with sessions, websockets, head management, icons, database, ...): the exact Cargo.toml lines for the Leptos version, the features an SSR app forwards, and a minimal usage example. Crates tied to a server integration follow the project's backend";
+    type Args = SuggestDependenciesArgs;
+    type Output = DependencyAdvice;
+
+    fn call(
+        tools: &LeptosTools,
+        args: SuggestDependenciesArgs,
+        _: &Progress,
+    ) -> Result<ToolOutput<Self::Output>, String> {
+        tools.suggest_dependencies(&args.need, args.version.as_deref())
+    }
+}
+
+/// Arguments of `lookup-api`
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct LookupApiArgs {
+    /// Item path; crate and module prefixes may be omitted
+    pub path: String,
+    /// Leptos version (0.6, 0.7, 0.8). Defaults to the session version
+    pub version: Option<String>,
+}
+
+pub struct LookupApi;
+
+impl McpTool for LookupApi {
+    const NAME: &'static str = "lookup-api";
+    const TITLE: &'static str = "Look Up API Item";
+    const DESCRIPTION: &'static str = "Look up the exact signature, generic bounds and doc comment of a Leptos API item, e.g. 'Resource::new' or 'leptos_router::hooks::use_params'";
+    type Args = LookupApiArgs;
+    type Output = ApiLookup;
+
+    fn call(
+        tools: &LeptosTools,
+        args: LookupApiArgs,
+        _: &Progress,
+    ) -> Result<ToolOutput<Self::Output>, String> {
+        tools.lookup_api(&args.path, args.version.as_deref())
+    }
+}
+
+/// Arguments of `api-diff`
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ApiDiffArgs {
+    /// Version to compare from (0.6, 0.7, 0.8)
+    pub from: String,
+    /// Version to compare to. Defaults to the session version
+    pub to: Option<String>,
+    /// Only report items matching this path; crate and module prefixes may be
+    /// omitted
+    pub item: Option<String>,
+}
+
+pub struct ApiDiff;
+
+impl McpTool for ApiDiff {
+    const NAME: &'static str = "api-diff";
+    const TITLE: &'static str = "Diff API Between Versions";
+    const DESCRIPTION: &'static str = "Compare the Leptos API between two versions: renamed or moved, removed and added items and signature changes. Pass 'item' to ask whether one function still exists, e.g. create_signal from 0.6 to 0.8";
+    type Args = ApiDiffArgs;
+    type Output = api::ApiDiff;
+
+    fn call(
+        tools: &LeptosTools,
+        args: ApiDiffArgs,
+        _: &Progress,
+    ) -> Result<ToolOutput<Self::Output>, String> {
+        tools.api_diff(&args.from, args.to.as_deref(), args.item.as_deref())
+    }
+}
+
+/// Arguments of `get-changelog`
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetChangelogArgs {
+    /// Version upgrading from, e.g. '0.6' (all of 0.6.x) or '0.7.2'
+    pub from_version: String,
+    /// Version upgrading to, inclusive. Defaults to the session version
+    pub to_version: Option<String>,
+}
+
+pub struct GetChangelog;
+
+impl McpTool for GetChangelog {
+    const NAME: &'static str = "get-changelog";
+    const TITLE: &'static str = "Get Changelog";
+    const DESCRIPTION: &'static str = "Leptos release notes between two versions, grouped into breaking changes, features and fixes. Use it to enumerate what an upgrade has to deal with";
+    type Args = GetChangelogArgs;
+    type Output = Changelog;
+
+    fn call(
+        tools: &LeptosTools,
+        args: GetChangelogArgs,
+        _: &Progress,
+    ) -> Result<ToolOutput<Self::Output>, String> {
+        tools.get_changelog(&args.from_version, args.to_version.as_deref())
+    }
+}
+
+/// Arguments of `verify-claims`
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct VerifyClaimsArgs {
+    /// One statement per entry
+    pub claims: Vec<String>,
+    /// Leptos version the claims are about (default: the selected version)
+    pub version: Option<String>,
+}
+
+pub struct VerifyClaims;
+
+impl McpTool for VerifyClaims {
+    const NAME: &'static str = "verify-claims";
+    const TITLE: &'static str = "Verify Claims";
+    const DESCRIPTION: &'static str = "Check statements about Leptos behavior (e.g. 'Resource::new refetches when its source signal changes') against a fact base drawn from the docs. Each claim comes back confirmed, contradicted or unknown, with the fact and the documentation section it cites. Use it to self-check explanations before giving them";
+    type Args = VerifyClaimsArgs;
+    type Output = ClaimChecks;
+
+    fn call(
+        tools: &LeptosTools,
+        args: VerifyClaimsArgs,
+        _: &Progress,
+    ) -> Result<ToolOutput<Self::Output>, String> {
+        tools.verify_claims(&args.claims, args.version.as_deref())
+    }
+}
+
+/// Arguments of `leptos-autofixer`
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct LeptosAutofixerArgs {
+    /// Leptos code to analyze
+    pub code: Option<String>,
+    /// Files analyzed together instead of 'code', with their paths in the
+    /// project (e.g. 'src/app.rs', 'src/components/mod.rs')
+    pub files: Option<Vec<SourceFile>>,
+    /// Render diagnostics as text (default) or JSON with rule id, severity,
+    /// line, column and suggested fix
+    #[serde(default)]
+    pub format: OutputFormat,
+    /// Lint rule overrides, layered over leptos-mcp.toml: {"disable":
+    /// ["L0002"], "severity": {"L0005": "warning"}}. Rules may be referenced
+    /// by id, name or category (e.g. "a11y")
+    pub rules: Option<RuleConfig>,
+}
+
+pub struct LeptosAutofixer;
+
+impl McpTool for LeptosAutofixer {
+    const NAME: &'static str = "leptos-autofixer";
+    const TITLE: &'static str = "Check Leptos Code";
+    const DESCRIPTION: &'static str = "Analyze Leptos code and suggest fixes for common issues. Pass several files of a project with 'files' to also check what spans files: components used without their mod/use, ssr-only server functions called from client code, duplicate route paths";
+    type Args = LeptosAutofixerArgs;
+    type Output = Findings;
+
+    fn call(
+        tools: &LeptosTools,
+        args: LeptosAutofixerArgs,
+        progress: &Progress,
+    ) -> Result<ToolOutput<Self::Output>, String> {
+        match args.files {
+            Some(files) => tools.leptos_autofixer_files(&files, args.rules, args.format, progress),
+            None => tools.leptos_autofixer(
+                args.code.as_deref().unwrap_or_default(),
+                args.rules,
+                args.format,
+            ),
+        }
+    }
+}
+
+pub struct ListRules;
+
+impl McpTool for ListRules {
+    const NAME: &'static str = "list-rules";
+    const TITLE: &'static str = "List Lint Rules";
+    const DESCRIPTION: &'static str =
+        "List the autofixer lint rules with their ids, names and default severities";
+    type Args = NoArgs;
+    type Output = RuleList;
+
+    fn call(
+        tools: &LeptosTools,
+        _: NoArgs,
+        _: &Progress,
+    ) -> Result<ToolOutput<Self::Output>, String> {
+        Ok(tools.list_rules())
+    }
+}
+
+/// Arguments of `explain-rule`
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ExplainRuleArgs {
+    /// Rule id or name, e.g. 'L0001' or 'get-without-move'
+    pub rule: String,
+    /// Leptos version (0.6, 0.7, 0.8) of the docs quoted. Defaults to the
+    /// session version
+    pub version: Option<String>,
+}
+
+pub struct ExplainRule;
+
+impl McpTool for ExplainRule {
+    const NAME: &'static str = "explain-rule";
+    const TITLE: &'static str = "Explain Lint Rule";
+    const DESCRIPTION: &'static str = "Explain an autofixer rule: what it flags, a flagged and a fixed example, and the documentation subsection with the reasoning behind it. Every autofixer diagnostic names its rule id";
+    type Args = ExplainRuleArgs;
+    type Output = RuleExplanation;
+
+    fn call(
+        tools: &LeptosTools,
+        args: ExplainRuleArgs,
+        _: &Progress,
+    ) -> Result<ToolOutput<Self::Output>, String> {
+        tools.explain_rule(&args.rule, args.version.as_deref())
+    }
+}
+
+/// Arguments of `analyze-project`
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AnalyzeProjectArgs {
+    /// Absolute path to the project directory (default: the project context's
+    /// root)
+    pub path: Option<String>,
+    /// Render diagnostics as text (default) or JSON with rule id, severity,
+    /// line, column and suggested fix
+    #[serde(default)]
+    pub format: OutputFormat,
+    /// Lint rule overrides, layered over leptos-mcp.toml: {"disable":
+    /// ["L0002"], "severity": {"L0005": "warning"}}. Rules may be referenced
+    /// by id, name or category (e.g. "a11y")
+    pub rules: Option<RuleConfig>,
+}
+
+pub struct AnalyzeProject;
+
+impl McpTool for AnalyzeProject {
+    const NAME: &'static str = "analyze-project";
+    const TITLE: &'static str = "Analyze Project";
+    const DESCRIPTION: &'static str = "Run the autofixer over every Rust file of a Leptos project (src/**/*.rs, honoring .gitignore) plus cross-file checks, grouped by file. Diagnostics with a mechanical fix carry an id for apply-fix";
+    type Args = AnalyzeProjectArgs;
+    type Output = ProjectFindings;
+
+    fn call(
+        tools: &LeptosTools,
+        args: AnalyzeProjectArgs,
+        progress: &Progress,
+    ) -> Result<ToolOutput<Self::Output>, String> {
+        tools.analyze_project(args.path.as_deref(), args.rules, args.format, progress)
+    }
+}
+
+/// Arguments of `apply-fix`
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ApplyFixArgs {
+    /// Id of a diagnostic from the session's last analyze-project (or
+    /// leptos-autofixer with files) run, e.g. "F3"
+    pub id: String,
+    /// Absolute path to the project the analysis ran in (default: that
+    /// project); the fix is refused if it doesn't match
+    pub path: Option<String>,
+}
+
+pub struct ApplyFix;
+
+impl McpTool for ApplyFix {
+    const NAME: &'static str = "apply-fix";
+    const TITLE: &'static str = "Apply Fix";
+    const DESCRIPTION: &'static str = "Apply the suggested rewrite of a diagnostic from the session's last analyze-project run to the file on disk and return the diff. Only mechanical fixes have an id; refuses fixes whose code changed since the analysis";
+    type Args = ApplyFixArgs;
+    type Output = AppliedFix;
+    const WRITES: bool = true;
+    // Rewrites a file of the project; a second call finds the fix used up
+    const READ_ONLY: bool = false;
+    const DESTRUCTIVE: bool = true;
+    const IDEMPOTENT: bool = false;
+
+    fn call(
+        tools: &LeptosTools,
+        args: ApplyFixArgs,
+        _: &Progress,
+    ) -> Result<ToolOutput<Self::Output>, String> {
+        tools.apply_fix(&args.id, args.path.as_deref())
+    }
+}
+
+/// Arguments of `review-diff`
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ReviewDiffArgs {
+    /// Unified diff of the change, e.g. the output of `git diff main...`,
+    /// with paths relative to the project or its repository
+    pub diff: Option<String>,
+    /// Absolute path to the project the diff applies to, checked out before
+    /// or after the change (default: the project context's root)
+    pub path: Option<String>,
+    /// A single file's content before the change, with 'new' instead of 'diff'
+    pub old: Option<String>,
+    /// The file's content after the change
+    pub new: Option<String>,
+    /// Path of the 'old'/'new' file in the project, e.g. 'src/app.rs'
+    pub file: Option<String>,
+    /// Render the review as text (default) or JSON
+    #[serde(default)]
+    pub format: OutputFormat,
+    /// Lint rule overrides, layered over leptos-mcp.toml: {"disable":
+    /// ["L0002"], "severity": {"L0005": "warning"}}. Rules may be referenced
+    /// by id, name or category (e.g. "a11y")
+    pub rules: Option<RuleConfig>,
+}
+
+pub struct ReviewDiff;
+
+impl McpTool for ReviewDiff {
+    const NAME: &'static str = "review-diff";
+    const TITLE: &'static str = "Review Diff";
+    const DESCRIPTION: &'static str = "Review a change like a PR bot: run the autofixer on the regions a unified diff of the project (or a file's old and new content) changes, and report the diagnostics the change introduces apart from pre-existing ones it touches and ones it resolves";
+    type Args = ReviewDiffArgs;
+    type Output = Review;
+
+    fn call(
+        tools: &LeptosTools,
+        args: ReviewDiffArgs,
+        progress: &Progress,
+    ) -> Result<ToolOutput<Self::Output>, String> {
+        match (args.diff, args.old, args.new) {
+            (Some(diff), None, None) => tools.review_diff(
+                &diff,
+                args.path.as_deref(),
+                args.rules,
+                args.format,
+                progress,
+            ),
+            (None, Some(old), Some(new)) => {
+                tools.review_change(&old, &new, args.file.as_deref(), args.rules, args.format)
+            }
+            _ => Err("Pass either 'diff', or 'old' and 'new'".to_string()),
+        }
+    }
+}
+
+/// Arguments of `analyze-reactivity`
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AnalyzeReactivityArgs {
+    /// Rust code with one or more #[component] functions, or a component body
+    pub code: String,
+    /// Only analyze the component with this name
+    pub component: Option<String>,
+    /// Render the graph as a Mermaid flowchart (default) or Graphviz DOT
+    #[serde(default)]
+    pub graph: GraphFormat,
+}
+
+pub struct AnalyzeReactivity;
+
+impl McpTool for AnalyzeReactivity {
+    const NAME: &'static str = "analyze-reactivity";
+    const TITLE: &'static str = "Analyze Reactive Graph";
+    const DESCRIPTION: &'static str = "Map how a component's signals, memos, derived signals, resources, effects and view depend on each other, as a Mermaid or DOT graph plus a structured edge list. Flags signals nothing reads and effects that write a signal they depend on (potential infinite loops)";
+    type Args = AnalyzeReactivityArgs;
+    type Output = ReactiveGraphs;
+
+    fn call(
+        tools: &LeptosTools,
+        args: AnalyzeReactivityArgs,
+        _: &Progress,
+    ) -> Result<ToolOutput<Self::Output>, String> {
+        tools.analyze_reactivity(&args.code, args.component.as_deref(), args.graph)
+    }
+}
+
+/// Arguments of `islands-advisor`
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct IslandsAdvisorArgs {
+    /// Rust code with the component tree; without it, the project's sources
+    /// are read
+    pub code: Option<String>,
+    /// Project root to read when no code is given (defaults to the project
+    /// context)
+    pub path: Option<String>,
+    /// Leptos version for the setup steps (defaults to the project's or
+    /// session's version)
+    pub version: Option<String>,
+}
+
+pub struct IslandsAdvisor;
+
+impl McpTool for IslandsAdvisor {
+    const NAME: &'static str = "islands-advisor";
+    const TITLE: &'static str = "Advise on Islands";
+    const DESCRIPTION: &'static str = "Suggest which components should be #[island]s (interactive leaves) and which stay server-only, flag island props that can't be serialized (closures, signals, views, types without serde derives), and list the feature flags and hydration setup islands mode needs for the project's Leptos version";
+    type Args = IslandsAdvisorArgs;
+    type Output = IslandsAdvice;
+
+    fn call(
+        tools: &LeptosTools,
+        args: IslandsAdvisorArgs,
+        progress: &Progress,
+    ) -> Result<ToolOutput<Self::Output>, String> {
+        tools.islands_advisor(
+            args.code.as_deref(),
+            args.path.as_deref(),
+            args.version.as_deref(),
+            progress,
+        )
+    }
+}
+
+/// Arguments of `extract-routes`
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ExtractRoutesArgs {
+    /// Rust code with the route definitions; without it, the project's
+    /// sources are read
+    pub code: Option<String>,
+    /// Project root to read when no code is given (defaults to the project
+    /// context)
+    pub path: Option<String>,
+}
+
+pub struct ExtractRoutes;
+
+impl McpTool for ExtractRoutes {
+    const NAME: &'static str = "extract-routes";
+    const TITLE: &'static str = "Extract Routes";
+    const DESCRIPTION: &'static str = "Map a project's routes from its <Router>/<Routes> definitions: the full route tree with each route's path, path params (including its parents'), nested <ParentRoute>s and whether their views render an <Outlet/>, and the component handling each route with the file it's defined in. Use it before changing navigation";
+    type Args = ExtractRoutesArgs;
+    type Output = RouteMap;
+
+    fn call(
+        tools: &LeptosTools,
+        args: ExtractRoutesArgs,
+        progress: &Progress,
+    ) -> Result<ToolOutput<Self::Output>, String> {
+        tools.extract_routes(args.code.as_deref(), args.path.as_deref(), progress)
+    }
+}
+
+/// Arguments of the tools mapping a project's components
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ComponentsArgs {
+    /// Rust code with the components; without it, the project's sources are
+    /// read
+    pub code: Option<String>,
+    /// Project root to read when no code is given (defaults to the project
+    /// context)
+    pub path: Option<String>,
+}
+
+pub struct ListComponents;
+
+impl McpTool for ListComponents {
+    const NAME: &'static str = "list-components";
+    const TITLE: &'static str = "List Components";
+    const DESCRIPTION: &'static str = "List every #[component] and #[island] in a project with its file and line, doc comment, props (name, type, optional/default, into), whether it takes children, and the components and routes that use it. Use it to get a structural map before editing";
+    type Args = ComponentsArgs;
+    type Output = ComponentList;
+
+    fn call(
+        tools: &LeptosTools,
+        args: ComponentsArgs,
+        progress: &Progress,
+    ) -> Result<ToolOutput<Self::Output>, String> {
+        tools.list_components(args.code.as_deref(), args.path.as_deref(), progress)
+    }
+}
+
+pub struct ExtractState;
+
+impl McpTool for ExtractState {
+    const NAME: &'static str = "extract-state";
+    const TITLE: &'static str = "Extract State";
+    const DESCRIPTION: &'static str = "List every signal, memo, resource, action, store and stored value a project declares: where it's created, whether it's provided as context (and under which type), and which components read or write it, following it through props and use_context/expect_context. Check it before adding state so you reuse what exists";
+    type Args = ComponentsArgs;
+    type Output = StateList;
+
+    fn call(
+        tools: &LeptosTools,
+        args: ComponentsArgs,
+        progress: &Progress,
+    ) -> Result<ToolOutput<Self::Output>, String> {
+        tools.extract_state(args.code.as_deref(), args.path.as_deref(), progress)
+    }
+}
+
+/// Arguments of `extract-strings`
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ExtractStringsArgs {
+    /// Rust code with the views; without it, the project's sources are read
+    pub code: Option<String>,
+    /// Project root to read when no code is given (defaults to the project
+    /// context)
+    pub path: Option<String>,
+    /// Translation crate (default: leptos-fluent if the project depends on
+    /// it, else leptos_i18n)
+    pub library: Option<Library>,
+    /// Default locale the locale file is written for (default 'en')
+    pub locale: Option<String>,
+}
+
+pub struct ExtractStrings;
+
+impl McpTool for ExtractStrings {
+    const NAME: &'static str = "extract-strings";
+    const TITLE: &'static str = "Extract Translatable Strings";
+    const DESCRIPTION: &'static str = "Find hard-coded user-facing strings in view! bodies (text and attributes like placeholder, title, alt, aria-label) and propose translation keys scoped by component, the t!/t_string! (leptos_i18n) or move_tr! (leptos-fluent) code replacing each, the default locale file's entries and the setup the replacements need";
+    type Args = ExtractStringsArgs;
+    type Output = Extraction;
+
+    fn call(
+        tools: &LeptosTools,
+        args: ExtractStringsArgs,
+        progress: &Progress,
+    ) -> Result<ToolOutput<Self::Output>, String> {
+        tools.extract_strings(
+            args.code.as_deref(),
+            args.path.as_deref(),
+            args.library,
+            args.locale.as_deref(),
+            progress,
+        )
+    }
+}
+
+pub struct GenerateComponent;
+
+impl McpTool for GenerateComponent {
+    const NAME: &'static str = "generate-component";
+    const TITLE: &'static str = "Generate Component";
+    const DESCRIPTION: &'static str = "Generate a ready-to-paste #[component] function with documented props, optional children, local signals and a skeleton view!, optionally with a typed NodeRef on its root element and HTML attributes passed through to it";
+    type Args = ComponentSpec;
+    type Output = Generated;
+
+    fn call(
+        tools: &LeptosTools,
+        spec: ComponentSpec,
+        _: &Progress,
+    ) -> Result<ToolOutput<Self::Output>, String> {
+        tools.generate_component(&spec)
+    }
+}
+
+pub struct GenerateServerFn;
+
+impl McpTool for GenerateServerFn {
+    const NAME: &'static str = "generate-server-fn";
+    const TITLE: &'static str = "Generate Server Function";
+    const DESCRIPTION: &'static str = "Generate a #[server] function, a ServerAction/ActionForm usage snippet and the Cargo.toml feature setup, as separate content blocks. With a state type, also the main.rs setup injecting it for the backend";
+    type Args = ServerFnSpec;
+    type Output = Generated;
+
+    fn call(
+        tools: &LeptosTools,
+        spec: ServerFnSpec,
+        _: &Progress,
+    ) -> Result<ToolOutput<Self::Output>, String> {
+        tools.generate_server_fn(&spec)
+    }
+}
+
+pub struct GenerateErrorType;
+
+impl McpTool for GenerateErrorType {
+    const NAME: &'static str = "generate-error-type";
+    const TITLE: &'static str = "Generate Error Type";
+    const DESCRIPTION: &'static str = "Generate a custom error enum for server functions: thiserror messages, From impls for other errors (ssr-gated for server-only crates), the ServerFnError wiring for the Leptos version (FromServerFnError on 0.8, ServerFnError<E> with FromStr before), a server function returning it and an ErrorBoundary fallback that matches on it, as separate content blocks";
+    type Args = ErrorTypeSpec;
+    type Output = Generated;
+
+    fn call(
+        tools: &LeptosTools,
+        spec: ErrorTypeSpec,
+        _: &Progress,
+    ) -> Result<ToolOutput<Self::Output>, String> {
+        tools.generate_error_type(&spec)
+    }
+}
+
+pub struct GenerateForm;
+
+impl McpTool for GenerateForm {
+    const NAME: &'static str = "generate-form";
+    const TITLE: &'static str = "Generate Form";
+    const DESCRIPTION: &'static str = "Generate a form: a controlled component with prop:value inputs and per-field validation signals, the #[server] function it posts to through ActionForm (re-running the same checks), and server errors shown through ErrorBoundary";
+    type Args = FormSpec;
+    type Output = Generated;
+
+    fn call(
+        tools: &LeptosTools,
+        spec: FormSpec,
+        _: &Progress,
+    ) -> Result<ToolOutput<Self::Output>, String> {
+        tools.generate_form(&spec)
+    }
+}
+
+pub struct GenerateRoute;
+
+impl McpTool for GenerateRoute {
+    const NAME: &'static str = "generate-route";
+    const TITLE: &'static str = "Generate Route";
+    const DESCRIPTION: &'static str = "Generate a leptos_router <Route> declaration, the page component with a typed Params struct, and optionally a ParentRoute layout with <Outlet/> and a lazy-loading wrapper";
+    type Args = RouteSpec;
+    type Output = Generated;
+
+    fn call(
+        tools: &LeptosTools,
+        spec: RouteSpec,
+        _: &Progress,
+    ) -> Result<ToolOutput<Self::Output>, String> {
+        tools.generate_route(&spec)
+    }
+}
+
+/// Arguments of `generate-tests`
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GenerateTestsArgs {
+    /// Rust source with the #[component] and #[server] functions to test
+    pub code: String,
+    /// Only test the component or server function of this name
+    pub name: Option<String>,
+    /// Leptos version of the test harness, e.g. '0.6' or '0.8' (default: the
+    /// selected version)
+    pub version: Option<String>,
+}
+
+pub struct GenerateTests;
+
+impl McpTool for GenerateTests {
+    const NAME: &'static str = "generate-tests";
+    const TITLE: &'static str = "Generate Tests";
+    const DESCRIPTION: &'static str = "Generate wasm-bindgen-test browser tests for components (mount, assert rendered text, fire their event handlers) and tokio tests for server functions with their contexts provided, plus the dev-dependencies";
+    type Args = GenerateTestsArgs;
+    type Output = Generated;
+
+    fn call(
+        tools: &LeptosTools,
+        args: GenerateTestsArgs,
+        _: &Progress,
+    ) -> Result<ToolOutput<Self::Output>, String> {
+        tools.generate_tests(&args.code, args.name.as_deref(), args.version.as_deref())
+    }
+}
+
+pub struct ScaffoldProject;
+
+impl McpTool for ScaffoldProject {
+    const NAME: &'static str = "scaffold-project";
+    const TITLE: &'static str = "Scaffold Project";
+    const DESCRIPTION: &'static str = "Generate every file of a new Leptos app as {path, content} entries: Cargo.toml with features and cargo-leptos metadata, the main.rs/lib.rs entry points, app.rs, styles and the Trunk config for client-side apps";
+    type Args = ProjectSpec;
+    type Output = ScaffoldedProject;
+
+    fn call(
+        tools: &LeptosTools,
+        spec: ProjectSpec,
+        _: &Progress,
+    ) -> Result<ToolOutput<Self::Output>, String> {
+        tools.scaffold_project(&spec)
+    }
+}
+
+/// Arguments of `html-to-view`
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct HtmlToViewArgs {
+    /// HTML fragment to convert
+    pub html: String,
+    /// Keep style="..." as one string (default) or split it into
+    /// style:property="value" attributes
+    #[serde(default)]
+    pub style: StyleMode,
+}
+
+pub struct HtmlToView;
+
+impl McpTool for HtmlToView {
+    const NAME: &'static str = "html-to-view";
+    const TITLE: &'static str = "Convert HTML to view!";
+    const DESCRIPTION: &'static str = "Convert an HTML fragment into idiomatic view! markup: quoted attributes, self-closed void elements, text as string literals, inline event handlers replaced by TODO comments";
+    type Args = HtmlToViewArgs;
+    type Output = View;
+
+    fn call(
+        tools: &LeptosTools,
+        args: HtmlToViewArgs,
+        _: &Progress,
+    ) -> Result<ToolOutput<Self::Output>, String> {
+        tools.html_to_view(&args.html, args.style)
+    }
+}
+
+/// Arguments of `check-snippet`
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CheckSnippetArgs {
+    /// Rust items, or statements/a view! expression which are wrapped in a
+    /// function. `use leptos::prelude::*` is added if missing
+    pub code: String,
+    /// Leptos version (0.6, 0.7, 0.8). Defaults to the session version
+    pub version: Option<String>,
+    /// Leptos features to enable, e.g. ["ssr"] or ["csr"]
+    #[serde(default)]
+    pub features: Vec<String>,
+    /// Time limit in seconds (default 600)
+    pub timeout_secs: Option<u64>,
+    /// Render diagnostics as text (default) or JSON
+    #[serde(default)]
+    pub format: OutputFormat,
+}
+
+pub struct CheckSnippet;
+
+impl McpTool for CheckSnippet {
+    const NAME: &'static str = "check-snippet";
+    const TITLE: &'static str = "Compile-Check Snippet";
+    const DESCRIPTION: &'static str = "Compile a Leptos code snippet with cargo check against a pinned Leptos version and return rustc diagnostics mapped to the snippet's lines. The first check of a version builds Leptos and can take minutes";
+    type Args = CheckSnippetArgs;
+    type Output = CheckResult;
+    const SUBPROCESS: bool = true;
+    // Writes a cached cargo project and fetches crates on first use
+    const READ_ONLY: bool = false;
+    const OPEN_WORLD: bool = true;
+
+    fn call(
+        tools: &LeptosTools,
+        args: CheckSnippetArgs,
+        progress: &Progress,
+    ) -> Result<ToolOutput<Self::Output>, String> {
+        tools.check_snippet(
+            &args.code,
+            args.version.as_deref(),
+            &args.features,
+            args.timeout_secs,
+            args.format,
+            progress,
+        )
+    }
+}
+
+/// Arguments of `cargo-check`
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CargoCheckArgs {
+    /// Project directory containing Cargo.toml (default: the project context's
+    /// root)
+    pub path: Option<String>,
+    /// Only report diagnostics in these files, e.g. ["src/app.rs"]
+    #[serde(default)]
+    pub files: Vec<String>,
+    /// Crate features to check with, e.g. ["ssr"] or ["hydrate"]
+    #[serde(default)]
+    pub features: Vec<String>,
+    /// Time limit in seconds (default 600)
+    pub timeout_secs: Option<u64>,
+    /// Render diagnostics as text (default) or JSON
+    #[serde(default)]
+    pub format: OutputFormat,
+}
+
+pub struct CargoCheck;
+
+impl McpTool for CargoCheck {
+    const NAME: &'static str = "cargo-check";
+    const TITLE: &'static str = "Cargo Check Project";
+    const DESCRIPTION: &'static str = "Run cargo check --message-format=json in a project and return its diagnostics by file and line. Leptos-related errors (IntoView not implemented, FnOnce closures in view!, ServerFnError conversions, ...) come with an explanation, a fix hint and the doc section to read";
+    type Args = CargoCheckArgs;
+    type Output = diagnostics::Report;
+    const SUBPROCESS: bool = true;
+    // Builds into the project's target directory and fetches crates it lacks
+    const READ_ONLY: bool = false;
+    const OPEN_WORLD: bool = true;
+
+    fn call(
+        tools: &LeptosTools,
+        args: CargoCheckArgs,
+        progress: &Progress,
+    ) -> Result<ToolOutput<Self::Output>, String> {
+        tools.cargo_check(
+            args.path.as_deref(),
+            &args.files,
+            &args.features,
+            args.timeout_secs,
+            args.format,
+            progress,
+        )
+    }
+}
+
+/// Arguments of `render-preview`
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RenderPreviewArgs {
+    /// A view! expression or statements ending in one, or items defining
+    /// components. `use leptos::prelude::*` is added if missing
+    pub code: String,
+    /// Component to render when the code defines several (default: the one no
+    /// other component uses). It is mounted without props
+    pub component: Option<String>,
+    /// Leptos version (0.6, 0.7, 0.8). Defaults to the session version
+    pub version: Option<String>,
+    /// Keep hydration keys and marker comments in the HTML
+    #[serde(default)]
+    pub markers: bool,
+    /// Time limit for the build in seconds (default 600)
+    pub timeout_secs: Option<u64>,
+}
+
+pub struct RenderPreview;
+
+impl McpTool for RenderPreview {
+    const NAME: &'static str = "render-preview";
+    const TITLE: &'static str = "Render Preview";
+    const DESCRIPTION: &'static str = "Render a view! snippet or component on the server and return the HTML it produces, to check structure, classes and conditional rendering without a browser. Builds the snippet against a pinned Leptos version with the ssr feature; the first render of a version builds Leptos and can take minutes";
+    type Args = RenderPreviewArgs;
+    type Output = RenderResult;
+    const SUBPROCESS: bool = true;
+    const EXECUTES: bool = true;
+    // Writes a cached cargo project, fetches crates on first use, and runs
+    // the build and then the client's code, which can do anything
+    const READ_ONLY: bool = false;
+    const DESTRUCTIVE: bool = true;
+    const OPEN_WORLD: bool = true;
+
+    fn call(
+        tools: &LeptosTools,
+        args: RenderPreviewArgs,
+        progress: &Progress,
+    ) -> Result<ToolOutput<Self::Output>, String> {
+        tools.render_preview(
+            &args.code,
+            args.component.as_deref(),
+            args.version.as_deref(),
+            args.markers,
+            args.timeout_secs,
+            progress,
+        )
+    }
+}
+
+/// Arguments of `explain-error`
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ExplainErrorArgs {
+    /// Raw cargo build/check output or a panic message
+    pub output: String,
+    /// Render explanations as text (default) or JSON
+    #[serde(default)]
+    pub format: OutputFormat,
+}
+
+pub struct ExplainError;
+
+impl McpTool for ExplainError {
+    const NAME: &'static str = "explain-error";
+    const TITLE: &'static str = "Explain Compiler Error";
+    const DESCRIPTION: &'static str = "Explain Leptos-specific errors in raw cargo build output or browser panic messages (IntoView not implemented, FnOnce closures in view!, hydration panics, ServerFnError conversions, ...) with a fix sketch and the relevant doc section";
+    type Args = ExplainErrorArgs;
+    type Output = Explanations;
+
+    fn call(
+        tools: &LeptosTools,
+        args: ExplainErrorArgs,
+        _: &Progress,
+    ) -> Result<ToolOutput<Self::Output>, String> {
+        tools.explain_error(&args.output, args.format)
+    }
+}
+
+/// Arguments of `format-view`
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct FormatViewArgs {
+    /// Rust code containing view! macros
+    pub code: String,
+    /// Maximum line width
+    #[serde(default = "default_max_width")]
+    pub max_width: usize,
+    /// Spaces per indentation level
+    #[serde(default = "default_tab_spaces")]
+    pub tab_spaces: usize,
+    /// Return a unified diff instead of the formatted code
+    #[serde(default)]
+    pub diff: bool,
+}
+
+fn default_max_width() -> usize {
+    FormatOptions::default().max_width
+}
+
+fn default_tab_spaces() -> usize {
+    FormatOptions::default().tab_spaces
+}
+
+pub struct FormatView;
+
+impl McpTool for FormatView {
+    const NAME: &'static str = "format-view";
+    const TITLE: &'static str = "Format view! Macros";
+    const DESCRIPTION: &'static str = "Format the view! macros in Rust code like leptosfmt: one element per line, long tags wrapped one attribute per line, short elements kept inline. Returns the formatted code or a unified diff";
+    type Args = FormatViewArgs;
+    type Output = Formatted;
+
+    fn call(
+        tools: &LeptosTools,
+        args: FormatViewArgs,
+        _: &Progress,
+    ) -> Result<ToolOutput<Self::Output>, String> {
+        let options = FormatOptions {
+            max_width: args.max_width,
+            tab_spaces: args.tab_spaces,
+        };
+        tools.format_view(&args.code, options, args.diff)
+    }
+}
+
+/// Arguments of `suggest-imports`
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SuggestImportsArgs {
+    /// Rust code, with or without its existing use statements
+    pub code: String,
+    /// Leptos version (0.6, 0.7, 0.8). Defaults to the session version
+    pub version: Option<String>,
+}
+
+pub struct SuggestImports;
+
+impl McpTool for SuggestImports {
+    const NAME: &'static str = "suggest-imports";
+    const TITLE: &'static str = "Suggest Imports";
+    const DESCRIPTION: &'static str = "Find the Leptos, leptos_router, leptos_meta and server integration names a snippet uses without importing them (Resource, ActionForm, Title, use_navigate, ...) and return the use statements for the target Leptos version: the prelude glob plus companion-crate items by name";
+    type Args = SuggestImportsArgs;
+    type Output = ImportSuggestions;
+
+    fn call(
+        tools: &LeptosTools,
+        args: SuggestImportsArgs,
+        _: &Progress,
+    ) -> Result<ToolOutput<Self::Output>, String> {
+        tools.suggest_imports(&args.code, args.version.as_deref())
+    }
+}
+
+/// Arguments of `sync-docs`
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SyncDocsArgs {
+    /// Download every page again instead of revalidating the cache
+    #[serde(default)]
+    pub force: bool,
+}
+
+pub struct SyncDocs;
+
+impl McpTool for SyncDocs {
+    const NAME: &'static str = "sync-docs";
+    const TITLE: &'static str = "Sync Leptos Book";
+    const DESCRIPTION: &'static str = "Download the latest Leptos book from GitHub and merge it over the bundled documentation sections for the rest of the session. Pages are cached and revalidated with ETags";
+    type Args = SyncDocsArgs;
+    type Output = SyncSummary;
+    const SUBPROCESS: bool = true;
+    // Downloads into the cache and replaces the served sections
+    const READ_ONLY: bool = false;
+    const OPEN_WORLD: bool = true;
+
+    fn call(
+        tools: &LeptosTools,
+        args: SyncDocsArgs,
+        progress: &Progress,
+    ) -> Result<ToolOutput<Self::Output>, String> {
+        tools.sync_docs(args.force, progress)
+    }
+}
+
+pub struct ServerStats;
+
+impl McpTool for ServerStats {
+    const NAME: &'static str = "server-stats";
+    const TITLE: &'static str = "Server Statistics";
+    const DESCRIPTION: &'static str = "Request counts, error rates and latencies per tool and method since the server started, shared by every session. Shows which tools are used and which are slow";
+    type Args = NoArgs;
+    type Output = Statistics;
+    // Counts keep growing between calls
+    const IDEMPOTENT: bool = false;
+
+    fn call(
+        tools: &LeptosTools,
+        _: NoArgs,
+        _: &Progress,
+    ) -> Result<ToolOutput<Self::Output>, String> {
+        Ok(tools.server_stats())
+    }
+}
@@ -0,0 +1,181 @@
+//! Cross-references between documentation sections
+//!
+//! Two sections are related when one links to the other or when they share
+//! distinctive keywords: API identifiers from their text and code (`Resource`,
+//! `ServerFnError`) and the use cases their metadata lists. Keywords are
+//! weighted by how few sections mention them, so `view!` relates nothing
+//! while `Suspense` ties Resources to Suspense. `get-documentation` ends every
+//! section with its strongest neighbours.
+
+use crate::docs::{self, DocSection, LeptosVersion};
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+/// Neighbours listed per section
+pub const LIMIT: usize = 3;
+
+/// Weight of an explicit link, worth several shared keywords
+const LINK_WEIGHT: f64 = 4.0;
+
+/// Shared-keyword weight below which sections aren't considered related
+const MIN_SCORE: f64 = 3.0;
+
+/// Keywords reported per neighbour
+const REASONS: usize = 3;
+
+/// A section worth reading after another
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct Related {
+    pub title: &'static str,
+    pub path: &'static str,
+    /// One section links to the other
+    pub linked: bool,
+    /// Most distinctive keywords the sections share
+    pub keywords: Vec<String>,
+    pub score: f64,
+}
+
+/// Sections of `version` related to the one at `path`, strongest first
+///
+/// The graph is rebuilt per call so synced book pages count; at the size of
+/// the docs that costs well under a millisecond.
+pub fn related(path: &str, version: LeptosVersion) -> Vec<Related> {
+    let sections = docs::list_sections(version);
+    let Some(own) = sections.iter().position(|s| s.path == path) else {
+        return Vec::new();
+    };
+    let keywords: Vec<HashSet<String>> = sections.iter().map(keywords).collect();
+    let links: Vec<HashSet<&str>> = sections.iter().map(|s| links(s, &sections)).collect();
+
+    // Inverse document frequency of each keyword
+    let mut frequency: HashMap<&str, usize> = HashMap::new();
+    for keyword in keywords.iter().flatten() {
+        *frequency.entry(keyword.as_str()).or_default() += 1;
+    }
+    let total = sections.len() as f64;
+    let weight = |keyword: &str| (total / frequency[keyword] as f64).ln();
+
+    let mut related: Vec<Related> = sections
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i != own)
+        .filter_map(|(i, other)| {
+            let linked = links[own].contains(other.path) || links[i].contains(path);
+            let mut shared: Vec<(&str, f64)> = keywords[own]
+                .intersection(&keywords[i])
+                .map(|k| (k.as_str(), weight(k)))
+                .filter(|(_, w)| *w > 0.0)
+                .collect();
+            shared.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(b.0)));
+            let shared_score: f64 = shared.iter().map(|(_, w)| w).sum();
+            if !linked && shared_score < MIN_SCORE {
+                return None;
+            }
+            Some(Related {
+                title: other.title,
+                path: other.path,
+                linked,
+                keywords: shared
+                    .iter()
+                    .take(REASONS)
+                    .map(|(k, _)| k.to_string())
+                    .collect(),
+                score: shared_score + if linked { LINK_WEIGHT } else { 0.0 },
+            })
+        })
+        .collect();
+    related.sort_by(|a, b| b.score.total_cmp(&a.score));
+    related.truncate(LIMIT);
+    related
+}
+
+/// Footer naming the related sections by path, empty when there are none
+pub fn footer(related: &[Related]) -> String {
+    if related.is_empty() {
+        return String::new();
+    }
+    let paths: Vec<&str> = related.iter().map(|r| r.path).collect();
+    format!("\n\n---\nRelated: {}", paths.join(", "))
+}
+
+/// Code-like identifiers of a section plus its use cases, lowercased
+fn keywords(section: &DocSection) -> HashSet<String> {
+    // Capitalized words count in code, where they are types, not sentence starts
+    let in_code = code(&section.content)
+        .into_iter()
+        .flat_map(words)
+        .filter(|word| word.starts_with(|c: char| c.is_ascii_uppercase()));
+    let identifiers = words(&section.content)
+        .filter(|word| is_identifier(word))
+        .chain(in_code)
+        .map(str::to_lowercase);
+    let use_cases = section
+        .use_cases
+        .split(',')
+        .map(|use_case| use_case.trim().to_lowercase())
+        // Marks sections every app needs, not a use case
+        .filter(|use_case| !use_case.is_empty() && use_case != "always");
+    identifiers.chain(use_cases).collect()
+}
+
+fn words(text: &str) -> impl Iterator<Item = &str> {
+    text.split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|word| word.len() > 3)
+}
+
+/// `CamelCase` or `snake_case`, as opposed to prose
+fn is_identifier(word: &str) -> bool {
+    let mut chars = word.chars();
+    let first = chars.next().is_some_and(|c| c.is_ascii_uppercase());
+    let rest: Vec<char> = chars.collect();
+    (first
+        && rest.iter().any(char::is_ascii_lowercase)
+        && rest.iter().any(char::is_ascii_uppercase))
+        || (word.contains('_') && !word.starts_with('_') && !word.ends_with('_'))
+}
+
+/// Inline code spans and fenced code of markdown, without comments and string literals
+fn code(markdown: &str) -> Vec<&str> {
+    markdown
+        .split("```")
+        .enumerate()
+        .flat_map(|(i, part)| -> Vec<&str> {
+            if i % 2 == 1 {
+                part.lines()
+                    .map(|line| line.split("//").next().unwrap_or_default())
+                    .flat_map(|line| line.split('"').step_by(2))
+                    .collect()
+            } else {
+                part.split('`').skip(1).step_by(2).collect()
+            }
+        })
+        .collect()
+}
+
+/// Paths of the sections `section` links to with markdown links
+///
+/// Targets may be section paths (`resources#loading`) or docs files relative
+/// to the page (`../resources.md`); external URLs are skipped.
+fn links(section: &DocSection, sections: &[DocSection]) -> HashSet<&'static str> {
+    section
+        .content
+        .split("](")
+        .skip(1)
+        .filter_map(|rest| rest.split(')').next())
+        .filter(|target| !target.contains("://") && !target.starts_with("mailto:"))
+        .filter_map(|target| {
+            let target = target.split(['#', ' ']).next()?;
+            let target = target.trim_start_matches("./").trim_start_matches("../");
+            let target = target.trim_end_matches(".md").trim_end_matches('/');
+            if target.is_empty() {
+                return None;
+            }
+            sections
+                .iter()
+                .find(|s| s.path == target || s.path.ends_with(&format!("/{}", target)))
+                .map(|s| s.path)
+        })
+        .filter(|path| *path != section.path)
+        .collect()
+}
@@ -0,0 +1,532 @@
+//! Diff review
+//!
+//! Reads the unified diff of a change, as `git diff` prints it, rebuilds each
+//! changed file before and after the change, and sorts the diagnostics found
+//! in the changed regions (the diff's hunks) into those the change introduced
+//! and those it only touched. A review bot can then comment on what a pull
+//! request adds without repeating every finding of the code around it.
+
+use crate::rules::Diagnostic;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+/// Changes to one file
+#[derive(Debug, Clone, Default)]
+pub struct FileDiff {
+    /// Path before the change, `None` for an added file
+    pub old_path: Option<String>,
+    /// Path after the change, `None` for a deleted file
+    pub new_path: Option<String>,
+    pub hunks: Vec<Hunk>,
+}
+
+/// One `@@` section of a file's diff
+#[derive(Debug, Clone)]
+pub struct Hunk {
+    pub old_start: usize,
+    pub old_len: usize,
+    pub new_start: usize,
+    pub new_len: usize,
+    /// Lines with their marker: `' '` for context, `'-'` removed, `'+'` added
+    pub lines: Vec<(char, String)>,
+}
+
+impl Hunk {
+    /// First line of the hunk before the change; an empty side's start
+    /// names the line before it
+    fn old_first(&self) -> usize {
+        if self.old_len == 0 {
+            self.old_start + 1
+        } else {
+            self.old_start
+        }
+    }
+
+    fn new_first(&self) -> usize {
+        if self.new_len == 0 {
+            self.new_start + 1
+        } else {
+            self.new_start
+        }
+    }
+}
+
+/// Diagnostics of one changed file, sorted by what the change did to them
+#[derive(Debug, Clone, Default, Serialize, JsonSchema)]
+pub struct FileReview {
+    pub path: String,
+    /// Found in the changed regions after the change and not before it
+    pub introduced: Vec<Diagnostic>,
+    /// Found in the changed regions both before and after the change
+    pub pre_existing: Vec<Diagnostic>,
+    /// Found in the changed regions before the change and gone after it,
+    /// with the lines they had before
+    pub resolved: Vec<Diagnostic>,
+}
+
+/// Parse a unified diff of one or more files
+///
+/// Paths lose git's `a/` and `b/` prefixes. Lines outside hunks (`diff --git`,
+/// `index`, mode changes) are skipped, so files without hunks, like pure
+/// renames, have none.
+pub fn parse(diff: &str) -> Result<Vec<FileDiff>, String> {
+    let mut files: Vec<FileDiff> = Vec::new();
+    let mut lines = diff.lines().peekable();
+    while let Some(line) = lines.next() {
+        if let Some(old) = line.strip_prefix("--- ") {
+            let Some(new) = lines.next_if(|l| l.starts_with("+++ ")) else {
+                continue;
+            };
+            files.push(FileDiff {
+                old_path: diff_path(old, "a/"),
+                new_path: diff_path(&new["+++ ".len()..], "b/"),
+                hunks: Vec::new(),
+            });
+            continue;
+        }
+        let Some(header) = line.strip_prefix("@@ -") else {
+            continue;
+        };
+        let Some(file) = files.last_mut() else {
+            return Err(format!(
+                "Hunk '{}' comes before any '---'/'+++' file header",
+                line
+            ));
+        };
+        let mut hunk =
+            hunk_header(header).ok_or_else(|| format!("Malformed hunk header '{}'", line))?;
+        let (mut old_left, mut new_left) = (hunk.old_len, hunk.new_len);
+        while old_left > 0 || new_left > 0 {
+            let Some(body) = lines.next() else {
+                return Err(format!(
+                    "Hunk '{}' of {} ends early",
+                    line,
+                    file.new_path
+                        .as_deref()
+                        .or(file.old_path.as_deref())
+                        .unwrap_or_default()
+                ));
+            };
+            if body.starts_with('\\') {
+                // "\ No newline at end of file"
+                continue;
+            }
+            // Some tools strip the space from empty context lines
+            let (marker, text) = match body.chars().next() {
+                Some(marker @ (' ' | '-' | '+')) => (marker, &body[1..]),
+                None => (' ', ""),
+                Some(_) => return Err(format!("Unexpected line '{}' in a hunk", body)),
+            };
+            if marker != '+' {
+                old_left = old_left
+                    .checked_sub(1)
+                    .ok_or("A hunk has more lines than its header says")?;
+            }
+            if marker != '-' {
+                new_left = new_left
+                    .checked_sub(1)
+                    .ok_or("A hunk has more lines than its header says")?;
+            }
+            hunk.lines.push((marker, text.to_string()));
+        }
+        file.hunks.push(hunk);
+    }
+    Ok(files)
+}
+
+/// A header path without its tab-separated timestamp and git prefix
+fn diff_path(path: &str, prefix: &str) -> Option<String> {
+    let path = path.split('\t').next().unwrap_or(path).trim();
+    if path == "/dev/null" {
+        return None;
+    }
+    Some(path.strip_prefix(prefix).unwrap_or(path).to_string())
+}
+
+/// `-12,7 +12,9 @@ ...` after the leading `@@ -`
+fn hunk_header(header: &str) -> Option<Hunk> {
+    let (ranges, _) = header.split_once(" @@")?;
+    let (old, new) = ranges.split_once(" +")?;
+    let range = |range: &str| -> Option<(usize, usize)> {
+        match range.split_once(',') {
+            Some((start, len)) => Some((start.parse().ok()?, len.parse().ok()?)),
+            None => Some((range.parse().ok()?, 1)),
+        }
+    };
+    let (old_start, old_len) = range(old)?;
+    let (new_start, new_len) = range(new)?;
+    Some(Hunk {
+        old_start,
+        old_len,
+        new_start,
+        new_len,
+        lines: Vec::new(),
+    })
+}
+
+impl FileDiff {
+    /// The path after the change, or before it for a deleted file
+    pub fn path(&self) -> &str {
+        self.new_path
+            .as_deref()
+            .or(self.old_path.as_deref())
+            .unwrap_or_default()
+    }
+
+    /// The file after the change, from its content before it; `None` if the
+    /// hunks don't match `old`
+    pub fn apply(&self, old: &str) -> Option<String> {
+        self.patch(old, true)
+    }
+
+    /// The file before the change, from its content after it; `None` if the
+    /// hunks don't match `new`
+    pub fn revert(&self, new: &str) -> Option<String> {
+        self.patch(new, false)
+    }
+
+    fn patch(&self, text: &str, forward: bool) -> Option<String> {
+        let (removed, added) = if forward { ('-', '+') } else { ('+', '-') };
+        let lines: Vec<&str> = text.lines().collect();
+        let mut out: Vec<&str> = Vec::new();
+        let mut at = 0;
+        for hunk in &self.hunks {
+            let first = if forward {
+                hunk.old_first()
+            } else {
+                hunk.new_first()
+            };
+            let start = first.checked_sub(1)?;
+            if start < at || start > lines.len() {
+                return None;
+            }
+            out.extend(&lines[at..start]);
+            let mut i = start;
+            for (marker, line) in &hunk.lines {
+                if *marker == added {
+                    out.push(line.as_str());
+                    continue;
+                }
+                // Trailing whitespace may not survive a diff pasted into a message
+                if lines.get(i).map(|l| l.trim_end()) != Some(line.trim_end()) {
+                    return None;
+                }
+                if *marker != removed {
+                    out.push(lines[i]);
+                }
+                i += 1;
+            }
+            at = i;
+        }
+        out.extend(&lines[at..]);
+        let mut patched = out.join("\n");
+        if !out.is_empty() && (text.is_empty() || text.ends_with('\n')) {
+            patched.push('\n');
+        }
+        Some(patched)
+    }
+
+    /// The line before the change of a line after it, if the change kept it
+    pub fn old_line(&self, new_line: usize) -> Option<usize> {
+        let mut delta = 0isize;
+        for hunk in &self.hunks {
+            if new_line < hunk.new_first() {
+                break;
+            }
+            if new_line < hunk.new_first() + hunk.new_len {
+                let (mut old, mut new) = (hunk.old_first(), hunk.new_first());
+                for (marker, _) in &hunk.lines {
+                    match marker {
+                        '-' => old += 1,
+                        '+' if new == new_line => return None,
+                        '+' => new += 1,
+                        _ if new == new_line => return Some(old),
+                        _ => {
+                            old += 1;
+                            new += 1;
+                        }
+                    }
+                }
+                return None;
+            }
+            delta = (hunk.old_first() + hunk.old_len) as isize
+                - (hunk.new_first() + hunk.new_len) as isize;
+        }
+        new_line.checked_add_signed(delta)
+    }
+
+    /// Whether the change replaced the line `old` before it with the line
+    /// `new` after it: both are in one run of removed and added lines
+    pub fn replaced(&self, old: usize, new: usize) -> bool {
+        self.hunks.iter().any(|hunk| {
+            let (mut old_at, mut new_at) = (hunk.old_first(), hunk.new_first());
+            let (mut removed, mut added) = (old_at..old_at, new_at..new_at);
+            for (marker, _) in &hunk.lines {
+                match marker {
+                    '-' => old_at += 1,
+                    '+' => new_at += 1,
+                    _ => {
+                        if removed.contains(&old) && added.contains(&new) {
+                            return true;
+                        }
+                        old_at += 1;
+                        new_at += 1;
+                        (removed, added) = (old_at..old_at, new_at..new_at);
+                        continue;
+                    }
+                }
+                (removed.end, added.end) = (old_at, new_at);
+            }
+            removed.contains(&old) && added.contains(&new)
+        })
+    }
+
+    /// Whether a line after the change is in one of its hunks; findings
+    /// without a line belong to the whole file
+    pub fn in_new(&self, line: Option<usize>) -> bool {
+        line.is_none_or(|line| {
+            self.hunks
+                .iter()
+                .any(|h| (h.new_first()..h.new_first() + h.new_len).contains(&line))
+        })
+    }
+
+    /// Whether a line before the change is in one of its hunks
+    pub fn in_old(&self, line: Option<usize>) -> bool {
+        line.is_none_or(|line| {
+            self.hunks
+                .iter()
+                .any(|h| (h.old_first()..h.old_first() + h.old_len).contains(&line))
+        })
+    }
+}
+
+/// Sort the diagnostics of a file before (`old`) and after (`new`) the
+/// change in `diff`, reported under `path`
+///
+/// A finding after the change is pre-existing if the same rule reported the
+/// same message before it: on the line the change kept, else on a line with
+/// the same text (moved code), else on a line in place of a removed one (an
+/// edited line). Only findings in the changed regions are kept.
+pub fn review(
+    path: String,
+    diff: &FileDiff,
+    old_code: &str,
+    new_code: &str,
+    old: Vec<Diagnostic>,
+    new: Vec<Diagnostic>,
+) -> FileReview {
+    let old_lines: Vec<&str> = old_code.lines().collect();
+    let new_lines: Vec<&str> = new_code.lines().collect();
+    let text = |lines: &[&str], line: Option<usize>| {
+        line.and_then(|l| lines.get(l.checked_sub(1)?))
+            .map(|l| l.trim().to_string())
+    };
+    let kept = |n: &Diagnostic, o: &Diagnostic| match n.line {
+        Some(line) => o.line.is_some() && diff.old_line(line) == o.line,
+        None => o.line.is_none(),
+    };
+    let moved =
+        |n: &Diagnostic, o: &Diagnostic| text(&new_lines, n.line) == text(&old_lines, o.line);
+    let edited = |n: &Diagnostic, o: &Diagnostic| match (o.line, n.line) {
+        (Some(old), Some(new)) => diff.replaced(old, new),
+        _ => false,
+    };
+    let passes = [
+        &kept as &dyn Fn(&Diagnostic, &Diagnostic) -> bool,
+        &moved,
+        &edited,
+    ];
+
+    let mut matched = vec![false; new.len()];
+    let mut used = vec![false; old.len()];
+    for pass in passes {
+        for (i, n) in new.iter().enumerate() {
+            if matched[i] {
+                continue;
+            }
+            let same = (0..old.len()).find(|&j| {
+                !used[j]
+                    && old[j].rule_id == n.rule_id
+                    && old[j].message == n.message
+                    && pass(n, &old[j])
+            });
+            if let Some(j) = same {
+                matched[i] = true;
+                used[j] = true;
+            }
+        }
+    }
+
+    let mut review = FileReview {
+        path,
+        ..FileReview::default()
+    };
+    for (diagnostic, matched) in new.into_iter().zip(matched) {
+        if !diff.in_new(diagnostic.line) {
+            continue;
+        }
+        if matched {
+            review.pre_existing.push(diagnostic);
+        } else {
+            review.introduced.push(diagnostic);
+        }
+    }
+    review.resolved = old
+        .into_iter()
+        .zip(used)
+        .filter(|(diagnostic, used)| !used && diff.in_old(diagnostic.line))
+        .map(|(diagnostic, _)| diagnostic)
+        .collect();
+    review
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::Severity;
+
+    const OLD: &str = "fn main() {\n    let a = 1;\n    let b = 2;\n    let c = 3;\n}\n";
+    const NEW: &str =
+        "fn main() {\n    let a = 1;\n    let b = 20;\n    let d = 4;\n    let c = 3;\n}\n";
+    const DIFF: &str = "\
+diff --git a/src/main.rs b/src/main.rs
+index 1111111..2222222 100644
+--- a/src/main.rs
++++ b/src/main.rs
+@@ -2,3 +2,4 @@ fn main() {
+     let a = 1;
+-    let b = 2;
++    let b = 20;
++    let d = 4;
+     let c = 3;
+";
+
+    fn diagnostic(rule_id: &'static str, message: &str, line: usize) -> Diagnostic {
+        Diagnostic {
+            rule_id,
+            severity: Severity::Warning,
+            message: message.to_string(),
+            line: Some(line),
+            column: None,
+            fix: None,
+            doc: None,
+            example: None,
+            id: None,
+            edit: None,
+        }
+    }
+
+    fn parsed() -> FileDiff {
+        parse(DIFF).unwrap().remove(0)
+    }
+
+    #[test]
+    fn parses_headers_and_hunks() {
+        let files = parse(DIFF).unwrap();
+        assert_eq!(files.len(), 1);
+        let file = &files[0];
+        assert_eq!(file.old_path.as_deref(), Some("src/main.rs"));
+        assert_eq!(file.path(), "src/main.rs");
+        let hunk = &file.hunks[0];
+        assert_eq!(
+            (hunk.old_start, hunk.old_len, hunk.new_start, hunk.new_len),
+            (2, 3, 2, 4)
+        );
+        let markers: String = hunk.lines.iter().map(|(m, _)| *m).collect();
+        assert_eq!(markers, " -++ ");
+    }
+
+    #[test]
+    fn parses_added_and_deleted_files() {
+        let diff = "--- /dev/null\n+++ b/new.rs\n@@ -0,0 +1,2 @@\n+a\n+b\n\\ No newline at end of file\n--- a/old.rs\n+++ /dev/null\n@@ -1 +0,0 @@\n-x\n";
+        let files = parse(diff).unwrap();
+        assert_eq!(files[0].old_path, None);
+        assert_eq!(files[0].path(), "new.rs");
+        assert_eq!(files[0].apply("").as_deref(), Some("a\nb\n"));
+        assert_eq!(files[1].new_path, None);
+        assert_eq!(files[1].path(), "old.rs");
+        assert_eq!(files[1].apply("x\n").as_deref(), Some(""));
+    }
+
+    #[test]
+    fn rejects_malformed_diffs() {
+        assert!(parse("@@ -1 +1 @@\n-a\n+b\n").is_err());
+        assert!(parse("--- a/x\n+++ b/x\n@@ -1,2 +1,2 @@\n-a\n").is_err());
+        assert!(parse("--- a/x\n+++ b/x\n@@ -1 +1 @@\n*a\n").is_err());
+        assert!(parse("--- a/x\n+++ b/x\n@@ -x +1 @@\n").is_err());
+        assert!(parse("not a diff").unwrap().is_empty());
+    }
+
+    #[test]
+    fn applies_and_reverts() {
+        let diff = parsed();
+        assert_eq!(diff.apply(OLD).as_deref(), Some(NEW));
+        assert_eq!(diff.revert(NEW).as_deref(), Some(OLD));
+        // The diff doesn't describe these files
+        assert_eq!(diff.apply(NEW), None);
+        assert_eq!(diff.revert(OLD), None);
+    }
+
+    #[test]
+    fn maps_lines_across_the_change() {
+        let diff = parsed();
+        assert_eq!(diff.old_line(1), Some(1));
+        assert_eq!(diff.old_line(2), Some(2));
+        // Added lines have no line before the change
+        assert_eq!(diff.old_line(3), None);
+        assert_eq!(diff.old_line(4), None);
+        assert_eq!(diff.old_line(5), Some(4));
+        assert_eq!(diff.old_line(6), Some(5));
+        assert!(diff.replaced(3, 3));
+        assert!(diff.replaced(3, 4));
+        assert!(!diff.replaced(2, 3));
+        assert!(diff.in_new(Some(4)) && !diff.in_new(Some(1)) && diff.in_new(None));
+        assert!(diff.in_old(Some(3)) && !diff.in_old(Some(5)));
+    }
+
+    #[test]
+    fn classifies_findings() {
+        let diff = parsed();
+        let old = vec![
+            diagnostic("L0001", "kept", 2),
+            diagnostic("L0002", "edited", 3),
+            diagnostic("L0003", "fixed", 3),
+            diagnostic("L0004", "outside", 1),
+        ];
+        let new = vec![
+            diagnostic("L0001", "kept", 2),
+            diagnostic("L0002", "edited", 3),
+            diagnostic("L0005", "added", 4),
+            diagnostic("L0004", "outside", 1),
+        ];
+        let review = review("src/main.rs".to_string(), &diff, OLD, NEW, old, new);
+        let ids = |diagnostics: &[Diagnostic]| -> Vec<&str> {
+            diagnostics.iter().map(|d| d.rule_id).collect()
+        };
+        assert_eq!(ids(&review.introduced), ["L0005"]);
+        assert_eq!(ids(&review.pre_existing), ["L0001", "L0002"]);
+        assert_eq!(ids(&review.resolved), ["L0003"]);
+    }
+
+    #[test]
+    fn moved_code_is_pre_existing() {
+        let old_code = "a\nmove_me\nb\n";
+        let new_code = "move_me\na\nb\n";
+        let diff = parse("--- a/x\n+++ b/x\n@@ -1,2 +1,2 @@\n+move_me\n a\n-move_me\n")
+            .unwrap()
+            .remove(0);
+        assert_eq!(diff.apply(old_code).as_deref(), Some(new_code));
+        let review = review(
+            "x".to_string(),
+            &diff,
+            old_code,
+            new_code,
+            vec![diagnostic("L0001", "m", 2)],
+            vec![diagnostic("L0001", "m", 1)],
+        );
+        assert!(review.introduced.is_empty());
+        assert_eq!(review.pre_existing.len(), 1);
+        assert!(review.resolved.is_empty());
+    }
+}
@@ -0,0 +1,473 @@
+//! Route map extraction
+//!
+//! Rebuilds a project's route tree from its `view!` markup: every `<Routes>`
+//! (or `<FlatRoutes>`) with the `<Route>`s nested in it through
+//! `<ParentRoute>`s, or through `<Route>`s with children on 0.6. Route
+//! fragments declared in their own component (`<AdminRoutes/>` inside
+//! `<Routes>`) are spliced in where they are used. Each route gets its full
+//! path, the params it can read and the component rendering it.
+
+use crate::project::SourceFile;
+use crate::rules::{normalize_route, position, route_path, LEAF_ROUTES, PARENT_ROUTES};
+use crate::source::functions;
+use crate::view::{parse_views, Element, Node};
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Elements holding a router's route definitions
+const ROUTE_TABLES: &[&str] = &["Routes", "FlatRoutes"];
+
+/// Fragments nested deeper than this are assumed to include themselves
+const MAX_FRAGMENT_DEPTH: usize = 8;
+
+/// Where something is declared
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct Location {
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub file: String,
+    pub line: usize,
+}
+
+impl Location {
+    fn new(file: &SourceFile, offset: usize) -> Self {
+        Self {
+            file: file.path.display().to_string(),
+            line: position(&file.content, offset).0,
+        }
+    }
+}
+
+impl std::fmt::Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.file.as_str() {
+            "" => write!(f, "line {}", self.line),
+            file => write!(f, "{}:{}", file, self.line),
+        }
+    }
+}
+
+/// One route of the tree
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct Route {
+    /// Full path, including the paths of enclosing routes
+    pub path: String,
+    /// The route's own path as declared, `""` for an empty path
+    pub segment: String,
+    /// `Route`, `ParentRoute`, `ProtectedRoute` or `ProtectedParentRoute`
+    pub element: String,
+    /// Every param the route can read, its parents' included
+    pub params: Vec<String>,
+    /// Component rendering the route, if its `view` names one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub component: Option<String>,
+    /// Where that component is defined, if it is in the sources
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub defined_at: Option<Location>,
+    /// The `view` attribute, verbatim
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub view: Option<String>,
+    /// `ssr` mode, e.g. `SsrMode::Async`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ssr: Option<String>,
+    /// For routes with children: whether the view renders an `<Outlet/>`,
+    /// `None` when its code isn't in the sources
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outlet: Option<bool>,
+    pub declared_at: Location,
+    pub children: Vec<Route>,
+}
+
+/// A `<Routes>` element and the route tree inside it
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct RouteTable {
+    /// `Routes`, `FlatRoutes`, or the fragment component when the sources
+    /// only declare a fragment
+    pub element: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fallback: Option<String>,
+    pub declared_at: Location,
+    pub routes: Vec<Route>,
+}
+
+/// The route trees of a set of source files
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct RouteMap {
+    pub tables: Vec<RouteTable>,
+    /// Child routes that can never show, and fragments that couldn't be found
+    pub issues: Vec<String>,
+}
+
+impl RouteMap {
+    /// Every route, depth first
+    pub fn routes(&self) -> Vec<&Route> {
+        fn walk<'a>(routes: &'a [Route], out: &mut Vec<&'a Route>) {
+            for route in routes {
+                out.push(route);
+                walk(&route.children, out);
+            }
+        }
+        let mut out = Vec::new();
+        for table in &self.tables {
+            walk(&table.routes, &mut out);
+        }
+        out
+    }
+}
+
+/// A route declaration before fragments are spliced in and paths joined
+#[derive(Debug, Clone)]
+enum Item {
+    Route {
+        element: String,
+        path: Option<String>,
+        path_expr: String,
+        view: Option<String>,
+        ssr: Option<String>,
+        at: Location,
+        children: Vec<Item>,
+    },
+    /// A component used among routes: a fragment declaring more of them
+    Include { name: String, at: Location },
+}
+
+struct Table {
+    element: String,
+    fallback: Option<String>,
+    at: Location,
+    items: Vec<Item>,
+}
+
+enum Frame {
+    Table(Table),
+    Route(Item),
+}
+
+/// Last segment of a possibly qualified name: `Route` for `components::Route`
+fn base_name(name: &str) -> &str {
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+fn is_route(name: &str) -> bool {
+    LEAF_ROUTES.contains(&name) || PARENT_ROUTES.contains(&name)
+}
+
+fn route_item(element: &Element, file: &SourceFile) -> Item {
+    let expr = |name: &str| element.attr(name).and_then(|a| a.expr()).map(String::from);
+    let path_expr = expr("path").unwrap_or_default();
+    Item::Route {
+        element: base_name(&element.name).to_string(),
+        path: route_path(&path_expr),
+        path_expr,
+        view: expr("view"),
+        ssr: expr("ssr"),
+        at: Location::new(file, element.offset),
+        children: Vec::new(),
+    }
+}
+
+/// Add a finished item to the innermost open table or route, or to the
+/// enclosing function's fragment when none is open
+fn attach(stack: &mut [Frame], item: Item, fragment: &mut Vec<Item>) {
+    match stack.last_mut() {
+        Some(Frame::Table(table)) => table.items.push(item),
+        Some(Frame::Route(Item::Route { children, .. })) => children.push(item),
+        _ => fragment.push(item),
+    }
+}
+
+/// Route tables and route fragments (by function name) declared in `file`
+fn scan(file: &SourceFile) -> (Vec<Table>, HashMap<String, Vec<Item>>) {
+    let code = file.content.as_str();
+    let items = functions(code);
+    let mut tables = Vec::new();
+    let mut fragments: HashMap<String, Vec<Item>> = HashMap::new();
+    for view in parse_views(code) {
+        let owner = items
+            .iter()
+            .filter(|f| f.body.contains(&view.start))
+            .min_by_key(|f| f.body.len())
+            .map_or_else(String::new, |f| f.name.clone());
+        let mut fragment = Vec::new();
+        let mut stack: Vec<Frame> = Vec::new();
+        for node in &view.nodes {
+            match node {
+                Node::Open(element) => {
+                    let name = base_name(&element.name);
+                    if ROUTE_TABLES.contains(&name) {
+                        let table = Table {
+                            element: name.to_string(),
+                            fallback: element
+                                .attr("fallback")
+                                .and_then(|a| a.expr())
+                                .map(String::from),
+                            at: Location::new(file, element.offset),
+                            items: Vec::new(),
+                        };
+                        match element.self_closing {
+                            true => tables.push(table),
+                            false => stack.push(Frame::Table(table)),
+                        }
+                    } else if is_route(name) {
+                        let item = route_item(element, file);
+                        match element.self_closing {
+                            true => attach(&mut stack, item, &mut fragment),
+                            false => stack.push(Frame::Route(item)),
+                        }
+                    } else if element.is_component() && !stack.is_empty() && name != "Router" {
+                        let item = Item::Include {
+                            name: name.to_string(),
+                            at: Location::new(file, element.offset),
+                        };
+                        attach(&mut stack, item, &mut fragment);
+                    }
+                }
+                Node::Close { name, .. } => {
+                    let name = base_name(name);
+                    let closes = match stack.last() {
+                        Some(Frame::Table(table)) => table.element == name,
+                        Some(Frame::Route(Item::Route { element, .. })) => element == name,
+                        _ => false,
+                    };
+                    if !closes {
+                        continue;
+                    }
+                    match stack.pop() {
+                        Some(Frame::Table(table)) => tables.push(table),
+                        Some(Frame::Route(item)) => attach(&mut stack, item, &mut fragment),
+                        None => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+        // Unclosed tags: keep what was found
+        while let Some(frame) = stack.pop() {
+            match frame {
+                Frame::Table(table) => tables.push(table),
+                Frame::Route(item) => attach(&mut stack, item, &mut fragment),
+            }
+        }
+        if !fragment.is_empty() {
+            fragments.entry(owner).or_default().extend(fragment);
+        }
+    }
+    (tables, fragments)
+}
+
+/// Names of the params in a path: `id` for `/:id`, `rest` for `/*rest`
+fn params(path: &str) -> Vec<String> {
+    path.split('/')
+        .filter_map(|segment| {
+            let name = segment
+                .strip_prefix(':')
+                .or_else(|| segment.strip_prefix('*'))?;
+            let name = name.trim_end_matches('?');
+            (!name.is_empty()).then(|| name.to_string())
+        })
+        .collect()
+}
+
+/// Whether a child path also matches its parent's own URL: an empty path,
+/// a wildcard or optional params only
+fn matches_empty(segment: &str) -> bool {
+    segment
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .all(|s| s.starts_with('*') || (s.starts_with(':') && s.ends_with('?')))
+}
+
+/// Component a `view` attribute renders: `HomePage` for `view=HomePage` or
+/// `view=|| view! { <HomePage/> }`
+fn view_component(view: &str) -> Option<String> {
+    if !view.is_empty()
+        && view
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == ':')
+    {
+        return Some(base_name(view).to_string());
+    }
+    parse_views(view).into_iter().find_map(|v| {
+        v.nodes.into_iter().find_map(|node| match node {
+            Node::Open(element) if element.is_component() && element.name != "Outlet" => {
+                Some(base_name(&element.name).to_string())
+            }
+            _ => None,
+        })
+    })
+}
+
+struct Resolver<'a> {
+    fragments: &'a HashMap<String, Vec<Item>>,
+    /// Component name to its definition and body source
+    components: &'a HashMap<String, (Location, String)>,
+    issues: Vec<String>,
+}
+
+impl Resolver<'_> {
+    fn resolve(
+        &mut self,
+        items: &[Item],
+        prefix: &str,
+        inherited: &[String],
+        depth: usize,
+    ) -> Vec<Route> {
+        let mut routes = Vec::new();
+        for item in items {
+            match item {
+                Item::Include { name, at } => match self.fragments.get(name) {
+                    Some(fragment) if depth < MAX_FRAGMENT_DEPTH => {
+                        let fragment = fragment.clone();
+                        routes.extend(self.resolve(&fragment, prefix, inherited, depth + 1));
+                    }
+                    Some(_) => {}
+                    None => self.issues.push(format!(
+                        "`<{}/>` among the routes at {} declares no routes in the given sources; its routes are missing from the map",
+                        name, at
+                    )),
+                },
+                Item::Route {
+                    element,
+                    path,
+                    path_expr,
+                    view,
+                    ssr,
+                    at,
+                    children,
+                } => {
+                    let segment = path
+                        .clone()
+                        .unwrap_or_else(|| format!("/{{{}}}", path_expr));
+                    let full = normalize_route(&format!("{}/{}", prefix, segment));
+                    let mut all_params = inherited.to_vec();
+                    all_params.extend(params(&segment));
+                    let component = view.as_deref().and_then(view_component);
+                    let definition = component.as_ref().and_then(|c| self.components.get(c));
+                    let children = self.resolve(children, &full, &all_params, depth);
+                    let outlet = match (&children[..], definition, view) {
+                        ([], _, _) => None,
+                        (_, _, Some(view)) if base_name(view) == "Outlet" => Some(true),
+                        (_, Some((_, body)), _) => Some(body.contains("<Outlet")),
+                        (_, None, Some(view)) if view.contains("view!") => {
+                            Some(view.contains("<Outlet"))
+                        }
+                        _ => None,
+                    };
+                    if outlet == Some(false) {
+                        self.issues.push(format!(
+                            "`{}` ({}) has child routes, but {} renders no `<Outlet/>`, so they never show",
+                            full,
+                            at,
+                            component
+                                .as_ref()
+                                .map_or_else(|| "its view".to_string(), |c| format!("`{}`", c))
+                        ));
+                    }
+                    let known: Vec<&str> = children
+                        .iter()
+                        .filter(|c| !c.segment.starts_with("/{"))
+                        .map(|c| c.segment.as_str())
+                        .collect();
+                    if !children.is_empty()
+                        && known.len() == children.len()
+                        && !known.iter().any(|s| matches_empty(s))
+                    {
+                        self.issues.push(format!(
+                            "`{}` ({}) has no child route with an empty path, so `{}` itself renders the fallback",
+                            full, at, full
+                        ));
+                    }
+                    routes.push(Route {
+                        path: full,
+                        segment,
+                        element: element.clone(),
+                        params: all_params,
+                        defined_at: definition.map(|(location, _)| location.clone()),
+                        component,
+                        view: view.clone(),
+                        ssr: ssr.clone(),
+                        outlet,
+                        declared_at: at.clone(),
+                        children,
+                    });
+                }
+            }
+        }
+        routes
+    }
+}
+
+/// Route trees declared in `files`
+pub fn extract(files: &[SourceFile]) -> RouteMap {
+    let mut components = HashMap::new();
+    let mut tables = Vec::new();
+    let mut fragments: HashMap<String, Vec<Item>> = HashMap::new();
+    for file in files {
+        for item in functions(&file.content)
+            .into_iter()
+            .filter(|f| f.is_component())
+        {
+            components.insert(
+                item.name.clone(),
+                (
+                    Location::new(file, item.params.start),
+                    file.content[item.body.clone()].to_string(),
+                ),
+            );
+        }
+        let (found, declared) = scan(file);
+        tables.extend(found);
+        for (name, items) in declared {
+            fragments.entry(name).or_default().extend(items);
+        }
+    }
+    // Sources declaring only fragments still have a route tree to show
+    if tables.is_empty() {
+        let mut names: Vec<&String> = fragments.keys().collect();
+        names.sort();
+        tables = names
+            .into_iter()
+            .map(|name| Table {
+                element: if name.is_empty() {
+                    "view!".to_string()
+                } else {
+                    name.clone()
+                },
+                fallback: None,
+                at: components.get(name).map_or(
+                    Location {
+                        file: String::new(),
+                        line: 1,
+                    },
+                    |(at, _)| at.clone(),
+                ),
+                items: vec![Item::Include {
+                    name: name.clone(),
+                    at: Location {
+                        file: String::new(),
+                        line: 1,
+                    },
+                }],
+            })
+            .collect();
+    }
+
+    let mut resolver = Resolver {
+        fragments: &fragments,
+        components: &components,
+        issues: Vec::new(),
+    };
+    let tables = tables
+        .into_iter()
+        .map(|table| RouteTable {
+            routes: resolver.resolve(&table.items, "", &[], 0),
+            element: table.element,
+            fallback: table.fallback,
+            declared_at: table.at,
+        })
+        .collect();
+    RouteMap {
+        tables,
+        issues: resolver.issues,
+    }
+}
@@ -0,0 +1,226 @@
+//! Accessibility rules for `view!` markup
+//!
+//! Checks screen reader and keyboard users depend on: text alternatives for
+//! images, keyboard access to clickable elements and accessible names for
+//! form controls and icon-like components. All are in the `a11y` category,
+//! so a project can turn them off together.
+
+use super::Finding;
+use crate::view::{parse_views, Element, Node, ViewMacro, VOID_ELEMENTS};
+use std::collections::HashSet;
+
+/// Elements the browser already makes focusable and keyboard-operable
+const INTERACTIVE_ELEMENTS: &[&str] = &[
+    "a", "button", "details", "input", "label", "option", "select", "summary", "textarea",
+];
+
+/// Attributes that give an element an accessible name
+const NAME_ATTRS: &[&str] = &["aria-label", "aria-labelledby", "title"];
+
+/// `<input type=...>` values that need no label
+const UNLABELLED_INPUT_TYPES: &[&str] = &["hidden", "submit", "button", "reset", "image"];
+
+/// Component name parts that suggest a widget needing an accessible name,
+/// with the attributes that give it one
+const WIDGET_COMPONENTS: &[(&str, &str)] = &[
+    ("IconButton", "attr:aria-label=\"Close\""),
+    // Decorative icons are hidden instead
+    (
+        "Icon",
+        "attr:aria-label=\"...\"  or  attr:aria-hidden=\"true\"",
+    ),
+    ("Modal", "attr:aria-labelledby=\"dialog-title\""),
+    ("Dialog", "attr:aria-labelledby=\"dialog-title\""),
+    (
+        "Toggle",
+        "attr:aria-label=\"...\" attr:aria-pressed=move || on.get()",
+    ),
+    (
+        "Switch",
+        "attr:aria-label=\"...\" attr:aria-checked=move || on.get()",
+    ),
+];
+
+/// Run every accessibility rule over the `view!` invocations in `code`
+pub fn check(code: &str) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    for view in parse_views(code) {
+        let labelled = labelled_ids(&view);
+        let mut open: Vec<&str> = Vec::new();
+        for node in &view.nodes {
+            match node {
+                Node::Open(element) => {
+                    check_img_alt(code, element, &mut findings);
+                    check_click(code, element, &mut findings);
+                    let in_label = open.contains(&"label");
+                    check_label(code, element, in_label, &labelled, &mut findings);
+                    check_component_name(code, element, &mut findings);
+                    if !element.self_closing && !VOID_ELEMENTS.contains(&element.name.as_str()) {
+                        open.push(&element.name);
+                    }
+                }
+                Node::Close { name, .. } => {
+                    if let Some(idx) = open.iter().rposition(|n| n == name) {
+                        open.truncate(idx);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    findings
+}
+
+/// L0500 `<img>` without `alt`
+fn check_img_alt(code: &str, element: &Element, findings: &mut Vec<Finding>) {
+    if element.name != "img" || has_any(element, &["alt", "attr:alt"]) || spreads(element) {
+        return;
+    }
+    findings.push(
+        Finding::new(
+            "L0500",
+            "`<img>` has no `alt` text; screen readers announce the file name instead",
+        )
+        .at(code, element.offset)
+        .with_fix("alt=\"What the image shows\"  or  alt=\"\" if it is decorative"),
+    );
+}
+
+/// L0501 `on:click` on a `<div>`/`<span>`/... that keyboards can't reach or trigger
+fn check_click(code: &str, element: &Element, findings: &mut Vec<Finding>) {
+    if element.is_component()
+        || INTERACTIVE_ELEMENTS.contains(&element.name.as_str())
+        || element.attr("on:click").is_none()
+        || spreads(element)
+    {
+        return;
+    }
+    let mut missing = Vec::new();
+    if element.attr("role").is_none() {
+        missing.push("`role`");
+    }
+    if !has_any(element, &["on:keydown", "on:keyup", "on:keypress"]) {
+        missing.push("keyboard handler");
+    }
+    if missing.is_empty() {
+        return;
+    }
+    findings.push(
+        Finding::new(
+            "L0501",
+            format!(
+                "`<{}>` handles clicks but has no {}; keyboard and screen reader users can't activate it",
+                element.name,
+                missing.join(" or ")
+            ),
+        )
+        .at(code, element.offset)
+        .with_fix(
+            "Use a <button>, or add role=\"button\" tabindex=\"0\" and an on:keydown handler for Enter and Space",
+        ),
+    );
+}
+
+/// L0502 form controls with no label: not wrapped in `<label>`, not named by
+/// a `<label for=...>` and without `aria-label`/`aria-labelledby`
+fn check_label(
+    code: &str,
+    element: &Element,
+    in_label: bool,
+    labelled: &HashSet<String>,
+    findings: &mut Vec<Finding>,
+) {
+    let control = match element.name.as_str() {
+        "input" => {
+            let kind = element.attr("type").and_then(|t| t.expr()).unwrap_or("");
+            !UNLABELLED_INPUT_TYPES.contains(&kind.trim_matches('"'))
+        }
+        "select" | "textarea" => true,
+        _ => false,
+    };
+    if !control || in_label || has_any(element, NAME_ATTRS) || spreads(element) {
+        return;
+    }
+    let id = element
+        .attr("id")
+        .filter(|id| id.is_string_literal())
+        .and_then(|id| id.expr())
+        .map(|id| id.trim_matches('"'));
+    if id.is_some_and(|id| labelled.contains(id)) {
+        return;
+    }
+    let fix = match id {
+        Some(id) => format!("<label for=\"{}\">...</label>", id),
+        None => "Wrap it in <label>\"Name\" ...</label>, or add aria-label=\"Name\"".to_string(),
+    };
+    findings.push(
+        Finding::new(
+            "L0502",
+            format!(
+                "`<{}>` has no label, so screen readers announce it without a name",
+                element.name
+            ),
+        )
+        .at(code, element.offset)
+        .with_fix(fix),
+    );
+}
+
+/// L0503 self-closing icon, toggle and dialog components without an accessible name
+fn check_component_name(code: &str, element: &Element, findings: &mut Vec<Finding>) {
+    if !element.is_component() || !element.self_closing || spreads(element) {
+        return;
+    }
+    let name = element.name.rsplit("::").next().unwrap_or(&element.name);
+    let Some((_, fix)) = WIDGET_COMPONENTS
+        .iter()
+        .find(|(part, _)| name.contains(part))
+    else {
+        return;
+    };
+    let named = element.attrs.iter().any(|a| {
+        a.name.starts_with("aria-")
+            || a.name.starts_with("attr:aria-")
+            || matches!(a.name.as_str(), "label" | "title" | "alt")
+    });
+    if named {
+        return;
+    }
+    findings.push(
+        Finding::new(
+            "L0503",
+            format!(
+                "`<{}>` has no accessible name; pass an aria-* attribute (forwarded with `attr:`) or a label prop",
+                element.name
+            ),
+        )
+        .at(code, element.offset)
+        .with_fix(format!("<{} {} />", element.name, fix)),
+    );
+}
+
+/// Ids named by `<label for="...">` in a view
+fn labelled_ids(view: &ViewMacro) -> HashSet<String> {
+    view.nodes
+        .iter()
+        .filter_map(|node| match node {
+            Node::Open(element) if element.name == "label" => element.attr("for"),
+            _ => None,
+        })
+        .filter(|attr| attr.is_string_literal())
+        .filter_map(|attr| attr.expr())
+        .map(|id| id.trim_matches('"').to_string())
+        .collect()
+}
+
+fn has_any(element: &Element, names: &[&str]) -> bool {
+    element
+        .attrs
+        .iter()
+        .any(|a| names.contains(&a.name.as_str()))
+}
+
+/// `{..attrs}` spreads could supply any attribute
+fn spreads(element: &Element) -> bool {
+    element.attrs.iter().any(|a| a.name.starts_with("{.."))
+}
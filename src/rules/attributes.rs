@@ -0,0 +1,193 @@
+//! Attribute forwarding rules for components (L0213–L0214)
+//!
+//! Since Leptos 0.7 a component passes HTML attributes through to the
+//! elements at the root of its view: callers write `attr:class="wide"`, or
+//! put plain attributes after a `{..}` marker, and spread a reusable set with
+//! `{..attrs}`. Components written before that take a `class` or `id` prop
+//! only to set it on their root, or collect attributes with 0.6's
+//! `#[prop(attrs)]`, which no longer exists.
+
+use super::Finding;
+use crate::source::{functions, identifier_uses, mask_literals, typed_params, FnItem};
+use crate::view::{parse_views, Element, Node, ViewMacro, VOID_ELEMENTS};
+
+/// Attributes a prop of the same name (with `_` for `-`) can forward,
+/// besides any `aria-*` and `data-*`
+const FORWARDABLE: &[&str] = &[
+    "class", "id", "style", "title", "role", "tabindex", "lang", "dir", "hidden",
+];
+
+/// Run every attribute forwarding rule over `code`
+pub fn check(code: &str) -> Vec<Finding> {
+    let masked = mask_literals(code);
+    let views = parse_views(code);
+    let mut findings = Vec::new();
+    for function in functions(code).iter().filter(|f| f.is_component()) {
+        check_prop_attrs(code, &views, function, &mut findings);
+        check_forwarding(code, &masked, &views, function, &mut findings);
+    }
+    findings
+}
+
+/// Offset of the parameter `name` in `function`'s parameter list
+fn param_offset(code: &str, function: &FnItem, name: &str) -> Option<usize> {
+    let params = &code[function.params.clone()];
+    params
+        .match_indices(name)
+        .find(|(at, _)| {
+            let before = params[..*at].chars().next_back();
+            let after = params[at + name.len()..].trim_start();
+            !before.is_some_and(|c| c.is_alphanumeric() || c == '_')
+                && after.starts_with(':')
+                && !after.starts_with("::")
+        })
+        .map(|(at, _)| function.params.start + at)
+}
+
+/// The nodes at the top of a view, which attributes given to the component land on
+fn roots(view: &ViewMacro) -> Vec<&Node> {
+    let mut roots = Vec::new();
+    let mut depth = 0usize;
+    for node in &view.nodes {
+        match node {
+            Node::Open(element) => {
+                if depth == 0 {
+                    roots.push(node);
+                }
+                if !element.self_closing && !VOID_ELEMENTS.contains(&element.name.as_str()) {
+                    depth += 1;
+                }
+            }
+            Node::Close { .. } => depth = depth.saturating_sub(1),
+            Node::Block { .. } | Node::Text { .. } if depth == 0 => roots.push(node),
+            _ => {}
+        }
+    }
+    roots
+}
+
+/// L0214 `#[prop(attrs)]`, 0.6's way of collecting a component's attributes
+fn check_prop_attrs(
+    code: &str,
+    views: &[ViewMacro],
+    function: &FnItem,
+    findings: &mut Vec<Finding>,
+) {
+    let params = &code[function.params.clone()];
+    for (at, _) in params.match_indices("#[prop(") {
+        let inner = &params[at + "#[prop(".len()..];
+        let Some(close) = inner.find(")]") else {
+            continue;
+        };
+        if !inner[..close]
+            .split(',')
+            .any(|option| option.trim() == "attrs")
+        {
+            continue;
+        }
+        let name: String = inner[close + 2..]
+            .trim_start()
+            .chars()
+            .take_while(|c| c.is_alphanumeric() || *c == '_')
+            .collect();
+        let spread = format!("{{..{}}}", name);
+        let spread_note = if views
+            .iter()
+            .filter(|v| function.body.contains(&v.start))
+            .flat_map(|v| &v.nodes)
+            .any(|node| matches!(node, Node::Open(e) if e.attrs.iter().any(|a| a.name.replace(' ', "") == spread)))
+        {
+            format!(" and `{}` from the view", spread)
+        } else {
+            String::new()
+        };
+        findings.push(
+            Finding::new(
+                "L0214",
+                format!(
+                    "`#[prop(attrs)]` was removed in Leptos 0.7; attributes given to `{}` reach the root elements of its view without a prop",
+                    function.name
+                ),
+            )
+            .at(code, function.params.start + at)
+            .with_fix(format!(
+                "Remove the `{}` prop{}; callers keep writing `<{} attr:class=\"...\"/>`, or `<{} {{..}} class=\"...\"/>` for several",
+                name, spread_note, function.name, function.name
+            )),
+        );
+    }
+}
+
+/// L0213 a prop named after an HTML attribute whose only use is setting that
+/// attribute on the component's single root element
+fn check_forwarding(
+    code: &str,
+    masked: &str,
+    views: &[ViewMacro],
+    function: &FnItem,
+    findings: &mut Vec<Finding>,
+) {
+    let own: Vec<&ViewMacro> = views
+        .iter()
+        .filter(|v| function.body.contains(&v.start))
+        .collect();
+    let [view] = own.as_slice() else {
+        return;
+    };
+    let [Node::Open(root)] = roots(view).as_slice() else {
+        return;
+    };
+    if root.is_component() {
+        return;
+    }
+    for (name, _) in typed_params(&code[function.params.clone()]) {
+        let attribute = name.replace('_', "-");
+        if !FORWARDABLE.contains(&attribute.as_str())
+            && !attribute.starts_with("aria-")
+            && !attribute.starts_with("data-")
+        {
+            continue;
+        }
+        if !forwards(root, &attribute, &name)
+            || identifier_uses(masked, function.body.clone(), &name).len() != 1
+        {
+            continue;
+        }
+        let Some(offset) = param_offset(code, function, &name) else {
+            continue;
+        };
+        findings.push(
+            Finding::new(
+                "L0213",
+                format!(
+                    "`{}` only passes `{}` on to the root <{}>; since Leptos 0.7 callers can set the attribute on `{}` directly",
+                    name, attribute, root.name, function.name
+                ),
+            )
+            .at(code, offset)
+            .with_fix(format!(
+                "Remove the `{}` prop and `{}={}` from <{}>, and have callers write `<{} attr:{}=...>` or `<{} {{..}} {}=...>`",
+                name, attribute, name, root.name, function.name, attribute, function.name, attribute
+            )),
+        );
+    }
+}
+
+/// Whether `element` sets `attribute` to exactly the prop `name` and nothing else
+fn forwards(element: &Element, attribute: &str, name: &str) -> bool {
+    let prefixed = format!("attr:{}", attribute);
+    let mut set = element
+        .attrs
+        .iter()
+        .filter(|a| a.name == attribute || a.name == prefixed);
+    let Some(attr) = set.next() else {
+        return false;
+    };
+    let value = attr.expr().unwrap_or_default();
+    let reads = [
+        name.to_string(),
+        format!("move || {}.get()", name),
+        format!("{}.clone()", name),
+    ];
+    set.next().is_none() && reads.iter().any(|read| value == read)
+}
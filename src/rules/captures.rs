@@ -0,0 +1,264 @@
+//! Closure capture analysis (L0006)
+//!
+//! A `move` closure takes ownership of every non-`Copy` value it mentions.
+//! Using that value again afterwards — typically a `String` prop passed to an
+//! event handler and then rendered — is the "use of moved value" error new
+//! Leptos users hit most often.
+
+use super::{position, Finding};
+use crate::source::{
+    closures, functions, identifier_uses, let_bindings, mask_literals, typed_params, Binding,
+    Closure,
+};
+use std::ops::Range;
+
+/// Type names that are `Copy`, so moving them into a closure is harmless
+const COPY_TYPES: &[&str] = &[
+    "bool",
+    "char",
+    "i8",
+    "i16",
+    "i32",
+    "i64",
+    "i128",
+    "isize",
+    "u8",
+    "u16",
+    "u32",
+    "u64",
+    "u128",
+    "usize",
+    "f32",
+    "f64",
+    "ReadSignal",
+    "WriteSignal",
+    "RwSignal",
+    "Memo",
+    "Signal",
+    "MaybeSignal",
+    "StoredValue",
+    "Callback",
+    "UnsyncCallback",
+    "Resource",
+    "LocalResource",
+    "Action",
+    "ServerAction",
+    "MultiAction",
+    "NodeRef",
+    "Trigger",
+    "ArenaItem",
+];
+
+/// Initializers that produce an owned, non-`Copy` value
+const OWNED_INITIALIZERS: &[&str] = &[
+    "String::",
+    ".to_string()",
+    ".to_owned()",
+    "format!",
+    "vec!",
+    "Vec::",
+    "HashMap::",
+    "HashSet::",
+    "BTreeMap::",
+    ".collect",
+    "Rc::new",
+    "Arc::new",
+    "Box::new",
+    ".clone()",
+];
+
+/// Initializers that wrap a value in a `Copy` reactive handle
+const COPY_WRAPPERS: &[&str] = &[
+    "StoredValue::",
+    "Signal::",
+    "signal(",
+    "RwSignal::",
+    "Memo::",
+    "Callback::",
+    "NodeRef::",
+    "Action::",
+    "ServerAction::",
+    "MultiAction::",
+    "Resource::",
+    "LocalResource::",
+    "Trigger::",
+];
+
+/// A variable that can be moved, with where it is visible
+struct Owned {
+    name: String,
+    is_prop: bool,
+    scope: Range<usize>,
+}
+
+pub fn check(code: &str) -> Vec<Finding> {
+    let components: Vec<_> = functions(code)
+        .into_iter()
+        .filter(|f| f.is_component())
+        .collect();
+    if components.is_empty() {
+        return Vec::new();
+    }
+    let masked = mask_literals(code);
+    let all_closures = closures(code);
+    let mut findings = Vec::new();
+
+    for component in components {
+        let body = component.body.clone();
+        let lets = let_bindings(&masked, body.clone());
+        let mut owned: Vec<Owned> = owned_props(&masked[component.params.clone()])
+            .into_iter()
+            .map(|name| Owned {
+                name,
+                is_prop: true,
+                scope: body.clone(),
+            })
+            .collect();
+        owned.extend(
+            lets.iter()
+                .filter(|b| is_owned_initializer(&masked[b.init.clone()]))
+                .map(|b| Owned {
+                    name: b.name.clone(),
+                    is_prop: false,
+                    scope: b.scope.clone(),
+                }),
+        );
+        let in_body: Vec<&Closure> = all_closures
+            .iter()
+            .filter(|c| body.contains(&c.start))
+            .collect();
+
+        for var in &owned {
+            // Uses that refer to this binding rather than a shadowing one
+            let uses: Vec<usize> = identifier_uses(&masked, var.scope.clone(), &var.name)
+                .into_iter()
+                .filter(|&u| !shadowed(&masked, var, u, &lets, &in_body))
+                .collect();
+            let moved_into = in_body.iter().find(|c| {
+                c.is_move && c.start >= var.scope.start && uses.iter().any(|u| c.body.contains(u))
+            });
+            let Some(closure) = moved_into else {
+                continue;
+            };
+            let end = closure.range().end;
+            let Some(&reuse) = uses.iter().find(|&&u| u >= end) else {
+                continue;
+            };
+            let (reuse_line, _) = position(code, reuse);
+            let fix = if var.is_prop {
+                format!(
+                    "Make the prop Copy with `let {n} = StoredValue::new({n});` and read it with `{n}.get_value()`, \
+                     or take it as `#[prop(into)] {n}: Signal<...>`",
+                    n = var.name
+                )
+            } else {
+                format!(
+                    "Clone it for the closure: `let {n}_clone = {n}.clone();` before the closure and use `{n}_clone` inside, \
+                     or wrap it in `StoredValue::new({n})` to make it Copy",
+                    n = var.name
+                )
+            };
+            findings.push(
+                Finding::new(
+                    "L0006",
+                    format!(
+                        "`{}` is moved into this `move` closure but used again on line {}",
+                        var.name, reuse_line
+                    ),
+                )
+                .at(code, closure.start)
+                .with_fix(fix),
+            );
+        }
+    }
+    findings
+}
+
+/// Whether a use of `var.name` at `offset` refers to a different binding:
+/// the name in a later `let`, a shadowing `let` in scope, or a closure
+/// parameter where it is declared or used
+fn shadowed(
+    masked: &str,
+    var: &Owned,
+    offset: usize,
+    lets: &[Binding],
+    closures: &[&Closure],
+) -> bool {
+    let declared_here = masked[..offset].trim_end().ends_with("let")
+        || masked[..offset].trim_end().ends_with("let mut");
+    let shadowing_let = lets.iter().any(|b| {
+        b.name == var.name && b.scope.start > var.scope.start && b.scope.contains(&offset)
+    });
+    let closure_param = closures.iter().any(|c| {
+        (c.params_range().contains(&offset) || c.body.contains(&offset))
+            && c.params
+                .split(|ch: char| !(ch.is_alphanumeric() || ch == '_'))
+                .any(|p| p == var.name)
+    });
+    declared_here || shadowing_let || closure_param
+}
+
+/// Names of component parameters whose type isn't `Copy`
+fn owned_props(params: &str) -> Vec<String> {
+    typed_params(params)
+        .into_iter()
+        .filter(|(_, ty)| !is_copy_type(ty))
+        .map(|(name, _)| name)
+        .collect()
+}
+
+fn is_copy_type(ty: &str) -> bool {
+    if ty.starts_with('&') {
+        return true;
+    }
+    if let Some(inner) = ty.strip_prefix("Option<").and_then(|t| t.strip_suffix('>')) {
+        return is_copy_type(inner.trim());
+    }
+    let head: &str = ty
+        .split(|c: char| c == '<' || c.is_whitespace())
+        .next()
+        .unwrap_or(ty);
+    let head = head.rsplit("::").next().unwrap_or(head);
+    COPY_TYPES.contains(&head)
+}
+
+fn is_owned_initializer(init: &str) -> bool {
+    let init = init.trim();
+    !COPY_WRAPPERS.iter().any(|w| init.starts_with(w))
+        && OWNED_INITIALIZERS.iter().any(|p| init.contains(p))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn component(body: &str) -> String {
+        format!(
+            "#[component]\nfn App(id: String) -> impl IntoView {{\n{}\n}}\n",
+            body
+        )
+    }
+
+    #[test]
+    fn reactive_handles_are_copy() {
+        let code = component(
+            "    let name = \"x\".to_string();\n    \
+             let upload = Action::new(|name: &String| save(name.clone()));\n    \
+             let load = Resource::new(move || id.clone(), |id| get_user(id));\n    \
+             view! { <button on:click=move |_| { upload.dispatch(name.clone()); } disabled=move || upload.pending().get()>{move || load.get()}</button> }",
+        );
+        assert!(check(&code).is_empty(), "{:?}", check(&code));
+    }
+
+    #[test]
+    fn closure_parameters_shadow() {
+        let code = component(
+            "    let user = Resource::new(move || id.clone(), |id| get_user(id));\n    view! { {move || user.get()} }",
+        );
+        assert!(check(&code).is_empty(), "{:?}", check(&code));
+        let code = component(
+            "    let a = move || id.clone();\n    let b = move || id.len();\n    view! { {a} {b} }",
+        );
+        assert_eq!(check(&code).len(), 1);
+    }
+}
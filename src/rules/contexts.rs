@@ -0,0 +1,503 @@
+//! Context API checks across a project (L0102–L0104)
+//!
+//! `provide_context` makes a value visible to the providing component and
+//! everything it renders; `expect_context` panics when no ancestor provided
+//! the type. Ancestry comes from `view!` markup: a component renders the
+//! components it names, and children inside a component element are
+//! rendered within it, so they see what the wrapping component provides.
+//! Checks are skipped where the tree can't be known: components rendered
+//! from plain functions, or providers whose type can't be inferred.
+
+use super::Finding;
+use crate::project::SourceFile;
+use crate::reactivity::{sources, NodeKind};
+use crate::source::{functions, let_bindings, mask_literals, typed_params, FnItem};
+use crate::view::{parse_views, skip_balanced, Node};
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use std::path::PathBuf;
+
+/// Contexts Leptos and its integrations provide themselves
+const FRAMEWORK_CONTEXTS: &[&str] = &[
+    "ResponseOptions",
+    "Parts",
+    "RequestParts",
+    "HttpRequest",
+    "HeaderMap",
+    "MetaContext",
+    "ServerMetaContext",
+    "RouterContext",
+    "Location",
+    "Owner",
+];
+
+/// Reactive wrapper types whose parameter an initializer doesn't reveal
+const GENERIC_WRAPPERS: &[&str] = &[
+    "RwSignal",
+    "ArcRwSignal",
+    "ReadSignal",
+    "WriteSignal",
+    "Memo",
+    "Signal",
+    "StoredValue",
+    "Resource",
+    "LocalResource",
+    "Action",
+    "ServerAction",
+    "Store",
+];
+
+/// A `provide_context` call
+struct Provide {
+    /// Normalized type, `None` when it can't be inferred
+    ty: Option<String>,
+    offset: usize,
+    /// Inside an `if`/`else`/`match`, so it doesn't run on every render
+    conditional: bool,
+}
+
+/// An `expect_context::<T>()` call
+struct Expect {
+    ty: String,
+    offset: usize,
+}
+
+struct Component {
+    name: String,
+    file: usize,
+    provides: Vec<Provide>,
+    expects: Vec<Expect>,
+}
+
+/// `parent` renders `child`, nested in the component elements `wrappers`
+struct Render {
+    parent: usize,
+    child: String,
+    wrappers: Vec<String>,
+}
+
+/// Findings of every context rule, by file path
+pub(crate) fn check_project(files: &[SourceFile]) -> HashMap<PathBuf, Vec<Finding>> {
+    let mut components = Vec::new();
+    let mut global = Vec::new();
+    let mut renders = Vec::new();
+    let mut unknown_parents = HashSet::new();
+    for (index, file) in files.iter().enumerate() {
+        let code = &file.content;
+        let masked = mask_literals(code);
+        let items = functions(code);
+        for call in calls(&masked, "provide_context") {
+            if !items
+                .iter()
+                .any(|f| f.is_component() && f.body.contains(&call.0))
+            {
+                let item = items.iter().rev().find(|f| f.body.contains(&call.0));
+                global.push(provide(code, &masked, item, call));
+            }
+        }
+        for item in items.iter().filter(|f| f.is_component()) {
+            let component = components.len();
+            components.push(Component {
+                name: item.name.clone(),
+                file: index,
+                provides: calls(&masked, "provide_context")
+                    .into_iter()
+                    .filter(|(offset, _, _)| item.body.contains(offset))
+                    .map(|call| provide(code, &masked, Some(item), call))
+                    .collect(),
+                expects: calls(&masked, "expect_context")
+                    .into_iter()
+                    .filter(|(offset, _, _)| item.body.contains(offset))
+                    .filter_map(|(offset, turbofish, _)| {
+                        let ty = turbofish.or_else(|| annotated_type(&masked, offset))?;
+                        Some(Expect {
+                            ty: normalize(&ty),
+                            offset,
+                        })
+                    })
+                    .collect(),
+            });
+            renders.extend(rendered(code, item.body.clone(), component));
+        }
+        // Components rendered from plain functions have ancestors we can't follow
+        for view in parse_views(code) {
+            if !items
+                .iter()
+                .any(|f| f.is_component() && f.body.contains(&view.start))
+            {
+                unknown_parents.extend(view.nodes.iter().filter_map(|node| match node {
+                    Node::Open(e) if e.is_component() => Some(short_name(&e.name).to_string()),
+                    _ => None,
+                }));
+            }
+        }
+    }
+
+    let mut findings: HashMap<PathBuf, Vec<Finding>> = HashMap::new();
+    let mut push = |component: &Component, finding: Finding| {
+        findings
+            .entry(files[component.file].path.clone())
+            .or_default()
+            .push(finding);
+    };
+    let global_unknown = global.iter().any(|p| p.ty.is_none());
+    for (index, component) in components.iter().enumerate() {
+        let code = &files[component.file].content;
+        let Some(above) = ancestors(index, &components, &renders, &unknown_parents) else {
+            continue;
+        };
+
+        for (i, provide) in component.provides.iter().enumerate() {
+            let Some(ty) = &provide.ty else { continue };
+            let earlier = component.provides[..i]
+                .iter()
+                .find(|p| p.ty.as_ref().is_some_and(|t| same_type(t, ty)));
+            let outer = above.iter().map(|&a| &components[a]).find(|a| {
+                a.provides
+                    .iter()
+                    .any(|p| p.ty.as_ref().is_some_and(|t| same_type(t, ty)))
+            });
+            let message = match (earlier, outer) {
+                (Some(earlier), _) => format!(
+                    "`{}` is provided twice in `{}` (first on line {}); the second value replaces the first",
+                    ty,
+                    component.name,
+                    super::position(code, earlier.offset).0
+                ),
+                (None, Some(outer)) => format!(
+                    "`{}` is already provided by `{}` above `{}`; this value shadows it for everything `{}` renders",
+                    ty, outer.name, component.name, component.name
+                ),
+                (None, None) => continue,
+            };
+            push(
+                component,
+                Finding::new("L0103", message)
+                    .at(code, provide.offset)
+                    .with_fix("Wrap one of the values in its own newtype if both are needed, or remove the duplicate provide_context"),
+            );
+        }
+
+        for expect in &component.expects {
+            if FRAMEWORK_CONTEXTS.contains(&head(&expect.ty)) {
+                continue;
+            }
+            let visible: Vec<(&Component, &Provide)> = std::iter::once(index)
+                .chain(above.iter().copied())
+                .map(|a| &components[a])
+                .flat_map(|a| a.provides.iter().map(move |p| (a, p)))
+                .collect();
+            if global_unknown
+                || visible.iter().any(|(_, p)| p.ty.is_none())
+                || global
+                    .iter()
+                    .any(|p| p.ty.as_ref().is_some_and(|t| same_type(t, &expect.ty)))
+            {
+                continue;
+            }
+            let providers: Vec<&(&Component, &Provide)> = visible
+                .iter()
+                .filter(|(_, p)| p.ty.as_ref().is_some_and(|t| same_type(t, &expect.ty)))
+                .collect();
+            if providers.is_empty() {
+                let roots: Vec<&str> = above
+                    .iter()
+                    .filter(|&&a| !renders.iter().any(|r| r.child == components[a].name))
+                    .map(|&a| components[a].name.as_str())
+                    .collect();
+                let fix = match roots.first() {
+                    Some(root) => format!(
+                        "Call `provide_context(...)` with a `{}` in `{}` or another ancestor, or read it with `use_context::<{}>()` and handle `None`",
+                        expect.ty, root, expect.ty
+                    ),
+                    None => format!(
+                        "Call `provide_context(...)` with a `{}` in a component that renders `{}`, or read it with `use_context::<{}>()` and handle `None`",
+                        expect.ty, component.name, expect.ty
+                    ),
+                };
+                push(
+                    component,
+                    Finding::new(
+                        "L0102",
+                        format!(
+                            "`expect_context::<{}>()` in `{}` has no matching `provide_context` in any component that renders it, so it panics",
+                            expect.ty, component.name
+                        ),
+                    )
+                    .at(code, expect.offset)
+                    .with_fix(fix),
+                );
+            } else if providers.iter().all(|(_, p)| p.conditional) {
+                let (provider, provide) = providers[0];
+                let line = super::position(&files[provider.file].content, provide.offset).0;
+                push(
+                    component,
+                    Finding::new(
+                        "L0104",
+                        format!(
+                            "`{}` is only provided conditionally (in `{}`, line {}); `expect_context` panics on paths where it isn't",
+                            expect.ty, provider.name, line
+                        ),
+                    )
+                    .at(code, expect.offset)
+                    .with_fix(format!(
+                        "use_context::<{}>().unwrap_or_else(|| /* fallback */)",
+                        expect.ty
+                    )),
+                );
+            }
+        }
+    }
+    findings
+}
+
+/// Calls of `name` as offset, turbofish type and argument range
+pub(crate) fn calls(masked: &str, name: &str) -> Vec<(usize, Option<String>, Range<usize>)> {
+    let bytes = masked.as_bytes();
+    masked
+        .match_indices(name)
+        .filter(|(offset, _)| {
+            *offset == 0
+                || !(bytes[offset - 1].is_ascii_alphanumeric() || bytes[offset - 1] == b'_')
+        })
+        .filter_map(|(offset, _)| {
+            let mut pos = offset + name.len();
+            let mut turbofish = None;
+            if masked[pos..].starts_with("::<") {
+                let open = pos + 2;
+                let close = skip_balanced(bytes, open, b'<', b'>')?;
+                turbofish = Some(masked[open + 1..close - 1].to_string());
+                pos = close;
+            }
+            let paren = pos + masked[pos..].find(|c: char| !c.is_whitespace())?;
+            if bytes[paren] != b'(' {
+                return None;
+            }
+            let end = skip_balanced(bytes, paren, b'(', b')')?;
+            Some((offset, turbofish, paren + 1..end - 1))
+        })
+        .collect()
+}
+
+/// A `provide_context` call with its type inferred from the function it's in
+fn provide(
+    code: &str,
+    masked: &str,
+    item: Option<&FnItem>,
+    (offset, turbofish, args): (usize, Option<String>, Range<usize>),
+) -> Provide {
+    let ty = turbofish.or_else(|| {
+        let arg = masked[args].trim();
+        let arg = arg.strip_suffix(".clone()").unwrap_or(arg);
+        expression_type(arg).or_else(|| binding_type(code, masked, item?, arg))
+    });
+    Provide {
+        ty: ty.map(|t| normalize(&t)),
+        offset,
+        conditional: item.is_some_and(|item| is_conditional(masked, offset, item.body.start)),
+    }
+}
+
+/// Type of a constructor expression: `Theme { .. }`, `AppState::new()`, `RwSignal::new(..)`
+pub(crate) fn expression_type(expr: &str) -> Option<String> {
+    let path: String = expr
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || *c == '_' || *c == ':')
+        .collect();
+    let ty = path
+        .split("::")
+        .filter(|s| s.starts_with(|c: char| c.is_ascii_uppercase()))
+        .last()?;
+    if matches!(ty, "Some" | "Ok" | "Err" | "Box" | "Rc" | "Arc") {
+        return None;
+    }
+    if GENERIC_WRAPPERS.contains(&ty) {
+        Some(format!("{}<_>", ty))
+    } else {
+        Some(ty.to_string())
+    }
+}
+
+/// Type of a variable: a typed parameter, an annotated `let`, or its initializer
+fn binding_type(code: &str, masked: &str, item: &FnItem, name: &str) -> Option<String> {
+    if let Some((_, ty)) = typed_params(&code[item.params.clone()])
+        .into_iter()
+        .find(|(n, _)| n == name)
+    {
+        return Some(ty);
+    }
+    if let Some(node) = sources(code, masked, item.body.clone())
+        .into_iter()
+        .find(|n| n.name == name || n.setter.as_deref() == Some(name))
+    {
+        return match (node.kind, node.setter.as_deref() == Some(name)) {
+            (NodeKind::Signal, true) => Some("WriteSignal<_>".to_string()),
+            (NodeKind::Signal, false) if node.setter.is_some() => Some("ReadSignal<_>".to_string()),
+            _ => expression_type(masked[node.body].trim()),
+        };
+    }
+    let binding = let_bindings(masked, item.body.clone())
+        .into_iter()
+        .rfind(|b| b.name == name)?;
+    annotated_type(masked, binding.init.start - 1)
+        .or_else(|| expression_type(masked[binding.init].trim()))
+}
+
+/// `T` of `let name: T = ...` for the statement containing `offset`
+pub(crate) fn annotated_type(masked: &str, offset: usize) -> Option<String> {
+    let start = masked[..offset].rfind("let ")? + 4;
+    let statement = &masked[start..offset];
+    if statement.contains(';') {
+        return None;
+    }
+    let pattern = statement.trim_start().trim_start_matches("mut ");
+    let name_len = pattern
+        .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .unwrap_or(pattern.len());
+    let ty = pattern[name_len..].trim_start().strip_prefix(':')?;
+    let ty = ty.split('=').next()?.trim();
+    (!ty.is_empty()).then(|| ty.to_string())
+}
+
+/// Whether an `if`, `else` or `match` block encloses `offset`
+fn is_conditional(masked: &str, offset: usize, body_start: usize) -> bool {
+    let bytes = masked.as_bytes();
+    let mut depth = 0usize;
+    for i in (body_start + 1..offset).rev() {
+        match bytes[i] {
+            b'}' => depth += 1,
+            b'{' if depth > 0 => depth -= 1,
+            b'{' => {
+                let head_start = masked[..i].rfind([';', '{', '}']).map_or(0, |p| p + 1);
+                let head = masked[head_start..i].trim();
+                let branch = head.starts_with("if ")
+                    || head.starts_with("else")
+                    || head.starts_with("match ")
+                    || head.ends_with("=>");
+                if branch {
+                    return true;
+                }
+            }
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Components rendered by the views in `body`
+fn rendered(code: &str, body: Range<usize>, parent: usize) -> Vec<Render> {
+    let mut renders = Vec::new();
+    for view in parse_views(code)
+        .into_iter()
+        .filter(|v| body.contains(&v.start))
+    {
+        let mut open: Vec<String> = Vec::new();
+        for node in &view.nodes {
+            match node {
+                Node::Open(element) if element.is_component() => {
+                    let name = short_name(&element.name).to_string();
+                    renders.push(Render {
+                        parent,
+                        child: name.clone(),
+                        wrappers: open.clone(),
+                    });
+                    // `<Route view=HomePage/>` renders HomePage inside Route
+                    let mut wrappers = open.clone();
+                    wrappers.push(name.clone());
+                    for attr in &element.attrs {
+                        let Some(value) = attr.expr() else { continue };
+                        if value.starts_with(|c: char| c.is_ascii_uppercase())
+                            && value.chars().all(|c| c.is_alphanumeric() || c == '_')
+                        {
+                            renders.push(Render {
+                                parent,
+                                child: value.to_string(),
+                                wrappers: wrappers.clone(),
+                            });
+                        }
+                    }
+                    if !element.self_closing {
+                        open.push(name);
+                    }
+                }
+                Node::Close { name, .. } => {
+                    if let Some(idx) = open.iter().rposition(|n| n == short_name(name)) {
+                        open.truncate(idx);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    renders
+}
+
+/// Components whose provided contexts `index` can see, not counting itself
+///
+/// `None` when some path leads through code the analysis can't follow.
+fn ancestors(
+    index: usize,
+    components: &[Component],
+    renders: &[Render],
+    unknown_parents: &HashSet<String>,
+) -> Option<Vec<usize>> {
+    let by_name = |name: &str| components.iter().position(|c| c.name == name);
+    let mut seen = HashSet::from([index]);
+    let mut found = Vec::new();
+    let mut stack = vec![index];
+    while let Some(current) = stack.pop() {
+        if unknown_parents.contains(&components[current].name) {
+            return None;
+        }
+        for render in renders
+            .iter()
+            .filter(|r| r.child == components[current].name)
+        {
+            for wrapper in render.wrappers.iter().filter_map(|w| by_name(w)) {
+                if seen.insert(wrapper) {
+                    found.push(wrapper);
+                }
+            }
+            if seen.insert(render.parent) {
+                found.push(render.parent);
+                stack.push(render.parent);
+            }
+        }
+    }
+    Some(found)
+}
+
+/// Whether two normalized types are the same, `_` matching any parameter
+pub(crate) fn same_type(a: &str, b: &str) -> bool {
+    a == b || (head(a) == head(b) && (a.ends_with("<_>") || b.ends_with("<_>")))
+}
+
+/// `RwSignal` of `RwSignal<bool>`
+fn head(ty: &str) -> &str {
+    ty.split('<').next().unwrap_or(ty)
+}
+
+/// `leptos::prelude::RwSignal< bool >` → `RwSignal<bool>`
+pub(crate) fn normalize(ty: &str) -> String {
+    let mut out = String::new();
+    for c in ty.chars().filter(|c| !c.is_whitespace()) {
+        if c == ':' {
+            if out.ends_with(':') {
+                out.pop();
+                let keep = out
+                    .trim_end_matches(|c: char| c.is_alphanumeric() || c == '_')
+                    .len();
+                out.truncate(keep);
+            } else {
+                out.push(c);
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+fn short_name(name: &str) -> &str {
+    name.rsplit("::").next().unwrap_or(name)
+}
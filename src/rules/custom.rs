@@ -0,0 +1,266 @@
+//! User-defined rules from plugin files
+//!
+//! Teams describe house conventions the built-in rules can't know about as
+//! regex rules in TOML or JSON files, listed under `plugins` in the server's
+//! `[rules]` table and loaded once at startup. Each rule's pattern is matched
+//! within a scope: the whole file, `view!` bodies, component or server
+//! function bodies, or the names of components or server functions.
+//!
+//! ```toml
+//! [[rule]]
+//! id = "H0001"
+//! name = "no-inline-styles"
+//! message = "Use the design-system classes instead of inline styles"
+//! pattern = 'style="'
+//! scope = "view"
+//! doc = "https://wiki.example.com/frontend/styling"
+//! bad = '<div style="color: red">'
+//! good = '<div class="text-danger">'
+//! ```
+
+use super::{find_rule, Category, Example, Finding, Rule, Severity};
+use crate::source::functions;
+use crate::view::parse_views;
+use regex::Regex;
+use serde::Deserialize;
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+/// Rules registered by [`load_plugins`], kept for the life of the process
+static CUSTOM: RwLock<Vec<&'static CustomRule>> = RwLock::new(Vec::new());
+
+/// Where a rule's pattern is matched
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Scope {
+    #[default]
+    File,
+    /// Bodies of `view!` macros
+    View,
+    /// Bodies of `#[component]` and `#[island]` functions
+    Component,
+    /// Bodies of `#[server]` functions
+    ServerFn,
+    /// Names of `#[component]` and `#[island]` functions
+    ComponentName,
+    /// Names of `#[server]` functions
+    ServerFnName,
+}
+
+/// A rule as written in a plugin file
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RuleSpec {
+    id: String,
+    name: String,
+    /// Shown for every match
+    message: String,
+    /// Listed by list-rules; the message if absent
+    summary: Option<String>,
+    #[serde(default = "default_severity")]
+    severity: Severity,
+    /// Regular expression flagged wherever it matches within the scope
+    pattern: String,
+    #[serde(default)]
+    scope: Scope,
+    /// Flag scopes (or names) the pattern does *not* match instead
+    #[serde(default)]
+    negate: bool,
+    fix: Option<String>,
+    /// Link or documentation section explaining the convention
+    doc: Option<String>,
+    /// Code the rule flags, shown with `good` by explain-rule
+    bad: Option<String>,
+    /// `bad` rewritten to follow the convention
+    good: Option<String>,
+}
+
+fn default_severity() -> Severity {
+    Severity::Warning
+}
+
+/// A plugin file: `[[rule]]` tables in TOML, a `rules` array in JSON
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PluginFile {
+    #[serde(rename = "rule", alias = "rules", default)]
+    rules: Vec<RuleSpec>,
+}
+
+/// A registered user-defined rule
+pub(crate) struct CustomRule {
+    pub rule: Rule,
+    pattern: Regex,
+    scope: Scope,
+    negate: bool,
+    message: String,
+    fix: Option<String>,
+}
+
+/// Load the rules of every plugin file and register them next to the built-ins
+///
+/// Files ending in `.json` are read as JSON, others as TOML. Returns how many
+/// rules were added; nothing is registered if any file or rule is invalid.
+pub fn load_plugins(paths: &[PathBuf]) -> Result<usize, String> {
+    let mut loaded: Vec<CustomRule> = Vec::new();
+    for path in paths {
+        for spec in read_plugin(path)? {
+            let context = format!("{} rule '{}'", path.display(), spec.id);
+            let taken = |key: &str| {
+                find_rule(key).is_some()
+                    || Category::parse(key).is_some()
+                    || loaded.iter().any(|c| {
+                        c.rule.id.eq_ignore_ascii_case(key) || c.rule.name.eq_ignore_ascii_case(key)
+                    })
+            };
+            if spec.id.trim().is_empty() || spec.name.trim().is_empty() {
+                return Err(format!("{}: id and name must not be empty", context));
+            }
+            if let Some(key) = [&spec.id, &spec.name].into_iter().find(|k| taken(k)) {
+                return Err(format!(
+                    "{}: '{}' is already a rule or category",
+                    context, key
+                ));
+            }
+            let pattern = Regex::new(&spec.pattern)
+                .map_err(|e| format!("{}: invalid pattern: {}", context, e))?;
+            let example = match (spec.bad, spec.good) {
+                (Some(bad), Some(good)) => Some(Example {
+                    bad: leak(bad),
+                    good: leak(good),
+                }),
+                (None, None) => None,
+                _ => return Err(format!("{}: bad and good must be given together", context)),
+            };
+            loaded.push(CustomRule {
+                rule: Rule {
+                    id: leak(spec.id),
+                    name: leak(spec.name),
+                    category: Category::Custom,
+                    default_severity: spec.severity,
+                    summary: leak(spec.summary.unwrap_or_else(|| spec.message.clone())),
+                    doc: spec.doc.map(leak),
+                    example,
+                },
+                pattern,
+                scope: spec.scope,
+                negate: spec.negate,
+                message: spec.message,
+                fix: spec.fix,
+            });
+        }
+    }
+    let count = loaded.len();
+    let mut custom = CUSTOM.write().unwrap();
+    custom.extend(loaded.into_iter().map(|rule| &*Box::leak(Box::new(rule))));
+    Ok(count)
+}
+
+fn read_plugin(path: &Path) -> Result<Vec<RuleSpec>, String> {
+    let raw = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read rule plugin {}: {}", path.display(), e))?;
+    let file: PluginFile = match path.extension().and_then(|e| e.to_str()) {
+        Some("json") => serde_json::from_str(&raw).map_err(|e| e.to_string()),
+        _ => toml::from_str(&raw).map_err(|e| e.to_string()),
+    }
+    .map_err(|e| format!("Invalid rule plugin {}: {}", path.display(), e))?;
+    Ok(file.rules)
+}
+
+/// Plugin rules live as long as the process, like the built-in rule table
+fn leak(value: String) -> &'static str {
+    Box::leak(value.into_boxed_str())
+}
+
+/// The registered user-defined rules
+pub(crate) fn rules() -> Vec<&'static CustomRule> {
+    CUSTOM.read().unwrap().clone()
+}
+
+/// Run the registered user-defined rules over a piece of code
+pub fn check(code: &str) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    for custom in rules() {
+        let finding = |offset: usize| {
+            let finding = Finding::new(custom.rule.id, custom.message.clone()).at(code, offset);
+            match &custom.fix {
+                Some(fix) => finding.with_fix(fix.clone()),
+                None => finding,
+            }
+        };
+        match custom.scope {
+            Scope::ComponentName | Scope::ServerFnName => {
+                for (name, offset) in names(code, custom.scope) {
+                    if custom.pattern.is_match(name) != custom.negate {
+                        findings.push(finding(offset));
+                    }
+                }
+            }
+            scope => {
+                for region in regions(code, scope) {
+                    let text = &code[region.clone()];
+                    if custom.negate {
+                        if !custom.pattern.is_match(text) {
+                            findings.push(finding(region.start));
+                        }
+                        continue;
+                    }
+                    findings.extend(
+                        custom
+                            .pattern
+                            .find_iter(text)
+                            .map(|m| finding(region.start + m.start())),
+                    );
+                }
+            }
+        }
+    }
+    findings
+}
+
+/// Byte ranges a text scope covers, outermost only so nested ones don't match twice
+fn regions(code: &str, scope: Scope) -> Vec<Range<usize>> {
+    let ranges: Vec<Range<usize>> = match scope {
+        Scope::View => parse_views(code)
+            .into_iter()
+            .map(|v| v.body_start..v.body_end)
+            .collect(),
+        Scope::Component => functions(code)
+            .into_iter()
+            .filter(|f| f.is_component())
+            .map(|f| f.body)
+            .collect(),
+        Scope::ServerFn => functions(code)
+            .into_iter()
+            .filter(|f| f.has_attr("server"))
+            .map(|f| f.body)
+            .collect(),
+        _ => std::iter::once(0..code.len()).collect(),
+    };
+    ranges
+        .iter()
+        .filter(|r| {
+            !ranges
+                .iter()
+                .any(|outer| *outer != **r && outer.start <= r.start && r.end <= outer.end)
+        })
+        .cloned()
+        .collect()
+}
+
+/// Names of the components or server functions, with the offset of each name
+fn names(code: &str, scope: Scope) -> Vec<(&str, usize)> {
+    functions(code)
+        .into_iter()
+        .filter(|f| match scope {
+            Scope::ComponentName => f.is_component(),
+            _ => f.has_attr("server"),
+        })
+        .filter_map(|f| {
+            let offset = code[..f.params.start].rfind(&f.name)?;
+            Some((&code[offset..offset + f.name.len()], offset))
+        })
+        .collect()
+}
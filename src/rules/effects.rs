@@ -0,0 +1,166 @@
+//! Effects used to compute derived state (L0007)
+//!
+//! An effect whose only statement copies a value computed from other signals
+//! into a signal makes the first render see a stale value, runs one tick
+//! behind every change and hands out a setter nothing else should call. A
+//! memo, or a plain `move ||` closure when the computation is cheap, derives
+//! the value on read instead.
+
+use super::Finding;
+use crate::reactivity::{accesses, sources, Access, NodeKind, ReactiveNode};
+use crate::source::{call_ranges, closures, mask_literals, statement_end, Closure};
+use crate::view::skip_balanced;
+use std::ops::Range;
+
+/// Effect constructors whose closure re-runs whenever what it reads changes
+const EFFECTS: &[&str] = &["Effect::new", "create_effect"];
+
+/// Run the rule over `code`
+pub fn check(code: &str) -> Vec<Finding> {
+    let masked = mask_literals(code);
+    let signals = sources(code, &masked, 0..code.len());
+    let all_closures = closures(code);
+    let mut findings = Vec::new();
+    for args in call_ranges(&masked, EFFECTS) {
+        let Some(closure) = all_closures.iter().find(|c| args.contains(&c.start)) else {
+            continue;
+        };
+        let Some((target, value)) = only_write(&masked, closure.body.clone(), &all_closures) else {
+            continue;
+        };
+        let Some(signal) = signals.iter().find(|s| {
+            s.kind == NodeKind::Signal && (s.name == target || s.setter.as_ref() == Some(&target))
+        }) else {
+            continue;
+        };
+        if !is_derived(&masked, &value, signal, &args, &signals) {
+            continue;
+        }
+        let expr = code[value].trim();
+        let start = call_start(&masked, args.start);
+        // Keep the rewrite in the API generation the code uses
+        let memo = if masked[start..].starts_with("create_effect") {
+            "create_memo"
+        } else {
+            "Memo::new"
+        };
+        let removed = match &signal.setter {
+            Some(setter) => format!("the effect and `{}`", setter),
+            None => "the effect".to_string(),
+        };
+        findings.push(
+            Finding::new(
+                "L0007",
+                format!(
+                    "Effect only copies a value computed from other signals into `{}`; derive it instead so it is never stale and needs no setter",
+                    signal.name
+                ),
+            )
+            .at(code, start)
+            .with_fix(format!(
+                "Remove {} and declare `let {} = {}(move |_| {});` (or `let {} = move || {};` if it is cheap to recompute)",
+                removed, signal.name, memo, expr, signal.name, expr
+            )),
+        );
+    }
+    findings
+}
+
+/// Target and value range of a closure body consisting of one write:
+/// `x.set(value)`, `set_x(value)` or `x.update(|v| *v = value)`
+fn only_write(
+    masked: &str,
+    body: Range<usize>,
+    all_closures: &[Closure],
+) -> Option<(String, Range<usize>)> {
+    let statement = single_statement(masked, body)?;
+    let text = &masked[statement.clone()];
+    let target: String = text
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || *c == '_')
+        .collect();
+    let rest = &text[target.len()..];
+    let method: String = rest
+        .strip_prefix('.')
+        .unwrap_or_default()
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || *c == '_')
+        .collect();
+    let path_len = match method.len() {
+        0 => target.len(),
+        len => target.len() + 1 + len,
+    };
+    let open = statement.start + path_len;
+    let bytes = masked.as_bytes();
+    if target.is_empty() || bytes.get(open) != Some(&b'(') {
+        return None;
+    }
+    if skip_balanced(bytes, open, b'(', b')') != Some(statement.end) {
+        return None;
+    }
+    let args = open + 1..statement.end - 1;
+    match method.as_str() {
+        "" | "set" => Some((target, args)),
+        "update" => {
+            let update = all_closures.iter().find(|c| args.contains(&c.start))?;
+            let assignment = single_statement(masked, update.body.clone())?;
+            let value = masked[assignment.clone()]
+                .strip_prefix(&format!("*{}", update.params.trim()))?
+                .trim_start()
+                .strip_prefix('=')
+                .filter(|v| !v.starts_with('='))?;
+            Some((
+                target,
+                assignment.end - value.trim_start().len()..assignment.end,
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// The one statement of a block or expression, without braces and `;`
+fn single_statement(masked: &str, range: Range<usize>) -> Option<Range<usize>> {
+    let trimmed = masked[range.clone()].trim();
+    let mut start = range.start + masked[range.clone()].find(trimmed)?;
+    let mut end = start + trimmed.len();
+    if let Some(inner) = trimmed.strip_prefix('{').and_then(|t| t.strip_suffix('}')) {
+        start += 1 + (inner.len() - inner.trim_start().len());
+        end -= 1 + (inner.len() - inner.trim_end().len());
+    }
+    let statement_end = statement_end(masked, start, end).unwrap_or(end);
+    let remainder = masked[statement_end..end].trim_start_matches(';').trim();
+    (start < statement_end && remainder.is_empty()).then_some(start..statement_end)
+}
+
+/// Whether `value` reads other signals but doesn't use `signal` itself, and
+/// nothing outside the effect writes `signal`
+fn is_derived(
+    masked: &str,
+    value: &Range<usize>,
+    signal: &ReactiveNode,
+    effect: &Range<usize>,
+    signals: &[ReactiveNode],
+) -> bool {
+    let reads = |node: &ReactiveNode| {
+        accesses(masked, value.clone(), node)
+            .iter()
+            .any(|(_, access)| *access == Access::Read)
+    };
+    let reads_others = signals.iter().any(|s| s.name != signal.name && reads(s));
+    // `set_log.set(log.get_untracked() + 1)` accumulates rather than derives
+    let uses_itself = !accesses(masked, value.clone(), signal).is_empty();
+    let written_elsewhere = accesses(masked, 0..masked.len(), signal)
+        .iter()
+        .filter(|(offset, _)| !effect.contains(offset) && !signal.definition.contains(offset))
+        .any(|(_, access)| *access == Access::Write);
+    reads_others && !uses_itself && !written_elsewhere
+}
+
+/// Offset of the `Effect::new`/`create_effect` path whose arguments open at `paren`
+fn call_start(masked: &str, paren: usize) -> usize {
+    let before = masked[..paren].trim_end();
+    EFFECTS
+        .iter()
+        .find(|name| before.ends_with(*name))
+        .map_or(paren, |name| before.len() - name.len())
+}
@@ -0,0 +1,340 @@
+//! Event handler rules for `on:` attributes (L0209–L0212)
+//!
+//! Leptos types each `on:` handler by its event: `on:click` takes a
+//! `MouseEvent`, `on:submit` a `SubmitEvent`, and a closure annotated with
+//! another type doesn't compile. Beyond the type, handlers run long after the
+//! component function returns, so they can't borrow from it, and they see the
+//! DOM event as the browser sends it: the target of a click on a `<div>` has
+//! no value to read, and a submitted `<form>` navigates away unless the
+//! handler prevents it.
+
+use super::Finding;
+use crate::source::{
+    closures, functions, identifier_uses, let_bindings, mask_literals, typed_params, Closure,
+    FnItem,
+};
+use crate::view::{parse_views, Attr, Element, Node};
+use std::ops::Range;
+
+/// Events and the `web_sys` types their handlers may take
+const EVENT_TYPES: &[(&[&str], &[&str])] = &[
+    (
+        &[
+            "click",
+            "dblclick",
+            "auxclick",
+            "contextmenu",
+            "mousedown",
+            "mouseup",
+            "mousemove",
+            "mouseover",
+            "mouseout",
+            "mouseenter",
+            "mouseleave",
+        ],
+        &["MouseEvent"],
+    ),
+    (
+        &[
+            "pointerdown",
+            "pointerup",
+            "pointermove",
+            "pointerover",
+            "pointerout",
+            "pointerenter",
+            "pointerleave",
+            "pointercancel",
+        ],
+        &["PointerEvent"],
+    ),
+    (&["keydown", "keyup", "keypress"], &["KeyboardEvent"]),
+    (&["focus", "blur", "focusin", "focusout"], &["FocusEvent"]),
+    (&["input", "beforeinput"], &["InputEvent", "Event"]),
+    (
+        &[
+            "change", "scroll", "load", "reset", "select", "toggle", "invalid",
+        ],
+        &["Event"],
+    ),
+    (&["submit"], &["SubmitEvent"]),
+    (&["wheel"], &["WheelEvent"]),
+    (
+        &["touchstart", "touchend", "touchmove", "touchcancel"],
+        &["TouchEvent"],
+    ),
+    (
+        &[
+            "drag",
+            "dragstart",
+            "dragend",
+            "dragenter",
+            "dragleave",
+            "dragover",
+            "drop",
+        ],
+        &["DragEvent"],
+    ),
+    (&["copy", "cut", "paste"], &["ClipboardEvent"]),
+    (
+        &["animationstart", "animationend", "animationiteration"],
+        &["AnimationEvent"],
+    ),
+    (
+        &["transitionstart", "transitionend", "transitionrun"],
+        &["TransitionEvent"],
+    ),
+];
+
+/// Elements whose event target has a `value` or `checked` property
+const VALUE_ELEMENTS: &[&str] = &["input", "textarea", "select", "button", "option"];
+
+/// Events that bubble up from form controls to their containers
+const CONTROL_EVENTS: &[&str] = &[
+    "input",
+    "beforeinput",
+    "change",
+    "keydown",
+    "keyup",
+    "keypress",
+    "focusin",
+    "focusout",
+];
+
+/// Helpers that cast the event target to an input element
+const TARGET_READS: &[&str] = &["event_target_value(", "event_target_checked("];
+
+/// Run every event handler rule over `code`
+pub fn check(code: &str) -> Vec<Finding> {
+    let masked = mask_literals(code);
+    let all_closures = closures(code);
+    let functions = functions(code);
+    let mut findings = Vec::new();
+    for view in parse_views(code) {
+        for node in &view.nodes {
+            let Node::Open(element) = node else {
+                continue;
+            };
+            for attr in element.attrs.iter().filter(|a| a.name.starts_with("on:")) {
+                let event = attr.name["on:".len()..]
+                    .split(':')
+                    .next()
+                    .unwrap_or_default();
+                let function = enclosing(&functions, attr.offset);
+                let Some(handler) = handler(code, &masked, &all_closures, function, attr) else {
+                    continue;
+                };
+                check_event_type(code, attr, event, handler, &mut findings);
+                check_target_reads(code, &masked, element, event, handler, &mut findings);
+                check_submit(code, &masked, element, event, handler, &mut findings);
+                if let Some(function) = function {
+                    check_borrows(code, &masked, function, attr, handler, &mut findings);
+                }
+            }
+        }
+    }
+    findings
+}
+
+/// Innermost function whose body contains `offset`
+fn enclosing(functions: &[FnItem], offset: usize) -> Option<&FnItem> {
+    functions
+        .iter()
+        .filter(|f| f.body.contains(&offset))
+        .max_by_key(|f| f.body.start)
+}
+
+/// The closure an `on:` attribute runs: written inline, or bound with `let`
+/// earlier in the same function
+fn handler<'a>(
+    code: &str,
+    masked: &str,
+    all_closures: &'a [Closure],
+    function: Option<&FnItem>,
+    attr: &Attr,
+) -> Option<&'a Closure> {
+    let value = attr.value.as_deref()?;
+    let start = attr.offset + code[attr.offset..].find(value)?;
+    let expr = attr.expr()?;
+    let closure_at = |range: Range<usize>| {
+        all_closures
+            .iter()
+            .filter(|c| range.contains(&c.start))
+            .min_by_key(|c| c.start)
+    };
+    if expr.starts_with("move") || expr.starts_with('|') {
+        return closure_at(start..start + value.len());
+    }
+    if !expr.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        return None;
+    }
+    let binding = let_bindings(masked, function?.body.clone())
+        .into_iter()
+        .rev()
+        .find(|b| b.name == expr && b.scope.contains(&attr.offset))?;
+    let init =
+        binding.init.start + masked[binding.init.clone()].find(|c: char| !c.is_whitespace())?;
+    closure_at(init..init + 1)
+}
+
+/// The type a handler's event parameter is annotated with, without its path
+fn event_param_type(handler: &Closure) -> Option<String> {
+    let (_, ty) = typed_params(&handler.params).into_iter().next()?;
+    Some(ty.rsplit("::").next().unwrap_or(&ty).trim().to_string())
+}
+
+/// L0209 a handler annotated with another event's type
+fn check_event_type(
+    code: &str,
+    attr: &Attr,
+    event: &str,
+    handler: &Closure,
+    findings: &mut Vec<Finding>,
+) {
+    let Some(ty) = event_param_type(handler) else {
+        return;
+    };
+    let Some((_, accepted)) = EVENT_TYPES
+        .iter()
+        .find(|(events, _)| events.contains(&event))
+    else {
+        return;
+    };
+    let known = EVENT_TYPES
+        .iter()
+        .any(|(_, types)| types.contains(&ty.as_str()));
+    if !known || accepted.contains(&ty.as_str()) {
+        return;
+    }
+    findings.push(
+        Finding::new(
+            "L0209",
+            format!(
+                "`{}` handlers take `ev::{}`, not `{}`",
+                attr.name, accepted[0], ty
+            ),
+        )
+        .at(code, handler.start)
+        .with_fix(format!(
+            "Annotate the parameter as `ev: ev::{}`, or leave the type out and let `{}` infer it",
+            accepted[0], attr.name
+        )),
+    );
+}
+
+/// L0210 `event_target_value` in a handler whose target has no value
+fn check_target_reads(
+    code: &str,
+    masked: &str,
+    element: &Element,
+    event: &str,
+    handler: &Closure,
+    findings: &mut Vec<Finding>,
+) {
+    if element.is_component()
+        || VALUE_ELEMENTS.contains(&element.name.as_str())
+        || CONTROL_EVENTS.contains(&event)
+    {
+        return;
+    }
+    for read in TARGET_READS {
+        let Some(rel) = masked[handler.body.clone()].find(read) else {
+            continue;
+        };
+        findings.push(
+            Finding::new(
+                "L0210",
+                format!(
+                    "`{}` reads the event target as an input, but the target of `{}` on <{}> isn't a form control",
+                    read.trim_end_matches('('),
+                    event,
+                    element.name
+                ),
+            )
+            .at(code, handler.body.start + rel)
+            .with_fix(
+                "Read the value in an `on:input` handler on the <input> itself, or keep it in a signal and read that here",
+            ),
+        );
+    }
+}
+
+/// L0211 a `<form>` submit handler that runs client logic without
+/// `prevent_default`, so the browser also submits the form and reloads
+fn check_submit(
+    code: &str,
+    masked: &str,
+    element: &Element,
+    event: &str,
+    handler: &Closure,
+    findings: &mut Vec<Finding>,
+) {
+    let body = masked[handler.body.clone()].trim();
+    if element.name != "form"
+        || event != "submit"
+        || body.contains("prevent_default")
+        || body
+            .trim_matches(|c: char| "{}()".contains(c) || c.is_whitespace())
+            .is_empty()
+    {
+        return;
+    }
+    findings.push(
+        Finding::new(
+            "L0211",
+            "Submit handler doesn't call `ev.prevent_default()`, so the browser submits the form and reloads the page while it runs",
+        )
+        .at(code, handler.start)
+        .with_fix("on:submit=move |ev: ev::SubmitEvent| { ev.prevent_default(); ... }"),
+    );
+}
+
+/// L0212 a handler using a reference borrowed from the function it's
+/// created in, which it outlives
+fn check_borrows(
+    code: &str,
+    masked: &str,
+    function: &FnItem,
+    attr: &Attr,
+    handler: &Closure,
+    findings: &mut Vec<Finding>,
+) {
+    let borrowed = |ty_or_init: &str| {
+        let value = ty_or_init.trim();
+        (value.starts_with('&') && !value.starts_with("&'static")) || value.ends_with(".as_str()")
+    };
+    // Later bindings shadow parameters and earlier bindings of the same name
+    let mut references: Vec<(String, bool)> = typed_params(&code[function.params.clone()])
+        .into_iter()
+        .map(|(name, ty)| (name, borrowed(&ty)))
+        .collect();
+    for binding in let_bindings(masked, function.body.clone()) {
+        if binding.scope.contains(&attr.offset) {
+            let is_ref = borrowed(&masked[binding.init.clone()]);
+            references.retain(|(name, _)| *name != binding.name);
+            references.push((binding.name, is_ref));
+        }
+    }
+    let references = references
+        .into_iter()
+        .filter(|(_, is_ref)| *is_ref)
+        .map(|(name, _)| name);
+    for name in references {
+        let Some(&offset) = identifier_uses(masked, handler.body.clone(), &name).first() else {
+            continue;
+        };
+        findings.push(
+            Finding::new(
+                "L0212",
+                format!(
+                    "`{}` handler uses `{}`, which `{}` only borrows, but handlers are 'static and outlive it",
+                    attr.name, name, function.name
+                ),
+            )
+            .at(code, offset)
+            .with_fix(format!(
+                "Move an owned copy in: `let {} = {}.to_owned();` before the view, or keep it in a `StoredValue`",
+                name, name
+            )),
+        );
+    }
+}
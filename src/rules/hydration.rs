@@ -0,0 +1,165 @@
+//! Hydration rules (L03xx)
+//!
+//! Components and views run twice with SSR: once on the server and again in
+//! the browser during hydration. These checks flag code in that render path
+//! that behaves differently on the two sides.
+
+use super::Finding;
+use crate::source::{call_ranges, callback_closures, functions, within};
+use crate::view::parse_views;
+use std::ops::Range;
+
+/// Calls whose arguments only run in the browser
+const CLIENT_ONLY_CALLS: &[&str] = &[
+    "Effect::new",
+    "Effect::watch",
+    "create_effect",
+    "LocalResource::new",
+    "request_animation_frame",
+    "set_timeout",
+    "set_interval",
+    "spawn_local",
+];
+
+/// Browser-only globals that panic or don't exist on the server
+const BROWSER_APIS: &[&str] = &["window()", "document()"];
+
+/// Values that differ between the server render and the hydration render
+const NONDETERMINISTIC: &[&str] = &[
+    "rand::",
+    "random()",
+    "thread_rng(",
+    "Uuid::new_v4(",
+    "SystemTime::now(",
+    "Instant::now(",
+    "Utc::now(",
+    "Local::now(",
+    "Date::now(",
+    "Date::new_0(",
+    "Math::random(",
+];
+
+/// Feature flags that differ between the server and client builds
+const TARGET_CFGS: &[&str] = &[
+    "cfg!(feature = \"ssr\")",
+    "cfg!(feature = \"hydrate\")",
+    "cfg!(feature = \"csr\")",
+];
+
+/// Run every hydration rule over `code`
+pub fn check(code: &str) -> Vec<Finding> {
+    let render = render_ranges(code);
+    if render.is_empty() {
+        return Vec::new();
+    }
+    let mut client_only = call_ranges(code, CLIENT_ONLY_CALLS);
+    client_only.extend(callback_closures(code));
+    let in_render = |offset: usize| {
+        within(&render, offset) && !within(&client_only, offset) && !in_comment(code, offset)
+    };
+
+    let mut findings = Vec::new();
+    for api in BROWSER_APIS {
+        for offset in occurrences(code, api).filter(|o| in_render(*o)) {
+            findings.push(
+                Finding::new(
+                    "L0300",
+                    format!(
+                        "`{}` runs during server rendering, where browser APIs don't exist",
+                        api
+                    ),
+                )
+                .at(code, offset)
+                .with_fix(
+                    "Move browser access into `Effect::new(move |_| { ... })` or an event handler",
+                ),
+            );
+        }
+    }
+
+    for pattern in NONDETERMINISTIC {
+        for offset in occurrences(code, pattern).filter(|o| in_render(*o)) {
+            findings.push(
+                Finding::new(
+                    "L0301",
+                    format!(
+                        "`{}` produces a different value on the server and during hydration",
+                        pattern.trim_end_matches(['(', ':'])
+                    ),
+                )
+                .at(code, offset)
+                .with_fix("Compute the value on the server and pass it in, or set it from an Effect after hydration"),
+            );
+        }
+    }
+
+    // Feature checks elsewhere in a component usually guard effects; only
+    // ones in view! code choose markup
+    let views: Vec<Range<usize>> = parse_views(code)
+        .into_iter()
+        .map(|v| v.start..v.body_end)
+        .collect();
+    for cfg in TARGET_CFGS {
+        for offset in occurrences(code, cfg).filter(|o| in_render(*o) && within(&views, *o)) {
+            findings.push(
+                Finding::new(
+                    "L0302",
+                    format!(
+                        "`{}` renders different markup on the server and the client",
+                        cfg
+                    ),
+                )
+                .at(code, offset)
+                .with_fix("Render the same markup on both sides and switch after hydration with a signal set in Effect::new"),
+            );
+        }
+    }
+
+    findings
+}
+
+/// Component bodies and view! invocations: code that runs on both sides
+fn render_ranges(code: &str) -> Vec<Range<usize>> {
+    let mut ranges: Vec<Range<usize>> = functions(code)
+        .into_iter()
+        .filter(|f| f.is_component())
+        .map(|f| f.body)
+        .collect();
+    ranges.extend(parse_views(code).into_iter().map(|v| v.start..v.body_end));
+    ranges
+}
+
+/// Offsets of `pattern` not preceded by an identifier character or `.`
+fn occurrences<'a>(code: &'a str, pattern: &'a str) -> impl Iterator<Item = usize> + 'a {
+    code.match_indices(pattern)
+        .map(|(o, _)| o)
+        .filter(move |&o| {
+            code[..o]
+                .chars()
+                .next_back()
+                .is_none_or(|c| !(c.is_alphanumeric() || c == '_' || c == '.'))
+        })
+}
+
+/// Whether `offset` sits in a `//` line comment
+fn in_comment(code: &str, offset: usize) -> bool {
+    let line_start = code[..offset].rfind('\n').map_or(0, |i| i + 1);
+    code[line_start..offset].contains("//")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn l0302(code: &str) -> usize {
+        check(code).iter().filter(|f| f.rule_id == "L0302").count()
+    }
+
+    #[test]
+    fn target_cfg_only_in_views() {
+        let guard = "#[component]\nfn App() -> impl IntoView {\n    if cfg!(feature = \"hydrate\") {\n        spawn_local(async {});\n    }\n    let ready = cfg!(feature = \"ssr\");\n    view! { <p>\"Hi\"</p> }\n}\n";
+        assert_eq!(l0302(guard), 0);
+        let markup = "#[component]\nfn App() -> impl IntoView {\n    view! { <p>{move || if cfg!(feature = \"ssr\") { \"server\" } else { \"client\" }}</p> }\n}\n";
+        assert_eq!(l0302(markup), 1);
+    }
+}
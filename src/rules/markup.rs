@@ -0,0 +1,373 @@
+//! Markup rules for `view!` bodies
+//!
+//! Checks that need the tag/attribute structure from [`crate::view`] rather
+//! than plain string matching.
+
+use super::Finding;
+use crate::view::{parse_views, Element, Node, ViewMacro, VOID_ELEMENTS};
+use std::collections::HashSet;
+
+/// Components exported by leptos, leptos_router and leptos_meta
+const BUILTIN_COMPONENTS: &[&str] = &[
+    "A",
+    "ActionForm",
+    "AutoReload",
+    "Await",
+    "Body",
+    "ErrorBoundary",
+    "For",
+    "ForEnumerate",
+    "Form",
+    "Html",
+    "HydrationScripts",
+    "Link",
+    "Meta",
+    "MetaTags",
+    "MultiActionForm",
+    "Outlet",
+    "ParentRoute",
+    "Portal",
+    "ProtectedParentRoute",
+    "ProtectedRoute",
+    "Provider",
+    "Redirect",
+    "Route",
+    "Router",
+    "Routes",
+    "Script",
+    "Show",
+    "Style",
+    "Stylesheet",
+    "Suspense",
+    "Title",
+    "Transition",
+];
+
+/// Run every markup rule over the `view!` invocations in `code`
+pub fn check(code: &str) -> Vec<Finding> {
+    let views = parse_views(code);
+    if views.is_empty() {
+        return Vec::new();
+    }
+
+    let known = known_components(code);
+    let mut findings = Vec::new();
+    for view in &views {
+        check_tag_balance(code, view, &mut findings);
+        for node in &view.nodes {
+            if let Node::Open(element) = node {
+                check_dynamic_class(code, element, &mut findings);
+                check_handlers(code, element, &mut findings);
+                if element.name == "For" {
+                    check_for_keys(code, element, &mut findings);
+                }
+                if let Some(known) = &known {
+                    check_component_known(code, element, known, &mut findings);
+                }
+            }
+        }
+        check_empty_components(code, view, &mut findings);
+        check_reactive_iteration(code, view, &mut findings);
+    }
+    findings
+}
+
+/// L0200 unclosed/mismatched tags and L0201 void elements written without `/>`
+fn check_tag_balance(code: &str, view: &ViewMacro, findings: &mut Vec<Finding>) {
+    let mut stack: Vec<&Element> = Vec::new();
+    for node in &view.nodes {
+        match node {
+            Node::Open(element) if element.self_closing => {}
+            Node::Open(element) if VOID_ELEMENTS.contains(&element.name.as_str()) => {
+                let finding = Finding::new(
+                    "L0201",
+                    format!(
+                        "`<{}>` is a void element and must be self-closed in view!",
+                        element.name
+                    ),
+                )
+                .at(code, element.offset)
+                .with_fix(format!("<{} ... />", element.name));
+                findings.push(match tag_end(code, element) {
+                    Some(end) => finding.with_edit(code, end..end + 1, "/>"),
+                    None => finding,
+                });
+            }
+            Node::Open(element) => stack.push(element),
+            Node::Close { name, offset } => match stack.iter().rposition(|e| &e.name == name) {
+                Some(idx) => {
+                    // Everything opened after the matching tag was left unclosed
+                    for unclosed in stack.drain(idx + 1..) {
+                        findings.push(unclosed_finding(code, unclosed));
+                    }
+                    stack.pop();
+                }
+                None => findings.push(
+                    Finding::new(
+                        "L0200",
+                        format!("Closing tag `</{}>` has no matching opening tag", name),
+                    )
+                    .at(code, *offset),
+                ),
+            },
+            _ => {}
+        }
+    }
+    for unclosed in stack {
+        findings.push(unclosed_finding(code, unclosed));
+    }
+}
+
+/// Offset of the `>` ending an element's opening tag
+fn tag_end(code: &str, element: &Element) -> Option<usize> {
+    let after = match element.attrs.last() {
+        Some(attr) => match &attr.value {
+            Some(value) => attr.offset + code[attr.offset..].find(value.as_str())? + value.len(),
+            None => attr.offset + attr.name.len(),
+        },
+        None => element.offset + 1 + element.name.len(),
+    };
+    let end = after + code.get(after..)?.find(|c: char| !c.is_whitespace())?;
+    (code.as_bytes()[end] == b'>').then_some(end)
+}
+
+fn unclosed_finding(code: &str, element: &Element) -> Finding {
+    let display = if element.name.is_empty() {
+        "<>".to_string()
+    } else {
+        format!("<{}>", element.name)
+    };
+    Finding::new("L0200", format!("Tag `{}` is never closed", display))
+        .at(code, element.offset)
+        .with_fix(format!(
+            "Add `</{}>` or self-close it with `<{} />`",
+            element.name, element.name
+        ))
+}
+
+/// L0202 `class=` computed with if/else instead of `class:name=` toggles
+fn check_dynamic_class(code: &str, element: &Element, findings: &mut Vec<Finding>) {
+    let Some(attr) = element.attr("class") else {
+        return;
+    };
+    if attr.is_string_literal() {
+        return;
+    }
+    let Some(expr) = attr.expr() else { return };
+    if expr.contains("if ") && expr.contains("else") {
+        findings.push(
+            Finding::new(
+                "L0202",
+                "Conditional `class=` value rebuilds the whole class string; toggle individual classes instead",
+            )
+            .at(code, attr.offset)
+            .with_fix(
+                "class:active=move || is_active.get()  or  class=(\"active\", move || is_active.get())",
+            ),
+        );
+    }
+}
+
+/// L0203 event handler closures that don't `move` their captures
+fn check_handlers(code: &str, element: &Element, findings: &mut Vec<Finding>) {
+    for attr in element.attrs.iter().filter(|a| a.name.starts_with("on:")) {
+        let Some(expr) = attr.expr() else { continue };
+        if expr.starts_with('|') {
+            let finding = Finding::new(
+                "L0203",
+                format!(
+                    "`{}` handler closure is missing `move`; captured signals must be moved into 'static handlers",
+                    attr.name
+                ),
+            )
+            .at(code, attr.offset)
+            .with_fix(format!("{}=move {}", attr.name, expr));
+            let pipe = attr.value.as_deref().and_then(|value| {
+                Some(attr.offset + code[attr.offset..].find(value)? + value.find('|')?)
+            });
+            findings.push(match pipe {
+                Some(pipe) => finding.with_edit(code, pipe..pipe + 1, "move |"),
+                None => finding,
+            });
+        }
+    }
+}
+
+/// L0206 `<For>` without `key` and L0207 keys that are just the loop index
+fn check_for_keys(code: &str, element: &Element, findings: &mut Vec<Finding>) {
+    let Some(key) = element.attr("key") else {
+        findings.push(
+            Finding::new("L0206", "<For> requires a `key` prop so rows can be diffed")
+                .at(code, element.offset)
+                .with_fix("key=|item| item.id"),
+        );
+        return;
+    };
+
+    let enumerated = element
+        .attr("each")
+        .and_then(|a| a.expr())
+        .is_some_and(|each| each.contains("enumerate()"));
+    let Some(expr) = key.expr() else { return };
+    if enumerated && keys_by_index(expr) {
+        findings.push(
+            Finding::new(
+                "L0207",
+                "<For> is keyed by the enumerate() index; inserting or removing an item re-renders every row after it",
+            )
+            .at(code, key.offset)
+            .with_fix("key=|(_, item)| item.id"),
+        );
+    }
+}
+
+/// `|(i, _)| *i` or `|pair| pair.0`: the key is the enumerate() index
+fn keys_by_index(key_expr: &str) -> bool {
+    let Some(params_end) = key_expr[1..].find('|').map(|p| p + 1) else {
+        return false;
+    };
+    let params = key_expr[1..params_end].trim();
+    let body = key_expr[params_end + 1..].trim();
+    let body = body.trim_start_matches(['*', '&']);
+
+    if let Some(tuple) = params.strip_prefix('(') {
+        let index = tuple.split(',').next().unwrap_or("").trim();
+        return !index.is_empty() && index != "_" && body == index;
+    }
+    body == format!("{}.0", params) || body == format!("*{}.0", params)
+}
+
+/// L0208 `.iter().map()` inside a reactive closure that rebuilds the whole list
+fn check_reactive_iteration(code: &str, view: &ViewMacro, findings: &mut Vec<Finding>) {
+    for node in &view.nodes {
+        let Node::Block {
+            offset,
+            code: block,
+        } = node
+        else {
+            continue;
+        };
+        let block = block.trim_start();
+        if !block.starts_with("move ||") {
+            continue;
+        }
+        let iterates = [".iter().map(", ".into_iter().map("]
+            .iter()
+            .find_map(|pattern| block.find(pattern));
+        if let Some(rel) = iterates {
+            let leading = code[*offset + 1..].len() - code[*offset + 1..].trim_start().len();
+            findings.push(
+                Finding::new(
+                    "L0208",
+                    "Reactive list rebuilt with .iter().map() recreates every row on each change; use <For> with a stable key",
+                )
+                .at(code, offset + 1 + leading + rel)
+                .with_fix("<For each=move || items.get() key=|item| item.id children=move |item| view! { ... } />"),
+            );
+        }
+    }
+}
+
+/// L0204 components that are neither defined nor imported in the file
+fn check_component_known(
+    code: &str,
+    element: &Element,
+    known: &HashSet<String>,
+    findings: &mut Vec<Finding>,
+) {
+    if !element.is_component() || element.name.contains("::") {
+        return;
+    }
+    if known.contains(&element.name) || BUILTIN_COMPONENTS.contains(&element.name.as_str()) {
+        return;
+    }
+    findings.push(
+        Finding::new(
+            "L0204",
+            format!(
+                "Component `<{}>` is not defined or imported in this file",
+                element.name
+            ),
+        )
+        .at(code, element.offset)
+        .with_fix(format!("use crate::path::to::{};", element.name)),
+    );
+}
+
+/// L0205 components written as an empty open/close pair
+fn check_empty_components(code: &str, view: &ViewMacro, findings: &mut Vec<Finding>) {
+    for pair in view.nodes.windows(2) {
+        if let [Node::Open(open), Node::Close { name, offset }] = pair {
+            if open.is_component() && !open.self_closing && &open.name == name {
+                let finding = Finding::new(
+                    "L0205",
+                    format!("`<{0}></{0}>` has no children; self-close it", name),
+                )
+                .at(code, open.offset)
+                .with_fix(format!("<{} />", name));
+                let close_end = code[*offset..].find('>').map(|end| offset + end + 1);
+                findings.push(match (tag_end(code, open), close_end) {
+                    (Some(end), Some(close_end)) => finding.with_edit(code, end..close_end, "/>"),
+                    _ => finding,
+                });
+            }
+        }
+    }
+}
+
+/// Component names defined or imported in `code`; `None` if a glob import could bring in anything
+pub(super) fn known_components(code: &str) -> Option<HashSet<String>> {
+    let mut known = HashSet::new();
+
+    for (idx, _) in code.match_indices("use ") {
+        let at_item_start = code[..idx]
+            .chars()
+            .next_back()
+            .is_none_or(|c| c.is_whitespace() || c == ';' || c == '{' || c == '}');
+        if !at_item_start {
+            continue;
+        }
+        let Some(len) = code[idx..].find(';') else {
+            continue;
+        };
+        let statement = &code[idx + 4..idx + len];
+        if statement.contains('*') && !is_leptos_glob(statement) {
+            return None;
+        }
+        known.extend(
+            statement
+                .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .filter(|w| w.starts_with(|c: char| c.is_uppercase()))
+                .map(String::from),
+        );
+    }
+
+    for keyword in ["fn ", "struct ", "enum ", "type "] {
+        for (idx, _) in code.match_indices(keyword) {
+            let name: String = code[idx + keyword.len()..]
+                .chars()
+                .take_while(|c| c.is_alphanumeric() || *c == '_')
+                .collect();
+            if !name.is_empty() {
+                known.insert(name);
+            }
+        }
+    }
+
+    Some(known)
+}
+
+/// Globs from the Leptos crates only bring in the built-in components
+fn is_leptos_glob(statement: &str) -> bool {
+    let statement = statement.trim();
+    [
+        "leptos",
+        "leptos_router",
+        "leptos_meta",
+        "leptos::",
+        "leptos_router::",
+        "leptos_meta::",
+    ]
+    .iter()
+    .any(|prefix| statement.starts_with(prefix))
+}
@@ -0,0 +1,1681 @@
+//! Lint rule registry
+//!
+//! Every autofixer check has a stable id (`L0001`…), a name, a category and
+//! a default severity. A [`RuleConfig`] — from a tool argument or the
+//! `[rules]` table of `leptos-mcp.toml` — can disable rules or override their
+//! severity, individually or a whole category at once. Rule plugins
+//! ([`load_plugins`]) add user-defined pattern rules next to the built-ins.
+
+mod a11y;
+mod attributes;
+mod captures;
+mod contexts;
+mod custom;
+mod effects;
+mod events;
+mod hydration;
+mod markup;
+mod modules;
+mod performance;
+mod routes;
+mod security;
+mod server;
+mod stores;
+mod styling;
+mod suspense;
+mod syntax;
+mod tailwind;
+mod tracking;
+mod types;
+
+pub(crate) use contexts::check_project as check_contexts;
+pub(crate) use contexts::{
+    annotated_type, calls, expression_type, normalize as normalize_type, same_type,
+};
+pub use custom::{load_plugins, Scope};
+pub(crate) use modules::check_project as check_modules;
+pub(crate) use routes::check_project as check_routes;
+pub(crate) use routes::{normalize as normalize_route, route_path, LEAF_ROUTES, PARENT_ROUTES};
+pub(crate) use server::{cfg_ssr_items, SSR_ONLY_CRATES};
+pub use styling::{StyleFramework, Styling};
+
+use crate::docs::{LeptosVersion, Syntax};
+use crate::source;
+use crate::view::{parse_views, Node};
+use crate::workspace::Target;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+/// Name of the configuration file looked up in the project root / working directory
+pub const CONFIG_FILE: &str = "leptos-mcp.toml";
+
+/// How serious a diagnostic is
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Info => "INFO",
+            Self::Warning => "WARNING",
+            Self::Error => "ERROR",
+        }
+    }
+}
+
+/// Group of related rules, usable in place of a rule id in a [`RuleConfig`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Category {
+    /// Reactivity and component code (L00xx)
+    Code,
+    /// Cross-file checks of the project analyzer (L01xx)
+    Project,
+    /// `view!` markup structure (L02xx)
+    Markup,
+    /// Server/client render mismatches (L03xx)
+    Hydration,
+    /// Server functions and server-only code (L04xx)
+    Server,
+    /// Accessibility of `view!` markup (L05xx)
+    A11y,
+    /// Avoidable recomputation and re-rendering (L06xx)
+    Performance,
+    /// Tailwind, stylers and stylance usage, for projects using them (L07xx)
+    Styling,
+    /// Suspense, Transition and resource loading states (L08xx)
+    Async,
+    /// Markup injection, unguarded server functions, leaked secrets and
+    /// permissive CORS or cookie settings (L09xx)
+    Security,
+    /// User-defined rules from plugin files
+    Custom,
+}
+
+impl Category {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Code => "code",
+            Self::Project => "project",
+            Self::Markup => "markup",
+            Self::Hydration => "hydration",
+            Self::Server => "server",
+            Self::A11y => "a11y",
+            Self::Performance => "performance",
+            Self::Styling => "styling",
+            Self::Async => "async",
+            Self::Security => "security",
+            Self::Custom => "custom",
+        }
+    }
+
+    pub fn parse(key: &str) -> Option<Self> {
+        [
+            Self::Code,
+            Self::Project,
+            Self::Markup,
+            Self::Hydration,
+            Self::Server,
+            Self::A11y,
+            Self::Performance,
+            Self::Styling,
+            Self::Async,
+            Self::Security,
+            Self::Custom,
+        ]
+        .into_iter()
+        .find(|c| c.as_str().eq_ignore_ascii_case(key))
+    }
+}
+
+/// A lint rule's identity and defaults
+#[derive(Debug, Clone, Copy, Serialize, JsonSchema)]
+pub struct Rule {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub category: Category,
+    pub default_severity: Severity,
+    pub summary: &'static str,
+    /// Documentation section (`section#heading`), or a plugin rule's link, explaining the fix
+    pub doc: Option<&'static str>,
+    /// Code the rule flags and the same code fixed
+    pub example: Option<Example>,
+}
+
+/// A minimal violation of a rule and its corrected form
+#[derive(Debug, Clone, Copy, Serialize, JsonSchema)]
+pub struct Example {
+    pub bad: &'static str,
+    pub good: &'static str,
+}
+
+/// All built-in rules. File rules are checked in [`check_file`]; project rules
+/// (L01xx) need cross-file context and are checked by the project analyzer;
+/// view! markup rules (L02xx) live in the `markup`, `events` and `attributes` modules, hydration rules
+/// (L03xx) in `hydration`, server function rules (L04xx) in `server`,
+/// accessibility rules (L05xx) in `a11y`, performance hints (L06xx) in
+/// `performance`, styling rules (L07xx), which only run for projects using
+/// the framework they check, in `styling`, async UI rules (L08xx) in
+/// `suspense` and security rules (L09xx) in `security`.
+pub const RULES: &[Rule] = &[
+    Rule {
+        id: "L0001",
+        name: "get-without-move",
+        category: Category::Code,
+        default_severity: Severity::Error,
+        summary: "Signal read with .get() in a view without a `move ||` closure",
+        doc: Some("signals#using-signals-in-views"),
+        example: Some(Example {
+            bad: "view! { <p>{count.get()}</p> }",
+            good: "view! { <p>{move || count.get()}</p> }",
+        }),
+    },
+    Rule {
+        id: "L0002",
+        name: "signal-destructuring",
+        category: Category::Code,
+        default_severity: Severity::Warning,
+        summary: "Signal tuple bound to a single name instead of destructured into getter/setter",
+        doc: Some("signals#creating-signals"),
+        example: Some(Example {
+            bad: "let count = signal(0);",
+            good: "let (count, set_count) = signal(0);",
+        }),
+    },
+    Rule {
+        id: "L0003",
+        name: "missing-component-attr",
+        category: Category::Code,
+        default_severity: Severity::Error,
+        summary: "Function returning `impl IntoView` without #[component]",
+        doc: Some("components#creating-a-component"),
+        example: Some(Example {
+            bad: "fn Header() -> impl IntoView { view! { <h1>\"Hi\"</h1> } }",
+            good: "#[component]\nfn Header() -> impl IntoView { view! { <h1>\"Hi\"</h1> } }",
+        }),
+    },
+    Rule {
+        id: "L0004",
+        name: "server-fn-error-type",
+        category: Category::Code,
+        default_severity: Severity::Info,
+        summary: "Server function that doesn't return Result<T, ServerFnError>",
+        doc: Some("server-functions#error-handling"),
+        example: Some(Example {
+            bad: "#[server]\npub async fn save(name: String) -> String",
+            good: "#[server]\npub async fn save(name: String) -> Result<String, ServerFnError>",
+        }),
+    },
+    Rule {
+        id: "L0005",
+        name: "deprecated-create-signal",
+        category: Category::Code,
+        default_severity: Severity::Info,
+        summary: "create_signal() instead of signal() (Leptos 0.7+)",
+        doc: Some("signals#creating-signals"),
+        example: Some(Example {
+            bad: "let (count, set_count) = create_signal(0);",
+            good: "let (count, set_count) = signal(0);",
+        }),
+    },
+    Rule {
+        id: "L0006",
+        name: "value-moved-into-closure",
+        category: Category::Code,
+        default_severity: Severity::Error,
+        summary: "Non-Copy value moved into a `move` closure and used again afterwards",
+        doc: Some("views#using-a-value-in-several-closures"),
+        example: Some(Example {
+            bad: "let title = move || name.to_uppercase();\nlet empty = move || name.is_empty();",
+            good: "let title = { let name = name.clone(); move || name.to_uppercase() };\nlet empty = move || name.is_empty();",
+        }),
+    },
+    Rule {
+        id: "L0007",
+        name: "effect-derived-state",
+        category: Category::Code,
+        default_severity: Severity::Warning,
+        summary:
+            "Effect whose only job is writing a value computed from other signals into a signal",
+        doc: Some("signals#derived-signals"),
+        example: Some(Example {
+            bad: "Effect::new(move |_| set_double.set(count.get() * 2));",
+            good: "let double = move || count.get() * 2;",
+        }),
+    },
+    Rule {
+        id: "L0008",
+        name: "stale-read-in-body",
+        category: Category::Code,
+        default_severity: Severity::Warning,
+        summary: "Signal read once in the component body and the result shown in the view",
+        doc: Some("signals#reading-in-the-component-body"),
+        example: Some(Example {
+            bad: "let total = count.get();\nview! { <p>{total}</p> }",
+            good: "view! { <p>{move || count.get()}</p> }",
+        }),
+    },
+    Rule {
+        id: "L0009",
+        name: "untracked-in-reactive-closure",
+        category: Category::Code,
+        default_severity: Severity::Warning,
+        summary: "View closure, memo or derived signal whose only signal reads are untracked",
+        doc: Some("signals#untracked-reads"),
+        example: Some(Example {
+            bad: "let label = Memo::new(move |_| count.get_untracked() + 1);",
+            good: "let label = Memo::new(move |_| count.get() + 1);",
+        }),
+    },
+    Rule {
+        id: "L0010",
+        name: "signal-read-in-spawn-local",
+        category: Category::Code,
+        default_severity: Severity::Warning,
+        summary:
+            "Signal read inside spawn_local outside an event handler, so nothing re-runs the task",
+        doc: Some("signals#signals-in-async-tasks"),
+        example: Some(Example {
+            bad: "spawn_local(async move { save(draft.get()).await; });",
+            good: "let saved = LocalResource::new(move || { let draft = draft.get(); async move { save(draft).await } });",
+        }),
+    },
+    Rule {
+        id: "L0011",
+        name: "nested-signal",
+        category: Category::Code,
+        default_severity: Severity::Warning,
+        summary: "Signal holding other signals, directly or through a struct, instead of a store",
+        doc: Some("stores#stores-instead-of-nested-signals"),
+        example: Some(Example {
+            bad: "let todos = RwSignal::new(vec![RwSignal::new(Todo::default())]);",
+            good: "#[derive(Store)]\nstruct State { todos: Vec<Todo> }\nlet state = Store::new(State::default());",
+        }),
+    },
+    Rule {
+        id: "L0012",
+        name: "nightly-signal-call",
+        category: Category::Code,
+        default_severity: Severity::Error,
+        summary: "Signal called as a function (`count()`, `set_count(1)`) in a stable project",
+        doc: Some("signals#nightly-syntax"),
+        example: Some(Example {
+            bad: "view! { <p>{count}</p> <button on:click=move |_| set_count(count() + 1)/> }",
+            good: "view! { <p>{count}</p> <button on:click=move |_| set_count.set(count.get() + 1)/> }",
+        }),
+    },
+    Rule {
+        id: "L0013",
+        name: "into-view-type-erasure",
+        category: Category::Code,
+        default_severity: Severity::Warning,
+        summary: "0.6 `.into_view()` or `View` type used to erase a view's type (Leptos 0.7+: `.into_any()`/`AnyView`)",
+        doc: Some("views#views-as-values"),
+        example: Some(Example {
+            bad: "fn badge(admin: bool) -> View { if admin { view! { <b>\"Admin\"</b> }.into_view() } else { view! { <i>\"User\"</i> }.into_view() } }",
+            good: "fn badge(admin: bool) -> AnyView { if admin { view! { <b>\"Admin\"</b> }.into_any() } else { view! { <i>\"User\"</i> }.into_any() } }",
+        }),
+    },
+    Rule {
+        id: "L0014",
+        name: "node-ref-cast",
+        category: Category::Code,
+        default_severity: Severity::Warning,
+        summary: "NodeRef element cast with dyn_into/unchecked_into, though node_ref= already types it",
+        doc: Some("views#element-references"),
+        example: Some(Example {
+            bad: "let input = input_ref.get().unwrap().dyn_into::<HtmlInputElement>().unwrap();\nview! { <input node_ref=input_ref/> }",
+            good: "let input = input_ref.get().unwrap();\nview! { <input node_ref=input_ref/> }",
+        }),
+    },
+    Rule {
+        id: "L0100",
+        name: "unknown-server-action",
+        category: Category::Project,
+        default_severity: Severity::Error,
+        summary: "ServerAction::<T> naming a type no #[server] function generates",
+        doc: Some("actions#creating-an-action"),
+        example: Some(Example {
+            bad: "let save = ServerAction::<SavePost>::new(); // no #[server] fn save_post",
+            good: "#[server]\npub async fn save_post(title: String) -> Result<(), ServerFnError> { ... }\nlet save = ServerAction::<SavePost>::new();",
+        }),
+    },
+    Rule {
+        id: "L0101",
+        name: "unawaited-server-fn",
+        category: Category::Project,
+        default_severity: Severity::Error,
+        summary: "Server function called as a statement without .await",
+        doc: Some("server-functions#basic-server-function"),
+        example: Some(Example {
+            bad: "save_post(title);",
+            good: "spawn_local(async move { let _ = save_post(title).await; });",
+        }),
+    },
+    Rule {
+        id: "L0102",
+        name: "context-without-provider",
+        category: Category::Project,
+        default_severity: Severity::Error,
+        summary: "expect_context::<T>() with no provide_context of T in any component rendering it",
+        doc: Some("components#sharing-state-with-context"),
+        example: Some(Example {
+            bad: "let theme = expect_context::<Theme>(); // nothing provides Theme",
+            good: "provide_context(Theme::default()); // in a component rendering this one\nlet theme = expect_context::<Theme>();",
+        }),
+    },
+    Rule {
+        id: "L0103",
+        name: "duplicate-context-provider",
+        category: Category::Project,
+        default_severity: Severity::Warning,
+        summary:
+            "Context type provided again in the same component or below another provider of it",
+        doc: Some("components#sharing-state-with-context"),
+        example: Some(Example {
+            bad: "provide_context(Theme::Light);\nprovide_context(Theme::Dark);",
+            good: "provide_context(RwSignal::new(Theme::Light));\n// children update it with expect_context::<RwSignal<Theme>>().set(Theme::Dark)",
+        }),
+    },
+    Rule {
+        id: "L0104",
+        name: "conditional-context-provider",
+        category: Category::Project,
+        default_severity: Severity::Info,
+        summary:
+            "expect_context::<T>() where T is only provided inside an if/match; use use_context",
+        doc: Some("components#conditional-providers"),
+        example: Some(Example {
+            bad: "if signed_in { provide_context(user) }\n// in a child:\nlet user = expect_context::<User>();",
+            good: "let user = use_context::<User>(); // None when not signed in",
+        }),
+    },
+    Rule {
+        id: "L0105",
+        name: "component-not-in-scope",
+        category: Category::Project,
+        default_severity: Severity::Error,
+        summary: "Component defined in another file whose module isn't declared with `mod` or that isn't imported with `use`",
+        doc: Some("components#creating-a-component"),
+        example: Some(Example {
+            bad: "// src/app.rs\nview! { <Header/> } // Header is in src/header.rs, never declared",
+            good: "// src/lib.rs\nmod header;\n// src/app.rs\nuse crate::header::Header;\nview! { <Header/> }",
+        }),
+    },
+    Rule {
+        id: "L0106",
+        name: "ssr-only-server-fn-call",
+        category: Category::Project,
+        default_severity: Severity::Error,
+        summary: "#[server] function only compiled with the `ssr` feature, called from code built for the client",
+        doc: Some("server-functions#ssr-only-dependencies"),
+        example: Some(Example {
+            bad: "#[cfg(feature = \"ssr\")]\n#[server]\npub async fn load() -> Result<Data, ServerFnError> { ... }",
+            good: "#[server]\npub async fn load() -> Result<Data, ServerFnError> { ... }",
+        }),
+    },
+    Rule {
+        id: "L0107",
+        name: "duplicate-route-path",
+        category: Category::Project,
+        default_severity: Severity::Warning,
+        summary: "Route path declared more than once, in one router or across files",
+        doc: Some("router/nested-routes"),
+        example: Some(Example {
+            bad: "<Route path=path!(\"/posts\") view=Posts/>\n<Route path=path!(\"/posts\") view=Archive/>",
+            good: "<Route path=path!(\"/posts\") view=Posts/>\n<Route path=path!(\"/archive\") view=Archive/>",
+        }),
+    },
+    Rule {
+        id: "L0108",
+        name: "server-crate-in-client-crate",
+        category: Category::Project,
+        default_severity: Severity::Error,
+        summary: "Server-only crate used outside `ssr` gating in a workspace crate the WASM client also compiles (a shared or frontend crate)",
+        doc: Some("server-functions#ssr-only-dependencies"),
+        example: Some(Example {
+            bad: "// shared/src/models.rs\npub struct Cache(tokio::sync::Mutex<Vec<Todo>>);",
+            good: "// shared/src/models.rs\n#[cfg(feature = \"ssr\")]\npub struct Cache(tokio::sync::Mutex<Vec<Todo>>);",
+        }),
+    },
+    Rule {
+        id: "L0200",
+        name: "unclosed-tag",
+        category: Category::Markup,
+        default_severity: Severity::Error,
+        summary: "Tag in view! that is never closed or closed with the wrong name",
+        doc: Some("views#closing-tags"),
+        example: Some(Example {
+            bad: "view! { <div><p>\"Hello\"</div> }",
+            good: "view! { <div><p>\"Hello\"</p></div> }",
+        }),
+    },
+    Rule {
+        id: "L0201",
+        name: "void-element-not-self-closed",
+        category: Category::Markup,
+        default_severity: Severity::Error,
+        summary: "Void HTML element like <input> written without `/>`",
+        doc: Some("views#void-elements"),
+        example: Some(Example {
+            bad: "view! { <input type=\"text\"> }",
+            good: "view! { <input type=\"text\"/> }",
+        }),
+    },
+    Rule {
+        id: "L0202",
+        name: "conditional-class-string",
+        category: Category::Markup,
+        default_severity: Severity::Warning,
+        summary: "class= computed with if/else instead of class:name= or class=(name, signal)",
+        doc: Some("views#dynamic-classes"),
+        example: Some(Example {
+            bad: "<div class=move || if active.get() { \"tab active\" } else { \"tab\" }>",
+            good: "<div class=\"tab\" class:active=move || active.get()>",
+        }),
+    },
+    Rule {
+        id: "L0203",
+        name: "handler-missing-move",
+        category: Category::Markup,
+        default_severity: Severity::Error,
+        summary: "on: event handler closure without `move`",
+        doc: Some("views#event-handlers"),
+        example: Some(Example {
+            bad: "<button on:click=|_| set_count.update(|n| *n += 1)>",
+            good: "<button on:click=move |_| set_count.update(|n| *n += 1)>",
+        }),
+    },
+    Rule {
+        id: "L0204",
+        name: "unknown-component",
+        category: Category::Markup,
+        default_severity: Severity::Warning,
+        summary: "Component used in view! but not defined or imported in the file",
+        doc: Some("components#creating-a-component"),
+        example: Some(Example {
+            bad: "view! { <UserCard user=user/> } // UserCard not imported",
+            good: "use crate::components::UserCard;\nview! { <UserCard user=user/> }",
+        }),
+    },
+    Rule {
+        id: "L0205",
+        name: "empty-component-pair",
+        category: Category::Markup,
+        default_severity: Severity::Info,
+        summary: "Component written as <Foo></Foo> instead of self-closing <Foo/>",
+        doc: Some("views#self-closing-components"),
+        example: Some(Example {
+            bad: "view! { <Spinner></Spinner> }",
+            good: "view! { <Spinner/> }",
+        }),
+    },
+    Rule {
+        id: "L0206",
+        name: "for-missing-key",
+        category: Category::Markup,
+        default_severity: Severity::Error,
+        summary: "<For> without a `key` prop",
+        doc: Some("views#iterating-lists"),
+        example: Some(Example {
+            bad: "<For each=move || todos.get() children=|todo| view! { <li>{todo.title}</li> }/>",
+            good: "<For each=move || todos.get() key=|todo| todo.id children=|todo| view! { <li>{todo.title}</li> }/>",
+        }),
+    },
+    Rule {
+        id: "L0207",
+        name: "for-index-key",
+        category: Category::Markup,
+        default_severity: Severity::Warning,
+        summary: "<For> keyed by the loop index instead of a stable item id",
+        doc: Some("views#iterating-lists"),
+        example: Some(Example {
+            bad: "<For each=move || todos.get().into_iter().enumerate() key=|(i, _)| *i children=...>",
+            good: "<For each=move || todos.get().into_iter().enumerate() key=|(_, todo)| todo.id children=...>",
+        }),
+    },
+    Rule {
+        id: "L0208",
+        name: "reactive-iter-map",
+        category: Category::Markup,
+        default_severity: Severity::Warning,
+        summary: ".iter().map() rebuilding a reactive list inside view! instead of <For>",
+        doc: Some("views#iterating-lists"),
+        example: Some(Example {
+            bad: "{move || todos.get().iter().map(|t| view! { <li>{t.title.clone()}</li> }).collect_view()}",
+            good: "<For each=move || todos.get() key=|t| t.id children=|t| view! { <li>{t.title}</li> }/>",
+        }),
+    },
+    Rule {
+        id: "L0209",
+        name: "handler-event-type",
+        category: Category::Markup,
+        default_severity: Severity::Error,
+        summary: "on: handler annotated with another event's type, e.g. a MouseEvent for on:input",
+        doc: Some("views#event-types"),
+        example: Some(Example {
+            bad: "<input on:input=move |ev: ev::MouseEvent| set_name.set(event_target_value(&ev))/>",
+            good: "<input on:input=move |ev| set_name.set(event_target_value(&ev))/>",
+        }),
+    },
+    Rule {
+        id: "L0210",
+        name: "target-value-non-input",
+        category: Category::Markup,
+        default_severity: Severity::Warning,
+        summary: "event_target_value/event_target_checked in a handler whose target isn't a form control",
+        doc: Some("views#reading-input-values"),
+        example: Some(Example {
+            bad: "<button on:click=move |ev| set_name.set(event_target_value(&ev))>\"Save\"</button>",
+            good: "<input on:input=move |ev| set_name.set(event_target_value(&ev))/>",
+        }),
+    },
+    Rule {
+        id: "L0211",
+        name: "submit-without-prevent-default",
+        category: Category::Markup,
+        default_severity: Severity::Warning,
+        summary: "<form> submit handler that runs client logic without ev.prevent_default()",
+        doc: Some("forms#submit-handlers"),
+        example: Some(Example {
+            bad: "<form on:submit=move |_| save.dispatch(name.get())>",
+            good: "<form on:submit=move |ev: ev::SubmitEvent| { ev.prevent_default(); save.dispatch(name.get()); }>",
+        }),
+    },
+    Rule {
+        id: "L0212",
+        name: "borrowed-handler-capture",
+        category: Category::Markup,
+        default_severity: Severity::Error,
+        summary: "on: handler using a reference borrowed from the function creating it",
+        doc: Some("views#borrowed-values-in-handlers"),
+        example: Some(Example {
+            bad: "fn row(label: &str, pick: WriteSignal<String>) -> impl IntoView { view! { <button on:click=move |_| pick.set(label.to_string())>\"Pick\"</button> } }",
+            good: "fn row(label: &str, pick: WriteSignal<String>) -> impl IntoView { let label = label.to_owned(); view! { <button on:click=move |_| pick.set(label.clone())>\"Pick\"</button> } }",
+        }),
+    },
+    Rule {
+        id: "L0213",
+        name: "manual-attribute-forwarding",
+        category: Category::Markup,
+        default_severity: Severity::Info,
+        summary: "Component prop like `class` or `aria_label` that only sets the same attribute on its root element",
+        doc: Some("components#replacing-forwarding-props"),
+        example: Some(Example {
+            bad: "#[component] fn Card(class: String, children: Children) -> impl IntoView { view! { <div class=class>{children()}</div> } }\nview! { <Card class=\"wide\">\"Hi\"</Card> }",
+            good: "#[component] fn Card(children: Children) -> impl IntoView { view! { <div>{children()}</div> } }\nview! { <Card {..} class=\"wide\">\"Hi\"</Card> }",
+        }),
+    },
+    Rule {
+        id: "L0214",
+        name: "prop-attrs-removed",
+        category: Category::Markup,
+        default_severity: Severity::Error,
+        summary: "`#[prop(attrs)]` from Leptos 0.6, which 0.7 replaced with attributes passed straight through",
+        doc: Some("components#replacing-forwarding-props"),
+        example: Some(Example {
+            bad: "#[component] fn Card(#[prop(attrs)] attrs: Vec<(&'static str, Attribute)>) -> impl IntoView { view! { <div {..attrs}/> } }",
+            good: "#[component] fn Card() -> impl IntoView { view! { <div/> } }\nview! { <Card attr:id=\"main\"/> }",
+        }),
+    },
+    Rule {
+        id: "L0300",
+        name: "browser-api-in-render",
+        category: Category::Hydration,
+        default_severity: Severity::Error,
+        summary:
+            "window()/document() called while rendering instead of in an Effect or event handler",
+        doc: Some("hydration#browser-apis"),
+        example: Some(Example {
+            bad: "let width = window().inner_width().unwrap();",
+            good: "Effect::new(move |_| set_width.set(window().inner_width().unwrap().as_f64().unwrap_or(0.0)));",
+        }),
+    },
+    Rule {
+        id: "L0301",
+        name: "nondeterministic-render",
+        category: Category::Hydration,
+        default_severity: Severity::Warning,
+        summary: "Random or time-dependent value rendered, which differs between server and client",
+        doc: Some("hydration#non-deterministic-values"),
+        example: Some(Example {
+            bad: "view! { <p>{rand::random::<u32>()}</p> }",
+            good: "let n = Resource::new(|| (), |_| random_number()); // computed on the server",
+        }),
+    },
+    Rule {
+        id: "L0302",
+        name: "cfg-dependent-markup",
+        category: Category::Hydration,
+        default_severity: Severity::Error,
+        summary: "cfg!(feature = \"ssr\") choosing what to render, causing a hydration mismatch",
+        doc: Some("hydration#server-only-markup"),
+        example: Some(Example {
+            bad: "{if cfg!(feature = \"ssr\") { \"Loading\" } else { \"Ready\" }}",
+            good: "let (ready, set_ready) = signal(false);\nEffect::new(move |_| set_ready.set(true));\n{move || if ready.get() { \"Ready\" } else { \"Loading\" }}",
+        }),
+    },
+    Rule {
+        id: "L0400",
+        name: "ssr-crate-outside-cfg",
+        category: Category::Server,
+        default_severity: Severity::Error,
+        summary:
+            "Server-only crate used outside a #[server] body or #[cfg(feature = \"ssr\")] item",
+        doc: Some("server-functions#ssr-only-dependencies"),
+        example: Some(Example {
+            bad: "use sqlx::PgPool; // at the top of a file built for the client",
+            good: "#[cfg(feature = \"ssr\")]\nuse sqlx::PgPool;",
+        }),
+    },
+    Rule {
+        id: "L0401",
+        name: "extractor-from-context",
+        category: Category::Server,
+        default_severity: Severity::Error,
+        summary: "Request extractor read with use_context/expect_context instead of extract()",
+        doc: Some("server-functions#extractors-axum"),
+        example: Some(Example {
+            bad: "let headers = expect_context::<HeaderMap>();",
+            good: "let headers: HeaderMap = leptos_axum::extract().await?;",
+        }),
+    },
+    Rule {
+        id: "L0402",
+        name: "blocking-in-server-fn",
+        category: Category::Server,
+        default_severity: Severity::Warning,
+        summary: "Blocking I/O or sleep inside an async server function",
+        doc: Some("server-functions#blocking-work"),
+        example: Some(Example {
+            bad: "let text = std::fs::read_to_string(\"data.json\")?;",
+            good: "let text = tokio::fs::read_to_string(\"data.json\").await?;",
+        }),
+    },
+    Rule {
+        id: "L0403",
+        name: "unserializable-server-type",
+        category: Category::Server,
+        default_severity: Severity::Error,
+        summary: "Server function argument or return type that can't be serialized",
+        doc: Some("server-functions#dtos-data-transfer-objects"),
+        example: Some(Example {
+            bad: "pub async fn find(db: &PgPool) -> Result<Rc<User>, ServerFnError>",
+            good: "pub async fn find(id: u32) -> Result<UserDto, ServerFnError>",
+        }),
+    },
+    Rule {
+        id: "L0500",
+        name: "img-missing-alt",
+        category: Category::A11y,
+        default_severity: Severity::Warning,
+        summary: "<img> without an `alt` attribute",
+        doc: Some("accessibility#image-alt-text"),
+        example: Some(Example {
+            bad: "<img src=\"/logo.png\"/>",
+            good: "<img src=\"/logo.png\" alt=\"Acme logo\"/>",
+        }),
+    },
+    Rule {
+        id: "L0501",
+        name: "click-on-non-interactive",
+        category: Category::A11y,
+        default_severity: Severity::Warning,
+        summary: "on:click on a non-interactive element without a `role` and a keyboard handler",
+        doc: Some("accessibility#clickable-elements"),
+        example: Some(Example {
+            bad: "<div on:click=move |_| open()>\"Details\"</div>",
+            good: "<button on:click=move |_| open()>\"Details\"</button>",
+        }),
+    },
+    Rule {
+        id: "L0502",
+        name: "input-missing-label",
+        category: Category::A11y,
+        default_severity: Severity::Warning,
+        summary: "Form control without a <label>, aria-label or aria-labelledby",
+        doc: Some("accessibility#form-labels"),
+        example: Some(Example {
+            bad: "<input type=\"email\" placeholder=\"Email\"/>",
+            good: "<label>\"Email\" <input type=\"email\"/></label>",
+        }),
+    },
+    Rule {
+        id: "L0503",
+        name: "component-missing-aria",
+        category: Category::A11y,
+        default_severity: Severity::Info,
+        summary: "Icon, button or dialog component used without an accessible name",
+        doc: Some("accessibility#accessible-names-for-components"),
+        example: Some(Example {
+            bad: "<IconButton icon=Icon::Trash/>",
+            good: "<IconButton icon=Icon::Trash attr:aria-label=\"Delete\"/>",
+        }),
+    },
+    Rule {
+        id: "L0600",
+        name: "expensive-view-closure",
+        category: Category::Performance,
+        default_severity: Severity::Info,
+        summary: "Sorting, looping or an iterator pipeline inside a view closure instead of a Memo",
+        doc: Some("signals#derived-signals"),
+        example: Some(Example {
+            bad: "{move || { let mut v = items.get(); v.sort(); v.len() }}",
+            good: "let sorted = Memo::new(move |_| { let mut v = items.get(); v.sort(); v });",
+        }),
+    },
+    Rule {
+        id: "L0601",
+        name: "vec-signal-cloned-in-view",
+        category: Category::Performance,
+        default_severity: Severity::Warning,
+        summary: "Vec signal cloned in a view to measure it, or fed to a <For> whose rows are edited in place",
+        doc: Some("views#iterating-lists"),
+        example: Some(Example {
+            bad: "{move || items.get().len()}",
+            good: "{move || items.with(|v| v.len())}",
+        }),
+    },
+    Rule {
+        id: "L0602",
+        name: "whole-struct-read",
+        category: Category::Performance,
+        default_severity: Severity::Info,
+        summary: "Struct signal cloned with .get() in a view to read a single field",
+        doc: Some("signals#reading-one-field"),
+        example: Some(Example {
+            bad: "{move || user.get().name}",
+            good: "{move || user.with(|u| u.name.clone())}",
+        }),
+    },
+    Rule {
+        id: "L0603",
+        name: "large-struct-signal",
+        category: Category::Performance,
+        default_severity: Severity::Info,
+        summary: "Struct with many fields kept whole in one signal and cloned with .get()",
+        doc: Some("stores#when-to-use-a-store"),
+        example: Some(Example {
+            bad: "let form = RwSignal::new(Form::default()); // 12 fields\nview! { <p>{move || form.get().email}</p> }",
+            good: "#[derive(Store)]\nstruct Form { email: String, /* ... */ }\nlet form = Store::new(Form::default());\nview! { <p>{move || form.email().get()}</p> }",
+        }),
+    },
+    Rule {
+        id: "L0700",
+        name: "unknown-tailwind-class",
+        category: Category::Styling,
+        default_severity: Severity::Warning,
+        summary: "Class that is neither a Tailwind utility nor defined in the project's theme or CSS",
+        doc: Some("meta/stylesheet#tailwind"),
+        example: Some(Example {
+            bad: "<div class=\"flex itms-center\">",
+            good: "<div class=\"flex items-center\">",
+        }),
+    },
+    Rule {
+        id: "L0701",
+        name: "conditional-tailwind-class",
+        category: Category::Styling,
+        default_severity: Severity::Info,
+        summary: "class= string picked by a condition instead of class: toggles for the classes that change",
+        doc: Some("views#class-name-variations"),
+        example: Some(Example {
+            bad: "class=move || if open.get() { \"px-4 bg-blue-500\" } else { \"px-4 bg-gray-200\" }",
+            good: "class=\"px-4\" class:bg-blue-500=move || open.get() class:bg-gray-200=move || !open.get()",
+        }),
+    },
+    Rule {
+        id: "L0702",
+        name: "interpolated-tailwind-class",
+        category: Category::Styling,
+        default_severity: Severity::Warning,
+        summary: "Class name assembled with format!, which Tailwind can't see to generate",
+        doc: Some("meta/stylesheet#tailwind"),
+        example: Some(Example {
+            bad: "class=format!(\"text-{}-500\", color)",
+            good: "class=match color { Color::Red => \"text-red-500\", Color::Blue => \"text-blue-500\" }",
+        }),
+    },
+    Rule {
+        id: "L0703",
+        name: "unknown-stylance-class",
+        category: Category::Styling,
+        default_severity: Severity::Error,
+        summary: "stylance class constant the imported stylesheet doesn't define",
+        doc: Some("meta/stylesheet#scoped-styles"),
+        example: Some(Example {
+            bad: "import_style!(style, \"card.module.css\");\nview! { <div class=style::cardd/> }",
+            good: "import_style!(style, \"card.module.css\");\nview! { <div class=style::card/> }",
+        }),
+    },
+    Rule {
+        id: "L0704",
+        name: "stylers-class-not-applied",
+        category: Category::Styling,
+        default_severity: Severity::Warning,
+        summary: "stylers style! class never applied with `class = name,` in a view!",
+        doc: Some("meta/stylesheet#scoped-styles"),
+        example: Some(Example {
+            bad: "let class_name = style! { .card { padding: 1rem; } };\nview! { <div class=\"card\"/> }",
+            good: "let class_name = style! { .card { padding: 1rem; } };\nview! { class = class_name, <div class=\"card\"/> }",
+        }),
+    },
+    Rule {
+        id: "L0800",
+        name: "read-outside-suspense",
+        category: Category::Async,
+        default_severity: Severity::Warning,
+        summary: "Resource read or Suspend::new in a view with no <Suspense> or <Transition> above it",
+        doc: Some("suspense#reading-resources-outside-a-boundary"),
+        example: Some(Example {
+            bad: "view! { <ul>{move || Suspend::new(async move { list(posts.await) })}</ul> }",
+            good: "view! { <Suspense fallback=|| \"Loading...\"><ul>{move || Suspend::new(async move { list(posts.await) })}</ul></Suspense> }",
+        }),
+    },
+    Rule {
+        id: "L0801",
+        name: "unwrapped-loading-state",
+        category: Category::Async,
+        default_severity: Severity::Warning,
+        summary: "Resource .get() unwrapped, which panics while it is still loading",
+        doc: Some("suspense#handling-the-loading-state"),
+        example: Some(Example {
+            bad: "{move || data.get().unwrap().title}",
+            good: "{move || data.get().map(|d| d.title)}",
+        }),
+    },
+    Rule {
+        id: "L0802",
+        name: "redundant-nested-suspense",
+        category: Category::Async,
+        default_severity: Severity::Info,
+        summary: "<Suspense>/<Transition> whose only child is another boundary, so its fallback never shows",
+        doc: Some("suspense#nested-suspense"),
+        example: Some(Example {
+            bad: "<Suspense fallback=|| \"Loading page...\"><Suspense fallback=|| \"Loading...\"><Posts/></Suspense></Suspense>",
+            good: "<Suspense fallback=|| \"Loading...\"><Posts/></Suspense>",
+        }),
+    },
+    Rule {
+        id: "L0803",
+        name: "suspend-misuse",
+        category: Category::Async,
+        default_severity: Severity::Warning,
+        summary: "Suspend::new that awaits nothing, or placed in a view without a `move ||` closure",
+        doc: Some("suspense#suspend"),
+        example: Some(Example {
+            bad: "{Suspend::new(async move { view! { <p>{user.await.name}</p> } })}",
+            good: "{move || Suspend::new(async move { view! { <p>{user.await.name}</p> } })}",
+        }),
+    },
+    Rule {
+        id: "L0900",
+        name: "unsanitized-inner-html",
+        category: Category::Security,
+        default_severity: Severity::Warning,
+        summary: "inner_html or set_inner_html given a value that isn't a literal or sanitized, which may inject user-supplied markup",
+        doc: Some("security#injecting-html"),
+        example: Some(Example {
+            bad: "view! { <div inner_html=move || comment.get().body/> }",
+            good: "view! { <div inner_html=move || ammonia::clean(&comment.get().body)/> }",
+        }),
+    },
+    Rule {
+        id: "L0901",
+        name: "server-fn-without-auth",
+        category: Category::Security,
+        default_severity: Severity::Warning,
+        summary: "#[server] function that inserts, updates or deletes data without checking the caller first",
+        doc: Some("security#authorizing-server-functions"),
+        example: Some(Example {
+            bad: "#[server]\npub async fn delete_post(id: i64) -> Result<(), ServerFnError> {\n    sqlx::query(\"DELETE FROM posts WHERE id = $1\").bind(id).execute(&pool()?).await?;\n    Ok(())\n}",
+            good: "#[server]\npub async fn delete_post(id: i64) -> Result<(), ServerFnError> {\n    let user = current_user().await?.ok_or_else(|| ServerFnError::new(\"Sign in first\"))?;\n    sqlx::query(\"DELETE FROM posts WHERE id = $1 AND author_id = $2\").bind(id).bind(user.id).execute(&pool()?).await?;\n    Ok(())\n}",
+        }),
+    },
+    Rule {
+        id: "L0902",
+        name: "secret-in-client-build",
+        category: Category::Security,
+        default_severity: Severity::Error,
+        summary: "Secret such as an API key read with env!/option_env! in code the WASM client also compiles",
+        doc: Some("security#secrets"),
+        example: Some(Example {
+            bad: "const STRIPE_KEY: &str = env!(\"STRIPE_SECRET_KEY\");",
+            good: "#[server]\npub async fn charge(cents: u32) -> Result<(), ServerFnError> {\n    let key = std::env::var(\"STRIPE_SECRET_KEY\")?;\n    ...\n}",
+        }),
+    },
+    Rule {
+        id: "L0903",
+        name: "permissive-cors",
+        category: Category::Security,
+        default_severity: Severity::Warning,
+        summary: "CORS layer allowing any origin, e.g. CorsLayer::permissive() or allow_origin(Any)",
+        doc: Some("security#cors"),
+        example: Some(Example {
+            bad: ".layer(CorsLayer::permissive())",
+            good: ".layer(CorsLayer::new().allow_origin(\"https://app.example.com\".parse::<HeaderValue>().unwrap()).allow_methods([Method::GET, Method::POST]))",
+        }),
+    },
+    Rule {
+        id: "L0904",
+        name: "insecure-cookie",
+        category: Category::Security,
+        default_severity: Severity::Warning,
+        summary: "Cookie set with http_only(false), secure(false) or SameSite::None, or a session cookie without HttpOnly and Secure",
+        doc: Some("security#cookies"),
+        example: Some(Example {
+            bad: "Cookie::build((\"session\", id)).path(\"/\")",
+            good: "Cookie::build((\"session\", id)).path(\"/\").http_only(true).secure(true).same_site(SameSite::Lax)",
+        }),
+    },
+];
+
+/// Rules about APIs a Leptos release introduced, which don't apply before it
+const SINCE: &[(&str, LeptosVersion)] = &[
+    ("L0005", LeptosVersion::V0_7),
+    ("L0011", LeptosVersion::V0_7),
+    ("L0013", LeptosVersion::V0_7),
+    ("L0014", LeptosVersion::V0_7),
+    ("L0213", LeptosVersion::V0_7),
+    ("L0214", LeptosVersion::V0_7),
+    ("L0603", LeptosVersion::V0_7),
+    ("L0803", LeptosVersion::V0_7),
+];
+
+/// The built-in rules followed by the ones loaded from plugins
+pub fn all_rules() -> Vec<&'static Rule> {
+    RULES
+        .iter()
+        .chain(custom::rules().into_iter().map(|c| &c.rule))
+        .collect()
+}
+
+/// Look up a rule by id (`L0001`) or name (`get-without-move`)
+pub fn find_rule(key: &str) -> Option<&'static Rule> {
+    all_rules()
+        .into_iter()
+        .find(|r| r.id.eq_ignore_ascii_case(key) || r.name.eq_ignore_ascii_case(key))
+}
+
+/// Whether a config key (rule id, rule name or category) selects `rule`
+fn selects(key: &str, rule: &Rule) -> bool {
+    find_rule(key).is_some_and(|r| r.id == rule.id) || Category::parse(key) == Some(rule.category)
+}
+
+/// A rule match before configuration is applied
+#[derive(Debug, Clone)]
+pub struct Finding {
+    pub rule_id: &'static str,
+    pub message: String,
+    /// 1-based line, if the rule can pinpoint the problem
+    pub line: Option<usize>,
+    /// 1-based column in characters
+    pub column: Option<usize>,
+    /// Replacement code or instruction that resolves the finding
+    pub fix: Option<String>,
+    /// The fix as a rewrite `apply-fix` can make, for mechanical ones
+    pub edit: Option<Edit>,
+}
+
+/// A rewrite of source code: `original`, starting at `line` and `column`,
+/// replaced with `replacement`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edit {
+    /// 1-based line
+    pub line: usize,
+    /// 1-based column in characters
+    pub column: usize,
+    pub original: String,
+    pub replacement: String,
+}
+
+impl Edit {
+    /// `code` with the edit made, or `None` if `original` is no longer
+    /// where it was
+    pub fn apply(&self, code: &str) -> Option<String> {
+        let line_start = if self.line == 1 {
+            0
+        } else {
+            code.match_indices('\n').nth(self.line - 2)?.0 + 1
+        };
+        let start = match code[line_start..].char_indices().nth(self.column - 1) {
+            Some((at, _)) => line_start + at,
+            None => code.len(),
+        };
+        if !code[start..].starts_with(&self.original) {
+            return None;
+        }
+        let end = start + self.original.len();
+        Some(format!(
+            "{}{}{}",
+            &code[..start],
+            self.replacement,
+            &code[end..]
+        ))
+    }
+
+    /// `other`, an edit of the same code, moved to where its text is once
+    /// this edit is made; `None` if this edit rewrites part of it
+    pub fn shift(&self, other: &Edit) -> Option<Edit> {
+        let start = (self.line, self.column);
+        let old_end = end_position(start, &self.original);
+        let new_end = end_position(start, &self.replacement);
+        let at = (other.line, other.column);
+        if end_position(at, &other.original) <= start {
+            return Some(other.clone());
+        }
+        if at < old_end {
+            return None;
+        }
+        let (line, column) = if at.0 == old_end.0 {
+            (new_end.0, at.1 - old_end.1 + new_end.1)
+        } else {
+            (at.0 - old_end.0 + new_end.0, at.1)
+        };
+        Some(Edit {
+            line,
+            column,
+            ..other.clone()
+        })
+    }
+}
+
+/// Line and column right after `text` written at `start`
+fn end_position(start: (usize, usize), text: &str) -> (usize, usize) {
+    match text.rsplit_once('\n') {
+        Some((before, last)) => (
+            start.0 + before.matches('\n').count() + 1,
+            last.chars().count() + 1,
+        ),
+        None => (start.0, start.1 + text.chars().count()),
+    }
+}
+
+impl Finding {
+    pub fn new(rule_id: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            rule_id,
+            message: message.into(),
+            line: None,
+            column: None,
+            fix: None,
+            edit: None,
+        }
+    }
+
+    /// Locate the finding at a byte offset of `code`
+    pub fn at(mut self, code: &str, offset: usize) -> Self {
+        let (line, column) = position(code, offset);
+        self.line = Some(line);
+        self.column = Some(column);
+        self
+    }
+
+    pub fn with_fix(mut self, fix: impl Into<String>) -> Self {
+        self.fix = Some(fix.into());
+        self
+    }
+
+    /// Make the fix mechanical: replace `range` of `code` with `replacement`
+    pub fn with_edit(
+        mut self,
+        code: &str,
+        range: Range<usize>,
+        replacement: impl Into<String>,
+    ) -> Self {
+        let (line, column) = position(code, range.start);
+        self.edit = Some(Edit {
+            line,
+            column,
+            original: code[range].to_string(),
+            replacement: replacement.into(),
+        });
+        self
+    }
+}
+
+/// 1-based line and character column of a byte offset
+pub fn position(code: &str, offset: usize) -> (usize, usize) {
+    let before = &code[..offset.min(code.len())];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (line, before[line_start..].chars().count() + 1)
+}
+
+/// A finding with its effective severity
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct Diagnostic {
+    pub rule_id: &'static str,
+    pub severity: Severity,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fix: Option<String>,
+    /// Documentation section explaining the rule, for get-documentation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub doc: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub example: Option<Example>,
+    /// Handle `apply-fix` makes the fix with, for mechanical fixes found by
+    /// an analysis of files on disk
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(skip)]
+    pub edit: Option<Edit>,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} [{}]", self.severity.as_str(), self.rule_id)?;
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(f, " {}:{}", line, column)?,
+            (Some(line), None) => write!(f, " line {}", line)?,
+            _ => {}
+        }
+        write!(f, ": {}", self.message)?;
+        if let Some(fix) = &self.fix {
+            write!(f, "\n  Suggested fix: {}", fix)?;
+        }
+        if let Some(id) = &self.id {
+            write!(f, "\n  Apply: apply-fix id=\"{}\"", id)?;
+        }
+        // Multi-line examples only fit explain-rule's output
+        if let Some(example) = self
+            .example
+            .filter(|e| !e.bad.contains('\n') && !e.good.contains('\n'))
+        {
+            write!(f, "\n  Example: `{}` -> `{}`", example.bad, example.good)?;
+        }
+        match self.doc {
+            Some(url) if url.starts_with("http") => write!(f, "\n  Docs: {}", url)?,
+            Some(doc) => write!(f, "\n  Docs: get-documentation section=\"{}\"", doc)?,
+            None => {}
+        }
+        Ok(())
+    }
+}
+
+/// Rule enable/disable and severity overrides
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct RuleConfig {
+    /// Rule ids, names or categories to skip
+    pub disable: Vec<String>,
+    /// Severity overrides keyed by rule id, name or category
+    pub severity: HashMap<String, Severity>,
+    /// Rule plugin files loaded at startup; only read from the server's config
+    #[schemars(skip)]
+    pub plugins: Vec<PathBuf>,
+}
+
+#[derive(Deserialize)]
+struct ConfigFile {
+    #[serde(default)]
+    rules: RuleConfig,
+}
+
+impl RuleConfig {
+    /// Read the `[rules]` table of a `leptos-mcp.toml`, or the default config if the file is absent
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        if !path.is_file() {
+            return Ok(Self::default());
+        }
+        let raw = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let file: ConfigFile =
+            toml::from_str(&raw).map_err(|e| format!("Invalid {}: {}", path.display(), e))?;
+        Ok(file.rules)
+    }
+
+    /// Disable the rules that don't apply to a project on `version`
+    pub fn for_version(version: LeptosVersion) -> Self {
+        Self {
+            disable: SINCE
+                .iter()
+                .filter(|(_, since)| version < *since)
+                .map(|(id, _)| id.to_string())
+                .collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Disable the rules that can't fire in a workspace crate built only for `target`
+    pub fn for_target(target: Target) -> Self {
+        let disable: &[&str] = match target {
+            // Nothing to hydrate, and nothing server-only to keep out
+            Target::Server => &["hydration", "L0400", "L0106", "L0902"],
+            // Server function bodies are never compiled here
+            Target::Client => &["L0401", "L0402", "L0901"],
+            Target::Shared => &[],
+        };
+        Self {
+            disable: disable.iter().map(|key| key.to_string()).collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Disable the rules against syntax a project on `syntax` may use
+    pub fn for_syntax(syntax: Syntax) -> Self {
+        Self {
+            disable: match syntax {
+                Syntax::Stable => Vec::new(),
+                Syntax::Nightly => vec!["L0012".to_string()],
+            },
+            ..Self::default()
+        }
+    }
+
+    /// Layer `other` on top of this config
+    pub fn merge(&mut self, other: RuleConfig) {
+        self.disable.extend(other.disable);
+        self.severity.extend(other.severity);
+        self.plugins.extend(other.plugins);
+    }
+
+    /// Reject unknown rule keys so typos don't silently do nothing
+    pub fn validate(&self) -> Result<(), String> {
+        let unknown: Vec<&str> = self
+            .disable
+            .iter()
+            .chain(self.severity.keys())
+            .filter(|key| find_rule(key).is_none() && Category::parse(key).is_none())
+            .map(|key| key.as_str())
+            .collect();
+        if unknown.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("Unknown lint rule(s): {}", unknown.join(", ")))
+        }
+    }
+
+    fn is_disabled(&self, rule: &Rule) -> bool {
+        self.disable.iter().any(|key| selects(key, rule))
+    }
+
+    /// A rule's own override wins over its category's
+    fn severity_for(&self, rule: &Rule) -> Severity {
+        let by_rule = self
+            .severity
+            .iter()
+            .find(|(key, _)| find_rule(key).is_some_and(|r| r.id == rule.id));
+        let by_category = || {
+            self.severity
+                .iter()
+                .find(|(key, _)| Category::parse(key) == Some(rule.category))
+        };
+        by_rule
+            .or_else(by_category)
+            .map_or(rule.default_severity, |(_, severity)| *severity)
+    }
+
+    /// Drop disabled findings and resolve effective severities
+    pub fn apply(&self, findings: Vec<Finding>) -> Vec<Diagnostic> {
+        findings
+            .into_iter()
+            .filter_map(|finding| {
+                let rule = find_rule(finding.rule_id)?;
+                if self.is_disabled(rule) {
+                    return None;
+                }
+                Some(Diagnostic {
+                    rule_id: rule.id,
+                    severity: self.severity_for(rule),
+                    message: finding.message,
+                    line: finding.line,
+                    column: finding.column,
+                    fix: finding.fix,
+                    doc: rule.doc,
+                    example: rule.example,
+                    id: None,
+                    edit: finding.edit,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Run every single-file rule over a piece of code
+pub fn check_file(code: &str) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    for offset in unwrapped_gets(code) {
+        findings.push(
+            Finding::new(
+                "L0001",
+                "Found .get() in view without `move ||`. \
+                 Reactive values should use `{move || value.get()}`",
+            )
+            .at(code, offset)
+            .with_fix("Wrap the expression in a closure: `{move || value.get()}`"),
+        );
+    }
+
+    for (offset, name) in undestructured_signals(code) {
+        findings.push(
+            Finding::new(
+                "L0002",
+                "Consider using `let (getter, setter) = signal(value)` pattern for clarity",
+            )
+            .at(code, offset)
+            .with_fix(format!("let ({}, set_{}) = signal(...);", name, name)),
+        );
+    }
+
+    for offset in unannotated_components(code) {
+        let line_start = code[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = code[offset..].find('\n').map_or(code.len(), |i| offset + i);
+        let line = &code[line_start..line_end];
+        let indent = &line[..line.len() - line.trim_start().len()];
+        let item = line_start + indent.len();
+        findings.push(
+            Finding::new(
+                "L0003",
+                "Functions returning `impl IntoView` should have #[component] attribute",
+            )
+            .at(code, offset)
+            .with_fix("Add `#[component]` above the function")
+            .with_edit(
+                code,
+                item..line_end,
+                format!("#[component]\n{}{}", indent, &code[item..line_end]),
+            ),
+        );
+    }
+
+    if !code.contains("ServerFnError") {
+        if let Some(offset) = code.find("#[server") {
+            findings.push(
+                Finding::new(
+                    "L0004",
+                    "Server functions should return Result<T, ServerFnError>",
+                )
+                .at(code, offset)
+                .with_fix("Change the return type to `Result<T, ServerFnError>`"),
+            );
+        }
+    }
+
+    for (offset, name) in code.match_indices("create_signal") {
+        let mut finding = Finding::new(
+            "L0005",
+            "In Leptos 0.8+, use `signal()` instead of `create_signal()`",
+        )
+        .at(code, offset)
+        .with_fix("Replace `create_signal(...)` with `signal(...)`");
+        let rest = &code[offset + name.len()..];
+        if rest.starts_with('(') || rest.starts_with("::<") {
+            finding = finding.with_edit(code, offset..offset + name.len(), "signal");
+        }
+        findings.push(finding);
+    }
+
+    findings.extend(captures::check(code));
+    findings.extend(effects::check(code));
+    findings.extend(tracking::check(code));
+    findings.extend(stores::check(code));
+    findings.extend(syntax::check(code));
+    findings.extend(types::check(code));
+    findings.extend(markup::check(code));
+    findings.extend(events::check(code));
+    findings.extend(attributes::check(code));
+    findings.extend(hydration::check(code));
+    findings.extend(server::check(code));
+    findings.extend(a11y::check(code));
+    findings.extend(performance::check(code));
+    findings.extend(suspense::check(code));
+    findings.extend(security::check(code));
+    findings.extend(custom::check(code));
+    findings
+}
+
+/// Run the single-file rules plus the styling rules `styling` enables
+///
+/// A conditional class string the styling rules can rewrite for Tailwind
+/// (L0701) replaces the generic L0202 finding at the same attribute.
+pub fn check_styled(code: &str, styling: &Styling) -> Vec<Finding> {
+    let mut findings = check_file(code);
+    if styling.is_empty() {
+        return findings;
+    }
+    let styled = styling::check(code, styling);
+    findings.retain(|f| {
+        f.rule_id != "L0202"
+            || !styled
+                .iter()
+                .any(|s| s.rule_id == "L0701" && (s.line, s.column) == (f.line, f.column))
+    });
+    findings.extend(styled);
+    findings
+}
+
+/// Each line paired with the byte offset it starts at
+pub fn lines_with_offsets(code: &str) -> impl Iterator<Item = (usize, &str)> {
+    code.split_inclusive('\n').scan(0, |offset, line| {
+        let start = *offset;
+        *offset += line.len();
+        Some((start, line.trim_end_matches(['\n', '\r'])))
+    })
+}
+
+/// Offsets of `.get()` calls in `view!` children and attribute values that
+/// no closure wraps, so they read once while rendering and never update
+///
+/// Event handlers are skipped: they run on events, not while rendering.
+fn unwrapped_gets(code: &str) -> Vec<usize> {
+    let masked = source::mask_literals(code);
+    let closures: Vec<Range<usize>> = source::closures(code).iter().map(|c| c.range()).collect();
+    let mut expressions = Vec::new();
+    for view in parse_views(code) {
+        for node in &view.nodes {
+            match node {
+                Node::Block {
+                    offset,
+                    code: block,
+                } => {
+                    expressions.push(offset + 1..offset + 1 + block.len());
+                }
+                Node::Open(element) => {
+                    for attr in &element.attrs {
+                        let Some(value) = &attr.value else {
+                            continue;
+                        };
+                        if attr.name.starts_with("on:") {
+                            continue;
+                        }
+                        let after_name = attr.offset + attr.name.len();
+                        let Some(eq) = code[after_name..].find('=') else {
+                            continue;
+                        };
+                        let rest = &code[after_name + eq + 1..];
+                        let start = code.len() - rest.trim_start().len();
+                        expressions.push(start..start + value.len());
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    // A nested view's blocks also lie inside the outer view's block
+    let mut offsets: Vec<usize> = expressions
+        .into_iter()
+        .flat_map(|range| {
+            masked[range.clone()]
+                .match_indices(".get()")
+                .map(move |(o, _)| range.start + o)
+                .collect::<Vec<_>>()
+        })
+        .filter(|&o| !source::within(&closures, o))
+        .collect();
+    offsets.sort_unstable();
+    offsets.dedup();
+    offsets
+}
+
+/// `let s = signal(0);` binds the (getter, setter) tuple to one name
+fn undestructured_signals(code: &str) -> Vec<(usize, String)> {
+    lines_with_offsets(code)
+        .filter_map(|(offset, line)| {
+            let indent = line.len() - line.trim_start().len();
+            let rest = line.trim().strip_prefix("let ")?;
+            let (binding, value) = rest.split_once('=')?;
+            let binding = binding.trim().trim_start_matches("mut ");
+            let value = value.trim_start();
+            let is_signal = value.starts_with("signal(") || value.starts_with("create_signal(");
+            (!binding.starts_with('(') && is_signal).then(|| {
+                (
+                    offset + indent,
+                    binding
+                        .trim_end_matches(|c: char| c == ':' || c.is_whitespace())
+                        .to_string(),
+                )
+            })
+        })
+        .collect()
+}
+
+/// Functions returning `impl IntoView` that aren't preceded by `#[component]`
+fn unannotated_components(code: &str) -> Vec<usize> {
+    let lines: Vec<(usize, &str)> = lines_with_offsets(code).collect();
+    lines
+        .iter()
+        .enumerate()
+        .filter(|(_, (_, line))| line.contains("fn ") && line.contains("-> impl IntoView"))
+        // The SSR `shell(options: LeptosOptions)` is a plain function by convention
+        .filter(|(_, (_, line))| !line.contains("LeptosOptions"))
+        .filter(|(idx, _)| {
+            // Walk back over attributes, doc comments and blank lines
+            !lines[..*idx]
+                .iter()
+                .rev()
+                .map(|(_, l)| l.trim())
+                .take_while(|l| l.is_empty() || l.starts_with("#[") || l.starts_with("///"))
+                .any(|l| l.starts_with("#[component") || l.starts_with("#[island"))
+        })
+        .map(|(_, (offset, line))| offset + line.find("fn ").unwrap_or(0))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edit(line: usize, column: usize, original: &str, replacement: &str) -> Edit {
+        Edit {
+            line,
+            column,
+            original: original.to_string(),
+            replacement: replacement.to_string(),
+        }
+    }
+
+    fn l0001_lines(code: &str) -> Vec<usize> {
+        check_file(code)
+            .iter()
+            .filter(|f| f.rule_id == "L0001")
+            .filter_map(|f| f.line)
+            .collect()
+    }
+
+    #[test]
+    fn get_in_view_needs_a_closure() {
+        let handler = "view! {\n    <button on:click=move |_| set_count.set(count.get() + 1)>\"+1\"</button>\n}";
+        assert!(l0001_lines(handler).is_empty());
+        let wrapped =
+            "view! {\n    <p class:big=move || count.get() > 9>{move || count.get()}</p>\n}";
+        assert!(l0001_lines(wrapped).is_empty());
+        let mapped = "view! { <ul>{items.iter().map(|i| i.get()).collect_view()}</ul> }";
+        assert!(l0001_lines(mapped).is_empty());
+        // A closure elsewhere in the view doesn't excuse these reads
+        let unwrapped = "view! {\n    <p>{move || a.get()}</p>\n    <p>{b.get()}</p>\n    <input prop:value=c.get()/>\n}";
+        assert_eq!(l0001_lines(unwrapped), vec![3, 4]);
+    }
+
+    #[test]
+    fn edit_applies_at_its_position() {
+        let code = "let (a, set_a) = create_signal(0);\nlet b = create_signal(1);\n";
+        let fixed = edit(2, 9, "create_signal", "signal").apply(code).unwrap();
+        assert_eq!(
+            fixed,
+            "let (a, set_a) = create_signal(0);\nlet b = signal(1);\n"
+        );
+        let fixed = edit(1, 18, "create_signal", "signal").apply(code).unwrap();
+        assert!(fixed.starts_with("let (a, set_a) = signal(0);"));
+    }
+
+    #[test]
+    fn edit_columns_count_characters() {
+        let code = "let é = \"ü\"; foo();";
+        let fixed = edit(1, 14, "foo", "bar").apply(code).unwrap();
+        assert_eq!(fixed, "let é = \"ü\"; bar();");
+    }
+
+    #[test]
+    fn edit_refuses_moved_text() {
+        let code = "a\nb\n";
+        assert_eq!(edit(1, 1, "b", "c").apply(code), None);
+        assert_eq!(edit(5, 1, "b", "c").apply(code), None);
+        // Past the end of the line
+        assert_eq!(edit(1, 9, "b", "c").apply(code), None);
+    }
+
+    #[test]
+    fn edit_from_finding_round_trips() {
+        let code = "fn f() {\n    x.get_untracked();\n}\n";
+        let start = code.find("get_untracked").unwrap();
+        let finding =
+            Finding::new("L0000", "m").with_edit(code, start..start + "get_untracked".len(), "get");
+        let edit = finding.edit.unwrap();
+        assert_eq!((edit.line, edit.column), (2, 7));
+        assert_eq!(edit.apply(code).unwrap(), "fn f() {\n    x.get();\n}\n");
+    }
+
+    #[test]
+    fn shift_moves_later_edits() {
+        let first = edit(1, 5, "create_signal", "signal");
+        // Before the edit: unchanged
+        let before = edit(1, 1, "let", "const");
+        assert_eq!(first.shift(&before), Some(before.clone()));
+        // Later on the same line: moved left by the shrinkage
+        let same_line = edit(1, 22, "x", "y");
+        assert_eq!(first.shift(&same_line), Some(edit(1, 15, "x", "y")));
+        // Later lines: unchanged by a one-line edit
+        let next_line = edit(3, 2, "x", "y");
+        assert_eq!(first.shift(&next_line), Some(next_line.clone()));
+        // Overlapping: can't be made after the first
+        assert_eq!(first.shift(&edit(1, 10, "signal", "s")), None);
+    }
+
+    #[test]
+    fn shift_follows_line_changes() {
+        let code = "a(b);\nc(d); e;\nf;\n";
+        // Splits line 1 in two
+        let split = edit(1, 3, "b", "\n  b\n");
+        let on_next_line = edit(2, 7, "e", "E");
+        let shifted = split.shift(&on_next_line).unwrap();
+        assert_eq!((shifted.line, shifted.column), (4, 7));
+        // Right after the edit on its own line
+        let after_on_line = edit(1, 4, ")", "]");
+        let shifted = split.shift(&after_on_line).unwrap();
+        assert_eq!((shifted.line, shifted.column), (3, 1));
+
+        let once = split.apply(code).unwrap();
+        let twice = split.shift(&on_next_line).unwrap().apply(&once).unwrap();
+        let twice = split.shift(&after_on_line).unwrap().apply(&twice).unwrap();
+        assert_eq!(twice, "a(\n  b\n];\nc(d); E;\nf;\n");
+    }
+}
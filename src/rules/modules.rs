@@ -0,0 +1,371 @@
+//! Module and feature checks across files (L0105–L0106, L0108)
+//!
+//! A file's module path follows from where it sits under `src/`:
+//! `app/src/pages/home.rs` is `pages::home` of the crate in `app`. That is
+//! enough to tell whether a component defined in one file is reachable from
+//! another (its module declared with `mod`, its name imported with `use`),
+//! and whether a server function only exists in the `ssr` build. Checks only
+//! run where the files involved were given; a parent module that wasn't
+//! passed could declare anything. In a workspace, the same `ssr` gating
+//! decides whether a crate the client compiles can use server-only crates.
+
+use super::markup::known_components;
+use super::server::{cfg_ssr_items, server_only_paths};
+use super::Finding;
+use crate::project::SourceFile;
+use crate::source::{attributes_before, functions, mask_literals, within};
+use crate::view::{parse_views, Node};
+use crate::workspace::{Target, Workspace};
+use std::collections::HashMap;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+/// Crate directory and module path of a source file
+///
+/// `src/lib.rs` and `src/main.rs` are the crate root (an empty path);
+/// `src/pages/mod.rs` and `src/pages.rs` are both `pages`.
+fn module_of(path: &Path) -> (PathBuf, Vec<String>) {
+    let parts: Vec<String> = path
+        .components()
+        .filter_map(|c| c.as_os_str().to_str())
+        .filter(|c| *c != ".")
+        .map(String::from)
+        .collect();
+    let (krate, rest) = match parts.iter().rposition(|p| p == "src") {
+        Some(src) => (parts[..src].iter().collect(), &parts[src + 1..]),
+        None => (PathBuf::new(), &parts[..]),
+    };
+    let mut module: Vec<String> = rest.to_vec();
+    if let Some(last) = module.pop() {
+        let stem = last.strip_suffix(".rs").unwrap_or(&last);
+        let root = module.is_empty() && matches!(stem, "lib" | "main");
+        if !root && stem != "mod" {
+            module.push(stem.to_string());
+        }
+    }
+    (krate, module)
+}
+
+/// Rust name of the crate in `dir`, for `use` paths from another crate
+fn crate_name(dir: &Path) -> String {
+    dir.file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("crate")
+        .replace('-', "_")
+}
+
+/// Whether attribute lines gate an item behind the `ssr` feature
+fn gated_ssr(attrs: &[String]) -> bool {
+    attrs.iter().any(|attr| {
+        let attr: String = attr.chars().filter(|c| !c.is_whitespace()).collect();
+        attr.contains("cfg(feature=\"ssr\")")
+    })
+}
+
+/// Offset of the `mod name;` declaration in `code`, if any
+fn mod_declaration(code: &str, name: &str) -> Option<usize> {
+    let mut offset = 0;
+    for line in code.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        let item = ["pub(crate) ", "pub(super) ", "pub "]
+            .iter()
+            .find_map(|vis| trimmed.strip_prefix(vis))
+            .unwrap_or(trimmed);
+        if item
+            .strip_prefix("mod ")
+            .is_some_and(|rest| rest.trim_start().trim_end().trim_end_matches(';') == name)
+        {
+            return Some(offset + line.len() - trimmed.len());
+        }
+        offset += line.len();
+    }
+    None
+}
+
+struct Files<'a> {
+    files: &'a [SourceFile],
+    modules: Vec<(PathBuf, Vec<String>)>,
+}
+
+impl Files<'_> {
+    /// Files of the module `module` in crate `krate`: both `lib.rs` and
+    /// `main.rs` for a crate root
+    fn find(&self, krate: &Path, module: &[String]) -> Vec<usize> {
+        (0..self.files.len())
+            .filter(|&i| self.modules[i].0 == krate && self.modules[i].1 == module)
+            .collect()
+    }
+
+    /// Where file `index` should be declared: the parent module's files
+    /// and the `mod` name, or `None` for a crate root
+    fn parent(&self, index: usize) -> Option<(Vec<usize>, &str)> {
+        let (krate, module) = &self.modules[index];
+        let (name, parent) = module.split_last()?;
+        Some((self.find(krate, parent), name))
+    }
+
+    /// The parent module's file that should declare file `index` with `mod`
+    /// but doesn't, and the module name
+    fn undeclared(&self, index: usize) -> Option<(usize, &str)> {
+        let (parents, name) = self.parent(index)?;
+        let declared = parents
+            .iter()
+            .any(|&p| mod_declaration(&self.files[p].content, name).is_some());
+        (!parents.is_empty() && !declared).then(|| (parents[0], name))
+    }
+
+    /// Why file `index` is only compiled with the `ssr` feature, if it is
+    fn ssr_only(&self, index: usize) -> Option<String> {
+        let code = &self.files[index].content;
+        let inner: String = code.chars().filter(|c| !c.is_whitespace()).collect();
+        if inner.contains("#![cfg(feature=\"ssr\")]") {
+            return Some(format!(
+                "{} is `#![cfg(feature = \"ssr\")]`",
+                self.path(index)
+            ));
+        }
+        match self.parent(index) {
+            Some((parents, name)) => parents.iter().find_map(|&p| {
+                let parent = &self.files[p].content;
+                let offset = mod_declaration(parent, name)?;
+                if gated_ssr(&attributes_before(parent, offset)) {
+                    Some(format!(
+                        "`mod {}` in {} is `#[cfg(feature = \"ssr\")]`",
+                        name,
+                        self.path(p)
+                    ))
+                } else {
+                    self.ssr_only(p)
+                }
+            }),
+            // A binary whose `main` is always an async server
+            None => functions(code)
+                .iter()
+                .any(|f| {
+                    f.name == "main"
+                        && (f.has_attr("tokio::main") || f.has_attr("actix_web::main"))
+                        && !gated_ssr(&f.attrs)
+                })
+                .then(|| format!("{} is a server binary", self.path(index))),
+        }
+    }
+
+    fn path(&self, index: usize) -> String {
+        self.files[index].path.display().to_string()
+    }
+}
+
+/// Findings of the module rules, by file path
+pub(crate) fn check_project(
+    files: &[SourceFile],
+    workspace: Option<&Workspace>,
+) -> HashMap<PathBuf, Vec<Finding>> {
+    let tree = Files {
+        files,
+        modules: files.iter().map(|f| module_of(&f.path)).collect(),
+    };
+    // Component name to the file defining it; names defined twice are ambiguous
+    let mut defined: HashMap<String, Option<usize>> = HashMap::new();
+    // Server functions that don't exist in the client build, with the reason
+    let mut server_only: Vec<(String, String, usize)> = Vec::new();
+    for (i, file) in files.iter().enumerate() {
+        let items = functions(&file.content);
+        for item in items.iter().filter(|f| f.is_component()) {
+            defined
+                .entry(item.name.clone())
+                .and_modify(|d| *d = d.filter(|d| *d == i))
+                .or_insert(Some(i));
+        }
+        let file_reason = tree.ssr_only(i);
+        for item in items.iter().filter(|f| f.has_attr("server")) {
+            let reason = if gated_ssr(&item.attrs) {
+                Some(format!(
+                    "it is `#[cfg(feature = \"ssr\")]` in {}",
+                    tree.path(i)
+                ))
+            } else {
+                file_reason.clone()
+            };
+            if let Some(reason) = reason {
+                server_only.push((item.name.clone(), reason, i));
+            }
+        }
+    }
+
+    let mut findings: HashMap<PathBuf, Vec<Finding>> = HashMap::new();
+    for (index, file) in files.iter().enumerate() {
+        let mut found = check_scope(&tree, &defined, index);
+        if tree.ssr_only(index).is_none() {
+            if !server_only.is_empty() {
+                found.extend(check_ssr_calls(&tree, &server_only, index));
+            }
+            if let Some(member) = workspace.and_then(|w| w.member_of(&file.path)) {
+                if let Some(target) = member.target.filter(Target::is_client) {
+                    found.extend(check_client_crate(file, &member.name, target));
+                }
+            }
+        }
+        if !found.is_empty() {
+            findings.entry(file.path.clone()).or_default().extend(found);
+        }
+    }
+    findings
+}
+
+/// L0105 components defined in another file that this one can't reach
+fn check_scope(
+    tree: &Files,
+    defined: &HashMap<String, Option<usize>>,
+    index: usize,
+) -> Vec<Finding> {
+    let code = &tree.files[index].content;
+    let known = known_components(code);
+    let mut findings = Vec::new();
+    for view in parse_views(code) {
+        for node in &view.nodes {
+            let Node::Open(element) = node else {
+                continue;
+            };
+            if !element.is_component() || element.name.contains("::") {
+                continue;
+            }
+            let Some(&Some(home)) = defined.get(&element.name) else {
+                continue;
+            };
+            if home == index {
+                continue;
+            }
+            if let Some((parent, name)) = tree.undeclared(home) {
+                findings.push(
+                    Finding::new(
+                        "L0105",
+                        format!(
+                            "`<{}>` is defined in {}, but {} has no `mod {};`, so that file isn't compiled",
+                            element.name,
+                            tree.path(home),
+                            tree.path(parent),
+                            name
+                        ),
+                    )
+                    .at(code, element.offset)
+                    .with_fix(format!("Add `pub mod {};` to {}", name, tree.path(parent))),
+                );
+            } else if known
+                .as_ref()
+                .is_some_and(|known| !known.contains(&element.name))
+            {
+                let (krate, module) = &tree.modules[home];
+                let root = if *krate == tree.modules[index].0 {
+                    "crate".to_string()
+                } else {
+                    crate_name(krate)
+                };
+                let path: Vec<&str> = std::iter::once(root.as_str())
+                    .chain(module.iter().map(String::as_str))
+                    .chain(std::iter::once(element.name.as_str()))
+                    .collect();
+                findings.push(
+                    Finding::new(
+                        "L0105",
+                        format!(
+                            "`<{}>` is defined in {} but not imported in this file",
+                            element.name,
+                            tree.path(home)
+                        ),
+                    )
+                    .at(code, element.offset)
+                    .with_fix(format!("use {};", path.join("::"))),
+                );
+            }
+        }
+    }
+    findings
+}
+
+/// L0106 server functions only compiled with `ssr` called from client code
+fn check_ssr_calls(
+    tree: &Files,
+    server_only: &[(String, String, usize)],
+    index: usize,
+) -> Vec<Finding> {
+    let code = &tree.files[index].content;
+    let masked = mask_literals(code);
+    // Bodies that run on the server anyway
+    let server_side: Vec<Range<usize>> = functions(code)
+        .into_iter()
+        .filter(|f| f.has_attr("server") || gated_ssr(&f.attrs))
+        .map(|f| f.body)
+        .collect();
+    let bytes = masked.as_bytes();
+    let mut findings = Vec::new();
+    for (name, reason, home) in server_only {
+        for (offset, _) in masked.match_indices(name.as_str()) {
+            let end = offset + name.len();
+            let called = masked[end..].trim_start().starts_with('(');
+            let bounded = (offset == 0
+                || !(bytes[offset - 1].is_ascii_alphanumeric() || bytes[offset - 1] == b'_'))
+                && bytes
+                    .get(end)
+                    .is_none_or(|b| !(b.is_ascii_alphanumeric() || *b == b'_'));
+            let definition = masked[..offset].trim_end().ends_with("fn");
+            if !called || !bounded || definition || within(&server_side, offset) {
+                continue;
+            }
+            findings.push(
+                Finding::new(
+                    "L0106",
+                    format!(
+                        "`{}` (declared in {}) only exists in the `ssr` build because {}, but this file is also compiled for the client",
+                        name,
+                        tree.path(*home),
+                        reason
+                    ),
+                )
+                .at(code, offset)
+                .with_fix(
+                    "Drop the `ssr` gate around the #[server] function: the macro already keeps its body on the server and generates the client-side call",
+                ),
+            );
+        }
+    }
+    findings
+}
+
+/// L0108 server-only crates in a workspace crate the client compiles
+///
+/// Files with `#[server]` functions are left to L0400, which covers them
+/// whatever crate they're in.
+fn check_client_crate(file: &SourceFile, krate: &str, target: Target) -> Vec<Finding> {
+    let code = &file.content;
+    if code.contains("#[server") {
+        return Vec::new();
+    }
+    let masked = mask_literals(code);
+    let (built_by, fix) = match target {
+        Target::Client => (
+            "is built for the WASM client",
+            "Move the code to the server crate and reach it through a #[server] function".to_string(),
+        ),
+        _ => (
+            "is shared with the WASM client",
+            format!(
+                "Gate the item with `#[cfg(feature = \"ssr\")]`, with an `ssr` feature of `{}` that the server crate enables, or move it to the server crate",
+                krate
+            ),
+        ),
+    };
+    server_only_paths(&masked, &cfg_ssr_items(code, &masked))
+        .into_iter()
+        .map(|(offset, used)| {
+            Finding::new(
+                "L0108",
+                format!(
+                    "`{}` only builds for the server, but crate `{}` {}, so its WASM build will fail",
+                    used, krate, built_by
+                ),
+            )
+            .at(code, offset)
+            .with_fix(fix.clone())
+        })
+        .collect()
+}
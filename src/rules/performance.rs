@@ -0,0 +1,255 @@
+//! Performance hints for `view!` closures (L0600–L0602)
+//!
+//! A `move ||` closure in a view re-runs every time a signal it reads
+//! changes, and re-renders what it returns. Work that is expensive to redo,
+//! whole lists cloned to be measured or fed to `<For>`, and whole structs
+//! cloned to show one field make those re-runs cost more than they need to.
+//! All are in the `performance` category, so a project can turn them off
+//! together.
+
+use super::tracking::receiver_before;
+use super::Finding;
+use crate::reactivity::{handlers, sources, ReactiveNode};
+use crate::source::{closures, identifier_uses, mask_literals, within, Closure};
+use crate::view::{parse_views, Node, ViewMacro};
+use std::collections::HashSet;
+use std::ops::Range;
+
+/// Iterator adapters and consumers; two or more in one closure make it a computation
+const ITERATOR_STEPS: &[&str] = &[
+    ".filter(",
+    ".map(",
+    ".filter_map(",
+    ".flat_map(",
+    ".fold(",
+    ".sum",
+    ".product",
+    ".count()",
+    ".collect",
+    ".zip(",
+    ".rev()",
+    ".max",
+    ".min",
+    ".position(",
+    ".find(",
+    ".any(",
+    ".all(",
+];
+
+/// Calls that are expensive on their own
+const EXPENSIVE_CALLS: &[&str] = &[".sort", ".dedup", "for ", "while "];
+
+/// Vec methods that only need to look at the list
+const VEC_QUERIES: &[&str] = &["len()", "is_empty()", "first()", "last()", "contains("];
+
+/// Signs of an item changed in place inside `update`/`write`
+const ITEM_EDITS: &[&str] = &["iter_mut", "get_mut", "last_mut", "first_mut", "]."];
+
+/// Reads that subscribe the closure
+const TRACKED_READS: &[&str] = &[".get()", ".with(", ".read()"];
+
+/// Run every performance rule over `code`
+pub fn check(code: &str) -> Vec<Finding> {
+    let masked = mask_literals(code);
+    let all_closures = closures(code);
+    let signals = sources(code, &masked, 0..code.len());
+    let mut findings = Vec::new();
+    for view in parse_views(code) {
+        let handlers = handlers(code, &view);
+        let body = view.body_start..view.body_end;
+        let reactive: Vec<&Closure> = all_closures
+            .iter()
+            .filter(|c| body.contains(&c.start))
+            .filter(|c| !c.has_params() && !within(&handlers, c.start))
+            .collect();
+        for closure in &reactive {
+            check_expensive(code, &masked, closure, &mut findings);
+        }
+        check_vec_clones(code, &masked, body.clone(), &handlers, &mut findings);
+        check_for_rows(code, &masked, &view, &signals, &mut findings);
+        check_struct_reads(code, &masked, body, &handlers, &mut findings);
+    }
+    findings
+}
+
+/// L0600 a view closure that sorts, loops or runs an iterator pipeline
+fn check_expensive(code: &str, masked: &str, closure: &Closure, findings: &mut Vec<Finding>) {
+    let body = &masked[closure.body.clone()];
+    // Closures building markup are L0208's concern
+    if body.contains("view!") || !TRACKED_READS.iter().any(|r| body.contains(r)) {
+        return;
+    }
+    let iterates = body.contains(".iter()") || body.contains(".into_iter()");
+    let steps = ITERATOR_STEPS
+        .iter()
+        .filter(|step| body.contains(*step))
+        .count();
+    let expensive = EXPENSIVE_CALLS.iter().any(|call| body.contains(call));
+    if !(expensive || (iterates && steps >= 2)) {
+        return;
+    }
+    findings.push(
+        Finding::new(
+            "L0600",
+            "View closure recomputes this on every change of what it reads and re-renders even when the result is the same; memoize it",
+        )
+        .at(code, closure.start)
+        .with_fix(format!(
+            "let value = Memo::new(move |_| {});  and render {{value}}",
+            code[closure.body.clone()].trim()
+        )),
+    );
+}
+
+/// L0601 `items.get().len()` in a view: the whole list cloned to measure it
+fn check_vec_clones(
+    code: &str,
+    masked: &str,
+    body: Range<usize>,
+    handlers: &[Range<usize>],
+    findings: &mut Vec<Finding>,
+) {
+    let mut reported = HashSet::new();
+    for (rel, _) in masked[body.clone()].match_indices(".get().") {
+        let offset = body.start + rel;
+        let after = &masked[offset + ".get().".len()..body.end];
+        let Some(method) = VEC_QUERIES.iter().find(|m| after.starts_with(*m)) else {
+            continue;
+        };
+        let list = receiver_before(masked, offset);
+        if within(handlers, offset) || list.is_empty() || !reported.insert(list.to_string()) {
+            continue;
+        }
+        let call = match method.strip_suffix('(') {
+            Some(name) => format!("{}(..)", name),
+            None => method.to_string(),
+        };
+        let method = method.trim_end_matches(['(', ')']);
+        findings.push(
+            Finding::new(
+                "L0601",
+                format!(
+                    "`{}.get().{}()` clones the whole list on every change just to call `{}`",
+                    list, method, method
+                ),
+            )
+            .at(code, offset - list.len())
+            .with_fix(format!(
+                "{}.with(|items| items.{})  or  {}.read().{}  reads it without cloning",
+                list, call, list, call
+            )),
+        );
+    }
+}
+
+/// L0601 `<For each=move || items.get()>` over a list whose items are edited in place
+fn check_for_rows(
+    code: &str,
+    masked: &str,
+    view: &ViewMacro,
+    signals: &[ReactiveNode],
+    findings: &mut Vec<Finding>,
+) {
+    for node in &view.nodes {
+        let Node::Open(element) = node else { continue };
+        if element.name != "For" {
+            continue;
+        }
+        let Some(each) = element.attr("each") else {
+            continue;
+        };
+        let Some(list) = each
+            .expr()
+            .and_then(|e| e.strip_prefix("move"))
+            .and_then(|e| e.trim_start().strip_prefix("||"))
+            .and_then(|e| e.trim().strip_suffix(".get()"))
+        else {
+            continue;
+        };
+        let Some(signal) = signals.iter().find(|s| s.name == list) else {
+            continue;
+        };
+        if !edits_items(masked, signal) {
+            continue;
+        }
+        findings.push(
+            Finding::new(
+                "L0601",
+                format!(
+                    "`<For>` clones all of `{}` on every change, and rows edited in place keep their key, so `<For>` doesn't re-render them",
+                    list
+                ),
+            )
+            .at(code, each.offset)
+            .with_fix(format!(
+                "Keep the list in a `#[derive(Store)]` struct and iterate the field, `each=move || state.{}()`, reading row fields through the store (`row.title()`) so an edited row updates in place",
+                list
+            )),
+        );
+    }
+}
+
+/// Whether `signal`'s items are mutated through `update`/`write` rather than replaced
+fn edits_items(masked: &str, signal: &ReactiveNode) -> bool {
+    let names = std::iter::once(&signal.name).chain(signal.setter.as_ref());
+    names
+        .flat_map(|name| {
+            identifier_uses(masked, 0..masked.len(), name)
+                .into_iter()
+                .map(move |offset| &masked[offset + name.len()..])
+        })
+        .any(|after| {
+            let edit = if let Some(args) = after.strip_prefix(".update(") {
+                let end = args.find(");").unwrap_or(args.len());
+                &args[..end]
+            } else if let Some(rest) = after.strip_prefix(".write()") {
+                rest.split(';').next().unwrap_or_default()
+            } else {
+                return false;
+            };
+            ITEM_EDITS.iter().any(|e| edit.contains(e))
+        })
+}
+
+/// L0602 `user.get().name` in a view: the whole struct cloned for one field
+fn check_struct_reads(
+    code: &str,
+    masked: &str,
+    body: Range<usize>,
+    handlers: &[Range<usize>],
+    findings: &mut Vec<Finding>,
+) {
+    let mut reported = HashSet::new();
+    for (rel, _) in masked[body.clone()].match_indices(".get().") {
+        let offset = body.start + rel;
+        if within(handlers, offset) {
+            continue;
+        }
+        let after = &masked[offset + ".get().".len()..body.end];
+        let field: String = after
+            .chars()
+            .take_while(|c| c.is_alphanumeric() || *c == '_')
+            .collect();
+        // A method call, not a field
+        let is_field = field.starts_with(|c: char| c.is_alphabetic() || c == '_')
+            && !after[field.len()..].trim_start().starts_with(['(', ':']);
+        let signal = receiver_before(masked, offset);
+        if !is_field || signal.is_empty() || !reported.insert(signal.to_string()) {
+            continue;
+        }
+        findings.push(
+            Finding::new(
+                "L0602",
+                format!(
+                    "`{}.get().{}` clones the whole struct to read one field, and re-renders whenever any field of `{}` changes",
+                    signal, field, signal
+                ),
+            )
+            .at(code, offset - signal.len())
+            .with_fix(format!(
+                "Memo::new(move |_| {}.with(|v| v.{}.clone()))  re-renders only when `{}` changes; or derive `Store` and read `{}.{}()`",
+                signal, field, field, signal, field
+            )),
+        );
+    }
+}
@@ -0,0 +1,159 @@
+//! Route path checks across files (L0107)
+//!
+//! Route declarations are read from `view!` markup: `<Route>` and
+//! `<ProtectedRoute>` paths joined to the paths of the `<ParentRoute>`s they
+//! are nested in. Paths written with `path!("/users/:id")`, segment tuples
+//! (`(StaticSegment("users"), ParamSegment("id"))`) or, on 0.6, string
+//! literals are understood; routes with any other path expression are skipped.
+
+use super::Finding;
+use crate::project::SourceFile;
+use crate::source::split_top_level;
+use crate::view::{parse_views, Element, Node};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Elements declaring a route that renders a page
+pub(crate) const LEAF_ROUTES: &[&str] = &["Route", "ProtectedRoute"];
+
+/// Elements whose path prefixes the routes nested in them
+pub(crate) const PARENT_ROUTES: &[&str] = &["ParentRoute", "ProtectedParentRoute"];
+
+/// A route declared in `view!` markup
+#[derive(Debug, Clone)]
+pub(crate) struct RouteDecl {
+    /// Full path, including the paths of enclosing parent routes
+    pub path: String,
+    /// Byte offset of the element
+    pub offset: usize,
+}
+
+/// Path of one `path=` attribute value, `None` if it isn't a literal form
+pub(crate) fn route_path(expr: &str) -> Option<String> {
+    let expr = expr.trim();
+    if let Some(inner) = expr
+        .strip_prefix("path!(")
+        .and_then(|rest| rest.strip_suffix(')'))
+    {
+        return string_literal(inner);
+    }
+    if expr.starts_with('"') {
+        return string_literal(expr);
+    }
+    let segments = expr
+        .strip_prefix('(')
+        .and_then(|rest| rest.strip_suffix(')'))
+        .map_or_else(|| vec![expr], split_top_level);
+    segments
+        .into_iter()
+        .filter(|s| !s.trim().is_empty())
+        .map(|segment| {
+            let segment = segment.trim();
+            let (kind, arg) = segment.split_once('(')?;
+            let arg = string_literal(arg.strip_suffix(')')?)?;
+            match kind.trim() {
+                "StaticSegment" if arg.is_empty() => Some(String::new()),
+                "StaticSegment" => Some(format!("/{}", arg.trim_matches('/'))),
+                "ParamSegment" => Some(format!("/:{}", arg)),
+                "OptionalParamSegment" => Some(format!("/:{}?", arg)),
+                "WildcardSegment" => Some(format!("/*{}", arg)),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+fn string_literal(text: &str) -> Option<String> {
+    let text = text.trim();
+    text.strip_prefix('"')?.strip_suffix('"').map(String::from)
+}
+
+/// `/users/` and `users` are both `/users`; the root is `/`
+pub(crate) fn normalize(path: &str) -> String {
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    format!("/{}", segments.join("/"))
+}
+
+/// The path with parameter names dropped: `/users/:id` and `/users/:user_id`
+/// match the same URLs
+fn shape(path: &str) -> String {
+    path.split('/')
+        .map(|segment| match segment.chars().next() {
+            Some(':') if segment.ends_with('?') => ":?",
+            Some(':') => ":",
+            Some('*') => "*",
+            _ => segment,
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Routes declared in `code`, in source order
+pub(crate) fn routes(code: &str) -> Vec<RouteDecl> {
+    let path_of = |element: &Element| {
+        element
+            .attr("path")
+            .and_then(|a| a.expr())
+            .and_then(route_path)
+    };
+    let mut found = Vec::new();
+    for view in parse_views(code) {
+        // Paths of the open parent routes; `None` for one whose path isn't known
+        let mut parents: Vec<Option<String>> = Vec::new();
+        for node in &view.nodes {
+            match node {
+                Node::Open(element)
+                    if PARENT_ROUTES.contains(&element.name.as_str()) && !element.self_closing =>
+                {
+                    parents.push(path_of(element));
+                }
+                Node::Open(element) if LEAF_ROUTES.contains(&element.name.as_str()) => {
+                    let prefix = parents.iter().try_fold(String::new(), |prefix, parent| {
+                        Some(format!("{}/{}", prefix, parent.as_ref()?))
+                    });
+                    if let (Some(prefix), Some(path)) = (prefix, path_of(element)) {
+                        found.push(RouteDecl {
+                            path: normalize(&format!("{}/{}", prefix, path)),
+                            offset: element.offset,
+                        });
+                    }
+                }
+                Node::Close { name, .. } if PARENT_ROUTES.contains(&name.as_str()) => {
+                    parents.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+    found
+}
+
+/// L0107 findings by file path: every declaration of a path after its first
+pub(crate) fn check_project(files: &[SourceFile]) -> HashMap<PathBuf, Vec<Finding>> {
+    let mut first: HashMap<String, (usize, usize)> = HashMap::new();
+    let mut findings: HashMap<PathBuf, Vec<Finding>> = HashMap::new();
+    for (index, file) in files.iter().enumerate() {
+        for route in routes(&file.content) {
+            let Some(&(home, offset)) = first.get(&shape(&route.path)) else {
+                first.insert(shape(&route.path), (index, route.offset));
+                continue;
+            };
+            let original = &files[home];
+            let (line, _) = super::position(&original.content, offset);
+            findings.entry(file.path.clone()).or_default().push(
+                Finding::new(
+                    "L0107",
+                    format!(
+                        "Route path `{}` is already declared at {}:{}; only one of them can match",
+                        route.path,
+                        original.path.display(),
+                        line
+                    ),
+                )
+                .at(&file.content, route.offset)
+                .with_fix("Remove one declaration or give it a distinct path"),
+            );
+        }
+    }
+    findings
+}
@@ -0,0 +1,475 @@
+//! Security rules (L09xx)
+//!
+//! The ways a Leptos app most often exposes its users or its server: strings
+//! injected as markup with `inner_html`, server functions that change stored
+//! data for anyone who calls their endpoint, secrets compiled into the WASM
+//! bundle with `env!`, and CORS or cookie settings in the server setup that
+//! let other sites in. All are in the `security` category, so a project can
+//! turn them off together.
+
+use super::server::cfg_ssr_items;
+use super::Finding;
+use crate::source::{functions, let_bindings, mask_literals, within, Binding};
+use crate::view::{parse_views, skip_balanced, Node};
+use std::ops::Range;
+
+/// Calls that escape or sanitize HTML before it is injected
+const SANITIZERS: &[&str] = &[
+    "ammonia::",
+    "clean(",
+    "sanitize",
+    "escape",
+    "encode_text",
+    "encode_safe",
+];
+
+/// SQL statements that change stored data, matched in string literals
+const SQL_WRITES: &[&str] = &["INSERT INTO", "DELETE FROM", "DROP TABLE", "TRUNCATE "];
+
+/// Diesel and SeaORM calls that change stored data
+const ORM_WRITES: &[&str] = &[
+    "insert_into(",
+    "diesel::update(",
+    "diesel::delete(",
+    "delete_by_id(",
+    "delete_many(",
+    "update_many(",
+    "insert_many(",
+];
+
+/// Names in a server function body that show it checks who is calling
+const AUTH_MARKERS: &[&str] = &[
+    "auth",
+    "session",
+    "_user(",
+    "user()",
+    "claims",
+    "permission",
+    "is_admin",
+    "logged_in",
+    "identity",
+    "jwt",
+    "bearer",
+    "token",
+];
+
+/// Server function names meant to be called by visitors who aren't signed in
+const PUBLIC_ACTIONS: &[&str] = &[
+    "login",
+    "log_in",
+    "sign_in",
+    "signin",
+    "sign_up",
+    "signup",
+    "register",
+    "logout",
+    "log_out",
+    "sign_out",
+    "reset_password",
+    "forgot_password",
+    "subscribe",
+    "contact",
+];
+
+/// Parts of environment variable names that hold secrets, besides a `_KEY` suffix
+const SECRET_NAMES: &[&str] = &[
+    "SECRET",
+    "TOKEN",
+    "PASSWORD",
+    "PASSWD",
+    "PRIVATE",
+    "CREDENTIAL",
+    "API_KEY",
+    "APIKEY",
+    "DATABASE_URL",
+    "DB_URL",
+    "DSN",
+];
+
+/// Macros that read an environment variable at compile time
+const COMPILE_TIME_ENV: &[&str] = &["env", "option_env", "dotenv"];
+
+/// CORS settings that let any site call the server
+const PERMISSIVE_CORS: &[&str] = &[
+    "CorsLayer::permissive()",
+    "CorsLayer::very_permissive()",
+    "allow_origin(Any)",
+    "allow_origin(cors::Any)",
+    "AllowOrigin::any()",
+    "AllowOrigin::mirror_request()",
+    "Cors::permissive()",
+    ".allow_any_origin()",
+];
+
+/// Scripts in the page can read a cookie without `HttpOnly`
+const READABLE: &str = "lets scripts in the page read the cookie, so a single XSS hole leaks it";
+
+/// Browsers send a cookie without `Secure` over plain HTTP too
+const UNENCRYPTED: &str =
+    "lets browsers send the cookie over plain HTTP, where it can be read in transit";
+
+/// Cookie settings that weaken a cookie, with what each allows and the fix
+const INSECURE_COOKIE_SETTINGS: &[(&str, &str, &str)] = &[
+    (".http_only(false)", READABLE, "Use `.http_only(true)`"),
+    (".set_http_only(false)", READABLE, "Use `.set_http_only(true)`"),
+    (".with_http_only(false)", READABLE, "Use `.with_http_only(true)`"),
+    (".secure(false)", UNENCRYPTED, "Use `.secure(true)`; for local development over HTTP, `.secure(!cfg!(debug_assertions))`"),
+    (".set_secure(false)", UNENCRYPTED, "Use `.set_secure(true)`; for local development over HTTP, `.set_secure(!cfg!(debug_assertions))`"),
+    (".with_secure(false)", UNENCRYPTED, "Use `.with_secure(true)`; for local development over HTTP, `.with_secure(!cfg!(debug_assertions))`"),
+    (
+        "SameSite::None",
+        "sends the cookie with requests other sites make, so they can act as the signed-in user (CSRF)",
+        "Use `SameSite::Lax` (or `Strict`) unless the app must work embedded in another site",
+    ),
+];
+
+/// Cookie name parts that suggest the cookie identifies the user
+const SESSION_COOKIES: &[&str] = &[
+    "session", "sid", "auth", "token", "jwt", "login", "remember",
+];
+
+/// Run every security rule over `code`
+pub fn check(code: &str) -> Vec<Finding> {
+    let masked = mask_literals(code);
+    let mut findings = Vec::new();
+    check_inner_html(code, &masked, &mut findings);
+    check_server_fn_auth(code, &masked, &mut findings);
+    check_env_secrets(code, &masked, &mut findings);
+    check_cors(code, &masked, &mut findings);
+    check_cookies(code, &masked, &mut findings);
+    findings
+}
+
+/// String literals within `range`: the offset of each one's contents and the contents
+fn literals<'a>(code: &'a str, masked: &str, range: Range<usize>) -> Vec<(usize, &'a str)> {
+    let mut found = Vec::new();
+    let mut from = range.start;
+    while let Some(open) = masked[from..range.end].find('"').map(|p| from + p + 1) {
+        let Some(close) = masked[open..range.end].find('"').map(|p| open + p) else {
+            break;
+        };
+        found.push((open, &code[open..close]));
+        from = close + 1;
+    }
+    found
+}
+
+/// Whether an `inner_html` value is a literal, a constant or sanitized markup
+fn is_safe_markup(expr: &str, masked: &str, bindings: &[Binding]) -> bool {
+    let expr = expr.trim();
+    if expr.starts_with('"')
+        || expr.starts_with("include_str!")
+        || SANITIZERS.iter().any(|s| expr.contains(s))
+    {
+        return true;
+    }
+    let value = expr
+        .trim_start_matches("move")
+        .trim_start()
+        .trim_start_matches("||")
+        .trim_start()
+        .trim_start_matches('&');
+    let name: String = value
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || *c == '_')
+        .collect();
+    let constant = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_');
+    // A binding to a literal, or to markup sanitized when it was built
+    constant
+        || bindings.iter().any(|b| {
+            let init = masked[b.init.clone()].trim();
+            b.name == name
+                && (init.starts_with('"')
+                    || init.starts_with("include_str!")
+                    || SANITIZERS.iter().any(|s| init.contains(s)))
+        })
+}
+
+/// L0900 `inner_html` set from a value that isn't sanitized
+fn check_inner_html(code: &str, masked: &str, findings: &mut Vec<Finding>) {
+    let bindings = let_bindings(masked, 0..masked.len());
+    let mut injected: Vec<(usize, String)> = parse_views(code)
+        .iter()
+        .flat_map(|v| &v.nodes)
+        .filter_map(|node| match node {
+            Node::Open(element) => Some(element),
+            _ => None,
+        })
+        .flat_map(|element| &element.attrs)
+        .filter(|a| a.name == "inner_html" || a.name == "prop:innerHTML")
+        .filter_map(|a| Some((a.offset, a.expr()?.to_string())))
+        .collect();
+    for (offset, call) in masked.match_indices(".set_inner_html(") {
+        let open = offset + call.len() - 1;
+        if let Some(close) = skip_balanced(masked.as_bytes(), open, b'(', b')') {
+            injected.push((offset + 1, code[open + 1..close - 1].to_string()));
+        }
+    }
+    for (offset, expr) in injected {
+        if is_safe_markup(&expr, masked, &bindings) {
+            continue;
+        }
+        let value = if expr.len() <= 40 && !expr.contains('\n') {
+            format!("`{}`", expr)
+        } else {
+            "a computed value".to_string()
+        };
+        findings.push(
+            Finding::new(
+                "L0900",
+                format!(
+                    "`inner_html` inserts {} as raw HTML without escaping; if it carries user data, a `<script>` or `onerror=` in it runs in your users' browsers",
+                    value
+                ),
+            )
+            .at(code, offset)
+            .with_fix("Render it as text with `{value}`, or sanitize it first: `inner_html=move || ammonia::clean(&value.get())`"),
+        );
+    }
+}
+
+/// The first write to stored data in `range`: its offset and what it is
+fn first_write(code: &str, masked: &str, range: Range<usize>) -> Option<(usize, &'static str)> {
+    let sql = literals(code, masked, range.clone())
+        .into_iter()
+        .find_map(|(offset, text)| {
+            let upper = text.to_ascii_uppercase();
+            let update = upper
+                .find("UPDATE ")
+                .filter(|at| upper[*at..].contains(" SET "))
+                .map(|at| (at, "UPDATE"));
+            SQL_WRITES
+                .iter()
+                .filter_map(|w| upper.find(w).map(|at| (at, w.trim_end())))
+                .chain(update)
+                .min_by_key(|(at, _)| *at)
+                .map(|(at, write)| (offset + at, write))
+        });
+    let orm = ORM_WRITES
+        .iter()
+        .filter_map(|call| {
+            masked[range.clone()]
+                .find(call)
+                .map(|at| (range.start + at, call.trim_end_matches('(')))
+        })
+        .min_by_key(|(offset, _)| *offset);
+    sql.into_iter().chain(orm).min_by_key(|(offset, _)| *offset)
+}
+
+/// L0901 a server function that changes stored data without checking who is calling
+fn check_server_fn_auth(code: &str, masked: &str, findings: &mut Vec<Finding>) {
+    for server_fn in functions(code).iter().filter(|f| f.has_attr("server")) {
+        let name = server_fn.name.to_ascii_lowercase();
+        if server_fn.has_attr("middleware") || PUBLIC_ACTIONS.iter().any(|a| name.contains(a)) {
+            continue;
+        }
+        let Some((offset, write)) = first_write(code, masked, server_fn.body.clone()) else {
+            continue;
+        };
+        let before = masked[server_fn.body.start..offset].to_ascii_lowercase();
+        if AUTH_MARKERS.iter().any(|marker| before.contains(marker)) {
+            continue;
+        }
+        findings.push(
+            Finding::new(
+                "L0901",
+                format!(
+                    "Server function `{}` changes stored data (`{}`) without first checking who is calling it; its endpoint is public, so anyone can call it directly",
+                    server_fn.name, write
+                ),
+            )
+            .at(code, offset)
+            .with_fix("Check the caller before the write, e.g. `let user = current_user().await?.ok_or_else(|| ServerFnError::new(\"Sign in first\"))?;`, and that they may change this record; or guard the function with `#[middleware(...)]`"),
+        );
+    }
+}
+
+/// Whether an environment variable name looks like it holds a secret
+fn is_secret(var: &str) -> bool {
+    let var = var.to_ascii_uppercase();
+    !var.starts_with("CARGO_")
+        && !var.contains("PUBLIC")
+        && !var.contains("PUBLISHABLE")
+        && (var.ends_with("_KEY") || SECRET_NAMES.iter().any(|s| var.contains(s)))
+}
+
+/// L0902 a secret read with `env!` in code the client build compiles
+fn check_env_secrets(code: &str, masked: &str, findings: &mut Vec<Finding>) {
+    if code.contains("#![cfg(feature = \"ssr\")]") {
+        return;
+    }
+    let mut ssr_only: Vec<Range<usize>> = functions(code)
+        .into_iter()
+        .filter(|f| f.has_attr("server"))
+        .map(|f| f.body)
+        .collect();
+    ssr_only.extend(cfg_ssr_items(code, masked));
+    for (offset, _) in masked.match_indices("env!(") {
+        let start = masked[..offset]
+            .rfind(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .map_or(0, |p| p + 1);
+        let name = &masked[start..offset + "env".len()];
+        if !COMPILE_TIME_ENV.contains(&name) || within(&ssr_only, offset) {
+            continue;
+        }
+        let Some(var) = code[offset + "env!(".len()..]
+            .trim_start()
+            .strip_prefix('"')
+            .and_then(|rest| rest.split('"').next())
+        else {
+            continue;
+        };
+        if !is_secret(var) {
+            continue;
+        }
+        findings.push(
+            Finding::new(
+                "L0902",
+                format!(
+                    "`{}!(\"{}\")` copies `{}` into the binary at compile time, and this code is also built for the browser, so the secret ships in the WASM bundle",
+                    name, var, var
+                ),
+            )
+            .at(code, start)
+            .with_fix(format!(
+                "Read it at runtime on the server: `std::env::var(\"{}\")` inside a #[server] function or a `#[cfg(feature = \"ssr\")]` item",
+                var
+            )),
+        );
+    }
+}
+
+/// L0903 CORS configured to accept requests from any origin
+fn check_cors(code: &str, masked: &str, findings: &mut Vec<Finding>) {
+    let credentials =
+        masked.contains("allow_credentials(true)") || masked.contains("supports_credentials()");
+    for setting in PERMISSIVE_CORS {
+        for (offset, _) in masked.match_indices(setting) {
+            let risk = if credentials {
+                "; with credentials allowed, those requests carry your users' cookies"
+            } else {
+                ""
+            };
+            let fix = if setting.starts_with("Cors::") || setting.starts_with(".allow_any") {
+                "List the origins that may call it: `Cors::default().allowed_origin(\"https://app.example.com\").allowed_methods([\"GET\", \"POST\"])`"
+            } else {
+                "List the origins that may call it: `CorsLayer::new().allow_origin(\"https://app.example.com\".parse::<HeaderValue>().unwrap()).allow_methods([Method::GET, Method::POST])`"
+            };
+            findings.push(
+                Finding::new(
+                    "L0903",
+                    format!(
+                        "`{}` lets any website call this server's routes and server functions from its visitors' browsers{}",
+                        setting.trim_start_matches('.'),
+                        risk
+                    ),
+                )
+                .at(code, offset)
+                .with_fix(fix),
+            );
+        }
+    }
+}
+
+/// L0904 cookies that page scripts can read, that travel over plain HTTP or
+/// that other sites' requests carry
+fn check_cookies(code: &str, masked: &str, findings: &mut Vec<Finding>) {
+    for (setting, risk, fix) in INSECURE_COOKIE_SETTINGS {
+        for (offset, _) in masked.match_indices(setting) {
+            findings.push(
+                Finding::new(
+                    "L0904",
+                    format!("`{}` {}", setting.trim_start_matches('.'), risk),
+                )
+                .at(code, offset + usize::from(setting.starts_with('.')))
+                .with_fix(*fix),
+            );
+        }
+    }
+    check_session_cookie_flags(code, masked, findings);
+}
+
+/// L0904 a cookie named like a session cookie, built or sent without
+/// `HttpOnly` and `Secure`
+fn check_session_cookie_flags(code: &str, masked: &str, findings: &mut Vec<Finding>) {
+    let functions = functions(code);
+    let lower = code.to_ascii_lowercase();
+    let mut cookies: Vec<(usize, bool)> = ["Cookie::build(", "Cookie::new("]
+        .iter()
+        .flat_map(|call| masked.match_indices(call))
+        .map(|(offset, _)| (offset, true))
+        .collect();
+    cookies.extend(
+        masked
+            .match_indices("SET_COOKIE")
+            .chain(lower.match_indices("\"set-cookie\""))
+            .map(|(offset, _)| (offset, false)),
+    );
+    cookies.sort_unstable();
+    for (offset, builder) in cookies {
+        let end = masked[offset..]
+            .find(';')
+            .map_or(masked.len(), |p| offset + p);
+        let Some(value) = literals(code, masked, offset..end)
+            .into_iter()
+            .map(|(_, text)| text)
+            .find(|text| !text.is_empty() && !text.eq_ignore_ascii_case("set-cookie"))
+        else {
+            continue;
+        };
+        let name = value.split('=').next().unwrap_or(value).trim();
+        if !SESSION_COOKIES
+            .iter()
+            .any(|s| name.to_ascii_lowercase().contains(s))
+        {
+            continue;
+        }
+        // Builders are often finished in later statements of the same function
+        let (flags, http_only, secure) = if builder {
+            let scope_end = functions
+                .iter()
+                .filter(|f| f.body.contains(&offset))
+                .map(|f| f.body.end)
+                .min()
+                .unwrap_or(masked.len());
+            let rest = &masked[offset..scope_end];
+            (rest, "http_only(true)", "secure(true)")
+        } else {
+            (value, "HttpOnly", "Secure")
+        };
+        let flags = flags.to_ascii_lowercase();
+        let missing: Vec<(&str, &str)> = [
+            (http_only, "page scripts can read it"),
+            (secure, "it is also sent over plain HTTP"),
+        ]
+        .into_iter()
+        .filter(|(flag, _)| !flags.contains(&flag.to_ascii_lowercase()))
+        .collect();
+        if missing.is_empty() {
+            continue;
+        }
+        let flags: Vec<&str> = missing.iter().map(|(flag, _)| *flag).collect();
+        let risks: Vec<&str> = missing.iter().map(|(_, risk)| *risk).collect();
+        let fix = if builder {
+            "Build it as `Cookie::build((\"session\", id)).http_only(true).secure(true).same_site(SameSite::Lax).path(\"/\")`"
+        } else {
+            "Add the attributes to the header value: `\"session=...; Path=/; HttpOnly; Secure; SameSite=Lax\"`"
+        };
+        findings.push(
+            Finding::new(
+                "L0904",
+                format!(
+                    "Cookie `{}` looks like it identifies the user but is set without `{}`, so {}",
+                    name,
+                    flags.join("` and `"),
+                    risks.join(" and ")
+                ),
+            )
+            .at(code, offset)
+            .with_fix(fix),
+        );
+    }
+}
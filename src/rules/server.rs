@@ -0,0 +1,343 @@
+//! Server function rules (L04xx)
+//!
+//! `#[server]` bodies are compiled only with the `ssr` feature, but the rest of
+//! the file is compiled for the browser too, and arguments and return values
+//! cross the network.
+
+use super::Finding;
+use crate::source::{
+    attributes_before, functions, mask_literals, split_top_level, typed_params, FnItem,
+};
+use crate::view::skip_balanced;
+use std::collections::HashSet;
+use std::ops::Range;
+
+/// Crates that only build for the server
+pub(crate) const SSR_ONLY_CRATES: &[&str] = &[
+    "sqlx",
+    "diesel",
+    "sea_orm",
+    "tokio_postgres",
+    "rusqlite",
+    "redis",
+    "mongodb",
+    "tokio",
+    "axum",
+    "actix_web",
+    "tower",
+    "tower_http",
+    "leptos_axum",
+    "leptos_actix",
+];
+
+/// Request data that must be extracted with `extract()`, not read from context
+const EXTRACTOR_TYPES: &[&str] = &[
+    "HeaderMap",
+    "Query",
+    "Path",
+    "Form",
+    "Json",
+    "TypedHeader",
+    "CookieJar",
+    "Session",
+    "ConnectInfo",
+    "Method",
+    "Uri",
+    "State",
+    "Extension",
+];
+
+/// Calls that block the async executor thread
+const BLOCKING_CALLS: &[&str] = &[
+    "std::fs::",
+    "std::thread::sleep",
+    "thread::sleep(",
+    "reqwest::blocking",
+    "std::net::",
+    "std::io::stdin",
+    "block_on(",
+];
+
+/// Types that can't cross the network as server function arguments or results
+const NON_SERIALIZABLE: &[&str] = &[
+    "ReadSignal",
+    "WriteSignal",
+    "RwSignal",
+    "Signal",
+    "Memo",
+    "Resource",
+    "Callback",
+    "NodeRef",
+    "Rc",
+    "RefCell",
+    "Cell",
+    "Mutex",
+    "RwLock",
+    "File",
+    "Pool",
+    "PgPool",
+    "SqlitePool",
+    "MySqlPool",
+    "Connection",
+    "JoinHandle",
+];
+
+/// Run every server function rule over `code`
+pub fn check(code: &str) -> Vec<Finding> {
+    if !code.contains("#[server") || code.contains("#![cfg(feature = \"ssr\")]") {
+        return Vec::new();
+    }
+    let masked = mask_literals(code);
+    let server_fns: Vec<FnItem> = functions(code)
+        .into_iter()
+        .filter(|f| f.has_attr("server"))
+        .collect();
+    let mut ssr_only: Vec<Range<usize>> = server_fns.iter().map(|f| f.body.clone()).collect();
+    ssr_only.extend(cfg_ssr_items(code, &masked));
+
+    let mut findings = Vec::new();
+    check_ssr_crates(code, &masked, &ssr_only, &mut findings);
+    check_context_extractors(code, &masked, &mut findings);
+    let local_types = unserializable_local_types(code, &masked);
+    for server_fn in &server_fns {
+        check_blocking(code, &masked, server_fn, &mut findings);
+        check_serializable(code, server_fn, &local_types, &mut findings);
+    }
+    findings
+}
+
+/// Items gated by `#[cfg(feature = "ssr")]`: from the attribute to the end of the item
+pub(crate) fn cfg_ssr_items(code: &str, masked: &str) -> Vec<Range<usize>> {
+    let bytes = masked.as_bytes();
+    code.match_indices("#[cfg(")
+        .filter_map(|(offset, _)| {
+            let attr_end = offset + code[offset..].find(']')?;
+            let attr = &code[offset..attr_end];
+            if !attr.contains("feature = \"ssr\"") || attr.contains("not(") {
+                return None;
+            }
+            let item_end = match masked[attr_end..].find(['{', ';']).map(|p| attr_end + p)? {
+                brace if bytes[brace] == b'{' => skip_balanced(bytes, brace, b'{', b'}')?,
+                semi => semi,
+            };
+            Some(offset..item_end)
+        })
+        .collect()
+}
+
+/// Server-only crate paths outside the `ssr_only` ranges, at most one per
+/// line: the offset and the crate
+pub(super) fn server_only_paths(
+    masked: &str,
+    ssr_only: &[Range<usize>],
+) -> Vec<(usize, &'static str)> {
+    let mut reported_lines = HashSet::new();
+    let mut found = Vec::new();
+    for krate in SSR_ONLY_CRATES {
+        let path = format!("{}::", krate);
+        for (offset, _) in masked.match_indices(&path) {
+            let qualified = masked[..offset]
+                .chars()
+                .next_back()
+                .is_some_and(|c| c.is_alphanumeric() || c == '_' || c == ':');
+            if qualified || ssr_only.iter().any(|r| r.contains(&offset)) {
+                continue;
+            }
+            let line = masked[..offset].matches('\n').count();
+            if reported_lines.insert(line) {
+                found.push((offset, *krate));
+            }
+        }
+    }
+    found
+}
+
+/// L0400 server-only crates used where the client build also compiles them
+fn check_ssr_crates(
+    code: &str,
+    masked: &str,
+    ssr_only: &[Range<usize>],
+    findings: &mut Vec<Finding>,
+) {
+    for (offset, krate) in server_only_paths(masked, ssr_only) {
+        findings.push(
+            Finding::new(
+                "L0400",
+                format!(
+                    "`{}` is server-only but is used outside a #[server] body or #[cfg(feature = \"ssr\")] item, so the client build will fail",
+                    krate
+                ),
+            )
+            .at(code, offset)
+            .with_fix("Move it into the #[server] function body, or gate the item with `#[cfg(feature = \"ssr\")]`"),
+        );
+    }
+}
+
+/// L0401 request extractors read with `use_context`/`expect_context`
+fn check_context_extractors(code: &str, masked: &str, findings: &mut Vec<Finding>) {
+    for call in ["expect_context::<", "use_context::<"] {
+        for (offset, _) in masked.match_indices(call) {
+            let ty_start = offset + call.len();
+            let ty: String = masked[ty_start..]
+                .chars()
+                .take_while(|c| c.is_alphanumeric() || *c == '_' || *c == ':')
+                .collect();
+            let head = ty.rsplit("::").next().unwrap_or(&ty);
+            if !EXTRACTOR_TYPES.contains(&head) {
+                continue;
+            }
+            findings.push(
+                Finding::new(
+                    "L0401",
+                    format!(
+                        "`{}` is an extractor and isn't provided as context; `{}{}>()` will fail at runtime",
+                        head,
+                        call,
+                        ty
+                    ),
+                )
+                .at(code, offset)
+                .with_fix(format!(
+                    "Extract it inside the server function: `let value: {}<...> = leptos_axum::extract().await?;`",
+                    head
+                )),
+            );
+        }
+    }
+}
+
+/// L0402 blocking I/O or sleeps inside an async server function
+fn check_blocking(code: &str, masked: &str, server_fn: &FnItem, findings: &mut Vec<Finding>) {
+    let body = &masked[server_fn.body.clone()];
+    let mut calls: Vec<(usize, &str)> = BLOCKING_CALLS
+        .iter()
+        .flat_map(|call| body.match_indices(call))
+        .collect();
+    // `fs::read` via `use std::fs;`, unless it's the async tokio/async-std variant
+    if masked.contains("use std::fs") {
+        calls.extend(body.match_indices("fs::").filter(|(o, _)| {
+            let before = &body[..*o];
+            !before.ends_with(|c: char| c.is_alphanumeric() || c == '_' || c == ':')
+        }));
+    }
+    calls.sort_unstable();
+    let mut covered = 0;
+    for (rel, call) in calls {
+        // `thread::sleep(` inside an already reported `std::thread::sleep`
+        if rel < covered {
+            continue;
+        }
+        covered = rel + call.len();
+        findings.push(
+            Finding::new(
+                "L0402",
+                format!(
+                    "`{}` blocks the async runtime inside server function `{}`",
+                    call.trim_end_matches(['(', ':']),
+                    server_fn.name
+                ),
+            )
+            .at(code, server_fn.body.start + rel)
+            .with_fix("Use the async equivalent (`tokio::fs`, async `reqwest`, `tokio::time::sleep`) or wrap it in `tokio::task::spawn_blocking`"),
+        );
+    }
+}
+
+/// Structs and enums defined in this file without both serde derives
+fn unserializable_local_types(code: &str, masked: &str) -> HashSet<String> {
+    let mut types = HashSet::new();
+    for keyword in ["struct ", "enum "] {
+        for (offset, _) in masked.match_indices(keyword) {
+            if masked[..offset]
+                .chars()
+                .next_back()
+                .is_some_and(|c| c.is_alphanumeric() || c == '_')
+            {
+                continue;
+            }
+            let name: String = masked[offset + keyword.len()..]
+                .chars()
+                .take_while(|c| c.is_alphanumeric() || *c == '_')
+                .collect();
+            if name.is_empty() {
+                continue;
+            }
+            let attrs = attributes_before(code, offset).join(" ");
+            let derived = attrs.contains("Serialize") && attrs.contains("Deserialize");
+            let manual = masked.contains(&format!("Serialize for {}", name));
+            if !derived && !manual {
+                types.insert(name);
+            }
+        }
+    }
+    types
+}
+
+/// L0403 arguments or return values that can't be serialized
+fn check_serializable(
+    code: &str,
+    server_fn: &FnItem,
+    local_types: &HashSet<String>,
+    findings: &mut Vec<Finding>,
+) {
+    let params = &code[server_fn.params.clone()];
+    let mut checked: Vec<(String, String)> = typed_params(params)
+        .into_iter()
+        .map(|(name, ty)| (format!("argument `{}`", name), ty))
+        .collect();
+    if let Some(ok) = success_type(&code[server_fn.returns.clone()]) {
+        checked.push(("return type".to_string(), ok));
+    }
+
+    for (what, ty) in checked {
+        let Some(reason) = unserializable_reason(&ty, local_types) else {
+            continue;
+        };
+        findings.push(
+            Finding::new(
+                "L0403",
+                format!(
+                    "The {} of server function `{}` (`{}`) isn't serializable: {}",
+                    what, server_fn.name, ty, reason
+                ),
+            )
+            .at(code, server_fn.params.start)
+            .with_fix("Use owned data types deriving `Serialize, Deserialize` (a DTO) and convert at the boundary"),
+        );
+    }
+}
+
+/// `T` of a `-> Result<T, E>` return, or the whole return type
+fn success_type(returns: &str) -> Option<String> {
+    let ty = returns.trim().strip_prefix("->")?;
+    let ty = ty.split(" where ").next().unwrap_or(ty).trim();
+    let inner = ty
+        .strip_prefix("Result<")
+        .and_then(|t| t.strip_suffix('>'))
+        .map(|t| split_top_level(t).first().map_or(t, |ok| ok.trim()))
+        .unwrap_or(ty);
+    Some(inner.to_string())
+}
+
+fn unserializable_reason(ty: &str, local_types: &HashSet<String>) -> Option<String> {
+    if ty.starts_with('&') || ty.contains("<&") || ty.contains(" &") {
+        return Some("references can't be deserialized; take an owned value".to_string());
+    }
+    if ty.contains("impl ") || ty.contains("dyn ") || ty.contains("fn(") {
+        return Some("closures and trait objects can't be sent over the network".to_string());
+    }
+    ty.split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .find_map(|ident| {
+            if NON_SERIALIZABLE.contains(&ident) {
+                Some(format!("`{}` is not serializable", ident))
+            } else if local_types.contains(ident) {
+                Some(format!(
+                    "`{}` doesn't derive `Serialize` and `Deserialize`",
+                    ident
+                ))
+            } else {
+                None
+            }
+        })
+}
@@ -0,0 +1,258 @@
+//! State that wants to be a store (L0011, L0603)
+//!
+//! Signals nested inside a signal and large structs kept whole in one signal
+//! are how fine-grained state was built before `reactive_stores`. From 0.7
+//! on, a `#[derive(Store)]` struct tracks each field on its own, without the
+//! second layer of signals or the clone of the whole struct on every read.
+
+use super::Finding;
+use crate::reactivity::{sources, NodeKind};
+use crate::source::{identifier_uses, mask_literals, split_top_level};
+use crate::view::skip_balanced;
+use std::ops::Range;
+
+/// Signal types whose value shouldn't itself hold signals
+const SIGNAL_TYPES: &[&str] = &[
+    "RwSignal",
+    "ReadSignal",
+    "WriteSignal",
+    "ArcRwSignal",
+    "ArcReadSignal",
+    "ArcWriteSignal",
+];
+
+/// Calls creating a signal from an initial value
+const SIGNAL_CALLS: &[&str] = &[
+    "signal",
+    "signal_local",
+    "arc_signal",
+    "create_signal",
+    "create_rw_signal",
+    "RwSignal::new",
+    "ArcRwSignal::new",
+];
+
+/// Fields from which a struct kept in one signal is worth a store
+const LARGE_STRUCT_FIELDS: usize = 5;
+
+/// A struct declared in the checked code
+struct StructDef {
+    name: String,
+    field_types: Vec<String>,
+}
+
+impl StructDef {
+    fn holds_signals(&self) -> bool {
+        self.field_types.iter().any(|ty| mentions(ty, SIGNAL_TYPES))
+    }
+}
+
+/// Run both store rules over `code`
+pub fn check(code: &str) -> Vec<Finding> {
+    let masked = mask_literals(code);
+    let structs = structs(&masked);
+    let mut findings = Vec::new();
+    check_nested_signals(code, &masked, &structs, &mut findings);
+    check_large_structs(code, &masked, &structs, &mut findings);
+    findings
+}
+
+/// L0011 a signal whose value holds signals, directly or through a struct
+fn check_nested_signals(
+    code: &str,
+    masked: &str,
+    structs: &[StructDef],
+    findings: &mut Vec<Finding>,
+) {
+    let with_signals: Vec<&str> = structs
+        .iter()
+        .filter(|s| s.holds_signals())
+        .map(|s| s.name.as_str())
+        .collect();
+    let nested = |inner: &str| -> Option<String> {
+        SIGNAL_TYPES
+            .iter()
+            .chain(with_signals.iter())
+            .find(|name| mentions(inner, &[name]))
+            .map(|name| name.to_string())
+            .or_else(|| {
+                SIGNAL_CALLS
+                    .iter()
+                    .find(|call| !calls(inner, &[call]).is_empty())
+                    .map(|call| call.to_string())
+            })
+    };
+
+    let mut flagged: Vec<Range<usize>> = Vec::new();
+    for call in calls(masked, SIGNAL_CALLS) {
+        let name_len = SIGNAL_CALLS
+            .iter()
+            .filter(|c| masked[call.start..].starts_with(*c))
+            .map(|c| c.len())
+            .max()
+            .unwrap_or_default();
+        let Some(inner) = nested(&masked[call.start + name_len..call.end]) else {
+            continue;
+        };
+        flagged.push(call.clone());
+        findings.push(nested_finding(code, call.start, &inner));
+    }
+    for ty in SIGNAL_TYPES {
+        for (offset, _) in masked.match_indices(&format!("{}<", ty)) {
+            if !is_word_start(masked, offset) || flagged.iter().any(|r| r.contains(&offset)) {
+                continue;
+            }
+            let open = offset + ty.len();
+            let Some(close) = skip_balanced(masked.as_bytes(), open, b'<', b'>') else {
+                continue;
+            };
+            if let Some(inner) = nested(&masked[open + 1..close - 1]) {
+                flagged.push(offset..close);
+                findings.push(nested_finding(code, offset, &inner));
+            }
+        }
+    }
+}
+
+fn nested_finding(code: &str, offset: usize, inner: &str) -> Finding {
+    let what = if SIGNAL_TYPES.contains(&inner) || SIGNAL_CALLS.contains(&inner) {
+        format!("another signal (`{}`)", inner)
+    } else {
+        format!("`{}`, whose fields are signals", inner)
+    };
+    Finding::new(
+        "L0011",
+        format!(
+            "Signal holds {}: two layers of reactivity to keep in sync, and the value can't be serialized or compared",
+            what
+        ),
+    )
+    .at(code, offset)
+    .with_fix(
+        "Make the inner fields plain values, `#[derive(Store)]` the state and read fields as `state.todos()` / `todo.done()`, which track each field on its own",
+    )
+}
+
+/// L0603 a struct of many fields kept whole in one signal and read with `.get()`
+fn check_large_structs(
+    code: &str,
+    masked: &str,
+    structs: &[StructDef],
+    findings: &mut Vec<Finding>,
+) {
+    for signal in sources(code, masked, 0..code.len())
+        .into_iter()
+        .filter(|n| n.kind == NodeKind::Signal)
+    {
+        let init = &masked[signal.body.clone()];
+        let Some(def) = structs.iter().find(|s| {
+            s.field_types.len() >= LARGE_STRUCT_FIELDS
+                && !s.holds_signals()
+                && mentions(init, &[&s.name])
+        }) else {
+            continue;
+        };
+        let cloned = identifier_uses(masked, 0..masked.len(), &signal.name)
+            .into_iter()
+            .any(|offset| masked[offset + signal.name.len()..].starts_with(".get()"));
+        if !cloned {
+            continue;
+        }
+        findings.push(
+            Finding::new(
+                "L0603",
+                format!(
+                    "`{}` keeps a {}-field `{}` in one signal: every `.get()` clones all of it, and any change re-runs everything that reads any field",
+                    signal.name,
+                    def.field_types.len(),
+                    def.name
+                ),
+            )
+            .at(code, signal.definition.start)
+            .with_fix(format!(
+                "#[derive(Store)] on `{}`, `let {} = Store::new(...)`, and read fields as `{}.field().get()`",
+                def.name, signal.name, signal.name
+            )),
+        );
+    }
+}
+
+/// Structs with named fields
+fn structs(masked: &str) -> Vec<StructDef> {
+    let bytes = masked.as_bytes();
+    let mut found = Vec::new();
+    for (offset, _) in masked.match_indices("struct ") {
+        if !is_word_start(masked, offset) {
+            continue;
+        }
+        let rest = &masked[offset + "struct ".len()..];
+        let name: String = rest
+            .chars()
+            .take_while(|c| c.is_alphanumeric() || *c == '_')
+            .collect();
+        let Some(open) = rest
+            .find(['{', ';', '('])
+            .map(|i| offset + "struct ".len() + i)
+        else {
+            continue;
+        };
+        if name.is_empty() || bytes[open] != b'{' {
+            continue;
+        }
+        let Some(close) = skip_balanced(bytes, open, b'{', b'}') else {
+            continue;
+        };
+        let field_types = split_top_level(&masked[open + 1..close - 1])
+            .into_iter()
+            .filter_map(|field| {
+                let mut field = field.trim();
+                while field.starts_with("#[") {
+                    field = field[field.find(']')? + 1..].trim_start();
+                }
+                let (_, ty) = field.split_once(':')?;
+                Some(ty.trim().to_string())
+            })
+            .collect();
+        found.push(StructDef { name, field_types });
+    }
+    found
+}
+
+/// Start offsets and ends of every call to one of `names`, turbofish included
+fn calls(masked: &str, names: &[&str]) -> Vec<Range<usize>> {
+    let bytes = masked.as_bytes();
+    let mut found = Vec::new();
+    for name in names {
+        for (offset, _) in masked.match_indices(name) {
+            if !is_word_start(masked, offset) {
+                continue;
+            }
+            let mut pos = offset + name.len();
+            if masked[pos..].starts_with("::<") {
+                let Some(end) = skip_balanced(bytes, pos + 2, b'<', b'>') else {
+                    continue;
+                };
+                pos = end;
+            }
+            if bytes.get(pos) != Some(&b'(') {
+                continue;
+            }
+            if let Some(end) = skip_balanced(bytes, pos, b'(', b')') {
+                found.push(offset..end);
+            }
+        }
+    }
+    found.sort_by_key(|r| r.start);
+    found
+}
+
+/// Whether `text` uses any of `names` as a whole word
+fn mentions(text: &str, names: &[&str]) -> bool {
+    text.split(|c: char| !c.is_alphanumeric() && c != '_')
+        .any(|word| names.contains(&word))
+}
+
+/// Whether the identifier at `offset` isn't the tail of a longer one or a path
+fn is_word_start(masked: &str, offset: usize) -> bool {
+    !masked[..offset].ends_with(|c: char| c.is_alphanumeric() || c == '_' || c == ':')
+}
@@ -0,0 +1,536 @@
+//! Styling integration checks (L0700–L0704)
+//!
+//! Optional checks for the styling setups Leptos apps commonly use. They run
+//! for the frameworks the session's project context lists, detected from
+//! the project or set with `set-project-context`: `class` values are checked
+//! against Tailwind's utilities and the project's theme, and the scoped
+//! classes of stylance modules and `stylers` blocks against their use.
+
+use super::tailwind::{self, Theme};
+use super::Finding;
+use crate::source::{functions, mask_literals};
+use crate::view::{parse_views, skip_balanced, Attr, Node};
+use regex::Regex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// A styling setup the styling rules know
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum StyleFramework {
+    /// Utility classes, from a `tailwind.config.*` or a stylesheet importing Tailwind
+    Tailwind,
+    /// `style!` and `style_sheet!` blocks scoped to a component's view
+    Stylers,
+    /// CSS modules imported with `import_style!`/`import_crate_style!`
+    Stylance,
+}
+
+impl StyleFramework {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Tailwind => "tailwind",
+            Self::Stylers => "stylers",
+            Self::Stylance => "stylance",
+        }
+    }
+
+    /// Frameworks a project in `root` uses, given its dependencies and the
+    /// `tailwind-input-file` of `[package.metadata.leptos]`
+    pub fn detect(
+        root: &Path,
+        has_dependency: impl Fn(&str) -> bool,
+        tailwind_input: Option<&str>,
+    ) -> Vec<Self> {
+        let mut found = Vec::new();
+        if tailwind::detect(root, tailwind_input) {
+            found.push(Self::Tailwind);
+        }
+        if has_dependency("stylers") {
+            found.push(Self::Stylers);
+        }
+        if has_dependency("stylance") {
+            found.push(Self::Stylance);
+        }
+        found
+    }
+}
+
+/// What the styling rules check a file against
+#[derive(Debug, Clone, Default)]
+pub struct Styling {
+    /// Directory stylance paths are relative to
+    root: PathBuf,
+    /// The project's Tailwind theme, when it uses Tailwind
+    tailwind: Option<Theme>,
+    stylers: bool,
+    stylance: bool,
+}
+
+impl Styling {
+    /// Styling of the project in `root` for `frameworks`, reading its
+    /// Tailwind theme if Tailwind is among them
+    pub fn load(root: &Path, frameworks: &[StyleFramework]) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            tailwind: frameworks
+                .contains(&StyleFramework::Tailwind)
+                .then(|| Theme::load(root)),
+            stylers: frameworks.contains(&StyleFramework::Stylers),
+            stylance: frameworks.contains(&StyleFramework::Stylance),
+        }
+    }
+
+    /// Whether no styling rule applies
+    pub fn is_empty(&self) -> bool {
+        self.tailwind.is_none() && !self.stylers && !self.stylance
+    }
+}
+
+/// Class names defined by the selectors of a stylesheet
+pub(crate) fn css_classes(css: &str) -> Vec<String> {
+    let chars: Vec<char> = strip_comments(css).chars().collect();
+    let mut classes = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let starts_name = chars
+            .get(i + 1)
+            .is_some_and(|c| c.is_ascii_alphabetic() || matches!(c, '_' | '-' | '\\'));
+        let after_name = i > 0
+            && (chars[i - 1].is_ascii_alphanumeric() || matches!(chars[i - 1], '_' | '-' | '\\'));
+        if chars[i] != '.' || !starts_name || after_name {
+            i += 1;
+            continue;
+        }
+        let mut name = String::new();
+        i += 1;
+        while i < chars.len() {
+            match chars[i] {
+                '\\' if i + 1 < chars.len() => {
+                    name.push(chars[i + 1]);
+                    i += 2;
+                }
+                c if c.is_ascii_alphanumeric() || c == '-' || c == '_' => {
+                    name.push(c);
+                    i += 1;
+                }
+                _ => break,
+            }
+        }
+        if !classes.contains(&name) {
+            classes.push(name);
+        }
+    }
+    classes
+}
+
+fn strip_comments(css: &str) -> String {
+    let mut out = String::with_capacity(css.len());
+    let mut rest = css;
+    while let Some(start) = rest.find("/*") {
+        out.push_str(&rest[..start]);
+        rest = rest[start..]
+            .find("*/")
+            .map_or("", |end| &rest[start + end + 2..]);
+    }
+    out.push_str(rest);
+    out.lines()
+        .map(|line| match line.find("//") {
+            // `//` in a `url(https://...)` isn't a comment
+            Some(i) if !line[..i].ends_with(':') => &line[..i],
+            _ => line,
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Run the styling rules that apply to `styling` over `code`
+pub(crate) fn check(code: &str, styling: &Styling) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    if let Some(theme) = &styling.tailwind {
+        check_tailwind(code, theme, &mut findings);
+    }
+    if styling.stylance {
+        check_stylance(code, &styling.root, &mut findings);
+    }
+    if styling.stylers {
+        check_stylers(code, &mut findings);
+    }
+    findings
+}
+
+/// String literals in `value` (source starting at `start` in the file),
+/// as content ranges of the file
+fn literals(value: &str, start: usize) -> Vec<Range<usize>> {
+    let bytes = value.as_bytes();
+    let mut found = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'"' {
+            let mut end = i + 1;
+            while end < bytes.len() && bytes[end] != b'"' {
+                end += if bytes[end] == b'\\' { 2 } else { 1 };
+            }
+            found.push(start + i + 1..start + end.min(bytes.len()));
+            i = end + 1;
+        } else {
+            i += 1;
+        }
+    }
+    found
+}
+
+/// Whitespace-separated words of `code[range]` with their offsets
+fn words(code: &str, range: Range<usize>) -> impl Iterator<Item = (usize, &str)> {
+    let text = &code[range.clone()];
+    text.split_whitespace().map(move |word| {
+        let offset = word.as_ptr() as usize - text.as_ptr() as usize;
+        (range.start + offset, word)
+    })
+}
+
+/// Byte offset of an attribute's value in `code`
+fn value_offset(code: &str, attr: &Attr) -> Option<usize> {
+    let value = attr.value.as_deref()?;
+    code[attr.offset..].find(value).map(|i| attr.offset + i)
+}
+
+/// L0700, L0701 and L0702 over every `class` attribute
+fn check_tailwind(code: &str, theme: &Theme, findings: &mut Vec<Finding>) {
+    for view in parse_views(code) {
+        for node in &view.nodes {
+            let Node::Open(element) = node else { continue };
+            for attr in &element.attrs {
+                if let Some(name) = attr.name.strip_prefix("class:") {
+                    check_word(code, attr.offset + "class:".len(), name, theme, findings);
+                    continue;
+                }
+                if attr.name != "class" {
+                    continue;
+                }
+                let (Some(value), Some(start)) = (attr.value.as_deref(), value_offset(code, attr))
+                else {
+                    continue;
+                };
+                for literal in literals(value, start) {
+                    for (offset, word) in words(code, literal) {
+                        if word.contains(['{', '}']) {
+                            check_interpolated(code, offset, word, findings);
+                        } else {
+                            check_word(code, offset, word, theme, findings);
+                        }
+                    }
+                }
+                if !attr.is_string_literal() {
+                    check_conditional(code, attr, findings);
+                }
+            }
+        }
+    }
+}
+
+/// L0700 a class that isn't a utility
+fn check_word(code: &str, offset: usize, class: &str, theme: &Theme, findings: &mut Vec<Finding>) {
+    let Err(unknown) = tailwind::check_class(class, theme) else {
+        return;
+    };
+    let fix = match &unknown.suggestion {
+        Some(suggestion) => format!("Did you mean `{}`?", suggestion),
+        None => "Use a Tailwind utility, an arbitrary value such as `w-[37px]`, or define the name in the theme or a stylesheet".to_string(),
+    };
+    findings.push(
+        Finding::new(
+            "L0700",
+            match &unknown.reason {
+                Some(reason) => format!(
+                    "`{}` is not a Tailwind utility or a class of the project ({}), so it has no styles",
+                    class, reason
+                ),
+                None => format!(
+                    "`{}` is not a Tailwind utility or a class of the project, so it has no styles",
+                    class
+                ),
+            },
+        )
+        .at(code, offset)
+        .with_fix(fix),
+    );
+}
+
+/// L0702 a class name assembled from pieces in a `format!` string
+fn check_interpolated(code: &str, offset: usize, word: &str, findings: &mut Vec<Finding>) {
+    // A whole class substituted in (`"{} p-4"`) is fine
+    let whole = word.starts_with('{') && word.ends_with('}') && word.matches('{').count() == 1;
+    if whole {
+        return;
+    }
+    findings.push(
+        Finding::new(
+            "L0702",
+            format!(
+                "`{}` builds a class name at runtime; Tailwind only generates classes it finds written out in full",
+                word
+            ),
+        )
+        .at(code, offset)
+        .with_fix(
+            "Map each value to a complete class: `match color { Color::Red => \"bg-red-500\", Color::Blue => \"bg-blue-500\" }`",
+        ),
+    );
+}
+
+/// The shapes of conditional `class` values [`check_conditional`] can rewrite:
+/// `if c { "a" } else { "b" }`, the same inside `format!("base {}", ...)`, and
+/// `c.then_some("a")` with an empty fallback
+fn conditional_shapes() -> &'static [Regex] {
+    static SHAPES: OnceLock<Vec<Regex>> = OnceLock::new();
+    SHAPES.get_or_init(|| {
+        [
+            r#"^(?:move\s*\|\|\s*)?\{?\s*if\s+(?P<cond>.+?)\s*\{\s*"(?P<on>[^"]*)"\s*\}\s*else\s*\{\s*"(?P<off>[^"]*)"\s*\}\s*\}?$"#,
+            r#"^(?:move\s*\|\|\s*)?format!\(\s*"(?P<base>[^"{]*)\{\}(?P<tail>[^"{]*)"\s*,\s*if\s+(?P<cond>.+?)\s*\{\s*"(?P<on>[^"]*)"\s*\}\s*else\s*\{\s*"(?P<off>[^"]*)"\s*\}\s*\)$"#,
+            r#"^(?:move\s*\|\|\s*)?(?P<cond>.+?)\s*\.then(?:_some\(\s*|\(\s*\|\|\s*)"(?P<on>[^"]*)"\s*\)\s*\.unwrap_or(?:_default\(\)|\(\s*""\s*\))$"#,
+        ]
+        .iter()
+        .map(|shape| Regex::new(shape).expect("valid class shape"))
+        .collect()
+    })
+}
+
+/// L0701 a class string chosen by a condition instead of toggled classes
+fn check_conditional(code: &str, attr: &Attr, findings: &mut Vec<Finding>) {
+    let Some(expr) = attr.expr() else { return };
+    if expr.starts_with('(') {
+        return;
+    }
+    let masked = mask_literals(expr);
+    let conditional = (masked.contains("if ") && masked.contains("else"))
+        || masked.contains("match ")
+        || masked.contains(".then(")
+        || masked.contains(".then_some(");
+    if !conditional || !expr.contains('"') {
+        return;
+    }
+    let fix = conditional_shapes()
+        .iter()
+        .find_map(|shape| shape.captures(expr))
+        .map(|caps| {
+            let part = |name| caps.name(name).map_or("", |m| m.as_str());
+            let cond = part("cond");
+            let on: Vec<&str> = part("on").split_whitespace().collect();
+            let off: Vec<&str> = part("off").split_whitespace().collect();
+            let mut always: Vec<&str> = part("base").split_whitespace().collect();
+            always.extend(part("tail").split_whitespace());
+            always.extend(on.iter().filter(|c| off.contains(c)));
+            let only_on: Vec<&str> = on.iter().copied().filter(|c| !off.contains(c)).collect();
+            let only_off: Vec<&str> = off.iter().copied().filter(|c| !on.contains(c)).collect();
+            let mut attrs = Vec::new();
+            if !always.is_empty() {
+                attrs.push(format!("class=\"{}\"", always.join(" ")));
+            }
+            if !only_on.is_empty() {
+                attrs.push(toggle(&only_on, cond));
+            }
+            if !only_off.is_empty() {
+                attrs.push(toggle(&only_off, &negate(cond)));
+            }
+            attrs.join(" ")
+        })
+        .unwrap_or_else(|| {
+            "Keep the classes every branch shares in `class=\"...\"` and toggle the rest: `class:shadow-lg=move || active.get()`, or `class=(\"hover:bg-blue-600\", move || active.get())` for names with `:` or `/`".to_string()
+        });
+    findings.push(
+        Finding::new(
+            "L0701",
+            "Conditional `class=` string rebuilds and re-sets the whole class list; toggle the classes that change instead",
+        )
+        .at(code, attr.offset)
+        .with_fix(fix),
+    );
+}
+
+/// The attribute turning `classes` on while `cond` holds
+fn toggle(classes: &[&str], cond: &str) -> String {
+    let plain = |class: &str| {
+        class
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    };
+    match classes {
+        [class] if plain(class) => format!("class:{}=move || {}", class, cond),
+        [class] => format!("class=(\"{}\", move || {})", class, cond),
+        _ => {
+            let quoted: Vec<String> = classes.iter().map(|c| format!("\"{}\"", c)).collect();
+            format!("class=([{}], move || {})", quoted.join(", "), cond)
+        }
+    }
+}
+
+fn negate(cond: &str) -> String {
+    if let Some(inner) = cond.strip_prefix('!') {
+        return inner.to_string();
+    }
+    let simple = cond
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '(' | ')'));
+    if simple {
+        format!("!{}", cond)
+    } else {
+        format!("!({})", cond)
+    }
+}
+
+/// `import_style!`/`import_crate_style!` invocations: module name, stylesheet
+/// path and the offset of the macro
+fn stylance_imports(code: &str) -> Vec<(String, String, usize)> {
+    let bytes = code.as_bytes();
+    let mut imports = Vec::new();
+    for name in ["import_style!", "import_crate_style!"] {
+        for (offset, _) in code.match_indices(name) {
+            let open = offset + name.len();
+            if bytes.get(open) != Some(&b'(') {
+                continue;
+            }
+            let Some(end) = skip_balanced(bytes, open, b'(', b')') else {
+                continue;
+            };
+            let Some((module, path)) = code[open + 1..end - 1].rsplit_once(',') else {
+                continue;
+            };
+            let module = module.split_whitespace().last().unwrap_or_default();
+            let path = path.trim().trim_matches('"');
+            if !module.is_empty() && !path.is_empty() {
+                imports.push((module.to_string(), path.to_string(), offset));
+            }
+        }
+    }
+    imports
+}
+
+/// The stylesheet an import names: relative to the crate root for
+/// `import_crate_style!`; `import_style!` paths are relative to the importing
+/// file, so any stylesheet under the root with that path suffix stands in
+fn resolve_stylesheet(root: &Path, path: &str) -> Option<PathBuf> {
+    let direct = root.join(path);
+    if direct.is_file() {
+        return Some(direct);
+    }
+    let suffix = Path::new(path.trim_start_matches("./"));
+    ignore::WalkBuilder::new(root)
+        .require_git(false)
+        .filter_entry(|e| e.file_name() != "target" && e.file_name() != "node_modules")
+        .build()
+        .filter_map(|entry| entry.ok())
+        .map(|e| e.into_path())
+        .find(|p| p.ends_with(suffix))
+}
+
+/// L0703 `style::name` for a class the imported stylesheet doesn't define
+fn check_stylance(code: &str, root: &Path, findings: &mut Vec<Finding>) {
+    let masked = mask_literals(code);
+    let bytes = masked.as_bytes();
+    for (module, path, _) in stylance_imports(code) {
+        let Some(file) = resolve_stylesheet(root, &path) else {
+            continue;
+        };
+        let Ok(css) = fs::read_to_string(&file) else {
+            continue;
+        };
+        // stylance turns `.btn-primary` into `style::btn_primary`
+        let classes: Vec<String> = css_classes(&css)
+            .into_iter()
+            .map(|c| c.replace('-', "_"))
+            .collect();
+        let prefix = format!("{}::", module);
+        for (offset, _) in masked.match_indices(&prefix) {
+            if offset > 0
+                && (bytes[offset - 1].is_ascii_alphanumeric()
+                    || bytes[offset - 1] == b'_'
+                    || bytes[offset - 1] == b':')
+            {
+                continue;
+            }
+            let start = offset + prefix.len();
+            let name: String = masked[start..]
+                .chars()
+                .take_while(|c| c.is_ascii_alphanumeric() || *c == '_')
+                .collect();
+            if name.is_empty() || classes.contains(&name) {
+                continue;
+            }
+            let closest = classes
+                .iter()
+                .map(|c| (c, crate::docs::similarity(&name, c)))
+                .filter(|(_, score)| *score >= 0.6)
+                .max_by(|a, b| a.1.total_cmp(&b.1))
+                .map(|(c, _)| c);
+            let fix = match closest {
+                Some(class) => format!("Did you mean `{}{}`?", prefix, class),
+                None => format!(
+                    "Add `.{}` to {} or use one of its classes",
+                    name.replace('_', "-"),
+                    path
+                ),
+            };
+            findings.push(
+                Finding::new(
+                    "L0703",
+                    format!(
+                        "`{}{}` has no matching class in {}; stylance only generates constants for the classes the stylesheet defines",
+                        prefix, name, path
+                    ),
+                )
+                .at(code, start)
+                .with_fix(fix),
+            );
+        }
+    }
+}
+
+/// L0704 a `style!` class no `view!` applies
+fn check_stylers(code: &str, findings: &mut Vec<Finding>) {
+    let views = parse_views(code);
+    for item in functions(code) {
+        let body = &code[item.body.clone()];
+        for (index, _) in body.match_indices("let ") {
+            let statement = &body[index + 4..];
+            let Some((name, init)) = statement.split_once('=') else {
+                continue;
+            };
+            let name = name.trim();
+            let init = init.trim_start();
+            let styled = init.starts_with("style!") || init.starts_with("style_sheet!");
+            if !styled || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                continue;
+            }
+            let applied = views
+                .iter()
+                .filter(|view| item.body.contains(&view.start))
+                .any(|view| {
+                    let head: String = code[view.body_start..view.body_end]
+                        .chars()
+                        .filter(|c| !c.is_whitespace())
+                        .take(name.len() + 7)
+                        .collect();
+                    head == format!("class={},", name)
+                });
+            if applied {
+                continue;
+            }
+            findings.push(
+                Finding::new(
+                    "L0704",
+                    format!(
+                        "`{}` holds the scoped class of a stylers block but no `view!` in `{}` applies it, so the styles match nothing",
+                        name, item.name
+                    ),
+                )
+                .at(code, item.body.start + index)
+                .with_fix(format!("view! {{ class = {}, <div>...</div> }}", name)),
+            );
+        }
+    }
+}
@@ -0,0 +1,278 @@
+//! Suspense and Transition rules (L0800–L0803)
+//!
+//! Resources load asynchronously, so every read of one has to cope with the
+//! moment it has no value yet: either under a `<Suspense>`/`<Transition>`
+//! boundary that shows a fallback meanwhile, or by handling the `None` a read
+//! returns while loading. These rules catch reads that do neither, boundaries
+//! that add nothing to the one inside them, and `Suspend::new` calls that
+//! won't re-run or don't await anything.
+
+use super::Finding;
+use crate::reactivity::{handlers, sources, NodeKind};
+use crate::source::{call_ranges, functions, identifier_uses, mask_literals, within};
+use crate::view::{parse_views, Node, ViewMacro};
+use std::collections::HashSet;
+use std::ops::Range;
+
+/// Components that catch resource reads below them and show a fallback
+const BOUNDARIES: &[&str] = &["Suspense", "Transition"];
+
+/// Reads that wait for or subscribe to a resource's value
+const RESOURCE_READS: &[&str] = &[".get()", ".read()", ".with("];
+
+/// Reads that return `None` while the resource is loading
+const LOADING_READS: &[&str] = &[".get()", ".get_untracked()", ".read()", ".read_untracked()"];
+
+/// Adapters between a read and the unwrap, e.g. `.read().as_ref().unwrap()`
+const PASS_THROUGH: &[&str] = &[".as_ref()", ".as_deref()", ".clone()", ".cloned()"];
+
+/// Calls that panic on `None`
+const UNWRAPS: &[&str] = &[".unwrap()", ".expect("];
+
+/// Run every Suspense rule over `code`
+pub fn check(code: &str) -> Vec<Finding> {
+    let masked = mask_literals(code);
+    let views = parse_views(code);
+    let resources: Vec<String> = sources(code, &masked, 0..code.len())
+        .into_iter()
+        .filter(|node| node.kind == NodeKind::Resource)
+        .map(|node| node.name)
+        .collect();
+    let mut findings = Vec::new();
+    check_unguarded(code, &masked, &views, &resources, &mut findings);
+    check_loading_unwraps(code, &masked, &resources, &mut findings);
+    for view in &views {
+        check_nested(code, view, &mut findings);
+    }
+    check_suspend_calls(code, &masked, &views, &mut findings);
+    findings
+}
+
+/// Whether a tag is `<Suspense>` or `<Transition>`, however it's imported
+fn is_boundary(name: &str) -> bool {
+    BOUNDARIES.contains(&name.rsplit("::").next().unwrap_or(name))
+}
+
+/// Index of the closing tag of every non-self-closing element, by the index
+/// of its opening tag
+fn matching_closes(view: &ViewMacro) -> Vec<(usize, usize)> {
+    let mut open: Vec<(usize, &str)> = Vec::new();
+    let mut pairs = Vec::new();
+    for (i, node) in view.nodes.iter().enumerate() {
+        match node {
+            Node::Open(element) if !element.self_closing => open.push((i, &element.name)),
+            Node::Close { name, .. } => {
+                // Tolerate unclosed tags in between, which markup rules report
+                if let Some(depth) = open.iter().rposition(|(_, n)| n == name) {
+                    pairs.push((open[depth].0, i));
+                    open.truncate(depth);
+                }
+            }
+            _ => {}
+        }
+    }
+    pairs
+}
+
+/// Byte ranges from each `<Suspense>`/`<Transition>` tag to its closing tag
+fn boundary_ranges(views: &[ViewMacro]) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    for view in views {
+        for (open, close) in matching_closes(view) {
+            let (Node::Open(element), Node::Close { offset, .. }) =
+                (&view.nodes[open], &view.nodes[close])
+            else {
+                continue;
+            };
+            if is_boundary(&element.name) {
+                ranges.push(element.offset..*offset);
+            }
+        }
+    }
+    ranges
+}
+
+/// L0800 `Suspend::new` or a resource read in a view with no boundary above it
+///
+/// A component whose own markup has no boundary is still fine when this file
+/// renders it inside one.
+fn check_unguarded(
+    code: &str,
+    masked: &str,
+    views: &[ViewMacro],
+    resources: &[String],
+    findings: &mut Vec<Finding>,
+) {
+    let guarded = boundary_ranges(views);
+    let functions = functions(code);
+    let rendered_guarded = |offset: usize| {
+        let Some(function) = functions
+            .iter()
+            .filter(|f| f.body.contains(&offset))
+            .max_by_key(|f| f.body.start)
+        else {
+            return false;
+        };
+        let tag = format!("<{}", function.name);
+        function.is_component()
+            && masked.match_indices(&tag).any(|(at, _)| {
+                !masked[at + tag.len()..].starts_with(|c: char| c.is_alphanumeric() || c == '_')
+                    && within(&guarded, at)
+            })
+    };
+    let mut reported = HashSet::new();
+    for view in views {
+        let body = view.body_start..view.body_end;
+        let handlers = handlers(code, view);
+        let mut reads: Vec<(usize, String)> = masked[body.clone()]
+            .match_indices("Suspend::new")
+            .map(|(rel, _)| (body.start + rel, "Suspend::new".to_string()))
+            .collect();
+        for name in resources {
+            for offset in identifier_uses(masked, body.clone(), name) {
+                let after = &masked[offset + name.len()..];
+                if let Some(read) = RESOURCE_READS.iter().find(|r| after.starts_with(*r)) {
+                    reads.push((offset, format!("{}{}", name, read.trim_end_matches('('))));
+                }
+            }
+        }
+        for (offset, read) in reads {
+            if within(&guarded, offset)
+                || within(&handlers, offset)
+                || rendered_guarded(offset)
+                || !reported.insert(offset)
+            {
+                continue;
+            }
+            findings.push(
+                Finding::new(
+                    "L0800",
+                    format!(
+                        "`{}` waits for a resource outside any <Suspense> or <Transition>: nothing shows while it loads, and the server doesn't wait for it before sending HTML",
+                        read
+                    ),
+                )
+                .at(code, offset)
+                .with_fix("Wrap this part of the view in <Suspense fallback=|| view! { <p>\"Loading...\"</p> }> ... </Suspense>, or render the component inside one"),
+            );
+        }
+    }
+}
+
+/// L0801 a resource read unwrapped as if it had always loaded
+fn check_loading_unwraps(
+    code: &str,
+    masked: &str,
+    resources: &[String],
+    findings: &mut Vec<Finding>,
+) {
+    for name in resources {
+        for offset in identifier_uses(masked, 0..masked.len(), name) {
+            let after = &masked[offset + name.len()..];
+            let Some(read) = LOADING_READS.iter().find(|r| after.starts_with(*r)) else {
+                continue;
+            };
+            let mut rest = after[read.len()..].trim_start();
+            while let Some(adapter) = PASS_THROUGH.iter().find(|a| rest.starts_with(*a)) {
+                rest = rest[adapter.len()..].trim_start();
+            }
+            let Some(unwrap) = UNWRAPS.iter().find(|u| rest.starts_with(*u)) else {
+                continue;
+            };
+            findings.push(
+                Finding::new(
+                    "L0801",
+                    format!(
+                        "`{}{}` is `None` until the resource loads, so `{}` panics on the first render",
+                        name,
+                        read,
+                        unwrap.trim_end_matches('(')
+                    ),
+                )
+                .at(code, offset)
+                .with_fix(format!(
+                    "Handle the loading state: `{}{}.map(|data| view! {{ ... }})`, or await it in `Suspend::new(async move {{ {}.await }})`",
+                    name, read, name
+                )),
+            );
+        }
+    }
+}
+
+/// L0802 a boundary whose only child is another boundary
+fn check_nested(code: &str, view: &ViewMacro, findings: &mut Vec<Finding>) {
+    let closes = matching_closes(view);
+    let close_of = |open: usize| closes.iter().find(|(o, _)| *o == open).map(|(_, c)| *c);
+    for &(open, close) in &closes {
+        let Node::Open(outer) = &view.nodes[open] else {
+            continue;
+        };
+        if !is_boundary(&outer.name) {
+            continue;
+        }
+        let children: Vec<usize> = (open + 1..close)
+            .filter(|i| !matches!(view.nodes[*i], Node::Comment { .. }))
+            .collect();
+        let (Some(&first), Some(&last)) = (children.first(), children.last()) else {
+            continue;
+        };
+        let Node::Open(inner) = &view.nodes[first] else {
+            continue;
+        };
+        if is_boundary(&inner.name) && close_of(first) == Some(last) {
+            findings.push(
+                Finding::new(
+                    "L0802",
+                    format!(
+                        "<{}> only wraps another <{}>, which catches every read inside it, so the outer fallback never shows",
+                        outer.name, inner.name
+                    ),
+                )
+                .at(code, outer.offset)
+                .with_fix(format!(
+                    "Merge the two into one <{}> with the fallback you want",
+                    inner.name
+                )),
+            );
+        }
+    }
+}
+
+/// L0803 `Suspend::new` that doesn't await, or that sits in a view without a
+/// `move ||` closure to re-run it
+fn check_suspend_calls(code: &str, masked: &str, views: &[ViewMacro], findings: &mut Vec<Finding>) {
+    for args in call_ranges(masked, &["Suspend::new"]) {
+        if masked[args.clone()].contains(".await") {
+            continue;
+        }
+        findings.push(
+            Finding::new(
+                "L0803",
+                "Suspend::new here never awaits anything, so it only delays rendering by a tick",
+            )
+            .at(code, args.start)
+            .with_fix("Await the resource inside it (`let data = resource.await;`), or render the value without Suspend"),
+        );
+    }
+    for view in views {
+        for node in &view.nodes {
+            let Node::Block {
+                offset,
+                code: block,
+            } = node
+            else {
+                continue;
+            };
+            if block.trim_start().starts_with("Suspend::new") {
+                findings.push(
+                    Finding::new(
+                        "L0803",
+                        "Suspend::new directly in a view runs once; it won't re-run when the resources it awaits reload",
+                    )
+                    .at(code, *offset)
+                    .with_fix("Put it in a closure: `{move || Suspend::new(async move { ... })}`"),
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,114 @@
+//! Nightly call syntax in stable projects (L0012)
+//!
+//! With Leptos's `nightly` feature, signals implement the `Fn` traits, so
+//! `count()` reads and `set_count(1)` writes. Without it those calls don't
+//! compile, and code copied from nightly examples breaks a stable build.
+
+use super::Finding;
+use crate::reactivity::{sources, NodeKind};
+use crate::source::{functions, identifier_uses, mask_literals, typed_params};
+use crate::view::skip_balanced;
+
+/// Prop types that are signals rather than closures
+const SIGNAL_TYPES: &[&str] = &[
+    "ReadSignal",
+    "WriteSignal",
+    "RwSignal",
+    "Signal",
+    "Memo",
+    "ArcReadSignal",
+    "ArcWriteSignal",
+    "ArcRwSignal",
+    "ArcSignal",
+    "ArcMemo",
+];
+
+/// How a signal binding is called
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Role {
+    Read,
+    Write,
+    /// `RwSignal`: a read without arguments, a write with one
+    Both,
+}
+
+/// L0012 signals called as functions
+pub fn check(code: &str) -> Vec<Finding> {
+    let masked = mask_literals(code);
+    let bytes = masked.as_bytes();
+    let mut findings = Vec::new();
+    for (name, role) in signal_names(code, &masked) {
+        for offset in identifier_uses(&masked, 0..masked.len(), &name) {
+            let after = offset + name.len();
+            let Some(open) = masked[after..]
+                .find(|c: char| !c.is_whitespace())
+                .map(|p| after + p)
+                .filter(|p| bytes[*p] == b'(')
+            else {
+                continue;
+            };
+            if masked[..offset].trim_end().ends_with("fn") {
+                continue;
+            }
+            let Some(close) = skip_balanced(bytes, open, b'(', b')') else {
+                continue;
+            };
+            let args = code[open + 1..close - 1].trim();
+            let method = match (role, args.is_empty()) {
+                (Role::Read, true) | (Role::Both, true) => "get",
+                (Role::Write, false) | (Role::Both, false) => "set",
+                _ => continue,
+            };
+            let call = &code[offset..close];
+            let fix = format!("{}.{}({})", name, method, args);
+            findings.push(
+                Finding::new(
+                    "L0012",
+                    format!(
+                        "`{}` calls a signal as a function, which only compiles with Leptos's `nightly` feature",
+                        call
+                    ),
+                )
+                .at(code, offset)
+                .with_fix(fix.clone())
+                .with_edit(code, offset..close, fix),
+            );
+        }
+    }
+    findings.sort_by_key(|f| (f.line, f.column));
+    findings
+}
+
+/// Signals bound with `let` and signal-typed component props
+fn signal_names(code: &str, masked: &str) -> Vec<(String, Role)> {
+    let mut names = Vec::new();
+    for node in sources(code, masked, 0..code.len()) {
+        let init = masked[node.body.clone()].trim_start();
+        match (node.kind, node.setter) {
+            (NodeKind::Signal, Some(setter)) => {
+                names.push((node.name, Role::Read));
+                names.push((setter, Role::Write));
+            }
+            (NodeKind::Signal, None) => names.push((node.name, Role::Both)),
+            (NodeKind::Memo, _) => names.push((node.name, Role::Read)),
+            (NodeKind::Derived, _) if !init.starts_with("move") => {
+                names.push((node.name, Role::Read))
+            }
+            _ => {}
+        }
+    }
+    for function in functions(code).into_iter().filter(|f| f.is_component()) {
+        for (name, ty) in typed_params(&code[function.params]) {
+            let base = ty.split('<').next().unwrap_or_default().trim();
+            let base = base.rsplit("::").next().unwrap_or(base);
+            let role = match base {
+                "WriteSignal" | "ArcWriteSignal" => Role::Write,
+                "RwSignal" | "ArcRwSignal" => Role::Both,
+                _ if SIGNAL_TYPES.contains(&base) => Role::Read,
+                _ => continue,
+            };
+            names.push((name, role));
+        }
+    }
+    names
+}
@@ -0,0 +1,1254 @@
+//! Tailwind utility classes
+//!
+//! Enough of Tailwind's vocabulary to tell a utility from a typo: the
+//! utilities that take no value, the prefixes that do with the kinds of value
+//! each accepts, the default palette and scales, and the variants. A
+//! project's theme adds its own colors, spacing, fonts, breakpoints and
+//! classes, read from `tailwind.config.js` (v3) or the `@theme` blocks,
+//! `@utility` rules and class selectors of its CSS (v4). Arbitrary values
+//! (`w-[37px]`) and properties (`[mask-type:alpha]`) are always accepted.
+
+use super::styling::css_classes;
+use crate::docs::similarity;
+use ignore::WalkBuilder;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// `tailwind.config.*` file names, looked up in the project root
+const CONFIG_FILES: &[&str] = &[
+    "tailwind.config.js",
+    "tailwind.config.cjs",
+    "tailwind.config.mjs",
+    "tailwind.config.ts",
+];
+
+/// Plugins whose classes are known; any other plugin may add classes of its own
+const KNOWN_PLUGINS: &[&str] = &[
+    "@tailwindcss/typography",
+    "@tailwindcss/forms",
+    "@tailwindcss/container-queries",
+    "@tailwindcss/aspect-ratio",
+];
+
+/// Classes of `@tailwindcss/forms`
+const FORM_CLASSES: &[&str] = &[
+    "form-input",
+    "form-textarea",
+    "form-select",
+    "form-multiselect",
+    "form-checkbox",
+    "form-radio",
+];
+
+/// Names of the default color palette, each with [`SHADES`]
+const PALETTE: &[&str] = &[
+    "slate", "gray", "zinc", "neutral", "stone", "red", "orange", "amber", "yellow", "lime",
+    "green", "emerald", "teal", "cyan", "sky", "blue", "indigo", "violet", "purple", "fuchsia",
+    "pink", "rose",
+];
+
+const SHADES: &[&str] = &[
+    "50", "100", "200", "300", "400", "500", "600", "700", "800", "900", "950",
+];
+
+/// Colors without shades
+const PLAIN_COLORS: &[&str] = &["inherit", "current", "transparent", "black", "white"];
+
+/// Utilities that take no value
+const STATIC: &[&str] = &[
+    // Display and position
+    "block",
+    "inline-block",
+    "inline",
+    "flex",
+    "inline-flex",
+    "grid",
+    "inline-grid",
+    "table",
+    "inline-table",
+    "table-caption",
+    "table-cell",
+    "table-column",
+    "table-column-group",
+    "table-footer-group",
+    "table-header-group",
+    "table-row-group",
+    "table-row",
+    "flow-root",
+    "contents",
+    "list-item",
+    "hidden",
+    "container",
+    "static",
+    "fixed",
+    "absolute",
+    "relative",
+    "sticky",
+    "visible",
+    "invisible",
+    "collapse",
+    "isolate",
+    "sr-only",
+    "not-sr-only",
+    // Flexbox and grid
+    "flex-row",
+    "flex-row-reverse",
+    "flex-col",
+    "flex-col-reverse",
+    "flex-wrap",
+    "flex-wrap-reverse",
+    "flex-nowrap",
+    "grow",
+    "shrink",
+    "grid-flow-row",
+    "grid-flow-col",
+    "grid-flow-dense",
+    "grid-flow-row-dense",
+    "grid-flow-col-dense",
+    // Typography
+    "italic",
+    "not-italic",
+    "underline",
+    "overline",
+    "line-through",
+    "no-underline",
+    "uppercase",
+    "lowercase",
+    "capitalize",
+    "normal-case",
+    "truncate",
+    "antialiased",
+    "subpixel-antialiased",
+    "ordinal",
+    "slashed-zero",
+    "lining-nums",
+    "oldstyle-nums",
+    "proportional-nums",
+    "tabular-nums",
+    "diagonal-fractions",
+    "stacked-fractions",
+    "normal-nums",
+    // Borders, effects and filters
+    "border",
+    "rounded",
+    "shadow",
+    "ring",
+    "outline",
+    "blur",
+    "grayscale",
+    "invert",
+    "sepia",
+    "drop-shadow",
+    "transition",
+    "transform",
+    "transform-gpu",
+    "transform-none",
+    "filter",
+    "backdrop-filter",
+    "divide-x",
+    "divide-y",
+    "divide-x-reverse",
+    "divide-y-reverse",
+    "space-x-reverse",
+    "space-y-reverse",
+    "ring-inset",
+    "resize",
+    // Interactivity
+    "scroll-smooth",
+    "scroll-auto",
+    "snap-start",
+    "snap-end",
+    "snap-center",
+    "snap-align-none",
+    "snap-normal",
+    "snap-always",
+    "snap-none",
+    "snap-x",
+    "snap-y",
+    "snap-both",
+    "snap-mandatory",
+    "snap-proximity",
+    // Markers for group-* and peer-* variants
+    "group",
+    "peer",
+];
+
+/// Kinds of value a utility prefix takes
+#[derive(Debug, Clone, Copy)]
+enum Kind {
+    /// Spacing scale: `4`, `0.5`, `px` or a theme spacing name
+    Spacing,
+    /// Spacing plus fractions and sizes such as `full`, `screen` or `md`
+    Size,
+    /// Palette or theme color, with an optional `/opacity`
+    Color,
+    /// A whole number
+    Number,
+    /// `text-*` font sizes
+    FontSize,
+    /// `font-*` families
+    Font,
+    /// `rounded-*` radii
+    Radius,
+    /// `shadow-*` sizes
+    Shadow,
+    /// `animate-*` animations
+    Animation,
+    /// One of the given words
+    Words(&'static [&'static str]),
+}
+
+use Kind::*;
+
+const SIZES: &[&str] = &[
+    "auto", "full", "screen", "svw", "lvw", "dvw", "svh", "lvh", "dvh", "min", "max", "fit",
+    "none", "prose", "3xs", "2xs", "xs", "sm", "md", "lg", "xl", "2xl", "3xl", "4xl", "5xl", "6xl",
+    "7xl",
+];
+const FONT_SIZES: &[&str] = &[
+    "xs", "sm", "base", "lg", "xl", "2xl", "3xl", "4xl", "5xl", "6xl", "7xl", "8xl", "9xl",
+];
+const FONTS: &[&str] = &["sans", "serif", "mono"];
+const RADII: &[&str] = &[
+    "none", "xs", "sm", "md", "lg", "xl", "2xl", "3xl", "4xl", "full",
+];
+const SHADOWS: &[&str] = &["2xs", "xs", "sm", "md", "lg", "xl", "2xl", "inner", "none"];
+const ANIMATIONS: &[&str] = &["none", "spin", "ping", "pulse", "bounce"];
+const BLURS: &[&str] = &["none", "xs", "sm", "md", "lg", "xl", "2xl", "3xl"];
+const LINE_STYLES: &[&str] = &["solid", "dashed", "dotted", "double", "hidden", "none"];
+const BLEND_MODES: &[&str] = &[
+    "normal",
+    "multiply",
+    "screen",
+    "overlay",
+    "darken",
+    "lighten",
+    "color-dodge",
+    "color-burn",
+    "hard-light",
+    "soft-light",
+    "difference",
+    "exclusion",
+    "hue",
+    "saturation",
+    "color",
+    "luminosity",
+    "plus-darker",
+    "plus-lighter",
+];
+const ALIGN: &[&str] = &[
+    "start",
+    "end",
+    "center",
+    "between",
+    "around",
+    "evenly",
+    "stretch",
+    "baseline",
+    "normal",
+    "auto",
+    "end-safe",
+    "center-safe",
+];
+const SIDES: &[&str] = &[
+    "center",
+    "top",
+    "top-right",
+    "right",
+    "bottom-right",
+    "bottom",
+    "bottom-left",
+    "left",
+    "top-left",
+    "left-top",
+    "left-bottom",
+    "right-top",
+    "right-bottom",
+];
+const CURSORS: &[&str] = &[
+    "auto",
+    "default",
+    "pointer",
+    "wait",
+    "text",
+    "move",
+    "help",
+    "not-allowed",
+    "none",
+    "context-menu",
+    "progress",
+    "cell",
+    "crosshair",
+    "vertical-text",
+    "alias",
+    "copy",
+    "no-drop",
+    "grab",
+    "grabbing",
+    "all-scroll",
+    "col-resize",
+    "row-resize",
+    "n-resize",
+    "e-resize",
+    "s-resize",
+    "w-resize",
+    "ne-resize",
+    "nw-resize",
+    "se-resize",
+    "sw-resize",
+    "ew-resize",
+    "ns-resize",
+    "nesw-resize",
+    "nwse-resize",
+    "zoom-in",
+    "zoom-out",
+];
+const GRADIENT_SIDES: &[&str] = &["t", "tr", "r", "br", "b", "bl", "l", "tl"];
+
+/// Prefixes that take a value, with the kinds of value each accepts
+const FUNCTIONAL: &[(&str, &[Kind])] = &[
+    // Spacing
+    ("p", &[Spacing]),
+    ("px", &[Spacing]),
+    ("py", &[Spacing]),
+    ("pt", &[Spacing]),
+    ("pr", &[Spacing]),
+    ("pb", &[Spacing]),
+    ("pl", &[Spacing]),
+    ("ps", &[Spacing]),
+    ("pe", &[Spacing]),
+    ("m", &[Spacing, Words(&["auto"])]),
+    ("mx", &[Spacing, Words(&["auto"])]),
+    ("my", &[Spacing, Words(&["auto"])]),
+    ("mt", &[Spacing, Words(&["auto"])]),
+    ("mr", &[Spacing, Words(&["auto"])]),
+    ("mb", &[Spacing, Words(&["auto"])]),
+    ("ml", &[Spacing, Words(&["auto"])]),
+    ("ms", &[Spacing, Words(&["auto"])]),
+    ("me", &[Spacing, Words(&["auto"])]),
+    ("gap", &[Spacing]),
+    ("gap-x", &[Spacing]),
+    ("gap-y", &[Spacing]),
+    ("space-x", &[Spacing]),
+    ("space-y", &[Spacing]),
+    ("indent", &[Spacing]),
+    // Sizing and placement
+    ("w", &[Size]),
+    ("h", &[Size]),
+    ("min-w", &[Size]),
+    ("min-h", &[Size]),
+    ("max-w", &[Size]),
+    ("max-h", &[Size]),
+    ("size", &[Size]),
+    ("basis", &[Size]),
+    ("inset", &[Size]),
+    ("inset-x", &[Size]),
+    ("inset-y", &[Size]),
+    ("top", &[Size]),
+    ("right", &[Size]),
+    ("bottom", &[Size]),
+    ("left", &[Size]),
+    ("start", &[Size]),
+    ("end", &[Size]),
+    ("translate-x", &[Size]),
+    ("translate-y", &[Size]),
+    // Colors
+    (
+        "bg",
+        &[
+            Color,
+            Words(&[
+                "fixed",
+                "local",
+                "scroll",
+                "cover",
+                "contain",
+                "auto",
+                "none",
+                "no-repeat",
+                "repeat",
+                "repeat-x",
+                "repeat-y",
+                "repeat-round",
+                "repeat-space",
+            ]),
+            Words(SIDES),
+        ],
+    ),
+    (
+        "bg-clip",
+        &[Words(&["border", "padding", "content", "text"])],
+    ),
+    ("bg-origin", &[Words(&["border", "padding", "content"])]),
+    ("bg-gradient-to", &[Words(GRADIENT_SIDES)]),
+    ("bg-linear-to", &[Words(GRADIENT_SIDES)]),
+    ("bg-blend", &[Words(BLEND_MODES)]),
+    ("mix-blend", &[Words(BLEND_MODES)]),
+    ("from", &[Color]),
+    ("via", &[Color]),
+    ("to", &[Color]),
+    (
+        "text",
+        &[
+            FontSize,
+            Color,
+            Words(&[
+                "left", "center", "right", "justify", "start", "end", "wrap", "nowrap", "balance",
+                "pretty", "ellipsis", "clip",
+            ]),
+        ],
+    ),
+    (
+        "font",
+        &[
+            Font,
+            Number,
+            Words(&[
+                "thin",
+                "extralight",
+                "light",
+                "normal",
+                "medium",
+                "semibold",
+                "bold",
+                "extrabold",
+                "black",
+            ]),
+        ],
+    ),
+    (
+        "border",
+        &[
+            Number,
+            Color,
+            Words(LINE_STYLES),
+            Words(&["collapse", "separate"]),
+        ],
+    ),
+    ("border-x", &[Number, Color]),
+    ("border-y", &[Number, Color]),
+    ("border-t", &[Number, Color]),
+    ("border-r", &[Number, Color]),
+    ("border-b", &[Number, Color]),
+    ("border-l", &[Number, Color]),
+    ("border-s", &[Number, Color]),
+    ("border-e", &[Number, Color]),
+    ("border-spacing", &[Spacing]),
+    ("divide", &[Color, Words(LINE_STYLES)]),
+    ("divide-x", &[Number]),
+    ("divide-y", &[Number]),
+    ("outline", &[Number, Color, Words(LINE_STYLES)]),
+    ("outline-offset", &[Number]),
+    ("ring", &[Number, Color]),
+    ("ring-offset", &[Number, Color]),
+    ("shadow", &[Shadow, Color]),
+    ("fill", &[Color, Words(&["none"])]),
+    ("stroke", &[Color, Number, Words(&["none"])]),
+    ("accent", &[Color, Words(&["auto"])]),
+    ("caret", &[Color]),
+    ("placeholder", &[Color]),
+    (
+        "decoration",
+        &[
+            Color,
+            Number,
+            Words(&[
+                "auto",
+                "from-font",
+                "solid",
+                "double",
+                "dotted",
+                "dashed",
+                "wavy",
+                "clone",
+                "slice",
+            ]),
+        ],
+    ),
+    ("underline-offset", &[Number, Words(&["auto"])]),
+    // Borders and effects
+    ("rounded", &[Radius]),
+    ("rounded-t", &[Radius]),
+    ("rounded-r", &[Radius]),
+    ("rounded-b", &[Radius]),
+    ("rounded-l", &[Radius]),
+    ("rounded-s", &[Radius]),
+    ("rounded-e", &[Radius]),
+    ("rounded-tl", &[Radius]),
+    ("rounded-tr", &[Radius]),
+    ("rounded-br", &[Radius]),
+    ("rounded-bl", &[Radius]),
+    ("rounded-ss", &[Radius]),
+    ("rounded-se", &[Radius]),
+    ("rounded-es", &[Radius]),
+    ("rounded-ee", &[Radius]),
+    ("opacity", &[Number]),
+    ("blur", &[Words(BLURS)]),
+    ("backdrop-blur", &[Words(BLURS)]),
+    ("drop-shadow", &[Words(SHADOWS)]),
+    ("brightness", &[Number]),
+    ("contrast", &[Number]),
+    ("saturate", &[Number]),
+    ("hue-rotate", &[Number]),
+    ("grayscale", &[Number]),
+    ("invert", &[Number]),
+    ("sepia", &[Number]),
+    ("backdrop-brightness", &[Number]),
+    ("backdrop-contrast", &[Number]),
+    ("backdrop-saturate", &[Number]),
+    ("backdrop-grayscale", &[Number]),
+    ("backdrop-opacity", &[Number]),
+    // Transforms, transitions and animation
+    ("scale", &[Number]),
+    ("scale-x", &[Number]),
+    ("scale-y", &[Number]),
+    ("rotate", &[Number]),
+    ("skew-x", &[Number]),
+    ("skew-y", &[Number]),
+    ("origin", &[Words(SIDES)]),
+    (
+        "transition",
+        &[Words(&[
+            "all",
+            "colors",
+            "opacity",
+            "shadow",
+            "transform",
+            "none",
+            "discrete",
+        ])],
+    ),
+    ("duration", &[Number]),
+    ("delay", &[Number]),
+    (
+        "ease",
+        &[Words(&["linear", "in", "out", "in-out", "initial"])],
+    ),
+    ("animate", &[Animation]),
+    (
+        "will-change",
+        &[Words(&["auto", "scroll", "contents", "transform"])],
+    ),
+    // Layout
+    ("z", &[Number, Words(&["auto"])]),
+    ("order", &[Number, Words(&["first", "last", "none"])]),
+    ("flex", &[Number, Words(&["auto", "initial", "none"])]),
+    ("grow", &[Number]),
+    ("shrink", &[Number]),
+    ("grid-cols", &[Number, Words(&["none", "subgrid"])]),
+    ("grid-rows", &[Number, Words(&["none", "subgrid"])]),
+    ("col", &[Words(&["auto"])]),
+    ("row", &[Words(&["auto"])]),
+    ("col-span", &[Number, Words(&["full"])]),
+    ("row-span", &[Number, Words(&["full"])]),
+    ("col-start", &[Number, Words(&["auto"])]),
+    ("col-end", &[Number, Words(&["auto"])]),
+    ("row-start", &[Number, Words(&["auto"])]),
+    ("row-end", &[Number, Words(&["auto"])]),
+    ("auto-cols", &[Words(&["auto", "min", "max", "fr"])]),
+    ("auto-rows", &[Words(&["auto", "min", "max", "fr"])]),
+    ("columns", &[Number, Words(SIZES)]),
+    ("items", &[Words(ALIGN)]),
+    ("justify", &[Words(ALIGN)]),
+    ("justify-items", &[Words(ALIGN)]),
+    ("justify-self", &[Words(ALIGN)]),
+    ("content", &[Words(ALIGN)]),
+    ("self", &[Words(ALIGN)]),
+    ("place-content", &[Words(ALIGN)]),
+    ("place-items", &[Words(ALIGN)]),
+    ("place-self", &[Words(ALIGN)]),
+    (
+        "overflow",
+        &[Words(&["auto", "hidden", "clip", "visible", "scroll"])],
+    ),
+    (
+        "overflow-x",
+        &[Words(&["auto", "hidden", "clip", "visible", "scroll"])],
+    ),
+    (
+        "overflow-y",
+        &[Words(&["auto", "hidden", "clip", "visible", "scroll"])],
+    ),
+    ("overscroll", &[Words(&["auto", "contain", "none"])]),
+    (
+        "object",
+        &[
+            Words(&["contain", "cover", "fill", "none", "scale-down"]),
+            Words(SIDES),
+        ],
+    ),
+    ("aspect", &[Words(&["auto", "square", "video"])]),
+    (
+        "float",
+        &[Words(&["left", "right", "none", "start", "end"])],
+    ),
+    (
+        "clear",
+        &[Words(&["left", "right", "both", "none", "start", "end"])],
+    ),
+    ("box", &[Words(&["border", "content"])]),
+    ("box-decoration", &[Words(&["clone", "slice"])]),
+    ("table", &[Words(&["auto", "fixed"])]),
+    ("isolation", &[Words(&["auto"])]),
+    ("line-clamp", &[Number, Words(&["none"])]),
+    // Typography
+    (
+        "leading",
+        &[
+            Spacing,
+            Words(&["none", "tight", "snug", "normal", "relaxed", "loose"]),
+        ],
+    ),
+    (
+        "tracking",
+        &[Words(&[
+            "tighter", "tight", "normal", "wide", "wider", "widest",
+        ])],
+    ),
+    (
+        "whitespace",
+        &[Words(&[
+            "normal",
+            "nowrap",
+            "pre",
+            "pre-line",
+            "pre-wrap",
+            "break-spaces",
+        ])],
+    ),
+    ("break", &[Words(&["normal", "words", "all", "keep"])]),
+    (
+        "list",
+        &[Words(&[
+            "none",
+            "disc",
+            "decimal",
+            "inside",
+            "outside",
+            "image-none",
+        ])],
+    ),
+    (
+        "align",
+        &[Words(&[
+            "baseline",
+            "top",
+            "middle",
+            "bottom",
+            "text-top",
+            "text-bottom",
+            "sub",
+            "super",
+        ])],
+    ),
+    ("text-ellipsis", &[]),
+    // Interactivity
+    ("cursor", &[Words(CURSORS)]),
+    ("select", &[Words(&["none", "text", "all", "auto"])]),
+    ("pointer-events", &[Words(&["none", "auto"])]),
+    ("resize", &[Words(&["none", "x", "y"])]),
+    ("appearance", &[Words(&["none", "auto"])]),
+    (
+        "touch",
+        &[Words(&[
+            "auto",
+            "none",
+            "pan-x",
+            "pan-left",
+            "pan-right",
+            "pan-y",
+            "pan-up",
+            "pan-down",
+            "pinch-zoom",
+            "manipulation",
+        ])],
+    ),
+    ("backface", &[Words(&["hidden", "visible"])]),
+];
+
+/// Variants written without a value
+const VARIANTS: &[&str] = &[
+    "hover",
+    "focus",
+    "focus-within",
+    "focus-visible",
+    "active",
+    "visited",
+    "target",
+    "first",
+    "last",
+    "only",
+    "odd",
+    "even",
+    "first-of-type",
+    "last-of-type",
+    "only-of-type",
+    "empty",
+    "disabled",
+    "enabled",
+    "checked",
+    "indeterminate",
+    "default",
+    "required",
+    "optional",
+    "valid",
+    "invalid",
+    "user-valid",
+    "user-invalid",
+    "in-range",
+    "out-of-range",
+    "placeholder-shown",
+    "autofill",
+    "read-only",
+    "open",
+    "inert",
+    "before",
+    "after",
+    "first-letter",
+    "first-line",
+    "marker",
+    "selection",
+    "file",
+    "backdrop",
+    "placeholder",
+    "details-content",
+    "sm",
+    "md",
+    "lg",
+    "xl",
+    "2xl",
+    "dark",
+    "portrait",
+    "landscape",
+    "motion-safe",
+    "motion-reduce",
+    "contrast-more",
+    "contrast-less",
+    "print",
+    "rtl",
+    "ltr",
+    "forced-colors",
+    "starting",
+    "pointer-fine",
+    "pointer-coarse",
+    "*",
+    "**",
+];
+
+/// Prefixes of variants that take a value (`group-hover`, `data-[open]`, `max-md`)
+const VARIANT_PREFIXES: &[&str] = &[
+    "group-",
+    "peer-",
+    "has-",
+    "not-",
+    "in-",
+    "aria-",
+    "data-",
+    "supports-",
+    "min-",
+    "max-",
+    "nth-",
+    "@",
+];
+
+/// A project's additions to the default theme
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Theme {
+    colors: Vec<String>,
+    spacing: Vec<String>,
+    fonts: Vec<String>,
+    font_sizes: Vec<String>,
+    radii: Vec<String>,
+    shadows: Vec<String>,
+    animations: Vec<String>,
+    /// Breakpoints, usable as variants
+    screens: Vec<String>,
+    /// Classes defined in the project's CSS, `@utility` names included
+    classes: Vec<String>,
+    /// `@utility name-*` prefixes
+    utility_prefixes: Vec<String>,
+    typography: bool,
+    forms: bool,
+    /// A plugin of unknown classes is in use, so only near-misses are reported
+    open: bool,
+}
+
+/// Theme keys of `tailwind.config.js` and the `@theme` variable namespaces,
+/// with the list each fills
+fn theme_list<'a>(theme: &'a mut Theme, key: &str) -> Option<&'a mut Vec<String>> {
+    Some(match key {
+        "colors" | "color" => &mut theme.colors,
+        "spacing" => &mut theme.spacing,
+        "fontFamily" | "font" => &mut theme.fonts,
+        "fontSize" | "text" => &mut theme.font_sizes,
+        "borderRadius" | "radius" => &mut theme.radii,
+        "boxShadow" | "shadow" => &mut theme.shadows,
+        "animation" | "animate" => &mut theme.animations,
+        "screens" | "breakpoint" => &mut theme.screens,
+        _ => return None,
+    })
+}
+
+impl Theme {
+    /// Theme of the project in `root`: its config file and every stylesheet
+    /// outside `target` and `node_modules`
+    pub(crate) fn load(root: &Path) -> Self {
+        let mut theme = Self::default();
+        for name in CONFIG_FILES {
+            if let Ok(config) = fs::read_to_string(root.join(name)) {
+                theme.read_config(&config);
+            }
+        }
+        for path in stylesheets(root) {
+            if let Ok(css) = fs::read_to_string(&path) {
+                theme.read_css(&css);
+            }
+        }
+        theme
+    }
+
+    /// Keys of the theme objects in a JS config
+    fn read_config(&mut self, config: &str) {
+        for (key, object) in js_objects(config) {
+            let names = object_keys(&object);
+            if let Some(list) = theme_list(self, &key) {
+                list.extend(names);
+            }
+        }
+        self.read_plugins(config);
+    }
+
+    /// `@theme` variables, `@utility` rules, `@custom-variant`s and class selectors
+    fn read_css(&mut self, css: &str) {
+        for (offset, _) in css.match_indices("--") {
+            let name: String = css[offset + 2..]
+                .chars()
+                .take_while(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+                .collect();
+            let rest = css[offset + 2 + name.len()..].trim_start();
+            if !rest.starts_with(':') {
+                continue;
+            }
+            if let Some((namespace, value)) = name.split_once('-') {
+                // `--text-lg--line-height` configures `text-lg`
+                let value = value.split("--").next().unwrap_or(value);
+                if let Some(list) = theme_list(self, namespace) {
+                    list.push(value.to_string());
+                }
+            }
+        }
+        for line in css.lines().map(str::trim) {
+            if let Some(rest) = line.strip_prefix("@utility ") {
+                let name = rest.trim_end_matches('{').trim();
+                match name.strip_suffix("-*") {
+                    Some(prefix) => self.utility_prefixes.push(prefix.to_string()),
+                    None => self.classes.push(name.to_string()),
+                }
+            } else if let Some(rest) = line.strip_prefix("@custom-variant ") {
+                if let Some(name) = rest.split_whitespace().next() {
+                    self.screens.push(name.trim_end_matches(';').to_string());
+                }
+            }
+        }
+        self.classes.extend(css_classes(css));
+        self.read_plugins(css);
+    }
+
+    fn read_plugins(&mut self, text: &str) {
+        for line in text.lines() {
+            let Some(start) = line.find("@plugin").or_else(|| line.find("require(")) else {
+                continue;
+            };
+            let Some(name) = line[start..]
+                .split(['"', '\''])
+                .nth(1)
+                .filter(|name| !name.starts_with('.'))
+            else {
+                continue;
+            };
+            match name {
+                "@tailwindcss/typography" => self.typography = true,
+                "@tailwindcss/forms" => self.forms = true,
+                _ if KNOWN_PLUGINS.contains(&name) => {}
+                _ if name.contains("tailwind") || line.contains("@plugin") => self.open = true,
+                _ => {}
+            }
+        }
+        if text.contains("daisyui") {
+            self.open = true;
+        }
+    }
+}
+
+/// Stylesheets of the project in `root`
+fn stylesheets(root: &Path) -> Vec<PathBuf> {
+    WalkBuilder::new(root)
+        .require_git(false)
+        .filter_entry(|e| e.file_name() != "target" && e.file_name() != "node_modules")
+        .build()
+        .filter_map(|entry| entry.ok())
+        .map(|e| e.into_path())
+        .filter(|p| {
+            p.extension()
+                .is_some_and(|ext| ext == "css" || ext == "scss" || ext == "sass")
+        })
+        .collect()
+}
+
+/// Whether the project in `root` is set up for Tailwind
+pub(crate) fn detect(root: &Path, input_file: Option<&str>) -> bool {
+    if input_file.is_some() || CONFIG_FILES.iter().any(|name| root.join(name).is_file()) {
+        return true;
+    }
+    stylesheets(root).iter().any(|path| {
+        fs::read_to_string(path).is_ok_and(|css| {
+            css.contains("@import \"tailwindcss\"")
+                || css.contains("@import 'tailwindcss'")
+                || css.contains("@tailwind ")
+        })
+    })
+}
+
+/// `key: { ... }` objects in JS source, with their bodies
+fn js_objects(source: &str) -> Vec<(String, String)> {
+    let bytes = source.as_bytes();
+    let mut objects = Vec::new();
+    for (colon, _) in source.match_indices(':') {
+        let key: String = source[..colon]
+            .trim_end()
+            .trim_end_matches(['"', '\''])
+            .chars()
+            .rev()
+            .take_while(|c| c.is_ascii_alphanumeric() || *c == '_')
+            .collect::<Vec<_>>()
+            .into_iter()
+            .rev()
+            .collect();
+        let open = colon + 1 + (source[colon + 1..].len() - source[colon + 1..].trim_start().len());
+        if key.is_empty() || bytes.get(open) != Some(&b'{') {
+            continue;
+        }
+        if let Some(end) = crate::view::skip_balanced(bytes, open, b'{', b'}') {
+            objects.push((key, source[open + 1..end - 1].to_string()));
+        }
+    }
+    objects
+}
+
+/// Keys of a JS object body; nested objects give `outer-inner` names, and
+/// `DEFAULT` the outer name alone
+fn object_keys(body: &str) -> Vec<String> {
+    let bytes = body.as_bytes();
+    let mut keys = Vec::new();
+    let mut pos = 0;
+    while pos < bytes.len() {
+        let entry_start = pos;
+        // Find the end of this entry: the next top-level comma
+        let mut end = pos;
+        while end < bytes.len() && bytes[end] != b',' {
+            end = match bytes[end] {
+                b'{' => crate::view::skip_balanced(bytes, end, b'{', b'}').unwrap_or(bytes.len()),
+                b'[' => crate::view::skip_balanced(bytes, end, b'[', b']').unwrap_or(bytes.len()),
+                b'(' => crate::view::skip_balanced(bytes, end, b'(', b')').unwrap_or(bytes.len()),
+                b'"' | b'\'' | b'`' => {
+                    let quote = bytes[end];
+                    let mut i = end + 1;
+                    while i < bytes.len() && bytes[i] != quote {
+                        i += if bytes[i] == b'\\' { 2 } else { 1 };
+                    }
+                    i + 1
+                }
+                _ => end + 1,
+            };
+        }
+        let entry = &body[entry_start..end.min(body.len())];
+        pos = end + 1;
+        let Some((key, value)) = entry.split_once(':') else {
+            continue;
+        };
+        let key = key.trim().trim_matches(['"', '\'']);
+        if key.is_empty() || key.starts_with("//") || key.starts_with("...") {
+            continue;
+        }
+        let value = value.trim();
+        if value.starts_with('{') {
+            for inner in object_keys(&value[1..value.len().saturating_sub(1)]) {
+                keys.push(if inner == "DEFAULT" {
+                    key.to_string()
+                } else {
+                    format!("{}-{}", key, inner)
+                });
+            }
+        } else {
+            keys.push(key.to_string());
+        }
+    }
+    keys
+}
+
+/// Why a class isn't a utility, with a close match
+#[derive(Debug, Clone)]
+pub(crate) struct Unknown {
+    /// What is wrong, when more is known than that the name isn't a utility
+    pub reason: Option<String>,
+    pub suggestion: Option<String>,
+}
+
+/// Check one class of a `class` attribute
+///
+/// Unknown names that don't resemble a utility are taken for the project's
+/// own classes and accepted: a known prefix with a value that is neither
+/// close to a valid one nor numeric (`my-card`) is one of those.
+pub(crate) fn check_class(class: &str, theme: &Theme) -> Result<(), Unknown> {
+    let parts = split_variants(class);
+    let Some((utility, variants)) = parts.split_last() else {
+        return Ok(());
+    };
+    for variant in variants {
+        if !is_variant(variant, theme) {
+            let suggestion = closest(
+                variant,
+                VARIANTS
+                    .iter()
+                    .copied()
+                    .chain(theme.screens.iter().map(String::as_str)),
+            )
+            .map(|v| class.replacen(&format!("{}:", variant), &format!("{}:", v), 1));
+            return Err(Unknown {
+                reason: Some(format!("unknown variant `{}`", variant)),
+                suggestion,
+            });
+        }
+    }
+    let utility = utility.trim_start_matches('!').trim_end_matches('!');
+    match check_utility(utility, theme) {
+        Ok(()) => Ok(()),
+        Err(reason) => {
+            let prefix = &class[..class.len() - parts.last().map_or(0, |u| u.len())];
+            let suggestion = suggest(utility, theme).map(|s| format!("{}{}", prefix, s));
+            let numeric = reason.is_some() && utility.contains(|c: char| c.is_ascii_digit());
+            if suggestion.is_some() || (numeric && !theme.open) {
+                Err(Unknown { reason, suggestion })
+            } else {
+                Ok(())
+            }
+        }
+    }
+}
+
+/// A class split at the `:`s outside brackets: variants, then the utility
+fn split_variants(class: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in class.char_indices() {
+        match c {
+            '[' | '(' => depth += 1,
+            ']' | ')' => depth -= 1,
+            ':' if depth == 0 => {
+                parts.push(&class[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&class[start..]);
+    parts
+}
+
+fn is_variant(variant: &str, theme: &Theme) -> bool {
+    let name = variant.split('/').next().unwrap_or(variant);
+    VARIANTS.contains(&name)
+        || theme.screens.iter().any(|s| s == name)
+        || (name.starts_with('[') && name.ends_with(']'))
+        || VARIANT_PREFIXES.iter().any(|p| name.starts_with(p))
+}
+
+/// `Ok` for a utility; `Err(Some(reason))` when the prefix is known but the
+/// value isn't, `Err(None)` when nothing matches
+fn check_utility(utility: &str, theme: &Theme) -> Result<(), Option<String>> {
+    if (utility.starts_with('[') && utility.ends_with(']'))
+        || STATIC.contains(&utility)
+        || theme.classes.iter().any(|c| c == utility)
+        || is_plugin_class(utility, theme)
+    {
+        return Ok(());
+    }
+    // `group/item` and `peer/item` name a group
+    if let Some(("group" | "peer", _)) = utility.split_once('/') {
+        return Ok(());
+    }
+    let positive = utility.strip_prefix('-').unwrap_or(utility);
+    if theme.utility_prefixes.iter().any(|p| {
+        positive
+            .strip_prefix(p.as_str())
+            .is_some_and(|v| v.starts_with('-'))
+    }) {
+        return Ok(());
+    }
+    let mut reason = None;
+    for (prefix, kinds) in FUNCTIONAL {
+        let Some(value) = positive
+            .strip_prefix(prefix)
+            .and_then(|rest| rest.strip_prefix('-'))
+        else {
+            continue;
+        };
+        if is_arbitrary(value) || kinds.iter().any(|kind| accepts(*kind, value, theme)) {
+            return Ok(());
+        }
+        // The longest matching prefix explains best
+        if reason
+            .as_ref()
+            .is_none_or(|(p, _): &(&str, _)| prefix.len() > p.len())
+        {
+            reason = Some((*prefix, describe(kinds, value)));
+        }
+    }
+    Err(reason.map(|(_, reason)| reason))
+}
+
+fn is_plugin_class(utility: &str, theme: &Theme) -> bool {
+    (theme.typography
+        && (utility == "prose" || utility.starts_with("prose-") || utility == "not-prose"))
+        || (theme.forms && FORM_CLASSES.contains(&utility))
+}
+
+/// `[37px]`, or `(--my-var)` for a CSS variable
+fn is_arbitrary(value: &str) -> bool {
+    (value.starts_with('[') && value.ends_with(']'))
+        || (value.starts_with('(') && value.ends_with(')'))
+}
+
+fn accepts(kind: Kind, value: &str, theme: &Theme) -> bool {
+    let listed = |defaults: &[&str], extra: &[String]| {
+        defaults.contains(&value) || extra.iter().any(|e| e == value)
+    };
+    match kind {
+        Spacing => is_number(value) || value == "px" || listed(&[], &theme.spacing),
+        Size => {
+            accepts(Spacing, value, theme)
+                || is_fraction(value)
+                || listed(SIZES, &theme.spacing)
+                || value.starts_with("screen-")
+        }
+        Color => {
+            let color = match value.rsplit_once('/') {
+                Some((color, opacity)) if is_number(opacity) || is_arbitrary(opacity) => color,
+                _ => value,
+            };
+            is_color(color, theme)
+        }
+        Number => is_number(value),
+        FontSize => listed(FONT_SIZES, &theme.font_sizes),
+        Font => listed(FONTS, &theme.fonts),
+        Radius => listed(RADII, &theme.radii),
+        Shadow => listed(SHADOWS, &theme.shadows),
+        Animation => listed(ANIMATIONS, &theme.animations),
+        Words(words) => words.contains(&value),
+    }
+}
+
+fn is_color(color: &str, theme: &Theme) -> bool {
+    PLAIN_COLORS.contains(&color)
+        || theme.colors.iter().any(|c| c == color)
+        || color
+            .rsplit_once('-')
+            .is_some_and(|(name, shade)| PALETTE.contains(&name) && SHADES.contains(&shade))
+}
+
+fn is_number(value: &str) -> bool {
+    !value.is_empty()
+        && value.chars().all(|c| c.is_ascii_digit() || c == '.')
+        && value.parse::<f64>().is_ok()
+}
+
+fn is_fraction(value: &str) -> bool {
+    value
+        .split_once('/')
+        .is_some_and(|(a, b)| is_number(a) && is_number(b))
+}
+
+/// What a prefix expected, for the message
+fn describe(kinds: &[Kind], value: &str) -> String {
+    let color = kinds.iter().any(|k| matches!(k, Color));
+    let only_color = kinds.iter().all(|k| matches!(k, Color | Number));
+    if color && (only_color || value.contains(|c: char| c.is_ascii_digit()) && value.contains('-'))
+    {
+        format!("unknown color `{}`", value)
+    } else {
+        format!("unknown value `{}`", value)
+    }
+}
+
+/// A valid utility close to `utility`
+fn suggest(utility: &str, theme: &Theme) -> Option<String> {
+    let mut candidates: Vec<String> = STATIC.iter().map(|s| s.to_string()).collect();
+    let (sign, positive) = match utility.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", utility),
+    };
+    // Every split into a prefix and a value, each side corrected on its own
+    for (split, _) in positive.match_indices('-') {
+        let (head, value) = (&positive[..split], &positive[split + 1..]);
+        for (prefix, kinds) in FUNCTIONAL {
+            if similarity(head, prefix) >= 0.6 {
+                candidates.push(format!("{}{}-{}", sign, prefix, value));
+            }
+            if *prefix == head {
+                candidates.extend(
+                    values(kinds, value, theme)
+                        .into_iter()
+                        .map(|v| format!("{}{}-{}", sign, prefix, v)),
+                );
+            }
+        }
+    }
+    let valid: Vec<String> = candidates
+        .into_iter()
+        .filter(|c| c != utility && check_utility(c, theme).is_ok())
+        .collect();
+    closest(utility, valid.iter().map(String::as_str)).map(String::from)
+}
+
+/// Values of `kinds` that could stand for a mistyped `value`
+fn values(kinds: &[Kind], value: &str, theme: &Theme) -> Vec<String> {
+    let mut values = Vec::new();
+    for kind in kinds {
+        match kind {
+            Color => {
+                let (name, shade) = value.rsplit_once('-').unwrap_or((value, ""));
+                for palette in PALETTE {
+                    values.push(if shade.is_empty() {
+                        palette.to_string()
+                    } else {
+                        format!("{}-{}", palette, shade)
+                    });
+                }
+                if !SHADES.contains(&shade) {
+                    values.push(format!("{}-500", name));
+                }
+                values.extend(PLAIN_COLORS.iter().map(|c| c.to_string()));
+                values.extend(theme.colors.iter().cloned());
+            }
+            FontSize => values.extend(FONT_SIZES.iter().map(|s| s.to_string())),
+            Font => values.extend(FONTS.iter().map(|s| s.to_string())),
+            Radius => values.extend(RADII.iter().map(|s| s.to_string())),
+            Shadow => values.extend(SHADOWS.iter().map(|s| s.to_string())),
+            Animation => values.extend(ANIMATIONS.iter().map(|s| s.to_string())),
+            Size => values.extend(SIZES.iter().map(|s| s.to_string())),
+            Words(words) => values.extend(words.iter().map(|s| s.to_string())),
+            Spacing | Number => {}
+        }
+    }
+    values
+}
+
+/// The most similar of `candidates`, if similar enough to be a typo
+fn closest<'a>(word: &str, candidates: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    candidates
+        .map(|c| (c, similarity(word, c)))
+        .filter(|(_, score)| *score >= 0.75)
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(c, _)| c)
+}
@@ -0,0 +1,285 @@
+//! Reads that don't track (L0008–L0010)
+//!
+//! A signal read subscribes only while something reactive runs. Reads in the
+//! component body, untracked reads in view closures and memos, and reads in
+//! `spawn_local` tasks all happen once, so what depends on them silently
+//! stops updating.
+
+use super::Finding;
+use crate::reactivity::{handlers, sources, NodeKind};
+use crate::source::{
+    call_ranges, callback_closures, closures, functions, identifier_uses, let_bindings,
+    mask_literals, typed_params, within,
+};
+use crate::view::parse_views;
+use std::collections::HashSet;
+use std::ops::Range;
+
+/// Reads that subscribe the running reactive context
+const TRACKED_READS: &[&str] = &[".get()", ".with(", ".read()", ".track()"];
+
+/// Reads that don't
+const UNTRACKED_READS: &[&str] = &[".get_untracked()", ".with_untracked(", ".read_untracked()"];
+
+/// Type names of reactive props
+const REACTIVE_TYPES: &[&str] = &["Signal", "Memo", "Resource"];
+
+/// Effect constructors, whose closures track what they read
+const EFFECTS: &[&str] = &["Effect::new", "create_effect", "RenderEffect::new"];
+
+/// Run every tracking rule over `code`
+pub fn check(code: &str) -> Vec<Finding> {
+    let masked = mask_literals(code);
+    let readable = readable_names(code, &masked);
+    let mut findings = Vec::new();
+    check_stale_reads(code, &masked, &readable, &mut findings);
+    check_untracked_closures(code, &masked, &readable, &mut findings);
+    check_spawn_local(code, &masked, &readable, &mut findings);
+    findings
+}
+
+/// L0008 `let x = count.get();` in a component body, with `x` shown in the view
+fn check_stale_reads(
+    code: &str,
+    masked: &str,
+    readable: &HashSet<String>,
+    findings: &mut Vec<Finding>,
+) {
+    let all_closures: Vec<Range<usize>> = closures(code).iter().map(|c| c.range()).collect();
+    let views = parse_views(code);
+    for component in functions(code).into_iter().filter(|f| f.is_component()) {
+        let in_views: Vec<(Range<usize>, Vec<Range<usize>>)> = views
+            .iter()
+            .filter(|v| component.body.contains(&v.start))
+            .map(|v| (v.body_start..v.body_end, handlers(code, v)))
+            .collect();
+        for binding in let_bindings(masked, component.body.clone()) {
+            if within(&all_closures, binding.init.start) {
+                continue;
+            }
+            let Some((_, source)) = tracked_reads(masked, binding.init.clone(), readable)
+                .into_iter()
+                .find(|(offset, _)| !within(&all_closures, *offset))
+            else {
+                continue;
+            };
+            let rendered = in_views.iter().any(|(body, handlers)| {
+                identifier_uses(masked, body.clone(), &binding.name)
+                    .into_iter()
+                    .any(|offset| !within(handlers, offset))
+            });
+            if !rendered {
+                continue;
+            }
+            let start = masked[..binding.init.start]
+                .rfind("let")
+                .unwrap_or(binding.init.start);
+            findings.push(
+                Finding::new(
+                    "L0008",
+                    format!(
+                        "`{}` reads `{}` once when the component runs; the view showing it won't update when `{}` changes",
+                        binding.name, source, source
+                    ),
+                )
+                .at(code, start)
+                .with_fix(format!(
+                    "let {} = move || {};  and render it as {{{}}}",
+                    binding.name,
+                    code[binding.init.clone()].trim(),
+                    binding.name
+                )),
+            );
+        }
+    }
+}
+
+/// L0009 view closures, memos and derived signals whose only reads are untracked
+fn check_untracked_closures(
+    code: &str,
+    masked: &str,
+    readable: &HashSet<String>,
+    findings: &mut Vec<Finding>,
+) {
+    let all_closures = closures(code);
+    let mut candidates: Vec<Range<usize>> = sources(code, masked, 0..code.len())
+        .into_iter()
+        .filter(|n| matches!(n.kind, NodeKind::Memo | NodeKind::Derived))
+        .map(|n| n.body)
+        .collect();
+    for view in parse_views(code) {
+        let handlers = handlers(code, &view);
+        candidates.extend(
+            all_closures
+                .iter()
+                .filter(|c| (view.body_start..view.body_end).contains(&c.start))
+                .filter(|c| !c.has_params() && !within(&handlers, c.start))
+                .map(|c| c.body.clone()),
+        );
+    }
+    let mut reported = HashSet::new();
+    for range in candidates {
+        let body = &masked[range.clone()];
+        // Closures that write or spawn are actions, not computed values
+        let acts = [".set(", ".update(", ".write()", "spawn_local"]
+            .iter()
+            .any(|p| body.contains(p));
+        // Also counts reads of signals declared elsewhere, like context or props
+        let tracks = TRACKED_READS.iter().any(|r| body.contains(r))
+            || !tracked_reads(masked, range.clone(), readable).is_empty();
+        if acts || tracks {
+            continue;
+        }
+        let Some(offset) = UNTRACKED_READS
+            .iter()
+            .filter_map(|read| body.find(read))
+            .min()
+            .map(|rel| range.start + rel)
+        else {
+            continue;
+        };
+        if !reported.insert(offset) {
+            continue;
+        }
+        let receiver = receiver_before(masked, offset);
+        findings.push(
+            Finding::new(
+                "L0009",
+                format!(
+                    "Untracked read of `{}` is the only read in this reactive closure, so it never updates when `{}` changes",
+                    receiver, receiver
+                ),
+            )
+            .at(code, offset - receiver.len())
+            .with_fix(
+                "Use `.get()`, `.with(...)` or `.read()` so the closure subscribes; keep untracked reads for event handlers and effects",
+            ),
+        );
+    }
+}
+
+/// L0010 signals read inside `spawn_local` outside event handlers
+fn check_spawn_local(
+    code: &str,
+    masked: &str,
+    readable: &HashSet<String>,
+    findings: &mut Vec<Finding>,
+) {
+    // Handlers and callbacks read once by design; effect closures take `|_|` too but track
+    let effects = call_ranges(masked, EFFECTS);
+    let callbacks: Vec<Range<usize>> = callback_closures(code)
+        .into_iter()
+        .filter(|c| !within(&effects, c.start))
+        .collect();
+    for task in call_ranges(masked, &["spawn_local"]) {
+        if within(&callbacks, task.start) {
+            continue;
+        }
+        let Some((offset, source)) = tracked_reads(masked, task.clone(), readable)
+            .into_iter()
+            .next()
+        else {
+            continue;
+        };
+        findings.push(
+            Finding::new(
+                "L0010",
+                format!(
+                    "`{}` is read inside `spawn_local`, once when the task runs; nothing re-runs it when `{}` changes",
+                    source, source
+                ),
+            )
+            .at(code, offset)
+            .with_fix(format!(
+                "Use `LocalResource::new(move || {{ let value = {}.get(); async move {{ ... }} }})` to re-run on changes, or read `{}.get_untracked()` before spawning if one read is intended",
+                source, source
+            )),
+        );
+    }
+}
+
+/// Signals, memos, resources and reactive props, by name
+fn readable_names(code: &str, masked: &str) -> HashSet<String> {
+    let mut names: HashSet<String> = sources(code, masked, 0..code.len())
+        .into_iter()
+        .map(|n| n.name)
+        .collect();
+    for function in functions(code).into_iter().filter(|f| f.is_component()) {
+        names.extend(
+            typed_params(&code[function.params])
+                .into_iter()
+                .filter(|(_, ty)| REACTIVE_TYPES.iter().any(|t| ty.contains(t)))
+                .map(|(name, _)| name),
+        );
+    }
+    names
+}
+
+/// Subscribing reads of `names` in `range`: `x.get()`, `x.with(...)`, `x.read()`, `x()`
+fn tracked_reads(
+    masked: &str,
+    range: Range<usize>,
+    names: &HashSet<String>,
+) -> Vec<(usize, String)> {
+    let mut reads: Vec<(usize, String)> = names
+        .iter()
+        .flat_map(|name| {
+            identifier_uses(masked, range.clone(), name)
+                .into_iter()
+                .filter(|offset| {
+                    let after = masked[offset + name.len()..].trim_start();
+                    after.starts_with("()") || TRACKED_READS.iter().any(|r| after.starts_with(r))
+                })
+                .map(|offset| (offset, name.clone()))
+        })
+        .collect();
+    reads.sort();
+    reads
+}
+
+/// Identifier directly before the `.` at `offset`
+pub(super) fn receiver_before(masked: &str, offset: usize) -> &str {
+    let before = &masked[..offset];
+    let start = before
+        .char_indices()
+        .rev()
+        .find(|&(_, c)| !c.is_alphanumeric() && c != '_')
+        .map_or(0, |(i, c)| i + c.len_utf8());
+    &before[start..]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn receiver_before_ascii() {
+        let code = "move || items.get()";
+        assert_eq!(receiver_before(code, code.find('.').unwrap()), "items");
+        assert_eq!(receiver_before(".get()", 0), "");
+    }
+
+    #[test]
+    fn receiver_before_multibyte() {
+        // Stops after the emoji rather than slicing into it
+        let code = "each=move ||it😀ems.get()";
+        assert_eq!(receiver_before(code, code.find('.').unwrap()), "ems");
+        let code = "é.get()";
+        assert_eq!(receiver_before(code, code.find('.').unwrap()), "é");
+    }
+
+    #[test]
+    fn multibyte_receiver_in_view() {
+        let code = r#"
+#[component]
+fn List() -> impl IntoView {
+    view! {
+        <For each=move ||it😀ems.get() key=|i| i.id let:item>
+            <p>{move ||it😀ems.get().len()}</p>
+        </For>
+    }
+}
+"#;
+        crate::rules::check_file(code);
+    }
+}
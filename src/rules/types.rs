@@ -0,0 +1,234 @@
+//! View and element type rules (L0013–L0014)
+//!
+//! Leptos 0.7 made views statically typed. 0.6's `View` enum is gone:
+//! `View<T>` is now generic and `.into_view()` keeps the type, so branches
+//! that used it to agree need `.into_any()` and an `AnyView`. Element
+//! references went the same way: a `NodeRef<Input>` (typed by the element
+//! it's bound to with `node_ref=`) yields an `HtmlInputElement`, so the
+//! `dyn_into` casts 0.6 code needed are redundant, or wrong when they name
+//! another element.
+
+use super::Finding;
+use crate::source::{identifier_uses, let_bindings, mask_literals, statement_end};
+use crate::view::{dom_interface, parse_views, skip_balanced, Node, ViewMacro};
+use std::ops::Range;
+
+/// Casts from `wasm_bindgen::JsCast`, and whether each yields the value itself
+const CASTS: &[(&str, bool)] = &[
+    (".dyn_into::<", true),
+    (".unchecked_into::<", true),
+    (".dyn_ref::<", false),
+    (".unchecked_ref::<", false),
+];
+
+/// Run every view and element type rule over `code`
+pub fn check(code: &str) -> Vec<Finding> {
+    let masked = mask_literals(code);
+    let views = parse_views(code);
+    let mut findings = Vec::new();
+    check_into_view(code, &masked, &views, &mut findings);
+    check_view_type(code, &masked, &views, &mut findings);
+    check_node_ref_casts(code, &masked, &views, &mut findings);
+    findings
+}
+
+/// L0013 `.into_view()` on a `view!` or ending a branch, where 0.6 code erased
+/// the view's type
+fn check_into_view(code: &str, masked: &str, views: &[ViewMacro], findings: &mut Vec<Finding>) {
+    for (offset, call) in masked.match_indices(".into_view()") {
+        let on_view = views.iter().any(|v| v.body_end + 1 == offset);
+        let ends_branch = masked[offset + call.len()..]
+            .trim_start()
+            .starts_with([',', '}']);
+        if !on_view && !ends_branch {
+            continue;
+        }
+        let method = offset + 1..offset + ".into_view".len();
+        findings.push(
+            Finding::new(
+                "L0013",
+                "`.into_view()` no longer erases a view's type in Leptos 0.7+, so branches built with it still differ",
+            )
+            .at(code, offset + 1)
+            .with_fix("Replace `.into_view()` with `.into_any()`, which returns an `AnyView`")
+            .with_edit(code, method, "into_any"),
+        );
+    }
+}
+
+/// L0013 0.6's `View` as a type, which needs a parameter in 0.7+
+fn check_view_type(code: &str, masked: &str, views: &[ViewMacro], findings: &mut Vec<Finding>) {
+    let defined = [
+        "struct View",
+        "enum View",
+        "type View",
+        "trait View",
+        "as View",
+    ]
+    .iter()
+    .any(|definition| masked.contains(definition));
+    if defined {
+        return;
+    }
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    for (offset, name) in masked.match_indices("View") {
+        let before = masked[..offset].trim_end();
+        let after = &masked[offset + name.len()..];
+        let in_type = before.ends_with("->")
+            || before.ends_with('<')
+            || (before.ends_with(':') && !before.ends_with("::"));
+        // `<View/>` in markup is a component
+        let in_markup = views
+            .iter()
+            .any(|v| (v.body_start..v.body_end).contains(&offset));
+        if !in_type
+            || in_markup
+            || masked[..offset].ends_with(is_ident)
+            || after.starts_with(is_ident)
+            || after.trim_start().starts_with(['<', ':'])
+        {
+            continue;
+        }
+        findings.push(
+            Finding::new(
+                "L0013",
+                "`View` was 0.6's type-erased view; in Leptos 0.7+ `View<T>` wraps one concrete view type",
+            )
+            .at(code, offset)
+            .with_fix("Use `AnyView`, and `.into_any()` to produce it")
+            .with_edit(code, offset..offset + name.len(), "AnyView"),
+        );
+    }
+}
+
+/// L0014 a `NodeRef`'s element cast to a `web_sys` type, which the element it
+/// is bound to already decides
+fn check_node_ref_casts(
+    code: &str,
+    masked: &str,
+    views: &[ViewMacro],
+    findings: &mut Vec<Finding>,
+) {
+    let bound_to = |name: &str| {
+        views
+            .iter()
+            .flat_map(|v| &v.nodes)
+            .find_map(|node| match node {
+                Node::Open(element)
+                    if element
+                        .attr("node_ref")
+                        .is_some_and(|a| a.expr() == Some(name)) =>
+                {
+                    Some(element.name.clone())
+                }
+                _ => None,
+            })
+    };
+    for binding in let_bindings(masked, 0..masked.len()) {
+        let init = masked[binding.init.clone()].trim();
+        if !init.starts_with("NodeRef") && !init.starts_with("create_node_ref") {
+            continue;
+        }
+        let Some(tag) = bound_to(&binding.name) else {
+            continue;
+        };
+        let interface = dom_interface(&tag);
+        for offset in identifier_uses(masked, binding.scope.clone(), &binding.name) {
+            let read = &masked[offset + binding.name.len()..];
+            if !read.starts_with(".get()") && !read.starts_with(".get_untracked()") {
+                continue;
+            }
+            let line_end = masked[offset..]
+                .find('\n')
+                .map_or(masked.len(), |p| offset + p);
+            let end = statement_end(masked, offset, binding.scope.end).unwrap_or(line_end);
+            let Some((cast, target, range)) = cast_in(masked, offset..end) else {
+                continue;
+            };
+            if target == "HtmlElement" || target == "Element" || target == "Node" {
+                continue;
+            }
+            let mut finding = if target == interface {
+                Finding::new(
+                    "L0014",
+                    format!(
+                        "`{}` is bound to <{}>, so `{}.get()` already returns `{}`; the `{}` cast is redundant",
+                        binding.name,
+                        tag,
+                        binding.name,
+                        interface,
+                        cast.trim_start_matches('.').trim_end_matches("::<")
+                    ),
+                )
+                .with_fix(format!(
+                    "Use the element `{}.get()` returns as it is",
+                    binding.name
+                ))
+            } else {
+                Finding::new(
+                    "L0014",
+                    format!(
+                        "`{}` is bound to <{}>, whose element is `{}`, so casting it to `{}` fails",
+                        binding.name, tag, interface, target
+                    ),
+                )
+                .with_fix(format!(
+                    "Move `node_ref={}` to the element you need, or drop the cast and use the `{}`",
+                    binding.name, interface
+                ))
+            };
+            finding = finding.at(code, range.start);
+            if target == interface {
+                if let Some(range) = removable(masked, cast, range) {
+                    finding = finding.with_edit(code, range, "");
+                }
+            }
+            findings.push(finding);
+        }
+    }
+}
+
+/// The first cast in `range` of `masked`: the cast method, its target type
+/// without a path, and the range of the call
+fn cast_in(masked: &str, range: Range<usize>) -> Option<(&'static str, String, Range<usize>)> {
+    let (start, cast) = CASTS
+        .iter()
+        .filter_map(|(cast, _)| {
+            masked[range.clone()]
+                .find(cast)
+                .map(|at| (range.start + at, *cast))
+        })
+        .min_by_key(|(start, _)| *start)?;
+    let ty_start = start + cast.len();
+    let ty_end = ty_start + masked[ty_start..range.end].find('>')?;
+    let target = masked[ty_start..ty_end].trim();
+    let target = target.rsplit("::").next().unwrap_or(target).to_string();
+    let open = ty_end + 1;
+    if masked.as_bytes().get(open) != Some(&b'(') {
+        return None;
+    }
+    let close = skip_balanced(masked.as_bytes(), open, b'(', b')')?;
+    Some((cast, target, start..close))
+}
+
+/// The text to delete so a redundant cast leaves the element itself: the
+/// call, plus the `.unwrap()`/`.expect(..)` of a `dyn_into`. Reference casts
+/// change the type, so they are left for a person to remove.
+fn removable(masked: &str, cast: &str, range: Range<usize>) -> Option<Range<usize>> {
+    if !CASTS.iter().any(|(c, owned)| *c == cast && *owned) {
+        return None;
+    }
+    if cast != ".dyn_into::<" {
+        return Some(range);
+    }
+    let rest = &masked[range.end..];
+    if rest.starts_with(".unwrap()") {
+        return Some(range.start..range.end + ".unwrap()".len());
+    }
+    if rest.starts_with(".expect(") {
+        let open = range.end + ".expect".len();
+        let close = skip_balanced(masked.as_bytes(), open, b'(', b')')?;
+        return Some(range.start..close);
+    }
+    None
+}
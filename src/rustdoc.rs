@@ -0,0 +1,1073 @@
+//! API reference generation from rustdoc JSON
+//!
+//! [`generate`] documents the leptos crates with `cargo +nightly rustdoc
+//! --output-format json` in a cached project pinned to a Leptos version, then
+//! walks each crate's public modules the way a user imports from them. Re-exports
+//! are followed into the crate they come from, which is documented the first
+//! time one leads there, so `leptos::prelude::signal` gets the signature
+//! `reactive_graph` declares. Only crates from the Leptos repository are
+//! followed; re-exported items of other crates (`web_sys`, `wasm_bindgen`) and
+//! whole crates re-exported as a module (`leptos::reactive`) are left out.
+//!
+//! Written against rustdoc JSON format version 57.
+
+use crate::api::{ApiItem, ApiReference};
+use crate::docs::LeptosVersion;
+use crate::sandbox;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::rc::Rc;
+
+/// Crates whose public API makes up the reference, with the features they're
+/// documented with
+pub const CRATES: &[(&str, &[&str])] = &[
+    ("leptos", &["ssr"]),
+    ("leptos_router", &["ssr"]),
+    ("leptos_meta", &["ssr"]),
+    ("leptos_axum", &[]),
+];
+
+/// Re-exports nested deeper than this many modules are ignored
+const MAX_DEPTH: usize = 8;
+
+/// Document `crates` (names from [`CRATES`]) at `version` and collect their
+/// public items
+pub fn generate(version: LeptosVersion, crates: &[String]) -> Result<ApiReference, String> {
+    let mut roots = Vec::new();
+    for name in crates {
+        let features = CRATES
+            .iter()
+            .find(|(known, _)| known == name)
+            .map(|(_, features)| *features)
+            .ok_or_else(|| {
+                let known: Vec<&str> = CRATES.iter().map(|(name, _)| *name).collect();
+                format!(
+                    "Unknown crate '{}'; expected one of {}",
+                    name,
+                    known.join(", ")
+                )
+            })?;
+        roots.push((name.as_str(), features));
+    }
+    if roots.is_empty() {
+        return Err("No crates to document".to_string());
+    }
+
+    let project = sandbox::cache_dir().join(format!("api-{}", version.as_str()));
+    write_project(&project, version, &roots)?;
+    let packages = leptos_packages(&project)?;
+    let mut walker = Walker {
+        docs: Docs {
+            project,
+            packages,
+            crates: HashMap::new(),
+        },
+        found: Vec::new(),
+        visited: HashSet::new(),
+    };
+    let mut documented = BTreeMap::new();
+    for (name, _) in &roots {
+        let krate = walker.docs.load(name)?;
+        documented.insert(name.to_string(), krate.version.clone());
+        let root = krate.root.clone();
+        walker.module(&krate, &root, name, 0)?;
+    }
+    Ok(ApiReference {
+        crate_version: version.as_str().to_string(),
+        crates: documented,
+        items: walker.items(),
+    })
+}
+
+fn write_project(
+    dir: &Path,
+    version: LeptosVersion,
+    roots: &[(&str, &[&str])],
+) -> Result<(), String> {
+    let src = dir.join("src");
+    fs::create_dir_all(&src).map_err(|e| format!("Failed to create {}: {}", src.display(), e))?;
+    let dependencies: Vec<String> = roots
+        .iter()
+        .map(|(name, features)| {
+            let features: Vec<String> = features.iter().map(|f| format!("{:?}", f)).collect();
+            format!(
+                "{} = {{ version = \"{}\", features = [{}] }}",
+                name,
+                sandbox::requirement(version),
+                features.join(", ")
+            )
+        })
+        .collect();
+    let manifest = format!(
+        "[package]\n\
+         name = \"leptos-mcp-api\"\n\
+         version = \"0.0.0\"\n\
+         edition = \"2021\"\n\
+         publish = false\n\n\
+         [dependencies]\n{}\n",
+        dependencies.join("\n")
+    );
+    sandbox::write_if_changed(&dir.join("Cargo.toml"), &manifest)?;
+    sandbox::write_if_changed(&src.join("lib.rs"), "")
+}
+
+/// Package specs (`reactive_graph@0.1.8`) of the Leptos repository's crates in
+/// the project's dependency graph, by library name
+fn leptos_packages(project: &Path) -> Result<HashMap<String, String>, String> {
+    let output = Command::new("cargo")
+        .args(["metadata", "--format-version", "1", "--manifest-path"])
+        .arg(project.join("Cargo.toml"))
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|e| format!("Failed to run cargo: {}", e))?;
+    if !output.status.success() {
+        return Err("cargo metadata failed".to_string());
+    }
+    let metadata: Value = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("Failed to parse cargo metadata: {}", e))?;
+    let packages = metadata["packages"].as_array().cloned().unwrap_or_default();
+    let repository = packages
+        .iter()
+        .find(|p| p["name"] == "leptos")
+        .map(|p| p["repository"].clone())
+        .ok_or("cargo metadata lists no leptos package")?;
+    let mut specs = HashMap::new();
+    for package in packages.iter().filter(|p| p["repository"] == repository) {
+        let spec = format!(
+            "{}@{}",
+            package["name"].as_str().unwrap_or_default(),
+            package["version"].as_str().unwrap_or_default()
+        );
+        let library = package["targets"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .find(|t| {
+                strings(&t["kind"])
+                    .iter()
+                    .any(|k| k == "lib" || k == "proc-macro")
+            });
+        if let Some(name) = library.and_then(|t| t["name"].as_str()) {
+            specs.insert(name.replace('-', "_"), spec);
+        }
+    }
+    Ok(specs)
+}
+
+/// One crate's rustdoc output
+struct Crate {
+    name: String,
+    version: String,
+    root: String,
+    index: Value,
+    paths: Value,
+    external: Value,
+    /// Ids of the crate's own items by their canonical path
+    by_path: HashMap<Vec<String>, String>,
+}
+
+impl Crate {
+    fn item(&self, id: &str) -> Option<&Value> {
+        self.index.get(id)
+    }
+}
+
+/// Rustdoc output of the crates reached so far, documented on first use
+struct Docs {
+    project: PathBuf,
+    /// Crates that may be documented, see [`leptos_packages`]
+    packages: HashMap<String, String>,
+    crates: HashMap<String, Rc<Crate>>,
+}
+
+impl Docs {
+    fn load(&mut self, name: &str) -> Result<Rc<Crate>, String> {
+        if let Some(krate) = self.crates.get(name) {
+            return Ok(krate.clone());
+        }
+        let krate = Rc::new(self.document(name)?);
+        self.crates.insert(name.to_string(), krate.clone());
+        Ok(krate)
+    }
+
+    fn document(&self, name: &str) -> Result<Crate, String> {
+        let spec = self
+            .packages
+            .get(name)
+            .ok_or_else(|| format!("{} isn't a crate of the Leptos repository", name))?;
+        let status = Command::new("cargo")
+            .args([
+                "+nightly",
+                "rustdoc",
+                "--lib",
+                "-p",
+                spec,
+                "--manifest-path",
+            ])
+            .arg(self.project.join("Cargo.toml"))
+            .args(["--", "-Z", "unstable-options", "--output-format", "json"])
+            .status()
+            .map_err(|e| format!("Failed to run cargo: {}", e))?;
+        if !status.success() {
+            return Err(format!("cargo rustdoc failed for {}", name));
+        }
+        let path = self
+            .project
+            .join("target")
+            .join("doc")
+            .join(format!("{}.json", name));
+        let raw = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let mut doc: Value = serde_json::from_str(&raw)
+            .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?;
+
+        let mut by_path = HashMap::new();
+        if let Some(paths) = doc["paths"].as_object() {
+            for (id, summary) in paths {
+                if summary["crate_id"] != 0 {
+                    continue;
+                }
+                let path: Vec<String> = strings(&summary["path"]);
+                by_path.entry(path).or_insert_with(|| id.clone());
+            }
+        }
+        Ok(Crate {
+            name: name.to_string(),
+            version: doc["crate_version"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string(),
+            root: id(&doc["root"]),
+            index: doc["index"].take(),
+            paths: doc["paths"].take(),
+            external: doc["external_crates"].take(),
+            by_path,
+        })
+    }
+}
+
+/// An item reached under a public path
+struct Found {
+    krate: Rc<Crate>,
+    id: String,
+    path: String,
+}
+
+struct Walker {
+    docs: Docs,
+    found: Vec<Found>,
+    /// Modules walked, by crate, module id and the path they were walked under
+    visited: HashSet<(String, String, String)>,
+}
+
+impl Walker {
+    fn module(
+        &mut self,
+        krate: &Rc<Crate>,
+        id: &str,
+        prefix: &str,
+        depth: usize,
+    ) -> Result<(), String> {
+        let key = (krate.name.clone(), id.to_string(), prefix.to_string());
+        if depth > MAX_DEPTH || !self.visited.insert(key) {
+            return Ok(());
+        }
+        let Some(items) = krate
+            .item(id)
+            .and_then(|m| m["inner"]["module"]["items"].as_array())
+        else {
+            return Ok(());
+        };
+        for child in items.clone() {
+            let child = self::id(&child);
+            let Some(item) = krate.item(&child) else {
+                continue;
+            };
+            if item["visibility"] != "public" {
+                continue;
+            }
+            let name = item["name"].as_str().unwrap_or_default();
+            let (kind, inner) = inner(item);
+            match kind {
+                "module" => {
+                    self.module(krate, &child, &format!("{}::{}", prefix, name), depth + 1)?;
+                }
+                "use" => self.reexport(krate, inner, prefix, depth)?,
+                "impl" | "extern_crate" | "primitive" => {}
+                _ => self.found.push(Found {
+                    krate: krate.clone(),
+                    id: child,
+                    path: format!("{}::{}", prefix, name),
+                }),
+            }
+        }
+        Ok(())
+    }
+
+    fn reexport(
+        &mut self,
+        krate: &Rc<Crate>,
+        import: &Value,
+        prefix: &str,
+        depth: usize,
+    ) -> Result<(), String> {
+        let Some((target, id)) = self.resolve(krate, &id(&import["id"]))? else {
+            return Ok(());
+        };
+        let Some(item) = target.item(&id) else {
+            return Ok(());
+        };
+        let (kind, _) = inner(item);
+        let name = import["name"].as_str().unwrap_or_default();
+        match (kind, import["is_glob"].as_bool().unwrap_or(false)) {
+            ("module", true) => self.module(&target, &id, prefix, depth),
+            (_, true) => Ok(()),
+            // A whole crate under another name
+            ("module", false) if id == target.root => Ok(()),
+            ("module", false) => {
+                self.module(&target, &id, &format!("{}::{}", prefix, name), depth + 1)
+            }
+            _ => {
+                self.found.push(Found {
+                    krate: target,
+                    id,
+                    path: format!("{}::{}", prefix, name),
+                });
+                Ok(())
+            }
+        }
+    }
+
+    /// The crate and id an item id of `krate` refers to, documenting the crate
+    /// it's defined in if that's another one
+    fn resolve(
+        &mut self,
+        krate: &Rc<Crate>,
+        id: &str,
+    ) -> Result<Option<(Rc<Crate>, String)>, String> {
+        if krate.item(id).is_some() {
+            return Ok(Some((krate.clone(), id.to_string())));
+        }
+        let summary = &krate.paths[id];
+        let crate_id = self::id(&summary["crate_id"]);
+        let Some(name) = krate.external[&crate_id]["name"].as_str() else {
+            return Ok(None);
+        };
+        if !self.docs.packages.contains_key(name) {
+            return Ok(None);
+        }
+        let target = self.docs.load(name)?;
+        let path = strings(&summary["path"]);
+        Ok(target.by_path.get(&path).cloned().map(|id| (target, id)))
+    }
+
+    /// The items found, each under its preferred path, with their methods
+    fn items(self) -> Vec<ApiItem> {
+        // An item reachable under several paths is listed under the one users
+        // import it from: the prelude if it's there, otherwise the shortest
+        let mut best: HashMap<(String, String), &Found> = HashMap::new();
+        for found in &self.found {
+            let key = (found.krate.name.clone(), found.id.clone());
+            let rank = |f: &Found| {
+                (
+                    !f.path.contains("::prelude::"),
+                    f.path.matches("::").count(),
+                    f.path.clone(),
+                )
+            };
+            match best.get(&key) {
+                Some(current) if rank(current) <= rank(found) => {}
+                _ => {
+                    best.insert(key, found);
+                }
+            }
+        }
+
+        let mut items = Vec::new();
+        for found in best.values() {
+            let Some(item) = found.krate.item(&found.id) else {
+                continue;
+            };
+            items.extend(convert(&found.krate, item, &found.path));
+        }
+        items.sort_by(|a, b| {
+            a.path
+                .cmp(&b.path)
+                .then_with(|| a.signature.cmp(&b.signature))
+        });
+        items.dedup_by(|a, b| {
+            a.path == b.path && a.signature == b.signature && a.bounds == b.bounds
+        });
+        items
+    }
+}
+
+/// An item and its methods as reference entries
+fn convert(krate: &Crate, item: &Value, path: &str) -> Vec<ApiItem> {
+    let name = item["name"].as_str().unwrap_or_default();
+    let docs = item["docs"].as_str().unwrap_or_default();
+    let (kind, inner) = inner(item);
+    let generics = &inner["generics"];
+    let mut bounds = bounds(generics);
+    let (kind, signature) = match kind {
+        "function" if name.starts_with(|c: char| c.is_ascii_uppercase()) => {
+            ("component", function(name, inner, true))
+        }
+        "function" => ("function", function(name, inner, true)),
+        // Generated by #[component]; the component's docs list the same props
+        "struct" if docs.starts_with("Props for the [`") => return Vec::new(),
+        "struct" => (
+            "struct",
+            format!("pub struct {}{}", name, params(generics, true)),
+        ),
+        "enum" => (
+            "enum",
+            format!("pub enum {}{}", name, params(generics, true)),
+        ),
+        "union" => (
+            "union",
+            format!("pub union {}{}", name, params(generics, true)),
+        ),
+        "trait" => {
+            // Supertraits may also be declared as `where Self: ...`
+            let mut supertraits = vec![generic_bounds(&inner["bounds"])];
+            bounds.retain(|bound| match bound.strip_prefix("Self: ") {
+                Some(declared) => {
+                    supertraits.push(declared.to_string());
+                    false
+                }
+                None => true,
+            });
+            supertraits.retain(|s| !s.is_empty());
+            let supertraits = match supertraits.is_empty() {
+                true => String::new(),
+                false => format!(": {}", supertraits.join(" + ")),
+            };
+            (
+                "trait",
+                format!(
+                    "pub trait {}{}{}",
+                    name,
+                    params(generics, true),
+                    supertraits
+                ),
+            )
+        }
+        "type_alias" => (
+            "type alias",
+            format!(
+                "pub type {}{} = {}",
+                name,
+                params(generics, true),
+                ty(&inner["type"])
+            ),
+        ),
+        "constant" => (
+            "constant",
+            format!("pub const {}: {}", name, ty(&inner["type"])),
+        ),
+        "static" => (
+            "static",
+            format!("pub static {}: {}", name, ty(&inner["type"])),
+        ),
+        "macro" => ("macro", format!("macro_rules! {} {{ ... }}", name)),
+        "proc_macro" => match inner["kind"].as_str() {
+            Some("attr") => ("attribute macro", format!("#[{}]", name)),
+            Some("derive") => ("derive macro", format!("#[derive({})]", name)),
+            _ => ("macro", format!("{}! {{ ... }}", name)),
+        },
+        _ => return Vec::new(),
+    };
+    if kind == "macro" || kind.ends_with(" macro") {
+        bounds.clear();
+    }
+    let mut items = vec![ApiItem {
+        path: path.to_string(),
+        kind: kind.to_string(),
+        signature,
+        bounds,
+        docs: summary(item, kind == "component"),
+        deprecated: deprecation(item),
+    }];
+
+    let methods: Vec<(Vec<String>, Vec<String>, bool)> = match inner["impls"].as_array() {
+        // Inherent impls only; trait impls are documented with the trait
+        Some(impls) => impls
+            .iter()
+            .filter_map(|id| krate.item(&self::id(id)))
+            .map(|i| &i["inner"]["impl"])
+            .filter(|i| i["trait"].is_null() && !i["is_synthetic"].as_bool().unwrap_or(false))
+            .map(|i| {
+                (
+                    strings_of_ids(&i["items"]),
+                    self::bounds(&i["generics"]),
+                    true,
+                )
+            })
+            .collect(),
+        None if kind == "trait" => vec![(strings_of_ids(&inner["items"]), Vec::new(), false)],
+        None => Vec::new(),
+    };
+    for (ids, impl_bounds, inherent) in methods {
+        for id in ids {
+            let Some(method) = krate.item(&id) else {
+                continue;
+            };
+            let (method_kind, method_inner) = self::inner(method);
+            if method_kind != "function" || (inherent && method["visibility"] != "public") {
+                continue;
+            }
+            let method_name = method["name"].as_str().unwrap_or_default();
+            let mut bounds = impl_bounds.clone();
+            bounds.extend(self::bounds(&method_inner["generics"]));
+            items.push(ApiItem {
+                path: format!("{}::{}", path, method_name),
+                kind: "method".to_string(),
+                signature: function(method_name, method_inner, inherent),
+                bounds,
+                docs: summary(method, false),
+                deprecated: deprecation(method),
+            });
+        }
+    }
+    items
+}
+
+/// `pub fn name<T>(value: T) -> Output`; bounds are listed separately
+fn function(name: &str, inner: &Value, public: bool) -> String {
+    let header = &inner["header"];
+    let mut signature = String::new();
+    if public {
+        signature.push_str("pub ");
+    }
+    for (flag, keyword) in [
+        ("is_const", "const "),
+        ("is_async", "async "),
+        ("is_unsafe", "unsafe "),
+    ] {
+        if header[flag].as_bool().unwrap_or(false) {
+            signature.push_str(keyword);
+        }
+    }
+    let inputs: Vec<String> = inner["sig"]["inputs"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|input| {
+            let name = input[0].as_str().unwrap_or("_");
+            let t = &input[1];
+            if name != "self" {
+                return format!("{}: {}", name, ty(t));
+            }
+            // `self`, `&self` and `&mut self` are written without their type
+            let written = ty(t);
+            let receiver = t["generic"] == "Self" || t["borrowed_ref"]["type"]["generic"] == "Self";
+            match written.strip_suffix("Self") {
+                Some(reference) if receiver => format!("{}self", reference),
+                _ => format!("self: {}", written),
+            }
+        })
+        .collect();
+    signature.push_str(&format!(
+        "fn {}{}({})",
+        name,
+        params(&inner["generics"], false),
+        inputs.join(", ")
+    ));
+    let output = &inner["sig"]["output"];
+    if !output.is_null() {
+        signature.push_str(&format!(" -> {}", ty(output)));
+    }
+    signature
+}
+
+/// `<'a, T, S = SyncStorage>`, without `impl Trait` arguments' synthetic
+/// parameters; `defaults` includes default types
+fn params(generics: &Value, defaults: bool) -> String {
+    let params: Vec<String> = generics["params"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|param| {
+            let name = param["name"].as_str().unwrap_or_default();
+            let kind = &param["kind"];
+            if let Some(t) = kind.get("type") {
+                if t["is_synthetic"].as_bool().unwrap_or(false) {
+                    return None;
+                }
+                return Some(match &t["default"] {
+                    default if defaults && !default.is_null() => {
+                        format!("{} = {}", name, ty(default))
+                    }
+                    _ => name.to_string(),
+                });
+            }
+            if let Some(c) = kind.get("const") {
+                return Some(format!("const {}: {}", name, ty(&c["type"])));
+            }
+            Some(name.to_string())
+        })
+        .collect();
+    match params.is_empty() {
+        true => String::new(),
+        false => format!("<{}>", params.join(", ")),
+    }
+}
+
+/// Bounds declared on generic parameters and in the `where` clause
+fn bounds(generics: &Value) -> Vec<String> {
+    let mut bounds = Vec::new();
+    for param in generics["params"].as_array().into_iter().flatten() {
+        let name = param["name"].as_str().unwrap_or_default();
+        let kind = &param["kind"];
+        let declared = match (kind.get("type"), kind.get("lifetime")) {
+            (Some(t), _) if !t["is_synthetic"].as_bool().unwrap_or(false) => {
+                generic_bounds(&t["bounds"])
+            }
+            (_, Some(l)) => strings(&l["outlives"]).join(" + "),
+            _ => String::new(),
+        };
+        if !declared.is_empty() {
+            bounds.push(format!("{}: {}", name, declared));
+        }
+    }
+    for predicate in generics["where_predicates"]
+        .as_array()
+        .into_iter()
+        .flatten()
+    {
+        if let Some(p) = predicate.get("bound_predicate") {
+            bounds.push(format!(
+                "{}{}: {}",
+                higher_ranked(&p["generic_params"]),
+                ty(&p["type"]),
+                generic_bounds(&p["bounds"])
+            ));
+        } else if let Some(p) = predicate.get("lifetime_predicate") {
+            bounds.push(format!(
+                "{}: {}",
+                p["lifetime"].as_str().unwrap_or_default(),
+                strings(&p["outlives"]).join(" + ")
+            ));
+        } else if let Some(p) = predicate.get("eq_predicate") {
+            let rhs = p["rhs"].get("type").map_or_else(|| "_".to_string(), ty);
+            bounds.push(format!("{} = {}", ty(&p["lhs"]), rhs));
+        }
+    }
+    bounds
+}
+
+/// `for<'a> ` for higher-ranked bounds
+fn higher_ranked(params: &Value) -> String {
+    let names: Vec<&str> = params
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|p| p["name"].as_str())
+        .collect();
+    match names.is_empty() {
+        true => String::new(),
+        false => format!("for<{}> ", names.join(", ")),
+    }
+}
+
+/// `Clone + Send + 'static`
+fn generic_bounds(bounds: &Value) -> String {
+    let bounds: Vec<String> = bounds
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|bound| {
+            if let Some(t) = bound.get("trait_bound") {
+                let modifier = match t["modifier"].as_str() {
+                    Some("maybe") => "?",
+                    Some("maybe_const") => "~const ",
+                    _ => "",
+                };
+                format!(
+                    "{}{}{}",
+                    higher_ranked(&t["generic_params"]),
+                    modifier,
+                    path(&t["trait"])
+                )
+            } else if let Some(lifetime) = bound.get("outlives").and_then(Value::as_str) {
+                lifetime.to_string()
+            } else if let Some(captured) = bound.get("use").and_then(Value::as_array) {
+                let names: Vec<&str> = captured
+                    .iter()
+                    .filter_map(|c| {
+                        c.as_str()
+                            .or_else(|| c["lifetime"].as_str())
+                            .or_else(|| c["param"].as_str())
+                    })
+                    .collect();
+                format!("use<{}>", names.join(", "))
+            } else {
+                "_".to_string()
+            }
+        })
+        .collect();
+    bounds.join(" + ")
+}
+
+/// A path as written in code: its last segment and generic arguments
+fn path(path: &Value) -> String {
+    let full = path["path"]
+        .as_str()
+        .or_else(|| path["name"].as_str())
+        .unwrap_or_default();
+    let name = full.rsplit("::").next().unwrap_or(full);
+    format!("{}{}", name, args(&path["args"]))
+}
+
+fn args(args: &Value) -> String {
+    if let Some(angle) = args.get("angle_bracketed") {
+        let mut parts: Vec<String> = angle["args"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|arg| {
+                if let Some(t) = arg.get("type") {
+                    ty(t)
+                } else if let Some(lifetime) = arg.get("lifetime").and_then(Value::as_str) {
+                    lifetime.to_string()
+                } else if let Some(c) = arg.get("const") {
+                    c["expr"].as_str().unwrap_or("_").to_string()
+                } else {
+                    "_".to_string()
+                }
+            })
+            .collect();
+        for constraint in angle["constraints"].as_array().into_iter().flatten() {
+            let name = format!(
+                "{}{}",
+                constraint["name"].as_str().unwrap_or_default(),
+                self::args(&constraint["args"])
+            );
+            let binding = &constraint["binding"];
+            if let Some(t) = binding["equality"].get("type") {
+                parts.push(format!("{} = {}", name, ty(t)));
+            } else if let Some(bounds) = binding.get("constraint") {
+                parts.push(format!("{}: {}", name, generic_bounds(bounds)));
+            }
+        }
+        return match parts.is_empty() {
+            true => String::new(),
+            false => format!("<{}>", parts.join(", ")),
+        };
+    }
+    if let Some(fn_args) = args.get("parenthesized") {
+        let inputs: Vec<String> = fn_args["inputs"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(ty)
+            .collect();
+        let output = match &fn_args["output"] {
+            Value::Null => String::new(),
+            output => format!(" -> {}", ty(output)),
+        };
+        return format!("({}){}", inputs.join(", "), output);
+    }
+    String::new()
+}
+
+/// A type as written in code
+fn ty(t: &Value) -> String {
+    // `"infer"` is the only type that isn't an object
+    let Some((kind, inner)) = t.as_object().and_then(|o| o.iter().next()) else {
+        return "_".to_string();
+    };
+    match kind.as_str() {
+        "resolved_path" => path(inner),
+        "generic" | "primitive" => inner.as_str().unwrap_or_default().to_string(),
+        "tuple" => {
+            let elements: Vec<String> = inner.as_array().into_iter().flatten().map(ty).collect();
+            match elements.len() {
+                1 => format!("({},)", elements[0]),
+                _ => format!("({})", elements.join(", ")),
+            }
+        }
+        "slice" => format!("[{}]", ty(inner)),
+        "array" => format!(
+            "[{}; {}]",
+            ty(&inner["type"]),
+            inner["len"].as_str().unwrap_or("_")
+        ),
+        "pat" => ty(&inner["type"]),
+        "impl_trait" => format!("impl {}", generic_bounds(inner)),
+        "dyn_trait" => {
+            let mut bounds: Vec<String> = inner["traits"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|t| {
+                    format!(
+                        "{}{}",
+                        higher_ranked(&t["generic_params"]),
+                        path(&t["trait"])
+                    )
+                })
+                .collect();
+            if let Some(lifetime) = inner["lifetime"].as_str() {
+                bounds.push(lifetime.to_string());
+            }
+            format!("dyn {}", bounds.join(" + "))
+        }
+        "borrowed_ref" => {
+            let mut reference = "&".to_string();
+            if let Some(lifetime) = inner["lifetime"].as_str() {
+                reference.push_str(lifetime);
+                reference.push(' ');
+            }
+            if inner["is_mutable"].as_bool().unwrap_or(false) {
+                reference.push_str("mut ");
+            }
+            reference + &ty(&inner["type"])
+        }
+        "raw_pointer" => {
+            let mutability = match inner["is_mutable"].as_bool().unwrap_or(false) {
+                true => "mut",
+                false => "const",
+            };
+            format!("*{} {}", mutability, ty(&inner["type"]))
+        }
+        "function_pointer" => {
+            let inputs: Vec<String> = inner["sig"]["inputs"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|input| ty(&input[1]))
+                .collect();
+            let output = match &inner["sig"]["output"] {
+                Value::Null => String::new(),
+                output => format!(" -> {}", ty(output)),
+            };
+            format!(
+                "{}fn({}){}",
+                higher_ranked(&inner["generic_params"]),
+                inputs.join(", "),
+                output
+            )
+        }
+        "qualified_path" => {
+            let name = format!(
+                "{}{}",
+                inner["name"].as_str().unwrap_or_default(),
+                args(&inner["args"])
+            );
+            // `Self::Value` inside a trait has a trait with an empty path
+            match &inner["trait"] {
+                tr if tr.is_null() || tr["path"] == "" => {
+                    format!("{}::{}", ty(&inner["self_type"]), name)
+                }
+                tr => format!("<{} as {}>::{}", ty(&inner["self_type"]), path(tr), name),
+            }
+        }
+        _ => "_".to_string(),
+    }
+}
+
+/// The first paragraph of an item's docs, plus the prop lists of a component
+fn summary(item: &Value, component: bool) -> String {
+    let docs = item["docs"].as_str().unwrap_or_default().trim();
+    let mut summary: String = docs
+        .lines()
+        .take_while(|line| {
+            !line.trim().is_empty() && !line.starts_with("```") && !line.starts_with('#')
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    if component {
+        if let Some(start) = docs
+            .find("# Required Props")
+            .or_else(|| docs.find("# Optional Props"))
+        {
+            if !summary.is_empty() {
+                summary.push_str("\n\n");
+            }
+            summary.push_str(docs[start..].trim());
+        }
+    }
+    summary
+}
+
+/// The deprecation note of a deprecated item; empty if it gives none
+fn deprecation(item: &Value) -> Option<String> {
+    let deprecation = &item["deprecation"];
+    if deprecation.is_null() {
+        return None;
+    }
+    Some(deprecation["note"].as_str().unwrap_or_default().to_string())
+}
+
+/// The kind of an item and its kind-specific fields: `("function", {...})`
+fn inner(item: &Value) -> (&str, &Value) {
+    item["inner"]
+        .as_object()
+        .and_then(|o| o.iter().next())
+        .map_or(("", &Value::Null), |(kind, inner)| (kind.as_str(), inner))
+}
+
+/// An item id as the string the index is keyed by
+fn id(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn strings(value: &Value) -> Vec<String> {
+    value
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|v| v.as_str().map(String::from))
+        .collect()
+}
+
+fn strings_of_ids(value: &Value) -> Vec<String> {
+    value.as_array().into_iter().flatten().map(id).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn path(name: &str, id: u32, args: Value) -> Value {
+        json!({ "resolved_path": { "path": name, "id": id, "args": args } })
+    }
+
+    fn generic_arg(t: &Value) -> Value {
+        json!({ "angle_bracketed": { "args": [{ "type": t }], "constraints": [] } })
+    }
+
+    fn trait_bound(name: &str) -> Value {
+        json!({ "trait_bound": {
+            "trait": { "path": name, "id": 90, "args": null },
+            "generic_params": [],
+            "modifier": "none"
+        } })
+    }
+
+    fn type_param(name: &str, bounds: Value) -> Value {
+        json!({ "name": name, "kind": { "type": { "bounds": bounds, "default": null, "is_synthetic": false } } })
+    }
+
+    fn krate(index: Value) -> Crate {
+        Crate {
+            name: "reactive_graph".to_string(),
+            version: "0.1.8".to_string(),
+            root: "0".to_string(),
+            index,
+            paths: json!({}),
+            external: json!({}),
+            by_path: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn converts_functions_with_their_bounds() {
+        let t = json!({ "generic": "T" });
+        let item = json!({
+            "name": "signal",
+            "visibility": "public",
+            "docs": "Creates a signal.\n\nLonger explanation.",
+            "deprecation": null,
+            "inner": { "function": {
+                "sig": {
+                    "inputs": [["value", t]],
+                    "output": { "tuple": [
+                        path("ReadSignal", 1, generic_arg(&t)),
+                        path("crate::signal::WriteSignal", 2, generic_arg(&t))
+                    ] },
+                    "is_c_variadic": false
+                },
+                "generics": {
+                    "params": [type_param("T", json!([trait_bound("Send"), { "outlives": "'static" }]))],
+                    "where_predicates": []
+                },
+                "header": { "is_const": false, "is_unsafe": false, "is_async": false, "abi": "Rust" },
+                "has_body": true
+            } }
+        });
+        let items = convert(&krate(json!({})), &item, "leptos::prelude::signal");
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].kind, "function");
+        assert_eq!(
+            items[0].signature,
+            "pub fn signal<T>(value: T) -> (ReadSignal<T>, WriteSignal<T>)"
+        );
+        assert_eq!(items[0].bounds, ["T: Send + 'static"]);
+        assert_eq!(items[0].docs, "Creates a signal.");
+        assert_eq!(items[0].deprecated, None);
+    }
+
+    #[test]
+    fn lists_inherent_methods_but_not_trait_impls() {
+        let header =
+            json!({ "is_const": false, "is_unsafe": false, "is_async": false, "abi": "Rust" });
+        let no_generics = json!({ "params": [], "where_predicates": [] });
+        let method = |name: &str, receiver: Value, deprecation: Value| {
+            json!({
+                "name": name,
+                "visibility": "public",
+                "docs": null,
+                "deprecation": deprecation,
+                "inner": { "function": {
+                    "sig": { "inputs": [["self", receiver]], "output": { "primitive": "bool" }, "is_c_variadic": false },
+                    "generics": no_generics,
+                    "header": header,
+                    "has_body": true
+                } }
+            })
+        };
+        let self_ref = json!({ "borrowed_ref": { "lifetime": null, "is_mutable": true, "type": { "generic": "Self" } } });
+        let index = json!({
+            "10": { "inner": { "impl": {
+                "trait": null,
+                "is_synthetic": false,
+                "generics": { "params": [], "where_predicates": [{ "bound_predicate": {
+                    "type": { "generic": "T" }, "bounds": [trait_bound("Clone")], "generic_params": []
+                } }] },
+                "items": [11]
+            } } },
+            "11": method("try_update", self_ref, json!({ "since": null, "note": "use `update`" })),
+            "20": { "inner": { "impl": {
+                "trait": { "path": "Clone", "id": 90, "args": null },
+                "is_synthetic": false,
+                "generics": no_generics,
+                "items": [21]
+            } } },
+            "21": method("clone", json!({ "generic": "Self" }), Value::Null)
+        });
+        let item = json!({
+            "name": "RwSignal",
+            "visibility": "public",
+            "docs": "A signal.",
+            "deprecation": null,
+            "inner": { "struct": {
+                "kind": { "plain": { "fields": [], "has_stripped_fields": true } },
+                "generics": { "params": [type_param("T", json!([]))], "where_predicates": [] },
+                "impls": [10, 20]
+            } }
+        });
+        let items = convert(&krate(index), &item, "leptos::prelude::RwSignal");
+        let paths: Vec<&str> = items.iter().map(|i| i.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "leptos::prelude::RwSignal",
+                "leptos::prelude::RwSignal::try_update"
+            ]
+        );
+        assert_eq!(items[0].signature, "pub struct RwSignal<T>");
+        assert_eq!(items[1].signature, "pub fn try_update(&mut self) -> bool");
+        assert_eq!(items[1].bounds, ["T: Clone"]);
+        assert_eq!(items[1].deprecated.as_deref(), Some("use `update`"));
+    }
+}
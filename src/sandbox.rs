@@ -0,0 +1,612 @@
+//! Compile-check sandbox
+//!
+//! Snippets are written into a small cargo project per Leptos version under a
+//! cache directory and checked with `cargo check`. The project's `Cargo.lock`
+//! and target directory are kept between runs, so the Leptos version is
+//! pinned after the first check and later checks only recompile the snippet.
+//! [`render`] builds a binary project the same way, with Leptos' `ssr`
+//! feature, that prints the snippet's server-rendered HTML. Calls sharing a
+//! project take turns on it through a lock file, across server processes too.
+
+use crate::docs::LeptosVersion;
+use crate::progress::{Progress, CANCELLED};
+use crate::source::{functions, FnItem};
+use schemars::JsonSchema;
+use serde::Serialize;
+use serde_json::Value;
+use std::fs::{self, File, TryLockError};
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Overrides where sandbox projects and their target directory live
+pub const CACHE_DIR_ENV: &str = "LEPTOS_MCP_CACHE_DIR";
+
+/// Default time limit for one `cargo check`; the first run builds Leptos itself
+pub const DEFAULT_TIMEOUT_SECS: u64 = 600;
+
+/// Time limit for running a built preview binary
+const RENDER_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A compiler diagnostic mapped back to the snippet
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct CompilerDiagnostic {
+    /// `error` or `warning`
+    pub level: String,
+    /// rustc error code such as `E0277`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    pub message: String,
+    /// 1-based line in the submitted snippet; None if it points into the wrapper
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+    /// rustc's rendered output, with wrapper line numbers
+    pub rendered: String,
+}
+
+/// Outcome of checking a snippet
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct CheckResult {
+    pub success: bool,
+    pub leptos_version: &'static str,
+    pub diagnostics: Vec<CompilerDiagnostic>,
+}
+
+/// Outcome of rendering a snippet
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct RenderResult {
+    pub success: bool,
+    pub leptos_version: &'static str,
+    /// The component that was rendered, when the snippet defines components
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub component: Option<String>,
+    /// Server-rendered HTML; None when the snippet doesn't compile
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub html: Option<String>,
+    pub diagnostics: Vec<CompilerDiagnostic>,
+}
+
+pub(crate) fn cache_dir() -> PathBuf {
+    std::env::var_os(CACHE_DIR_ENV)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| std::env::temp_dir().join("leptos-mcp-server"))
+}
+
+/// Version requirement written to the sandbox manifest
+pub(crate) fn requirement(version: LeptosVersion) -> &'static str {
+    match version {
+        LeptosVersion::V0_6 => "~0.6",
+        LeptosVersion::V0_7 => "~0.7",
+        LeptosVersion::V0_8 => "~0.8",
+    }
+}
+
+fn manifest(version: LeptosVersion, features: &[String]) -> String {
+    let req = requirement(version);
+    let features: Vec<String> = features.iter().map(|f| format!("{:?}", f)).collect();
+    format!(
+        "[package]\n\
+         name = \"leptos-mcp-snippet\"\n\
+         version = \"0.0.0\"\n\
+         edition = \"2021\"\n\
+         publish = false\n\n\
+         [lib]\n\
+         path = \"src/lib.rs\"\n\n\
+         [dependencies]\n\
+         leptos = {{ version = \"{req}\", features = [{features}] }}\n\
+         leptos_router = \"{req}\"\n\
+         leptos_meta = \"{req}\"\n\
+         serde = {{ version = \"1\", features = [\"derive\"] }}\n\n\
+         [workspace]\n",
+        req = req,
+        features = features.join(", ")
+    )
+}
+
+/// Manifest of the render project: a binary with the server-side features
+fn render_manifest(version: LeptosVersion) -> String {
+    format!(
+        "[package]\n\
+         name = \"leptos-mcp-preview\"\n\
+         version = \"0.0.0\"\n\
+         edition = \"2021\"\n\
+         publish = false\n\n\
+         [dependencies]\n\
+         leptos = {{ version = \"{req}\", features = [\"ssr\"] }}\n\
+         leptos_router = {{ version = \"{req}\", features = [\"ssr\"] }}\n\
+         leptos_meta = {{ version = \"{req}\", features = [\"ssr\"] }}\n\
+         serde = {{ version = \"1\", features = [\"derive\"] }}\n\n\
+         [workspace]\n",
+        req = requirement(version)
+    )
+}
+
+/// The snippet as a binary printing its HTML, how many lines precede the
+/// snippet, and the component rendered
+///
+/// Items must define a component, which is mounted without props: `component`
+/// or else the first one no other component uses. Statements or a bare
+/// `view!` become the body of the rendered function.
+fn wrap_render(
+    code: &str,
+    version: LeptosVersion,
+    component: Option<&str>,
+) -> Result<(String, usize, Option<String>), String> {
+    let prelude = match version {
+        LeptosVersion::V0_6 => "use leptos::*;",
+        _ => "use leptos::prelude::*;",
+    };
+    let mut prefix = String::from("#![allow(unused, non_snake_case)]\n");
+    if !code.contains(prelude) {
+        prefix.push_str(prelude);
+        prefix.push('\n');
+    }
+    let (body, rendered) = if has_items(code) {
+        let components: Vec<FnItem> = functions(code)
+            .into_iter()
+            .filter(FnItem::is_component)
+            .collect();
+        let used = |name: &str| {
+            components
+                .iter()
+                .any(|c| c.name != name && code[c.body.clone()].contains(&format!("<{}", name)))
+        };
+        let name = match component {
+            Some(name) if components.iter().any(|c| c.name == name) => name.to_string(),
+            Some(name) => return Err(format!("No component named '{}' in the code", name)),
+            None => components
+                .iter()
+                .find(|c| !used(&c.name))
+                .map(|c| c.name.clone())
+                .ok_or("The code defines no #[component] to render; pass a view! expression or a component")?,
+        };
+        (
+            format!(
+                "{}\n\nfn __preview() -> impl IntoView {{\n    view! {{ <{} /> }}\n}}\n",
+                code, name
+            ),
+            Some(name),
+        )
+    } else {
+        prefix.push_str("fn __preview() -> impl IntoView {\n");
+        (format!("{}\n}}\n", code), None)
+    };
+    let render = match version {
+        LeptosVersion::V0_6 => "leptos::ssr::render_to_string(__preview).to_string()",
+        _ => "Owner::new().with(|| __preview().to_html())",
+    };
+    let offset = prefix.lines().count();
+    let source = format!(
+        "{}{}\nfn main() {{\n    let html = {};\n    print!(\"{{}}\", html);\n}}\n",
+        prefix, body, render
+    );
+    Ok((source, offset, rendered))
+}
+
+/// Whether `code` is a list of items rather than statements or a bare `view!`
+fn has_items(code: &str) -> bool {
+    const ITEM_STARTS: &[&str] = &[
+        "fn ", "pub ", "struct ", "enum ", "impl", "use ", "mod ", "const ", "static ", "trait ",
+        "type ", "#[", "async fn",
+    ];
+    let top_level: Vec<&str> = code
+        .lines()
+        .filter(|l| !l.is_empty() && !l.starts_with(char::is_whitespace))
+        .collect();
+    top_level
+        .iter()
+        .any(|l| ITEM_STARTS.iter().any(|s| l.starts_with(s)))
+        && !top_level
+            .iter()
+            .any(|l| l.starts_with("let ") || l.starts_with("view!"))
+}
+
+/// The snippet as a library crate, and how many lines precede the snippet
+///
+/// Code without items (a bare `view!` or a few statements) is wrapped in a function.
+fn wrap(code: &str) -> (String, usize) {
+    let mut prefix = String::from("#![allow(unused, non_snake_case)]\n");
+    if !code.contains("leptos::prelude::*") {
+        prefix.push_str("use leptos::prelude::*;\n");
+    }
+    if has_items(code) {
+        let offset = prefix.lines().count();
+        (format!("{}{}\n", prefix, code), offset)
+    } else {
+        prefix.push_str("fn __snippet() {\n    let _ = {\n");
+        let offset = prefix.lines().count();
+        (format!("{}{}\n    }};\n}}\n", prefix, code), offset)
+    }
+}
+
+/// Run `cargo check` on `code` against the given Leptos version
+pub fn check(
+    code: &str,
+    version: LeptosVersion,
+    features: &[String],
+    timeout: Duration,
+    progress: &Progress,
+) -> Result<CheckResult, String> {
+    progress.report(0, None, "Preparing sandbox project");
+    let root = cache_dir();
+    let project = root.join(format!("v{}", version.as_str()));
+    fs::create_dir_all(project.join("src"))
+        .map_err(|e| format!("Failed to create {}: {}", project.display(), e))?;
+    let _lock = lock(&project.join("check.lock"), progress)?;
+    write_if_changed(&project.join("Cargo.toml"), &manifest(version, features))?;
+    let (source, offset) = wrap(code);
+    let snippet_lines = code.lines().count().max(1);
+    fs::write(project.join("src/lib.rs"), source)
+        .map_err(|e| format!("Failed to write snippet: {}", e))?;
+
+    let target = root.join("target");
+    let run = cargo("check", &project, &[], Some(&target), timeout, progress)?;
+    let diagnostics: Vec<CompilerDiagnostic> = run
+        .messages
+        .iter()
+        .filter_map(|msg| diagnostic(msg, offset, snippet_lines))
+        .collect();
+
+    // A failure without compiler messages is a manifest or dependency problem
+    if !run.success && diagnostics.is_empty() {
+        return Err(format!("cargo check failed:\n{}", run.stderr.trim()));
+    }
+    Ok(CheckResult {
+        success: run.success,
+        leptos_version: version.as_str(),
+        diagnostics,
+    })
+}
+
+/// Build `code` with Leptos' `ssr` feature and return the HTML it renders to
+///
+/// Rendering is synchronous, so `<Suspense>` and resources show their
+/// fallback. Hydration markers are removed unless `markers` is set.
+pub fn render(
+    code: &str,
+    version: LeptosVersion,
+    component: Option<&str>,
+    markers: bool,
+    timeout: Duration,
+    progress: &Progress,
+) -> Result<RenderResult, String> {
+    progress.report(0, None, "Preparing render project");
+    let root = cache_dir();
+    let project = root.join(format!("render-v{}", version.as_str()));
+    fs::create_dir_all(project.join("src"))
+        .map_err(|e| format!("Failed to create {}: {}", project.display(), e))?;
+    // Every version's binary lands at the same path of the shared target
+    // directory, so the lock is held until it has run
+    let _lock = lock(&root.join("render.lock"), progress)?;
+    write_if_changed(&project.join("Cargo.toml"), &render_manifest(version))?;
+    let (source, offset, rendered) = wrap_render(code, version, component)?;
+    let snippet_lines = code.lines().count().max(1);
+    fs::write(project.join("src/main.rs"), source)
+        .map_err(|e| format!("Failed to write snippet: {}", e))?;
+
+    let target = root.join("target");
+    let run = cargo("build", &project, &[], Some(&target), timeout, progress)?;
+    let diagnostics: Vec<CompilerDiagnostic> = run
+        .messages
+        .iter()
+        .filter_map(|msg| diagnostic(msg, offset, snippet_lines))
+        .collect();
+    if !run.success && diagnostics.is_empty() {
+        return Err(format!("cargo build failed:\n{}", run.stderr.trim()));
+    }
+    let html = match (run.success, run.executables.last()) {
+        (true, Some(executable)) => {
+            let html = run_preview(executable, progress.limits().max_output_bytes)?;
+            Some(match markers {
+                true => html,
+                false => strip_markers(&html),
+            })
+        }
+        (true, None) => return Err("cargo build produced no binary".to_string()),
+        (false, _) => None,
+    };
+    Ok(RenderResult {
+        success: run.success,
+        leptos_version: version.as_str(),
+        component: rendered,
+        html,
+        diagnostics,
+    })
+}
+
+/// Lock `path` exclusively until the returned file is dropped, waiting for
+/// the call holding it
+fn lock(path: &Path, progress: &Progress) -> Result<File, String> {
+    let file = File::options()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    match file.try_lock() {
+        Ok(()) => {}
+        Err(TryLockError::WouldBlock) => {
+            progress.report(0, None, "Waiting for another build in the sandbox");
+            file.lock()
+                .map_err(|e| format!("Failed to lock {}: {}", path.display(), e))?;
+        }
+        Err(TryLockError::Error(e)) => {
+            return Err(format!("Failed to lock {}: {}", path.display(), e));
+        }
+    }
+    Ok(file)
+}
+
+/// Run a preview binary and capture the HTML it prints, up to `max_output` bytes
+fn run_preview(executable: &Path, max_output: usize) -> Result<String, String> {
+    let mut child = Command::new(executable)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run {}: {}", executable.display(), e))?;
+    let mut stdout = child.stdout.take().ok_or("preview stdout unavailable")?;
+    let mut stderr = child.stderr.take().ok_or("preview stderr unavailable")?;
+    let out_reader = thread::spawn(move || read_capped(&mut stdout, max_output + 1));
+    let err_reader = thread::spawn(move || read_capped(&mut stderr, max_output));
+    let started = Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if started.elapsed() >= RENDER_TIMEOUT => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!(
+                    "Rendering didn't finish within {}s",
+                    RENDER_TIMEOUT.as_secs()
+                ));
+            }
+            Ok(None) => thread::sleep(Duration::from_millis(20)),
+            Err(e) => return Err(format!("Failed to wait for the preview: {}", e)),
+        }
+    };
+    let stdout = out_reader.join().unwrap_or_default();
+    let stderr = err_reader.join().unwrap_or_default();
+    if !status.success() {
+        return Err(format!("Rendering panicked:\n{}", stderr.trim()));
+    }
+    if stdout.len() > max_output {
+        return Err(format!(
+            "The preview printed more than {} bytes of HTML (limits.max_output_bytes)",
+            max_output
+        ));
+    }
+    Ok(stdout)
+}
+
+/// The first `max` bytes `reader` yields; the rest is read and dropped, so
+/// the process writing them doesn't block on a full pipe
+fn read_capped(reader: &mut impl Read, max: usize) -> String {
+    let mut buf = String::new();
+    let _ = reader.take(max as u64).read_to_string(&mut buf);
+    let _ = io::copy(reader, &mut io::sink());
+    buf
+}
+
+/// `html` without hydration keys and marker comments
+fn strip_markers(html: &str) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(pos) = rest.find(['<', ' ']) {
+        out.push_str(&rest[..pos]);
+        rest = &rest[pos..];
+        let skip = if rest.starts_with("<!>") {
+            Some(3)
+        } else if rest.starts_with("<!--hk=") || rest.starts_with("<!--leptos-") {
+            rest.find("-->").map(|end| end + 3)
+        } else if let Some(key) = rest.strip_prefix(" data-hk=\"") {
+            key.find('"').map(|end| rest.len() - key.len() + end + 1)
+        } else {
+            None
+        };
+        match skip {
+            Some(len) => rest = &rest[len..],
+            None => {
+                out.push_str(&rest[..1]);
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// What a cargo run produced
+pub(crate) struct CargoRun {
+    pub success: bool,
+    /// The `message` of every `compiler-message`, in rustc's JSON format
+    pub messages: Vec<Value>,
+    /// Binaries built by the run
+    pub executables: Vec<PathBuf>,
+    pub stderr: String,
+}
+
+/// Run `cargo <subcommand> --message-format=json` in `dir`, reporting compiled
+/// crates as progress
+///
+/// Stops the process when `timeout` passes, the request is cancelled or it
+/// prints more than `max_output_bytes`.
+pub(crate) fn cargo(
+    subcommand: &str,
+    dir: &Path,
+    args: &[String],
+    target_dir: Option<&Path>,
+    timeout: Duration,
+    progress: &Progress,
+) -> Result<CargoRun, String> {
+    let mut command = Command::new("cargo");
+    command
+        .args([subcommand, "--quiet", "--message-format=json"])
+        .args(args)
+        .current_dir(dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    if let Some(target_dir) = target_dir {
+        command.env("CARGO_TARGET_DIR", target_dir);
+    }
+    let mut child = command
+        .spawn()
+        .map_err(|e| format!("Failed to run cargo: {}", e))?;
+
+    // Drain the pipes on threads so a chatty build can't block on a full buffer
+    let stdout = child.stdout.take().ok_or("cargo stdout unavailable")?;
+    let mut stderr = child.stderr.take().ok_or("cargo stderr unavailable")?;
+    // Count finished crates as they stream by, for progress reports
+    let checked = Arc::new(AtomicU64::new(0));
+    let out_checked = Arc::clone(&checked);
+    let max_output = progress.limits().max_output_bytes;
+    let overflowed = Arc::new(AtomicBool::new(false));
+    let out_overflowed = Arc::clone(&overflowed);
+    let out_reader = thread::spawn(move || {
+        let mut buf = String::new();
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            if line.contains("\"reason\":\"compiler-artifact\"") {
+                out_checked.fetch_add(1, Ordering::Relaxed);
+            }
+            if buf.len() + line.len() >= max_output {
+                out_overflowed.store(true, Ordering::Relaxed);
+                break;
+            }
+            buf.push_str(&line);
+            buf.push('\n');
+        }
+        buf
+    });
+    let err_reader = thread::spawn(move || read_capped(&mut stderr, max_output));
+    let too_long = || {
+        format!(
+            "cargo {} printed more than {} bytes of output (limits.max_output_bytes)",
+            subcommand, max_output
+        )
+    };
+
+    // Packages in the lockfile approximate how many crates cargo will check
+    let total = fs::read_to_string(dir.join("Cargo.lock"))
+        .ok()
+        .map(|lock| lock.matches("[[package]]").count() as u64);
+    let started = Instant::now();
+    let status = loop {
+        let done = checked.load(Ordering::Relaxed);
+        progress.report(
+            done + 1,
+            total.map(|t| t + 2),
+            &format!("cargo {}: {} crate(s) compiled", subcommand, done),
+        );
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if overflowed.load(Ordering::Relaxed) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(too_long());
+            }
+            Ok(None) if progress.is_cancelled() => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(CANCELLED.to_string());
+            }
+            Ok(None) if started.elapsed() >= timeout => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!(
+                    "cargo {} timed out after {}s. The first run builds all dependencies; retry with a larger timeout_secs",
+                    subcommand,
+                    timeout.as_secs()
+                ));
+            }
+            Ok(None) => thread::sleep(Duration::from_millis(100)),
+            Err(e) => return Err(format!("Failed to wait for cargo: {}", e)),
+        }
+    };
+    let stdout = out_reader.join().unwrap_or_default();
+    let stderr = err_reader.join().unwrap_or_default();
+    if overflowed.load(Ordering::Relaxed) {
+        return Err(too_long());
+    }
+    let steps = checked.load(Ordering::Relaxed) + 2;
+    let steps = total.map_or(steps, |t| steps.max(t + 2));
+    progress.report(steps, Some(steps), "Collecting diagnostics");
+
+    let mut messages = Vec::new();
+    let mut executables = Vec::new();
+    for mut msg in stdout
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+    {
+        if msg["reason"] == "compiler-message" {
+            messages.push(msg["message"].take());
+        } else if let Some(executable) = msg["executable"].as_str() {
+            executables.push(PathBuf::from(executable));
+        }
+    }
+    Ok(CargoRun {
+        success: status.success(),
+        messages,
+        executables,
+        stderr,
+    })
+}
+
+fn diagnostic(message: &Value, offset: usize, snippet_lines: usize) -> Option<CompilerDiagnostic> {
+    let spans = message["spans"].as_array()?;
+    // Summary lines like "aborting due to 2 previous errors" have no spans
+    if spans.is_empty() {
+        return None;
+    }
+    let primary = spans
+        .iter()
+        .find(|s| s["is_primary"] == true)
+        .unwrap_or(&spans[0]);
+    let line = primary["line_start"]
+        .as_u64()
+        .map(|l| l as usize)
+        .filter(|l| *l > offset && *l <= offset + snippet_lines)
+        .map(|l| l - offset);
+    Some(CompilerDiagnostic {
+        level: message["level"].as_str().unwrap_or("error").to_string(),
+        code: message["code"]["code"].as_str().map(String::from),
+        message: message["message"].as_str().unwrap_or_default().to_string(),
+        column: line.and(primary["column_start"].as_u64().map(|c| c as usize)),
+        line,
+        rendered: message["rendered"].as_str().unwrap_or_default().to_string(),
+    })
+}
+
+/// Avoid touching the manifest when it's unchanged, which would force a re-resolve
+pub(crate) fn write_if_changed(path: &Path, content: &str) -> Result<(), String> {
+    if fs::read_to_string(path).is_ok_and(|existing| existing == content) {
+        return Ok(());
+    }
+    fs::write(path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cancel::CancelToken;
+
+    #[test]
+    fn lock_is_exclusive_until_dropped() {
+        let dir = std::env::temp_dir().join(format!("leptos-mcp-lock-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("render.lock");
+        let progress = Progress::new(None, CancelToken::default());
+        let held = lock(&path, &progress).unwrap();
+        let other = File::options().write(true).open(&path).unwrap();
+        assert!(matches!(other.try_lock(), Err(TryLockError::WouldBlock)));
+        drop(held);
+        assert!(other.try_lock().is_ok());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,733 @@
+//! Project scaffolding
+//!
+//! A new Leptos app is mostly wiring: the features that split one crate into
+//! a server binary and a WASM client, the cargo-leptos metadata that builds
+//! both, and the entry points that mount or hydrate the root component. Each
+//! rendering mode (client-side with Trunk, SSR with hydration, or islands)
+//! wires them differently, and the APIs moved between 0.6 and 0.7, so the
+//! whole file set is generated from one spec for the target version.
+
+use crate::docs::{LeptosVersion, Syntax};
+use crate::generate::Backend;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Arguments of `scaffold-project`
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ProjectSpec {
+    /// Crate name, e.g. `my-app`
+    pub name: String,
+    /// Client-side rendering with Trunk, server rendering with hydration, or
+    /// islands
+    #[serde(default)]
+    pub mode: RenderMode,
+    /// Server integration for ssr and islands; defaults to the project
+    /// context's, then Axum
+    #[serde(default)]
+    pub backend: Option<Backend>,
+    /// Stylesheet setup
+    #[serde(default)]
+    pub styling: Styling,
+    /// Leptos version, e.g. `0.6` or `0.8`; defaults to the selected one
+    pub version: Option<String>,
+    /// Signal syntax; `nightly` adds Leptos's nightly feature and a
+    /// rust-toolchain.toml pinning nightly. Defaults to the project
+    /// context's, then stable
+    #[serde(default)]
+    pub syntax: Option<Syntax>,
+}
+
+/// Where the app renders
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum RenderMode {
+    /// In the browser only, built and served by Trunk
+    Csr,
+    /// On the server, then hydrated in the browser
+    #[default]
+    Ssr,
+    /// On the server, hydrating only `#[island]` components
+    Islands,
+}
+
+/// How the app is styled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Styling {
+    None,
+    #[default]
+    Css,
+    Scss,
+    Tailwind,
+}
+
+/// A file of the generated project
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ProjectFile {
+    /// Relative to the project root
+    pub path: String,
+    pub content: String,
+}
+
+/// The generated project and how to run it
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct Project {
+    pub files: Vec<ProjectFile>,
+    pub next_steps: Vec<String>,
+}
+
+/// Everything a template needs to know
+struct Target<'a> {
+    name: &'a str,
+    /// `name` as a Rust identifier
+    crate_name: String,
+    mode: RenderMode,
+    backend: Backend,
+    styling: Styling,
+    version: LeptosVersion,
+    syntax: Syntax,
+}
+
+impl Target<'_> {
+    fn modern(&self) -> bool {
+        self.version != LeptosVersion::V0_6
+    }
+
+    fn islands(&self) -> bool {
+        self.mode == RenderMode::Islands
+    }
+}
+
+/// The file set of a new project for `spec`
+///
+/// `backend` and `syntax` are used when the spec doesn't name them.
+pub fn project(
+    spec: &ProjectSpec,
+    version: LeptosVersion,
+    backend: Backend,
+    syntax: Syntax,
+) -> Result<Project, String> {
+    check_crate_name(&spec.name)?;
+    if spec.mode == RenderMode::Csr && spec.backend.is_some() {
+        return Err(
+            "A CSR app has no server, so it takes no 'backend'; use mode 'ssr' or 'islands' for a server"
+                .to_string(),
+        );
+    }
+    let target = Target {
+        name: &spec.name,
+        crate_name: spec.name.replace('-', "_"),
+        mode: spec.mode,
+        backend: spec.backend.unwrap_or(backend),
+        styling: spec.styling,
+        version,
+        syntax: spec.syntax.unwrap_or(syntax),
+    };
+
+    let file = |path: &str, content: String| ProjectFile {
+        path: path.to_string(),
+        content,
+    };
+    let mut files = vec![file("Cargo.toml", cargo_toml(&target))];
+    if target.mode == RenderMode::Csr {
+        files.push(file("Trunk.toml", trunk_toml()));
+        files.push(file("index.html", index_html(&target)));
+        files.push(file("src/main.rs", csr_main(&target)));
+    } else {
+        files.push(file("src/main.rs", server_main(&target)));
+    }
+    files.push(file("src/lib.rs", lib_rs(&target)));
+    files.push(file("src/app.rs", app_rs(&target)));
+    if let Some((path, content)) = stylesheet(target.styling) {
+        files.push(file(path, content));
+    }
+    files.push(file(".gitignore", gitignore(target.mode)));
+    if target.syntax == Syntax::Nightly {
+        files.push(file("rust-toolchain.toml", rust_toolchain()));
+    }
+
+    Ok(Project {
+        files,
+        next_steps: next_steps(&target),
+    })
+}
+
+fn check_crate_name(name: &str) -> Result<(), String> {
+    let valid = name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "Project name '{}' must be a lowercase crate name, e.g. 'my-app'",
+            name
+        ))
+    }
+}
+
+fn cargo_toml(target: &Target) -> String {
+    let v = target.version.as_str();
+    let mut out = format!(
+        "[package]\nname = \"{}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n",
+        target.name
+    );
+    if target.mode == RenderMode::Csr {
+        // Trunk enables nothing, so the client features are always on
+        let features = |krate: &str| match target.version {
+            _ if krate == "leptos" => leptos_dependency(target, vec!["csr"]),
+            LeptosVersion::V0_6 => format!(
+                "{} = {{ version = \"{}\", features = [\"csr\"] }}\n",
+                krate, v
+            ),
+            _ => format!("{} = \"{}\"\n", krate, v),
+        };
+        out.push_str("[dependencies]\n");
+        for krate in ["leptos", "leptos_meta", "leptos_router"] {
+            out.push_str(&features(krate));
+        }
+        out.push_str("console_error_panic_hook = \"0.1\"\n\n");
+        out.push_str(
+            "[profile.release]\nopt-level = \"z\"\nlto = true\ncodegen-units = 1\npanic = \"abort\"\n",
+        );
+        return out;
+    }
+
+    let islands = match (target.islands(), target.version) {
+        (false, _) => "",
+        (true, LeptosVersion::V0_6) => ", features = [\"experimental-islands\"]",
+        (true, _) => ", features = [\"islands\"]",
+    };
+    out.push_str("[lib]\ncrate-type = [\"cdylib\", \"rlib\"]\n\n[dependencies]\n");
+    out.push_str(&leptos_dependency(
+        target,
+        match (target.islands(), target.version) {
+            (false, _) => vec![],
+            (true, LeptosVersion::V0_6) => vec!["experimental-islands"],
+            (true, _) => vec!["islands"],
+        },
+    ));
+    out.push_str(&format!(
+        "leptos_meta = \"{}\"\nleptos_router = \"{}\"\n",
+        v, v
+    ));
+    out.push_str("console_error_panic_hook = { version = \"0.1\", optional = true }\n");
+    out.push_str("wasm-bindgen = { version = \"0.2\", optional = true }\n");
+    // The server builds its own 0.6 islands support
+    let server_islands = match target.version {
+        LeptosVersion::V0_6 => islands,
+        _ => "",
+    };
+    let server_crates = match target.backend {
+        Backend::Axum => {
+            let axum = match target.version {
+                LeptosVersion::V0_8 => "0.8",
+                _ => "0.7",
+            };
+            out.push_str(&format!(
+                "axum = {{ version = \"{}\", optional = true }}\n",
+                axum
+            ));
+            out.push_str(&format!(
+                "leptos_axum = {{ version = \"{}\", optional = true{} }}\n",
+                v, server_islands
+            ));
+            out.push_str(
+                "tokio = { version = \"1\", features = [\"rt-multi-thread\"], optional = true }\n",
+            );
+            if target.version == LeptosVersion::V0_6 {
+                out.push_str(
+                    "tower-http = { version = \"0.5\", features = [\"fs\"], optional = true }\n",
+                );
+                vec!["axum", "leptos_axum", "tokio", "tower-http"]
+            } else {
+                vec!["axum", "leptos_axum", "tokio"]
+            }
+        }
+        Backend::Actix => {
+            out.push_str("actix-files = { version = \"0.6\", optional = true }\n");
+            out.push_str(
+                "actix-web = { version = \"4\", features = [\"macros\"], optional = true }\n",
+            );
+            out.push_str(&format!(
+                "leptos_actix = {{ version = \"{}\", optional = true{} }}\n",
+                v, server_islands
+            ));
+            vec!["actix-files", "actix-web", "leptos_actix"]
+        }
+    };
+
+    let mut hydrate = vec![
+        "\"leptos/hydrate\"".to_string(),
+        "\"dep:console_error_panic_hook\"".to_string(),
+        "\"dep:wasm-bindgen\"".to_string(),
+    ];
+    // Since 0.7 the companion crates follow leptos' hydrate feature themselves
+    if target.version == LeptosVersion::V0_6 {
+        hydrate.push("\"leptos_meta/hydrate\"".to_string());
+        hydrate.push("\"leptos_router/hydrate\"".to_string());
+    }
+    let mut ssr: Vec<String> = server_crates
+        .iter()
+        .map(|c| format!("\"dep:{}\"", c))
+        .collect();
+    for krate in ["leptos", "leptos_meta", "leptos_router"] {
+        ssr.push(format!("\"{}/ssr\"", krate));
+    }
+    out.push_str(&format!(
+        "\n[features]\nhydrate = [{}]\nssr = [{}]\n\n",
+        hydrate.join(", "),
+        ssr.join(", ")
+    ));
+
+    out.push_str("# Size-optimized profile for the WASM client\n[profile.wasm-release]\ninherits = \"release\"\nopt-level = \"z\"\nlto = true\ncodegen-units = 1\npanic = \"abort\"\n\n");
+
+    out.push_str(&format!(
+        "[package.metadata.leptos]\noutput-name = \"{}\"\nsite-root = \"target/site\"\nsite-pkg-dir = \"pkg\"\n",
+        target.name
+    ));
+    match target.styling {
+        Styling::None => {}
+        Styling::Css => out.push_str("style-file = \"style/main.css\"\n"),
+        Styling::Scss => out.push_str("style-file = \"style/main.scss\"\n"),
+        Styling::Tailwind => out.push_str("tailwind-input-file = \"style/tailwind.css\"\n"),
+    }
+    out.push_str(
+        "site-addr = \"127.0.0.1:3000\"\nreload-port = 3001\nenv = \"DEV\"\n\
+         bin-features = [\"ssr\"]\nbin-default-features = false\n\
+         lib-features = [\"hydrate\"]\nlib-default-features = false\n\
+         lib-profile-release = \"wasm-release\"\n",
+    );
+    out
+}
+
+fn trunk_toml() -> String {
+    "[build]\ntarget = \"index.html\"\n\n[serve]\nport = 8080\n".to_string()
+}
+
+fn index_html(target: &Target) -> String {
+    let style = match target.styling {
+        Styling::None => String::new(),
+        Styling::Css => "    <link data-trunk rel=\"css\" href=\"style/main.css\" />\n".to_string(),
+        Styling::Scss => {
+            "    <link data-trunk rel=\"scss\" href=\"style/main.scss\" />\n".to_string()
+        }
+        Styling::Tailwind => {
+            "    <link data-trunk rel=\"tailwind-css\" href=\"style/tailwind.css\" />\n".to_string()
+        }
+    };
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n  <head>\n    <meta charset=\"utf-8\" />\n    <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\" />\n    <link data-trunk rel=\"rust\" data-wasm-opt=\"z\" />\n{}  </head>\n  <body></body>\n</html>\n",
+        style
+    )
+}
+
+fn csr_main(target: &Target) -> String {
+    let import = match target.modern() {
+        true => "leptos::mount::mount_to_body",
+        false => "leptos::mount_to_body",
+    };
+    format!(
+        "use {};\nuse {}::app::App;\n\nfn main() {{\n    console_error_panic_hook::set_once();\n    mount_to_body(App);\n}}\n",
+        import, target.crate_name
+    )
+}
+
+fn server_main(target: &Target) -> String {
+    let body = match (target.backend, target.modern()) {
+        (Backend::Axum, true) => format!(
+            "#[cfg(feature = \"ssr\")]
+#[tokio::main]
+async fn main() {{
+    use axum::Router;
+    use leptos::logging::log;
+    use leptos::prelude::*;
+    use leptos_axum::{{generate_route_list, LeptosRoutes}};
+    use {c}::app::{{shell, App}};
+
+    let conf = get_configuration(None).unwrap();
+    let addr = conf.leptos_options.site_addr;
+    let leptos_options = conf.leptos_options;
+    // Every <Route> of the app, so the server renders them
+    let routes = generate_route_list(App);
+
+    let app = Router::new()
+        .leptos_routes(&leptos_options, routes, {{
+            let leptos_options = leptos_options.clone();
+            move || shell(leptos_options.clone())
+        }})
+        // The WASM, JS and CSS cargo-leptos builds, and a 404 page for the rest
+        .fallback(leptos_axum::file_and_error_handler(shell))
+        .with_state(leptos_options);
+
+    log!(\"listening on http://{{}}\", &addr);
+    let listener = tokio::net::TcpListener::bind(&addr).await.unwrap();
+    axum::serve(listener, app.into_make_service())
+        .await
+        .unwrap();
+}}
+",
+            c = target.crate_name
+        ),
+        (Backend::Axum, false) => format!(
+            "#[cfg(feature = \"ssr\")]
+#[tokio::main]
+async fn main() {{
+    use axum::Router;
+    use leptos::*;
+    use leptos_axum::{{generate_route_list, LeptosRoutes}};
+    use tower_http::services::ServeDir;
+    use {c}::app::App;
+
+    let conf = get_configuration(None).await.unwrap();
+    let leptos_options = conf.leptos_options;
+    let addr = leptos_options.site_addr;
+    // Every <Route> of the app, so the server renders them
+    let routes = generate_route_list(App);
+
+    let app = Router::new()
+        .leptos_routes(&leptos_options, routes, App)
+        // The WASM, JS and CSS cargo-leptos builds
+        .fallback_service(ServeDir::new(leptos_options.site_root.clone()))
+        .with_state(leptos_options);
+
+    logging::log!(\"listening on http://{{}}\", &addr);
+    let listener = tokio::net::TcpListener::bind(&addr).await.unwrap();
+    axum::serve(listener, app.into_make_service())
+        .await
+        .unwrap();
+}}
+",
+            c = target.crate_name
+        ),
+        (Backend::Actix, true) => format!(
+            "#[cfg(feature = \"ssr\")]
+#[actix_web::main]
+async fn main() -> std::io::Result<()> {{
+    use actix_files::Files;
+    use actix_web::{{web, HttpServer}};
+    use leptos::prelude::*;
+    use leptos_actix::{{generate_route_list, LeptosRoutes}};
+    use {c}::app::{{shell, App}};
+
+    let conf = get_configuration(None).unwrap();
+    let addr = conf.leptos_options.site_addr;
+    println!(\"listening on http://{{}}\", &addr);
+
+    HttpServer::new(move || {{
+        // Every <Route> of the app, so the server renders them
+        let routes = generate_route_list(App);
+        let leptos_options = &conf.leptos_options;
+        let site_root = leptos_options.site_root.clone().to_string();
+
+        // `App` is the Leptos component here, so actix' is spelled out
+        actix_web::App::new()
+            // The WASM, JS and CSS cargo-leptos builds
+            .service(Files::new(\"/pkg\", format!(\"{{site_root}}/pkg\")))
+            .leptos_routes(routes, {{
+                let leptos_options = leptos_options.clone();
+                move || shell(leptos_options.clone())
+            }})
+            .app_data(web::Data::new(leptos_options.to_owned()))
+    }})
+    .bind(&addr)?
+    .run()
+    .await
+}}
+",
+            c = target.crate_name
+        ),
+        (Backend::Actix, false) => format!(
+            "#[cfg(feature = \"ssr\")]
+#[actix_web::main]
+async fn main() -> std::io::Result<()> {{
+    use actix_files::Files;
+    use actix_web::{{web, HttpServer}};
+    use leptos::*;
+    use leptos_actix::{{generate_route_list, LeptosRoutes}};
+    use {c}::app::App;
+
+    let conf = get_configuration(None).await.unwrap();
+    let addr = conf.leptos_options.site_addr;
+    // Every <Route> of the app, so the server renders them
+    let routes = generate_route_list(App);
+    println!(\"listening on http://{{}}\", &addr);
+
+    HttpServer::new(move || {{
+        let leptos_options = &conf.leptos_options;
+        let site_root = &leptos_options.site_root;
+
+        // `App` is the Leptos component here, so actix' is spelled out
+        actix_web::App::new()
+            // The WASM, JS and CSS cargo-leptos builds
+            .service(Files::new(\"/pkg\", format!(\"{{site_root}}/pkg\")))
+            .leptos_routes(leptos_options.to_owned(), routes.to_owned(), App)
+            .app_data(web::Data::new(leptos_options.to_owned()))
+    }})
+    .bind(&addr)?
+    .run()
+    .await
+}}
+",
+            c = target.crate_name
+        ),
+    };
+    format!(
+        "{}
+#[cfg(not(feature = \"ssr\"))]
+pub fn main() {{
+    // The client starts from `hydrate` in lib.rs
+}}
+",
+        body
+    )
+}
+
+fn lib_rs(target: &Target) -> String {
+    if target.mode == RenderMode::Csr {
+        return "pub mod app;\n".to_string();
+    }
+    let start = match (target.islands(), target.modern()) {
+        (false, true) => "    leptos::mount::hydrate_body(App);",
+        (false, false) => "    leptos::mount_to_body(App);",
+        // Islands hydrate themselves from the server-rendered HTML
+        (true, true) => "    leptos::mount::hydrate_islands();",
+        (true, false) => "    leptos::leptos_dom::HydrationCtx::stop_hydrating();",
+    };
+    let import = match target.islands() {
+        true => "",
+        false => "    use crate::app::App;\n\n",
+    };
+    format!(
+        "pub mod app;
+
+/// Entry point of the WASM client
+#[cfg(feature = \"hydrate\")]
+#[wasm_bindgen::prelude::wasm_bindgen]
+pub fn hydrate() {{
+{}    console_error_panic_hook::set_once();
+{}
+}}
+",
+        import, start
+    )
+}
+
+fn app_rs(target: &Target) -> String {
+    let tailwind = target.styling == Styling::Tailwind;
+    let (h1, button) = match tailwind {
+        true => (
+            " class=\"text-4xl font-bold\"",
+            " class=\"rounded bg-sky-600 px-4 py-2 text-white\"",
+        ),
+        false => ("", ""),
+    };
+    let ssr = target.mode != RenderMode::Csr;
+    let stylesheet = match (ssr, target.styling) {
+        (true, Styling::None) | (false, _) => String::new(),
+        (true, _) => format!(
+            "        <Stylesheet id=\"leptos\" href=\"/pkg/{}.css\" />\n",
+            target.name
+        ),
+    };
+    let mut out = String::new();
+
+    if target.modern() {
+        out.push_str("use leptos::prelude::*;\n");
+        let meta: &[&str] = match (ssr, stylesheet.is_empty()) {
+            (true, false) => &["provide_meta_context", "MetaTags", "Stylesheet", "Title"],
+            (true, true) => &["provide_meta_context", "MetaTags", "Title"],
+            (false, _) => &["provide_meta_context", "Title"],
+        };
+        out.push_str(&format!("use leptos_meta::{{{}}};\n", meta.join(", ")));
+        out.push_str(
+            "use leptos_router::components::{Route, Router, Routes};\nuse leptos_router::path;\n",
+        );
+        if ssr {
+            out.push_str(&format!(
+                "
+/// The HTML document the server renders every page into
+pub fn shell(options: LeptosOptions) -> impl IntoView {{
+    view! {{
+        <!DOCTYPE html>
+        <html lang=\"en\">
+            <head>
+                <meta charset=\"utf-8\" />
+                <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\" />
+                <AutoReload options=options.clone() />
+                <HydrationScripts options{} />
+                <MetaTags />
+            </head>
+            <body>
+                <App />
+            </body>
+        </html>
+    }}
+}}
+",
+                if target.islands() {
+                    " islands=true"
+                } else {
+                    ""
+                }
+            ));
+        }
+        out.push_str(&format!(
+            "
+#[component]
+pub fn App() -> impl IntoView {{
+    // Lets leptos_meta tags like <Title> render into <head>
+    provide_meta_context();
+
+    view! {{
+{}        <Title text=\"Welcome to Leptos\" />
+        <Router>
+            <main>
+                <Routes fallback=|| \"Page not found.\">
+                    <Route path=path!(\"/\") view=HomePage />
+                </Routes>
+            </main>
+        </Router>
+    }}
+}}
+",
+            stylesheet
+        ));
+    } else {
+        out.push_str("use leptos::*;\nuse leptos_meta::*;\nuse leptos_router::*;\n");
+        out.push_str(&format!(
+            "
+#[component]
+pub fn App() -> impl IntoView {{
+    // Lets leptos_meta tags like <Title> render into <head>
+    provide_meta_context();
+
+    view! {{
+{}        <Title text=\"Welcome to Leptos\" />
+        <Router>
+            <main>
+                <Routes>
+                    <Route path=\"\" view=HomePage />
+                </Routes>
+            </main>
+        </Router>
+    }}
+}}
+",
+            stylesheet
+        ));
+    }
+
+    let counter = match target.modern() {
+        true => "    let count = RwSignal::new(0);\n    let on_click = move |_| *count.write() += 1;\n",
+        false => "    let (count, set_count) = create_signal(0);\n    let on_click = move |_| set_count.update(|count| *count += 1);\n",
+    };
+    let button = format!(
+        "<button{} on:click=on_click>\"Click Me: \" {{count}}</button>",
+        button
+    );
+    if target.islands() {
+        out.push_str(&format!(
+            "
+/// Rendered on the server only; its HTML never needs hydrating
+#[component]
+fn HomePage() -> impl IntoView {{
+    view! {{
+        <h1{}>\"Welcome to Leptos!\"</h1>
+        <Counter />
+    }}
+}}
+
+/// Interactive, so it ships to the browser and hydrates on its own
+#[island]
+fn Counter() -> impl IntoView {{
+{}
+    view! {{ {} }}
+}}
+",
+            h1, counter, button
+        ));
+    } else {
+        out.push_str(&format!(
+            "
+#[component]
+fn HomePage() -> impl IntoView {{
+{}
+    view! {{
+        <h1{}>\"Welcome to Leptos!\"</h1>
+        {}
+    }}
+}}
+",
+            counter, h1, button
+        ));
+    }
+    out
+}
+
+fn stylesheet(styling: Styling) -> Option<(&'static str, String)> {
+    let base = "body {\n    font-family: sans-serif;\n    text-align: center;\n}\n";
+    match styling {
+        Styling::None => None,
+        Styling::Css => Some(("style/main.css", base.to_string())),
+        Styling::Scss => Some((
+            "style/main.scss",
+            format!(
+                "$accent: #0284c7;\n\n{}\nbutton {{\n    background: $accent;\n    color: white;\n    border: none;\n    padding: 0.5rem 1rem;\n}}\n",
+                base
+            ),
+        )),
+        Styling::Tailwind => Some(("style/tailwind.css", "@import \"tailwindcss\";\n".to_string())),
+    }
+}
+
+fn gitignore(mode: RenderMode) -> String {
+    match mode {
+        RenderMode::Csr => "/target\n/dist\n".to_string(),
+        _ => "/target\n".to_string(),
+    }
+}
+
+/// The `leptos` dependency with `features`, plus `nightly` for nightly syntax
+fn leptos_dependency(target: &Target, mut features: Vec<&str>) -> String {
+    let v = target.version.as_str();
+    if target.syntax == Syntax::Nightly {
+        features.push("nightly");
+    }
+    if features.is_empty() {
+        return format!("leptos = \"{}\"\n", v);
+    }
+    let features: Vec<String> = features.iter().map(|f| format!("\"{}\"", f)).collect();
+    format!(
+        "leptos = {{ version = \"{}\", features = [{}] }}\n",
+        v,
+        features.join(", ")
+    )
+}
+
+/// Pins nightly, which the `nightly` feature needs
+fn rust_toolchain() -> String {
+    "[toolchain]\nchannel = \"nightly\"\ntargets = [\"wasm32-unknown-unknown\"]\n".to_string()
+}
+
+fn next_steps(target: &Target) -> Vec<String> {
+    let mut steps = vec!["rustup target add wasm32-unknown-unknown".to_string()];
+    if target.mode == RenderMode::Csr {
+        steps.push("cargo install trunk --locked".to_string());
+        if target.styling == Styling::Tailwind {
+            steps.push("Trunk downloads the Tailwind CLI on the first build".to_string());
+        }
+        steps.push("trunk serve --open, then edit src/app.rs".to_string());
+    } else {
+        steps.push("cargo install cargo-leptos --locked".to_string());
+        if target.styling == Styling::Tailwind {
+            steps.push("cargo-leptos downloads the Tailwind CLI on the first build".to_string());
+        }
+        steps.push(
+            "cargo leptos watch, open http://127.0.0.1:3000, then edit src/app.rs".to_string(),
+        );
+    }
+    steps
+}
@@ -0,0 +1,122 @@
+//! Schemas advertised by `tools/list`
+//!
+//! Every tool returns `structuredContent` alongside its text, and its shape is
+//! advertised as the tool's `outputSchema`; results reported with `isError`
+//! carry only text.
+//!
+//! Both input and output schemas are generated with `schemars` from the types
+//! the tools parse and return, so they can't drift apart.
+
+use schemars::generate::{Contract, SchemaSettings};
+use schemars::transform::{transform_subschemas, Transform};
+use schemars::{JsonSchema, Schema};
+use serde_json::{json, Map, Value};
+
+/// Simplifies generated schemas to the subset MCP clients handle
+///
+/// Optional arguments are left out rather than passed as null, so input
+/// schemas drop `null` from their types. Unit enums become a plain `enum`
+/// list, formats like `uint64` go, and wrapped doc comment lines are joined.
+#[derive(Clone)]
+struct Tidy {
+    /// Keep `null` in types, for output values that may be null
+    nullable: bool,
+}
+
+impl Transform for Tidy {
+    fn transform(&mut self, schema: &mut Schema) {
+        transform_subschemas(self, schema);
+        let Some(object) = schema.as_object_mut() else {
+            return;
+        };
+        if !self.nullable {
+            strip_null(object);
+        }
+        if let Some(Value::Array(variants)) = object.get("oneOf") {
+            let values: Option<Vec<Value>> =
+                variants.iter().map(|v| v.get("const").cloned()).collect();
+            if let Some(values) = values {
+                object.remove("oneOf");
+                object.insert("type".to_string(), json!("string"));
+                object.insert("enum".to_string(), Value::Array(values));
+            }
+        }
+        if object.get("format").is_some_and(Value::is_string) {
+            object.remove("format");
+        }
+        if let Some(Value::String(description)) = object.get_mut("description") {
+            *description = description
+                .split("\n\n")
+                .map(|paragraph| paragraph.replace('\n', " "))
+                .collect::<Vec<_>>()
+                .join("\n\n");
+        }
+    }
+}
+
+/// `T | null` as `T`
+fn strip_null(object: &mut Map<String, Value>) {
+    if let Some(Value::Array(types)) = object.get_mut("type") {
+        types.retain(|t| t != "null");
+        if let [single] = types.as_slice() {
+            let single = single.clone();
+            object.insert("type".to_string(), single);
+        }
+    }
+    if let Some(Value::Array(values)) = object.get_mut("enum") {
+        values.retain(|v| !v.is_null());
+    }
+    if object.get("default").is_some_and(Value::is_null) {
+        object.remove("default");
+    }
+    let Some(Value::Array(mut variants)) = object.remove("anyOf") else {
+        return;
+    };
+    variants.retain(|v| v.get("type") != Some(&json!("null")));
+    match variants.pop() {
+        Some(Value::Object(variant)) if variants.is_empty() => {
+            for (key, value) in variant {
+                object.entry(key).or_insert(value);
+            }
+        }
+        Some(variant) => {
+            variants.push(variant);
+            object.insert("anyOf".to_string(), Value::Array(variants));
+        }
+        None => {}
+    }
+}
+
+/// Schema of `T` with its subschemas inlined, as a standalone value
+fn generate<T: JsonSchema>(contract: Contract) -> Value {
+    let nullable = matches!(contract, Contract::Serialize);
+    let mut schema = SchemaSettings::draft2020_12()
+        .with(|s| {
+            s.inline_subschemas = true;
+            s.meta_schema = None;
+            s.contract = contract;
+        })
+        .with_transform(Tidy { nullable })
+        .into_generator()
+        .into_root_schema_for::<T>();
+    schema.remove("title");
+    schema.to_value()
+}
+
+/// `inputSchema` of a tool whose arguments deserialize into `T`
+///
+/// The tool's own description says what it does, so the type's doc comment
+/// is left out.
+pub fn input<T: JsonSchema>() -> Value {
+    let mut schema = generate::<T>(Contract::Deserialize);
+    if let Some(object) = schema.as_object_mut() {
+        object.remove("description");
+        object.entry("properties").or_insert_with(|| json!({}));
+    }
+    schema
+}
+
+/// `outputSchema` of a tool whose results serialize from `T`
+pub fn output<T: JsonSchema>() -> Value {
+    generate::<T>(Contract::Serialize)
+}
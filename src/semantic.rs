@@ -0,0 +1,525 @@
+//! Semantic documentation search
+//!
+//! Keyword search misses questions phrased in terms of symptoms ("my page
+//! flashes empty then fills in"). `search-docs` with `mode: "semantic"` ranks
+//! subsections by the cosine similarity of their embedding to the query's
+//! instead.
+//!
+//! The embedder is pluggable. The built-in one hashes words, word pairs and
+//! word fragments into a fixed-size vector and expands the query with the
+//! Leptos concepts its wording points at; it needs nothing beyond the binary.
+//! [`HttpEmbedder`] calls an OpenAI-compatible `/embeddings` endpoint (through
+//! `curl`, like the book sync) for model-quality vectors.
+//!
+//! The index is built on the first semantic search, not at startup, and its
+//! vectors are cached under `~/.cache/leptos-mcp/embeddings/`, keyed by the
+//! embedder and the indexed content, so later sessions load them from disk.
+
+use crate::docs::{self, DocChunk, LeptosVersion, SearchHit};
+use crate::sync;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+
+/// Turns text into vectors compared by cosine similarity
+pub trait Embedder: Send + Sync {
+    /// Identifies the vectors this embedder produces, for the on-disk cache
+    fn id(&self) -> String;
+
+    /// One vector per text, all of the same dimension
+    fn embed(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>, String>;
+
+    /// Vector of a search query; defaults to embedding it like a document
+    fn embed_query(&self, query: &str) -> Result<Vec<f32>, String> {
+        self.embed(&[query])?
+            .pop()
+            .ok_or_else(|| "Embedder returned no vector".to_string())
+    }
+}
+
+fn embedder_slot() -> &'static RwLock<Arc<dyn Embedder>> {
+    static EMBEDDER: OnceLock<RwLock<Arc<dyn Embedder>>> = OnceLock::new();
+    EMBEDDER.get_or_init(|| RwLock::new(Arc::new(HashingEmbedder)))
+}
+
+/// Use `embedder` for semantic search from now on
+pub fn set_embedder(embedder: Arc<dyn Embedder>) {
+    *embedder_slot().write().unwrap() = embedder;
+}
+
+fn embedder() -> Arc<dyn Embedder> {
+    embedder_slot().read().unwrap().clone()
+}
+
+/// Subsections of `version` most similar in meaning to `query`
+pub fn search(query: &str, version: LeptosVersion, limit: usize) -> Result<Vec<SearchHit>, String> {
+    if query.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let embedder = embedder();
+    let chunks = docs::chunks(version);
+    let index = index(embedder.as_ref(), version, &chunks)?;
+    let query_vector = embedder.embed_query(query)?;
+
+    let lower = query.to_lowercase();
+    let terms: Vec<&str> = words(&lower).filter(|w| !is_stopword(w)).collect();
+    let mut scored: Vec<(usize, f32)> = index
+        .vectors
+        .iter()
+        .map(|vector| cosine(&query_vector, vector))
+        .enumerate()
+        .filter(|(_, score)| *score > 0.0)
+        .collect();
+    scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+    Ok(scored
+        .into_iter()
+        .take(limit)
+        .map(|(i, score)| chunks[i].hit(f64::from(score), &terms))
+        .collect())
+}
+
+/// Vectors of a version's chunks, in [`docs::chunks`] order
+struct Index {
+    /// Hash of the embedder id and the chunk texts the vectors belong to
+    key: u64,
+    vectors: Vec<Vec<f32>>,
+}
+
+/// The index for `chunks`: kept in memory, else read from disk, else embedded
+///
+/// Rebuilt when the embedder or the content changes (a book sync).
+fn index(
+    embedder: &dyn Embedder,
+    version: LeptosVersion,
+    chunks: &[DocChunk],
+) -> Result<Arc<Index>, String> {
+    static INDEXES: OnceLock<Mutex<HashMap<LeptosVersion, Arc<Index>>>> = OnceLock::new();
+    let mut hasher = Fnv::default();
+    hasher.write(embedder.id().as_bytes());
+    for chunk in chunks {
+        hasher.write(&[0]);
+        hasher.write(chunk.text().as_bytes());
+    }
+    let key = hasher.finish();
+
+    // Held while embedding, so concurrent searches don't embed twice
+    let mut indexes = INDEXES.get_or_init(Default::default).lock().unwrap();
+    if let Some(index) = indexes.get(&version).filter(|index| index.key == key) {
+        return Ok(index.clone());
+    }
+    let path = cache_path(key);
+    let vectors = match read_vectors(&path).filter(|v| v.len() == chunks.len()) {
+        Some(vectors) => vectors,
+        None => {
+            let texts: Vec<&str> = chunks.iter().map(|c| c.text()).collect();
+            let vectors = embedder.embed(&texts)?;
+            if vectors.len() != chunks.len() {
+                return Err(format!(
+                    "Embedder returned {} vectors for {} texts",
+                    vectors.len(),
+                    chunks.len()
+                ));
+            }
+            // A failed write only costs a re-embed next session
+            if let Err(e) = write_vectors(&path, &vectors) {
+                tracing::warn!("Failed to cache embeddings: {}", e);
+            }
+            vectors
+        }
+    };
+    let index = Arc::new(Index { key, vectors });
+    indexes.insert(version, index.clone());
+    Ok(index)
+}
+
+fn cache_path(key: u64) -> PathBuf {
+    sync::cache_dir("embeddings").join(format!("{:016x}.bin", key))
+}
+
+/// Cached vectors: vector count and dimension as little-endian `u32`s, then the `f32`s
+fn read_vectors(path: &PathBuf) -> Option<Vec<Vec<f32>>> {
+    let bytes = fs::read(path).ok()?;
+    let header = |i: usize| -> Option<usize> {
+        Some(u32::from_le_bytes(bytes.get(i * 4..i * 4 + 4)?.try_into().ok()?) as usize)
+    };
+    let (count, dims) = (header(0)?, header(1)?);
+    let data = bytes.get(8..)?;
+    if dims == 0 || data.len() != count * dims * 4 {
+        return None;
+    }
+    let floats: Vec<f32> = data
+        .chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect();
+    Some(floats.chunks(dims).map(<[f32]>::to_vec).collect())
+}
+
+fn write_vectors(path: &PathBuf, vectors: &[Vec<f32>]) -> Result<(), String> {
+    let dir = path.parent().ok_or("Invalid cache path")?;
+    fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let dims = vectors.first().map_or(0, Vec::len);
+    let mut bytes = Vec::with_capacity(8 + vectors.len() * dims * 4);
+    bytes.extend((vectors.len() as u32).to_le_bytes());
+    bytes.extend((dims as u32).to_le_bytes());
+    for value in vectors.iter().flatten() {
+        bytes.extend(value.to_le_bytes());
+    }
+    // Written aside and renamed, so a concurrent server never reads half a file
+    let partial = path.with_extension("partial");
+    fs::write(&partial, bytes)
+        .and_then(|()| fs::rename(&partial, path))
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm = |v: &[f32]| v.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norms = norm(a) * norm(b);
+    if norms == 0.0 {
+        0.0
+    } else {
+        dot / norms
+    }
+}
+
+/// Dimension of the built-in embedder's vectors
+const HASH_DIMS: usize = 1024;
+
+/// Everyday wording and the Leptos concepts it points at, for query expansion
+const CONCEPTS: &[(&[&str], &[&str])] = &[
+    (
+        &[
+            "flash",
+            "flicker",
+            "blink",
+            "empty",
+            "fill",
+            "spinner",
+            "placeholder",
+            "loading",
+        ],
+        &["suspense", "transition", "fallback", "resource"],
+    ),
+    (
+        &["mismatch", "differ", "hydrat", "warning", "console"],
+        &["hydration", "ssr", "browser"],
+    ),
+    (
+        &[
+            "screen",
+            "reader",
+            "keyboard",
+            "blind",
+            "accessible",
+            "wcag",
+        ],
+        &["accessibility", "aria", "label", "alt"],
+    ),
+    (
+        &["xss", "vulnerab", "exploit", "attack", "csrf", "secur"],
+        &["security", "inner_html", "auth", "cors", "cookie"],
+    ),
+    (
+        &["fetch", "load", "async", "api", "data", "request", "await"],
+        &["resource", "async", "server", "function"],
+    ),
+    (
+        &[
+            "submit",
+            "form",
+            "mutation",
+            "post",
+            "save",
+            "optimistic",
+            "pending",
+        ],
+        &["action", "actionform", "server", "function"],
+    ),
+    (
+        &[
+            "url", "navigate", "link", "param", "query", "page", "redirect",
+        ],
+        &["router", "route", "params", "navigation"],
+    ),
+    (
+        &[
+            "state", "reactive", "rerender", "update", "change", "counter", "derive",
+        ],
+        &["signal", "memo", "derived", "effect"],
+    ),
+    (
+        &["error", "fail", "panic", "crash", "unwrap", "wrong"],
+        &["error", "errorboundary", "result", "serverfnerror"],
+    ),
+    (
+        &[
+            "list",
+            "loop",
+            "iterate",
+            "row",
+            "table",
+            "item",
+            "collection",
+        ],
+        &["for", "each", "key", "iterating"],
+    ),
+    (
+        &["child", "slot", "wrap", "prop", "pass", "reuse"],
+        &["component", "props", "children"],
+    ),
+    (
+        &[
+            "database", "db", "sql", "secret", "auth", "cookie", "header", "session",
+        ],
+        &["server", "function", "extractor", "ssr"],
+    ),
+    (
+        &["title", "meta", "head", "stylesheet", "css", "favicon"],
+        &["meta", "title", "stylesheet"],
+    ),
+    (
+        &["window", "document", "localstorage", "dom", "browser"],
+        &["effect", "hydration", "browser", "apis"],
+    ),
+    (
+        &[
+            "timer", "interval", "debounce", "throttle", "listener", "resize",
+        ],
+        &["use_interval", "use_event_listener", "leptos", "use"],
+    ),
+];
+
+/// Feature-hashing embedder built into the server
+///
+/// Vectors hold sublinear counts of stemmed words (weight 1), adjacent word
+/// pairs (0.5) and four-letter fragments (0.25), so `signals` and `signal`
+/// overlap. Queries also get the concept words of [`CONCEPTS`] their wording
+/// matches (0.6).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HashingEmbedder;
+
+impl HashingEmbedder {
+    fn vector(text: &str, expand: bool) -> Vec<f32> {
+        let lower = text.to_lowercase();
+        let kept: Vec<&str> = words(&lower).filter(|w| !is_stopword(w)).collect();
+        let stems: Vec<&str> = kept.iter().map(|w| stem(w)).collect();
+        let mut counts: HashMap<usize, f32> = HashMap::new();
+        let mut add = |feature: &[&str], weight: f32| {
+            let mut hasher = Fnv::default();
+            for part in feature {
+                hasher.write(part.as_bytes());
+                hasher.write(&[0]);
+            }
+            *counts
+                .entry(hasher.finish() as usize % HASH_DIMS)
+                .or_default() += weight;
+        };
+        for (i, word) in stems.iter().enumerate() {
+            add(&[word], 1.0);
+            if let Some(next) = stems.get(i + 1) {
+                add(&[word, next], 0.5);
+            }
+            let chars: Vec<(usize, char)> = word.char_indices().collect();
+            for window in chars.windows(4) {
+                let end = window[3].0 + window[3].1.len_utf8();
+                add(&["#", &word[window[0].0..end]], 0.25);
+            }
+        }
+        if expand {
+            for (triggers, concepts) in CONCEPTS {
+                if kept
+                    .iter()
+                    .any(|w| triggers.iter().any(|t| w.starts_with(t)))
+                {
+                    for concept in *concepts {
+                        add(&[stem(concept)], 0.6);
+                    }
+                }
+            }
+        }
+
+        let mut vector = vec![0.0; HASH_DIMS];
+        for (dim, count) in counts {
+            vector[dim] = 1.0 + count.ln().max(0.0);
+        }
+        vector
+    }
+}
+
+impl Embedder for HashingEmbedder {
+    fn id(&self) -> String {
+        format!("hashing-v1-{}", HASH_DIMS)
+    }
+
+    fn embed(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>, String> {
+        Ok(texts.iter().map(|text| Self::vector(text, false)).collect())
+    }
+
+    fn embed_query(&self, query: &str) -> Result<Vec<f32>, String> {
+        Ok(Self::vector(query, true))
+    }
+}
+
+/// Environment variable holding the bearer token for [`HttpEmbedder`]
+pub const EMBEDDINGS_KEY_ENV: &str = "LEPTOS_MCP_EMBEDDINGS_KEY";
+
+/// Texts sent per `/embeddings` request
+const BATCH_SIZE: usize = 64;
+
+/// Time limit for one `/embeddings` request
+const TIMEOUT_SECS: &str = "60";
+
+/// Embeds through an OpenAI-compatible `/embeddings` endpoint
+#[derive(Debug, Clone)]
+pub struct HttpEmbedder {
+    /// Full endpoint URL, e.g. `http://localhost:11434/v1/embeddings`
+    url: String,
+    model: Option<String>,
+}
+
+impl HttpEmbedder {
+    pub fn new(url: impl Into<String>, model: Option<String>) -> Self {
+        Self {
+            url: url.into(),
+            model,
+        }
+    }
+
+    fn request(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>, String> {
+        let mut body = json!({ "input": texts });
+        if let Some(model) = &self.model {
+            body["model"] = json!(model);
+        }
+        let mut command = Command::new("curl");
+        command
+            .args(["--silent", "--show-error", "--fail-with-body", "--location"])
+            .args(["--max-time", TIMEOUT_SECS])
+            .args(["--header", "Content-Type: application/json"])
+            .args(["--data-binary", "@-"]);
+        if let Ok(key) = std::env::var(EMBEDDINGS_KEY_ENV) {
+            command
+                .arg("--header")
+                .arg(format!("Authorization: Bearer {}", key.trim()));
+        }
+        let mut child = command
+            .arg(&self.url)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("Failed to run curl: {}", e))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(body.to_string().as_bytes())
+                .map_err(|e| format!("Failed to send the embeddings request: {}", e))?;
+        }
+        let output = child
+            .wait_with_output()
+            .map_err(|e| format!("Failed to run curl: {}", e))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let body = String::from_utf8_lossy(&output.stdout);
+            return Err(format!(
+                "Embeddings request to {} failed: {} {}",
+                self.url,
+                stderr.trim(),
+                body.trim()
+            ));
+        }
+
+        let response: Value = serde_json::from_slice(&output.stdout)
+            .map_err(|e| format!("Invalid embeddings response: {}", e))?;
+        let mut data: Vec<&Value> = response
+            .get("data")
+            .and_then(Value::as_array)
+            .ok_or("Embeddings response has no 'data' array")?
+            .iter()
+            .collect();
+        // Entries carry their input position; not every server keeps them in order
+        data.sort_by_key(|entry| entry.get("index").and_then(Value::as_u64));
+        data.iter()
+            .map(|entry| {
+                entry
+                    .get("embedding")
+                    .and_then(Value::as_array)
+                    .map(|values| {
+                        values
+                            .iter()
+                            .filter_map(Value::as_f64)
+                            .map(|v| v as f32)
+                            .collect()
+                    })
+                    .ok_or_else(|| "Embeddings response entry has no 'embedding'".to_string())
+            })
+            .collect()
+    }
+}
+
+impl Embedder for HttpEmbedder {
+    fn id(&self) -> String {
+        format!("http:{}:{}", self.url, self.model.as_deref().unwrap_or(""))
+    }
+
+    fn embed(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>, String> {
+        let mut vectors = Vec::with_capacity(texts.len());
+        for batch in texts.chunks(BATCH_SIZE) {
+            vectors.extend(self.request(batch)?);
+        }
+        Ok(vectors)
+    }
+}
+
+fn words(text: &str) -> impl Iterator<Item = &str> {
+    text.split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|w| w.len() > 1)
+}
+
+fn is_stopword(word: &str) -> bool {
+    const STOPWORDS: &[&str] = &[
+        "a", "an", "and", "are", "as", "at", "be", "but", "by", "can", "do", "does", "for", "from",
+        "how", "i", "if", "in", "into", "is", "it", "its", "my", "of", "on", "or", "so", "that",
+        "the", "then", "this", "to", "use", "what", "when", "why", "with", "you", "your",
+    ];
+    STOPWORDS.contains(&word)
+}
+
+/// Crude suffix stripping, so `signals`/`signal` and `loading`/`load` share a word
+fn stem(word: &str) -> &str {
+    for suffix in ["ing", "ed", "es", "s"] {
+        if let Some(stem) = word.strip_suffix(suffix) {
+            if stem.len() >= 3 {
+                return stem;
+            }
+        }
+    }
+    word
+}
+
+/// 64-bit FNV-1a, stable across builds (unlike `DefaultHasher`) for cache keys
+struct Fnv(u64);
+
+impl Default for Fnv {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Fnv {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
@@ -0,0 +1,123 @@
+//! Snippet library
+//!
+//! Small, complete examples of things apps keep needing, one file each under
+//! `snippets/`, tagged by topic. Where the docs explain a concept across a
+//! section, a snippet is the one canonical working version of a pattern.
+//! Snippets target the latest supported Leptos version.
+
+use crate::docs::similarity;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+/// A self-contained example
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct Snippet {
+    /// Identifier, the file name under `snippets/` without `.rs`
+    pub name: &'static str,
+    pub title: &'static str,
+    pub description: &'static str,
+    pub tags: &'static [&'static str],
+    /// Crates the snippet uses beyond `leptos` itself
+    pub dependencies: &'static [&'static str],
+    #[serde(skip)]
+    pub code: &'static str,
+}
+
+/// Every snippet, in listing order
+pub const SNIPPETS: &[Snippet] = &[
+    Snippet {
+        name: "debounced-input",
+        title: "Debounced search input",
+        description: "Search box that queries a server function once typing pauses",
+        tags: &["input", "debounce", "search", "resource", "server-fn"],
+        dependencies: &["leptos-use"],
+        code: include_str!("../snippets/debounced-input.rs"),
+    },
+    Snippet {
+        name: "infinite-scroll",
+        title: "Infinite scroll",
+        description: "Feed that loads the next page from a server function near the bottom",
+        tags: &["scroll", "pagination", "list", "server-fn"],
+        dependencies: &["leptos-use"],
+        code: include_str!("../snippets/infinite-scroll.rs"),
+    },
+    Snippet {
+        name: "auth-guard-route",
+        title: "Auth-guarded route",
+        description: "Login form and a dashboard route that redirects until the user is logged in",
+        tags: &["auth", "router", "redirect", "login", "server-fn"],
+        dependencies: &["leptos_router", "serde"],
+        code: include_str!("../snippets/auth-guard-route.rs"),
+    },
+    Snippet {
+        name: "file-upload",
+        title: "File upload via server function",
+        description: "Form that streams files to a multipart server function",
+        tags: &["upload", "file", "form", "multipart", "server-fn"],
+        dependencies: &[],
+        code: include_str!("../snippets/file-upload.rs"),
+    },
+    Snippet {
+        name: "websocket-server-signals",
+        title: "WebSocket into a signal",
+        description: "Websocket server function whose messages update a signal as they arrive",
+        tags: &["websocket", "realtime", "stream", "signal", "server-fn"],
+        dependencies: &["futures", "tokio"],
+        code: include_str!("../snippets/websocket-server-signals.rs"),
+    },
+];
+
+/// Similarity at which a name is taken to mean a snippet
+const MATCH_THRESHOLD: f64 = 0.7;
+
+/// Snippet by name, tolerating typos
+pub fn get(name: &str) -> Option<&'static Snippet> {
+    let name = name.trim().to_lowercase();
+    SNIPPETS.iter().find(|s| s.name == name).or_else(|| {
+        suggest(&name, 1)
+            .into_iter()
+            .find(|s| similarity(&name, s.name) >= MATCH_THRESHOLD)
+    })
+}
+
+/// Snippets with `tag`, or all of them
+pub fn list(tag: Option<&str>) -> Vec<&'static Snippet> {
+    let tag = tag.map(|t| t.trim().to_lowercase());
+    SNIPPETS
+        .iter()
+        .filter(|s| {
+            tag.as_ref()
+                .is_none_or(|tag| s.tags.contains(&tag.as_str()))
+        })
+        .collect()
+}
+
+/// Every tag in use, sorted
+pub fn tags() -> Vec<&'static str> {
+    let mut tags: Vec<&str> = SNIPPETS
+        .iter()
+        .flat_map(|s| s.tags.iter().copied())
+        .collect();
+    tags.sort_unstable();
+    tags.dedup();
+    tags
+}
+
+/// Snippets closest to `query` by name or tag, best first
+pub fn suggest(query: &str, limit: usize) -> Vec<&'static Snippet> {
+    let query = query.trim().to_lowercase();
+    let mut scored: Vec<(&Snippet, f64)> = SNIPPETS
+        .iter()
+        .map(|s| {
+            let score = s
+                .tags
+                .iter()
+                .map(|tag| similarity(&query, tag))
+                .fold(similarity(&query, s.name), f64::max);
+            (s, score)
+        })
+        .filter(|(_, score)| *score > 0.3)
+        .collect();
+    scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+    scored.into_iter().take(limit).map(|(s, _)| s).collect()
+}
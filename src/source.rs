@@ -0,0 +1,448 @@
+//! Rust source helpers
+//!
+//! Just enough structure recovery for lint rules: function items with their
+//! attributes and body ranges, call-argument ranges, and closure bodies.
+//! Everything works on byte offsets into the original source.
+
+use crate::view::{skip_balanced, skip_char, skip_string};
+use std::ops::Range;
+
+/// A function item found in source
+#[derive(Debug, Clone)]
+pub struct FnItem {
+    pub name: String,
+    /// Attributes directly above the function, e.g. `#[component]`
+    pub attrs: Vec<String>,
+    /// Byte range of the parameter list between the parentheses
+    pub params: Range<usize>,
+    /// Byte range between the parameters and the body: `-> T where ...`
+    pub returns: Range<usize>,
+    /// Byte range of the body including braces
+    pub body: Range<usize>,
+}
+
+impl FnItem {
+    pub fn has_attr(&self, name: &str) -> bool {
+        self.attrs.iter().any(|a| {
+            a.strip_prefix("#[")
+                .is_some_and(|rest| rest.starts_with(name))
+        })
+    }
+
+    /// `#[component]` or `#[island]` functions
+    pub fn is_component(&self) -> bool {
+        self.has_attr("component") || self.has_attr("island")
+    }
+}
+
+/// All function items with a body, in source order
+pub fn functions(code: &str) -> Vec<FnItem> {
+    let bytes = code.as_bytes();
+    let mut items = Vec::new();
+    for (offset, _) in code.match_indices("fn ") {
+        if offset > 0 && (bytes[offset - 1].is_ascii_alphanumeric() || bytes[offset - 1] == b'_') {
+            continue;
+        }
+        let name: String = code[offset + 3..]
+            .trim_start()
+            .chars()
+            .take_while(|c| c.is_alphanumeric() || *c == '_')
+            .collect();
+        if !name.starts_with(|c: char| c.is_alphabetic() || c == '_') {
+            continue;
+        }
+        let Some(paren) = code[offset..].find('(').map(|p| offset + p) else {
+            continue;
+        };
+        let Some(params_end) = skip_balanced(bytes, paren, b'(', b')') else {
+            continue;
+        };
+        // The body is the first top-level `{` after the parameters; a `;` first means no body
+        let rest = &code[params_end..];
+        let Some(brace) = rest.find(['{', ';']).map(|p| params_end + p) else {
+            continue;
+        };
+        if bytes[brace] == b';' {
+            continue;
+        }
+        let Some(body_end) = skip_balanced(bytes, brace, b'{', b'}') else {
+            continue;
+        };
+        items.push(FnItem {
+            name,
+            attrs: attributes_before(code, offset),
+            params: paren + 1..params_end - 1,
+            returns: params_end..brace,
+            body: brace..body_end,
+        });
+    }
+    items
+}
+
+/// Attribute lines directly above the line containing `offset`, skipping doc comments
+pub fn attributes_before(code: &str, offset: usize) -> Vec<String> {
+    let line_start = code[..offset].rfind('\n').map_or(0, |i| i + 1);
+    code[..line_start]
+        .lines()
+        .rev()
+        .map(str::trim)
+        .take_while(|l| l.starts_with("#[") || l.starts_with("///") || l.is_empty())
+        .filter(|l| l.starts_with("#["))
+        .map(String::from)
+        .collect()
+}
+
+/// Byte ranges of the parenthesized arguments of every call to one of `callees`
+pub fn call_ranges(code: &str, callees: &[&str]) -> Vec<Range<usize>> {
+    let bytes = code.as_bytes();
+    let mut ranges = Vec::new();
+    for callee in callees {
+        for (offset, _) in code.match_indices(callee) {
+            if offset > 0
+                && (bytes[offset - 1].is_ascii_alphanumeric() || bytes[offset - 1] == b'_')
+            {
+                continue;
+            }
+            let after = offset + callee.len();
+            let Some(paren) = code[after..]
+                .find(|c: char| !c.is_whitespace())
+                .map(|p| after + p)
+                .filter(|p| bytes[*p] == b'(')
+            else {
+                continue;
+            };
+            if let Some(end) = skip_balanced(bytes, paren, b'(', b')') {
+                ranges.push(paren..end);
+            }
+        }
+    }
+    ranges
+}
+
+/// A closure expression
+#[derive(Debug, Clone)]
+pub struct Closure {
+    /// Offset of `move`, or of the opening `|` for borrowing closures
+    pub start: usize,
+    pub is_move: bool,
+    /// Parameter list between the bars
+    pub params: String,
+    /// Byte range of the body expression
+    pub body: Range<usize>,
+}
+
+impl Closure {
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.body.end
+    }
+
+    /// Byte range of the parameter list between the bars
+    pub fn params_range(&self) -> Range<usize> {
+        self.body.start - 1 - self.params.len()..self.body.start - 1
+    }
+
+    /// Whether the closure declares any parameters
+    pub fn has_params(&self) -> bool {
+        !self.params.trim().is_empty()
+    }
+}
+
+/// All closure expressions in source order
+pub fn closures(code: &str) -> Vec<Closure> {
+    let masked = mask_literals(code);
+    let bytes = masked.as_bytes();
+    let mut found = Vec::new();
+    let mut search = 0;
+    while let Some(rel) = masked[search..].find('|') {
+        let bar = search + rel;
+        search = bar + 1;
+        // Closure params start after `move`, `=`, `(`, `,` or `{`; anything else is an `|`/`||` operator
+        let before = masked[..bar].trim_end();
+        let is_move = before.ends_with("move")
+            && !before[..before.len() - 4].ends_with(|c: char| c.is_alphanumeric() || c == '_');
+        let opens_closure = is_move || before.ends_with(['=', '(', ',', '{']);
+        if !opens_closure {
+            continue;
+        }
+        let params_end = if bytes.get(bar + 1) == Some(&b'|') {
+            bar + 1
+        } else {
+            match masked[bar + 1..].find(['|', '\n']) {
+                Some(len) if bytes[bar + 1 + len] == b'|' => bar + 1 + len,
+                _ => continue,
+            }
+        };
+        let body_start = params_end + 1;
+        let end = expression_end(&masked, body_start);
+        found.push(Closure {
+            start: if is_move { before.len() - 4 } else { bar },
+            is_move,
+            params: masked[(bar + 1).min(params_end)..params_end].to_string(),
+            body: body_start..end,
+        });
+        search = body_start;
+    }
+    found
+}
+
+/// End of the expression starting at `from`: a balanced block, or up to the
+/// first top-level `,`, `;`, line break or unmatched closing bracket
+fn expression_end(masked: &str, from: usize) -> usize {
+    let bytes = masked.as_bytes();
+    let first = masked[from..]
+        .find(|c: char| !c.is_whitespace())
+        .map_or(masked.len(), |p| from + p);
+    if bytes.get(first) == Some(&b'{') {
+        return skip_balanced(bytes, first, b'{', b'}').unwrap_or(masked.len());
+    }
+    let mut depth = 0usize;
+    for (i, &b) in bytes.iter().enumerate().skip(first) {
+        match b {
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' if depth == 0 => return i,
+            b')' | b']' | b'}' => depth -= 1,
+            b',' | b';' | b'\n' if depth == 0 => return i,
+            _ => {}
+        }
+    }
+    masked.len()
+}
+
+/// Byte ranges of closures that take at least one argument (`move |ev| ...`)
+///
+/// Such closures are event handlers or callbacks, which only run in response
+/// to something happening on the client rather than during render.
+pub fn callback_closures(code: &str) -> Vec<Range<usize>> {
+    closures(code)
+        .into_iter()
+        .filter(Closure::has_params)
+        .map(|c| c.range())
+        .collect()
+}
+
+/// `code` with the contents of string/char literals and comments blanked out
+///
+/// Offsets are preserved, so positions found in the result index into `code`.
+pub fn mask_literals(code: &str) -> String {
+    let mut out = code.as_bytes().to_vec();
+    for (range, _) in literals(code) {
+        for b in &mut out[range] {
+            if *b != b'\n' {
+                *b = b' ';
+            }
+        }
+    }
+    String::from_utf8(out).unwrap_or_else(|_| code.to_string())
+}
+
+/// Contents of the string literals in `code`, between their quotes
+pub fn string_contents(code: &str) -> Vec<Range<usize>> {
+    literals(code)
+        .into_iter()
+        .filter(|(_, string)| *string)
+        .map(|(range, _)| range)
+        .collect()
+}
+
+/// Contents of the literals and comments in `code`, each with whether it is
+/// a string literal
+fn literals(code: &str) -> Vec<(Range<usize>, bool)> {
+    let bytes = code.as_bytes();
+    let mut found = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' => {
+                let end = skip_string(bytes, i);
+                found.push((i + 1..end.saturating_sub(1).max(i + 1), true));
+                i = end;
+            }
+            b'r' if raw_string_start(bytes, i) => {
+                let hashes = bytes[i + 1..].iter().take_while(|b| **b == b'#').count();
+                let open = i + 1 + hashes;
+                let closing = format!("\"{}", "#".repeat(hashes));
+                let end = code[open + 1..]
+                    .find(&closing)
+                    .map_or(bytes.len(), |p| open + 1 + p);
+                found.push((open + 1..end, true));
+                i = (end + closing.len()).min(bytes.len());
+            }
+            b'\'' => {
+                let end = skip_char(bytes, i);
+                if end > i + 2 {
+                    found.push((i + 1..end - 1, false));
+                }
+                i = end;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                let end = code[i..].find('\n').map_or(bytes.len(), |p| i + p);
+                found.push((i..end, false));
+                i = end;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                let end = code[i + 2..]
+                    .find("*/")
+                    .map_or(bytes.len(), |p| i + 2 + p + 2);
+                found.push((i..end, false));
+                i = end;
+            }
+            _ => i += 1,
+        }
+    }
+    found
+}
+
+/// `r"`, `r#"`… not part of a longer identifier
+fn raw_string_start(bytes: &[u8], pos: usize) -> bool {
+    if pos > 0 && (bytes[pos - 1].is_ascii_alphanumeric() || bytes[pos - 1] == b'_') {
+        return false;
+    }
+    let hashes = bytes[pos + 1..].iter().take_while(|b| **b == b'#').count();
+    bytes.get(pos + 1 + hashes) == Some(&b'"')
+}
+
+/// Offsets of `name` as a whole identifier within `range` of already-masked code
+///
+/// Field accesses (`x.name`), paths (`a::name`) and labels (`name: value`,
+/// `name=` attribute keys) aren't uses of a variable and are skipped.
+pub fn identifier_uses(masked: &str, range: Range<usize>, name: &str) -> Vec<usize> {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    masked[range.clone()]
+        .match_indices(name)
+        .map(|(o, _)| range.start + o)
+        .filter(|&o| {
+            let before = masked[..o].chars().next_back();
+            let after = &masked[o + name.len()..];
+            let after_trimmed = after.trim_start();
+            let qualified = before.is_some_and(|c| is_ident(c) || c == '.' || c == ':');
+            let attr_key = after.starts_with('=') && !after.starts_with("==");
+            let label = after_trimmed.starts_with(':') && !after_trimmed.starts_with("::");
+            !(qualified || after.starts_with(is_ident) || attr_key || label)
+        })
+        .collect()
+}
+
+/// A `let` binding of a single name
+#[derive(Debug, Clone)]
+pub struct Binding {
+    pub name: String,
+    /// Byte range of the initializer expression
+    pub init: Range<usize>,
+    /// Where the name becomes visible (after the `;`) until its block closes
+    pub scope: Range<usize>,
+}
+
+/// Single-name `let` bindings within `range` of already-masked code
+///
+/// Destructuring patterns are skipped.
+pub fn let_bindings(masked: &str, range: Range<usize>) -> Vec<Binding> {
+    let bytes = masked.as_bytes();
+    let mut bindings = Vec::new();
+    for (rel, _) in masked[range.clone()].match_indices("let ") {
+        let offset = range.start + rel;
+        if offset > 0 && (bytes[offset - 1].is_ascii_alphanumeric() || bytes[offset - 1] == b'_') {
+            continue;
+        }
+        let pattern = masked[offset + 4..].trim_start();
+        let pattern = pattern.strip_prefix("mut ").unwrap_or(pattern).trim_start();
+        let name: String = pattern
+            .chars()
+            .take_while(|c| c.is_alphanumeric() || *c == '_')
+            .collect();
+        if name.is_empty() || name == "_" {
+            continue;
+        }
+        let after_name = pattern[name.len()..].trim_start();
+        if !after_name.starts_with([':', '=']) || after_name.starts_with("==") {
+            continue;
+        }
+        let Some(eq) = masked[offset..range.end].find('=').map(|p| offset + p + 1) else {
+            continue;
+        };
+        let Some(semi) = statement_end(masked, eq, range.end) else {
+            continue;
+        };
+        bindings.push(Binding {
+            name,
+            init: eq..semi,
+            scope: semi..block_end(masked, semi, range.end),
+        });
+    }
+    bindings
+}
+
+/// The top-level `;` ending the statement that continues at `from`
+pub fn statement_end(masked: &str, from: usize, limit: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, b) in masked.bytes().enumerate().take(limit).skip(from) {
+        match b {
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' if depth == 0 => return None,
+            b')' | b']' | b'}' => depth -= 1,
+            b';' if depth == 0 => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Offset of the `}` closing the block that `from` is in
+fn block_end(masked: &str, from: usize, limit: usize) -> usize {
+    let mut depth = 0usize;
+    for (i, b) in masked.bytes().enumerate().take(limit).skip(from) {
+        match b {
+            b'{' => depth += 1,
+            b'}' if depth == 0 => return i,
+            b'}' => depth -= 1,
+            _ => {}
+        }
+    }
+    limit
+}
+
+/// `name: Type` pairs of a parameter list, without `#[prop(...)]` attributes
+///
+/// Patterns other than a plain (optionally `mut`) name are skipped.
+pub fn typed_params(params: &str) -> Vec<(String, String)> {
+    split_top_level(params)
+        .into_iter()
+        .filter_map(|param| {
+            let mut param = param.trim();
+            while param.starts_with("#[") {
+                let close = param.find(']')?;
+                param = param[close + 1..].trim_start();
+            }
+            let (pattern, ty) = param.split_once(':')?;
+            let name = pattern.trim().trim_start_matches("mut ").trim();
+            let simple = !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+            simple.then(|| (name.to_string(), ty.trim().to_string()))
+        })
+        .collect()
+}
+
+/// Split on commas that aren't nested in brackets or generics
+pub fn split_top_level(list: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut start = 0;
+    for (i, c) in list.char_indices() {
+        match c {
+            '(' | '[' | '{' | '<' => depth += 1,
+            // `->` in `impl Fn() -> T` isn't a closing bracket
+            '>' if list[..i].ends_with('-') => {}
+            ')' | ']' | '}' | '>' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(&list[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&list[start..]);
+    parts
+}
+
+/// Whether `offset` falls inside any of `ranges`
+pub fn within(ranges: &[Range<usize>], offset: usize) -> bool {
+    ranges.iter().any(|r| r.contains(&offset))
+}
@@ -0,0 +1,446 @@
+//! State inventory
+//!
+//! Lists the reactive state a project declares: signals, memos, resources,
+//! actions, stores and stored values bound with `let`, and those created
+//! inline in a `provide_context` call. For each one it follows where the
+//! value goes: passed by name as a prop (`<Counter count=count/>`) it is
+//! tracked under the prop's name in that component, and put into context it
+//! is tracked wherever `use_context`/`expect_context` asks for its type. The
+//! functions that read or write it along the way are reported.
+
+use crate::project::SourceFile;
+use crate::reactivity::{accesses, sources, Access, NodeKind, ReactiveNode};
+use crate::rules::{annotated_type, calls, expression_type, normalize_type, position, same_type};
+use crate::source::{functions, let_bindings, mask_literals, within, FnItem};
+use crate::view::{parse_views, Node};
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::ops::Range;
+
+/// Constructors of state `reactivity` doesn't track, with their kind
+const OTHER_CONSTRUCTORS: &[(&str, StateKind)] = &[
+    ("Action::new", StateKind::Action),
+    ("Action::new_local", StateKind::Action),
+    ("ServerAction::", StateKind::Action),
+    ("MultiAction::new", StateKind::Action),
+    ("ServerMultiAction::", StateKind::Action),
+    ("create_action(", StateKind::Action),
+    ("create_server_action", StateKind::Action),
+    ("create_multi_action(", StateKind::Action),
+    ("create_server_multi_action", StateKind::Action),
+    ("Store::new", StateKind::Store),
+    ("ArcStore::new", StateKind::Store),
+    ("StoredValue::new", StateKind::StoredValue),
+    ("store_value(", StateKind::StoredValue),
+    // Inline in `provide_context`, where `reactivity` doesn't look
+    ("RwSignal::new", StateKind::Signal),
+    ("ArcRwSignal::new", StateKind::Signal),
+    ("create_rw_signal(", StateKind::Signal),
+    ("Memo::new", StateKind::Memo),
+    ("Resource::new", StateKind::Resource),
+    ("LocalResource::new", StateKind::Resource),
+];
+
+/// Calls reading a value from context
+const CONTEXT_GETTERS: &[&str] = &["use_context", "expect_context"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StateKind {
+    Signal,
+    Memo,
+    Resource,
+    Action,
+    Store,
+    StoredValue,
+}
+
+impl StateKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Signal => "signal",
+            Self::Memo => "memo",
+            Self::Resource => "resource",
+            Self::Action => "action",
+            Self::Store => "store",
+            Self::StoredValue => "stored value",
+        }
+    }
+}
+
+/// One piece of state and who uses it
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct State {
+    /// Binding name, the getter for `(getter, setter)` pairs; the
+    /// constructor for values created inside `provide_context`
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub setter: Option<String>,
+    pub kind: StateKind,
+    /// Function it is created in
+    pub function: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub file: String,
+    pub line: usize,
+    /// Types it is provided as context under, e.g. `RwSignal<_>`
+    pub context: Vec<String>,
+    /// Functions reading it, tracked or not
+    pub readers: Vec<String>,
+    /// Functions setting or updating it, or dispatching an action
+    pub writers: Vec<String>,
+}
+
+/// A function of the project
+struct Func<'a> {
+    file: usize,
+    item: FnItem,
+    code: &'a str,
+    masked: &'a str,
+}
+
+/// Names a value goes by in one function
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct Handle {
+    func: usize,
+    getter: Option<String>,
+    setter: Option<String>,
+    /// The statement declaring the names, whose uses don't count
+    declared: Range<usize>,
+}
+
+/// Every piece of state declared in `files`, in file and source order
+pub fn inventory(files: &[SourceFile]) -> Vec<State> {
+    let masked: Vec<String> = files.iter().map(|f| mask_literals(&f.content)).collect();
+    let funcs: Vec<Func> = files
+        .iter()
+        .enumerate()
+        .flat_map(|(index, file)| {
+            let masked = masked[index].as_str();
+            functions(&file.content).into_iter().map(move |item| Func {
+                file: index,
+                item,
+                code: &file.content,
+                masked,
+            })
+        })
+        .collect();
+    // Context consumers: function, binding name and requested type
+    let consumers: Vec<(usize, String, String)> = funcs
+        .iter()
+        .enumerate()
+        .flat_map(|(index, func)| {
+            context_bindings(func)
+                .into_iter()
+                .map(move |(name, ty)| (index, name, ty))
+        })
+        .collect();
+
+    let mut states = Vec::new();
+    for (index, func) in funcs.iter().enumerate() {
+        for (mut state, handle) in declared(index, func, &funcs) {
+            state.file = files[func.file].path.display().to_string();
+            let mut start = vec![handle];
+            for ty in &state.context {
+                start.extend(
+                    consumers
+                        .iter()
+                        .filter(|(_, _, wanted)| same_type(wanted, ty))
+                        .map(|(func, name, _)| Handle {
+                            func: *func,
+                            getter: Some(name.clone()),
+                            setter: None,
+                            declared: 0..0,
+                        }),
+                );
+            }
+            let (readers, writers) = follow(start, &funcs, state.kind);
+            state.readers = readers;
+            state.writers = writers;
+            states.push(state);
+        }
+    }
+    states
+}
+
+/// State created directly in `func`, not in a function nested in it
+fn declared(index: usize, func: &Func, funcs: &[Func]) -> Vec<(State, Handle)> {
+    let (code, masked) = (func.code, func.masked);
+    let body = func.item.body.clone();
+    let own = |offset: usize| {
+        !funcs.iter().any(|other| {
+            other.file == func.file
+                && other.item.body.start > body.start
+                && other.item.body.end <= body.end
+                && other.item.body.contains(&offset)
+        })
+    };
+    let state = |name: String, setter: Option<String>, kind, offset| State {
+        name,
+        setter,
+        kind,
+        function: func.item.name.clone(),
+        file: String::new(),
+        line: position(code, offset).0,
+        context: Vec::new(),
+        readers: Vec::new(),
+        writers: Vec::new(),
+    };
+
+    let mut found: Vec<(State, Range<usize>)> = Vec::new();
+    for node in sources(code, masked, body.clone()) {
+        let kind = match node.kind {
+            NodeKind::Signal => StateKind::Signal,
+            NodeKind::Memo => StateKind::Memo,
+            NodeKind::Resource => StateKind::Resource,
+            _ => continue,
+        };
+        if own(node.definition.start) {
+            let definition = node.definition.clone();
+            // `let (count, _) = signal(0)` has no setter to follow
+            let setter = node.setter.filter(|s| !s.starts_with('_'));
+            found.push((state(node.name, setter, kind, definition.start), definition));
+        }
+    }
+    for binding in let_bindings(masked, body.clone()) {
+        let init = masked[binding.init.clone()].trim_start();
+        let Some(kind) = constructor_kind(init) else {
+            continue;
+        };
+        let taken = found.iter().any(|(_, d)| d.contains(&binding.init.start));
+        if !taken && own(binding.init.start) {
+            let start = masked[..binding.init.start]
+                .rfind("let")
+                .unwrap_or(binding.init.start);
+            found.push((
+                state(binding.name, None, kind, start),
+                start..binding.init.end,
+            ));
+        }
+    }
+
+    // Values put into context, by name or created in the call
+    for (offset, turbofish, args) in calls(masked, "provide_context") {
+        if !body.contains(&offset) || !own(offset) {
+            continue;
+        }
+        let arg = masked[args.clone()].trim();
+        let arg = arg.strip_suffix(".clone()").unwrap_or(arg);
+        let named = found.iter_mut().find_map(|(state, definition)| {
+            let ty = if state.name == arg {
+                match (&state.setter, state.kind) {
+                    (Some(_), _) => "ReadSignal<_>".to_string(),
+                    _ => {
+                        let eq = definition.start + masked[definition.clone()].find('=')?;
+                        annotated_type(masked, eq)
+                            .or_else(|| expression_type(masked[eq + 1..definition.end].trim()))?
+                    }
+                }
+            } else if state.setter.as_deref() == Some(arg) {
+                "WriteSignal<_>".to_string()
+            } else {
+                return None;
+            };
+            Some((state, ty))
+        });
+        match named {
+            Some((state, ty)) => {
+                let ty = normalize_type(turbofish.as_deref().unwrap_or(&ty));
+                if !state.context.contains(&ty) {
+                    state.context.push(ty);
+                }
+            }
+            None => {
+                let (Some(kind), Some(ty)) = (constructor_kind(arg), expression_type(arg)) else {
+                    continue;
+                };
+                let name: String = arg.chars().take_while(|c| *c != '(').collect();
+                let mut inline = state(name.trim().to_string(), None, kind, args.start);
+                inline
+                    .context
+                    .push(normalize_type(turbofish.as_deref().unwrap_or(&ty)));
+                found.push((inline, args));
+            }
+        }
+    }
+
+    found.sort_by_key(|(_, definition)| definition.start);
+    found
+        .into_iter()
+        .map(|(state, declared)| {
+            let handle = Handle {
+                func: index,
+                getter: (!state.name.contains("::")).then(|| state.name.clone()),
+                setter: state.setter.clone(),
+                declared,
+            };
+            (state, handle)
+        })
+        .collect()
+}
+
+fn constructor_kind(init: &str) -> Option<StateKind> {
+    OTHER_CONSTRUCTORS
+        .iter()
+        .find(|(prefix, _)| init.starts_with(prefix))
+        .map(|(_, kind)| *kind)
+}
+
+/// `let` bindings of `use_context`/`expect_context` calls with the type asked for
+fn context_bindings(func: &Func) -> Vec<(String, String)> {
+    let masked = func.masked;
+    let_bindings(masked, func.item.body.clone())
+        .into_iter()
+        .filter_map(|binding| {
+            let init = masked[binding.init.clone()].trim_start();
+            let getter = CONTEXT_GETTERS.iter().find(|g| init.starts_with(*g))?;
+            let rest = &init[getter.len()..];
+            let ty = match rest.strip_prefix("::<") {
+                Some(rest) => {
+                    let mut depth = 1;
+                    let end = rest.find(|c: char| {
+                        match c {
+                            '<' => depth += 1,
+                            '>' => depth -= 1,
+                            _ => {}
+                        }
+                        depth == 0
+                    })?;
+                    rest[..end].to_string()
+                }
+                None => {
+                    let ty = annotated_type(masked, binding.init.start - 1)?;
+                    // `Option<T>` from `use_context`
+                    match ty.strip_prefix("Option<") {
+                        Some(inner) => inner.strip_suffix('>')?.to_string(),
+                        None => ty,
+                    }
+                }
+            };
+            Some((binding.name, normalize_type(&ty)))
+        })
+        .collect()
+}
+
+/// Functions reading and writing the value behind `start`, following it into
+/// the components it is passed to as a prop
+fn follow(start: Vec<Handle>, funcs: &[Func], kind: StateKind) -> (Vec<String>, Vec<String>) {
+    let mut readers = Vec::new();
+    let mut writers = Vec::new();
+    let mut seen = HashSet::new();
+    let mut queue = start;
+    while let Some(handle) = queue.pop() {
+        if !seen.insert(handle.clone()) {
+            continue;
+        }
+        let func = &funcs[handle.func];
+        let (code, masked) = (func.code, func.masked);
+        let body = func.item.body.clone();
+
+        // Declaring a handle, or passing it on as a prop or into context, isn't a use of it
+        let mut passes: Vec<Range<usize>> = vec![handle.declared.clone()];
+        passes.extend(
+            calls(masked, "provide_context")
+                .into_iter()
+                .map(|(_, _, args)| args),
+        );
+        for view in parse_views(code)
+            .into_iter()
+            .filter(|v| body.contains(&v.start))
+        {
+            for node in &view.nodes {
+                let Node::Open(element) = node else { continue };
+                if !element.is_component() {
+                    continue;
+                }
+                let child = element.name.rsplit("::").next().unwrap_or(&element.name);
+                let target = funcs
+                    .iter()
+                    .position(|f| f.item.is_component() && f.item.name == child);
+                for attr in &element.attrs {
+                    let Some(expr) = attr.expr() else { continue };
+                    let expr = expr.strip_suffix(".clone()").unwrap_or(expr);
+                    let getter = handle.getter.as_deref() == Some(expr);
+                    let setter = handle.setter.as_deref() == Some(expr);
+                    if !getter && !setter {
+                        continue;
+                    }
+                    let end = attr.value.as_deref().and_then(|value| {
+                        code[attr.offset..]
+                            .find(value)
+                            .map(|p| attr.offset + p + value.len())
+                    });
+                    passes.push(attr.offset..end.unwrap_or(attr.offset));
+                    if let Some(target) = target {
+                        queue.push(Handle {
+                            func: target,
+                            getter: getter.then(|| attr.name.clone()),
+                            setter: setter.then(|| attr.name.clone()),
+                            declared: 0..0,
+                        });
+                    }
+                }
+            }
+        }
+
+        let mut reads = false;
+        let mut writes = false;
+        if let Some(getter) = &handle.getter {
+            let node = ReactiveNode {
+                name: getter.clone(),
+                kind: NodeKind::Signal,
+                line: 0,
+                setter: None,
+                definition: 0..0,
+                body: body.clone(),
+            };
+            for (offset, access) in accesses(masked, body.clone(), &node) {
+                if within(&passes, offset) || is_binding(masked, offset) {
+                    continue;
+                }
+                match access {
+                    Access::Write => writes = true,
+                    _ if kind == StateKind::Action && dispatches(masked, offset, getter) => {
+                        writes = true
+                    }
+                    _ => reads = true,
+                }
+            }
+        }
+        if let Some(setter) = &handle.setter {
+            let node = ReactiveNode {
+                name: setter.clone(),
+                kind: NodeKind::Signal,
+                line: 0,
+                setter: Some(setter.clone()),
+                definition: 0..0,
+                body: body.clone(),
+            };
+            writes |= accesses(masked, body.clone(), &node)
+                .into_iter()
+                .any(|(offset, _)| !within(&passes, offset) && !is_binding(masked, offset));
+        }
+        let name = &func.item.name;
+        if reads && !readers.contains(name) {
+            readers.push(name.clone());
+        }
+        if writes && !writers.contains(name) {
+            writers.push(name.clone());
+        }
+    }
+    (readers, writers)
+}
+
+/// Whether the name at `offset` is bound there by a `let`, e.g. a context consumer's
+fn is_binding(masked: &str, offset: usize) -> bool {
+    let before = masked[..offset].trim_end();
+    before.ends_with("let") || before.ends_with("mut")
+}
+
+/// `action.dispatch(...)`
+fn dispatches(masked: &str, offset: usize, name: &str) -> bool {
+    masked[offset + name.len()..]
+        .trim_start()
+        .strip_prefix('.')
+        .is_some_and(|rest| rest.trim_start().starts_with("dispatch"))
+}
@@ -0,0 +1,229 @@
+//! Leptos book sync
+//!
+//! `sync-docs` downloads the pages of the Leptos book that correspond to each
+//! documentation section and merges them over the embedded content. Pages are
+//! cached under `~/.cache/leptos-mcp/` and revalidated with their ETag, so an
+//! unchanged page isn't downloaded again; a page that can't be fetched falls
+//! back to its cached copy. Downloads go through `curl`.
+
+use crate::docs;
+use crate::progress::Progress;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Overrides the base URL book pages are fetched from
+pub const BOOK_URL_ENV: &str = "LEPTOS_MCP_BOOK_URL";
+
+const DEFAULT_BOOK_URL: &str = "https://raw.githubusercontent.com/leptos-rs/book/main/src";
+
+/// Time limit for one page download
+const TIMEOUT_SECS: &str = "30";
+
+/// Book pages that make up each section, in order
+const BOOK_PAGES: &[(&str, &[&str])] = &[
+    ("getting-started", &["getting_started/README.md"]),
+    (
+        "components",
+        &["view/03_components.md", "view/09_component_children.md"],
+    ),
+    ("signals", &["reactivity/working_with_signals.md"]),
+    (
+        "views",
+        &[
+            "view/01_basic_component.md",
+            "view/02_dynamic_attributes.md",
+            "view/06_control_flow.md",
+        ],
+    ),
+    ("resources", &["async/10_resources.md"]),
+    (
+        "actions",
+        &[
+            "async/13_actions.md",
+            "progressive_enhancement/action_form.md",
+        ],
+    ),
+    (
+        "server-functions",
+        &["server/25_server_functions.md", "server/26_extractors.md"],
+    ),
+    (
+        "routing",
+        &[
+            "router/16_routes.md",
+            "router/17_nested_routing.md",
+            "router/18_params_and_queries.md",
+        ],
+    ),
+    ("forms", &["view/05_forms.md"]),
+    ("error-handling", &["view/07_errors.md"]),
+    (
+        "suspense",
+        &["async/11_suspense.md", "async/12_transition.md"],
+    ),
+    ("hydration", &["ssr/24_hydration_bugs.md"]),
+];
+
+/// Outcome of a sync
+#[derive(Debug, Default)]
+pub struct SyncReport {
+    /// Sections now served from the book
+    pub sections: Vec<&'static str>,
+    /// Pages downloaded because they were new or changed
+    pub updated: usize,
+    /// Pages whose cached copy was still current
+    pub unchanged: usize,
+    /// Pages that couldn't be fetched, with the reason; cached copies are used if present
+    pub failed: Vec<(&'static str, String)>,
+}
+
+/// `name` under `~/.cache/leptos-mcp/` (`$XDG_CACHE_HOME` if set)
+pub(crate) fn cache_dir(name: &str) -> PathBuf {
+    let base = std::env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+        .unwrap_or_else(std::env::temp_dir);
+    base.join("leptos-mcp").join(name)
+}
+
+/// Fetch the book and merge it over the embedded sections
+///
+/// With `force`, cached ETags are ignored and every page is downloaded again.
+pub fn sync(force: bool, progress: &Progress) -> Result<SyncReport, String> {
+    let base_url = std::env::var(BOOK_URL_ENV).unwrap_or_else(|_| DEFAULT_BOOK_URL.to_string());
+    let base_url = base_url.trim_end_matches('/');
+    let cache = cache_dir("book");
+    let total = BOOK_PAGES
+        .iter()
+        .map(|(_, pages)| pages.len())
+        .sum::<usize>() as u64;
+
+    let mut report = SyncReport::default();
+    let mut book: HashMap<&str, String> = HashMap::new();
+    let mut done = 0;
+    for (section, pages) in BOOK_PAGES {
+        let mut content = Vec::new();
+        for page in *pages {
+            progress.check_cancelled()?;
+            progress.report(done, Some(total), &format!("Fetching {}", page));
+            done += 1;
+
+            let cached = cache.join(page);
+            match fetch(&format!("{}/{}", base_url, page), &cached, !force) {
+                Ok(true) => report.updated += 1,
+                Ok(false) => report.unchanged += 1,
+                Err(e) => report.failed.push((page, e)),
+            }
+            if let Ok(text) = fs::read_to_string(&cached) {
+                content.push(clean_page(&text));
+            }
+        }
+        // A section is only replaced when all of its pages are available
+        if content.len() == pages.len() {
+            book.insert(section, content.join("\n\n"));
+            report.sections.push(section);
+        }
+    }
+    progress.report(total, Some(total), "Merging sections");
+
+    if book.is_empty() {
+        let reason = report
+            .failed
+            .first()
+            .map_or("no pages", |(_, e)| e.as_str());
+        return Err(format!("Failed to sync the Leptos book: {}", reason));
+    }
+    docs::apply_book(&book);
+    Ok(report)
+}
+
+/// Download `url` into `cached`; `Ok(false)` if the cached copy is current
+fn fetch(url: &str, cached: &Path, revalidate: bool) -> Result<bool, String> {
+    let dir = cached.parent().ok_or("Invalid cache path")?;
+    fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let etag_path = with_suffix(cached, "etag");
+    let headers_path = with_suffix(cached, "headers");
+    let body_path = with_suffix(cached, "download");
+
+    let mut command = Command::new("curl");
+    command
+        .args(["--silent", "--show-error", "--location"])
+        .args(["--max-time", TIMEOUT_SECS])
+        .arg("--dump-header")
+        .arg(&headers_path)
+        .arg("--output")
+        .arg(&body_path)
+        .args(["--write-out", "%{http_code}"]);
+    let etag = fs::read_to_string(&etag_path)
+        .ok()
+        .filter(|_| revalidate && cached.exists());
+    if let Some(etag) = &etag {
+        command
+            .arg("--header")
+            .arg(format!("If-None-Match: {}", etag.trim()));
+    }
+    let output = command
+        .arg(url)
+        .output()
+        .map_err(|e| format!("Failed to run curl: {}", e))?;
+    let headers = fs::read_to_string(&headers_path).unwrap_or_default();
+    let _ = fs::remove_file(&headers_path);
+
+    let status = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    match status.as_str() {
+        "304" => {
+            let _ = fs::remove_file(&body_path);
+            Ok(false)
+        }
+        "200" => {
+            fs::rename(&body_path, cached)
+                .map_err(|e| format!("Failed to write {}: {}", cached.display(), e))?;
+            match response_etag(&headers) {
+                Some(etag) => fs::write(&etag_path, etag)
+                    .map_err(|e| format!("Failed to write {}: {}", etag_path.display(), e))?,
+                None => {
+                    let _ = fs::remove_file(&etag_path);
+                }
+            }
+            Ok(true)
+        }
+        _ => {
+            let _ = fs::remove_file(&body_path);
+            if output.status.success() {
+                Err(format!("HTTP {} for {}", status, url))
+            } else {
+                Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+            }
+        }
+    }
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(".");
+    path.push(suffix);
+    PathBuf::from(path)
+}
+
+/// ETag of the final response; `--location` dumps the headers of every redirect
+fn response_etag(headers: &str) -> Option<&str> {
+    headers
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .filter(|(name, _)| name.trim().eq_ignore_ascii_case("etag"))
+        .map(|(_, value)| value.trim())
+        .next_back()
+}
+
+/// Drop mdBook directives and embedded sandboxes, which mean nothing outside the book
+fn clean_page(text: &str) -> String {
+    text.lines()
+        .filter(|line| {
+            let line = line.trim_start();
+            !line.starts_with("{{#") && !line.starts_with("<iframe")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
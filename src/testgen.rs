@@ -0,0 +1,546 @@
+//! Test generator
+//!
+//! Writes the harness boilerplate Leptos tests need: components are mounted
+//! into a real DOM with `wasm-bindgen-test`, checked for the text they render
+//! and driven through their event handlers; server functions are called from
+//! `tokio` tests inside a reactive owner that provides the contexts they read.
+//! Values and assertions the code doesn't reveal are left as marked TODOs.
+
+use crate::docs::LeptosVersion;
+use crate::generate::Backend;
+use crate::source::{functions, mask_literals, split_top_level, FnItem};
+use crate::view::{parse_views, Element, Node};
+
+/// Integer and float types, which get a zero literal
+const NUMBERS: &[&str] = &[
+    "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
+];
+
+/// Events dispatched with `.click()` instead of a synthetic `Event`
+const CLICK: &str = "on:click";
+
+/// A parameter with whether the call can leave it out
+struct Param {
+    name: String,
+    ty: String,
+    /// `#[prop(into)]`
+    into: bool,
+    /// `#[prop(optional)]` or `#[prop(default = ...)]`
+    omittable: bool,
+}
+
+/// A DOM element a test can find and an event to fire on it
+struct Interaction {
+    selector: String,
+    /// Position among the elements matching `selector`
+    index: usize,
+    event: String,
+    is_input: bool,
+}
+
+/// Test files for the components and server functions in `code`
+///
+/// With `name`, only the component or server function of that name is covered.
+pub fn tests(
+    code: &str,
+    name: Option<&str>,
+    version: LeptosVersion,
+    backend: Backend,
+) -> Result<Vec<String>, String> {
+    let items: Vec<FnItem> = functions(code)
+        .into_iter()
+        .filter(|f| f.is_component() || f.has_attr("server"))
+        .collect();
+    if items.is_empty() {
+        return Err("No #[component] or #[server] functions found in 'code'".to_string());
+    }
+    let selected: Vec<&FnItem> = items
+        .iter()
+        .filter(|f| name.is_none_or(|n| f.name == n))
+        .collect();
+    if selected.is_empty() {
+        let found: Vec<&str> = items.iter().map(|f| f.name.as_str()).collect();
+        return Err(format!(
+            "No component or server function named '{}'. Found: {}",
+            name.unwrap_or_default(),
+            found.join(", ")
+        ));
+    }
+
+    let components: Vec<&FnItem> = selected
+        .iter()
+        .copied()
+        .filter(|f| f.is_component())
+        .collect();
+    let server_fns: Vec<&FnItem> = selected
+        .iter()
+        .copied()
+        .filter(|f| f.has_attr("server"))
+        .collect();
+    let mut blocks = Vec::new();
+    if !components.is_empty() {
+        blocks.push(component_tests(code, &components, version));
+    }
+    if !server_fns.is_empty() {
+        blocks.push(server_fn_tests(code, &server_fns, version, backend));
+    }
+    blocks.push(dev_dependencies(
+        !components.is_empty(),
+        !server_fns.is_empty(),
+        server_fns.iter().any(|f| uses_extractors(code, f)),
+        backend,
+    ));
+    Ok(blocks)
+}
+
+fn component_tests(code: &str, components: &[&FnItem], version: LeptosVersion) -> String {
+    let modern = version != LeptosVersion::V0_6;
+    let mut out = String::from(
+        "// Browser tests: save as tests/web.rs and run with `wasm-pack test --headless --chrome`\n",
+    );
+    out.push_str("#![cfg(target_arch = \"wasm32\")]\n\n");
+    if modern {
+        out.push_str(
+            "use leptos::mount::mount_to;\nuse leptos::prelude::*;\nuse leptos::task::tick;\n",
+        );
+    } else {
+        out.push_str("use leptos::*;\n");
+    }
+    out.push_str("use wasm_bindgen::JsCast;\nuse wasm_bindgen_test::*;\n");
+    let names: Vec<&str> = components.iter().map(|c| c.name.as_str()).collect();
+    out.push_str(&format!(
+        "// TODO: import from your crate, e.g. `use my_app::components::{{{}}};`\n\n",
+        names.join(", ")
+    ));
+    out.push_str("wasm_bindgen_test_configure!(run_in_browser);\n");
+
+    for component in components {
+        let params = params(&code[component.params.clone()]);
+        let (markup, child_text) = mount_markup(component, &params);
+        let snake = snake_case(&component.name);
+        let texts = static_texts(code, component);
+        let mount = format!(
+            "    let wrapper = document().create_element(\"section\").unwrap();\n    document().body().unwrap().append_child(&wrapper).unwrap();\n    let {} = mount_to(wrapper.clone().unchecked_into(), || view! {{ {} }});\n",
+            if modern { "_handle" } else { "_" },
+            markup
+        );
+
+        out.push_str(&format!(
+            "\n#[wasm_bindgen_test]\n{}fn {}_renders() {{\n",
+            if modern { "async " } else { "" },
+            snake
+        ));
+        out.push_str(&mount);
+        out.push_str("\n    let text = wrapper.text_content().unwrap_or_default();\n");
+        let expected: Vec<&String> = texts.iter().chain(child_text.iter()).take(3).collect();
+        if expected.is_empty() {
+            out.push_str("    // TODO: assert the rendered text\n    assert!(!text.is_empty(), \"nothing rendered\");\n");
+        }
+        for text in expected {
+            out.push_str(&format!(
+                "    assert!(text.contains({:?}), \"rendered: {{text}}\");\n",
+                text
+            ));
+        }
+        out.push_str("}\n");
+
+        for interaction in interactions(code, component) {
+            let event = interaction.event.trim_start_matches("on:");
+            let nth = match interaction.index {
+                0 => String::new(),
+                index => format!("_{}", index + 1),
+            };
+            out.push_str(&format!(
+                "\n#[wasm_bindgen_test]\n{}fn {}_handles_{}_on_{}{}() {{\n",
+                if modern { "async " } else { "" },
+                snake,
+                event.replace('-', "_"),
+                selector_name(&interaction.selector),
+                nth
+            ));
+            out.push_str(&mount);
+            let element_type = if interaction.is_input {
+                "web_sys::HtmlInputElement"
+            } else {
+                "web_sys::HtmlElement"
+            };
+            let found = if interaction.index == 0 {
+                format!(
+                    "wrapper\n        .query_selector({:?})\n        .unwrap()\n        .expect(\"{} is rendered\")",
+                    interaction.selector, interaction.selector
+                )
+            } else {
+                format!(
+                    "wrapper\n        .query_selector_all({:?})\n        .unwrap()\n        .item({})\n        .expect(\"{} #{} is rendered\")",
+                    interaction.selector,
+                    interaction.index,
+                    interaction.selector,
+                    interaction.index + 1
+                )
+            };
+            out.push_str(&format!(
+                "    let element = {}\n        .unchecked_into::<{}>();\n",
+                found, element_type
+            ));
+            out.push_str("    let before = wrapper.inner_html();\n\n");
+            if interaction.event == CLICK {
+                out.push_str("    element.click();\n");
+            } else {
+                if interaction.is_input {
+                    out.push_str("    element.set_value(\"test\");\n");
+                }
+                out.push_str(&format!(
+                    "    element\n        .dispatch_event(&web_sys::Event::new({:?}).unwrap())\n        .unwrap();\n",
+                    event
+                ));
+            }
+            if modern {
+                out.push_str("    // Let the reactive system flush the update to the DOM\n    tick().await;\n");
+            }
+            out.push_str("\n    // TODO: assert the exact expected text instead\n");
+            out.push_str(&format!(
+                "    assert_ne!(wrapper.inner_html(), before, \"{} should update the view\");\n}}\n",
+                interaction.event
+            ));
+        }
+    }
+    out
+}
+
+fn server_fn_tests(
+    code: &str,
+    server_fns: &[&FnItem],
+    version: LeptosVersion,
+    backend: Backend,
+) -> String {
+    let modern = version != LeptosVersion::V0_6;
+    let mut out = String::from(
+        "// Server function tests: add to the module defining them and run with `cargo test --features ssr`\n",
+    );
+    out.push_str(
+        "#[cfg(all(test, feature = \"ssr\"))]\nmod server_fn_tests {\n    use super::*;\n",
+    );
+    for server_fn in server_fns {
+        let params = params(&code[server_fn.params.clone()]);
+        out.push_str(&format!(
+            "\n    #[tokio::test]\n    async fn {}_succeeds() {{\n",
+            server_fn.name
+        ));
+        if modern {
+            out.push_str("        // Server functions read context from the current reactive owner\n        let owner = Owner::new();\n        owner.set();\n");
+        } else {
+            out.push_str("        // Server functions read context from the reactive runtime\n        let runtime = create_runtime();\n");
+        }
+        let contexts = context_types(code, server_fn);
+        if !contexts.is_empty() {
+            out.push_str("        // TODO: replace with test doubles\n");
+        }
+        for ty in contexts {
+            out.push_str(&format!("        provide_context({}::default());\n", ty));
+        }
+        if uses_extractors(code, server_fn) {
+            out.push_str(&match backend {
+                Backend::Axum => "        // `extract()` reads the request parts from context\n        provide_context(http::Request::builder().uri(\"/\").body(()).unwrap().into_parts().0);\n".to_string(),
+                Backend::Actix => "        // `extract()` reads the request from context\n        provide_context(actix_web::test::TestRequest::default().to_http_request());\n".to_string(),
+            });
+        }
+        let args: Vec<String> = params
+            .iter()
+            .map(|p| example_value(&p.ty, false).unwrap_or_else(|| "todo!()".to_string()))
+            .collect();
+        out.push_str(&format!(
+            "\n        let result = {}({}).await;\n        assert!(result.is_ok(), \"{} failed: {{:?}}\", result.err());\n",
+            server_fn.name,
+            args.join(", "),
+            server_fn.name
+        ));
+        out.push_str("        // TODO: assert the returned value\n");
+        if !modern {
+            out.push_str("        runtime.dispose();\n");
+        }
+        out.push_str("    }\n");
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn dev_dependencies(browser: bool, server: bool, extractors: bool, backend: Backend) -> String {
+    let mut out = String::from("# Cargo.toml: test dependencies\n[dev-dependencies]\n");
+    if browser {
+        out.push_str("wasm-bindgen = \"0.2\"\nwasm-bindgen-test = \"0.3\"\nweb-sys = { version = \"0.3\", features = [\"HtmlElement\", \"HtmlInputElement\", \"Event\", \"NodeList\"] }\n");
+    }
+    if server {
+        out.push_str("tokio = { version = \"1\", features = [\"macros\", \"rt-multi-thread\"] }\n");
+    }
+    if extractors && backend == Backend::Axum {
+        out.push_str("http = \"1\"\n");
+    }
+    out
+}
+
+/// Parameters of a component or server function, with their `#[prop]` options
+fn params(list: &str) -> Vec<Param> {
+    split_top_level(list)
+        .into_iter()
+        .filter_map(|param| {
+            let mut param = param.trim();
+            let mut attrs = String::new();
+            while param.starts_with("#[") {
+                let close = param.find(']')?;
+                attrs.push_str(&param[..=close]);
+                param = param[close + 1..].trim_start();
+            }
+            let (pattern, ty) = param.split_once(':')?;
+            Some(Param {
+                name: pattern.trim().trim_start_matches("mut ").trim().to_string(),
+                ty: ty.trim().to_string(),
+                into: attrs.contains("into"),
+                omittable: attrs.contains("optional") || attrs.contains("default"),
+            })
+        })
+        .collect()
+}
+
+/// `<Name prop=value ...>children</Name>`, and the text passed as children
+fn mount_markup(component: &FnItem, params: &[Param]) -> (String, Option<String>) {
+    let mut attrs = Vec::new();
+    let mut children = None;
+    for param in params.iter().filter(|p| !p.omittable) {
+        if param.name == "children" {
+            children = Some("Child content".to_string());
+            continue;
+        }
+        let value = example_value(&param.ty, param.into).unwrap_or_else(|| "todo!()".to_string());
+        let simple = value
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '_' | '.' | '"' | ' '));
+        if simple {
+            attrs.push(format!("{}={}", param.name, value));
+        } else {
+            attrs.push(format!("{}={{{}}}", param.name, value));
+        }
+    }
+    let open = if attrs.is_empty() {
+        component.name.clone()
+    } else {
+        format!("{} {}", component.name, attrs.join(" "))
+    };
+    let markup = match &children {
+        Some(text) => format!("<{}>{:?}</{}>", open, text, component.name),
+        None => format!("<{}/>", open),
+    };
+    (markup, children)
+}
+
+/// A value of `ty` to pass in a test, `None` when there's no sensible one
+fn example_value(ty: &str, into: bool) -> Option<String> {
+    let ty = ty.trim();
+    let inner = |prefix: &str| {
+        ty.strip_prefix(prefix)
+            .and_then(|t| t.strip_suffix('>'))
+            .map(str::trim)
+    };
+    if NUMBERS.contains(&ty) {
+        return Some("0".to_string());
+    }
+    Some(match ty {
+        "f32" | "f64" => "0.0".to_string(),
+        "bool" => "false".to_string(),
+        "char" => "'a'".to_string(),
+        "String" if into => "\"test\"".to_string(),
+        "String" => "\"test\".to_string()".to_string(),
+        t if t.starts_with('&') && t.ends_with("str") => "\"test\"".to_string(),
+        t if t.starts_with("impl Into<String>") => "\"test\"".to_string(),
+        t if t.starts_with("Option<") => "None".to_string(),
+        t if t.starts_with("Vec<") => "vec![]".to_string(),
+        t if t.starts_with("Callback<") || t.starts_with("UnsyncCallback<") => {
+            "Callback::new(|_| {})".to_string()
+        }
+        t if t.starts_with("impl Fn") || t.starts_with("impl FnMut") => {
+            let args = t
+                .split_once('(')
+                .and_then(|(_, rest)| rest.split_once(')'))
+                .map_or(0, |(args, _)| {
+                    split_top_level(args)
+                        .iter()
+                        .filter(|a| !a.trim().is_empty())
+                        .count()
+                });
+            format!("|{}| {{}}", vec!["_"; args].join(", "))
+        }
+        _ => {
+            for (wrapper, make) in [
+                ("Signal<", "Signal::stored({})"),
+                ("MaybeSignal<", "MaybeSignal::Static({})"),
+                ("RwSignal<", "RwSignal::new({})"),
+                ("ReadSignal<", "signal({}).0"),
+            ] {
+                if let Some(inner) = inner(wrapper) {
+                    // `#[prop(into)]` signals accept the plain value
+                    return match into {
+                        true => example_value(inner, true),
+                        false => example_value(inner, false).map(|v| make.replace("{}", &v)),
+                    };
+                }
+            }
+            return None;
+        }
+    })
+}
+
+/// Text literals of the component's outermost `view!`, outside control-flow components
+fn static_texts(code: &str, component: &FnItem) -> Vec<String> {
+    let views: Vec<_> = parse_views(code)
+        .into_iter()
+        .filter(|v| component.body.contains(&v.start))
+        .collect();
+    let Some(view) = views.iter().find(|v| {
+        !views
+            .iter()
+            .any(|outer| (outer.body_start..outer.body_end).contains(&v.start))
+    }) else {
+        return Vec::new();
+    };
+    let mut depth = 0usize;
+    let mut texts = Vec::new();
+    for node in &view.nodes {
+        match node {
+            Node::Open(element) if element.is_component() && !element.self_closing => depth += 1,
+            Node::Close { name, .. } if name.starts_with(|c: char| c.is_uppercase()) => {
+                depth = depth.saturating_sub(1)
+            }
+            Node::Text { text, .. } if depth == 0 && !text.trim().is_empty() => {
+                texts.push(text.trim().to_string())
+            }
+            _ => {}
+        }
+    }
+    texts
+}
+
+/// HTML elements of the component's views with event handlers
+fn interactions(code: &str, component: &FnItem) -> Vec<Interaction> {
+    let views: Vec<_> = parse_views(code)
+        .into_iter()
+        .filter(|v| component.body.contains(&v.start))
+        .collect();
+    let mut found: Vec<Interaction> = Vec::new();
+    let mut seen: Vec<&Element> = Vec::new();
+    for view in &views {
+        for node in &view.nodes {
+            let Node::Open(element) = node else { continue };
+            if element.is_component() {
+                continue;
+            }
+            let selector = selector(element);
+            let index = seen.iter().filter(|e| matches(&selector, e)).count();
+            seen.push(element);
+            for attr in element.attrs.iter().filter(|a| a.name.starts_with("on:")) {
+                found.push(Interaction {
+                    selector: selector.clone(),
+                    index,
+                    event: attr.name.clone(),
+                    is_input: matches!(element.name.as_str(), "input" | "textarea"),
+                });
+            }
+        }
+    }
+    found
+}
+
+/// CSS selector for an element: its id, or its tag and static classes
+fn selector(element: &Element) -> String {
+    if let Some(id) = literal(element, "id") {
+        return format!("#{}", id);
+    }
+    match literal(element, "class") {
+        Some(classes) if !classes.trim().is_empty() => {
+            format!(
+                "{}.{}",
+                element.name,
+                classes.split_whitespace().collect::<Vec<_>>().join(".")
+            )
+        }
+        _ => element.name.clone(),
+    }
+}
+
+/// Whether `element` matches a selector built by [`selector`]
+fn matches(selector: &str, element: &Element) -> bool {
+    if let Some(id) = selector.strip_prefix('#') {
+        return literal(element, "id").as_deref() == Some(id);
+    }
+    let mut parts = selector.split('.');
+    let classes = literal(element, "class").unwrap_or_default();
+    parts.next() == Some(element.name.as_str())
+        && parts.all(|class| classes.split_whitespace().any(|c| c == class))
+}
+
+/// The value of a string literal attribute, without quotes
+fn literal(element: &Element, name: &str) -> Option<String> {
+    element
+        .attr(name)
+        .filter(|a| a.is_string_literal())
+        .and_then(|a| a.value.as_deref())
+        .map(|v| v.trim_matches('"').to_string())
+}
+
+/// `button.primary` → `button_primary`, for test names
+fn selector_name(selector: &str) -> String {
+    selector
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect::<String>()
+        .trim_matches('_')
+        .to_lowercase()
+}
+
+/// Types the server function reads with `use_context`/`expect_context`
+fn context_types(code: &str, server_fn: &FnItem) -> Vec<String> {
+    let masked = mask_literals(code);
+    let body = &masked[server_fn.body.clone()];
+    let mut types: Vec<String> = Vec::new();
+    for call in ["use_context", "expect_context"] {
+        for (rel, _) in body.match_indices(call) {
+            let after = &body[rel + call.len()..];
+            let ty = match after.strip_prefix("::<") {
+                Some(rest) => rest.split_once(">(").map(|(ty, _)| ty.trim().to_string()),
+                // `let pool: DbPool = expect_context();`
+                None => body[..rel].rsplit_once("let ").and_then(|(_, binding)| {
+                    let (_, ty) = binding.split_once(':')?;
+                    let ty = ty.trim().trim_end_matches('=').trim();
+                    let ty = ty
+                        .strip_prefix("Option<")
+                        .and_then(|t| t.strip_suffix('>'))
+                        .unwrap_or(ty);
+                    Some(ty.to_string())
+                }),
+            };
+            if let Some(ty) = ty.filter(|t| !t.is_empty() && !types.contains(t)) {
+                types.push(ty);
+            }
+        }
+    }
+    types
+}
+
+fn uses_extractors(code: &str, server_fn: &FnItem) -> bool {
+    let body = &code[server_fn.body.clone()];
+    body.contains("extract(") || body.contains("extract::<")
+}
+
+/// `UserCard` → `user_card`
+fn snake_case(name: &str) -> String {
+    let mut out = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i > 0 {
+                out.push('_');
+            }
+            out.push(c.to_ascii_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
@@ -2,89 +2,3530 @@
 //!
 //! Implements the tool handlers for the MCP server.
 
-use crate::docs;
+use crate::api::{self, ApiDiff, ApiItem, Lookup};
+use crate::bundle;
+use crate::cache;
+use crate::changelog::{self, Category};
+use crate::components;
+use crate::context::{Environment, Manifest, ProjectContext};
+use crate::dependencies;
+use crate::diagnostics;
+use crate::docs::{self, DocSection, Language, LeptosVersion, Syntax};
+use crate::explain;
+use crate::facts::{self, Verdict};
+use crate::features;
+use crate::format::{self, FormatOptions};
+use crate::generate::{
+    self, Backend, ComponentSpec, ErrorTypeSpec, FormSpec, RouteSpec, ServerFnSpec,
+};
+use crate::glossary;
+use crate::html::{self, StyleMode};
+use crate::i18n::{self, Extraction, Library};
+use crate::imports;
+use crate::islands::{self, Advice, Role};
+use crate::markdown;
+use crate::metrics;
+use crate::output::{
+    self, ApiLookup, AppliedFix, BlockLanguage, BundleReport, ChangelogEntry, ClaimChecks,
+    ComponentGraph, ComponentList, DependencyAdvice, Documentation, EnvironmentReport, Example,
+    Examples, Explanations, FailedPage, FeatureCheck, FileFindings, Findings, Formatted, Generated,
+    GeneratedBlock, HeadingInfo, ImportSuggestions, IslandsAdvice, ListedSection, NeedAdvice,
+    ProjectContextSet, ProjectFindings, ReactiveGraphs, Recommendations, Review, RuleExplanation,
+    RuleList, ScaffoldedProject, SearchResult, SearchResults, SectionInfo, SectionList,
+    SectionLookup, SnippetCode, SnippetList, SnippetLookup, StateList, Statistics, SyncSummary,
+    TermLookup, VersionSelected, View,
+};
+use crate::progress::Progress;
+use crate::project::{self, SourceFile};
+use crate::reactivity::{self, GraphFormat};
+use crate::recommend;
+use crate::related;
+use crate::review::{self, FileReview};
+use crate::routemap::{self, Route, RouteMap};
+use crate::rules::{self, Diagnostic, Edit, RuleConfig, StyleFramework, Styling};
+use crate::sandbox::{self, CheckResult, RenderResult};
+use crate::scaffold::{self, ProjectSpec, RenderMode};
+use crate::semantic;
+use crate::snippets::{self, Snippet};
+use crate::state::{self, State};
+use crate::sync;
+use crate::testgen;
+use crate::workspace::{Target, Workspace};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Mutex, RwLock};
+use std::time::Duration;
+
+/// Default number of results returned by search-docs
+pub const DEFAULT_SEARCH_LIMIT: usize = 5;
+
+/// Rough characters-per-token ratio used to turn `max_tokens` into a character budget
+const CHARS_PER_TOKEN: usize = 4;
+
+/// Character budget from the optional `max_tokens`/`max_chars` arguments (smallest wins)
+pub fn char_budget(max_tokens: Option<u64>, max_chars: Option<u64>) -> Option<usize> {
+    let from_tokens = max_tokens.map(|t| t as usize * CHARS_PER_TOKEN);
+    match (from_tokens, max_chars.map(|c| c as usize)) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+/// Offset encoded in a pagination cursor; no cursor starts at the beginning
+///
+/// Cursors are opaque to clients: they only pass back what a previous page returned.
+pub fn parse_cursor(cursor: Option<&str>) -> Result<usize, String> {
+    match cursor {
+        None => Ok(0),
+        Some(cursor) => cursor
+            .parse()
+            .map_err(|_| format!("Invalid cursor: {}", cursor)),
+    }
+}
+
+/// Result of a tool call: display text plus optional machine-readable content
+///
+/// Tools return their own result type as `T`; the protocol handles results
+/// as JSON.
+#[derive(Debug)]
+pub struct ToolOutput<T = Value> {
+    pub text: String,
+    /// Further text content blocks returned after `text`
+    pub blocks: Vec<String>,
+    /// Returned as MCP `structuredContent`; must serialize to a JSON object
+    pub structured: Option<T>,
+}
+
+impl<T> ToolOutput<T> {
+    pub fn structured(text: String, structured: T) -> Self {
+        Self {
+            text,
+            blocks: Vec::new(),
+            structured: Some(structured),
+        }
+    }
+
+    /// One content block per item; `blocks` must not be empty
+    pub fn blocks(mut blocks: Vec<String>) -> Self {
+        let text = blocks.remove(0);
+        Self {
+            text,
+            blocks,
+            structured: None,
+        }
+    }
+}
+
+impl<T: Serialize> ToolOutput<T> {
+    /// The same result with its structured content as JSON
+    pub fn into_json(self) -> Result<ToolOutput, String> {
+        let structured = self
+            .structured
+            .map(|s| {
+                serde_json::to_value(s).map_err(|e| format!("Failed to serialize result: {}", e))
+            })
+            .transpose()?;
+        Ok(ToolOutput {
+            text: self.text,
+            blocks: self.blocks,
+            structured,
+        })
+    }
+}
+
+impl From<String> for ToolOutput {
+    fn from(text: String) -> Self {
+        Self {
+            text,
+            blocks: Vec::new(),
+            structured: None,
+        }
+    }
+}
+
+/// Which part of a section `get-documentation` returns
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DocOptions<'a> {
+    /// Only the subsection under this heading (slug or title)
+    pub heading: Option<&'a str>,
+    /// The heading outline instead of the content
+    pub toc: bool,
+    /// Longest part of the content one call returns
+    pub max_chars: Option<usize>,
+    /// Where the part starts: the `continue` cursor the previous part returned
+    pub cursor: Option<&'a str>,
+}
+
+/// Text rendering of analysis results
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+impl OutputFormat {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            other => Err(format!(
+                "Unsupported format '{}'. Expected 'text' or 'json'",
+                other
+            )),
+        }
+    }
+}
+
+/// Rendering of documentation text, for clients that display it literally
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DocFormat {
+    /// As stored
+    #[default]
+    Markdown,
+    /// Markup stripped
+    Plain,
+    /// An HTML fragment
+    Html,
+}
+
+impl DocFormat {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "markdown" => Ok(Self::Markdown),
+            "plain" => Ok(Self::Plain),
+            "html" => Ok(Self::Html),
+            other => Err(format!(
+                "Unsupported format '{}'. Expected 'markdown', 'plain' or 'html'",
+                other
+            )),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Markdown => "markdown",
+            Self::Plain => "plain",
+            Self::Html => "html",
+        }
+    }
+
+    /// Markdown `content` converted to this format
+    pub fn render(&self, content: &str) -> String {
+        match self {
+            Self::Markdown => content.to_string(),
+            Self::Plain => markdown::to_plain(content),
+            Self::Html => markdown::to_html(content),
+        }
+    }
+}
+
+/// How search-docs ranks subsections
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchMode {
+    /// Matching terms in the text, headings and use cases
+    #[default]
+    Keyword,
+    /// Embedding similarity to the query, for questions about symptoms or goals
+    Semantic,
+}
+
+impl SearchMode {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "keyword" => Ok(Self::Keyword),
+            "semantic" => Ok(Self::Semantic),
+            other => Err(format!(
+                "Unsupported mode '{}'. Expected 'keyword' or 'semantic'",
+                other
+            )),
+        }
+    }
+}
 
 /// Leptos Tools implementation
-pub struct LeptosTools {}
+pub struct LeptosTools {
+    /// Leptos version used when a tool call doesn't specify one
+    version: RwLock<LeptosVersion>,
+    /// Rule config every lint starts from, before project files and arguments
+    rules: RuleConfig,
+    /// Project set with `set-project-context`, consulted when a call doesn't say
+    context: RwLock<Option<ProjectContext>>,
+    /// Mechanical fixes the last analysis found, for `apply-fix`
+    fixes: Mutex<Fixes>,
+}
+
+/// Fixes of files on disk that `apply-fix` can make
+#[derive(Debug, Default)]
+struct Fixes {
+    /// Ids handed out so far, so an id from an earlier run can't name a
+    /// fix of a later one
+    issued: usize,
+    pending: Vec<PendingFix>,
+}
+
+#[derive(Debug, Clone)]
+struct PendingFix {
+    id: String,
+    rule_id: &'static str,
+    /// Project root of the analysis
+    root: PathBuf,
+    /// File path relative to `root`
+    path: String,
+    edit: Edit,
+}
+
+/// A copy with the same base rules, selected version and project context,
+/// and no fixes
+impl Clone for LeptosTools {
+    fn clone(&self) -> Self {
+        Self {
+            version: RwLock::new(self.version()),
+            rules: self.rules.clone(),
+            context: RwLock::new(self.project_context()),
+            fixes: Mutex::default(),
+        }
+    }
+}
+
+impl Default for LeptosTools {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl LeptosTools {
     pub fn new() -> Self {
-        Self {}
+        Self {
+            version: RwLock::new(LeptosVersion::default()),
+            rules: RuleConfig::default(),
+            context: RwLock::new(None),
+            fixes: Mutex::default(),
+        }
+    }
+
+    /// Start with `version` selected instead of the latest
+    pub fn with_version(self, version: LeptosVersion) -> Self {
+        *self.version.write().unwrap() = version;
+        self
+    }
+
+    /// Lint with `rules` as the base config
+    pub fn with_rules(mut self, rules: RuleConfig) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// Currently selected Leptos version
+    pub fn version(&self) -> LeptosVersion {
+        *self.version.read().unwrap()
+    }
+
+    /// Project set with `set-project-context`, if any
+    pub fn project_context(&self) -> Option<ProjectContext> {
+        self.context.read().unwrap().clone()
+    }
+
+    /// Root of the project context, or the working directory without one
+    pub fn project_root(&self) -> PathBuf {
+        self.context
+            .read()
+            .unwrap()
+            .as_ref()
+            .map_or_else(|| PathBuf::from("."), |context| context.root.clone())
+    }
+
+    /// Backend of the project context, or the default without one
+    fn backend(&self) -> Backend {
+        self.context
+            .read()
+            .unwrap()
+            .as_ref()
+            .and_then(|context| context.backend)
+            .unwrap_or_default()
+    }
+
+    /// Signal syntax of the project context, or stable without one
+    fn syntax(&self) -> Syntax {
+        self.context
+            .read()
+            .unwrap()
+            .as_ref()
+            .map(|context| context.syntax)
+            .unwrap_or_default()
+    }
+
+    /// Resolve an optional version argument against the selected version
+    fn resolve_version(&self, version: Option<&str>) -> Result<LeptosVersion, String> {
+        match version {
+            Some(v) => LeptosVersion::parse(v).ok_or_else(|| unsupported_version(v)),
+            None => Ok(self.version()),
+        }
+    }
+
+    /// Key caching a call to `tool` with `arguments` in this session
+    pub fn cache_key(&self, tool: &str, arguments: &Value) -> cache::Key {
+        cache::Key::new(
+            tool,
+            arguments,
+            self.version(),
+            self.syntax(),
+            self.backend(),
+        )
+    }
+
+    /// Rules for the selected version and syntax, then the base rules, then
+    /// `leptos-mcp.toml` in `dir`, then the tool argument
+    fn rule_config(&self, dir: &Path, overrides: Option<RuleConfig>) -> Result<RuleConfig, String> {
+        let mut config = RuleConfig::for_version(self.version());
+        config.merge(RuleConfig::for_syntax(self.syntax()));
+        config.merge(self.rules.clone());
+        config.merge(RuleConfig::from_file(&dir.join(rules::CONFIG_FILE))?);
+        if let Some(overrides) = overrides {
+            config.merge(overrides);
+        }
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Styling rules for the project in `root`: the context's frameworks when
+    /// it is the context's project, else the ones detected there
+    fn styling(&self, root: &Path) -> Styling {
+        let frameworks = match self.project_context() {
+            Some(context) if context.root == root => context.styling,
+            _ => Environment::detect(root)
+                .map(|env| env.styling)
+                .unwrap_or_default(),
+        };
+        Styling::load(root, &frameworks)
+    }
+
+    /// Select the Leptos version used by subsequent tool calls
+    pub fn set_leptos_version(&self, version: &str) -> Result<ToolOutput<VersionSelected>, String> {
+        let parsed = LeptosVersion::parse(version).ok_or_else(|| unsupported_version(version))?;
+        *self.version.write().unwrap() = parsed;
+        Ok(ToolOutput::structured(
+            format!(
+                "Leptos version set to {}. Documentation will target this version.",
+                parsed.as_str()
+            ),
+            VersionSelected {
+                version: parsed.as_str(),
+            },
+        ))
+    }
+
+    /// Remember the project at `path` for subsequent tool calls
+    ///
+    /// The Leptos version, backend and signal syntax are read from its
+    /// `Cargo.toml` unless given; a detected version also becomes the
+    /// selected one.
+    pub fn set_project_context(
+        &self,
+        path: &str,
+        version: Option<&str>,
+        backend: Option<Backend>,
+        styling: Option<&[StyleFramework]>,
+        syntax: Option<Syntax>,
+    ) -> Result<ToolOutput<ProjectContextSet>, String> {
+        if path.is_empty() {
+            return Err("Missing 'path' argument".to_string());
+        }
+        let mut context = ProjectContext::detect(Path::new(path))?;
+        if let Some(styling) = styling {
+            context.styling = styling.to_vec();
+        }
+        if let Some(version) = version {
+            context.version =
+                Some(LeptosVersion::parse(version).ok_or_else(|| unsupported_version(version))?);
+        }
+        if backend.is_some() {
+            context.backend = backend;
+        }
+        if let Some(syntax) = syntax {
+            context.syntax = syntax;
+        }
+        if let Some(version) = context.version {
+            *self.version.write().unwrap() = version;
+        }
+
+        let mut text = format!("Project context set to {}", context.root.display());
+        if let Some(package) = &context.package {
+            text.push_str(&format!(" ({})", package));
+        }
+        text.push_str(&match context.version {
+            Some(version) => format!(".\nLeptos version: {}", version.as_str()),
+            None => format!(
+                ".\nLeptos version: not detected, keeping {}",
+                self.version().as_str()
+            ),
+        });
+        text.push_str(&match context.backend {
+            Some(backend) => format!("\nBackend: {}", backend.as_str()),
+            None => "\nBackend: not detected, generators default to axum".to_string(),
+        });
+        text.push_str(&format!(
+            "\nStyling checks: {}",
+            styling_list(&context.styling)
+        ));
+        text.push_str(match context.syntax {
+            Syntax::Stable => "\nSyntax: stable, signals are read and set with methods",
+            Syntax::Nightly => "\nSyntax: nightly, signals may be called as functions",
+        });
+        let structured = ProjectContextSet {
+            root: context.root.display().to_string(),
+            package: context.package.clone(),
+            version: self.version().as_str(),
+            version_detected: context.version.is_some(),
+            backend: context.backend,
+            styling: context.styling.clone(),
+            syntax: context.syntax,
+        };
+        *self.context.write().unwrap() = Some(context);
+        Ok(ToolOutput::structured(text, structured))
+    }
+
+    /// Report a project's Leptos crate versions and features
+    ///
+    /// Without `path`, the project context's root is read. The project becomes
+    /// the session's context, so docs and lints follow its locked version.
+    pub fn detect_environment(
+        &self,
+        path: Option<&str>,
+    ) -> Result<ToolOutput<EnvironmentReport>, String> {
+        let root = path.map_or_else(|| self.project_root(), PathBuf::from);
+        let env = Environment::detect(&root)?;
+        let context = env.context();
+        if let Some(version) = context.version {
+            *self.version.write().unwrap() = version;
+        }
+
+        let mut text = format!("Environment of {}", env.root.display());
+        if let Some(package) = &env.package {
+            text.push_str(&format!(" ({})", package));
+        }
+        text.push_str("\n\nCrates:");
+        if env.crates.is_empty() {
+            text.push_str("\n- no Leptos crates found");
+        }
+        for c in &env.crates {
+            let locked = c.locked.as_deref().unwrap_or("not locked");
+            text.push_str(&format!("\n- {} {}", c.name, locked));
+            match (&c.requirement, c.direct) {
+                (Some(requirement), _) => text.push_str(&format!(" (requires {})", requirement)),
+                (None, true) => text.push_str(" (git or path dependency)"),
+                (None, false) => text.push_str(" (transitive)"),
+            }
+        }
+        text.push_str("\n\nFeatures:");
+        for feature in &env.features {
+            let mut via: Vec<String> = Vec::new();
+            if feature.always {
+                via.push("always on".to_string());
+            }
+            via.extend(
+                feature
+                    .crate_features
+                    .iter()
+                    .map(|f| format!("crate feature `{}`", f)),
+            );
+            let via = if via.is_empty() {
+                "not enabled".to_string()
+            } else {
+                via.join(", ")
+            };
+            text.push_str(&format!("\n- {}: {}", feature.name, via));
+        }
+        text.push_str(&format!("\n\nStyling: {}", styling_list(&env.styling)));
+        text.push_str(&match context.version {
+            Some(version) => format!("\n\nDocs and lints now target Leptos {}.", version.as_str()),
+            None => format!(
+                "\n\nNo supported Leptos version detected; keeping {}.",
+                self.version().as_str()
+            ),
+        });
+
+        let structured = EnvironmentReport {
+            environment: env,
+            version: self.version().as_str(),
+            backend: context.backend,
+            syntax: context.syntax,
+        };
+        *self.context.write().unwrap() = Some(context);
+        Ok(ToolOutput::structured(text, structured))
+    }
+
+    /// Check a project's Cargo features for SSR/hydrate misconfigurations
+    ///
+    /// Without `path`, the project context's root is checked.
+    pub fn check_features(&self, path: Option<&str>) -> Result<ToolOutput<FeatureCheck>, String> {
+        let root = path.map_or_else(|| self.project_root(), PathBuf::from);
+        let issues = features::check(&root)?;
+        if issues.is_empty() {
+            let text = format!("✓ No feature misconfigurations found in {}", root.display());
+            let structured = FeatureCheck {
+                root: root.display().to_string(),
+                issues,
+            };
+            return Ok(ToolOutput::structured(text, structured));
+        }
+
+        let mut text = format!(
+            "Found {} feature issue(s) in {}",
+            issues.len(),
+            root.join("Cargo.toml").display()
+        );
+        for issue in &issues {
+            text.push_str(&format!(
+                "\n\n## [{}] {}\n```toml\n{}\n```",
+                issue.id, issue.message, issue.patch
+            ));
+        }
+        let structured = FeatureCheck {
+            root: root.display().to_string(),
+            issues,
+        };
+        Ok(ToolOutput::structured(text, structured))
     }
 
-    /// List all available Leptos documentation sections
-    pub fn list_sections(&self) -> String {
-        let sections = docs::list_sections();
-        let output: Vec<String> = sections
+    /// Suggest ways to shrink a project's WASM bundle, largest savings first
+    ///
+    /// Without `path`, the project context's root is checked.
+    pub fn analyze_bundle(
+        &self,
+        path: Option<&str>,
+        progress: &Progress,
+    ) -> Result<ToolOutput<BundleReport>, String> {
+        let root = path.map_or_else(|| self.project_root(), PathBuf::from);
+        let suggestions = bundle::analyze(&root, progress)?;
+        if suggestions.is_empty() {
+            let text = format!("✓ No bundle size issues found in {}", root.display());
+            let structured = BundleReport {
+                root: root.display().to_string(),
+                suggestions,
+            };
+            return Ok(ToolOutput::structured(text, structured));
+        }
+
+        let mut text = format!(
+            "{} size suggestion(s) for {}, largest savings first",
+            suggestions.len(),
+            root.display()
+        );
+        for (i, suggestion) in suggestions.iter().enumerate() {
+            text.push_str(&format!(
+                "\n\n## {}. [{}] {}\n{}",
+                i + 1,
+                suggestion.priority.as_str(),
+                suggestion.id,
+                suggestion.message
+            ));
+            if !suggestion.locations.is_empty() {
+                text.push_str(&format!("\nAt: {}", suggestion.locations.join(", ")));
+            }
+            text.push_str(&format!("\n```\n{}\n```", suggestion.fix));
+        }
+        let structured = BundleReport {
+            root: root.display().to_string(),
+            suggestions,
+        };
+        Ok(ToolOutput::structured(text, structured))
+    }
+
+    /// List all available Leptos documentation sections, grouped by crate
+    pub fn list_sections(&self) -> ToolOutput<SectionList> {
+        let version = self.version();
+        let sections = docs::list_sections(version);
+        let groups: Vec<String> = docs::GROUPS
             .iter()
-            .map(|s| {
-                format!(
-                    "* title: {}, use_cases: {}, path: {}",
-                    s.title, s.use_cases, s.path
-                )
+            .map(|group| {
+                let mut out = format!("## {} ({})", group.name, group.description);
+                for s in sections.iter().filter(|s| s.group().prefix == group.prefix) {
+                    out.push_str(&format!(
+                        "\n* title: {}, use_cases: {}, path: {}",
+                        s.title, s.use_cases, s.path
+                    ));
+                }
+                out
             })
             .collect();
-        output.join("\n")
+        let structured = SectionList {
+            version: version.as_str(),
+            sections: sections
+                .iter()
+                .map(|s| ListedSection {
+                    section: SectionInfo::from(s),
+                    use_cases: s.use_cases,
+                })
+                .collect(),
+        };
+        ToolOutput::structured(groups.join("\n\n"), structured)
+    }
+
+    /// Completion candidates for a tool argument being typed
+    ///
+    /// `section` completes to section paths, or to `path#slug` subsection
+    /// addresses once the value contains `#`; `version` to supported versions.
+    pub fn complete(&self, tool: &str, argument: &str, value: &str) -> Vec<String> {
+        let value = value.trim().to_lowercase();
+        match (tool, argument) {
+            ("get-documentation" | "get-examples", "section") => {
+                let sections = docs::list_sections(self.version());
+                match value.split_once('#') {
+                    Some((path, heading)) => sections
+                        .iter()
+                        .filter(|s| s.path == path)
+                        .flat_map(|s| s.headings.iter())
+                        .filter(|h| h.level > 1 && h.slug.starts_with(heading))
+                        .map(|h| format!("{}#{}", path, h.slug))
+                        .collect(),
+                    None => {
+                        // Prefix matches on the path or title first, then substring matches
+                        let (prefix, rest): (Vec<&DocSection>, Vec<&DocSection>) = sections
+                            .iter()
+                            .filter(|s| {
+                                s.path.contains(&value) || s.title.to_lowercase().contains(&value)
+                            })
+                            .partition(|s| {
+                                s.path.starts_with(&value)
+                                    || s.title.to_lowercase().starts_with(&value)
+                            });
+                        prefix
+                            .into_iter()
+                            .chain(rest)
+                            .map(|s| s.path.to_string())
+                            .collect()
+                    }
+                }
+            }
+            ("explain-rule", "rule") => rules::all_rules()
+                .into_iter()
+                .flat_map(|rule| [rule.id, rule.name])
+                .filter(|key| key.to_lowercase().starts_with(&value))
+                .map(String::from)
+                .collect(),
+            ("define-term", "term") => glossary::TERMS
+                .iter()
+                .map(|t| t.term)
+                .filter(|term| term.contains(&value))
+                .map(String::from)
+                .collect(),
+            ("suggest-dependencies", "need") => dependencies::NEEDS
+                .iter()
+                .map(|n| n.id)
+                .filter(|id| id.contains(&value))
+                .map(String::from)
+                .collect(),
+            ("get-snippet", "name") => snippets::SNIPPETS
+                .iter()
+                .map(|s| s.name)
+                .filter(|name| name.contains(&value))
+                .map(String::from)
+                .collect(),
+            ("lookup-api", "path") => {
+                let query = value.trim_start_matches("::");
+                let mut paths: Vec<String> = api::items(self.version())
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|item| item.path.clone())
+                    .filter(|path| path.to_lowercase().contains(query))
+                    .collect();
+                // Items whose name starts with the value first
+                paths.sort_by_key(|path| {
+                    let name = path.rsplit("::").next().unwrap_or_default();
+                    !name.to_lowercase().starts_with(query)
+                });
+                paths
+            }
+            ("list-snippets", "tag") => snippets::tags()
+                .into_iter()
+                .filter(|tag| tag.starts_with(&value))
+                .map(String::from)
+                .collect(),
+            (
+                "get-documentation" | "search-docs" | "recommend-sections" | "set-leptos-version"
+                | "check-snippet" | "render-preview",
+                "version",
+            )
+            | ("api-diff", "from" | "to")
+            | ("get-changelog", "from_version" | "to_version") => LeptosVersion::ALL
+                .iter()
+                .map(|v| v.as_str().to_string())
+                .filter(|v| v.starts_with(value.trim_start_matches('v')))
+                .collect(),
+            _ => Vec::new(),
+        }
     }
 
     /// Get documentation content for a specific section
-    pub fn get_documentation(&self, section: &str) -> String {
-        if let Some(doc) = docs::get_section(section) {
-            format!("# {}\n\n{}", doc.title, doc.content)
+    ///
+    /// `section` may address a subsection directly (`signals#derived-signals`).
+    /// Content longer than `max_chars` is returned in parts, each with the
+    /// `continue` cursor of the next; the budget applies to the markdown,
+    /// before it's rendered in `format`. With `lang`, a translation is served
+    /// when the section has one, English otherwise.
+    pub fn get_documentation(
+        &self,
+        section: &str,
+        version: Option<&str>,
+        lang: Option<&str>,
+        options: DocOptions,
+        format: DocFormat,
+    ) -> Result<ToolOutput<Documentation>, String> {
+        let DocOptions {
+            heading,
+            toc,
+            max_chars,
+            cursor,
+        } = options;
+        let version = self.resolve_version(version)?;
+        let language = lang.map(Language::parse).transpose()?.unwrap_or_default();
+        let (section, anchor) = match section.split_once('#') {
+            Some((section, anchor)) => (section, Some(anchor)),
+            None => (section, None),
+        };
+        let heading = heading.or(anchor).filter(|h| !h.trim().is_empty());
+
+        let Some(doc) = docs::get_section(section, version) else {
+            return Ok(section_not_found(section, version));
+        };
+        let translated = doc.translated(language);
+        let doc = translated.as_ref().unwrap_or(&doc);
+
+        let mut note = if doc.version == version {
+            String::new()
+        } else {
+            format!(
+                "> Note: no Leptos {}-specific version of this section exists; content targets Leptos {}.\n\n",
+                version.as_str(),
+                doc.version.as_str()
+            )
+        };
+        if doc.language != language {
+            note.push_str(&format!(
+                "> Note: no {} translation of this section exists; content is in {}.\n\n",
+                language.name(),
+                doc.language.name()
+            ));
+        }
+
+        let mut structured = Documentation {
+            found: true,
+            section: Some(SectionInfo::from(doc)),
+            headings: Some(
+                doc.headings
+                    .iter()
+                    .filter(|h| h.level > 1)
+                    .map(|h| HeadingInfo {
+                        title: h.title.clone(),
+                        level: h.level,
+                        address: format!("{}#{}", doc.path, h.slug),
+                    })
+                    .collect(),
+            ),
+            ..Documentation::default()
+        };
+        let body = if toc {
+            table_of_contents(doc)
+        } else if let Some(query) = heading {
+            match doc.find_heading(query) {
+                Some(h) => {
+                    structured.heading = Some(format!("{}#{}", doc.path, h.slug));
+                    doc.subsection(h).to_string()
+                }
+                None => {
+                    structured.found = false;
+                    let text = format!(
+                        "Heading '{}' not found in section '{}'. Available headings:\n{}",
+                        query,
+                        doc.path,
+                        table_of_contents(doc)
+                    );
+                    return Ok(ToolOutput::structured(text, structured));
+                }
+            }
         } else {
+            doc.content_for(self.syntax(), self.backend())
+        };
+        let offset = parse_cursor(cursor)?;
+        if offset > body.len() || !body.is_char_boundary(offset) {
+            return Err(format!("Invalid continue cursor: {}", offset));
+        }
+        let body = match max_chars {
+            Some(max) => {
+                let (part, next) = doc_part(&body, doc.path, max, offset);
+                structured.cursor = next.map(|next| next.to_string());
+                part
+            }
+            None => body[offset..].to_string(),
+        };
+        if !toc {
+            structured.content = Some(format.render(&body));
+        }
+        let related = related::related(doc.path, version);
+        let text = format!(
+            "# {}\n\n{}{}{}",
+            doc.title,
+            note,
+            body.trim_end(),
+            related::footer(&related)
+        );
+        structured.related = Some(related);
+        structured.format = Some(format.as_str());
+        Ok(ToolOutput::structured(format.render(&text), structured))
+    }
+
+    /// Ranked reading list of sections for a task description
+    pub fn recommend_sections(
+        &self,
+        task: &str,
+        version: Option<&str>,
+        limit: usize,
+    ) -> Result<ToolOutput<Recommendations>, String> {
+        if task.trim().is_empty() {
+            return Err("Missing 'task' argument".to_string());
+        }
+        let version = self.resolve_version(version)?;
+        let recommendations = recommend::recommend(task, version, limit);
+        if recommendations.is_empty() {
+            let text = format!(
+                "No sections match '{}'. Use list-sections to browse available sections.",
+                task
+            );
+            return Ok(ToolOutput::structured(
+                text,
+                Recommendations { recommendations },
+            ));
+        }
+        let lines: Vec<String> = recommendations
+            .iter()
+            .enumerate()
+            .map(|(i, r)| format!("{}. {} ({})\n   {}", i + 1, r.title, r.address, r.reason))
+            .collect();
+        let text = format!(
+            "Reading list for Leptos {}:\n\n{}",
+            version.as_str(),
+            lines.join("\n")
+        );
+        Ok(ToolOutput::structured(
+            text,
+            Recommendations { recommendations },
+        ))
+    }
+
+    /// Search documentation subsections by keyword or by meaning
+    ///
+    /// Returns `limit` results from `cursor` on; the output ends with the
+    /// cursor of the next page when there are more.
+    pub fn search_docs(
+        &self,
+        query: &str,
+        mode: SearchMode,
+        version: Option<&str>,
+        limit: usize,
+        cursor: Option<&str>,
+        max_chars: Option<usize>,
+    ) -> Result<ToolOutput<SearchResults>, String> {
+        let version = self.resolve_version(version)?;
+        let offset = parse_cursor(cursor)?;
+        // One hit past the page tells whether there is a next one
+        let mut hits = match mode {
+            SearchMode::Keyword => docs::search(query, version, offset + limit + 1),
+            SearchMode::Semantic => semantic::search(query, version, offset + limit + 1)
+                .map_err(|e| format!("Semantic search failed: {}", e))?,
+        };
+        if hits.len() <= offset {
+            let text = if offset == 0 {
+                format!(
+                    "No documentation matches '{}'. Use list-sections to browse available sections.",
+                    query
+                )
+            } else {
+                format!("No more results for '{}'.", query)
+            };
+            let structured = SearchResults {
+                results: Vec::new(),
+                next_cursor: None,
+            };
+            return Ok(ToolOutput::structured(text, structured));
+        }
+        let more = hits.len() > offset + limit;
+        hits.truncate(offset + limit);
+
+        let entries: Vec<String> = hits[offset..]
+            .iter()
+            .enumerate()
+            .map(|(i, hit)| {
+                let label = if hit.heading.is_empty() {
+                    hit.section_title.clone()
+                } else {
+                    format!("{} > {}", hit.section_title, hit.heading)
+                };
+                if hit.excerpt.is_empty() {
+                    format!("{}. {} ({})", offset + i + 1, label, hit.address())
+                } else {
+                    format!(
+                        "{}. {} ({})\n   {}",
+                        offset + i + 1,
+                        label,
+                        hit.address(),
+                        hit.excerpt
+                    )
+                }
+            })
+            .collect();
+
+        // Drop whole entries rather than cutting one in half
+        let mut output = String::new();
+        let mut shown = 0;
+        for entry in &entries {
+            let extra = if output.is_empty() { 0 } else { 1 };
+            if max_chars.is_some_and(|max| shown > 0 && output.len() + extra + entry.len() > max) {
+                break;
+            }
+            if extra > 0 {
+                output.push('\n');
+            }
+            output.push_str(entry);
+            shown += 1;
+        }
+        let next = offset + shown;
+        let structured = SearchResults {
+            results: hits[offset..next]
+                .iter()
+                .map(|hit| SearchResult {
+                    section_title: hit.section_title.clone(),
+                    section_path: hit.section_path.clone(),
+                    heading: hit.heading.clone(),
+                    address: hit.address(),
+                    score: hit.score,
+                    excerpt: hit.excerpt.clone(),
+                })
+                .collect(),
+            next_cursor: (more || shown < entries.len()).then(|| next.to_string()),
+        };
+        if let Some(max) = max_chars.filter(|_| shown < entries.len()) {
+            output.push_str(&format!(
+                "\n\n[Truncated: {} more result(s) omitted to fit max_chars={}. Continue with cursor \"{}\", raise the budget or narrow the query.]",
+                entries.len() - shown,
+                max,
+                next
+            ));
+        } else if more {
+            output.push_str(&format!(
+                "\n\n[More results: continue with cursor \"{}\".]",
+                next
+            ));
+        }
+        Ok(ToolOutput::structured(output, structured))
+    }
+
+    /// Extract the Rust code examples of a section, labelled by heading
+    pub fn get_examples(
+        &self,
+        section: &str,
+        version: Option<&str>,
+        lang: Option<&str>,
+        format: DocFormat,
+    ) -> Result<ToolOutput<Examples>, String> {
+        let version = self.resolve_version(version)?;
+        let language = lang.map(Language::parse).transpose()?.unwrap_or_default();
+        let Some(doc) = docs::get_section(section, version) else {
+            return Ok(section_not_found(section, version));
+        };
+        let translated = doc.translated(language);
+        let doc = translated.as_ref().unwrap_or(&doc);
+
+        let blocks: Vec<_> = markdown::code_blocks(&doc.content)
+            .into_iter()
+            .filter(|b| b.lang == "rust")
+            .collect();
+        let label = |heading: &str| {
+            if heading.is_empty() {
+                doc.title.to_string()
+            } else {
+                heading.to_string()
+            }
+        };
+        let structured = Examples {
+            found: true,
+            section: Some(SectionInfo::from(doc)),
+            examples: Some(
+                blocks
+                    .iter()
+                    .map(|b| Example {
+                        heading: label(&b.heading),
+                        code: b.code.clone(),
+                    })
+                    .collect(),
+            ),
+            suggestions: None,
+        };
+
+        if blocks.is_empty() {
+            let text = format!("Section '{}' has no Rust code examples.", doc.path);
+            return Ok(ToolOutput::structured(text, structured));
+        }
+        let examples: Vec<String> = blocks
+            .iter()
+            .enumerate()
+            .map(|(i, b)| {
+                format!(
+                    "### {}. {}\n\n```rust\n{}```",
+                    i + 1,
+                    label(&b.heading),
+                    b.code
+                )
+            })
+            .collect();
+        let text = format!(
+            "# {} examples ({})\n\n{}",
+            doc.title,
+            examples.len(),
+            examples.join("\n\n")
+        );
+        Ok(ToolOutput::structured(format.render(&text), structured))
+    }
+
+    /// Snippets in the library, optionally only those tagged `tag`
+    pub fn list_snippets(&self, tag: Option<&str>) -> ToolOutput<SnippetList> {
+        let found = snippets::list(tag);
+        let text = if found.is_empty() {
             format!(
-                "Section '{}' not found. Use list-sections to see available sections.",
-                section
+                "No snippets tagged '{}'. Tags: {}",
+                tag.unwrap_or_default(),
+                snippets::tags().join(", ")
             )
+        } else {
+            found
+                .iter()
+                .map(|s| format!("* {} ({}): {}", s.name, s.tags.join(", "), s.description))
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        let structured = SnippetList {
+            snippets: found,
+            tags: snippets::tags(),
+        };
+        ToolOutput::structured(text, structured)
+    }
+
+    /// A snippet's code, by name
+    pub fn get_snippet(&self, name: &str) -> Result<ToolOutput<SnippetLookup>, String> {
+        if name.trim().is_empty() {
+            return Err("Missing 'name' argument".to_string());
         }
+        let Some(snippet) = snippets::get(name) else {
+            let candidates = snippets::suggest(name, 3);
+            let text = if candidates.is_empty() {
+                format!(
+                    "Snippet '{}' not found. Use list-snippets to see available snippets.",
+                    name
+                )
+            } else {
+                let names: Vec<String> = candidates
+                    .iter()
+                    .map(|s| format!("* {} ({})", s.name, s.title))
+                    .collect();
+                format!(
+                    "Snippet '{}' not found. Did you mean:\n{}",
+                    name,
+                    names.join("\n")
+                )
+            };
+            let structured = SnippetLookup {
+                found: false,
+                snippet: None,
+                suggestions: Some(candidates),
+            };
+            return Ok(ToolOutput::structured(text, structured));
+        };
+        Ok(snippet_output(snippet))
+    }
+
+    /// Short definition of a Leptos term, with its types and docs section
+    pub fn define_term(&self, term: &str) -> Result<ToolOutput<TermLookup>, String> {
+        if term.trim().is_empty() {
+            return Err("Missing 'term' argument".to_string());
+        }
+        let Some(found) = glossary::get(term) else {
+            let candidates = glossary::suggest(term, 3);
+            let text = if candidates.is_empty() {
+                let terms: Vec<&str> = glossary::TERMS.iter().map(|t| t.term).collect();
+                format!(
+                    "No definition of '{}'. Defined terms: {}",
+                    term,
+                    terms.join(", ")
+                )
+            } else {
+                let names: Vec<String> =
+                    candidates.iter().map(|t| format!("* {}", t.term)).collect();
+                format!(
+                    "No definition of '{}'. Did you mean:\n{}",
+                    term,
+                    names.join("\n")
+                )
+            };
+            let structured = TermLookup {
+                found: false,
+                term: None,
+                suggestions: Some(candidates),
+            };
+            return Ok(ToolOutput::structured(text, structured));
+        };
+        let text = format!(
+            "# {}\n\n{}\n\nTypes: {}\n\nFull section: {} (get-documentation)",
+            found.term,
+            found.definition,
+            found.types.join(", "),
+            found.doc
+        );
+        let structured = TermLookup {
+            found: true,
+            term: Some(found),
+            suggestions: None,
+        };
+        Ok(ToolOutput::structured(text, structured))
+    }
+
+    /// Vetted crates for a need, with Cargo.toml lines and usage for the
+    /// session's Leptos version and backend
+    pub fn suggest_dependencies(
+        &self,
+        need: &str,
+        version: Option<&str>,
+    ) -> Result<ToolOutput<DependencyAdvice>, String> {
+        if need.trim().is_empty() {
+            return Err("Missing 'need' argument".to_string());
+        }
+        let version = self.resolve_version(version)?;
+        let backend = self.backend();
+        let needs = dependencies::find(need, 3);
+        if needs.is_empty() {
+            let available: Vec<&str> = dependencies::NEEDS.iter().map(|n| n.id).collect();
+            let text = format!(
+                "No vetted crates for '{}'. Covered needs: {}",
+                need,
+                available.join(", ")
+            );
+            let structured = DependencyAdvice {
+                version: version.as_str(),
+                needs: Vec::new(),
+                available: Some(available),
+            };
+            return Ok(ToolOutput::structured(text, structured));
+        }
+
+        let mut sections = Vec::new();
+        let mut structured = Vec::new();
+        for need in needs {
+            let crates = need.advice(version, backend);
+            let mut section = format!("# {}\n", need.title);
+            if let Some(note) = need.note {
+                section.push_str(&format!("\n{}\n", note));
+            }
+            if crates.is_empty() {
+                section.push_str(&format!(
+                    "\nNo vetted crate supports Leptos {} yet.\n",
+                    version.as_str()
+                ));
+            }
+            for advice in &crates {
+                section.push_str(&format!(
+                    "\n## {} {}\n\n{}\n\n```toml\n{}```\n",
+                    advice.name, advice.version, advice.purpose, advice.cargo_toml
+                ));
+                if !advice.usage.is_empty() {
+                    section.push_str(&format!("\n```rust\n{}\n```\n", advice.usage));
+                }
+            }
+            sections.push(section);
+            structured.push(NeedAdvice {
+                id: need.id,
+                title: need.title,
+                note: need.note,
+                crates,
+            });
+        }
+        let text = format!(
+            "Leptos {} ({} backend)\n\n{}",
+            version.as_str(),
+            backend.as_str(),
+            sections.join("\n")
+        );
+        let structured = DependencyAdvice {
+            version: version.as_str(),
+            needs: structured,
+            available: None,
+        };
+        Ok(ToolOutput::structured(text, structured))
+    }
+
+    /// Look up the signature and docs of a Leptos API item
+    pub fn lookup_api(
+        &self,
+        path: &str,
+        version: Option<&str>,
+    ) -> Result<ToolOutput<ApiLookup>, String> {
+        let version = self.resolve_version(version)?;
+        let coverage = api::reference(version)?.coverage();
+        let (text, items, candidates) = match api::lookup(path, version)? {
+            Lookup::Found(items) => {
+                let entries: Vec<String> = items.iter().map(format_api_item).collect();
+                (entries.join("\n\n"), items, Vec::new())
+            }
+            Lookup::NotFound(candidates) if candidates.is_empty() => (
+                format!(
+                    "No Leptos {} API item matches '{}'. {}",
+                    version.as_str(),
+                    path,
+                    coverage
+                ),
+                Vec::new(),
+                candidates,
+            ),
+            Lookup::NotFound(candidates) => (
+                format!(
+                    "No Leptos {} API item matches '{}'. Did you mean:\n{}\n\n{}",
+                    version.as_str(),
+                    path,
+                    candidates
+                        .iter()
+                        .map(|c| format!("* {}", c))
+                        .collect::<Vec<_>>()
+                        .join("\n"),
+                    coverage
+                ),
+                Vec::new(),
+                candidates,
+            ),
+        };
+        let structured = ApiLookup {
+            version: version.as_str(),
+            items,
+            candidates,
+        };
+        Ok(ToolOutput::structured(text, structured))
+    }
+
+    /// Markdown reference of the items matching `path` in the session version,
+    /// as served for `leptos-api://` resources
+    pub fn api_reference(&self, path: &str) -> Result<String, String> {
+        let version = self.version();
+        match api::lookup(path, version)? {
+            Lookup::Found(items) => Ok(items
+                .iter()
+                .map(format_api_item)
+                .collect::<Vec<_>>()
+                .join("\n\n")),
+            Lookup::NotFound(candidates) if candidates.is_empty() => Err(format!(
+                "No Leptos {} API item matches '{}'. {}",
+                version.as_str(),
+                path,
+                api::reference(version)?.coverage()
+            )),
+            Lookup::NotFound(candidates) => Err(format!(
+                "No Leptos {} API item matches '{}'. Did you mean: {}. {}",
+                version.as_str(),
+                path,
+                candidates.join(", "),
+                api::reference(version)?.coverage()
+            )),
+        }
+    }
+
+    /// Renamed, removed, added and changed API items between two versions
+    ///
+    /// `to` defaults to the session version; `item` limits the report to
+    /// entries involving matching paths.
+    pub fn api_diff(
+        &self,
+        from: &str,
+        to: Option<&str>,
+        item: Option<&str>,
+    ) -> Result<ToolOutput<ApiDiff>, String> {
+        if from.trim().is_empty() {
+            return Err("Missing 'from' argument".to_string());
+        }
+        let from = self.resolve_version(Some(from))?;
+        let to = self.resolve_version(to)?;
+        if from == to {
+            return Err(format!(
+                "'from' and 'to' are both Leptos {}; pass two different versions",
+                from.as_str()
+            ));
+        }
+        let item = item.filter(|i| !i.trim().is_empty());
+        let diff = api::diff(from, to, item)?;
+        Ok(ToolOutput::structured(format_api_diff(&diff, item), diff))
+    }
+
+    /// Changelog entries an upgrade from `from` to `to` crosses, by kind
+    ///
+    /// `to` defaults to the session version.
+    pub fn get_changelog(
+        &self,
+        from: &str,
+        to: Option<&str>,
+    ) -> Result<ToolOutput<output::Changelog>, String> {
+        if from.trim().is_empty() {
+            return Err("Missing 'from_version' argument".to_string());
+        }
+        let to = to
+            .filter(|v| !v.trim().is_empty())
+            .map_or_else(|| self.version().as_str().to_string(), str::to_string);
+        let entries = changelog::between(from, &to)?;
+
+        let mut structured = output::Changelog {
+            from: from.to_string(),
+            to: to.clone(),
+            breaking: Vec::new(),
+            features: Vec::new(),
+            fixes: Vec::new(),
+        };
+        let mut sections = vec![format!("# Leptos changes from {} to {}", from, to)];
+        for category in Category::ALL {
+            let group: Vec<&changelog::Entry> =
+                entries.iter().filter(|e| e.category == category).collect();
+            let listed = match category {
+                Category::Breaking => &mut structured.breaking,
+                Category::Feature => &mut structured.features,
+                Category::Fix => &mut structured.fixes,
+            };
+            listed.extend(group.iter().map(|e| ChangelogEntry {
+                version: e.version.clone(),
+                text: e.text.clone(),
+            }));
+            if group.is_empty() {
+                continue;
+            }
+            let lines: Vec<String> = group
+                .iter()
+                .map(|e| format!("- **{}**: {}", e.version, e.text))
+                .collect();
+            sections.push(format!(
+                "## {} ({})\n\n{}",
+                category.title(),
+                group.len(),
+                lines.join("\n")
+            ));
+        }
+        if entries.is_empty() {
+            sections.push(format!(
+                "No changelog entries after {} up to {}. Releases covered: {}",
+                from,
+                to,
+                changelog::releases().join(", ")
+            ));
+        }
+        Ok(ToolOutput::structured(sections.join("\n\n"), structured))
+    }
+
+    /// Check statements about Leptos behavior against the fact base, citing
+    /// the documentation each verdict rests on
+    pub fn verify_claims(
+        &self,
+        claims: &[String],
+        version: Option<&str>,
+    ) -> Result<ToolOutput<ClaimChecks>, String> {
+        let claims: Vec<&str> = claims
+            .iter()
+            .map(|claim| claim.trim())
+            .filter(|claim| !claim.is_empty())
+            .collect();
+        if claims.is_empty() {
+            return Err("Missing 'claims' argument".to_string());
+        }
+        let version = self.resolve_version(version)?;
+        let checks: Vec<facts::Check> = claims
+            .iter()
+            .map(|claim| facts::verify(claim, version))
+            .collect();
+        let count = |verdict| checks.iter().filter(|c| c.verdict == verdict).count();
+
+        let mut text = format!(
+            "Checked {} claim(s) against Leptos {}: {} confirmed, {} contradicted, {} unknown",
+            checks.len(),
+            version.as_str(),
+            count(Verdict::Confirmed),
+            count(Verdict::Contradicted),
+            count(Verdict::Unknown)
+        );
+        for (i, check) in checks.iter().enumerate() {
+            let mark = match check.verdict {
+                Verdict::Confirmed => "✓",
+                Verdict::Contradicted => "✗",
+                Verdict::Unknown => "?",
+            };
+            text.push_str(&format!(
+                "\n\n{}. {} {}: {}",
+                i + 1,
+                mark,
+                check.verdict.as_str(),
+                check.claim
+            ));
+            match (check.fact, check.doc) {
+                (Some(fact), Some(doc)) => text.push_str(&format!(
+                    "\n   Fact: {}\n   Source: get-documentation section=\"{}\"",
+                    fact, doc
+                )),
+                _ => text.push_str("\n   No fact covers this claim; check it with search-docs."),
+            }
+        }
+        let structured = ClaimChecks {
+            version: version.as_str(),
+            checks,
+        };
+        Ok(ToolOutput::structured(text, structured))
     }
 
     /// Analyze Leptos code and suggest fixes
-    pub fn leptos_autofixer(&self, code: &str) -> String {
-        let mut suggestions = Vec::new();
-
-        // TODO: Add more checks
-        
-        // Check for common issues
-
-        // 1. Check for direct .get() in view without move ||
-        if code.contains(".get()") && !code.contains("move ||") && code.contains("view!") {
-            suggestions.push(
-                "ERROR: Found .get() in view without `move ||`. \
-                 Reactive values should use `{move || value.get()}`",
+    ///
+    /// `rules` is layered over the `leptos-mcp.toml` in the project context's
+    /// root (or the working directory), which is layered over the server's base
+    /// rules.
+    pub fn leptos_autofixer(
+        &self,
+        code: &str,
+        rules: Option<RuleConfig>,
+        format: OutputFormat,
+    ) -> Result<ToolOutput<Findings>, String> {
+        let root = self.project_root();
+        let config = self.rule_config(&root, rules)?;
+        let styling = self.styling(&root);
+        let structured = Findings {
+            diagnostics: Some(config.apply(rules::check_styled(code, &styling))),
+            ..Findings::default()
+        };
+        let diagnostics = structured.diagnostics.as_deref().unwrap_or_default();
+
+        let text = match format {
+            OutputFormat::Json => pretty_json(&structured),
+            OutputFormat::Text if diagnostics.is_empty() => {
+                "✓ No issues found. Code looks good!".to_string()
+            }
+            OutputFormat::Text => {
+                let lines: Vec<String> = diagnostics.iter().map(|d| d.to_string()).collect();
+                lines.join("\n")
+            }
+        };
+        Ok(ToolOutput::structured(text, structured))
+    }
+
+    /// Analyze several files of one project together
+    ///
+    /// Besides the per-file checks, runs the cross-file ones `analyze-project`
+    /// runs: components reachable through `mod` and `use`, server functions
+    /// only built with `ssr`, duplicate routes, contexts and server actions.
+    /// Paths are the files' paths in the project, e.g. `src/app.rs`.
+    pub fn leptos_autofixer_files(
+        &self,
+        sources: &[SourceFile],
+        rules: Option<RuleConfig>,
+        format: OutputFormat,
+        progress: &Progress,
+    ) -> Result<ToolOutput<Findings>, String> {
+        if sources.is_empty() {
+            return Err("'files' is empty".to_string());
+        }
+        let root = self.project_root();
+        let config = self.rule_config(&root, rules)?;
+        let styling = self.styling(&root);
+        let report = project::analyze_sources(
+            sources,
+            Workspace::load(&root),
+            |code| rules::check_styled(code, &styling),
+            progress,
+        )?;
+        let (mut files, total) = file_diagnostics(report.files, &config, report.workspace.as_ref());
+        self.record_fixes(&root, &mut files);
+        let structured = Findings {
+            files_scanned: Some(report.files_scanned),
+            total: Some(total),
+            files: Some(files),
+            ..Findings::default()
+        };
+        let files = structured.files.as_deref().unwrap_or_default();
+        let text = match format {
+            OutputFormat::Json => pretty_json(&structured),
+            OutputFormat::Text if files.is_empty() => format!(
+                "✓ No issues found in {} file(s). Code looks good!",
+                sources.len()
+            ),
+            OutputFormat::Text => format!(
+                "Checked {} file(s): {} finding(s) in {} file(s){}",
+                sources.len(),
+                total,
+                files.len(),
+                files_text(files)
+            ),
+        };
+        Ok(ToolOutput::structured(text, structured))
+    }
+
+    /// List the lint rules with their ids and default severities
+    pub fn list_rules(&self) -> ToolOutput<RuleList> {
+        let rules = rules::all_rules();
+        let lines: Vec<String> = rules
+            .iter()
+            .map(|r| {
+                format!(
+                    "* {} {} [{}] ({}): {}",
+                    r.id,
+                    r.name,
+                    r.category.as_str(),
+                    r.default_severity.as_str(),
+                    r.summary
+                )
+            })
+            .collect();
+        ToolOutput::structured(lines.join("\n"), RuleList { rules })
+    }
+
+    /// A rule's summary, example and the documentation subsection behind it
+    pub fn explain_rule(
+        &self,
+        key: &str,
+        version: Option<&str>,
+    ) -> Result<ToolOutput<RuleExplanation>, String> {
+        let version = self.resolve_version(version)?;
+        let rule = rules::find_rule(key.trim()).ok_or_else(|| {
+            format!(
+                "Unknown rule '{}'. Use list-rules to see rule ids and names",
+                key
+            )
+        })?;
+        let mut text = format!(
+            "# {} {}\n\nCategory: {} · Default severity: {}\n\n{}",
+            rule.id,
+            rule.name,
+            rule.category.as_str(),
+            rule.default_severity.as_str(),
+            rule.summary
+        );
+        if let Some(example) = rule.example {
+            text.push_str(&format!(
+                "\n\n## Example\n\nFlagged:\n\n```rust\n{}\n```\n\nFixed:\n\n```rust\n{}\n```",
+                example.bad, example.good
+            ));
+        }
+        // Built-in rules cite `section#heading`; plugin rules may link anywhere
+        let section = rule
+            .doc
+            .filter(|doc| !doc.starts_with("http"))
+            .and_then(|doc| {
+                let (path, anchor) = doc.split_once('#').unwrap_or((doc, ""));
+                let section = docs::get_section(path, version)?;
+                let rationale = match section.find_heading(anchor) {
+                    Some(heading) => section.subsection(heading).to_string(),
+                    None => section.content_for(self.syntax(), self.backend()),
+                };
+                Some(rationale)
+            });
+        match (&section, rule.doc) {
+            (Some(rationale), Some(doc)) => text.push_str(&format!(
+                "\n\nWhy, from get-documentation section=\"{}\":\n\n{}",
+                doc,
+                rationale.trim_end()
+            )),
+            (None, Some(doc)) => text.push_str(&format!("\n\nDocs: {}", doc)),
+            _ => {}
+        }
+        let structured = RuleExplanation {
+            rule,
+            rationale: section,
+        };
+        Ok(ToolOutput::structured(text, structured))
+    }
+
+    /// Generate a `#[component]` function from a spec
+    pub fn generate_component(
+        &self,
+        spec: &ComponentSpec,
+    ) -> Result<ToolOutput<Generated>, String> {
+        if spec.store && self.version() == LeptosVersion::V0_6 {
+            return Err("Stores need Leptos 0.7 or later; the session targets 0.6".to_string());
+        }
+        let mut blocks = vec![generate::component(spec, self.version())?];
+        if spec.store {
+            blocks.push(generate::store_dependency(self.version()));
+        }
+        Ok(generated(blocks))
+    }
+
+    /// Generate a server function with its ActionForm usage and Cargo features
+    pub fn generate_server_fn(&self, spec: &ServerFnSpec) -> Result<ToolOutput<Generated>, String> {
+        generate::server_fn(spec, self.backend()).map(generated)
+    }
+
+    /// Generate a custom server function error type, its `From` impls and an
+    /// `ErrorBoundary` fallback rendering it
+    pub fn generate_error_type(
+        &self,
+        spec: &ErrorTypeSpec,
+    ) -> Result<ToolOutput<Generated>, String> {
+        let version = self.resolve_version(spec.version.as_deref())?;
+        generate::error_type(spec, version).map(generated)
+    }
+
+    /// Generate a validated form component with its server function
+    pub fn generate_form(&self, spec: &FormSpec) -> Result<ToolOutput<Generated>, String> {
+        generate::form(spec).map(generated)
+    }
+
+    /// Generate a route declaration, its page component and an optional lazy wrapper
+    pub fn generate_route(&self, spec: &RouteSpec) -> Result<ToolOutput<Generated>, String> {
+        generate::route(spec).map(generated)
+    }
+
+    /// Generate browser tests for the components and tokio tests for the
+    /// server functions in `code`
+    pub fn generate_tests(
+        &self,
+        code: &str,
+        name: Option<&str>,
+        version: Option<&str>,
+    ) -> Result<ToolOutput<Generated>, String> {
+        if code.trim().is_empty() {
+            return Err("Missing 'code' argument".to_string());
+        }
+        let version = self.resolve_version(version)?;
+        testgen::tests(code, name, version, self.backend()).map(generated)
+    }
+
+    /// Generate the file set of a new Leptos project
+    pub fn scaffold_project(
+        &self,
+        spec: &ProjectSpec,
+    ) -> Result<ToolOutput<ScaffoldedProject>, String> {
+        let version = self.resolve_version(spec.version.as_deref())?;
+        let backend = spec.backend.unwrap_or_else(|| self.backend());
+        let project = scaffold::project(spec, version, backend, self.syntax())?;
+
+        let mut text = format!(
+            "# {} (Leptos {}, {})\n",
+            spec.name,
+            version.as_str(),
+            match spec.mode {
+                RenderMode::Csr => "client-side rendered with Trunk".to_string(),
+                RenderMode::Ssr => format!("SSR with {} and cargo-leptos", backend.as_str()),
+                RenderMode::Islands =>
+                    format!("islands with {} and cargo-leptos", backend.as_str()),
+            }
+        );
+        for file in &project.files {
+            let language = match file.path.rsplit_once('.').map(|(_, ext)| ext) {
+                Some("rs") => "rust",
+                Some("toml") => "toml",
+                Some("html") => "html",
+                Some("css") => "css",
+                Some("scss") => "scss",
+                _ => "text",
+            };
+            text.push_str(&format!(
+                "\n## {}\n\n```{}\n{}```\n",
+                file.path, language, file.content
+            ));
+        }
+        text.push_str("\nNext steps:\n");
+        for (i, step) in project.next_steps.iter().enumerate() {
+            text.push_str(&format!("{}. {}\n", i + 1, step));
+        }
+
+        let structured = ScaffoldedProject {
+            name: spec.name.clone(),
+            version: version.as_str(),
+            mode: spec.mode,
+            backend: (spec.mode != RenderMode::Csr).then_some(backend),
+            styling: spec.styling,
+            files: project.files,
+            next_steps: project.next_steps,
+        };
+        Ok(ToolOutput::structured(text, structured))
+    }
+
+    /// Convert an HTML fragment into `view!` markup
+    pub fn html_to_view(&self, html: &str, style: StyleMode) -> Result<ToolOutput<View>, String> {
+        if html.trim().is_empty() {
+            return Err("Missing 'html' argument".to_string());
+        }
+        let view = html::to_view(html, style);
+        Ok(ToolOutput::structured(view.clone(), View { view }))
+    }
+
+    /// Format the `view!` macros in `code`, or show the changes as a diff
+    pub fn format_view(
+        &self,
+        code: &str,
+        options: FormatOptions,
+        diff: bool,
+    ) -> Result<ToolOutput<Formatted>, String> {
+        if code.trim().is_empty() {
+            return Err("Missing 'code' argument".to_string());
+        }
+        let formatted = format::format_views(code, options)?;
+        let changed = formatted != code;
+        let mut structured = Formatted {
+            changed,
+            formatted: formatted.clone(),
+            diff: None,
+        };
+        if !diff {
+            return Ok(ToolOutput::structured(formatted, structured));
+        }
+        if !changed {
+            return Ok(ToolOutput::structured(
+                "✓ Already formatted".to_string(),
+                structured,
+            ));
+        }
+        let patch = format::unified_diff(code, &formatted);
+        structured.diff = Some(patch.clone());
+        Ok(ToolOutput::structured(patch, structured))
+    }
+
+    /// The `use` statements for the Leptos names `code` uses without importing
+    pub fn suggest_imports(
+        &self,
+        code: &str,
+        version: Option<&str>,
+    ) -> Result<ToolOutput<ImportSuggestions>, String> {
+        if code.trim().is_empty() {
+            return Err("Missing 'code' argument".to_string());
+        }
+        let version = self.resolve_version(version)?;
+        let suggestion = imports::suggest(code, version, self.backend());
+        let mut text = match suggestion.statements.is_empty() {
+            true => "✓ Every Leptos name in the code is already imported".to_string(),
+            false => suggestion.statements.join("\n"),
+        };
+        if !suggestion.unavailable.is_empty() {
+            let names: Vec<String> = suggestion
+                .unavailable
+                .iter()
+                .map(|n| format!("`{}`", n))
+                .collect();
+            text.push_str(&format!(
+                "\n\nNot in Leptos {}: {} (see api-diff for replacements)",
+                version.as_str(),
+                names.join(", ")
+            ));
+        }
+        let structured = ImportSuggestions {
+            version: version.as_str(),
+            suggestion,
+        };
+        Ok(ToolOutput::structured(text, structured))
+    }
+
+    /// Fetch the latest Leptos book and merge it over the embedded sections
+    pub fn sync_docs(
+        &self,
+        force: bool,
+        progress: &Progress,
+    ) -> Result<ToolOutput<SyncSummary>, String> {
+        let report = sync::sync(force, progress)?;
+        let mut text = format!(
+            "✓ Synced {} section(s) from the Leptos book ({} page(s) updated, {} unchanged)\n\nSections: {}",
+            report.sections.len(),
+            report.updated,
+            report.unchanged,
+            report.sections.join(", ")
+        );
+        if !report.failed.is_empty() {
+            text.push_str(&format!(
+                "\n\n⚠ {} page(s) could not be fetched; cached copies are used where available:",
+                report.failed.len()
+            ));
+            for (page, reason) in &report.failed {
+                text.push_str(&format!("\n- {}: {}", page, reason));
+            }
+        }
+        let structured = SyncSummary {
+            sections: report.sections,
+            updated: report.updated,
+            unchanged: report.unchanged,
+            failed: report
+                .failed
+                .into_iter()
+                .map(|(page, reason)| FailedPage { page, reason })
+                .collect(),
+        };
+        Ok(ToolOutput::structured(text, structured))
+    }
+
+    /// Request counts, outcomes and latencies per method and tool since start
+    pub fn server_stats(&self) -> ToolOutput<Statistics> {
+        let stats = metrics::snapshot();
+        let uptime = metrics::uptime().as_secs();
+        let requests: u64 = stats.iter().map(|s| s.requests).sum();
+        let mut text = format!(
+            "# Server statistics\n\n{} request(s) in {}h {:02}m {:02}s",
+            requests,
+            uptime / 3600,
+            uptime / 60 % 60,
+            uptime % 60
+        );
+        if !stats.is_empty() {
+            text.push_str("\n\n| Method / tool | Requests | Errors | Tool errors | Cancelled | Rate limited | Timed out | Mean | p95 | Max |\n");
+            text.push_str("| --- | ---: | ---: | ---: | ---: | ---: | ---: | ---: | ---: | ---: |");
+            for s in &stats {
+                text.push_str(&format!(
+                    "\n| {} | {} | {} | {} | {} | {} | {} | {:.1} ms | ≤{} ms | {} ms |",
+                    s.tool.as_deref().unwrap_or(&s.method),
+                    s.requests,
+                    s.errors,
+                    s.tool_errors,
+                    s.cancelled,
+                    s.rate_limited,
+                    s.timed_out,
+                    s.mean_ms,
+                    s.p95_ms,
+                    s.max_ms
+                ));
+            }
+        }
+        let structured = Statistics {
+            uptime_secs: uptime,
+            requests,
+            stats,
+        };
+        ToolOutput::structured(text, structured)
+    }
+
+    /// Compile a snippet with `cargo check` against a pinned Leptos version
+    pub fn check_snippet(
+        &self,
+        code: &str,
+        version: Option<&str>,
+        features: &[String],
+        timeout_secs: Option<u64>,
+        format: OutputFormat,
+        progress: &Progress,
+    ) -> Result<ToolOutput<CheckResult>, String> {
+        if code.trim().is_empty() {
+            return Err("Missing 'code' argument".to_string());
+        }
+        let version = self.resolve_version(version)?;
+        let timeout = Duration::from_secs(timeout_secs.unwrap_or(sandbox::DEFAULT_TIMEOUT_SECS));
+        let result = sandbox::check(code, version, features, timeout, progress)?;
+        if format == OutputFormat::Json {
+            return Ok(ToolOutput::structured(pretty_json(&result), result));
+        }
+
+        let errors = result
+            .diagnostics
+            .iter()
+            .filter(|d| d.level == "error")
+            .count();
+        let mut text = if result.success {
+            format!(
+                "✓ Compiles against Leptos {} ({} warning(s))",
+                result.leptos_version,
+                result.diagnostics.len()
+            )
+        } else {
+            format!(
+                "✗ Does not compile against Leptos {}: {} error(s)",
+                result.leptos_version, errors
+            )
+        };
+        for d in &result.diagnostics {
+            let code = d
+                .code
+                .as_deref()
+                .map_or(String::new(), |c| format!("[{}]", c));
+            let location = match (d.line, d.column) {
+                (Some(line), Some(column)) => format!(" {}:{}", line, column),
+                _ => " (generated wrapper)".to_string(),
+            };
+            text.push_str(&format!(
+                "\n\n{}{}{}: {}\n{}",
+                d.level.to_uppercase(),
+                code,
+                location,
+                d.message,
+                d.rendered.trim_end()
+            ));
+        }
+        Ok(ToolOutput::structured(text, result))
+    }
+
+    /// Render a snippet on the server and return its HTML
+    pub fn render_preview(
+        &self,
+        code: &str,
+        component: Option<&str>,
+        version: Option<&str>,
+        markers: bool,
+        timeout_secs: Option<u64>,
+        progress: &Progress,
+    ) -> Result<ToolOutput<RenderResult>, String> {
+        if code.trim().is_empty() {
+            return Err("Missing 'code' argument".to_string());
+        }
+        let version = self.resolve_version(version)?;
+        let timeout = Duration::from_secs(timeout_secs.unwrap_or(sandbox::DEFAULT_TIMEOUT_SECS));
+        let result = sandbox::render(code, version, component, markers, timeout, progress)?;
+        let Some(html) = &result.html else {
+            let errors: Vec<String> = result
+                .diagnostics
+                .iter()
+                .filter(|d| d.level == "error")
+                .map(|d| {
+                    let location = match (d.line, d.column) {
+                        (Some(line), Some(column)) => format!(" {}:{}", line, column),
+                        _ => " (generated wrapper)".to_string(),
+                    };
+                    format!(
+                        "ERROR{}: {}\n{}",
+                        location,
+                        d.message,
+                        d.rendered.trim_end()
+                    )
+                })
+                .collect();
+            let text = format!(
+                "✗ Does not compile against Leptos {} with `ssr`: {} error(s)\n\n{}",
+                result.leptos_version,
+                errors.len(),
+                errors.join("\n\n")
             );
+            return Ok(ToolOutput::structured(text, result));
+        };
+        let what = result
+            .component
+            .as_deref()
+            .map_or("the view".to_string(), |c| format!("`<{} />`", c));
+        let text = format!(
+            "Server-rendered HTML of {} (Leptos {}):\n\n```html\n{}\n```",
+            what, result.leptos_version, html
+        );
+        Ok(ToolOutput::structured(text, result))
+    }
+
+    /// Run `cargo check` in a project and explain its Leptos-specific errors
+    ///
+    /// Without `path`, the project context's root is checked.
+    pub fn cargo_check(
+        &self,
+        path: Option<&str>,
+        files: &[String],
+        features: &[String],
+        timeout_secs: Option<u64>,
+        format: OutputFormat,
+        progress: &Progress,
+    ) -> Result<ToolOutput<diagnostics::Report>, String> {
+        let root = path.map_or_else(|| self.project_root(), PathBuf::from);
+        let timeout = Duration::from_secs(timeout_secs.unwrap_or(sandbox::DEFAULT_TIMEOUT_SECS));
+        let report = diagnostics::check(&root, files, features, timeout, progress)?;
+        if format == OutputFormat::Json {
+            return Ok(ToolOutput::structured(pretty_json(&report), report));
+        }
+
+        let mut text = match (report.success, report.diagnostics.is_empty()) {
+            (true, true) => format!("✓ {} compiles without warnings", root.display()),
+            (true, false) => format!(
+                "✓ {} compiles ({} warning(s))",
+                root.display(),
+                report.warnings
+            ),
+            (false, _) => format!(
+                "✗ {} does not compile: {} error(s), {} warning(s)",
+                root.display(),
+                report.errors,
+                report.warnings
+            ),
+        };
+        if report.filtered_out > 0 {
+            text.push_str(&format!(
+                "\n{} diagnostic(s) in other files not shown",
+                report.filtered_out
+            ));
         }
+        for d in &report.diagnostics {
+            let code = d
+                .code
+                .as_deref()
+                .map_or(String::new(), |c| format!("[{}]", c));
+            text.push_str(&format!(
+                "\n\n{}{} {}:{}:{}: {}\n{}",
+                d.level.to_uppercase(),
+                code,
+                d.file,
+                d.line,
+                d.column,
+                d.message,
+                d.rendered.trim_end()
+            ));
+            if let Some(hint) = &d.hint {
+                let fix = match hint.fix.contains('\n') {
+                    true => format!("Fix:\n```rust\n{}\n```", hint.fix),
+                    false => format!("Fix: {}", hint.fix),
+                };
+                text.push_str(&format!(
+                    "\n\nLeptos: {}. {}\n{}\nDocs: get-documentation section=\"{}\"",
+                    hint.title, hint.explanation, fix, hint.doc
+                ));
+            }
+        }
+        Ok(ToolOutput::structured(text, report))
+    }
+
+    /// Explain Leptos-specific errors in `cargo build` output
+    pub fn explain_error(
+        &self,
+        output: &str,
+        format: OutputFormat,
+    ) -> Result<ToolOutput<Explanations>, String> {
+        if output.trim().is_empty() {
+            return Err("Missing 'output' argument".to_string());
+        }
+        let (explanations, unrecognized) = explain::explain(output);
+        let structured = Explanations {
+            explanations,
+            unrecognized,
+        };
+        if format == OutputFormat::Json {
+            return Ok(ToolOutput::structured(pretty_json(&structured), structured));
+        }
+        let Explanations {
+            explanations: explained,
+            unrecognized,
+        } = &structured;
+
+        let mut sections: Vec<String> = explained
+            .iter()
+            .enumerate()
+            .map(|(i, e)| {
+                let location = e
+                    .location
+                    .as_deref()
+                    .map_or(String::new(), |l| format!(" ({})", l));
+                // Multi-line fixes are code sketches; one-liners are instructions
+                let fix = if e.fix.contains('\n') {
+                    format!("Fix:\n```rust\n{}\n```", e.fix)
+                } else {
+                    format!("Fix: {}", e.fix)
+                };
+                format!(
+                    "## {}. {}{}\n\n> {}\n\n{}\n\n{}\nDocs: get-documentation section=\"{}\"",
+                    i + 1,
+                    e.title,
+                    location,
+                    e.error,
+                    e.explanation,
+                    fix,
+                    e.doc
+                )
+            })
+            .collect();
+        if !unrecognized.is_empty() {
+            let list: Vec<String> = unrecognized.iter().map(|u| format!("* {}", u)).collect();
+            sections.push(format!(
+                "{} error(s) not recognized as Leptos-specific:\n{}",
+                unrecognized.len(),
+                list.join("\n")
+            ));
+        }
+        if explained.is_empty() && unrecognized.is_empty() {
+            sections.push("No errors found in the output.".to_string());
+        }
+        Ok(ToolOutput::structured(sections.join("\n\n"), structured))
+    }
+
+    /// Run the autofixer over every Rust source of a project directory
+    ///
+    /// Without `path`, the project context's root is analyzed. `rules` is
+    /// layered over the `leptos-mcp.toml` in the project root, which is layered
+    /// over the server's base rules.
+    pub fn analyze_project(
+        &self,
+        path: Option<&str>,
+        rules: Option<RuleConfig>,
+        format: OutputFormat,
+        progress: &Progress,
+    ) -> Result<ToolOutput<ProjectFindings>, String> {
+        let root = path.map_or_else(|| self.project_root(), PathBuf::from);
+        let root = root.as_path();
+        let config = self.rule_config(root, rules)?;
+        let styling = self.styling(root);
+        let report =
+            project::analyze_project(root, |code| rules::check_styled(code, &styling), progress)?;
+        let workspace = report.workspace.as_ref();
+        let (mut files, total) = file_diagnostics(report.files, &config, workspace);
+        self.record_fixes(root, &mut files);
+        let structured = ProjectFindings {
+            root: root.display().to_string(),
+            files_scanned: report.files_scanned,
+            total,
+            files,
+            crates: workspace.map(|workspace| workspace.members.clone()),
+        };
+        if format == OutputFormat::Json {
+            return Ok(ToolOutput::structured(pretty_json(&structured), structured));
+        }
+
+        let crates = workspace.map_or_else(String::new, |workspace| {
+            let members: Vec<String> = workspace
+                .members
+                .iter()
+                .map(|m| {
+                    format!(
+                        "{} ({})",
+                        m.name,
+                        m.target.map_or("not built by the app", |t| t.as_str())
+                    )
+                })
+                .collect();
+            format!("\nWorkspace crates: {}", members.join(", "))
+        });
+        let output = format!(
+            "Scanned {} file(s) in {}: {} finding(s) in {} file(s){}{}",
+            report.files_scanned,
+            root.display(),
+            total,
+            structured.files.len(),
+            crates,
+            files_text(&structured.files)
+        );
+        Ok(ToolOutput::structured(output, structured))
+    }
+
+    /// Give the mechanical fixes among `files`' diagnostics ids `apply-fix`
+    /// takes, replacing the fixes of the session's previous analysis
+    fn record_fixes(&self, root: &Path, files: &mut [FileFindings]) {
+        let mut fixes = self.fixes.lock().unwrap();
+        fixes.pending.clear();
+        for file in files.iter_mut() {
+            // Fixes are only offered for files inside the project
+            let Some(path) = project_path(root, &file.path) else {
+                continue;
+            };
+            let path = path.display().to_string();
+            for diagnostic in file.diagnostics.iter_mut() {
+                let Some(edit) = diagnostic.edit.clone() else {
+                    continue;
+                };
+                fixes.issued += 1;
+                let id = format!("F{}", fixes.issued);
+                diagnostic.id = Some(id.clone());
+                fixes.pending.push(PendingFix {
+                    id,
+                    rule_id: diagnostic.rule_id,
+                    root: root.to_path_buf(),
+                    path: path.clone(),
+                    edit,
+                });
+            }
+        }
+    }
 
-        // 2. Check for signal without destructuring
-        if code.contains("let signal =") || code.contains("create_signal") {
-            suggestions.push(
-                "WARNING: Consider using `let (getter, setter) = signal(value)` pattern for clarity",
+    /// Make a fix from the session's last analysis in the file on disk and
+    /// return the diff
+    ///
+    /// `path` is the project the analysis ran in (default: that one) and
+    /// guards against applying a fix to the wrong checkout. Each fix applies
+    /// once; the file's other fixes are moved along with the lines they were
+    /// found on, and a fix whose code changed since is refused.
+    pub fn apply_fix(
+        &self,
+        id: &str,
+        path: Option<&str>,
+    ) -> Result<ToolOutput<AppliedFix>, String> {
+        let mut fixes = self.fixes.lock().unwrap();
+        let Some(index) = fixes.pending.iter().position(|f| f.id == id) else {
+            return Err(if fixes.issued == 0 {
+                "No fixes yet: run analyze-project (or leptos-autofixer with files) first, then pass the id of one of its diagnostics".to_string()
+            } else {
+                format!(
+                    "Unknown fix id '{}'. Ids come from the session's last analyze-project or leptos-autofixer run and apply once; run the analysis again for current ids",
+                    id
+                )
+            });
+        };
+        let fix = fixes.pending[index].clone();
+        if let Some(path) = path {
+            let same = |a: &Path, b: &Path| match (a.canonicalize(), b.canonicalize()) {
+                (Ok(a), Ok(b)) => a == b,
+                _ => a == b,
+            };
+            if !same(Path::new(path), &fix.root) {
+                return Err(format!(
+                    "Fix {} was found in {}, not {}",
+                    id,
+                    fix.root.display(),
+                    path
+                ));
+            }
+        }
+
+        let file = project_path(&fix.root, &fix.path)
+            .map(|path| fix.root.join(path))
+            .ok_or_else(|| format!("{} is outside {}", fix.path, fix.root.display()))?;
+        let code = fs::read_to_string(&file)
+            .map_err(|e| format!("Failed to read {}: {}", file.display(), e))?;
+        let fixed = fix.edit.apply(&code).ok_or_else(|| {
+            format!(
+                "{} changed since the analysis: `{}` is no longer at line {}, column {}. Run the analysis again for current fixes",
+                fix.path, fix.edit.original, fix.edit.line, fix.edit.column
+            )
+        })?;
+        fs::write(&file, &fixed)
+            .map_err(|e| format!("Failed to write {}: {}", file.display(), e))?;
+
+        fixes.pending.remove(index);
+        fixes.pending.retain_mut(|other| {
+            if other.root != fix.root || other.path != fix.path {
+                return true;
+            }
+            match fix.edit.shift(&other.edit) {
+                Some(edit) => {
+                    other.edit = edit;
+                    true
+                }
+                None => false,
+            }
+        });
+        drop(fixes);
+
+        let diff = format::unified_diff_labeled(
+            &code,
+            &fixed,
+            &format!("a/{}", fix.path),
+            &format!("b/{}", fix.path),
+        );
+        let text = format!(
+            "Applied {} [{}] to {}:\n\n```diff\n{}```",
+            id, fix.rule_id, fix.path, diff
+        );
+        let structured = AppliedFix {
+            id: id.to_string(),
+            rule_id: fix.rule_id,
+            path: fix.path,
+            diff,
+        };
+        Ok(ToolOutput::structured(text, structured))
+    }
+
+    /// Diagnostics a change to the project introduces, from its unified diff
+    ///
+    /// The project at `path` (default: the project context's root) may be
+    /// checked out before or after the change; each changed file is rebuilt
+    /// on the other side by applying the diff forward or backward. Both
+    /// versions of the project get the cross-file checks of `analyze-project`,
+    /// only the changed files get the per-file ones, and only findings in the
+    /// diff's hunks are reported. Files outside the project and files other
+    /// than Rust sources are skipped.
+    pub fn review_diff(
+        &self,
+        diff: &str,
+        path: Option<&str>,
+        rules: Option<RuleConfig>,
+        format: OutputFormat,
+        progress: &Progress,
+    ) -> Result<ToolOutput<Review>, String> {
+        let changes = review::parse(diff)?;
+        if changes.is_empty() {
+            return Err(
+                "'diff' has no file changes; pass a unified diff such as the output of `git diff`"
+                    .to_string(),
             );
         }
+        let root = path.map_or_else(|| self.project_root(), PathBuf::from);
+        let root = root.canonicalize().unwrap_or(root);
+        let config = self.rule_config(&root, rules)?;
+        let styling = self.styling(&root);
+        let sources = project::load_sources(&root, progress)?;
+        let read = |file: &Option<PathBuf>| match file {
+            None => Some(String::new()),
+            Some(file) => sources
+                .iter()
+                .find(|s| &s.path == file)
+                .map(|s| s.content.clone())
+                .or_else(|| fs::read_to_string(root.join(file)).ok()),
+        };
+
+        let mut changed = Vec::new();
+        for change in changes {
+            let in_project = |path: &Option<String>| match path {
+                Some(path) if path.ends_with(".rs") => project_path(&root, path).map(Some),
+                Some(_) => None,
+                None => Some(None),
+            };
+            let (Some(old_file), Some(new_file)) =
+                (in_project(&change.old_path), in_project(&change.new_path))
+            else {
+                continue;
+            };
+            let after = read(&new_file).and_then(|new| Some((change.revert(&new)?, new)));
+            let before =
+                || read(&old_file).and_then(|old| Some((old.clone(), change.apply(&old)?)));
+            let Some((old, new)) = after.or_else(before) else {
+                return Err(format!(
+                    "{} in {} matches the diff neither before nor after the change; check out the commit the diff was made from or its result",
+                    change.path(),
+                    root.display()
+                ));
+            };
+            changed.push((change, old_file, new_file, old, new));
+        }
+        if changed.is_empty() {
+            return Ok(review_output(Vec::new(), format));
+        }
+
+        let mut old_sources = sources.clone();
+        let mut new_sources = sources.clone();
+        for (_, old_file, new_file, old, new) in &changed {
+            for (sources, file, content) in [
+                (&mut old_sources, old_file, old),
+                (&mut new_sources, new_file, new),
+            ] {
+                sources.retain(|s| {
+                    Some(&s.path) != old_file.as_ref() && Some(&s.path) != new_file.as_ref()
+                });
+                if let Some(file) = file {
+                    sources.push(SourceFile {
+                        path: file.clone(),
+                        content: content.clone(),
+                    });
+                }
+            }
+        }
+        // Unchanged files only matter to the cross-file checks
+        let changed_code: HashSet<&str> = changed
+            .iter()
+            .flat_map(|(_, _, _, old, new)| [old.as_str(), new.as_str()])
+            .collect();
+        let check = |code: &str| {
+            if changed_code.contains(code) {
+                rules::check_styled(code, &styling)
+            } else {
+                Vec::new()
+            }
+        };
+        let workspace = Workspace::load(&root);
+        let diagnostics =
+            |sources: &[SourceFile]| -> Result<HashMap<String, Vec<Diagnostic>>, String> {
+                let report = project::analyze_sources(sources, workspace.clone(), check, progress)?;
+                let (files, _) = file_diagnostics(report.files, &config, report.workspace.as_ref());
+                Ok(files.into_iter().map(|f| (f.path, f.diagnostics)).collect())
+            };
+        let mut before = diagnostics(&old_sources)?;
+        let mut after = diagnostics(&new_sources)?;
+
+        let reviews: Vec<FileReview> = changed
+            .into_iter()
+            .map(|(change, old_file, new_file, old, new)| {
+                let take = |files: &mut HashMap<String, Vec<Diagnostic>>,
+                            file: &Option<PathBuf>| {
+                    file.as_ref()
+                        .and_then(|file| files.remove(&file.display().to_string()))
+                        .unwrap_or_default()
+                };
+                let path = new_file
+                    .as_ref()
+                    .or(old_file.as_ref())
+                    .map(|f| f.display().to_string());
+                review::review(
+                    path.unwrap_or_default(),
+                    &change,
+                    &old,
+                    &new,
+                    take(&mut before, &old_file),
+                    take(&mut after, &new_file),
+                )
+            })
+            .collect();
+        Ok(review_output(reviews, format))
+    }
+
+    /// Diagnostics a change to one file introduces, from its content before
+    /// and after the change
+    ///
+    /// Runs the per-file checks, as `leptos-autofixer` does for `code`;
+    /// `file` is the file's path in reports.
+    pub fn review_change(
+        &self,
+        old: &str,
+        new: &str,
+        file: Option<&str>,
+        rules: Option<RuleConfig>,
+        format: OutputFormat,
+    ) -> Result<ToolOutput<Review>, String> {
+        let root = self.project_root();
+        let config = self.rule_config(&root, rules)?;
+        let styling = self.styling(&root);
+        let path = file.unwrap_or("code");
+        let diff =
+            format::unified_diff_labeled(old, new, &format!("a/{}", path), &format!("b/{}", path));
+        let change = review::parse(&diff)?.into_iter().next().unwrap_or_default();
+        let check = |code: &str| config.apply(rules::check_styled(code, &styling));
+        let review = review::review(path.to_string(), &change, old, new, check(old), check(new));
+        Ok(review_output(vec![review], format))
+    }
+
+    /// Dependency graph between the signals, memos, resources and effects of a component
+    ///
+    /// Without `component`, every component in `code` is analyzed; code
+    /// without components is analyzed as one body.
+    pub fn analyze_reactivity(
+        &self,
+        code: &str,
+        component: Option<&str>,
+        format: GraphFormat,
+    ) -> Result<ToolOutput<ReactiveGraphs>, String> {
+        if code.trim().is_empty() {
+            return Err("Missing 'code' argument".to_string());
+        }
+        let mut graphs = reactivity::analyze(code);
+        if let Some(name) = component {
+            let names: Vec<String> = graphs.iter().map(|g| g.component.clone()).collect();
+            graphs.retain(|g| g.component == name);
+            if graphs.is_empty() {
+                return Err(format!(
+                    "No component named '{}'. Components: {}",
+                    name,
+                    names.join(", ")
+                ));
+            }
+        }
 
-        // 3. Check for missing #[component] macro
-        if code.contains("-> impl IntoView") && !code.contains("#[component]") {
-            suggestions.push(
-                "ERROR: Functions returning `impl IntoView` should have #[component] attribute",
+        let mut sections = Vec::new();
+        for graph in &graphs {
+            let mut text = format!(
+                "## {}
+",
+                graph.component
             );
+            if graph.nodes.is_empty() {
+                text.push_str("\nNo signals, memos, resources or effects found.");
+                sections.push(text);
+                continue;
+            }
+            let line_of = |name: &str| {
+                graph
+                    .nodes
+                    .iter()
+                    .find(|n| n.name == name)
+                    .map_or(0, |n| n.line)
+            };
+            for name in &graph.unused {
+                text.push_str(&format!(
+                    "\n⚠ `{}` (line {}) is never read; remove it or use it",
+                    name,
+                    line_of(name)
+                ));
+            }
+            for cycle in &graph.loops {
+                text.push_str(&format!(
+                    "\n⚠ {} (line {}) writes `{}`, which it depends on ({}); it will re-run itself, possibly forever",
+                    cycle.effect,
+                    line_of(&cycle.effect),
+                    cycle.signal,
+                    cycle.path.join(" → ")
+                ));
+            }
+            if graph.unused.is_empty() && graph.loops.is_empty() {
+                text.push_str("\n✓ No unused signals or self-triggering effects");
+            }
+            match format {
+                GraphFormat::Mermaid => {
+                    text.push_str(&format!("\n\n```mermaid\n{}```", graph.mermaid()))
+                }
+                GraphFormat::Dot => text.push_str(&format!("\n\n```dot\n{}```", graph.dot())),
+            }
+            sections.push(text);
+        }
+        let structured = ReactiveGraphs {
+            components: graphs
+                .into_iter()
+                .map(|graph| ComponentGraph {
+                    mermaid: graph.mermaid(),
+                    dot: graph.dot(),
+                    graph,
+                })
+                .collect(),
+        };
+        Ok(ToolOutput::structured(sections.join("\n\n"), structured))
+    }
+
+    /// Which components should be islands, which props islands can't take,
+    /// and how to turn islands mode on
+    ///
+    /// Reads `code` when given, otherwise every source file of the project at
+    /// `path` (the project context's root without one), whose manifest then
+    /// tells whether the `islands` feature is on.
+    pub fn islands_advisor(
+        &self,
+        code: Option<&str>,
+        path: Option<&str>,
+        version: Option<&str>,
+        progress: &Progress,
+    ) -> Result<ToolOutput<IslandsAdvice>, String> {
+        let (files, env) = match code.filter(|c| !c.trim().is_empty()) {
+            Some(code) => (
+                vec![project::SourceFile {
+                    path: PathBuf::new(),
+                    content: code.to_string(),
+                }],
+                None,
+            ),
+            None => {
+                let root = path.map_or_else(|| self.project_root(), PathBuf::from);
+                let files = project::load_sources(&root, progress)?;
+                (files, Environment::detect(&root).ok())
+            }
+        };
+        let version = match (version, env.as_ref().and_then(|e| e.context().version)) {
+            (None, Some(detected)) => detected,
+            (version, _) => self.resolve_version(version)?,
+        };
+        let report = islands::advise(&files, version);
+        if report.components.is_empty() {
+            return Err("No #[component] or #[island] functions found".to_string());
+        }
+
+        let location = |file: &str, line: usize| match file {
+            "" => format!("line {}", line),
+            file => format!("{}:{}", file, line),
+        };
+        let mut text = format!("# Islands advice (Leptos {})", version.as_str());
+        text.push_str("\n\n## Island candidates\n");
+        let candidates: Vec<String> = report
+            .with_role(Role::Island)
+            .map(|a| {
+                let action = if a.is_island {
+                    "✓ already `#[island]`"
+                } else {
+                    "→ mark it `#[island]`"
+                };
+                format!(
+                    "- `{}` ({}): {} {}",
+                    a.component,
+                    location(&a.file, a.line),
+                    a.interactivity.join(", "),
+                    action
+                )
+            })
+            .collect();
+        text.push_str(&match candidates.is_empty() {
+            true => "None: no component is an interactive leaf.".to_string(),
+            false => candidates.join("\n"),
+        });
+
+        let splits: Vec<String> = report
+            .with_role(Role::Split)
+            .map(|a| {
+                format!(
+                    "- `{}` ({}): {}, but also renders static {} → keep it a `#[component]`, move the interactive part into a new `#[island]` and pass the static content to it as `children`, which stays server-rendered",
+                    a.component,
+                    location(&a.file, a.line),
+                    a.interactivity.join(", "),
+                    a.static_children
+                        .iter()
+                        .map(|c| format!("`{}`", c))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            })
+            .collect();
+        if !splits.is_empty() {
+            text.push_str("\n\n## Split\n");
+            text.push_str(&splits.join("\n"));
+        }
+
+        let server: Vec<&Advice> = report.with_role(Role::Server).collect();
+        if !server.is_empty() {
+            text.push_str("\n\n## Server-only (stay `#[component]`, ship no wasm)\n");
+            let names: Vec<String> = server
+                .iter()
+                .map(|a| format!("`{}`", a.component))
+                .collect();
+            text.push_str(&format!("- {}", names.join(", ")));
+            for advice in server.iter().filter(|a| a.is_island) {
+                text.push_str(&format!(
+                    "\n- ⚠ `{}` ({}) is `#[island]`, but nothing in it runs in the browser; make it a `#[component]` to keep it out of the wasm bundle",
+                    advice.component,
+                    location(&advice.file, advice.line)
+                ));
+            }
         }
 
-        // 4. Check for server function without proper error handling
-        if code.contains("#[server") && !code.contains("ServerFnError") {
-            suggestions.push("INFO: Server functions should return Result<T, ServerFnError>");
+        if !report.props.is_empty() {
+            text.push_str("\n\n## Props islands can't take\n");
+            let lines: Vec<String> = report
+                .props
+                .iter()
+                .map(|p| {
+                    format!(
+                        "- ⚠ `{}` prop `{}: {}` ({}): {}",
+                        p.component,
+                        p.prop,
+                        p.ty,
+                        location(&p.file, p.line),
+                        p.reason
+                    )
+                })
+                .collect();
+            text.push_str(&lines.join("\n"));
+        }
+
+        // 0.6 calls the feature `experimental-islands`, which the environment doesn't track
+        let enabled = env
+            .as_ref()
+            .filter(|_| version != LeptosVersion::V0_6)
+            .and_then(|e| e.features.iter().find(|f| f.name == "islands"))
+            .map(|f| f.is_enabled());
+        text.push_str("\n\n## Setup\n");
+        match enabled {
+            Some(true) => text.push_str("✓ The `islands` feature is enabled\n"),
+            Some(false) => text.push_str("✗ The `islands` feature isn't enabled in Cargo.toml\n"),
+            None => {}
+        }
+        for issue in &report.setup_issues {
+            text.push_str(&format!("✗ {}\n", issue));
+        }
+        let steps = islands::setup_steps(version);
+        for (i, step) in steps.iter().enumerate() {
+            text.push_str(&format!("\n{}. {}", i + 1, step));
+        }
+
+        let structured = IslandsAdvice {
+            version: version.as_str(),
+            islands_enabled: enabled,
+            components: report.components,
+            props: report.props,
+            setup_issues: report.setup_issues,
+            setup_steps: steps,
+        };
+        Ok(ToolOutput::structured(text, structured))
+    }
+    /// The route tree of a project: full paths, params, nesting and the
+    /// component rendering each route
+    ///
+    /// Reads `code` when given, otherwise every source file of the project at
+    /// `path` (the project context's root without one).
+    pub fn extract_routes(
+        &self,
+        code: Option<&str>,
+        path: Option<&str>,
+        progress: &Progress,
+    ) -> Result<ToolOutput<RouteMap>, String> {
+        let files = match code.filter(|c| !c.trim().is_empty()) {
+            Some(code) => vec![project::SourceFile {
+                path: PathBuf::new(),
+                content: code.to_string(),
+            }],
+            None => {
+                let root = path.map_or_else(|| self.project_root(), PathBuf::from);
+                project::load_sources(&root, progress)?
+            }
+        };
+        let map = routemap::extract(&files);
+        if map.tables.is_empty() {
+            return Err("No <Routes> or route declarations found".to_string());
+        }
+
+        fn render(route: &Route, depth: usize, text: &mut String) {
+            let path = match route.segment.trim_matches('/') {
+                "" if depth > 0 && route.children.is_empty() => {
+                    format!("`{}` (empty path)", route.path)
+                }
+                _ => format!("`{}`", route.path),
+            };
+            let target = match (&route.component, &route.view) {
+                (Some(component), _) => format!("`{}`", component),
+                (None, Some(_)) => "inline view".to_string(),
+                (None, None) => "no view".to_string(),
+            };
+            text.push_str(&format!("\n{}- {} → {}", "  ".repeat(depth), path, target));
+            if let Some(at) = &route.defined_at {
+                text.push_str(&format!(" ({})", at));
+            }
+            let mut notes = Vec::new();
+            if !route.params.is_empty() {
+                notes.push(format!("params: {}", route.params.join(", ")));
+            }
+            if route.element.starts_with("Protected") {
+                notes.push("protected".to_string());
+            }
+            if let Some(ssr) = &route.ssr {
+                notes.push(format!("ssr: {}", ssr));
+            }
+            match route.outlet {
+                Some(true) => notes.push("renders children in its <Outlet/>".to_string()),
+                Some(false) => notes.push("⚠ no <Outlet/>".to_string()),
+                None => {}
+            }
+            if !notes.is_empty() {
+                text.push_str(&format!(" · {}", notes.join(" · ")));
+            }
+            for child in &route.children {
+                render(child, depth + 1, text);
+            }
+        }
+
+        let count = map.routes().len();
+        let mut text = format!("# Route map ({} route(s))", count);
+        for table in &map.tables {
+            text.push_str(&format!(
+                "\n\n## `<{}>` at {}",
+                table.element, table.declared_at
+            ));
+            if let Some(fallback) = &table.fallback {
+                text.push_str(&format!(" (fallback: `{}`)", fallback));
+            }
+            if table.routes.is_empty() {
+                text.push_str("\nNo routes with a known path.");
+            }
+            for route in &table.routes {
+                render(route, 0, &mut text);
+            }
+        }
+        if !map.issues.is_empty() {
+            text.push_str("\n\n## Issues\n");
+            let lines: Vec<String> = map.issues.iter().map(|i| format!("- ⚠ {}", i)).collect();
+            text.push_str(&lines.join("\n"));
+        }
+        Ok(ToolOutput::structured(text, map))
+    }
+    /// Every component of a project with its props, whether it takes
+    /// children, and where it is used
+    ///
+    /// Reads `code` when given, otherwise every source file of the project at
+    /// `path` (the project context's root without one).
+    pub fn list_components(
+        &self,
+        code: Option<&str>,
+        path: Option<&str>,
+        progress: &Progress,
+    ) -> Result<ToolOutput<ComponentList>, String> {
+        let files = match code.filter(|c| !c.trim().is_empty()) {
+            Some(code) => vec![project::SourceFile {
+                path: PathBuf::new(),
+                content: code.to_string(),
+            }],
+            None => {
+                let root = path.map_or_else(|| self.project_root(), PathBuf::from);
+                project::load_sources(&root, progress)?
+            }
+        };
+        let inventory = components::inventory(&files);
+        if inventory.is_empty() {
+            return Err("No #[component] or #[island] functions found".to_string());
         }
 
-        // 5. Check for deprecated create_signal
-        if code.contains("create_signal") {
-            suggestions.push(
-                "INFO: In Leptos 0.8+, use `signal()` instead of `create_signal()`",
+        let location = |file: &str, line: usize| match file {
+            "" => format!("line {}", line),
+            file => format!("{}:{}", file, line),
+        };
+        let mut sections = vec![format!("# Components ({})", inventory.len())];
+        for component in &inventory {
+            let mut text = format!(
+                "## `{}` ({})",
+                component.name,
+                location(&component.file, component.line)
+            );
+            if component.island {
+                text.push_str(" · island");
+            }
+            if !component.doc.is_empty() {
+                text.push_str(&format!("\n{}", component.doc));
+            }
+            if component.props.is_empty() {
+                text.push_str("\nNo props");
+            } else {
+                text.push_str("\nProps:");
+            }
+            for prop in &component.props {
+                text.push_str(&format!("\n- `{}: {}`", prop.name, prop.ty));
+                let mut options = Vec::new();
+                match &prop.default {
+                    Some(default) => options.push(format!("default `{}`", default)),
+                    None if prop.optional => options.push("optional".to_string()),
+                    None => {}
+                }
+                if prop.into {
+                    options.push("into".to_string());
+                }
+                if !options.is_empty() {
+                    text.push_str(&format!(" ({})", options.join(", ")));
+                }
+                if !prop.doc.is_empty() {
+                    text.push_str(&format!(": {}", prop.doc));
+                }
+            }
+            let users: Vec<String> = component
+                .used_by
+                .iter()
+                .map(|usage| match usage.function.as_str() {
+                    "" => location(&usage.file, usage.line),
+                    function => format!("`{}` ({})", function, location(&usage.file, usage.line)),
+                })
+                .collect();
+            text.push_str(&match users.is_empty() {
+                true => "\nUsed by: nothing in the scanned sources".to_string(),
+                false => format!("\nUsed by: {}", users.join(", ")),
+            });
+            sections.push(text);
+        }
+        Ok(ToolOutput::structured(
+            sections.join("\n\n"),
+            ComponentList {
+                components: inventory,
+            },
+        ))
+    }
+    /// Every signal, memo, resource, action, store and stored value of a
+    /// project, whether it is provided as context, and the functions reading
+    /// and writing it
+    ///
+    /// Reads `code` when given, otherwise every source file of the project at
+    /// `path` (the project context's root without one).
+    pub fn extract_state(
+        &self,
+        code: Option<&str>,
+        path: Option<&str>,
+        progress: &Progress,
+    ) -> Result<ToolOutput<StateList>, String> {
+        let files = match code.filter(|c| !c.trim().is_empty()) {
+            Some(code) => vec![project::SourceFile {
+                path: PathBuf::new(),
+                content: code.to_string(),
+            }],
+            None => {
+                let root = path.map_or_else(|| self.project_root(), PathBuf::from);
+                project::load_sources(&root, progress)?
+            }
+        };
+        let states = state::inventory(&files);
+        if states.is_empty() {
+            return Err(
+                "No signals, memos, resources, actions, stores or stored values found".to_string(),
             );
         }
 
-        if suggestions.is_empty() {
-            "✓ No issues found. Code looks good!".to_string()
-        } else {
-            suggestions.join("\n")
+        let names = |list: &[String]| {
+            list.iter()
+                .map(|name| format!("`{}`", name))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let mut text = format!("# State ({} item(s))", states.len());
+        let mut current: Option<(&str, &str)> = None;
+        for item in &states {
+            if current != Some((item.file.as_str(), item.function.as_str())) {
+                current = Some((item.file.as_str(), item.function.as_str()));
+                text.push_str(&match item.file.as_str() {
+                    "" => format!("\n\n## `{}`", item.function),
+                    file => format!("\n\n## `{}` ({})", item.function, file),
+                });
+            }
+            let name = match &item.setter {
+                Some(setter) => format!("`{}` / `{}`", item.name, setter),
+                None => format!("`{}`", item.name),
+            };
+            text.push_str(&format!(
+                "\n- {} {} (line {})",
+                name,
+                item.kind.as_str(),
+                item.line
+            ));
+            if !item.context.is_empty() {
+                text.push_str(&format!(" · in context as {}", names(&item.context)));
+            }
+            let mut uses = Vec::new();
+            if !item.readers.is_empty() {
+                uses.push(format!("read by {}", names(&item.readers)));
+            }
+            if !item.writers.is_empty() {
+                uses.push(format!("written by {}", names(&item.writers)));
+            }
+            if uses.is_empty() {
+                uses.push("⚠ nothing reads or writes it".to_string());
+            }
+            text.push_str(&format!("\n  {}", uses.join(" · ")));
+        }
+
+        // The same name declared in several places is often state created twice
+        let mut duplicates: Vec<(&str, Vec<&State>)> = Vec::new();
+        for item in &states {
+            match duplicates.iter_mut().find(|(name, _)| *name == item.name) {
+                Some((_, found)) => found.push(item),
+                None => duplicates.push((&item.name, vec![item])),
+            }
+        }
+        duplicates.retain(|(_, found)| {
+            found
+                .iter()
+                .any(|s| s.function != found[0].function || s.file != found[0].file)
+        });
+        if !duplicates.is_empty() {
+            text.push_str("\n\n## Declared more than once\n");
+            let lines: Vec<String> = duplicates
+                .iter()
+                .map(|(name, found)| {
+                    let places: Vec<String> = found
+                        .iter()
+                        .map(|s| format!("`{}` (line {})", s.function, s.line))
+                        .collect();
+                    format!(
+                        "- `{}` in {}: if they hold the same data, share one through a prop or context",
+                        name,
+                        places.join(", ")
+                    )
+                })
+                .collect();
+            text.push_str(&lines.join("\n"));
+        }
+        Ok(ToolOutput::structured(text, StateList { state: states }))
+    }
+
+    /// Find hard-coded user-facing strings in views and propose translation keys
+    ///
+    /// Reads `code` when given, otherwise every source file of the project at
+    /// `path` (the project context's root without one). Without `library`,
+    /// a project depending on `leptos-fluent` gets Fluent keys, any other
+    /// `leptos_i18n` ones.
+    pub fn extract_strings(
+        &self,
+        code: Option<&str>,
+        path: Option<&str>,
+        library: Option<Library>,
+        locale: Option<&str>,
+        progress: &Progress,
+    ) -> Result<ToolOutput<Extraction>, String> {
+        let root = path.map_or_else(|| self.project_root(), PathBuf::from);
+        let files = match code.filter(|c| !c.trim().is_empty()) {
+            Some(code) => vec![project::SourceFile {
+                path: PathBuf::new(),
+                content: code.to_string(),
+            }],
+            None => project::load_sources(&root, progress)?,
+        };
+        let library = match library {
+            Some(library) => library,
+            None => {
+                let fluent = Manifest::load(&root).ok().is_some_and(|manifest| {
+                    manifest
+                        .own
+                        .get("dependencies")
+                        .is_some_and(|d| d.get("leptos-fluent").is_some())
+                });
+                match fluent {
+                    true => Library::LeptosFluent,
+                    false => Library::LeptosI18n,
+                }
+            }
+        };
+        let locale = locale
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .unwrap_or("en");
+        let extraction = i18n::extract(&files, library, locale);
+        if extraction.messages.is_empty() {
+            return Err("No hard-coded user-facing strings found in view! bodies".to_string());
+        }
+
+        let mut text = format!(
+            "# {} string(s) to translate ({})",
+            extraction.messages.len(),
+            library.as_str()
+        );
+        let mut current: Option<(&str, &str)> = None;
+        for message in &extraction.messages {
+            if current != Some((message.file.as_str(), message.function.as_str())) {
+                current = Some((message.file.as_str(), message.function.as_str()));
+                text.push_str(&match message.file.as_str() {
+                    "" => format!("\n\n## `{}`", message.function),
+                    file => format!("\n\n## `{}` ({})", message.function, file),
+                });
+            }
+            let original = match &message.attribute {
+                Some(attribute) => format!("{}={}", attribute, message.original),
+                None => message.original.clone(),
+            };
+            text.push_str(&format!(
+                "\n- line {}: `{}` → `{}`",
+                message.line, original, message.replacement
+            ));
+        }
+        let language = match library {
+            Library::LeptosI18n => "json",
+            Library::LeptosFluent => "ftl",
+        };
+        text.push_str(&format!(
+            "\n\n## {}\n```{}\n{}```\n\n## Setup\n```rust\n{}```",
+            extraction.locale_file, language, extraction.locale_content, extraction.setup
+        ));
+        Ok(ToolOutput::structured(text, extraction))
+    }
+}
+
+fn unsupported_version(version: &str) -> String {
+    let supported: Vec<&str> = LeptosVersion::ALL.iter().map(|v| v.as_str()).collect();
+    format!(
+        "Unsupported Leptos version '{}'. Supported versions: {}",
+        version,
+        supported.join(", ")
+    )
+}
+
+fn section_not_found<T: SectionLookup>(section: &str, version: LeptosVersion) -> ToolOutput<T> {
+    let candidates = docs::suggest_sections(section, version, 3);
+    let structured = T::not_found(candidates.iter().map(SectionInfo::from).collect());
+    if candidates.is_empty() {
+        let text = format!(
+            "Section '{}' not found. Use list-sections to see available sections.",
+            section
+        );
+        return ToolOutput::structured(text, structured);
+    }
+    let suggestions: Vec<String> = candidates
+        .iter()
+        .map(|s| format!("* {} (path: {})", s.title, s.path))
+        .collect();
+    let text = format!(
+        "Section '{}' not found. Did you mean:\n{}",
+        section,
+        suggestions.join("\n")
+    );
+    ToolOutput::structured(text, structured)
+}
+
+/// A snippet as text with its dependencies, and as structured output with its code
+fn snippet_output(snippet: &'static Snippet) -> ToolOutput<SnippetLookup> {
+    let dependencies = if snippet.dependencies.is_empty() {
+        String::new()
+    } else {
+        format!("Also depends on: {}\n\n", snippet.dependencies.join(", "))
+    };
+    let text = format!(
+        "# {}\n\n{} (Leptos {}).\n\n{}```rust\n{}```",
+        snippet.title,
+        snippet.description,
+        LeptosVersion::default().as_str(),
+        dependencies,
+        snippet.code
+    );
+    let structured = SnippetLookup {
+        found: true,
+        snippet: Some(SnippetCode {
+            snippet,
+            code: snippet.code,
+        }),
+        suggestions: None,
+    };
+    ToolOutput::structured(text, structured)
+}
+
+/// Generated code, one content block per snippet
+///
+/// Snippets starting with a comment saying where they go get that comment as
+/// their label in structured output.
+fn generated(blocks: Vec<String>) -> ToolOutput<Generated> {
+    let structured = Generated {
+        blocks: blocks
+            .iter()
+            .map(|code| {
+                let first = code.lines().next().unwrap_or_default();
+                let (label, language) = match first.strip_prefix("# ") {
+                    Some(label) => (Some(label), BlockLanguage::Toml),
+                    None => (first.strip_prefix("// "), BlockLanguage::Rust),
+                };
+                GeneratedBlock {
+                    label: label.map(str::to_string),
+                    language,
+                    code: code.clone(),
+                }
+            })
+            .collect(),
+    };
+    let mut output = ToolOutput::blocks(blocks);
+    output.structured = Some(structured);
+    output
+}
+
+/// Indented heading outline with addressable `section#slug` paths
+fn table_of_contents(doc: &DocSection) -> String {
+    let lines: Vec<String> = doc
+        .headings
+        .iter()
+        .filter(|h| h.level > 1)
+        .map(|h| {
+            format!(
+                "{}- {} ({}#{})",
+                "  ".repeat(h.level - 2),
+                h.title,
+                doc.path,
+                h.slug
+            )
+        })
+        .collect();
+    lines.join("\n")
+}
+
+/// Cut documentation at a block boundary and describe what was left out
+/// The part of `body` starting at byte `offset` that fits in `max_chars`,
+/// with a note saying which part it is, and where the next part starts
+fn doc_part(body: &str, path: &str, max_chars: usize, offset: usize) -> (String, Option<usize>) {
+    let parts = markdown::chunks(body, max_chars);
+    let (range, label) = match parts.iter().position(|p| p.start == offset) {
+        Some(_) if parts.len() <= 1 => return (body.to_string(), None),
+        Some(index) => (
+            parts[index].clone(),
+            format!("Part {} of {}", index + 1, parts.len()),
+        ),
+        // A cursor from a call with another budget
+        None => {
+            let rest = markdown::chunks(&body[offset..], max_chars);
+            let end = offset + rest.first().map_or(0, |p| p.end);
+            (offset..end, "Continued".to_string())
         }
+    };
+    let mut output = body[range.clone()].trim_end().to_string();
+    if range.end >= body.len() {
+        output.push_str(&format!(
+            "\n\n[{}: characters {}-{} of {}, the end of the section.]",
+            label,
+            range.start,
+            range.end,
+            body.len()
+        ));
+        return (output, None);
+    }
+    let omitted: Vec<String> = markdown::parse_headings(&body[range.end..])
+        .iter()
+        .filter(|h| h.level > 1)
+        .map(|h| format!("{}#{}", path, h.slug))
+        .collect();
+    output.push_str(&format!(
+        "\n\n[{}: characters {}-{} of {}. Continue with continue=\"{}\"",
+        label,
+        range.start,
+        range.end,
+        body.len(),
+        range.end
+    ));
+    if omitted.is_empty() {
+        output.push_str(".]");
+    } else {
+        output.push_str(&format!(
+            ", or fetch a later subsection with get-documentation: {}]",
+            omitted.join(", ")
+        ));
+    }
+    (output, Some(range.end))
+}
+
+fn format_api_item(item: &ApiItem) -> String {
+    let mut signature = item.signature.clone();
+    if !item.bounds.is_empty() {
+        signature.push_str("\nwhere\n");
+        let bounds: Vec<String> = item.bounds.iter().map(|b| format!("    {},", b)).collect();
+        signature.push_str(&bounds.join("\n"));
+    }
+    let deprecated = match item.deprecated.as_deref() {
+        Some("") => "**Deprecated.**\n\n".to_string(),
+        Some(note) => format!("**Deprecated:** {}\n\n", note),
+        None => String::new(),
+    };
+    format!(
+        "## {} ({})\n\n```rust\n{}\n```\n\n{}{}",
+        item.path, item.kind, signature, deprecated, item.docs
+    )
+}
+
+fn format_api_diff(diff: &ApiDiff, item: Option<&str>) -> String {
+    let scope = item.map_or(String::new(), |item| format!(" for '{}'", item));
+    let heading = format!(
+        "# Leptos API changes from {} to {}{}",
+        diff.from, diff.to, scope
+    );
+    if diff.is_empty() {
+        let text = match (item, diff.unchanged.as_slice()) {
+            (Some(_), []) => format!(
+                "{}\n\nNo item matching '{}' in the Leptos {} or {} API reference.",
+                heading,
+                item.unwrap_or_default(),
+                diff.from,
+                diff.to
+            ),
+            (Some(_), unchanged) => format!("{}\n\nUnchanged: {}", heading, unchanged.join(", ")),
+            (None, _) => format!("{}\n\nNo changes.", heading),
+        };
+        return match &diff.note {
+            Some(note) => format!("{}\n\n{}", text, note),
+            None => text,
+        };
+    }
+
+    let mut sections = vec![heading];
+    if !diff.renamed.is_empty() {
+        let entries: Vec<String> = diff
+            .renamed
+            .iter()
+            .map(|r| {
+                if r.from_signature == r.to_signature {
+                    format!("* {} -> {}", r.from, r.to)
+                } else {
+                    format!(
+                        "* {} -> {}\n  - {}\n  + {}",
+                        r.from, r.to, r.from_signature, r.to_signature
+                    )
+                }
+            })
+            .collect();
+        sections.push(format!(
+            "## Renamed or moved ({})\n\n{}",
+            entries.len(),
+            entries.join("\n")
+        ));
+    }
+    if !diff.changed.is_empty() {
+        let entries: Vec<String> = diff
+            .changed
+            .iter()
+            .map(|c| {
+                let mut entry = format!("* {}", c.path);
+                if c.from_signature != c.to_signature {
+                    entry.push_str(&format!(
+                        "\n  - {}\n  + {}",
+                        c.from_signature, c.to_signature
+                    ));
+                }
+                if c.from_bounds != c.to_bounds {
+                    entry.push_str(&format!(
+                        "\n  bounds: {} -> {}",
+                        bounds(&c.from_bounds),
+                        bounds(&c.to_bounds)
+                    ));
+                }
+                entry
+            })
+            .collect();
+        sections.push(format!(
+            "## Signature changes ({})\n\n{}",
+            entries.len(),
+            entries.join("\n")
+        ));
+    }
+    for (title, items) in [("Removed", &diff.removed), ("Added", &diff.added)] {
+        if items.is_empty() {
+            continue;
+        }
+        let entries: Vec<String> = items
+            .iter()
+            .map(|i| format!("* {}: {}", i.path, i.signature))
+            .collect();
+        sections.push(format!(
+            "## {} ({})\n\n{}",
+            title,
+            entries.len(),
+            entries.join("\n")
+        ));
+    }
+    if item.is_none() {
+        sections.push(format!(
+            "Covers the items in the bundled API references; {} other items are unchanged.",
+            diff.unchanged.len()
+        ));
+    }
+    if let Some(note) = &diff.note {
+        sections.push(note.clone());
+    }
+    sections.join("\n\n")
+}
+
+fn bounds(bounds: &[String]) -> String {
+    if bounds.is_empty() {
+        "none".to_string()
+    } else {
+        bounds.join(", ")
+    }
+}
+
+/// Diagnostics of the files with any left after `config`, narrowed to each
+/// file's workspace crate, and their total
+fn file_diagnostics(
+    reports: Vec<project::FileReport>,
+    config: &RuleConfig,
+    workspace: Option<&Workspace>,
+) -> (Vec<FileFindings>, usize) {
+    // Crates built for one side skip the other side's rules
+    let targeted = [Target::Server, Target::Client, Target::Shared].map(|target| {
+        let mut targeted = config.clone();
+        targeted.merge(RuleConfig::for_target(target));
+        (target, targeted)
+    });
+    let config_for = |path: &Path| {
+        let target = workspace.and_then(|w| w.member_of(path)?.target);
+        targeted
+            .iter()
+            .find(|(t, _)| Some(*t) == target)
+            .map_or(config, |(_, config)| config)
+    };
+    let files: Vec<FileFindings> = reports
+        .into_iter()
+        .map(|f| FileFindings {
+            diagnostics: config_for(&f.path).apply(f.findings),
+            path: f.path.display().to_string(),
+        })
+        .filter(|f| !f.diagnostics.is_empty())
+        .collect();
+    let total = files.iter().map(|f| f.diagnostics.len()).sum();
+    (files, total)
+}
+
+/// Diagnostics listed under a heading per file
+fn files_text(files: &[FileFindings]) -> String {
+    let mut output = String::new();
+    for file in files {
+        output.push_str(&format!(
+            "\n\n## {} ({})\n",
+            file.path,
+            file.diagnostics.len()
+        ));
+        let lines: Vec<String> = file
+            .diagnostics
+            .iter()
+            .map(|d| format!("- {}", d))
+            .collect();
+        output.push_str(&lines.join("\n"));
+    }
+    output
+}
+
+/// Where a client-sent path is in the project at `root`, or `None` outside it
+///
+/// The path may be relative to the project or to a repository containing
+/// it: the first of `root` and its parents holding the path's directory is
+/// taken as its base. Absolute paths and paths with `..` are outside.
+fn project_path(root: &Path, path: &str) -> Option<PathBuf> {
+    let path = Path::new(path);
+    if !path
+        .components()
+        .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+    {
+        return None;
+    }
+    let dir = path.parent().unwrap_or(Path::new(""));
+    let base = root.ancestors().find(|base| base.join(dir).is_dir())?;
+    let within = root.strip_prefix(base).ok()?;
+    path.strip_prefix(within).ok().map(Path::to_path_buf)
+}
+
+/// Output of a review: totals, and each file with findings in its changed regions
+fn review_output(reviews: Vec<FileReview>, format: OutputFormat) -> ToolOutput<Review> {
+    let count = |list: fn(&FileReview) -> &Vec<Diagnostic>| -> usize {
+        reviews.iter().map(|r| list(r).len()).sum()
+    };
+    let introduced = count(|r| &r.introduced);
+    let pre_existing = count(|r| &r.pre_existing);
+    let resolved = count(|r| &r.resolved);
+    let files_reviewed = reviews.len();
+    let files: Vec<FileReview> = reviews
+        .into_iter()
+        .filter(|r| {
+            !r.introduced.is_empty() || !r.pre_existing.is_empty() || !r.resolved.is_empty()
+        })
+        .collect();
+    let structured = Review {
+        files_reviewed,
+        introduced,
+        pre_existing,
+        resolved,
+        files,
+    };
+    if format == OutputFormat::Json {
+        return ToolOutput::structured(pretty_json(&structured), structured);
+    }
+
+    let mut text = if introduced == 0 {
+        format!(
+            "✓ Reviewed {} changed file(s): the change introduces no issues ({} pre-existing, {} resolved)",
+            files_reviewed,
+            pre_existing,
+            resolved
+        )
+    } else {
+        format!(
+            "Reviewed {} changed file(s): {} introduced, {} pre-existing, {} resolved",
+            files_reviewed, introduced, pre_existing, resolved
+        )
+    };
+    for review in &structured.files {
+        text.push_str(&format!("\n\n## {}", review.path));
+        for (heading, diagnostics) in [
+            ("Introduced", &review.introduced),
+            ("Pre-existing", &review.pre_existing),
+        ] {
+            if diagnostics.is_empty() {
+                continue;
+            }
+            text.push_str(&format!("\n{} ({}):", heading, diagnostics.len()));
+            for diagnostic in diagnostics {
+                text.push_str(&format!("\n- {}", diagnostic));
+            }
+        }
+        if !review.resolved.is_empty() {
+            text.push_str(&format!("\nResolved ({}):", review.resolved.len()));
+        }
+        // Fixes and docs of findings that are gone are only noise
+        for diagnostic in &review.resolved {
+            let line = diagnostic.line.map_or_else(String::new, |line| {
+                format!(" line {} before the change", line)
+            });
+            text.push_str(&format!(
+                "\n- {} [{}]{}: {}",
+                diagnostic.severity.as_str(),
+                diagnostic.rule_id,
+                line,
+                diagnostic.message
+            ));
+        }
+    }
+    ToolOutput::structured(text, structured)
+}
+
+/// Frameworks as a comma-separated list, or a note that none apply
+fn styling_list(frameworks: &[StyleFramework]) -> String {
+    if frameworks.is_empty() {
+        return "none".to_string();
+    }
+    let names: Vec<&str> = frameworks.iter().map(StyleFramework::as_str).collect();
+    names.join(", ")
+}
+
+fn pretty_json(value: &impl Serialize) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn project_path_stays_inside_the_project() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        assert_eq!(
+            project_path(root, "src/tools.rs"),
+            Some(PathBuf::from("src/tools.rs"))
+        );
+        assert_eq!(
+            project_path(root, "./src/tools.rs"),
+            Some(PathBuf::from("./src/tools.rs"))
+        );
+        assert_eq!(project_path(root, "../evil/x.rs"), None);
+        assert_eq!(project_path(root, "src/../../evil/x.rs"), None);
+        assert_eq!(project_path(root, "/tmp/evil/x.rs"), None);
     }
 }
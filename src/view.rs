@@ -0,0 +1,482 @@
+//! `view!` macro parser
+//!
+//! A tolerant, hand-rolled scanner for the bodies of `view!` invocations. It
+//! doesn't build a full syntax tree: it reports the tags, attributes and
+//! braced Rust blocks in source order with byte offsets into the original
+//! code, which is enough for markup-level lint rules. Rust expressions are
+//! skipped by balancing brackets and string literals.
+
+/// HTML elements that never have children and must be written `<input/>` in `view!`
+pub const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// Elements whose `web_sys` interface isn't `Html` + the tag name + `Element`
+const DOM_INTERFACES: &[(&[&str], &str)] = &[
+    (&["a"], "HtmlAnchorElement"),
+    (&["img"], "HtmlImageElement"),
+    (&["textarea"], "HtmlTextAreaElement"),
+    (&["p"], "HtmlParagraphElement"),
+    (&["ul"], "HtmlUListElement"),
+    (&["ol"], "HtmlOListElement"),
+    (&["dl"], "HtmlDListElement"),
+    (&["h1", "h2", "h3", "h4", "h5", "h6"], "HtmlHeadingElement"),
+    (&["tr"], "HtmlTableRowElement"),
+    (&["td", "th"], "HtmlTableCellElement"),
+    (&["thead", "tbody", "tfoot"], "HtmlTableSectionElement"),
+    (&["col", "colgroup"], "HtmlTableColElement"),
+    (&["caption"], "HtmlTableCaptionElement"),
+    (&["q", "blockquote"], "HtmlQuoteElement"),
+    (&["ins", "del"], "HtmlModElement"),
+];
+
+/// Elements with an interface named after the tag, e.g. `HtmlInputElement`
+const NAMED_INTERFACES: &[&str] = &[
+    "audio", "base", "body", "br", "button", "canvas", "data", "datalist", "details", "dialog",
+    "div", "embed", "fieldset", "form", "head", "hr", "html", "iframe", "input", "label", "legend",
+    "li", "link", "map", "meta", "meter", "object", "optgroup", "option", "output", "picture",
+    "pre", "progress", "script", "select", "slot", "source", "span", "style", "table", "template",
+    "time", "title", "track", "video",
+];
+
+/// Type of an element in `leptos::html`, which `NodeRef` takes: `Input` for `<input>`
+pub fn element_type(tag: &str) -> String {
+    let mut chars = tag.chars();
+    chars
+        .next()
+        .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+        .unwrap_or_default()
+}
+
+/// The `web_sys` interface of an element, which a filled `NodeRef` holds:
+/// `HtmlInputElement` for `<input>`, plain `HtmlElement` for `<section>`
+pub fn dom_interface(tag: &str) -> String {
+    if let Some((_, interface)) = DOM_INTERFACES.iter().find(|(tags, _)| tags.contains(&tag)) {
+        return interface.to_string();
+    }
+    if NAMED_INTERFACES.contains(&tag) {
+        format!("Html{}Element", element_type(tag))
+    } else {
+        "HtmlElement".to_string()
+    }
+}
+
+/// One `view!` invocation
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct ViewMacro {
+    /// Byte offset of the `view!` token
+    pub start: usize,
+    /// Byte range of the body between the outer braces
+    pub body_start: usize,
+    pub body_end: usize,
+    pub nodes: Vec<Node>,
+}
+
+/// A markup item inside a `view!` body
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub enum Node {
+    Open(Element),
+    Close {
+        name: String,
+        offset: usize,
+    },
+    /// A `{ ... }` Rust block in child position
+    Block {
+        offset: usize,
+        code: String,
+    },
+    /// A `"..."` text literal
+    Text {
+        offset: usize,
+        text: String,
+    },
+    /// A `// ...` line comment, `<!-- ... -->` or `<!DOCTYPE ...>`, verbatim
+    Comment {
+        offset: usize,
+        text: String,
+    },
+}
+
+/// An opening (or self-closing) tag
+#[derive(Debug, Clone)]
+pub struct Element {
+    pub name: String,
+    pub offset: usize,
+    pub attrs: Vec<Attr>,
+    pub self_closing: bool,
+}
+
+impl Element {
+    /// Components are written with an uppercase first letter
+    pub fn is_component(&self) -> bool {
+        self.name
+            .rsplit("::")
+            .next()
+            .is_some_and(|n| n.starts_with(|c: char| c.is_uppercase()))
+    }
+
+    pub fn attr(&self, name: &str) -> Option<&Attr> {
+        self.attrs.iter().find(|a| a.name == name)
+    }
+}
+
+/// An attribute such as `class="a"`, `on:click=move |_| ...` or `{..attrs}`
+#[derive(Debug, Clone)]
+pub struct Attr {
+    pub name: String,
+    /// Raw value source: a string literal with quotes, a `{...}` block or a bare expression
+    pub value: Option<String>,
+    pub offset: usize,
+}
+
+impl Attr {
+    /// Value without surrounding braces, for expression checks
+    pub fn expr(&self) -> Option<&str> {
+        let value = self.value.as_deref()?.trim();
+        Some(
+            value
+                .strip_prefix('{')
+                .and_then(|v| v.strip_suffix('}'))
+                .unwrap_or(value)
+                .trim(),
+        )
+    }
+
+    pub fn is_string_literal(&self) -> bool {
+        self.value.as_deref().is_some_and(|v| v.starts_with('"'))
+    }
+}
+
+/// Find and parse every `view!` invocation, including ones nested in blocks
+pub fn parse_views(code: &str) -> Vec<ViewMacro> {
+    let bytes = code.as_bytes();
+    let mut views = Vec::new();
+    for (start, _) in code.match_indices("view!") {
+        // Skip identifiers that merely end in `view!`, e.g. `preview!`
+        if start > 0 && is_ident_byte(bytes[start - 1]) {
+            continue;
+        }
+        let mut pos = start + "view!".len();
+        while pos < bytes.len() && bytes[pos].is_ascii_whitespace() {
+            pos += 1;
+        }
+        let Some(&open) = bytes.get(pos) else {
+            continue;
+        };
+        let close = match open {
+            b'{' => b'}',
+            b'(' => b')',
+            b'[' => b']',
+            _ => continue,
+        };
+        let Some(end) = skip_balanced(bytes, pos, open, close) else {
+            continue;
+        };
+        let body_start = pos + 1;
+        let body_end = end - 1;
+        views.push(ViewMacro {
+            start,
+            body_start,
+            body_end,
+            nodes: parse_body(code, body_start, body_end),
+        });
+    }
+    views
+}
+
+fn is_ident_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_'
+}
+
+/// Index just past the bracket matching the one at `pos`, skipping strings and chars
+pub fn skip_balanced(bytes: &[u8], pos: usize, open: u8, close: u8) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut i = pos;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' => i = skip_string(bytes, i),
+            b'\'' => i = skip_char(bytes, i),
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            b if b == open => {
+                depth += 1;
+                i += 1;
+            }
+            b if b == close => {
+                depth -= 1;
+                i += 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+/// Index just past a string literal whose opening quote is at `pos`
+pub fn skip_string(bytes: &[u8], pos: usize) -> usize {
+    let mut i = pos + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'"' => return i + 1,
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+/// Skip a char literal, or just the quote if it's a lifetime like `'static`
+pub fn skip_char(bytes: &[u8], pos: usize) -> usize {
+    if bytes.get(pos + 1) == Some(&b'\\') {
+        let mut i = pos + 2;
+        while i < bytes.len() && bytes[i] != b'\'' {
+            i += 1;
+        }
+        return i + 1;
+    }
+    // 'x' is a char; anything else ('a in 'static) is a lifetime
+    let rest = &bytes[pos + 1..];
+    let char_len = match rest.first() {
+        Some(b) if *b < 0x80 => 1,
+        Some(b) if *b >= 0xF0 => 4,
+        Some(b) if *b >= 0xE0 => 3,
+        Some(_) => 2,
+        None => return pos + 1,
+    };
+    if rest.get(char_len) == Some(&b'\'') {
+        pos + 1 + char_len + 1
+    } else {
+        pos + 1
+    }
+}
+
+fn parse_body(code: &str, start: usize, end: usize) -> Vec<Node> {
+    let bytes = &code.as_bytes()[..end];
+    let mut nodes = Vec::new();
+    let mut i = start;
+
+    while i < end {
+        match bytes[i] {
+            b'<' if code[i..].starts_with("<!--") => {
+                let next = code[i..end].find("-->").map_or(end, |p| i + p + 3);
+                nodes.push(Node::Comment {
+                    offset: i,
+                    text: code[i..next].to_string(),
+                });
+                i = next;
+            }
+            b'<' if code[i..].starts_with("<!") => {
+                let next = code[i..end].find('>').map_or(end, |p| i + p + 1);
+                nodes.push(Node::Comment {
+                    offset: i,
+                    text: code[i..next].to_string(),
+                });
+                i = next;
+            }
+            b'<' if bytes.get(i + 1) == Some(&b'/') => {
+                let name_start = i + 2;
+                let name_end = scan_tag_name(bytes, name_start);
+                nodes.push(Node::Close {
+                    name: code[name_start..name_end].to_string(),
+                    offset: i,
+                });
+                i = code[name_end..end]
+                    .find('>')
+                    .map_or(end, |p| name_end + p + 1);
+            }
+            b'<' => {
+                let (element, next) = parse_element(code, i, end);
+                nodes.push(Node::Open(element));
+                i = next;
+            }
+            b'{' => {
+                let next = skip_balanced(bytes, i, b'{', b'}').unwrap_or(end);
+                nodes.push(Node::Block {
+                    offset: i,
+                    code: code[i + 1..next.saturating_sub(1).max(i + 1)].to_string(),
+                });
+                i = next;
+            }
+            b'"' => {
+                let next = skip_string(bytes, i);
+                nodes.push(Node::Text {
+                    offset: i,
+                    text: code[i + 1..next.saturating_sub(1).max(i + 1)].to_string(),
+                });
+                i = next;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                let next = code[i..end].find('\n').map_or(end, |p| i + p);
+                nodes.push(Node::Comment {
+                    offset: i,
+                    text: code[i..next].trim_end().to_string(),
+                });
+                i = next;
+            }
+            _ => i += 1,
+        }
+    }
+    nodes
+}
+
+fn scan_tag_name(bytes: &[u8], start: usize) -> usize {
+    let mut i = start;
+    while i < bytes.len() && (is_ident_byte(bytes[i]) || matches!(bytes[i], b'-' | b':' | b'.')) {
+        i += 1;
+    }
+    i
+}
+
+/// Parse an opening tag at `start` (pointing at `<`); returns the element and the index after `>`
+fn parse_element(code: &str, start: usize, end: usize) -> (Element, usize) {
+    let bytes = code.as_bytes();
+    let name_start = start + 1;
+    let name_end = scan_tag_name(bytes, name_start);
+    let mut element = Element {
+        name: code[name_start..name_end].to_string(),
+        offset: start,
+        attrs: Vec::new(),
+        self_closing: false,
+    };
+
+    let mut i = name_end;
+    while i < end {
+        let b = bytes[i];
+        if b.is_ascii_whitespace() {
+            i += 1;
+        } else if b == b'>' {
+            return (element, i + 1);
+        } else if b == b'/' && bytes.get(i + 1) == Some(&b'>') {
+            element.self_closing = true;
+            return (element, i + 2);
+        } else if b == b'{' {
+            // Spread (`{..attrs}`) or braced shorthand attribute
+            let next = skip_balanced(bytes, i, b'{', b'}').unwrap_or(end);
+            element.attrs.push(Attr {
+                name: code[i..next].to_string(),
+                value: None,
+                offset: i,
+            });
+            i = next;
+        } else if is_ident_byte(b) {
+            let attr_start = i;
+            let attr_end = scan_tag_name(bytes, i);
+            let mut attr = Attr {
+                name: code[attr_start..attr_end].to_string(),
+                value: None,
+                offset: attr_start,
+            };
+            i = attr_end;
+            while i < end && bytes[i].is_ascii_whitespace() {
+                i += 1;
+            }
+            if i < end && bytes[i] == b'=' {
+                i += 1;
+                while i < end && bytes[i].is_ascii_whitespace() {
+                    i += 1;
+                }
+                let value_end = scan_attr_value(code, i, end);
+                attr.value = Some(code[i..value_end].trim_end().to_string());
+                i = value_end;
+            }
+            element.attrs.push(attr);
+        } else {
+            i += 1;
+        }
+    }
+    (element, end)
+}
+
+/// End of an attribute value: a literal, a block, or an expression that runs
+/// until the tag ends or the next attribute starts
+fn scan_attr_value(code: &str, start: usize, end: usize) -> usize {
+    let bytes = code.as_bytes();
+    match bytes.get(start) {
+        Some(b'"') => return skip_string(bytes, start).min(end),
+        Some(b'{') => return skip_balanced(bytes, start, b'{', b'}').unwrap_or(end),
+        _ => {}
+    }
+
+    let mut i = start;
+    while i < end {
+        match bytes[i] {
+            b'"' => i = skip_string(bytes, i),
+            b'\'' => i = skip_char(bytes, i),
+            b'(' => i = skip_balanced(bytes, i, b'(', b')').unwrap_or(end),
+            b'[' => i = skip_balanced(bytes, i, b'[', b']').unwrap_or(end),
+            b'{' => i = skip_balanced(bytes, i, b'{', b'}').unwrap_or(end),
+            b'>' if is_comparison(bytes, i, end) => i += 1,
+            b'>' => return i,
+            b'/' if bytes.get(i + 1) == Some(&b'>') => return i,
+            b if b.is_ascii_whitespace() => {
+                let mut j = i;
+                while j < end && bytes[j].is_ascii_whitespace() {
+                    j += 1;
+                }
+                if j >= end || starts_attribute(code, j, end) {
+                    return i;
+                }
+                i = j;
+            }
+            _ => i += 1,
+        }
+    }
+    end
+}
+
+/// Whether the `>` at `pos` inside an unbraced attribute value is an operator
+/// (`a > 10`, `a >= b`, `-> T`) rather than the end of the tag
+///
+/// Children can't start with a bare identifier or number (text is quoted), so
+/// one following the `>` means the expression continues.
+fn is_comparison(bytes: &[u8], pos: usize, end: usize) -> bool {
+    if pos > 0 && bytes[pos - 1] == b'-' {
+        return true;
+    }
+    let mut j = pos + 1;
+    if bytes.get(j) == Some(&b'=') {
+        return true;
+    }
+    while j < end && matches!(bytes[j], b' ' | b'\t') {
+        j += 1;
+    }
+    j < end && (is_ident_byte(bytes[j]) || bytes[j] == b'(')
+}
+
+/// Whether the text at `pos` looks like the start of another attribute or the end of the tag
+fn starts_attribute(code: &str, pos: usize, end: usize) -> bool {
+    let bytes = code.as_bytes();
+    if bytes[pos] == b'>' {
+        return !is_comparison(bytes, pos, end);
+    }
+    if code[pos..end].starts_with("/>") {
+        return true;
+    }
+    if code[pos..end].starts_with("{..") {
+        return true;
+    }
+    if !bytes[pos].is_ascii_alphabetic() {
+        return false;
+    }
+    let name_end = scan_tag_name(bytes, pos);
+    let mut j = name_end;
+    while j < end && bytes[j].is_ascii_whitespace() {
+        j += 1;
+    }
+    match bytes.get(j) {
+        // `name=` but not `a == b`
+        Some(b'=') => bytes.get(j + 1) != Some(&b'='),
+        // Valueless attribute right before the end of the tag, e.g. `disabled>`
+        Some(b'>') | Some(b'/') => true,
+        _ => code[pos..name_end].contains(':'),
+    }
+}
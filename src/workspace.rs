@@ -0,0 +1,316 @@
+//! Cargo workspaces
+//!
+//! Larger Leptos apps split into crates: a `frontend` built to WASM with
+//! `hydrate`, a `server` binary built with `ssr`, and a `shared` crate of
+//! models and server functions that both depend on. Which of them a file
+//! belongs to decides which rules apply: a crate that never runs in the
+//! browser can't have hydration mismatches, and `tokio` is fine in the server
+//! but breaks the WASM build of every crate the frontend depends on.
+//!
+//! A crate's [`Target`] comes from its own manifest where that is explicit
+//! (`leptos` with `ssr` or `hydrate` always on, a server integration
+//! dependency, or both features forwarded like a single-crate cargo-leptos
+//! app), else from the members that depend on it through `path`.
+
+use crate::context::{leptos_features, read_toml, Manifest};
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use toml::Value;
+
+/// Dependencies only the server build has
+const SERVER_CRATES: &[&str] = &["leptos_axum", "leptos_actix", "axum", "actix-web"];
+
+/// What a workspace crate is compiled for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Target {
+    /// Only the server binary, with `ssr`
+    Server,
+    /// Only the WASM client, with `hydrate` or `csr`
+    Client,
+    /// Both: models and server functions the two sides share
+    Shared,
+}
+
+impl Target {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Server => "server",
+            Self::Client => "client",
+            Self::Shared => "shared",
+        }
+    }
+
+    /// Whether the WASM client compiles the crate
+    pub fn is_client(&self) -> bool {
+        matches!(self, Self::Client | Self::Shared)
+    }
+
+    fn join(self, other: Self) -> Self {
+        if self == other {
+            self
+        } else {
+            Self::Shared
+        }
+    }
+}
+
+/// A crate of the workspace
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct Member {
+    /// `[package] name`
+    pub name: String,
+    /// Directory relative to the workspace root; empty for the root package
+    pub path: PathBuf,
+    /// `None` for a crate neither build reaches, e.g. a standalone tool
+    pub target: Option<Target>,
+}
+
+/// The workspace an analyzed directory is, or is part of
+#[derive(Debug, Clone)]
+pub struct Workspace {
+    /// Directory of the workspace's `Cargo.toml`
+    pub root: PathBuf,
+    /// The analyzed directory, which file paths are relative to
+    base: PathBuf,
+    pub members: Vec<Member>,
+}
+
+impl Workspace {
+    /// The workspace `dir` is the root or a member of, if it has more than
+    /// one crate. A manifest that can't be read leaves it out, so the
+    /// project is analyzed as a single crate.
+    pub fn load(dir: &Path) -> Option<Self> {
+        let base = dir.canonicalize().ok()?;
+        let (root, manifest) = base.ancestors().find_map(|dir| {
+            let manifest = read_toml(&dir.join("Cargo.toml")).ok()?;
+            manifest
+                .get("workspace")
+                .is_some()
+                .then(|| (dir.to_path_buf(), manifest))
+        })?;
+        let dirs = member_dirs(&root, &manifest);
+        if dirs.len() < 2 {
+            return None;
+        }
+
+        let manifests: Vec<Manifest> = dirs
+            .iter()
+            .map(|dir| Manifest::load(&root.join(dir)))
+            .collect::<Result<_, _>>()
+            .ok()?;
+        let dependencies: Vec<Vec<PathBuf>> = dirs
+            .iter()
+            .zip(&manifests)
+            .map(|(dir, manifest)| path_dependencies(dir, manifest))
+            .collect();
+        let direct: Vec<Option<Target>> = manifests.iter().map(direct_target).collect();
+        // A crate without a target of its own is built for whatever builds
+        // the members depending on it; targets only widen, so this settles
+        let mut targets = direct.clone();
+        loop {
+            let mut changed = false;
+            for (i, dir) in dirs
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| direct[*i].is_none())
+            {
+                let reached = (0..dirs.len())
+                    .filter(|&j| dependencies[j].contains(dir))
+                    .filter_map(|j| targets[j])
+                    .reduce(Target::join);
+                if reached != targets[i] {
+                    targets[i] = reached;
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        let members = dirs
+            .into_iter()
+            .zip(&manifests)
+            .zip(targets)
+            .map(|((path, manifest), target)| Member {
+                name: manifest
+                    .own
+                    .get("package")
+                    .and_then(|p| p.get("name"))
+                    .and_then(Value::as_str)
+                    .map(String::from)
+                    .unwrap_or_else(|| path.display().to_string()),
+                path,
+                target,
+            })
+            .collect();
+        Some(Self {
+            root,
+            base,
+            members,
+        })
+    }
+
+    /// The crate a file belongs to, by its path relative to the analyzed directory
+    pub fn member_of(&self, path: &Path) -> Option<&Member> {
+        let file = self.base.join(path);
+        self.members
+            .iter()
+            .filter(|m| file.starts_with(self.root.join(&m.path)))
+            .max_by_key(|m| m.path.components().count())
+    }
+}
+
+/// Whether `dir` is a workspace root listing members, whose sources live
+/// outside its own `src`
+pub(crate) fn has_members(dir: &Path) -> bool {
+    read_toml(&dir.join("Cargo.toml")).is_ok_and(|manifest| {
+        manifest
+            .get("workspace")
+            .and_then(|w| w.get("members"))
+            .and_then(Value::as_array)
+            .is_some_and(|members| !members.is_empty())
+    })
+}
+
+/// Member directories relative to `root`: the `members` globs minus
+/// `exclude`, plus the root package
+fn member_dirs(root: &Path, manifest: &Value) -> Vec<PathBuf> {
+    let workspace = manifest.get("workspace");
+    let patterns = |key: &str| -> Vec<&str> {
+        workspace
+            .and_then(|w| w.get(key))
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .collect()
+    };
+    let excluded: Vec<PathBuf> = patterns("exclude").into_iter().map(normalize).collect();
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    if manifest.get("package").is_some() {
+        dirs.push(PathBuf::new());
+    }
+    for pattern in patterns("members") {
+        for dir in expand(root, pattern) {
+            if root.join(&dir).join("Cargo.toml").is_file()
+                && !excluded.contains(&dir)
+                && !dirs.contains(&dir)
+            {
+                dirs.push(dir);
+            }
+        }
+    }
+    dirs
+}
+
+/// Directories matching a `members` entry, with `*` wildcards in any segment
+fn expand(root: &Path, pattern: &str) -> Vec<PathBuf> {
+    let mut dirs = vec![PathBuf::new()];
+    for segment in pattern.split('/').filter(|s| !s.is_empty() && *s != ".") {
+        if !segment.contains('*') {
+            dirs.iter_mut().for_each(|dir| dir.push(segment));
+            continue;
+        }
+        dirs = dirs
+            .into_iter()
+            .flat_map(|dir| {
+                let mut names: Vec<String> = fs::read_dir(root.join(&dir))
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|entry| entry.ok())
+                    .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+                    .filter_map(|entry| entry.file_name().into_string().ok())
+                    .filter(|name| wildcard(segment, name))
+                    .collect();
+                names.sort();
+                names.into_iter().map(move |name| dir.join(name))
+            })
+            .collect();
+    }
+    dirs.into_iter().map(normalize).collect()
+}
+
+/// Whether `name` matches `pattern`, where `*` stands for any run of characters
+fn wildcard(pattern: &str, name: &str) -> bool {
+    let parts: Vec<&str> = pattern.split('*').collect();
+    let (first, last) = (parts[0], parts[parts.len() - 1]);
+    if name.len() < first.len() + last.len() || !name.starts_with(first) || !name.ends_with(last) {
+        return false;
+    }
+    let mut rest = &name[first.len()..name.len() - last.len()];
+    for part in &parts[1..parts.len() - 1] {
+        match rest.find(part) {
+            Some(at) => rest = &rest[at + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+/// `a/./b/../c` as `a/c`, without touching the file system
+fn normalize(path: impl AsRef<Path>) -> PathBuf {
+    let mut normal = PathBuf::new();
+    for component in path.as_ref().components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normal.pop();
+            }
+            other => normal.push(other),
+        }
+    }
+    normal
+}
+
+/// Directories, relative to the workspace root, that the member in `dir`
+/// depends on with `path`, directly or through `workspace = true`
+fn path_dependencies(dir: &Path, manifest: &Manifest) -> Vec<PathBuf> {
+    manifest
+        .own
+        .get("dependencies")
+        .and_then(Value::as_table)
+        .into_iter()
+        .flatten()
+        .filter_map(|(name, entry)| {
+            if let Some(path) = entry.get("path").and_then(Value::as_str) {
+                return Some(normalize(dir.join(path)));
+            }
+            let inherited = entry.get("workspace").and_then(Value::as_bool) == Some(true);
+            let path = manifest
+                .workspace_dependency(name)
+                .filter(|_| inherited)?
+                .get("path")?
+                .as_str()?;
+            Some(normalize(path))
+        })
+        .collect()
+}
+
+/// The target a member's own manifest decides, if it does
+fn direct_target(manifest: &Manifest) -> Option<Target> {
+    let features = leptos_features(manifest);
+    let feature = |name: &str| features.iter().find(|f| f.name == name);
+    let always = |name: &str| feature(name).is_some_and(|f| f.always);
+    let forwarded = |name: &str| feature(name).is_some_and(|f| !f.crate_features.is_empty());
+    let dependencies = manifest.own.get("dependencies");
+    let required = |name: &str| {
+        dependencies
+            .and_then(|deps| deps.get(name))
+            .is_some_and(|entry| entry.get("optional").and_then(Value::as_bool) != Some(true))
+    };
+
+    let server = always("ssr") || SERVER_CRATES.iter().any(|name| required(name));
+    let client = always("hydrate") || always("csr");
+    match (server, client) {
+        (true, true) => Some(Target::Shared),
+        (true, false) => Some(Target::Server),
+        (false, true) => Some(Target::Client),
+        // One crate built twice, once per feature
+        _ if forwarded("ssr") && (forwarded("hydrate") || forwarded("csr")) => Some(Target::Shared),
+        _ => None,
+    }
+}